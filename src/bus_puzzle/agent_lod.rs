@@ -0,0 +1,78 @@
+// src/bus_puzzle/agent_lod.rs - 乘客细节层级（屏幕外或远景缩放时降低更新频率，支撑上千名乘客的压力测试关卡）
+
+use crate::bus_puzzle::{simulation_running, CameraController, LevelManager, PathfindingAgent};
+use bevy::{prelude::*, window::PrimaryWindow};
+
+/// 镜头缩小到超过此缩放时，所有乘客都按远景处理
+const FAR_ZOOM_THRESHOLD: f32 = 1.8;
+
+/// 远景乘客每累计这么多模拟秒才更新一次
+const REDUCED_TICK_SECS: f32 = 0.25;
+
+/// 重新划分细节层级的间隔（真实时间）
+const LOD_REFRESH_SECS: f32 = 0.2;
+
+/// 视野外多留的格子数，避免乘客在屏幕边缘频繁切换层级
+const VIEW_MARGIN_TILES: f32 = 1.0;
+
+/// 乘客的细节层级。远景乘客把每帧的模拟时间攒起来，按较低频率一次性结算
+#[derive(Component, Default)]
+pub struct AgentLod {
+    pub reduced: bool,
+    pending_secs: f32,
+}
+
+impl AgentLod {
+    /// 本帧该乘客要结算的模拟秒数；远景乘客还没攒够时返回 None
+    pub fn tick(&mut self, dt: f32) -> Option<f32> {
+        self.pending_secs += dt;
+        if self.reduced && self.pending_secs < REDUCED_TICK_SECS {
+            return None;
+        }
+        Some(std::mem::take(&mut self.pending_secs))
+    }
+}
+
+// ============ 乘客细节层级插件 ============
+
+pub struct AgentLodPlugin;
+
+impl Plugin for AgentLodPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_agent_lod.run_if(simulation_running));
+    }
+}
+
+/// 按镜头视野和缩放划分乘客的细节层级
+fn update_agent_lod(
+    time: Res<Time>,
+    mut since_refresh: Local<f32>,
+    camera_controller: Res<CameraController>,
+    camera_query: Query<&Transform, With<Camera>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    level_manager: Res<LevelManager>,
+    mut passengers: Query<(&mut AgentLod, &Transform), With<PathfindingAgent>>,
+) -> Result {
+    *since_refresh += time.delta_secs();
+    if *since_refresh < LOD_REFRESH_SECS {
+        return Ok(());
+    }
+    *since_refresh = 0.0;
+
+    let camera_transform = camera_query.single()?;
+    let window_size = windows.single()?.size();
+    let far_zoom = camera_controller.zoom > FAR_ZOOM_THRESHOLD;
+    let view = Rect::from_center_size(
+        camera_transform.translation.truncate(),
+        window_size * camera_controller.zoom,
+    )
+    .inflate(VIEW_MARGIN_TILES * level_manager.tile_size);
+
+    for (mut lod, transform) in passengers.iter_mut() {
+        let reduced = far_zoom || !view.contains(transform.translation.truncate());
+        if lod.reduced != reduced {
+            lod.reduced = reduced;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,940 @@
+// src/bus_puzzle/autosave.rs - 关卡进行中的自动存档（崩溃或中途退回主菜单后，从主菜单“继续”恢复到离开时的状态）
+
+use crate::bus_puzzle::{
+    line_number, push_step_events, smart_bus_generation::bus_bundle, spawn_passenger_entity,
+    spawn_route_segment, ActiveChallenge, AgentState, BusDirection, BusLoop, BusPathfindingAgent,
+    BusState, BusVehicle, ButtonComponent, ButtonType, ContractBoard, CustomLevel, EconomyMode,
+    EventDeck, ExpressSegment, Fleet, FlushSavesEvent, GameState, GameStateEnum, GridPos,
+    LevelData, LevelManager, MirrorMode, NetworkLayer, OnBus, PassengerColor, PassengerStats,
+    PathfindingAgent, PlacedSegment, RouteLines, RouteSchedules, RouteSegmentType,
+    ScenarioProgress, SimClock, StationEntity, StationUpgrades, ThemeRegistry, DEFAULT_LINE,
+    PASSENGER_Z,
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+const AUTOSAVE_FILE: &str = "autosave.json";
+
+/// 关卡进行中每隔多少秒自动存档一次
+const AUTOSAVE_INTERVAL: f32 = 30.0;
+
+// ============ 自动存档数据 ============
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AutosaveData {
+    pub level_id: String,
+    pub mirrored: bool,
+    pub game_time: f32,
+    pub total_cost: u32,
+    pub inventory: HashMap<RouteSegmentType, u32>,
+    pub segments: Vec<(GridPos, RouteSegmentType, u32, u32, bool)>, // 位置、类型、旋转、花费、快速路线
+    pub elevated_segments: Vec<(GridPos, RouteSegmentType, u32, u32)>, // 高架层：位置、类型、旋转、花费
+    pub spawned_counts: Vec<u32>,                                      // 按需求顺序的已生成乘客数
+    pub line_assignments: Vec<(GridPos, u32)>,                         // 地面路线段所属的线路
+    pub passenger_stats: PassengerStatsSnapshot,
+    pub passengers: Vec<PassengerSnapshot>,
+    pub buses: Vec<BusSnapshot>,
+    pub fleet: Fleet,               // 手动车队模式：车场停放和各线路分到的车辆
+    pub scenario: ScenarioProgress, // 剧情时间线已触发的步骤
+    pub economy: EconomyMode,       // 经营模式：开关和本关收支（支出中的 total_cost 单独保存）
+    pub contracts: ContractBoard,   // 限时合约：开关、进行中的合约和加分
+    pub schedules: RouteSchedules,  // 各线路的车辆数和发车频率
+    pub station_upgrades: Vec<(GridPos, StationUpgrades)>, // 已升级的站点
+    pub event_seed: Option<u64>,    // 随机事件牌堆的种子，恢复时抽出相同的天气和封路
+}
+
+/// 乘客统计（生成、到达、放弃人数和累计等待时间）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassengerStatsSnapshot {
+    pub total_spawned: u32,
+    pub total_arrived: u32,
+    pub total_gave_up: u32,
+    pub arrived_by_color: Vec<(PassengerColor, u32)>,
+    pub total_wait_time: f32,
+}
+
+impl PassengerStatsSnapshot {
+    fn capture(stats: &PassengerStats) -> Self {
+        let mut arrived_by_color: Vec<_> = stats
+            .arrived_by_color
+            .iter()
+            .map(|(color, count)| (*color, *count))
+            .collect();
+        arrived_by_color.sort_by_key(|(color, _)| format!("{:?}", color));
+
+        Self {
+            total_spawned: stats.total_spawned,
+            total_arrived: stats.total_arrived,
+            total_gave_up: stats.total_gave_up,
+            arrived_by_color,
+            total_wait_time: stats.total_wait_time,
+        }
+    }
+
+    fn restore(&self) -> PassengerStats {
+        PassengerStats {
+            total_spawned: self.total_spawned,
+            total_arrived: self.total_arrived,
+            total_gave_up: self.total_gave_up,
+            arrived_by_color: self.arrived_by_color.iter().copied().collect(),
+            total_wait_time: self.total_wait_time,
+        }
+    }
+}
+
+/// 仍在路上的乘客：在站台等车，或坐在某辆公交车上
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassengerSnapshot {
+    pub color: PassengerColor,
+    pub origin: String,
+    pub destination: String,
+    pub via: Option<String>,
+    pub state: AgentState,
+    pub patience: f32,
+    pub max_patience: f32,
+    pub waiting_time: f32,
+    pub lines_ridden: Vec<String>,
+    pub group_size: u32,
+    pub riding: Option<RidingSnapshot>,
+}
+
+/// 乘客所在的公交车（按车辆编号对应）和下车站
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RidingSnapshot {
+    pub vehicle_id: String,
+    pub target_station: String,
+    pub ride_secs: f32,
+}
+
+/// 公交车的位置、行驶状态、当前路径和行驶圈
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusSnapshot {
+    pub position: [f32; 3],
+    pub vehicle_id: String,
+    pub route_id: String,
+    pub capacity: u32,
+    pub current_stop_index: usize,
+    pub direction: BusDirection,
+    pub state: BusState,
+    pub speed: f32,
+    pub dwell_time: f32,
+    pub remaining_dwell: f32,
+    pub target_position: Option<[f32; 3]>,
+    pub agent: BusPathfindingAgent,
+    pub bus_loop: Option<BusLoop>,
+}
+
+impl BusSnapshot {
+    fn capture(
+        bus_vehicle: &BusVehicle,
+        agent: &BusPathfindingAgent,
+        bus_loop: Option<&BusLoop>,
+        transform: &Transform,
+    ) -> Self {
+        Self {
+            position: transform.translation.to_array(),
+            vehicle_id: bus_vehicle.vehicle_id.clone(),
+            route_id: bus_vehicle.route_id.clone(),
+            capacity: bus_vehicle.capacity,
+            current_stop_index: bus_vehicle.current_stop_index,
+            direction: bus_vehicle.direction.clone(),
+            state: bus_vehicle.state.clone(),
+            speed: bus_vehicle.speed,
+            dwell_time: bus_vehicle.dwell_time,
+            remaining_dwell: bus_vehicle.remaining_dwell,
+            target_position: bus_vehicle.target_position.map(|pos| pos.to_array()),
+            agent: agent.clone(),
+            bus_loop: bus_loop.cloned(),
+        }
+    }
+
+    /// 乘客列表在恢复乘客时重新填入
+    fn bus_vehicle(&self) -> BusVehicle {
+        BusVehicle {
+            vehicle_id: self.vehicle_id.clone(),
+            route_id: self.route_id.clone(),
+            capacity: self.capacity,
+            current_passengers: Vec::new(),
+            current_stop_index: self.current_stop_index,
+            direction: self.direction.clone(),
+            state: self.state.clone(),
+            speed: self.speed,
+            dwell_time: self.dwell_time,
+            remaining_dwell: self.remaining_dwell,
+            target_position: self.target_position.map(Vec3::from_array),
+        }
+    }
+}
+
+/// 存档时读取的乘客、公交车和站点
+pub type SavedPassengers<'w, 's> =
+    Query<'w, 's, (&'static PathfindingAgent, Option<&'static OnBus>)>;
+pub type SavedBuses<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static BusVehicle,
+        &'static BusPathfindingAgent,
+        Option<&'static BusLoop>,
+        &'static Transform,
+    ),
+>;
+pub type SavedStations<'w, 's> = Query<'w, 's, &'static StationEntity>;
+
+/// 存档时读取的模式资源：经营模式、限时合约、线路调度和随机事件牌堆
+pub type SavedModes<'w> = (
+    Res<'w, EconomyMode>,
+    Res<'w, ContractBoard>,
+    Res<'w, RouteSchedules>,
+    Res<'w, EventDeck>,
+);
+
+impl AutosaveData {
+    pub fn capture(
+        game_state: &GameState,
+        mirrored: bool,
+        route_lines: &RouteLines,
+        fleet: &Fleet,
+        scenario: &ScenarioProgress,
+        (economy, contracts, schedules, deck): &SavedModes,
+        passengers: &SavedPassengers,
+        buses: &SavedBuses,
+        stations: &SavedStations,
+        sim_clock: &SimClock,
+    ) -> Option<Self> {
+        let level_data = game_state.current_level.as_ref()?;
+
+        let mut segments: Vec<_> = game_state
+            .placed_segments
+            .iter()
+            .map(|(pos, placed)| {
+                (
+                    *pos,
+                    placed.segment_type,
+                    placed.rotation,
+                    placed.cost,
+                    placed.express,
+                )
+            })
+            .collect();
+        segments.sort_by_key(|(pos, ..)| (pos.x, pos.y));
+
+        let mut elevated_segments: Vec<_> = game_state
+            .elevated_segments
+            .iter()
+            .map(|(pos, placed)| (*pos, placed.segment_type, placed.rotation, placed.cost))
+            .collect();
+        elevated_segments.sort_by_key(|(pos, ..)| (pos.x, pos.y));
+
+        let mut line_assignments: Vec<_> = game_state
+            .placed_segments
+            .keys()
+            .map(|pos| (*pos, route_lines.line_of(*pos)))
+            .collect();
+        line_assignments.sort_by_key(|(pos, _)| (pos.x, pos.y));
+
+        // 已到达或放弃的乘客本帧就会被清理，不再保存
+        let passengers = passengers
+            .iter()
+            .filter(|(agent, _)| !matches!(agent.state, AgentState::Arrived | AgentState::GaveUp))
+            .map(|(agent, on_bus)| PassengerSnapshot {
+                color: agent.color,
+                origin: agent.origin.clone(),
+                destination: agent.destination.clone(),
+                via: agent.via.clone(),
+                state: agent.state.clone(),
+                patience: agent.patience,
+                max_patience: agent.max_patience,
+                waiting_time: agent.waiting_time,
+                lines_ridden: agent.lines_ridden.clone(),
+                group_size: agent.group_size,
+                riding: on_bus.and_then(|on_bus| {
+                    let (bus_vehicle, ..) = buses.get(on_bus.bus_entity).ok()?;
+                    Some(RidingSnapshot {
+                        vehicle_id: bus_vehicle.vehicle_id.clone(),
+                        target_station: on_bus.target_station.clone(),
+                        ride_secs: sim_clock.elapsed_secs() - on_bus.boarding_time,
+                    })
+                }),
+            })
+            .collect();
+
+        let buses = buses
+            .iter()
+            .map(|(bus_vehicle, agent, bus_loop, transform)| {
+                BusSnapshot::capture(bus_vehicle, agent, bus_loop, transform)
+            })
+            .collect();
+
+        let mut station_upgrades: Vec<_> = stations
+            .iter()
+            .filter(|station| station.upgrades != StationUpgrades::default())
+            .map(|station| (station.station_data.position, station.upgrades))
+            .collect();
+        station_upgrades.sort_by_key(|(pos, _)| (pos.x, pos.y));
+
+        Some(Self {
+            level_id: level_data.id.clone(),
+            mirrored,
+            game_time: game_state.game_time,
+            total_cost: game_state.total_cost,
+            inventory: game_state.player_inventory.clone(),
+            segments,
+            elevated_segments,
+            spawned_counts: level_data
+                .passenger_demands
+                .iter()
+                .map(|demand| demand.spawned_count)
+                .collect(),
+            line_assignments,
+            passenger_stats: PassengerStatsSnapshot::capture(&game_state.passenger_stats),
+            passengers,
+            buses,
+            fleet: fleet.clone(),
+            scenario: scenario.clone(),
+            economy: (*economy).clone(),
+            contracts: (*contracts).clone(),
+            schedules: (*schedules).clone(),
+            station_upgrades,
+            event_seed: level_data.random_events.then_some(deck.seed),
+        })
+    }
+
+    /// 整份存档写成一个 JSON 文档
+    pub fn serialize(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|e| {
+            warn!("自动存档无法序列化: {}", e);
+            String::new()
+        })
+    }
+
+    pub fn deserialize(content: &str) -> Option<Self> {
+        let data: Self = serde_json::from_str(content)
+            .map_err(|e| warn!("自动存档无法解析: {}", e))
+            .ok()?;
+        (!data.level_id.is_empty()).then_some(data)
+    }
+}
+
+pub fn parse_segment_type(name: &str) -> Option<RouteSegmentType> {
+    match name {
+        "Straight" => Some(RouteSegmentType::Straight),
+        "Curve" => Some(RouteSegmentType::Curve),
+        "TSplit" => Some(RouteSegmentType::TSplit),
+        "Cross" => Some(RouteSegmentType::Cross),
+        "Bridge" => Some(RouteSegmentType::Bridge),
+        "Tunnel" => Some(RouteSegmentType::Tunnel),
+        _ => None,
+    }
+}
+
+/// 启动时读取、等待下次加载关卡时恢复的存档
+#[derive(Resource, Default)]
+pub struct PendingAutosave(pub Option<AutosaveData>);
+
+#[derive(Resource)]
+struct AutosaveTimer(Timer);
+
+impl Default for AutosaveTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(AUTOSAVE_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+// ============ 自动存档插件 ============
+
+pub struct AutosavePlugin;
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingAutosave>()
+            .init_resource::<AutosaveTimer>()
+            .add_systems(Startup, load_autosave)
+            .add_systems(
+                Update,
+                periodic_autosave.run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameStateEnum::Paused,
+                    entered: GameStateEnum::MainMenu,
+                },
+                save_on_quit_to_menu,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameStateEnum::Playing,
+                    entered: GameStateEnum::MainMenu,
+                },
+                save_on_quit_to_menu,
+            )
+            .add_systems(
+                Update,
+                handle_resume_buttons.run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(OnEnter(GameStateEnum::LevelComplete), clear_autosave)
+            .add_systems(OnEnter(GameStateEnum::GameOver), clear_autosave)
+            .add_systems(PostUpdate, flush_autosave); // 退出前写入自动存档
+    }
+}
+
+// ============ 存档读写 ============
+
+fn load_autosave(mut pending: ResMut<PendingAutosave>) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Ok(content) = std::fs::read_to_string(AUTOSAVE_FILE) {
+            pending.0 = AutosaveData::deserialize(&content);
+            if pending.0.is_none() {
+                warn!("自动存档格式错误，已忽略");
+            }
+        }
+    }
+
+    if let Some(data) = &pending.0 {
+        info!(
+            "💾 发现自动存档: {} ({} 个路线段, {:.0}s)",
+            data.level_id,
+            data.segments.len(),
+            data.game_time
+        );
+    }
+}
+
+fn write_autosave(data: &AutosaveData) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(AUTOSAVE_FILE, data.serialize()) {
+            warn!("写入自动存档失败: {}", e);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        let _ = data.serialize();
+        info!("WASM环境：自动存档已更新到内存");
+    }
+}
+
+fn save_current_level(
+    game_state: &GameState,
+    mirror_mode: &MirrorMode,
+    route_lines: &RouteLines,
+    fleet: &Fleet,
+    scenario: &ScenarioProgress,
+    modes: &SavedModes,
+    passengers: &SavedPassengers,
+    buses: &SavedBuses,
+    stations: &SavedStations,
+    sim_clock: &SimClock,
+) -> Option<AutosaveData> {
+    let data = AutosaveData::capture(
+        game_state,
+        mirror_mode.enabled,
+        route_lines,
+        fleet,
+        scenario,
+        modes,
+        passengers,
+        buses,
+        stations,
+        sim_clock,
+    )?;
+    write_autosave(&data);
+    Some(data)
+}
+
+fn periodic_autosave(
+    time: Res<Time>,
+    mut timer: ResMut<AutosaveTimer>,
+    (game_state, mirror_mode, route_lines, fleet, scenario): (
+        Res<GameState>,
+        Res<MirrorMode>,
+        Res<RouteLines>,
+        Res<Fleet>,
+        Res<ScenarioProgress>,
+    ),
+    modes: SavedModes,
+    passengers: SavedPassengers,
+    buses: SavedBuses,
+    stations: SavedStations,
+    sim_clock: Res<SimClock>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        save_current_level(
+            &game_state,
+            &mirror_mode,
+            &route_lines,
+            &fleet,
+            &scenario,
+            &modes,
+            &passengers,
+            &buses,
+            &stations,
+            &sim_clock,
+        );
+    }
+}
+
+/// 关卡中途退回主菜单时保存完整进度，主菜单上显示“继续”按钮
+fn save_on_quit_to_menu(
+    mut pending: ResMut<PendingAutosave>,
+    (game_state, mirror_mode, route_lines, fleet, scenario): (
+        Res<GameState>,
+        Res<MirrorMode>,
+        Res<RouteLines>,
+        Res<Fleet>,
+        Res<ScenarioProgress>,
+    ),
+    modes: SavedModes,
+    passengers: SavedPassengers,
+    buses: SavedBuses,
+    stations: SavedStations,
+    sim_clock: Res<SimClock>,
+) {
+    pending.0 = save_current_level(
+        &game_state,
+        &mirror_mode,
+        &route_lines,
+        &fleet,
+        &scenario,
+        &modes,
+        &passengers,
+        &buses,
+        &stations,
+        &sim_clock,
+    );
+    if let Some(data) = &pending.0 {
+        info!(
+            "💾 已保存中途进度: {} ({} 名乘客, {} 辆公交车)",
+            data.level_id,
+            data.passengers.len(),
+            data.buses.len()
+        );
+    }
+}
+
+fn flush_autosave(
+    mut flush_events: EventReader<FlushSavesEvent>,
+    current_state: Res<State<GameStateEnum>>,
+    (game_state, mirror_mode, route_lines, fleet, scenario): (
+        Res<GameState>,
+        Res<MirrorMode>,
+        Res<RouteLines>,
+        Res<Fleet>,
+        Res<ScenarioProgress>,
+    ),
+    modes: SavedModes,
+    passengers: SavedPassengers,
+    buses: SavedBuses,
+    stations: SavedStations,
+    sim_clock: Res<SimClock>,
+) {
+    if flush_events.read().last().is_none() {
+        return;
+    }
+
+    // 只在关卡进行中保存，关卡结束后存档已被清除
+    if matches!(
+        current_state.get(),
+        GameStateEnum::Playing | GameStateEnum::Paused
+    ) {
+        save_current_level(
+            &game_state,
+            &mirror_mode,
+            &route_lines,
+            &fleet,
+            &scenario,
+            &modes,
+            &passengers,
+            &buses,
+            &stations,
+            &sim_clock,
+        );
+        info!("💾 已保存关卡进度");
+    }
+}
+
+fn clear_autosave(mut pending: ResMut<PendingAutosave>) {
+    discard_autosave(&mut pending);
+}
+
+/// 丢弃存档（关卡结束或从主菜单重新开始时）
+fn discard_autosave(pending: &mut PendingAutosave) {
+    pending.0 = None;
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if std::fs::remove_file(AUTOSAVE_FILE).is_ok() {
+            info!("已清除自动存档");
+        }
+    }
+}
+
+/// 主菜单：“继续”恢复存档，“开始游戏”从头开始并丢弃存档
+fn handle_resume_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut pending: ResMut<PendingAutosave>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+    mut active_challenge: ResMut<ActiveChallenge>,
+    mut custom_level: ResMut<CustomLevel>,
+    mut mirror_mode: ResMut<MirrorMode>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::ContinueGame => {
+                info!("▶️ 继续上次进度");
+                // 之前选过的挑战、导入关卡和镜像开关会让 restore_autosave 认为存档属于其他关卡
+                active_challenge.0 = None;
+                custom_level.0 = None;
+                if let Some(data) = &pending.0 {
+                    mirror_mode.enabled = data.mirrored;
+                }
+                next_state.set(GameStateEnum::Playing);
+            }
+            ButtonType::StartGame if pending.0.is_some() => discard_autosave(&mut pending),
+            _ => {}
+        }
+    }
+}
+
+// ============ 恢复存档 ============
+
+/// 离开主菜单时，若存档属于某个普通关卡，则直接继续该关卡
+pub fn select_autosaved_level(
+    pending: Res<PendingAutosave>,
+    active_challenge: Res<ActiveChallenge>,
+    custom_level: Res<CustomLevel>,
+    mut level_manager: ResMut<LevelManager>,
+) {
+    let Some(data) = &pending.0 else {
+        return;
+    };
+    if active_challenge.0.is_some() || custom_level.0.is_some() {
+        return;
+    }
+
+    if let Some(index) = level_manager
+        .available_levels
+        .iter()
+        .position(|level_id| *level_id == data.level_id)
+    {
+        level_manager.current_level_index = index;
+    }
+}
+
+/// 在 load_current_level 之后执行：重建已放置的路线段、线路、乘客和公交车，
+/// 并恢复库存、时间、乘客统计、乘客生成进度、站点升级、经营收支、合约和线路调度。
+/// 天气和封路由游戏时间和沿用存档种子抽出的事件推算，无需单独恢复
+pub fn restore_autosave(
+    mut commands: Commands,
+    mut pending: ResMut<PendingAutosave>,
+    mut game_state: ResMut<GameState>,
+    mirror_mode: Res<MirrorMode>,
+    mut level_manager: ResMut<LevelManager>,
+    mut route_lines: ResMut<RouteLines>,
+    mut fleet: ResMut<Fleet>,
+    mut scenario: ResMut<ScenarioProgress>,
+    (mut economy, mut contracts, mut schedules): (
+        ResMut<EconomyMode>,
+        ResMut<ContractBoard>,
+        ResMut<RouteSchedules>,
+    ),
+    mut stations: Query<&mut StationEntity>,
+    sim_clock: Res<SimClock>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    // 从关卡选择进入其他关卡时保留存档，主菜单上仍可“继续”
+    let Some(data) = pending
+        .0
+        .take_if(|data| data.level_id == level_data.id && data.mirrored == mirror_mode.enabled)
+    else {
+        if pending.0.is_some() {
+            info!("自动存档属于其他关卡，跳过恢复");
+        }
+        return;
+    };
+
+    // 路线段坐标依赖 LevelManager 中的网格尺寸，这里提前同步（sync_level_data 要到 Update 才运行）
+    level_manager.current_level = Some(level_data.clone());
+
+    for (pos, segment_type, rotation, cost, express) in &data.segments {
+        let entity = spawn_route_segment(
+            &mut commands,
+            &asset_server,
+            &theme,
+            *pos,
+            *segment_type,
+            *rotation,
+            &level_manager,
+            NetworkLayer::Ground,
+        );
+        if *express {
+            commands.entity(entity).insert(ExpressSegment);
+        }
+        game_state.placed_segments.insert(
+            *pos,
+            PlacedSegment {
+                segment_type: *segment_type,
+                rotation: *rotation,
+                entity,
+                cost: *cost,
+                express: *express,
+            },
+        );
+    }
+
+    for (pos, segment_type, rotation, cost) in &data.elevated_segments {
+        let entity = spawn_route_segment(
+            &mut commands,
+            &asset_server,
+            &theme,
+            *pos,
+            *segment_type,
+            *rotation,
+            &level_manager,
+            NetworkLayer::Elevated,
+        );
+        game_state.elevated_segments.insert(
+            *pos,
+            PlacedSegment {
+                segment_type: *segment_type,
+                rotation: *rotation,
+                entity,
+                cost: *cost,
+                express: false,
+            },
+        );
+    }
+
+    // 线路直接写入，不改修订号，避免恢复后立即重新生成公交车
+    route_lines
+        .assignments
+        .extend(data.line_assignments.iter().copied());
+
+    if let Some(level_data) = &level_manager.current_level {
+        restore_buses_and_passengers(
+            &mut commands,
+            &asset_server,
+            &theme,
+            &level_manager,
+            level_data,
+            &data,
+            sim_clock.elapsed_secs(),
+        );
+    }
+
+    *fleet = data.fleet.clone();
+    game_state.player_inventory = data.inventory.clone();
+    game_state.total_cost = data.total_cost;
+    game_state.game_time = data.game_time;
+    game_state.passenger_stats = data.passenger_stats.restore();
+
+    if let Some(level_data) = &mut game_state.current_level {
+        for (demand, spawned_count) in level_data
+            .passenger_demands
+            .iter_mut()
+            .zip(&data.spawned_counts)
+        {
+            demand.spawned_count = *spawned_count;
+        }
+
+        // 重建已触发剧情步骤注入的事件，消息不再重复显示
+        for (step_index, fired_at) in data.scenario.fired_at.iter().enumerate() {
+            push_step_events(level_data, step_index, *fired_at);
+        }
+    }
+    *scenario = data.scenario.clone();
+
+    // 站点已由 load_current_level 生成；扩容费用已计入 total_cost，这里只恢复效果
+    for mut station in stations.iter_mut() {
+        if let Some((_, upgrades)) = data
+            .station_upgrades
+            .iter()
+            .find(|(pos, _)| *pos == station.station_data.position)
+        {
+            station.restore_upgrades(*upgrades);
+        }
+    }
+
+    // 按存档时的模式继续：经营模式的收支和合约进度一并恢复，避免恢复后余额只剩启动资金减支出
+    *economy = data.economy.clone();
+    *contracts = data.contracts.clone();
+    *schedules = data.schedules.clone();
+
+    // 寻路图由 update_pathfinding_graph 每帧根据 RouteSegment 重建，恢复的路线段下一帧即接入网络
+    info!(
+        "💾 已恢复关卡 {}: {} 个路线段, {} 名乘客, {} 辆公交车, 游戏时间 {:.0}s",
+        data.level_id,
+        data.segments.len(),
+        data.passengers.len(),
+        data.buses.len(),
+        data.game_time
+    );
+}
+
+/// 先为公交车预留实体，乘车的乘客按车辆编号上车后再插入公交车组件
+fn restore_buses_and_passengers(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    theme: &ThemeRegistry,
+    level_manager: &LevelManager,
+    level_data: &LevelData,
+    data: &AutosaveData,
+    sim_elapsed: f32,
+) {
+    let mut buses: Vec<(Entity, BusVehicle)> = data
+        .buses
+        .iter()
+        .map(|bus| (commands.spawn_empty().id(), bus.bus_vehicle()))
+        .collect();
+    let (grid_width, grid_height) = level_data.grid_size;
+
+    for passenger in &data.passengers {
+        let mut agent = PathfindingAgent {
+            color: passenger.color,
+            origin: passenger.origin.clone(),
+            destination: passenger.destination.clone(),
+            current_path: Vec::new(),
+            current_step: 0,
+            state: passenger.state.clone(),
+            patience: passenger.patience,
+            max_patience: passenger.max_patience,
+            waiting_time: passenger.waiting_time,
+            via: passenger.via.clone(),
+            lines_ridden: passenger.lines_ridden.clone(),
+            group_size: passenger.group_size,
+        };
+
+        let riding = passenger.riding.as_ref().and_then(|riding| {
+            let index = data
+                .buses
+                .iter()
+                .position(|bus| bus.vehicle_id == riding.vehicle_id)?;
+            Some((index, riding))
+        });
+
+        let world_pos = match riding {
+            Some((index, _)) => Vec3::from_array(data.buses[index].position),
+            None => {
+                // 不在车上的乘客回到站台等车
+                agent.state = AgentState::WaitingAtStation;
+                let Some(station) = level_data
+                    .stations
+                    .iter()
+                    .find(|station| station.name == agent.origin)
+                else {
+                    warn!("找不到乘客的起点站: {}", agent.origin);
+                    continue;
+                };
+                station
+                    .position
+                    .to_world_pos(level_manager.tile_size, grid_width, grid_height)
+            }
+        }
+        .truncate()
+        .extend(PASSENGER_Z);
+
+        let group_size = agent.group_size as usize;
+        let entity =
+            spawn_passenger_entity(commands, asset_server, theme, agent, world_pos, level_data);
+
+        if let Some((index, riding)) = riding {
+            let (bus_entity, bus_vehicle) = &mut buses[index];
+            bus_vehicle
+                .current_passengers
+                .extend(std::iter::repeat_n(entity, group_size));
+            commands.entity(entity).insert(OnBus {
+                bus_entity: *bus_entity,
+                target_station: riding.target_station.clone(),
+                boarding_time: sim_elapsed - riding.ride_secs,
+            });
+        }
+    }
+
+    for ((bus_entity, bus_vehicle), bus) in buses.into_iter().zip(&data.buses) {
+        let line = bus
+            .agent
+            .line_id
+            .as_deref()
+            .and_then(line_number)
+            .unwrap_or(DEFAULT_LINE);
+        let mut entity = commands.entity(bus_entity);
+        entity.insert(bus_bundle(
+            asset_server,
+            theme,
+            line,
+            Vec3::from_array(bus.position),
+            bus_vehicle,
+            bus.agent.clone(),
+        ));
+        if let Some(bus_loop) = &bus.bus_loop {
+            entity.insert(bus_loop.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus_puzzle::{BusFrequency, RouteSchedule};
+
+    #[test]
+    fn autosave_survives_json_round_trip() {
+        let mut data = AutosaveData {
+            level_id: "level_02".to_string(),
+            economy: EconomyMode {
+                enabled: true,
+                income: 21,
+                penalties: 5,
+                upkeep: 7,
+            },
+            station_upgrades: vec![(
+                GridPos::new(3, 4),
+                StationUpgrades {
+                    capacity_level: 2,
+                    shelter: true,
+                    accessible: false,
+                },
+            )],
+            event_seed: Some(u64::MAX),
+            segments: vec![(GridPos::new(1, 5), RouteSegmentType::Curve, 90, 2, true)],
+            ..default()
+        };
+        data.inventory.insert(RouteSegmentType::Straight, 4);
+        data.fleet.assigned.insert(2, 3);
+        data.contracts.enabled = true;
+        data.contracts.bonus_points = 60;
+        data.schedules.schedules.insert(
+            "Station A → Station B".to_string(),
+            RouteSchedule {
+                bus_count: 3,
+                frequency: BusFrequency::High,
+            },
+        );
+
+        let restored = AutosaveData::deserialize(&data.serialize()).expect("存档应当可以读回");
+
+        assert_eq!(restored.economy.income, 21);
+        assert_eq!(restored.economy.penalties, 5);
+        assert_eq!(restored.economy.upkeep, 7);
+        assert!(restored.economy.enabled);
+        assert_eq!(restored.station_upgrades, data.station_upgrades);
+        assert_eq!(restored.event_seed, Some(u64::MAX));
+        assert!(restored.contracts.enabled);
+        assert_eq!(restored.contracts.bonus_points, 60);
+        assert_eq!(restored.schedules.schedules, data.schedules.schedules);
+        assert_eq!(restored.segments, data.segments);
+        assert_eq!(restored.inventory, data.inventory);
+        assert_eq!(restored.fleet.assigned, data.fleet.assigned);
+    }
+}
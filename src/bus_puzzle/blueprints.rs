@@ -0,0 +1,703 @@
+// src/bus_puzzle/blueprints.rs - 蓝图库（把框选的路线段保存为命名蓝图，在任意关卡中盖章放置，随进度存档保存）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, parse_segment_type, shortcut_modifier_pressed,
+    show_contextual_tip, write_save_game, ButtonComponent, ButtonType, ClipboardSegment,
+    ConfirmAction, ConfirmRequestEvent, CurrentLanguage, DialogConfirmedEvent, GameState,
+    GameStateEnum, GridPos, InputAction, InputMap, InputState, Language, LevelManager,
+    LocalizedText, LocalizedTextComponent, SegmentSelection, TipType, UIAssets,
+    BLUEPRINT_DEFAULT_NAME, BLUEPRINT_LIBRARY, BLUEPRINT_LIBRARY_EMPTY, BLUEPRINT_NAME_PROMPT,
+    BLUEPRINT_NEED_SELECTION, BLUEPRINT_SAVE, BLUEPRINT_SAVED, BLUEPRINT_SEGMENTS, BLUEPRINT_STAMP,
+    BLUEPRINT_STAMP_HINT, CANCEL, CLOSE, CONFIRM_DELETE,
+};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        InputSystem,
+    },
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
+};
+
+const SAVE_BLUEPRINT_KEY: KeyCode = KeyCode::KeyB; // 与 Ctrl 组合
+const LIBRARY_TOGGLE_KEY: KeyCode = KeyCode::KeyB;
+
+/// 蓝图名称的最大字符数
+const MAX_NAME_LENGTH: usize = 24;
+
+const MODIFIER_KEYS: [KeyCode; 6] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+];
+
+/// 一组可重复使用的路线段布局，位置相对左下角
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blueprint {
+    pub name: String,
+    pub segments: Vec<ClipboardSegment>,
+}
+
+/// 玩家保存的所有蓝图，启动时从进度存档读取，不随关卡重置
+#[derive(Resource, Default)]
+pub struct BlueprintLibrary {
+    pub blueprints: Vec<Blueprint>,
+}
+
+impl BlueprintLibrary {
+    /// 同名蓝图直接覆盖，否则追加到末尾
+    pub fn store(&mut self, blueprint: Blueprint) {
+        match self
+            .blueprints
+            .iter_mut()
+            .find(|existing| existing.name == blueprint.name)
+        {
+            Some(existing) => *existing = blueprint,
+            None => self.blueprints.push(blueprint),
+        }
+    }
+}
+
+/// 存档中的一行：名称|类型:x:y:旋转;类型:x:y:旋转
+pub fn encode_blueprint(blueprint: &Blueprint) -> String {
+    let segments: Vec<String> = blueprint
+        .segments
+        .iter()
+        .map(|segment| {
+            format!(
+                "{:?}:{}:{}:{}",
+                segment.segment_type, segment.offset.x, segment.offset.y, segment.rotation
+            )
+        })
+        .collect();
+    format!("{}|{}", blueprint.name, segments.join(";"))
+}
+
+/// 任何一个路线段无法解析时整个蓝图作废
+pub fn decode_blueprint(value: &str) -> Option<Blueprint> {
+    let (name, segments) = value.split_once('|')?;
+    let name = sanitize_name(name);
+    if name.is_empty() {
+        return None;
+    }
+
+    let segments = segments
+        .split(';')
+        .filter(|segment| !segment.trim().is_empty())
+        .map(|segment| {
+            let fields: Vec<&str> = segment.trim().split(':').collect();
+            let [segment_type, x, y, rotation] = fields[..] else {
+                return None;
+            };
+            Some(ClipboardSegment {
+                offset: GridPos::new(x.parse().ok()?, y.parse().ok()?),
+                segment_type: parse_segment_type(segment_type)?,
+                rotation: rotation.parse().ok()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if segments.is_empty() {
+        return None;
+    }
+
+    Some(Blueprint { name, segments })
+}
+
+/// 去掉控制字符和存档分隔符，并限制长度
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_control() && *c != '|')
+        .take(MAX_NAME_LENGTH)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// 命名面板，保存时写入的路线段在按下 Ctrl+B 时就已确定
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct BlueprintNamePanel {
+    name: String,
+    segments: Vec<ClipboardSegment>,
+}
+
+#[derive(Component)]
+pub struct BlueprintNameText;
+
+/// 蓝图库面板，按 B 打开或关闭
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct BlueprintLibraryPanel;
+
+// ============ 蓝图插件 ============
+
+pub struct BlueprintsPlugin;
+
+impl Plugin for BlueprintsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BlueprintLibrary>()
+            // 输入名称时屏蔽其他快捷键（镜头移动、旋转、暂停等），ESC 先用于关闭蓝图库
+            .add_systems(
+                PreUpdate,
+                capture_blueprint_keys
+                    .after(InputSystem)
+                    .run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_blueprint_keys,
+                    type_blueprint_name,
+                    handle_blueprint_buttons,
+                    refresh_blueprint_library_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn capture_blueprint_keys(
+    mut commands: Commands,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    name_panels: Query<(), With<BlueprintNamePanel>>,
+    library_panels: Query<Entity, With<BlueprintLibraryPanel>>,
+) {
+    if !name_panels.is_empty() {
+        // 修饰键保留，用于判断 Ctrl+字母 不算输入
+        let keys: Vec<KeyCode> = keyboard_input
+            .get_pressed()
+            .chain(keyboard_input.get_just_released())
+            .filter(|key| !MODIFIER_KEYS.contains(key))
+            .copied()
+            .collect();
+        for key in keys {
+            keyboard_input.reset(key);
+        }
+        return;
+    }
+
+    if !library_panels.is_empty() && input_map.just_pressed(&keyboard_input, InputAction::Pause) {
+        for entity in library_panels.iter() {
+            commands.entity(entity).despawn();
+        }
+        keyboard_input.clear_just_pressed(input_map.key(InputAction::Pause));
+    }
+}
+
+// ============ 快捷键 ============
+
+/// Ctrl+B 把框选的路线段保存为蓝图，B 打开或关闭蓝图库
+fn handle_blueprint_keys(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    selection: Res<SegmentSelection>,
+    game_state: Res<GameState>,
+    library: Res<BlueprintLibrary>,
+    name_panels: Query<(), With<BlueprintNamePanel>>,
+    library_panels: Query<Entity, With<BlueprintLibraryPanel>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if game_state.is_paused || !name_panels.is_empty() {
+        return;
+    }
+    let language = current_language.language;
+    let modifier_pressed = shortcut_modifier_pressed(&keyboard_input);
+
+    if modifier_pressed && keyboard_input.just_pressed(SAVE_BLUEPRINT_KEY) {
+        let segments = selection.selected_segments(&game_state);
+        if segments.is_empty() {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text(&BLUEPRINT_NEED_SELECTION, language),
+                TipType::Controls,
+                3.0,
+            );
+            return;
+        }
+
+        let default_name = get_text_with_args(
+            &BLUEPRINT_DEFAULT_NAME,
+            language,
+            &[&(library.blueprints.len() + 1).to_string()],
+        );
+        spawn_name_panel(
+            &mut commands,
+            &ui_assets,
+            language,
+            sanitize_name(&default_name),
+            segments,
+        );
+        return;
+    }
+
+    if !modifier_pressed && keyboard_input.just_pressed(LIBRARY_TOGGLE_KEY) {
+        if library_panels.is_empty() {
+            spawn_library_panel(&mut commands, &ui_assets, &library, language);
+        } else {
+            for entity in library_panels.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+/// 面板打开时接收键盘输入：Backspace 删除，Enter 保存，Esc 关闭
+fn type_blueprint_name(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut panels: Query<(Entity, &mut BlueprintNamePanel)>,
+    mut name_texts: Query<&mut Text, With<BlueprintNameText>>,
+    mut library: ResMut<BlueprintLibrary>,
+    level_manager: Res<LevelManager>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let Ok((entity, mut panel)) = panels.single_mut() else {
+        keyboard_events.clear();
+        return;
+    };
+
+    let modifier_pressed = shortcut_modifier_pressed(&keyboard_input);
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(text) if !modifier_pressed => {
+                panel.name = sanitize_name(&format!("{}{}", panel.name, text));
+            }
+            Key::Space if panel.name.chars().count() < MAX_NAME_LENGTH => {
+                panel.name.push(' ');
+            }
+            Key::Backspace => {
+                panel.name.pop();
+            }
+            Key::Enter => {
+                save_blueprint(
+                    &mut commands,
+                    &panel,
+                    &mut library,
+                    &level_manager,
+                    &ui_assets,
+                    current_language.language,
+                );
+                commands.entity(entity).despawn();
+                return;
+            }
+            Key::Escape => {
+                commands.entity(entity).despawn();
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    if panel.is_changed() {
+        for mut text in name_texts.iter_mut() {
+            text.0 = format!("{}_", panel.name);
+        }
+    }
+}
+
+/// 名称为空时不保存；保存后立即写入存档
+fn save_blueprint(
+    commands: &mut Commands,
+    panel: &BlueprintNamePanel,
+    library: &mut BlueprintLibrary,
+    level_manager: &LevelManager,
+    ui_assets: &UIAssets,
+    language: Language,
+) {
+    let name = sanitize_name(&panel.name);
+    if name.is_empty() {
+        return;
+    }
+
+    show_contextual_tip(
+        commands,
+        ui_assets,
+        &get_text_with_args(
+            &BLUEPRINT_SAVED,
+            language,
+            &[&name, &panel.segments.len().to_string()],
+        ),
+        TipType::Controls,
+        3.0,
+    );
+    info!("📐 保存蓝图 {}: {} 个路线段", name, panel.segments.len());
+    library.store(Blueprint {
+        name,
+        segments: panel.segments.clone(),
+    });
+    write_save_game(level_manager, library);
+}
+
+// ============ 按钮 ============
+
+fn handle_blueprint_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut confirmed_events: EventReader<DialogConfirmedEvent>,
+    mut confirm_requests: EventWriter<ConfirmRequestEvent>,
+    mut library: ResMut<BlueprintLibrary>,
+    level_manager: Res<LevelManager>,
+    mut selection: ResMut<SegmentSelection>,
+    mut input_state: ResMut<InputState>,
+    name_panels: Query<(Entity, &BlueprintNamePanel)>,
+    library_panels: Query<Entity, With<BlueprintLibraryPanel>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let language = current_language.language;
+
+    for DialogConfirmedEvent(action) in confirmed_events.read() {
+        let ConfirmAction::DeleteBlueprint(index) = *action else {
+            continue;
+        };
+        if index < library.blueprints.len() {
+            let removed = library.blueprints.remove(index);
+            write_save_game(&level_manager, &library);
+            info!("🗑️ 已删除蓝图: {}", removed.name);
+        }
+    }
+
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::StampBlueprint(index) => {
+                let Some(blueprint) = library.blueprints.get(index) else {
+                    continue;
+                };
+                // 盖章沿用粘贴流程：点击格子放置，库存不足或位置受阻时会提示，可用 Ctrl+Z 撤销
+                selection.start_paste(blueprint.segments.clone(), input_state.active_layer);
+                input_state.selected_segment = None;
+                for entity in library_panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+                show_contextual_tip(
+                    &mut commands,
+                    &ui_assets,
+                    &get_text_with_args(&BLUEPRINT_STAMP_HINT, language, &[&blueprint.name]),
+                    TipType::Controls,
+                    3.0,
+                );
+            }
+            ButtonType::DeleteBlueprint(index) => {
+                confirm_requests.write(ConfirmRequestEvent(ConfirmAction::DeleteBlueprint(index)));
+            }
+            ButtonType::CloseBlueprintLibrary => {
+                for entity in library_panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ButtonType::SaveBlueprint => {
+                for (entity, panel) in name_panels.iter() {
+                    save_blueprint(
+                        &mut commands,
+                        panel,
+                        &mut library,
+                        &level_manager,
+                        &ui_assets,
+                        language,
+                    );
+                    commands.entity(entity).despawn();
+                }
+            }
+            ButtonType::CancelBlueprintName => {
+                for (entity, _) in name_panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 蓝图库变化（保存、删除）时重建已打开的面板
+fn refresh_blueprint_library_panel(
+    mut commands: Commands,
+    library: Res<BlueprintLibrary>,
+    library_panels: Query<Entity, With<BlueprintLibraryPanel>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !library.is_changed() || library_panels.is_empty() {
+        return;
+    }
+    for entity in library_panels.iter() {
+        commands.entity(entity).despawn();
+    }
+    spawn_library_panel(
+        &mut commands,
+        &ui_assets,
+        &library,
+        current_language.language,
+    );
+}
+
+// ============ 面板 ============
+
+fn spawn_name_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    language: Language,
+    name: String,
+    segments: Vec<ClipboardSegment>,
+) {
+    let prompt = get_text_with_args(
+        &BLUEPRINT_NAME_PROMPT,
+        language,
+        &[&segments.len().to_string()],
+    );
+    let display = format!("{}_", name);
+
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(4000),
+            BlueprintNamePanel { name, segments },
+            Name::new("Blueprint Name Panel"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(420.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(12.0),
+                        padding: UiRect::all(Px(24.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.4, 0.4, 0.6)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(prompt),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+
+                    // 输入框
+                    parent
+                        .spawn((
+                            Node {
+                                width: Px(300.0),
+                                height: Px(40.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.05, 0.05, 0.1)),
+                            Outline::new(Px(1.0), Val::ZERO, Color::srgb(0.5, 0.5, 0.7)),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new(display),
+                                TextFont {
+                                    font: ui_assets.font.clone(),
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                                BlueprintNameText,
+                            ));
+                        });
+
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Px(16.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_small_button(
+                                parent,
+                                ui_assets,
+                                ButtonType::SaveBlueprint,
+                                &BLUEPRINT_SAVE,
+                                language,
+                            );
+                            spawn_small_button(
+                                parent,
+                                ui_assets,
+                                ButtonType::CancelBlueprintName,
+                                &CANCEL,
+                                language,
+                            );
+                        });
+                });
+        });
+}
+
+fn spawn_library_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    library: &BlueprintLibrary,
+    language: Language,
+) {
+    commands
+        .spawn((
+            Node {
+                width: Px(320.0),
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(120.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Px(8.0),
+                padding: UiRect::all(Px(16.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.18, 0.95)),
+            Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.4, 0.4, 0.6)),
+            ZIndex(3000),
+            BlueprintLibraryPanel,
+            Name::new("Blueprint Library Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&BLUEPRINT_LIBRARY, language)),
+                LocalizedTextComponent::new(&BLUEPRINT_LIBRARY),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            if library.blueprints.is_empty() {
+                parent.spawn((
+                    Text::new(get_text(&BLUEPRINT_LIBRARY_EMPTY, language)),
+                    LocalizedTextComponent::new(&BLUEPRINT_LIBRARY_EMPTY),
+                    TextFont {
+                        font: ui_assets.font.clone(),
+                        font_size: 13.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ));
+            }
+
+            for (index, blueprint) in library.blueprints.iter().enumerate() {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(format!(
+                                "{}\n{}",
+                                blueprint.name,
+                                get_text_with_args(
+                                    &BLUEPRINT_SEGMENTS,
+                                    language,
+                                    &[&blueprint.segments.len().to_string()],
+                                )
+                            )),
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            Node {
+                                flex_grow: 1.0,
+                                ..default()
+                            },
+                        ));
+                        spawn_small_button(
+                            parent,
+                            ui_assets,
+                            ButtonType::StampBlueprint(index),
+                            &BLUEPRINT_STAMP,
+                            language,
+                        );
+                        spawn_small_button(
+                            parent,
+                            ui_assets,
+                            ButtonType::DeleteBlueprint(index),
+                            &CONFIRM_DELETE,
+                            language,
+                        );
+                    });
+            }
+
+            spawn_small_button(
+                parent,
+                ui_assets,
+                ButtonType::CloseBlueprintLibrary,
+                &CLOSE,
+                language,
+            );
+        });
+}
+
+fn spawn_small_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    text_key: &'static LocalizedText,
+    language: Language,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(80.0),
+                height: Px(30.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(text_key, language)),
+                LocalizedTextComponent::new(text_key),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
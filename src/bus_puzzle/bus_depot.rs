@@ -0,0 +1,451 @@
+// src/bus_puzzle/bus_depot.rs - 公交车场（手动车队模式：用预算购买公交车，从车场拖到线路上运营）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, line_id, show_contextual_tip,
+    smart_bus_generation::spawn_smart_bus, ui_audio::GameplayUI, BusPathfindingManager, BusVehicle,
+    ButtonComponent, ButtonType, CurrentLanguage, EconomyMode, GameState, GameStateEnum,
+    InputState, LevelEntity, LevelManager, PathfindingGraph, RouteLines, RouteSchedules,
+    StationType, ThemeRegistry, TipType, UIAssets, BUS_ASSIGNED, BUS_PURCHASED, BUY_BUS,
+    INSUFFICIENT_FUNDS, LINE_FLEET_FULL, MAX_BUSES_PER_ROUTE, PASSENGER_Z,
+};
+use bevy::{
+    platform::collections::HashMap,
+    prelude::{Val::Px, *},
+};
+use serde::{Deserialize, Serialize};
+
+/// 每辆公交车的价格
+pub const BUS_PRICE: u32 = 3;
+
+/// 车场里每排停放的车辆数和车辆间距
+const PARKED_BUSES_PER_ROW: u32 = 3;
+const PARKED_BUS_SPACING: f32 = 20.0;
+
+/// 停放车辆的显示尺寸（比运营中的车小一些）
+const PARKED_BUS_SIZE: f32 = 32.0;
+
+/// 按下鼠标时离车辆多近算抓住（格子大小的比例）
+const GRAB_RADIUS_TILES: f32 = 0.4;
+
+// ============ 车队数据 ============
+
+/// 本关购买的公交车：停在车场的车辆数，以及每条线路上运营的车辆数
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct Fleet {
+    pub parked: u32,
+    pub assigned: HashMap<u32, u32>,
+}
+
+impl Fleet {
+    pub fn buses_on(&self, line: u32) -> u32 {
+        self.assigned.get(&line).copied().unwrap_or(0)
+    }
+}
+
+/// 当前关卡是否使用手动车队
+pub fn manual_fleet(level_manager: &LevelManager) -> bool {
+    level_manager
+        .current_level
+        .as_ref()
+        .is_some_and(|level_data| level_data.manual_fleet)
+}
+
+/// 停在车场、等待拖到线路上的公交车
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct ParkedBus {
+    slot: u32,
+}
+
+/// 购买按钮上的文字
+#[derive(Component)]
+pub struct BuyBusText;
+
+/// 正在拖动的车场公交车
+#[derive(Resource, Default)]
+struct DraggedBus(Option<Entity>);
+
+// ============ 公交车场插件 ============
+
+pub struct BusDepotPlugin;
+
+impl Plugin for BusDepotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Fleet>()
+            .init_resource::<DraggedBus>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_fleet)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_fleet)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_buy_bus_button)
+            .add_systems(OnExit(GameStateEnum::Playing), release_dragged_bus)
+            .add_systems(
+                Update,
+                (
+                    handle_buy_bus_button,
+                    sync_parked_buses,
+                    drag_parked_buses,
+                    deploy_assigned_buses,
+                    update_buy_bus_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+pub fn reset_fleet(mut fleet: ResMut<Fleet>) {
+    *fleet = Fleet::default();
+}
+
+/// 离开游戏状态时松开正在拖动的车，回来后停回车场
+fn release_dragged_bus(
+    mut dragged: ResMut<DraggedBus>,
+    mut parked: Query<(&ParkedBus, &mut Transform)>,
+    level_manager: Res<LevelManager>,
+) {
+    let Some(entity) = dragged.0.take() else {
+        return;
+    };
+    if let (Ok((parked_bus, mut transform)), Some(depot)) =
+        (parked.get_mut(entity), depot_position(&level_manager))
+    {
+        transform.translation = parked_slot_position(depot, parked_bus.slot);
+    }
+}
+
+/// 车场位置：关卡中的第一个车场，没有车场时使用第一个站点
+fn depot_position(level_manager: &LevelManager) -> Option<Vec3> {
+    let level_data = level_manager.current_level.as_ref()?;
+    let depot = level_data
+        .stations
+        .iter()
+        .find(|station| station.station_type == StationType::Depot)
+        .or(level_data.stations.first())?;
+    let (grid_width, grid_height) = level_data.grid_size;
+    Some(
+        depot
+            .position
+            .to_world_pos(level_manager.tile_size, grid_width, grid_height),
+    )
+}
+
+/// 停放车辆围绕车场排成几排
+fn parked_slot_position(depot: Vec3, slot: u32) -> Vec3 {
+    let column = (slot % PARKED_BUSES_PER_ROW) as f32 - (PARKED_BUSES_PER_ROW - 1) as f32 / 2.0;
+    let row = (slot / PARKED_BUSES_PER_ROW) as f32;
+    Vec3::new(
+        depot.x + column * PARKED_BUS_SPACING,
+        depot.y - row * PARKED_BUS_SPACING,
+        PASSENGER_Z + 0.1,
+    )
+}
+
+// ============ 购买 ============
+
+fn spawn_buy_bus_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    level_manager: Res<LevelManager>,
+    fleet: Res<Fleet>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !manual_fleet(&level_manager) {
+        return;
+    }
+
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(10.0),
+                top: Px(600.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.3, 0.45)),
+            ZIndex(200),
+            GameplayUI,
+            ButtonComponent {
+                button_type: ButtonType::BuyBus,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            Name::new("Buy Bus Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(buy_bus_label(&fleet, &current_language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                BuyBusText,
+            ));
+        });
+}
+
+fn buy_bus_label(fleet: &Fleet, current_language: &CurrentLanguage) -> String {
+    get_text_with_args(
+        &BUY_BUS,
+        current_language.language,
+        &[&BUS_PRICE.to_string(), &fleet.parked.to_string()],
+    )
+}
+
+fn handle_buy_bus_button(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut fleet: ResMut<Fleet>,
+    mut game_state: ResMut<GameState>,
+    economy: Res<EconomyMode>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let pressed = button_query.iter().any(|(interaction, button)| {
+        matches!(*interaction, Interaction::Pressed)
+            && matches!(button.button_type, ButtonType::BuyBus)
+    });
+    if !pressed {
+        return;
+    }
+
+    let language = current_language.language;
+    if !economy.can_afford(&game_state, BUS_PRICE) {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(&INSUFFICIENT_FUNDS, language),
+            TipType::Warning,
+            2.0,
+        );
+        return;
+    }
+
+    game_state.total_cost += BUS_PRICE;
+    fleet.parked += 1;
+
+    show_contextual_tip(
+        &mut commands,
+        &ui_assets,
+        &get_text(&BUS_PURCHASED, language),
+        TipType::Strategy,
+        3.0,
+    );
+    info!(
+        "🚌 购买公交车，花费 {}，车场停放 {} 辆",
+        BUS_PRICE, fleet.parked
+    );
+}
+
+/// 车场里显示的车辆与停放数量保持一致（购买、分配、恢复存档后）
+fn sync_parked_buses(
+    mut commands: Commands,
+    fleet: Res<Fleet>,
+    parked: Query<(Entity, &ParkedBus)>,
+    level_manager: Res<LevelManager>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
+    let mut slots: Vec<(Entity, u32)> = parked
+        .iter()
+        .map(|(entity, parked_bus)| (entity, parked_bus.slot))
+        .collect();
+    let shown = slots.len() as u32;
+    if shown == fleet.parked {
+        return;
+    }
+
+    if shown > fleet.parked {
+        slots.sort_by_key(|(_, slot)| std::cmp::Reverse(*slot));
+        for (entity, _) in slots.iter().take((shown - fleet.parked) as usize) {
+            commands.entity(*entity).despawn();
+        }
+        return;
+    }
+
+    let Some(depot) = depot_position(&level_manager) else {
+        return;
+    };
+
+    // 新车停到空出来的位置上
+    let mut slot = 0;
+    for _ in shown..fleet.parked {
+        while slots.iter().any(|(_, used)| *used == slot) {
+            slot += 1;
+        }
+        commands.spawn((
+            Name::new("Parked Bus"),
+            Sprite {
+                image: asset_server.load(theme.bus()),
+                custom_size: Some(Vec2::splat(PARKED_BUS_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(parked_slot_position(depot, slot)),
+            ParkedBus { slot },
+        ));
+        slot += 1;
+    }
+}
+
+// ============ 拖到线路上 ============
+
+/// 未选中库存路线段时按住车场里的车拖动，松开在某条线路的路线段上即分配到该线路
+fn drag_parked_buses(
+    mut commands: Commands,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_state: Res<InputState>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    mut dragged: ResMut<DraggedBus>,
+    mut parked: Query<(Entity, &ParkedBus, &mut Transform)>,
+    mut fleet: ResMut<Fleet>,
+    (game_state, route_lines, level_manager): (Res<GameState>, Res<RouteLines>, Res<LevelManager>),
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let cursor = input_state.mouse_world_pos.truncate();
+
+    if mouse_button_input.just_pressed(MouseButton::Left)
+        && input_state.selected_segment.is_none()
+        && ui_buttons
+            .iter()
+            .all(|interaction| *interaction == Interaction::None)
+    {
+        let grab_radius = level_manager.tile_size * GRAB_RADIUS_TILES;
+        dragged.0 = parked
+            .iter()
+            .filter(|(.., transform)| {
+                transform.translation.truncate().distance(cursor) <= grab_radius
+            })
+            .max_by_key(|(_, parked_bus, _)| parked_bus.slot)
+            .map(|(entity, ..)| entity);
+    }
+
+    let Some(entity) = dragged.0 else {
+        return;
+    };
+    let Ok((_, parked_bus, mut transform)) = parked.get_mut(entity) else {
+        dragged.0 = None;
+        return;
+    };
+
+    if mouse_button_input.pressed(MouseButton::Left) {
+        transform.translation.x = cursor.x;
+        transform.translation.y = cursor.y;
+        return;
+    }
+
+    // 松开鼠标：落在地面路线段上则分配到该路线段的线路，否则停回车场
+    dragged.0 = None;
+    let language = current_language.language;
+    let line = input_state
+        .grid_cursor_pos
+        .filter(|pos| game_state.placed_segments.contains_key(pos))
+        .map(|pos| route_lines.line_of(pos));
+
+    match line {
+        Some(line) if fleet.buses_on(line) >= MAX_BUSES_PER_ROUTE => {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text_with_args(
+                    &LINE_FLEET_FULL,
+                    language,
+                    &[&line.to_string(), &MAX_BUSES_PER_ROUTE.to_string()],
+                ),
+                TipType::Warning,
+                2.0,
+            );
+        }
+        Some(line) => {
+            commands.entity(entity).despawn();
+            fleet.parked -= 1;
+            *fleet.assigned.entry(line).or_default() += 1;
+
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text_with_args(&BUS_ASSIGNED, language, &[&line.to_string()]),
+                TipType::Strategy,
+                2.0,
+            );
+            info!("🚌 车场公交车分配到线路 {}", line);
+            return;
+        }
+        None => {}
+    }
+
+    if let Some(depot) = depot_position(&level_manager) {
+        transform.translation = parked_slot_position(depot, parked_bus.slot);
+    }
+}
+
+/// 线路分到的车辆多于正在运营的车辆时，在线路的第一条路线上补发新车
+/// 线路还没连通两个站点时先记在分配里，等公交系统重新生成时一并投放
+fn deploy_assigned_buses(
+    mut commands: Commands,
+    fleet: Res<Fleet>,
+    mut bus_manager: ResMut<BusPathfindingManager>,
+    schedules: Res<RouteSchedules>,
+    buses: Query<&BusVehicle>,
+    level_manager: Res<LevelManager>,
+    pathfinding_graph: Res<PathfindingGraph>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
+    if !fleet.is_changed() || !manual_fleet(&level_manager) {
+        return;
+    }
+
+    for (&line, &count) in &fleet.assigned {
+        let Some(route) = bus_manager.bus_routes.get_mut(&line_id(line)) else {
+            continue;
+        };
+        route.max_vehicles = count;
+
+        let route_buses: Vec<&BusVehicle> = buses
+            .iter()
+            .filter(|bus| bus.route_id == route.route_id)
+            .collect();
+        let schedule = schedules.get(&route.stations);
+
+        // 新车使用还没被占用的序号，避免与现有车辆重名
+        let mut bus_index = 0;
+        for _ in route_buses.len() as u32..count {
+            while route_buses.iter().any(|bus| {
+                bus.vehicle_id == format!("智能公交_{}_{}", route.route_id, bus_index + 1)
+            }) {
+                bus_index += 1;
+            }
+            spawn_smart_bus(
+                &mut commands,
+                &asset_server,
+                &theme,
+                &level_manager,
+                &pathfinding_graph,
+                route,
+                schedule,
+                bus_index,
+            );
+            bus_index += 1;
+        }
+    }
+}
+
+fn update_buy_bus_text(
+    fleet: Res<Fleet>,
+    current_language: Res<CurrentLanguage>,
+    mut texts: Query<&mut Text, With<BuyBusText>>,
+) {
+    if !fleet.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in texts.iter_mut() {
+        text.0 = buy_bus_label(&fleet, &current_language);
+    }
+}
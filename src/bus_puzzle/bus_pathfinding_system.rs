@@ -2,15 +2,27 @@
 // 使用乘客验证过的寻路算法来驱动公交车移动
 
 use crate::bus_puzzle::{
-    find_optimal_path, BusDirection, BusState, BusVehicle, GameState, GameStateEnum, LevelManager,
-    PathNode, PathNodeType, PathfindingGraph, RouteSegment, StationEntity, PASSENGER_Z, ROUTE_Z,
+    find_optimal_path, find_optimal_path_on_line, get_text_with_args, show_contextual_tip,
+    simulation_running, ActiveWeather, BusDirection, BusState, BusVehicle, CurrentLanguage,
+    ExpressSegment, GameState, GameStateEnum, GridPos, InputState, LevelManager, NetworkLayer,
+    PathNode, PathNodeType, PathfindingGraph, RouteSegment, SimClock, StationEntity, ThemeRegistry,
+    TipType, UIAssets, BUS_LOOP_CIRCULAR, BUS_LOOP_SHUTTLE, DEFAULT_LINE, EFFECT_Z,
+    ELEVATED_SEGMENT_Z, EXPRESS_SPEED_MULTIPLIER, PASSENGER_Z, ROUTE_Z,
 };
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+/// 点击公交车时允许的距离（像素）
+const BUS_PICK_RADIUS: f32 = 24.0;
+
+/// 选中公交车时行驶圈高亮线的宽度和透明度
+const BUS_LOOP_LINE_WIDTH: f32 = 6.0;
+const BUS_LOOP_LINE_ALPHA: f32 = 0.6;
+
 // ============ 公交车寻路组件 ============
 
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct BusPathfindingAgent {
     pub vehicle_id: String,
     pub route_id: String,
@@ -22,10 +34,99 @@ pub struct BusPathfindingAgent {
     pub next_station_index: usize,
     pub stations_to_visit: Vec<String>, // 路线上的所有站点
     pub direction: BusDirection,
-    pub is_returning: bool, // 是否在返程
+    pub is_returning: bool,      // 是否在返程
+    pub line_id: Option<String>, // 所属线路，只沿该线路的路线段行驶；为空时不限线路
+}
+
+/// 公交车分配到的固定行驶圈：一圈内依次经过的各段路径（站点到站点），跑完最后一段回到起点站
+///
+/// 环形线路首尾站点之间另有一条不重复的路相连；否则按原路往返，回程是去程各段的反向。
+/// 路径在发车时一次算好，公交车不再在每站重新寻路，避免在路网中绕来绕去。
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct BusLoop {
+    pub legs: Vec<Vec<PathNode>>,
+    pub is_circular: bool,
+    pub next_leg: usize,
+}
+
+impl BusLoop {
+    /// 依次取出下一段路径，跑完一圈后从头开始
+    pub fn take_next_leg(&mut self) -> Vec<PathNode> {
+        let leg = self.legs[self.next_leg].clone();
+        self.next_leg = (self.next_leg + 1) % self.legs.len();
+        leg
+    }
+
+    /// 往返线路上，下一段是否属于回程
+    fn next_leg_returns(&self) -> bool {
+        !self.is_circular && self.next_leg >= self.legs.len() / 2
+    }
+
+    /// 一圈经过的格子（相邻两段首尾相接的站点只出现一次），用于显示选中公交车的行驶圈
+    pub fn tiles(&self) -> Vec<GridPos> {
+        let mut tiles: Vec<GridPos> = Vec::new();
+        for node in self.legs.iter().flatten() {
+            if tiles.last() != Some(&node.position) {
+                tiles.push(node.position);
+            }
+        }
+        tiles
+    }
+}
+
+/// 为经过 `stations` 的线路计算公交车的行驶圈；有线路时只走该线路的路线段，任意两站之间不通时返回 None
+pub fn compute_bus_loop(
+    pathfinding_graph: &PathfindingGraph,
+    stations: &[String],
+    line_id: Option<&str>,
+) -> Option<BusLoop> {
+    if stations.len() < 2 {
+        return None;
+    }
+
+    let find_leg = |from: &str, to: &str| match line_id {
+        Some(line_id) => find_optimal_path_on_line(pathfinding_graph, from, to, line_id),
+        None => find_optimal_path(pathfinding_graph, from, to),
+    };
+
+    let mut legs = stations
+        .windows(2)
+        .map(|pair| find_leg(&pair[0], &pair[1]))
+        .collect::<Option<Vec<_>>>()?;
+
+    // 末站回到首站的路不与去程重合时组成环线
+    let used: HashSet<GridPos> = legs.iter().flatten().map(|node| node.position).collect();
+    let closing_leg = (stations.len() >= 3)
+        .then(|| find_leg(&stations[stations.len() - 1], &stations[0]))
+        .flatten()
+        .filter(|leg| {
+            leg.len() > 2
+                && leg[1..leg.len() - 1]
+                    .iter()
+                    .all(|node| !used.contains(&node.position))
+        });
+
+    let is_circular = closing_leg.is_some();
+    match closing_leg {
+        Some(leg) => legs.push(leg),
+        None => {
+            let return_legs: Vec<_> = legs
+                .iter()
+                .rev()
+                .map(|leg| leg.iter().rev().cloned().collect())
+                .collect();
+            legs.extend(return_legs);
+        }
+    }
+
+    Some(BusLoop {
+        legs,
+        is_circular,
+        next_leg: 0,
+    })
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BusPathfindingState {
     Planning,       // 规划路径中
     Following,      // 跟随路径中
@@ -41,6 +142,15 @@ pub struct BusPathfindingManager {
     pub path_cache: HashMap<(String, String), Vec<PathNode>>, // 路径缓存
 }
 
+/// 当前显示行驶圈的公交车
+#[derive(Resource, Default)]
+pub struct SelectedBus(pub Option<Entity>);
+
+/// 选中公交车的行驶圈高亮线
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct BusLoopHighlight;
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct BusRouteInfo {
@@ -48,6 +158,7 @@ pub struct BusRouteInfo {
     pub stations: Vec<String>,
     pub is_circular: bool,
     pub max_vehicles: u32,
+    pub line: u32, // 所属的命名线路
 }
 
 // ============ 公交车寻路系统插件 ============
@@ -56,19 +167,29 @@ pub struct BusPathfindingPlugin;
 
 impl Plugin for BusPathfindingPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<BusPathfindingManager>().add_systems(
-            Update,
-            (
-                discover_bus_routes_pathfinding,
-                update_bus_pathfinding,
-                move_buses_along_paths,
-                handle_bus_station_stops,
-                debug_bus_pathfinding,
-                disable_passenger_pathfinding_system, // 新增：禁用乘客寻路
+        app.init_resource::<BusPathfindingManager>()
+            .init_resource::<SelectedBus>()
+            .add_systems(OnExit(GameStateEnum::Playing), clear_selected_bus)
+            .add_systems(
+                Update,
+                (
+                    discover_bus_routes_pathfinding,
+                    update_bus_pathfinding,
+                    move_buses_along_paths,
+                    // F5 在正式版本中是快速存档
+                    #[cfg(feature = "dev")]
+                    debug_bus_pathfinding,
+                    disable_passenger_pathfinding_system, // 新增：禁用乘客寻路
+                )
+                    .chain()
+                    .run_if(simulation_running),
             )
-                .chain()
-                .run_if(in_state(GameStateEnum::Playing)),
-        );
+            .add_systems(
+                Update,
+                (select_bus_on_click, draw_selected_bus_loop)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
     }
 }
 
@@ -102,16 +223,16 @@ fn has_station_connections(
     // 检查任意两个站点之间是否有路径
     for (i, start_station) in station_names.iter().enumerate() {
         for end_station in station_names.iter().skip(i + 1) {
-            if let Some(path) = find_optimal_path(pathfinding_graph, start_station, end_station) {
-                if path.len() > 1 {
-                    info!(
-                        "检测到站点连接: {} -> {} (路径长度: {})",
-                        start_station,
-                        end_station,
-                        path.len()
-                    );
-                    return true;
-                }
+            if let Some(path) = find_optimal_path(pathfinding_graph, start_station, end_station)
+                && path.len() > 1
+            {
+                info!(
+                    "检测到站点连接: {} -> {} (路径长度: {})",
+                    start_station,
+                    end_station,
+                    path.len()
+                );
+                return true;
             }
         }
     }
@@ -126,6 +247,7 @@ fn discover_bus_routes_pathfinding(
     stations: Query<&StationEntity>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     level_manager: Res<LevelManager>,
     existing_buses: Query<Entity, With<BusVehicle>>,
     game_state: Res<GameState>,
@@ -171,6 +293,7 @@ fn discover_bus_routes_pathfinding(
                 spawn_pathfinding_bus(
                     &mut commands,
                     &asset_server,
+                    &theme,
                     &level_manager,
                     &route_info,
                     &pathfinding_graph,
@@ -200,6 +323,8 @@ fn discover_routes_using_pathfinding(
     pathfinding_graph: &PathfindingGraph,
     stations: Query<&StationEntity>,
 ) -> Vec<BusRouteInfo> {
+    let _span = info_span!("discover_routes_using_pathfinding").entered();
+
     let mut routes = Vec::new();
     let mut processed_pairs = HashSet::new();
 
@@ -238,10 +363,10 @@ fn discover_routes_using_pathfinding(
 
                     // 添加路径中的中转站点
                     for path_node in &path[1..path.len() - 1] {
-                        if let PathNodeType::Station(station_name) = &path_node.node_type {
-                            if !route_stations.contains(station_name) {
-                                route_stations.push(station_name.clone());
-                            }
+                        if let PathNodeType::Station(station_name) = &path_node.node_type
+                            && !route_stations.contains(station_name)
+                        {
+                            route_stations.push(station_name.clone());
                         }
                     }
 
@@ -253,6 +378,7 @@ fn discover_routes_using_pathfinding(
                         stations: route_stations.clone(),
                         is_circular: false,
                         max_vehicles: 1,
+                        line: DEFAULT_LINE,
                     };
 
                     info!(
@@ -288,6 +414,7 @@ fn discover_routes_using_pathfinding(
                 stations: all_stations.clone(),
                 is_circular: false,
                 max_vehicles: 1,
+                line: DEFAULT_LINE,
             };
 
             info!("创建主干路线: {:?}", all_stations);
@@ -334,6 +461,7 @@ fn check_passenger_coverage(routes: &[BusRouteInfo], game_state: &GameState) {
 fn spawn_pathfinding_bus(
     commands: &mut Commands,
     asset_server: &AssetServer,
+    theme: &ThemeRegistry,
     level_manager: &LevelManager,
     route_info: &BusRouteInfo,
     pathfinding_graph: &PathfindingGraph,
@@ -371,7 +499,7 @@ fn spawn_pathfinding_bus(
         commands.spawn((
             Name::new(format!("Smart Bus {}", vehicle_id)),
             Sprite {
-                image: asset_server.load("textures/bus.png"),
+                image: asset_server.load(theme.bus()),
                 color: route_color,
                 custom_size: Some(Vec2::new(48.0, 48.0)),
                 ..default()
@@ -404,6 +532,7 @@ fn spawn_pathfinding_bus(
                 stations_to_visit: route_info.stations.clone(),
                 direction: BusDirection::Forward,
                 is_returning: false,
+                line_id: None,
             },
         ));
 
@@ -417,24 +546,35 @@ fn spawn_pathfinding_bus(
 // ============ 公交车寻路更新系统 ============
 
 fn update_bus_pathfinding(
-    mut buses: Query<(&mut BusPathfindingAgent, &mut BusVehicle)>,
+    mut buses: Query<(
+        &mut BusPathfindingAgent,
+        &mut BusVehicle,
+        Option<&mut BusLoop>,
+    )>,
     pathfinding_graph: Res<PathfindingGraph>,
-    time: Res<Time>,
+    stations: Query<&StationEntity>,
+    sim_clock: Res<SimClock>,
 ) {
-    let dt = time.delta_secs();
+    let dt = sim_clock.delta_secs();
 
-    for (mut agent, mut bus_vehicle) in buses.iter_mut() {
+    for (mut agent, mut bus_vehicle, mut bus_loop) in buses.iter_mut() {
         match agent.state {
-            BusPathfindingState::Planning => {
+            BusPathfindingState::Planning => match bus_loop.as_deref_mut() {
+                // 有固定行驶圈的公交车直接跑下一段
+                Some(bus_loop) => follow_bus_loop(&mut agent, bus_loop),
                 // 规划新路径
-                plan_next_route(&mut agent, &pathfinding_graph);
-            }
+                None => plan_next_route(&mut agent, &pathfinding_graph),
+            },
             BusPathfindingState::Following => {
                 // 跟随当前路径，无需额外处理，移动在另一个系统中处理
             }
             BusPathfindingState::AtStation => {
-                // 🔧 修复：在站点停靠的处理逻辑
-                bus_vehicle.remaining_dwell -= dt;
+                // 🔧 修复：在站点停靠的处理逻辑，无障碍站点上下客更快
+                let speedup = stations
+                    .iter()
+                    .find(|station| station.station_data.name == agent.target_station)
+                    .map_or(1.0, StationEntity::dwell_speedup);
+                bus_vehicle.remaining_dwell -= dt * speedup;
 
                 // 🔧 新增：停靠期间的调试信息
                 if bus_vehicle.remaining_dwell % 2.0 < dt {
@@ -450,17 +590,21 @@ fn update_bus_pathfinding(
                 }
 
                 if bus_vehicle.remaining_dwell <= 0.0 {
-                    // 🔧 修复：停靠结束后，更新站点索引
-                    match agent.direction {
-                        BusDirection::Forward => {
-                            agent.next_station_index += 1;
-                        }
-                        BusDirection::Backward => {
-                            agent.next_station_index = agent.next_station_index.saturating_sub(1);
+                    // 🔧 修复：停靠结束后，更新站点索引（按行驶圈行驶的公交车不使用站点索引）
+                    if bus_loop.is_none() {
+                        match agent.direction {
+                            BusDirection::Forward => {
+                                agent.next_station_index += 1;
+                            }
+                            BusDirection::Backward => {
+                                agent.next_station_index =
+                                    agent.next_station_index.saturating_sub(1);
+                            }
                         }
                     }
 
                     agent.state = BusPathfindingState::Planning;
+                    bus_vehicle.state = BusState::Traveling;
                     info!(
                         "🚌 公交车 {} 离开站点 {}，下一站索引: {}",
                         agent.vehicle_id, agent.target_station, agent.next_station_index
@@ -481,16 +625,53 @@ fn update_bus_pathfinding(
     }
 }
 
+/// 沿固定行驶圈前往下一站
+fn follow_bus_loop(agent: &mut BusPathfindingAgent, bus_loop: &mut BusLoop) {
+    let returning = bus_loop.next_leg_returns();
+    let path = bus_loop.take_next_leg();
+    let Some(PathNodeType::Station(target)) = path.last().map(|node| node.node_type.clone()) else {
+        agent.current_path.clear();
+        agent.state = BusPathfindingState::WaitingForPath;
+        return;
+    };
+
+    debug!(
+        "🚌 公交车 {} 沿行驶圈前往 {} ({}步)",
+        agent.vehicle_id,
+        target,
+        path.len()
+    );
+    agent.current_path = path;
+    agent.current_step = 0;
+    agent.target_station = target;
+    agent.state = BusPathfindingState::Following;
+    agent.path_progress = 0.0;
+    agent.is_returning = returning;
+    agent.direction = if returning {
+        BusDirection::Backward
+    } else {
+        BusDirection::Forward
+    };
+}
+
 /// 规划下一段路线
 fn plan_next_route(agent: &mut BusPathfindingAgent, pathfinding_graph: &PathfindingGraph) {
+    let _span = info_span!("plan_next_route", route_id = %agent.route_id).entered();
+
     let current_station = agent.target_station.clone();
 
     // 🔧 修复：确定下一个目标站点
     let next_target = get_next_station_target_fixed(agent);
 
     if let Some(target) = next_target {
-        // 使用乘客的寻路算法计算路径
-        if let Some(path) = find_optimal_path(pathfinding_graph, &current_station, &target) {
+        // 使用乘客的寻路算法计算路径，有线路的公交车不驶出自己的线路
+        let path = match &agent.line_id {
+            Some(line_id) => {
+                find_optimal_path_on_line(pathfinding_graph, &current_station, &target, line_id)
+            }
+            None => find_optimal_path(pathfinding_graph, &current_station, &target),
+        };
+        if let Some(path) = path {
             agent.current_path = path;
             agent.current_step = 0;
             agent.target_station = target.clone();
@@ -575,9 +756,15 @@ fn turn_around_pathfinding(agent: &mut BusPathfindingAgent) {
 fn move_buses_along_paths(
     mut buses: Query<(&mut BusPathfindingAgent, &mut Transform, &mut BusVehicle)>,
     level_manager: Res<LevelManager>,
-    time: Res<Time>,
+    express_segments: Query<&RouteSegment, With<ExpressSegment>>,
+    weather: Res<ActiveWeather>,
+    sim_clock: Res<SimClock>,
 ) {
-    let dt = time.delta_secs();
+    let dt = sim_clock.delta_secs();
+    let express_positions: HashSet<_> = express_segments
+        .iter()
+        .map(|segment| segment.grid_pos)
+        .collect();
 
     for (mut agent, mut transform, mut bus_vehicle) in buses.iter_mut() {
         if agent.state != BusPathfindingState::Following || agent.current_path.is_empty() {
@@ -615,10 +802,25 @@ fn move_buses_along_paths(
         let distance_to_target = transform.translation.distance(target_world_pos);
 
         if distance_to_target > 8.0 {
-            // 继续移动
-            let movement = direction * bus_vehicle.speed * dt;
+            // 继续移动，驶向快速路线或高架层时加速，驶入恶劣天气的地面格子时减速
+            let is_elevated = current_node.layer == NetworkLayer::Elevated;
+            let speed = if is_elevated || express_positions.contains(&current_node.position) {
+                bus_vehicle.speed * EXPRESS_SPEED_MULTIPLIER
+            } else {
+                bus_vehicle.speed
+            };
+            let speed = if is_elevated {
+                speed
+            } else {
+                speed * weather.speed_factor(current_node.position)
+            };
+            let movement = direction * speed * dt;
             transform.translation += movement;
-            transform.translation.z = ROUTE_Z + 0.1;
+            transform.translation.z = if is_elevated {
+                ELEVATED_SEGMENT_Z + 0.1
+            } else {
+                ROUTE_Z + 0.1
+            };
 
             // 调整朝向
             if direction.length() > 0.1 {
@@ -638,31 +840,129 @@ fn move_buses_along_paths(
     }
 }
 
-// ============ 站点停靠处理 ============
+// ============ 行驶圈显示 ============
+
+fn clear_selected_bus(mut selected: ResMut<SelectedBus>) {
+    selected.0 = None;
+}
+
+/// 未选中库存路线段时点击公交车，显示（或隐藏）它的行驶圈
+fn select_bus_on_click(
+    mut commands: Commands,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_state: Res<InputState>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    buses: Query<(Entity, &Transform, &BusPathfindingAgent, &BusLoop)>,
+    mut selected: ResMut<SelectedBus>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    // 公交车重新生成后取消选中
+    if selected.0.is_some_and(|entity| !buses.contains(entity)) {
+        selected.0 = None;
+    }
+
+    if !mouse_button_input.just_released(MouseButton::Left)
+        || input_state.selected_segment.is_some()
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    let cursor = input_state.mouse_world_pos.truncate();
+    let picked = buses
+        .iter()
+        .map(|(entity, transform, agent, bus_loop)| {
+            let distance = transform.translation.truncate().distance(cursor);
+            (entity, distance, agent, bus_loop)
+        })
+        .filter(|(_, distance, ..)| *distance <= BUS_PICK_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    match picked {
+        Some((entity, _, agent, bus_loop)) if selected.0 != Some(entity) => {
+            selected.0 = Some(entity);
+
+            let (text, separator) = if bus_loop.is_circular {
+                (&BUS_LOOP_CIRCULAR, " → ")
+            } else {
+                (&BUS_LOOP_SHUTTLE, " ⇄ ")
+            };
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text_with_args(
+                    text,
+                    current_language.language,
+                    &[&agent.stations_to_visit.join(separator)],
+                ),
+                TipType::Strategy,
+                3.0,
+            );
+        }
+        _ if selected.0.is_some() => selected.0 = None,
+        _ => {}
+    }
+}
 
-fn handle_bus_station_stops(
-    mut buses: Query<(&mut BusPathfindingAgent, &mut BusVehicle)>,
-    time: Res<Time>,
+/// 选中的公交车变化时重画行驶圈，线条使用公交车的线路颜色
+fn draw_selected_bus_loop(
+    mut commands: Commands,
+    selected: Res<SelectedBus>,
+    buses: Query<(&BusLoop, &Sprite)>,
+    highlights: Query<Entity, With<BusLoopHighlight>>,
+    level_manager: Res<LevelManager>,
 ) {
-    let dt = time.delta_secs();
+    if !selected.is_changed() {
+        return;
+    }
+
+    for entity in highlights.iter() {
+        commands.entity(entity).despawn();
+    }
 
-    for (mut agent, mut bus_vehicle) in buses.iter_mut() {
-        if agent.state == BusPathfindingState::AtStation {
-            bus_vehicle.remaining_dwell -= dt;
+    let Some((bus_loop, sprite)) = selected.0.and_then(|entity| buses.get(entity).ok()) else {
+        return;
+    };
+    let Some(level_data) = &level_manager.current_level else {
+        return;
+    };
 
-            if bus_vehicle.remaining_dwell <= 0.0 {
-                // 停靠结束，开始规划下一段路程
-                agent.state = BusPathfindingState::Planning;
-                bus_vehicle.state = BusState::Traveling;
+    let (grid_width, grid_height) = level_data.grid_size;
+    let color = sprite.color.with_alpha(BUS_LOOP_LINE_ALPHA);
+    let points: Vec<Vec2> = bus_loop
+        .tiles()
+        .into_iter()
+        .map(|pos| {
+            pos.to_world_pos(level_manager.tile_size, grid_width, grid_height)
+                .truncate()
+        })
+        .collect();
 
-                info!("公交车 {} 停靠结束，准备前往下一站", agent.vehicle_id);
-            }
+    for pair in points.windows(2) {
+        let delta = pair[1] - pair[0];
+        let length = delta.length();
+        if length <= f32::EPSILON {
+            continue;
         }
+        let center = (pair[0] + pair[1]) / 2.0;
+        commands.spawn((
+            Sprite::from_color(
+                color,
+                Vec2::new(length + BUS_LOOP_LINE_WIDTH, BUS_LOOP_LINE_WIDTH),
+            ),
+            Transform::from_translation(center.extend(EFFECT_Z))
+                .with_rotation(Quat::from_rotation_z(delta.y.atan2(delta.x))),
+            BusLoopHighlight,
+        ));
     }
 }
 
 // ============ 调试系统 ============
 
+#[cfg(feature = "dev")]
 fn debug_bus_pathfinding(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     buses: Query<(&BusPathfindingAgent, &Transform, &BusVehicle)>,
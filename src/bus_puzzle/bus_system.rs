@@ -1,6 +1,6 @@
 // src/bus_puzzle/bus_system.rs - 公交车系统核心实现
 
-use crate::bus_puzzle::GridPos;
+use crate::bus_puzzle::{GridPos, LevelEntity};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,6 +8,7 @@ use std::collections::HashMap;
 // ============ 公交车实体组件 ============
 
 #[derive(Component, Debug, Clone)]
+#[require(LevelEntity)]
 #[allow(dead_code)]
 pub struct BusVehicle {
     pub vehicle_id: String,
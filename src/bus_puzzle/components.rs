@@ -1,4 +1,7 @@
-use crate::bus_puzzle::{PathNode, Station, SEGMENT_PLACEMENT_COST};
+use crate::bus_puzzle::{
+    AgentLod, GameStateEnum, LevelEntity, PathNode, Station, StationUpgrades,
+    SEGMENT_PLACEMENT_COST,
+};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -105,17 +108,6 @@ impl RouteSegmentType {
         }
     }
 
-    pub fn get_texture_path(&self) -> &'static str {
-        match self {
-            RouteSegmentType::Straight => "textures/routes/straight.png",
-            RouteSegmentType::Curve => "textures/routes/curve.png",
-            RouteSegmentType::TSplit => "textures/routes/t_split.png",
-            RouteSegmentType::Cross => "textures/routes/cross.png",
-            RouteSegmentType::Bridge => "textures/routes/bridge.png",
-            RouteSegmentType::Tunnel => "textures/routes/tunnel.png",
-        }
-    }
-
     /// 获取路线段的基础连接方向（0°旋转时的方向）
     /// 返回相对偏移量：(dx, dy)
     pub fn get_base_connection_offsets(&self) -> Vec<(i32, i32)> {
@@ -187,6 +179,7 @@ pub enum StationType {
     BusStop,
     TransferHub,
     Terminal,
+    Depot, // 车场：手动车队模式下购买的公交车停在这里
 }
 
 // 乘客颜色
@@ -201,14 +194,8 @@ pub enum PassengerColor {
 }
 
 // Bevy 组件
-#[derive(Component)]
-#[allow(dead_code)]
-pub struct GridTile {
-    pub grid_pos: GridPos,
-    pub terrain_type: TerrainType,
-}
-
 #[derive(Component, Clone, Copy)]
+#[require(LevelEntity)]
 pub struct RouteSegment {
     pub grid_pos: GridPos,
     pub segment_type: RouteSegmentType,
@@ -216,11 +203,17 @@ pub struct RouteSegment {
     pub is_active: bool,
 }
 
+/// 升级为快速路线的直线段：公交车通过更快，寻路成本更低
 #[derive(Component)]
+pub struct ExpressSegment;
+
+#[derive(Component)]
+#[require(LevelEntity)]
 #[allow(dead_code)]
 pub struct StationEntity {
     pub station_data: Station,
-    pub current_passengers: u32,
+    pub current_passengers: u32, // 正在候车的乘客数
+    pub upgrades: StationUpgrades,
 }
 
 #[derive(Component)]
@@ -235,6 +228,7 @@ pub struct PassengerEntity {
 
 // 寻路组件
 #[derive(Component)]
+#[require(LevelEntity, AgentLod)]
 pub struct PathfindingAgent {
     pub color: PassengerColor,
     pub origin: String,
@@ -245,30 +239,48 @@ pub struct PathfindingAgent {
     pub patience: f32,
     pub max_patience: f32,
     pub waiting_time: f32,
+    pub via: Option<String>,       // 尚未经过的中转站
+    pub lines_ridden: Vec<String>, // 依次乘坐过的线路 ID
+    pub group_size: u32,           // 同行人数，成组乘客一起上下车、一起到达
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum AgentState {
-    WaitingAtStation,
-    Traveling,
-    Transferring,
-    Arrived,
-    GaveUp,
+impl PathfindingAgent {
+    /// 当前这段行程的目标：先去中转站，再去终点
+    pub fn next_stop(&self) -> &str {
+        self.via.as_deref().unwrap_or(&self.destination)
+    }
+
+    /// 换乘次数：相邻两次乘车的线路不同才算换乘
+    pub fn transfers(&self) -> u32 {
+        self.lines_ridden
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .count() as u32
+    }
 }
 
-// UI 组件
+/// 乘客头顶的中转站图标
 #[derive(Component)]
-pub struct MainMenuUI;
+pub struct ViaIcon;
 
+/// 乘客头顶的耐心条底框，耐心不足时才显示
 #[derive(Component)]
-pub struct GameplayUI;
+pub struct PatienceBar;
 
+/// 耐心条中随耐心缩短的填充部分
 #[derive(Component)]
-pub struct PauseMenuUI;
+pub struct PatienceBarFill;
 
-#[derive(Component)]
-pub struct LevelCompleteUI;
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AgentState {
+    WaitingAtStation,
+    Traveling,
+    Transferring,
+    Arrived,
+    GaveUp,
+}
 
+// UI 组件
 #[derive(Component)]
 #[allow(dead_code)]
 pub struct InventoryUI {
@@ -306,6 +318,7 @@ pub struct DraggableSegment {
 }
 
 #[derive(Component)]
+#[require(LevelEntity)]
 #[allow(dead_code)]
 pub struct SegmentPreview {
     pub segment_type: RouteSegmentType,
@@ -330,6 +343,10 @@ pub struct InventorySlot {
     pub available_count: u32,
 }
 
+/// 库存面板容器（触屏布局下会移到屏幕底部）
+#[derive(Component)]
+pub struct InventoryPanel;
+
 #[derive(Component)]
 pub struct InventoryCountText {
     pub segment_type: RouteSegmentType,
@@ -344,4 +361,5 @@ pub struct ObjectiveTracker {
 
 // 显示旋转提示的UI组件
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
 pub struct RotationHintUI;
@@ -46,9 +46,21 @@ pub const MAX_PASSENGERS_PER_STATION: u32 = 20;
 /// 默认乘客耐心值（秒）
 pub const DEFAULT_PASSENGER_PATIENCE: f32 = 60.0;
 
+/// 同时放弃的乘客超过此人数时关卡失败
+pub const MAX_GAVE_UP_PASSENGERS: u32 = 3;
+
 /// 路线段放置成本数组 [直线, 转弯, T型, 十字, 桥梁, 隧道]
 pub const SEGMENT_PLACEMENT_COST: [u32; 6] = [1, 2, 3, 4, 5, 6];
 
+/// 直线段升级为快速路线的费用
+pub const EXPRESS_UPGRADE_COST: u32 = 3;
+
+/// 公交车在快速路线上的速度倍数
+pub const EXPRESS_SPEED_MULTIPLIER: f32 = 1.75;
+
+/// 高架路线段的费用倍数（相对同类型的地面路线段）
+pub const ELEVATED_COST_MULTIPLIER: u32 = 2;
+
 // ============ 寻路算法常量 ============
 
 /// 寻路算法最大迭代次数
@@ -59,6 +71,9 @@ pub const MAX_PATHFINDING_ITERATIONS: usize = 1000;
 #[allow(dead_code)]
 pub const TRANSFER_COST_MULTIPLIER: f32 = 5.0;
 
+/// 进入快速路线的连接成本（普通公交连接为 1.0）
+pub const EXPRESS_CONNECTION_COST: f32 = 0.5;
+
 /// 步行速度 (像素/秒)
 #[allow(dead_code)]
 pub const WALKING_SPEED: f32 = 50.0;
@@ -79,3 +94,11 @@ pub const BUTTON_HOVER_SCALE: f32 = 1.1;
 
 /// 库存槽位大小（像素）
 pub const INVENTORY_SLOT_SIZE: f32 = 70.0;
+
+/// 等车乘客耐心低于该比例时在头顶显示耐心条
+pub const PASSENGER_PATIENCE_BAR_THRESHOLD: f32 = 0.5;
+
+/// 乘客头顶耐心条的尺寸（像素）和距乘客中心的高度
+pub const PASSENGER_PATIENCE_BAR_WIDTH: f32 = 28.0;
+pub const PASSENGER_PATIENCE_BAR_HEIGHT: f32 = 4.0;
+pub const PASSENGER_PATIENCE_BAR_OFFSET_Y: f32 = 24.0;
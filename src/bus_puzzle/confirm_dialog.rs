@@ -0,0 +1,308 @@
+// src/bus_puzzle/confirm_dialog.rs - 通用确认对话框（退出、重新开始、删除关卡等操作先确认再执行，支持键盘和手柄）
+
+use crate::bus_puzzle::{
+    get_text, ButtonComponent, ButtonType, CurrentLanguage, InputAction, InputMap, Language,
+    LocalizedText, LocalizedTextComponent, UIAssets, CANCEL, CONFIRM_DELETE,
+    CONFIRM_DELETE_BLUEPRINT, CONFIRM_DELETE_LEVEL, CONFIRM_MAIN_MENU, CONFIRM_QUIT,
+    CONFIRM_RESTART_LEVEL, MAIN_MENU, QUIT_CONFIRM, RESTART_LEVEL,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// 需要确认的操作，确认后由各自的模块通过 DialogConfirmedEvent 执行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    QuitGame,
+    RestartLevel,
+    ReturnToMainMenu,
+    DeleteCustomLevel(usize), // 自定义关卡列表中的索引
+    DeleteBlueprint(usize),   // 蓝图库中的索引
+}
+
+impl ConfirmAction {
+    fn message(self) -> &'static LocalizedText {
+        match self {
+            ConfirmAction::QuitGame => &QUIT_CONFIRM,
+            ConfirmAction::RestartLevel => &CONFIRM_RESTART_LEVEL,
+            ConfirmAction::ReturnToMainMenu => &CONFIRM_MAIN_MENU,
+            ConfirmAction::DeleteCustomLevel(_) => &CONFIRM_DELETE_LEVEL,
+            ConfirmAction::DeleteBlueprint(_) => &CONFIRM_DELETE_BLUEPRINT,
+        }
+    }
+
+    fn confirm_label(self) -> &'static LocalizedText {
+        match self {
+            ConfirmAction::QuitGame => &CONFIRM_QUIT,
+            ConfirmAction::RestartLevel => &RESTART_LEVEL,
+            ConfirmAction::ReturnToMainMenu => &MAIN_MENU,
+            ConfirmAction::DeleteCustomLevel(_) | ConfirmAction::DeleteBlueprint(_) => {
+                &CONFIRM_DELETE
+            }
+        }
+    }
+}
+
+/// 请求弹出确认框；已有确认框时替换为新的请求
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ConfirmRequestEvent(pub ConfirmAction);
+
+/// 玩家在确认框中选择了确认
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DialogConfirmedEvent(pub ConfirmAction);
+
+/// 确认框根节点；不随状态切换自动销毁，由确认或取消关闭
+#[derive(Component)]
+pub struct ConfirmDialogUI {
+    pub action: ConfirmAction,
+    focus_confirm: bool, // 默认聚焦“取消”，误按回车不会执行危险操作
+}
+
+/// 确认框中的按钮，用于绘制键盘焦点
+#[derive(Component)]
+pub struct ConfirmDialogButton {
+    confirm: bool,
+}
+
+// ============ 确认框插件 ============
+
+pub struct ConfirmDialogPlugin;
+
+impl Plugin for ConfirmDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ConfirmRequestEvent>()
+            .add_event::<DialogConfirmedEvent>()
+            .add_systems(
+                Update,
+                (
+                    spawn_requested_dialogs,
+                    handle_confirm_dialog,
+                    update_dialog_focus,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn spawn_requested_dialogs(
+    mut commands: Commands,
+    mut requests: EventReader<ConfirmRequestEvent>,
+    dialogs: Query<Entity, With<ConfirmDialogUI>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let Some(ConfirmRequestEvent(action)) = requests.read().last().copied() else {
+        return;
+    };
+
+    for entity in dialogs.iter() {
+        commands.entity(entity).despawn();
+    }
+    spawn_confirm_dialog(&mut commands, &ui_assets, current_language.language, action);
+    info!("❓ 等待确认: {:?}", action);
+}
+
+fn spawn_confirm_dialog(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    language: Language,
+    action: ConfirmAction,
+) {
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                top: Px(0.0),
+                left: Px(0.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            ZIndex(5000), // 在所有菜单之上
+            ConfirmDialogUI {
+                action,
+                focus_confirm: false,
+            },
+            Name::new("Confirm Dialog"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(380.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(20.0),
+                        padding: UiRect::all(Px(30.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.3)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.6, 0.2, 0.2)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(get_text(action.message(), language)),
+                        LocalizedTextComponent::new(action.message()),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Px(20.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_dialog_button(
+                                parent,
+                                ui_assets,
+                                action.confirm_label(),
+                                language,
+                                true,
+                                Color::srgb(0.6, 0.2, 0.2),
+                            );
+                            spawn_dialog_button(
+                                parent,
+                                ui_assets,
+                                &CANCEL,
+                                language,
+                                false,
+                                Color::srgb(0.3, 0.3, 0.5),
+                            );
+                        });
+                });
+        });
+}
+
+fn spawn_dialog_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text_key: &'static LocalizedText,
+    language: Language,
+    confirm: bool,
+    color: Color,
+) {
+    let button_type = if confirm {
+        ButtonType::ConfirmDialog
+    } else {
+        ButtonType::CancelDialog
+    };
+
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(150.0),
+                height: Px(45.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(color),
+            Outline::new(Px(3.0), Px(2.0), Color::NONE),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            ConfirmDialogButton { confirm },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(text_key, language)),
+                LocalizedTextComponent::new(text_key),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+// ============ 确认框交互 ============
+
+/// 鼠标点击按钮，或用键盘（←/→/Tab 切换，回车/空格确定，暂停键取消）
+/// 和手柄（十字键切换，A 确定，B 取消）操作确认框
+fn handle_confirm_dialog(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    gamepads: Query<&Gamepad>,
+    mut dialogs: Query<(Entity, &mut ConfirmDialogUI)>,
+    mut confirmed_events: EventWriter<DialogConfirmedEvent>,
+) {
+    let Ok((entity, mut dialog)) = dialogs.single_mut() else {
+        return;
+    };
+
+    let gamepad_pressed =
+        |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+
+    let mut decision = button_query
+        .iter()
+        .filter(|(interaction, _)| matches!(**interaction, Interaction::Pressed))
+        .find_map(|(_, button)| match button.button_type {
+            ButtonType::ConfirmDialog => Some(true),
+            ButtonType::CancelDialog => Some(false),
+            _ => None,
+        });
+
+    if keyboard_input.any_just_pressed([KeyCode::ArrowLeft, KeyCode::ArrowRight, KeyCode::Tab])
+        || gamepad_pressed(GamepadButton::DPadLeft)
+        || gamepad_pressed(GamepadButton::DPadRight)
+    {
+        dialog.focus_confirm = !dialog.focus_confirm;
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space])
+        || gamepad_pressed(GamepadButton::South)
+    {
+        decision = decision.or(Some(dialog.focus_confirm));
+    }
+    if input_map.just_pressed(&keyboard_input, InputAction::Pause)
+        || gamepad_pressed(GamepadButton::East)
+    {
+        decision = decision.or(Some(false));
+    }
+
+    let Some(confirmed) = decision else {
+        return;
+    };
+    commands.entity(entity).despawn();
+    if confirmed {
+        info!("✔️ 已确认: {:?}", dialog.action);
+        confirmed_events.write(DialogConfirmedEvent(dialog.action));
+    } else {
+        info!("✖️ 已取消: {:?}", dialog.action);
+    }
+}
+
+fn update_dialog_focus(
+    dialogs: Query<&ConfirmDialogUI, Changed<ConfirmDialogUI>>,
+    mut buttons: Query<(&ConfirmDialogButton, &mut Outline)>,
+) {
+    let Ok(dialog) = dialogs.single() else {
+        return;
+    };
+    for (button, mut outline) in buttons.iter_mut() {
+        outline.color = if button.confirm == dialog.focus_confirm {
+            FOCUS_OUTLINE_COLOR
+        } else {
+            Color::NONE
+        };
+    }
+}
@@ -10,9 +10,10 @@
 // 5. 所有系统（寻路、连接、可视化）使用相同的连接定义
 
 use crate::bus_puzzle::{
-    manhattan_distance, Connection, ConnectionType, GraphNode, GraphNodeType, GridPos,
-    PathfindingGraph, RouteSegment, RouteSegmentType, StationEntity,
+    manhattan_distance, GridPos, PathfindingGraph, RouteSegment, RouteSegmentType, StationEntity,
 };
+#[cfg(feature = "dev")]
+use crate::bus_puzzle::{Connection, ConnectionType, GraphNode, GraphNodeType, NetworkLayer};
 use bevy::prelude::*;
 
 /// 修复后的连接系统 - 正确处理方向性
@@ -24,6 +25,8 @@ impl Plugin for FixedConnectionSystemPlugin {
             Update,
             (
                 debug_connections_with_directions,
+                // F9 在正式版本中是快速读档
+                #[cfg(feature = "dev")]
                 force_rebuild_connections_fixed,
                 visualize_segment_directions,
             ),
@@ -146,6 +149,7 @@ fn debug_connections_with_directions(
 }
 
 /// F9 - 强制重建修复后的连接
+#[cfg(feature = "dev")]
 fn force_rebuild_connections_fixed(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut pathfinding_graph: ResMut<PathfindingGraph>,
@@ -198,6 +202,7 @@ fn visualize_segment_directions(
 struct DirectionVisualization;
 
 /// 修复后的寻路图重建函数
+#[cfg(feature = "dev")]
 pub fn rebuild_pathfinding_graph_fixed(
     pathfinding_graph: &mut PathfindingGraph,
     stations: &Query<&StationEntity>,
@@ -261,6 +266,7 @@ pub fn rebuild_pathfinding_graph_fixed(
 }
 
 /// 考虑方向的路线段连接创建
+#[cfg(feature = "dev")]
 fn create_segment_connections_directional(
     pathfinding_graph: &mut PathfindingGraph,
     route_segments: &Query<&RouteSegment>,
@@ -307,6 +313,7 @@ fn create_segment_connections_directional(
 }
 
 /// 考虑方向的站点连接创建
+#[cfg(feature = "dev")]
 fn create_station_connections_directional(
     pathfinding_graph: &mut PathfindingGraph,
     stations: &Query<&StationEntity>,
@@ -476,6 +483,7 @@ fn get_connection_reason(station_pos: GridPos, segment: &RouteSegment) -> String
 }
 
 /// 创建双向连接
+#[cfg(feature = "dev")]
 fn create_bidirectional_connection(
     pathfinding_graph: &mut PathfindingGraph,
     pos_a: GridPos,
@@ -495,6 +503,7 @@ fn create_bidirectional_connection(
         .or_default()
         .push(Connection {
             to: pos_b,
+            to_layer: NetworkLayer::Ground,
             cost,
             route_id: Some(format!("route_{}_{}", pos_a.x, pos_a.y)),
             connection_type: connection_type.clone(),
@@ -507,6 +516,7 @@ fn create_bidirectional_connection(
         .or_default()
         .push(Connection {
             to: pos_a,
+            to_layer: NetworkLayer::Ground,
             cost,
             route_id: Some(format!("route_{}_{}", pos_b.x, pos_b.y)),
             connection_type,
@@ -0,0 +1,394 @@
+// src/bus_puzzle/contracts.rs - 限时合约（关卡进行中出现的支线任务，完成后加分）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, passenger_color_name, show_contextual_tip, ui_audio::GameplayUI,
+    ui_audio::MainMenuUI, ButtonComponent, ButtonType, CurrentLanguage, GameState, GameStateEnum,
+    Language, PassengerArrivedEvent, PassengerColor, SimClock, TipType, UIAssets, CONTRACTS_PANEL,
+    CONTRACT_COMPLETED, CONTRACT_FAILED, CONTRACT_LINE, CONTRACT_OFFERED, SIDE_CONTRACTS,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// 第一份合约出现的游戏时间
+const FIRST_CONTRACT_TIME: f32 = 15.0;
+
+/// 两份合约之间的间隔
+const CONTRACT_INTERVAL: f32 = 30.0;
+
+/// 同时进行的合约上限
+const MAX_ACTIVE_CONTRACTS: usize = 2;
+
+/// 每位乘客给予的完成时限（秒）和奖励分
+const SECONDS_PER_PASSENGER: f32 = 15.0;
+const POINTS_PER_PASSENGER: u32 = 30;
+
+/// 结束的合约在面板上保留多久
+const FINISHED_CONTRACT_LINGER: f32 = 4.0;
+
+// ============ 合约数据 ============
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ContractState {
+    Active,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Contract {
+    pub color: PassengerColor,
+    pub required: u32,
+    pub delivered: u32,
+    pub time_left: f32,
+    pub reward: u32,
+    pub state: ContractState,
+    linger: f32, // 结束后的剩余显示时间
+}
+
+impl Contract {
+    fn new(color: PassengerColor, required: u32) -> Self {
+        Self {
+            color,
+            required,
+            delivered: 0,
+            time_left: required as f32 * SECONDS_PER_PASSENGER + 10.0,
+            reward: required * POINTS_PER_PASSENGER,
+            state: ContractState::Active,
+            linger: FINISHED_CONTRACT_LINGER,
+        }
+    }
+}
+
+/// 开启后关卡中途会出现支线合约，奖励计入总分（不影响主目标）；自动存档时整体保存
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct ContractBoard {
+    pub enabled: bool,
+    pub contracts: Vec<Contract>,
+    pub bonus_points: u32, // 本关完成合约获得的加分
+    next_offer_time: f32,
+}
+
+impl ContractBoard {
+    pub fn reset_level(&mut self) {
+        self.contracts.clear();
+        self.bonus_points = 0;
+        self.next_offer_time = FIRST_CONTRACT_TIME;
+    }
+
+    fn active_count(&self) -> usize {
+        self.contracts
+            .iter()
+            .filter(|contract| contract.state == ContractState::Active)
+            .count()
+    }
+}
+
+#[derive(Component)]
+pub struct ContractsToggleText;
+
+#[derive(Component)]
+pub struct ContractsPanelText;
+
+// ============ 限时合约插件 ============
+
+pub struct ContractsPlugin;
+
+impl Plugin for ContractsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ContractBoard>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_contracts_toggle)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_contracts)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_contracts)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_contracts_panel)
+            .add_systems(
+                Update,
+                (handle_contracts_buttons, update_contracts_toggle_text)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(
+                Update,
+                (offer_contracts, track_contracts, update_contracts_panel)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+// ============ 主菜单开关 ============
+
+fn setup_contracts_toggle(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    board: Res<ContractBoard>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(140.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::ToggleContracts,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            MainMenuUI,
+            Name::new("Side Contracts Toggle"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(contracts_toggle_label(&board, current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                ContractsToggleText,
+            ));
+        });
+}
+
+fn contracts_toggle_label(board: &ContractBoard, language: Language) -> String {
+    let check = if board.enabled { "[x]" } else { "[ ]" };
+    format!("{} {}", check, get_text(&SIDE_CONTRACTS, language))
+}
+
+fn handle_contracts_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut board: ResMut<ContractBoard>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::ToggleContracts
+        {
+            board.enabled = !board.enabled;
+            info!("📜 限时合约: {}", board.enabled);
+        }
+    }
+}
+
+fn update_contracts_toggle_text(
+    board: Res<ContractBoard>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<&mut Text, With<ContractsToggleText>>,
+) {
+    if !board.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in toggle_texts.iter_mut() {
+        *text = Text::new(contracts_toggle_label(&board, current_language.language));
+    }
+}
+
+// ============ 合约发放与结算 ============
+
+pub fn reset_contracts(mut board: ResMut<ContractBoard>) {
+    board.reset_level();
+}
+
+/// 按关卡中出现的乘客颜色发放合约
+fn offer_contracts(
+    mut commands: Commands,
+    mut board: ResMut<ContractBoard>,
+    game_state: Res<GameState>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !board.enabled || game_state.game_time < board.next_offer_time {
+        return;
+    }
+    board.next_offer_time = game_state.game_time + CONTRACT_INTERVAL;
+
+    if board.active_count() >= MAX_ACTIVE_CONTRACTS {
+        return;
+    }
+
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    // 只选择还会继续生成乘客的需求，要求人数不超过剩余人数，保证合约有机会完成
+    let candidates: Vec<(PassengerColor, u32)> = level_data
+        .passenger_demands
+        .iter()
+        .map(|demand| {
+            let remaining = demand
+                .total_count
+                .map_or(u32::MAX, |total| total.saturating_sub(demand.spawned_count));
+            (demand.color, remaining)
+        })
+        .filter(|(_, remaining)| *remaining > 0)
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let (color, remaining) = candidates[rng.gen_range(0..candidates.len())];
+    let contract = Contract::new(color, rng.gen_range(2..=4).min(remaining));
+
+    let language = current_language.language;
+    let offer = get_text_with_args(
+        &CONTRACT_OFFERED,
+        language,
+        &[&contract_line(&contract, language)],
+    );
+    show_contextual_tip(&mut commands, &ui_assets, &offer, TipType::Strategy, 5.0);
+    info!("📜 新合约: {}", offer);
+
+    board.contracts.push(contract);
+}
+
+fn track_contracts(
+    mut commands: Commands,
+    sim_clock: Res<SimClock>,
+    mut board: ResMut<ContractBoard>,
+    mut arrived_events: EventReader<PassengerArrivedEvent>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !board.enabled {
+        arrived_events.clear();
+        return;
+    }
+
+    let dt = sim_clock.delta_secs();
+
+    for event in arrived_events.read() {
+        // 同一位乘客只计入最早的一份同色合约
+        if let Some(contract) = board.contracts.iter_mut().find(|contract| {
+            contract.state == ContractState::Active && contract.color == event.color
+        }) {
+            contract.delivered += 1;
+        }
+    }
+
+    let language = current_language.language;
+    let mut earned = 0;
+    for contract in board.contracts.iter_mut() {
+        match contract.state {
+            ContractState::Active => {
+                contract.time_left -= dt;
+                let line = contract_line(contract, language);
+                if contract.delivered >= contract.required {
+                    contract.state = ContractState::Completed;
+                    earned += contract.reward;
+                    let message = get_text_with_args(
+                        &CONTRACT_COMPLETED,
+                        language,
+                        &[&line, &contract.reward.to_string()],
+                    );
+                    show_contextual_tip(
+                        &mut commands,
+                        &ui_assets,
+                        &message,
+                        TipType::LevelGoal,
+                        4.0,
+                    );
+                    info!("✅ 合约完成: {}", message);
+                } else if contract.time_left <= 0.0 {
+                    contract.state = ContractState::Failed;
+                    let message = get_text_with_args(&CONTRACT_FAILED, language, &[&line]);
+                    show_contextual_tip(&mut commands, &ui_assets, &message, TipType::Warning, 4.0);
+                    info!("⌛ 合约失败: {}", message);
+                }
+            }
+            ContractState::Completed | ContractState::Failed => contract.linger -= dt,
+        }
+    }
+
+    board.bonus_points += earned;
+    board.contracts.retain(|contract| contract.linger > 0.0);
+}
+
+// ============ 合约面板 ============
+
+fn contract_line(contract: &Contract, language: Language) -> String {
+    get_text_with_args(
+        &CONTRACT_LINE,
+        language,
+        &[
+            &contract.required.to_string(),
+            &get_text(passenger_color_name(contract.color), language),
+            &format!("{:.0}", contract.time_left.max(0.0)),
+            &contract.reward.to_string(),
+        ],
+    )
+}
+
+fn contracts_panel_label(board: &ContractBoard, language: Language) -> String {
+    let mut lines = vec![get_text(&CONTRACTS_PANEL, language)];
+    for contract in &board.contracts {
+        let status = match contract.state {
+            ContractState::Active => format!("{}/{}", contract.delivered, contract.required),
+            ContractState::Completed => "✔".to_string(),
+            ContractState::Failed => "✘".to_string(),
+        };
+        lines.push(format!("{} {}", status, contract_line(contract, language)));
+    }
+    lines.join("\n")
+}
+
+fn spawn_contracts_panel(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    board: Res<ContractBoard>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !board.enabled {
+        return;
+    }
+
+    commands.spawn((
+        Text::new(contracts_panel_label(&board, current_language.language)),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.8, 0.5)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Percent(50.0),
+            top: Px(100.0),
+            margin: UiRect::left(Px(-150.0)),
+            padding: UiRect::all(Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        GameplayUI,
+        ContractsPanelText,
+        Name::new("Contracts Panel"),
+    ));
+}
+
+fn update_contracts_panel(
+    board: Res<ContractBoard>,
+    current_language: Res<CurrentLanguage>,
+    mut panel_texts: Query<&mut Text, With<ContractsPanelText>>,
+) {
+    if !board.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in panel_texts.iter_mut() {
+        *text = Text::new(contracts_panel_label(&board, current_language.language));
+    }
+}
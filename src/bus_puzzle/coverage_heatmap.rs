@@ -0,0 +1,146 @@
+// src/bus_puzzle/coverage_heatmap.rs - 覆盖范围热力图（按到最近已连通站点/路线的距离给格子着色）
+
+use crate::bus_puzzle::{
+    get_text, manhattan_distance, shortcut_modifier_pressed, show_contextual_tip, CurrentLanguage,
+    GameState, GameStateEnum, GridPos, LevelEntity, LevelManager, PathfindingGraph, TipType,
+    UIAssets, COVERAGE_HEATMAP_HINT, TERRAIN_Z,
+};
+use bevy::{platform::collections::HashSet, prelude::*};
+
+/// 切换热力图的按键（Coverage）
+const COVERAGE_TOGGLE_KEY: KeyCode = KeyCode::KeyC;
+
+/// 距离分档：不超过该距离的格子视为覆盖良好 / 勉强覆盖，更远的为服务空白
+const WELL_SERVED_DISTANCE: u32 = 1;
+const PARTLY_SERVED_DISTANCE: u32 = 3;
+
+const WELL_SERVED_COLOR: Color = Color::srgba(0.2, 0.9, 0.3, 0.35);
+const PARTLY_SERVED_COLOR: Color = Color::srgba(0.95, 0.85, 0.2, 0.35);
+const UNDERSERVED_COLOR: Color = Color::srgba(0.95, 0.25, 0.2, 0.35);
+
+/// 热力图开关与上次着色时的已连通格子
+#[derive(Resource, Default)]
+pub struct CoverageHeatmap {
+    pub visible: bool,
+    served: HashSet<GridPos>,
+}
+
+/// 热力图格子
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct CoverageHeatmapCell;
+
+// ============ 覆盖热力图插件 ============
+
+pub struct CoverageHeatmapPlugin;
+
+impl Plugin for CoverageHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CoverageHeatmap>().add_systems(
+            Update,
+            (toggle_coverage_heatmap, update_coverage_heatmap)
+                .chain()
+                .run_if(in_state(GameStateEnum::Playing)),
+        );
+    }
+}
+
+fn toggle_coverage_heatmap(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut heatmap: ResMut<CoverageHeatmap>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    // Ctrl+C 用于复制路线段
+    if !keyboard_input.just_pressed(COVERAGE_TOGGLE_KEY)
+        || shortcut_modifier_pressed(&keyboard_input)
+    {
+        return;
+    }
+
+    heatmap.visible = !heatmap.visible;
+    if heatmap.visible {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(&COVERAGE_HEATMAP_HINT, current_language.language),
+            TipType::Strategy,
+            4.0,
+        );
+    }
+    info!("🗺️ 覆盖热力图: {}", heatmap.visible);
+}
+
+/// 已连通的格子：有连接的路线段，以及接入路线的站点
+fn served_positions(pathfinding_graph: &PathfindingGraph) -> HashSet<GridPos> {
+    pathfinding_graph
+        .connections
+        .iter()
+        .filter(|(_, connections)| !connections.is_empty())
+        .map(|(pos, _)| *pos)
+        .collect()
+}
+
+/// 寻路图每帧重建，这里只在已连通格子变化时才重新着色
+fn update_coverage_heatmap(
+    mut commands: Commands,
+    mut heatmap: ResMut<CoverageHeatmap>,
+    pathfinding_graph: Res<PathfindingGraph>,
+    game_state: Res<GameState>,
+    level_manager: Res<LevelManager>,
+    cells: Query<Entity, With<CoverageHeatmapCell>>,
+) {
+    if !heatmap.visible {
+        if !cells.is_empty() {
+            for entity in cells.iter() {
+                commands.entity(entity).despawn();
+            }
+            heatmap.served.clear();
+        }
+        return;
+    }
+
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    let served = served_positions(&pathfinding_graph);
+    // 关卡重新加载会清掉格子，此时即使连通情况相同也要重建
+    if served == heatmap.served && !cells.is_empty() {
+        return;
+    }
+
+    for entity in cells.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let (width, height) = level_data.grid_size;
+    let tile_size = level_manager.tile_size;
+    for x in 0..width as i32 {
+        for y in 0..height as i32 {
+            let grid_pos = GridPos::new(x, y);
+            let distance = served
+                .iter()
+                .map(|served_pos| manhattan_distance(grid_pos, *served_pos))
+                .min();
+
+            let color = match distance {
+                Some(distance) if distance <= WELL_SERVED_DISTANCE => WELL_SERVED_COLOR,
+                Some(distance) if distance <= PARTLY_SERVED_DISTANCE => PARTLY_SERVED_COLOR,
+                _ => UNDERSERVED_COLOR,
+            };
+
+            let mut world_pos = grid_pos.to_world_pos(tile_size, width, height);
+            world_pos.z = TERRAIN_Z + 0.5;
+            commands.spawn((
+                Sprite::from_color(color, Vec2::splat(tile_size)),
+                Transform::from_translation(world_pos),
+                CoverageHeatmapCell,
+                Name::new("Coverage Heatmap Cell"),
+            ));
+        }
+    }
+
+    heatmap.served = served;
+}
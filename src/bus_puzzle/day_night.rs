@@ -0,0 +1,210 @@
+// src/bus_puzzle/day_night.rs - 昼夜循环（关卡内时钟推进，乘客按早晚高峰曲线生成，夜晚世界变暗）
+
+use crate::bus_puzzle::{
+    get_text, ui_audio::GameplayUI, CurrentLanguage, GameState, GameStateEnum, LevelEntity,
+    LevelManager, UIAssets, RUSH_HOUR, STATION_Z,
+};
+use bevy::prelude::{Val::Px, *};
+use serde::{Deserialize, Serialize};
+
+/// 早晚高峰的中心时刻（小时）
+const MORNING_PEAK_HOUR: f32 = 8.0;
+const EVENING_PEAK_HOUR: f32 = 18.0;
+
+/// 高峰的持续范围（小时，高峰曲线的标准差）
+const PEAK_WIDTH_HOURS: f32 = 1.5;
+
+/// 日出、日落时刻，以及天色渐变持续的小时数
+const SUNRISE_HOUR: f32 = 6.0;
+const SUNSET_HOUR: f32 = 19.0;
+const TWILIGHT_HOURS: f32 = 1.5;
+
+/// 夜色遮罩：画在站点之上、乘客和公交车之下，保证夜里也能看清车辆
+const NIGHT_OVERLAY_Z: f32 = STATION_Z + 0.5;
+const NIGHT_COLOR: Color = Color::srgb(0.05, 0.08, 0.25);
+const MAX_NIGHT_ALPHA: f32 = 0.45;
+
+/// 时钟显示的最小刻度（分钟），避免每帧改写文字
+const CLOCK_STEP_MINUTES: u32 = 15;
+
+// ============ 昼夜数据 ============
+
+/// 关卡的昼夜循环：一整天对应多少秒游戏时间，以及开局时刻
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeCycle {
+    pub day_length_secs: f32,
+    pub start_hour: f32,
+}
+
+impl TimeCycle {
+    /// 游戏时间对应的时刻（0-24 小时）
+    pub fn hour_at(&self, game_time: f32) -> f32 {
+        (self.start_hour + game_time / self.day_length_secs.max(1.0) * 24.0).rem_euclid(24.0)
+    }
+}
+
+/// 乘客需求的早晚高峰曲线：高峰时刻的生成倍率和其余时段的基础倍率
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DemandCurve {
+    pub morning_peak: f32,
+    pub evening_peak: f32,
+    pub off_peak: f32,
+}
+
+impl DemandCurve {
+    /// 某一时刻的生成倍率：基础倍率上叠加两个以高峰时刻为中心的钟形曲线
+    pub fn multiplier(&self, hour: f32) -> f32 {
+        self.off_peak
+            + (self.morning_peak - self.off_peak) * peak_weight(hour, MORNING_PEAK_HOUR)
+            + (self.evening_peak - self.off_peak) * peak_weight(hour, EVENING_PEAK_HOUR)
+    }
+}
+
+/// 离高峰时刻越近越接近 1
+fn peak_weight(hour: f32, peak_hour: f32) -> f32 {
+    let distance = (hour - peak_hour) / PEAK_WIDTH_HOURS;
+    (-0.5 * distance * distance).exp()
+}
+
+pub fn is_rush_hour(hour: f32) -> bool {
+    [MORNING_PEAK_HOUR, EVENING_PEAK_HOUR]
+        .iter()
+        .any(|peak| (hour - peak).abs() <= PEAK_WIDTH_HOURS)
+}
+
+/// 夜色深浅：白天为 0，深夜为 1，日出日落前后渐变
+fn darkness_at(hour: f32) -> f32 {
+    let dawn = ((SUNRISE_HOUR + TWILIGHT_HOURS - hour) / TWILIGHT_HOURS).clamp(0.0, 1.0);
+    let dusk = ((hour - SUNSET_HOUR) / TWILIGHT_HOURS).clamp(0.0, 1.0);
+    dawn.max(dusk)
+}
+
+/// 夜色遮罩
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct NightOverlay;
+
+/// HUD 中的时钟
+#[derive(Component)]
+pub struct DayClockText;
+
+// ============ 昼夜循环插件 ============
+
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameStateEnum::Playing), spawn_day_clock)
+            .add_systems(
+                Update,
+                (update_night_overlay, update_day_clock).run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn spawn_day_clock(mut commands: Commands, game_state: Res<GameState>, ui_assets: Res<UIAssets>) {
+    let has_cycle = game_state
+        .current_level
+        .as_ref()
+        .is_some_and(|level_data| level_data.time_cycle.is_some());
+    if !has_cycle {
+        return;
+    }
+
+    // 放在顶部状态栏里、暂停按钮左侧
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.9, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Px(140.0),
+            top: Px(28.0),
+            ..default()
+        },
+        ZIndex(1001),
+        GameplayUI,
+        DayClockText,
+        Name::new("Day Clock"),
+    ));
+}
+
+fn update_day_clock(
+    game_state: Res<GameState>,
+    current_language: Res<CurrentLanguage>,
+    mut clocks: Query<&mut Text, With<DayClockText>>,
+) {
+    let Some(cycle) = game_state
+        .current_level
+        .as_ref()
+        .and_then(|level_data| level_data.time_cycle)
+    else {
+        return;
+    };
+
+    let hour = cycle.hour_at(game_state.game_time);
+    let minutes = (hour * 60.0) as u32 / CLOCK_STEP_MINUTES * CLOCK_STEP_MINUTES;
+    let icon = if darkness_at(hour) > 0.5 {
+        "🌙"
+    } else {
+        "☀"
+    };
+    let mut label = format!("{} {:02}:{:02}", icon, minutes / 60, minutes % 60);
+    if is_rush_hour(hour) {
+        label = format!(
+            "{}  {}",
+            label,
+            get_text(&RUSH_HOUR, current_language.language)
+        );
+    }
+
+    for mut text in clocks.iter_mut() {
+        text.set_if_neq(Text::new(label.clone()));
+    }
+}
+
+/// 有昼夜循环的关卡铺一层覆盖整张地图的夜色遮罩，按时刻调整透明度
+fn update_night_overlay(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    level_manager: Res<LevelManager>,
+    mut overlays: Query<&mut Sprite, With<NightOverlay>>,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    let Some(cycle) = level_data.time_cycle else {
+        return;
+    };
+
+    let color =
+        NIGHT_COLOR.with_alpha(darkness_at(cycle.hour_at(game_state.game_time)) * MAX_NIGHT_ALPHA);
+
+    if overlays.is_empty() {
+        let (grid_width, grid_height) = level_data.grid_size;
+        commands.spawn((
+            Name::new("Night Overlay"),
+            Sprite {
+                color,
+                custom_size: Some(Vec2::new(
+                    grid_width as f32 * level_manager.tile_size,
+                    grid_height as f32 * level_manager.tile_size,
+                )),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, NIGHT_OVERLAY_Z),
+            NightOverlay,
+        ));
+        return;
+    }
+
+    for mut sprite in overlays.iter_mut() {
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
@@ -20,7 +20,8 @@ impl Plugin for DebugInfoPlugin {
                 // debug_state_switch,
                 // debug_level_reset,       // 新增调试功能
                 // debug_level_status,      // 新增关卡状态调试
-                debug_score_calculation, // 新增分数计算调试
+                #[cfg(feature = "dev")]
+                debug_score_calculation, // 新增分数计算调试（F9 在正式版本中是快速读档）
                 debug_trigger_game_over, // 新增：测试游戏失败菜单
             ),
         );
@@ -122,7 +123,7 @@ fn debug_info_system(
         info!("F8: 连接系统调试 🔗");
         info!("F9: 分数计算调试 📊");
         info!("F10: 鼠标坐标转换调试 🖱️");
-        info!("F11: 寻路图状态调试 🗺️");
+        info!("Shift+F11: 寻路图状态调试 🗺️");
         info!("F12: 测试游戏失败菜单");
         info!("💡 Tips系统: 根据关卡自动显示相关提示和策略建议");
     }
@@ -284,6 +285,7 @@ fn debug_level_status(
 }
 
 /// F9 - 调试分数计算
+#[cfg(feature = "dev")]
 fn debug_score_calculation(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     game_state: Res<GameState>,
@@ -312,32 +314,17 @@ fn debug_score_calculation(
             );
 
             // 速度奖励
-            let speed_bonus = if game_state.game_time < 60.0 {
-                level_data.scoring.speed_bonus
-            } else {
-                0
-            };
+            let speed_bonus = level_data.scoring.speed_bonus_for(game_state.game_time);
             info!(
-                "速度奖励: {} (条件: <60秒, 当前: {:.1}秒)",
-                speed_bonus, game_state.game_time
+                "速度奖励: {} (条件: <{:.0}秒, 当前: {:.1}秒)",
+                speed_bonus, level_data.scoring.speed_time_limit, game_state.game_time
             );
 
             // 成本奖励
-            let cost_threshold = match level_data.id.as_str() {
-                "tutorial_01" => 10,
-                "level_02_transfer" => 15,
-                "level_03_multiple_routes" => 25,
-                "level_04_time_pressure" => 20,
-                _ => 15,
-            };
-            let cost_bonus = if game_state.total_cost <= cost_threshold {
-                level_data.scoring.cost_bonus
-            } else {
-                0
-            };
+            let cost_bonus = level_data.scoring.cost_bonus_for(game_state.total_cost);
             info!(
                 "成本奖励: {} (条件: ≤{}, 当前: {})",
-                cost_bonus, cost_threshold, game_state.total_cost
+                cost_bonus, level_data.scoring.cost_threshold, game_state.total_cost
             );
 
             // 总分
@@ -346,6 +333,12 @@ fn debug_score_calculation(
                 "计算总分: {} + {} + {} + {} = {}",
                 base_points, efficiency_bonus, speed_bonus, cost_bonus, total_calculated
             );
+            info!(
+                "连击: {}, 合约: {}, 倍率: ×{:.2}",
+                game_state.score.combo_bonus,
+                game_state.score.contract_bonus,
+                game_state.score.multiplier
+            );
             info!("当前实际总分: {}", game_state.score.total_score);
 
             // 乘客统计
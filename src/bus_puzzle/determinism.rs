@@ -0,0 +1,185 @@
+// src/bus_puzzle/determinism.rs - 确定性回归测试（`cargo test determinism`）
+//
+// 无窗口把同一段录像以固定步长回放两遍，比较事件流和最终统计，
+// 防止在模拟流程中混入 thread_rng、系统时间或 HashMap 遍历顺序之类的不确定来源。
+
+use crate::bus_puzzle::{
+    BusPuzzleGamePlugin, GameState, GameStateEnum, PassengerArrivedEvent, PassengerGaveUpEvent,
+    PassengerSpawnedEvent, Replay, ReplayPlayback, SegmentPlacedEvent,
+};
+use bevy::{
+    asset::AssetMetaCheck,
+    prelude::*,
+    render::{settings::WgpuSettings, RenderPlugin},
+    time::TimeUpdateStrategy,
+    window::ExitCondition,
+    winit::{WinitPlugin, WinitSettings},
+};
+use std::time::Duration;
+
+/// 每帧推进的固定时长
+const FRAME_SECS: f64 = 1.0 / 30.0;
+
+/// 开场动画结束后回放的帧数（约 90 秒游戏时间）
+const REPLAY_FRAMES: usize = 2700;
+
+/// 回放期间按帧记录的事件
+#[derive(Resource, Default)]
+struct EventLog(Vec<String>);
+
+/// 教程关卡：沿第 5 行铺一条 A -> B 的路线，再生成三名红色乘客
+fn tutorial_replay() -> Replay {
+    let mut entries = vec![place(1, 5, "Curve", 90)];
+    entries.extend((2..=7).map(|x| place(x, 5, "Straight", 0)));
+    entries.push(place(8, 5, "Curve", 0));
+    entries.extend([0.5, 1.5, 2.5].map(|time| {
+        serde_json::json!({
+            "time": time,
+            "action": { "SpawnPassenger": {
+                "color": "Red",
+                "origin": "Station A",
+                "destination": "Station B",
+                "group_size": 1,
+            } },
+        })
+    }));
+
+    serde_json::from_value(serde_json::json!({
+        "level_id": "tutorial_01",
+        "mirrored": false,
+        "event_seed": null,
+        "duration": 90.0,
+        "entries": entries,
+    }))
+    .expect("录像格式应当有效")
+}
+
+fn place(x: i32, y: i32, segment_type: &str, rotation: u32) -> serde_json::Value {
+    serde_json::json!({
+        "time": 0.0,
+        "action": { "Place": {
+            "position": { "x": x, "y": y },
+            "segment_type": segment_type,
+            "rotation": rotation,
+        } },
+    })
+}
+
+fn log_events(
+    mut log: ResMut<EventLog>,
+    game_state: Res<GameState>,
+    mut placed_events: EventReader<SegmentPlacedEvent>,
+    mut spawned_events: EventReader<PassengerSpawnedEvent>,
+    mut arrived_events: EventReader<PassengerArrivedEvent>,
+    mut gave_up_events: EventReader<PassengerGaveUpEvent>,
+) {
+    let time = game_state.game_time;
+    for event in placed_events.read() {
+        log.0.push(format!(
+            "{:.3} 放置 {:?} {:?} {}",
+            time, event.position, event.segment_type, event.rotation
+        ));
+    }
+    for event in spawned_events.read() {
+        log.0.push(format!(
+            "{:.3} 生成 {:?} {} -> {} x{}",
+            time, event.color, event.origin, event.destination, event.group_size
+        ));
+    }
+    for event in arrived_events.read() {
+        log.0.push(format!(
+            "{:.3} 到达 {:?} 用时 {:.3} 换乘 {}",
+            time, event.color, event.travel_time, event.transfers
+        ));
+    }
+    for event in gave_up_events.read() {
+        log.0.push(format!(
+            "{:.3} 放弃 {:?} {} -> {}",
+            time, event.color, event.origin, event.destination
+        ));
+    }
+}
+
+/// 无窗口、无渲染后端的完整游戏，时间按固定步长推进
+fn headless_app(replay: Replay) -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(AssetPlugin {
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            // 保留主窗口实体供查询，没有 winit 不会真正打开窗口
+            .set(WindowPlugin {
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+                ..default()
+            })
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+                ..default()
+            })
+            .disable::<WinitPlugin>(),
+    )
+    // 帧率限制等系统依赖 winit 的设置资源
+    .init_resource::<WinitSettings>()
+    .add_plugins(BusPuzzleGamePlugin)
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        FRAME_SECS,
+    )))
+    .init_resource::<EventLog>()
+    .add_systems(Startup, |mut commands: Commands| {
+        commands.spawn(Camera2d);
+    })
+    .add_systems(Last, log_events.run_if(in_state(GameStateEnum::Replay)));
+
+    app.world_mut()
+        .resource_mut::<ReplayPlayback>()
+        .queue(replay);
+    app
+}
+
+/// 回放一遍，返回事件流和最终统计
+fn run_replay(replay: Replay) -> (Vec<String>, String) {
+    let mut app = headless_app(replay);
+
+    // 开场动画结束后进入主菜单，随即开始回放
+    while *app.world().resource::<State<GameStateEnum>>().get() != GameStateEnum::Replay {
+        app.update();
+    }
+    for _ in 0..REPLAY_FRAMES {
+        app.update();
+    }
+
+    let game_state = app.world().resource::<GameState>();
+    let stats = &game_state.passenger_stats;
+    let summary = format!(
+        "时间 {:.3} 花费 {} 生成 {} 到达 {} 放弃 {} 等待 {:.3}",
+        game_state.game_time,
+        game_state.total_cost,
+        stats.total_spawned,
+        stats.total_arrived,
+        stats.total_gave_up,
+        stats.total_wait_time
+    );
+    let log = std::mem::take(&mut app.world_mut().resource_mut::<EventLog>().0);
+    (log, summary)
+}
+
+#[test]
+fn replay_runs_identically_twice() {
+    let (first_log, first_summary) = run_replay(tutorial_replay());
+    let (second_log, second_summary) = run_replay(tutorial_replay());
+
+    assert!(
+        first_log.iter().any(|line| line.contains("到达")),
+        "录像应当让乘客到达终点: {:#?}",
+        first_log
+    );
+    assert_eq!(first_log, second_log);
+    assert_eq!(first_summary, second_summary);
+}
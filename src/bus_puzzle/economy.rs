@@ -0,0 +1,342 @@
+// src/bus_puzzle/economy.rs - 经营模式（车费收入购买路线段，路线段需要养护费，资金耗尽即破产）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ui_audio::GameplayUI, ui_audio::MainMenuUI, ButtonComponent,
+    ButtonType, CurrentLanguage, GameState, GameStateEnum, InventoryUpdatedEvent, Language,
+    PassengerArrivedEvent, PassengerGaveUpEvent, RouteSegmentType, SimClock, UIAssets, ECONOMY_HUD,
+    ECONOMY_MODE,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+use serde::{Deserialize, Serialize};
+
+/// 每关开始时的启动资金
+const STARTING_FUNDS: u32 = 15;
+
+/// 每位到达乘客支付的车费
+const FARE_PER_PASSENGER: u32 = 3;
+
+/// 每位放弃的乘客需要支付的赔偿
+const GAVE_UP_COMPENSATION: u32 = 5;
+
+/// 养护费结算间隔（模拟秒）
+const UPKEEP_INTERVAL_SECS: f32 = 10.0;
+
+/// 每个已放置的路线段每次结算的养护费
+const UPKEEP_PER_SEGMENT: u32 = 1;
+
+// ============ 经营模式资源 ============
+
+/// 经营模式下路线段用资金购买，不再消耗关卡的固定库存。
+/// 支出直接使用 GameState::total_cost（放置、升级、拆除退款都已记录在内），
+/// 另加乘客赔偿和路线段养护费
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct EconomyMode {
+    pub enabled: bool,
+    pub income: u32,    // 本关车费收入
+    pub penalties: u32, // 本关乘客放弃的赔偿
+    pub upkeep: u32,    // 本关已支付的养护费
+}
+
+impl EconomyMode {
+    pub fn expenses(&self, game_state: &GameState) -> u32 {
+        game_state.total_cost + self.penalties + self.upkeep
+    }
+
+    pub fn balance(&self, game_state: &GameState) -> i64 {
+        STARTING_FUNDS as i64 + self.income as i64 - self.expenses(game_state) as i64
+    }
+
+    /// 资金为负即破产，由 check_level_failure_conditions 结束关卡
+    pub fn is_bankrupt(&self, game_state: &GameState) -> bool {
+        self.enabled && self.balance(game_state) < 0
+    }
+
+    /// 每次结算的养护费（两个图层上的路线段都计入）
+    pub fn upkeep_per_interval(&self, game_state: &GameState) -> u32 {
+        game_state.segment_count() as u32 * UPKEEP_PER_SEGMENT
+    }
+
+    /// 未开启经营模式时总是可以支付
+    pub fn can_afford(&self, game_state: &GameState, cost: u32) -> bool {
+        !self.enabled || self.balance(game_state) >= cost as i64
+    }
+
+    pub fn reset_level(&mut self) {
+        self.income = 0;
+        self.penalties = 0;
+        self.upkeep = 0;
+    }
+}
+
+#[derive(Component)]
+pub struct EconomyToggleText;
+
+#[derive(Component)]
+pub struct EconomyHudText;
+
+// ============ 经营模式插件 ============
+
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EconomyMode>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_economy_toggle)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_economy)
+            // 从菜单直接进入关卡不经过 Loading，同样要清空上一关的收支
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_economy)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_economy_hud)
+            .add_systems(
+                Update,
+                (handle_economy_buttons, update_economy_toggle_text)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(
+                Update,
+                (
+                    collect_fares,
+                    charge_segment_upkeep,
+                    stock_inventory_from_funds,
+                    update_economy_hud,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+// ============ 主菜单开关 ============
+
+fn setup_economy_toggle(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    economy: Res<EconomyMode>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(100.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::ToggleEconomyMode,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            MainMenuUI,
+            Name::new("Economy Mode Toggle"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(economy_toggle_label(&economy, current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                EconomyToggleText,
+            ));
+        });
+}
+
+fn economy_toggle_label(economy: &EconomyMode, language: Language) -> String {
+    let check = if economy.enabled { "[x]" } else { "[ ]" };
+    format!("{} {}", check, get_text(&ECONOMY_MODE, language))
+}
+
+fn handle_economy_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut economy: ResMut<EconomyMode>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::ToggleEconomyMode
+        {
+            economy.enabled = !economy.enabled;
+            info!("💰 经营模式: {}", economy.enabled);
+        }
+    }
+}
+
+fn update_economy_toggle_text(
+    economy: Res<EconomyMode>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<&mut Text, With<EconomyToggleText>>,
+) {
+    if !economy.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in toggle_texts.iter_mut() {
+        *text = Text::new(economy_toggle_label(&economy, current_language.language));
+    }
+}
+
+// ============ 收支结算 ============
+
+pub fn reset_economy(mut economy: ResMut<EconomyMode>) {
+    economy.reset_level();
+}
+
+fn collect_fares(
+    mut economy: ResMut<EconomyMode>,
+    mut arrived_events: EventReader<PassengerArrivedEvent>,
+    mut gave_up_events: EventReader<PassengerGaveUpEvent>,
+) {
+    if !economy.enabled {
+        arrived_events.clear();
+        gave_up_events.clear();
+        return;
+    }
+
+    for _ in arrived_events.read() {
+        economy.income += FARE_PER_PASSENGER;
+    }
+
+    for _ in gave_up_events.read() {
+        economy.penalties += GAVE_UP_COMPENSATION;
+    }
+}
+
+/// 每隔 UPKEEP_INTERVAL_SECS 模拟秒按已放置的路线段数量扣除养护费，暂停时不结算
+fn charge_segment_upkeep(
+    mut economy: ResMut<EconomyMode>,
+    game_state: Res<GameState>,
+    sim_clock: Res<SimClock>,
+    mut charged_intervals: Local<u32>,
+) {
+    let intervals = (sim_clock.elapsed_secs() / UPKEEP_INTERVAL_SECS) as u32;
+    // 新关卡开始时模拟时钟归零
+    if intervals < *charged_intervals {
+        *charged_intervals = 0;
+    }
+    if !economy.enabled || intervals == *charged_intervals {
+        *charged_intervals = intervals;
+        return;
+    }
+
+    let due = (intervals - *charged_intervals) * economy.upkeep_per_interval(&game_state);
+    *charged_intervals = intervals;
+    if due > 0 {
+        economy.upkeep += due;
+        info!("🔧 支付路线段养护费 {}", due);
+    }
+}
+
+/// 库存数量改为"当前资金能买几个"：关卡只决定提供哪些路线段类型，
+/// 放置时照常扣减库存并计入 total_cost，下一帧按新余额重新计算
+fn stock_inventory_from_funds(
+    economy: Res<EconomyMode>,
+    mut game_state: ResMut<GameState>,
+    mut inventory_updated_events: EventWriter<InventoryUpdatedEvent>,
+) {
+    if !economy.enabled {
+        return;
+    }
+
+    let funds = economy.balance(&game_state).max(0) as u32;
+    let offered: Vec<RouteSegmentType> = game_state.player_inventory.keys().copied().collect();
+
+    for segment_type in offered {
+        let affordable = funds / segment_type.get_cost();
+        if game_state.player_inventory[&segment_type] != affordable {
+            game_state.player_inventory.insert(segment_type, affordable);
+            inventory_updated_events.write(InventoryUpdatedEvent {
+                segment_type,
+                new_count: affordable,
+            });
+        }
+    }
+}
+
+// ============ 收支显示 ============
+
+fn spawn_economy_hud(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    economy: Res<EconomyMode>,
+    game_state: Res<GameState>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !economy.enabled {
+        return;
+    }
+
+    commands.spawn((
+        Text::new(economy_label(
+            &economy,
+            &game_state,
+            current_language.language,
+        )),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.4, 0.9, 0.4)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Percent(50.0),
+            top: Px(72.0),
+            margin: UiRect::left(Px(-150.0)),
+            ..default()
+        },
+        GameplayUI,
+        EconomyHudText,
+        Name::new("Economy HUD"),
+    ));
+}
+
+fn economy_label(economy: &EconomyMode, game_state: &GameState, language: Language) -> String {
+    get_text_with_args(
+        &ECONOMY_HUD,
+        language,
+        &[
+            &economy.balance(game_state).to_string(),
+            &economy.income.to_string(),
+            &economy.expenses(game_state).to_string(),
+            &economy.upkeep_per_interval(game_state).to_string(),
+        ],
+    )
+}
+
+fn update_economy_hud(
+    economy: Res<EconomyMode>,
+    game_state: Res<GameState>,
+    current_language: Res<CurrentLanguage>,
+    mut hud_texts: Query<(&mut Text, &mut TextColor), With<EconomyHudText>>,
+) {
+    if !economy.is_changed() && !game_state.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    let balance = economy.balance(&game_state);
+    for (mut text, mut color) in hud_texts.iter_mut() {
+        *text = Text::new(economy_label(
+            &economy,
+            &game_state,
+            current_language.language,
+        ));
+        // 资金不够买最便宜的路线段时标红
+        color.0 = if balance >= RouteSegmentType::Straight.get_cost() as i64 {
+            Color::srgb(0.4, 0.9, 0.4)
+        } else {
+            Color::srgb(0.9, 0.3, 0.2)
+        };
+    }
+}
@@ -0,0 +1,219 @@
+// src/bus_puzzle/elevated_layer.rs - 高架快线图层（与地面路网分层，跨越道路不形成路口，只在站点换乘）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, is_valid_placement, line_color, show_contextual_tip,
+    ui_audio::GameplayUI, ButtonComponent, ButtonType, CurrentLanguage, GameState, GameStateEnum,
+    GridPos, InputState, LocalizedText, RouteSegmentType, TipType, UIAssets,
+    ELEVATED_COST_MULTIPLIER, ELEVATED_LAYER_HINT, EXPRESS_LINE, NETWORK_LAYER_ELEVATED,
+    NETWORK_LAYER_GROUND, ROUTE_Z,
+};
+use bevy::prelude::{Val::Px, *};
+use serde::{Deserialize, Serialize};
+
+/// 切换放置图层的按键（Elevated）
+const LAYER_TOGGLE_KEY: KeyCode = KeyCode::KeyE;
+
+/// 高架路线段画在地面路线段和地面公交车之上、站点之下
+pub const ELEVATED_SEGMENT_Z: f32 = ROUTE_Z + 0.5;
+
+/// 路网图层。同一格可以同时有地面和高架路线段，两层互不相连，
+/// 寻路图中各有一套节点，只有站点同时连接两层（换乘点）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NetworkLayer {
+    #[default]
+    Ground,
+    Elevated,
+}
+
+impl NetworkLayer {
+    pub fn toggled(self) -> Self {
+        match self {
+            NetworkLayer::Ground => NetworkLayer::Elevated,
+            NetworkLayer::Elevated => NetworkLayer::Ground,
+        }
+    }
+
+    /// 在该图层放置路线段的费用
+    pub fn segment_cost(self, segment_type: RouteSegmentType) -> u32 {
+        match self {
+            NetworkLayer::Ground => segment_type.get_cost(),
+            NetworkLayer::Elevated => segment_type.get_cost() * ELEVATED_COST_MULTIPLIER,
+        }
+    }
+
+    fn label(self) -> &'static LocalizedText {
+        match self {
+            NetworkLayer::Ground => &NETWORK_LAYER_GROUND,
+            NetworkLayer::Elevated => &NETWORK_LAYER_ELEVATED,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            NetworkLayer::Ground => Color::WHITE,
+            NetworkLayer::Elevated => line_color(EXPRESS_LINE),
+        }
+    }
+}
+
+/// 高架层上的路线段
+#[derive(Component)]
+pub struct ElevatedSegment;
+
+/// 显示当前图层的按钮文字
+#[derive(Component)]
+pub struct ActiveLayerText;
+
+/// 高架层只能铺设直线段和弯道
+pub fn can_be_elevated(segment_type: RouteSegmentType) -> bool {
+    matches!(
+        segment_type,
+        RouteSegmentType::Straight | RouteSegmentType::Curve
+    )
+}
+
+/// 检查路线段能否放在指定图层。高架桥墩不受地形和封路限制，只要在网格内且不占用站点
+pub fn is_valid_placement_on_layer(
+    game_state: &GameState,
+    position: GridPos,
+    segment_type: &RouteSegmentType,
+    layer: NetworkLayer,
+) -> bool {
+    if layer == NetworkLayer::Ground {
+        return is_valid_placement(game_state, position, segment_type);
+    }
+
+    let Some(level_data) = &game_state.current_level else {
+        return false;
+    };
+    let (width, height) = level_data.grid_size;
+
+    can_be_elevated(*segment_type)
+        && !game_state.elevated_segments.contains_key(&position)
+        && position.x >= 0
+        && position.y >= 0
+        && position.x < width as i32
+        && position.y < height as i32
+        && !level_data
+            .stations
+            .iter()
+            .any(|station| station.position == position)
+}
+
+// ============ 高架图层插件 ============
+
+pub struct ElevatedLayerPlugin;
+
+impl Plugin for ElevatedLayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameStateEnum::Loading), reset_active_layer)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_layer_button)
+            .add_systems(
+                Update,
+                (handle_layer_input, update_layer_button)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn reset_active_layer(mut input_state: ResMut<InputState>) {
+    input_state.active_layer = NetworkLayer::Ground;
+}
+
+fn handle_layer_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut input_state: ResMut<InputState>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let button_pressed = button_query.iter().any(|(interaction, button)| {
+        matches!(*interaction, Interaction::Pressed)
+            && matches!(button.button_type, ButtonType::ToggleNetworkLayer)
+    });
+    if !keyboard_input.just_pressed(LAYER_TOGGLE_KEY) && !button_pressed {
+        return;
+    }
+
+    input_state.active_layer = input_state.active_layer.toggled();
+    if input_state.active_layer == NetworkLayer::Elevated {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &ELEVATED_LAYER_HINT,
+                current_language.language,
+                &[&ELEVATED_COST_MULTIPLIER.to_string()],
+            ),
+            TipType::Controls,
+            4.0,
+        );
+    }
+    info!("🚝 当前图层: {:?}", input_state.active_layer);
+}
+
+fn spawn_layer_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    input_state: Res<InputState>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let layer = input_state.active_layer;
+
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(10.0),
+                top: Px(650.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.2, 0.3)),
+            ZIndex(200),
+            GameplayUI,
+            ButtonComponent {
+                button_type: ButtonType::ToggleNetworkLayer,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            Name::new("Network Layer Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(layer.label(), current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(layer.color()),
+                ActiveLayerText,
+            ));
+        });
+}
+
+fn update_layer_button(
+    input_state: Res<InputState>,
+    current_language: Res<CurrentLanguage>,
+    mut texts: Query<(&mut Text, &mut TextColor), With<ActiveLayerText>>,
+) {
+    if !input_state.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    let layer = input_state.active_layer;
+    for (mut text, mut color) in texts.iter_mut() {
+        let label = get_text(layer.label(), current_language.language);
+        if text.0 != label {
+            *text = Text::new(label);
+        }
+        color.0 = layer.color();
+    }
+}
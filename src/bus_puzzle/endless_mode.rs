@@ -0,0 +1,301 @@
+// src/bus_puzzle/endless_mode.rs - 无尽模式（库存无限、没有目标，乘客持续生成且难度随时间上升）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ui_audio::GameplayUI, ui_audio::MainMenuUI, ButtonComponent,
+    ButtonType, CurrentLanguage, GameState, GameStateEnum, InventoryUpdatedEvent, Language,
+    SegmentPlacedEvent, UIAssets, ENDLESS_HUD, ENDLESS_MODE,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+
+/// 无尽模式下每种路线段始终保持的数量
+const ENDLESS_SEGMENT_COUNT: u32 = 99;
+
+/// 每隔多少秒（游戏时间）难度提升一级
+const DIFFICULTY_STEP_SECS: f32 = 45.0;
+
+/// 每级难度乘客生成速度的倍率
+const SPAWN_RATE_GROWTH: f32 = 1.2;
+
+/// 最高难度等级，避免后期乘客生成过快
+const MAX_DIFFICULTY: u32 = 10;
+
+// ============ 游戏模式资源 ============
+
+/// 当前游戏模式，决定是否检查目标和失败条件
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Standard,
+    Endless, // 没有目标和时间限制，乘客放弃过多时结束
+}
+
+impl GameMode {
+    pub fn is_endless(self) -> bool {
+        self == GameMode::Endless
+    }
+}
+
+/// 目标检查和过关判定的运行条件
+pub fn objectives_enabled(game_mode: Res<GameMode>) -> bool {
+    !game_mode.is_endless()
+}
+
+/// 本局无尽模式的难度进度
+#[derive(Resource, Default)]
+pub struct EndlessProgress {
+    pub difficulty: u32,
+}
+
+#[derive(Component)]
+pub struct EndlessModeToggleText;
+
+#[derive(Component)]
+pub struct EndlessHudText;
+
+// ============ 无尽模式插件 ============
+
+pub struct EndlessModePlugin;
+
+impl Plugin for EndlessModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<EndlessProgress>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_endless_toggle)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_endless_hud)
+            .add_systems(
+                Update,
+                (handle_endless_buttons, update_endless_toggle_text)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(
+                Update,
+                (
+                    refill_endless_inventory,
+                    scale_endless_difficulty,
+                    update_endless_hud,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing))
+                    .run_if(|game_mode: Res<GameMode>| game_mode.is_endless()),
+            );
+    }
+}
+
+// ============ 主菜单开关 ============
+
+fn setup_endless_toggle(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    game_mode: Res<GameMode>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(260.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::ToggleEndlessMode,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            MainMenuUI,
+            Name::new("Endless Mode Toggle"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(endless_toggle_label(*game_mode, current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                EndlessModeToggleText,
+            ));
+        });
+}
+
+fn endless_toggle_label(game_mode: GameMode, language: Language) -> String {
+    let check = if game_mode.is_endless() { "[x]" } else { "[ ]" };
+    format!("{} {}", check, get_text(&ENDLESS_MODE, language))
+}
+
+fn handle_endless_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::ToggleEndlessMode
+        {
+            *game_mode = match *game_mode {
+                GameMode::Standard => GameMode::Endless,
+                GameMode::Endless => GameMode::Standard,
+            };
+            info!("♾️ 游戏模式: {:?}", *game_mode);
+        }
+    }
+}
+
+fn update_endless_toggle_text(
+    game_mode: Res<GameMode>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<&mut Text, With<EndlessModeToggleText>>,
+) {
+    if !game_mode.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in toggle_texts.iter_mut() {
+        *text = Text::new(endless_toggle_label(*game_mode, current_language.language));
+    }
+}
+
+// ============ 关卡改造 ============
+
+/// 关卡加载后改造为无尽关卡（在 load_current_level 之后执行）：
+/// 去掉目标，乘客需求不再有数量和时间窗口限制，库存拉满
+pub fn apply_game_mode(
+    game_mode: Res<GameMode>,
+    mut progress: ResMut<EndlessProgress>,
+    mut game_state: ResMut<GameState>,
+) {
+    progress.difficulty = 0;
+    if !game_mode.is_endless() {
+        return;
+    }
+
+    let game_state = &mut *game_state;
+    let Some(level_data) = &mut game_state.current_level else {
+        return;
+    };
+
+    level_data.objectives.clear();
+    for demand in &mut level_data.passenger_demands {
+        demand.total_count = None;
+        demand.spawn_time_range = None;
+    }
+    game_state.objectives_completed.clear();
+
+    for count in game_state.player_inventory.values_mut() {
+        *count = ENDLESS_SEGMENT_COUNT;
+    }
+
+    info!("♾️ 无尽模式：{} 已改造为无尽关卡", level_data.id);
+}
+
+// ============ 无尽模式系统 ============
+
+/// 放置路线段后立即补满库存
+fn refill_endless_inventory(
+    mut placed_events: EventReader<SegmentPlacedEvent>,
+    mut game_state: ResMut<GameState>,
+    mut inventory_updated_events: EventWriter<InventoryUpdatedEvent>,
+) {
+    if placed_events.read().last().is_none() {
+        return;
+    }
+
+    for (segment_type, count) in game_state.player_inventory.iter_mut() {
+        if *count < ENDLESS_SEGMENT_COUNT {
+            *count = ENDLESS_SEGMENT_COUNT;
+            inventory_updated_events.write(InventoryUpdatedEvent {
+                segment_type: *segment_type,
+                new_count: ENDLESS_SEGMENT_COUNT,
+            });
+        }
+    }
+}
+
+/// 每过一段游戏时间提升一级难度，所有乘客需求的生成速度随之加快
+fn scale_endless_difficulty(
+    mut progress: ResMut<EndlessProgress>,
+    mut game_state: ResMut<GameState>,
+) {
+    let target = ((game_state.game_time / DIFFICULTY_STEP_SECS) as u32).min(MAX_DIFFICULTY);
+    if target <= progress.difficulty {
+        return;
+    }
+
+    let steps = target - progress.difficulty;
+    progress.difficulty = target;
+
+    if let Some(level_data) = &mut game_state.current_level {
+        let growth = SPAWN_RATE_GROWTH.powi(steps as i32);
+        for demand in &mut level_data.passenger_demands {
+            demand.spawn_rate *= growth;
+        }
+    }
+    info!("♾️ 无尽模式难度提升到 {} 级", target);
+}
+
+// ============ 无尽模式显示 ============
+
+fn spawn_endless_hud(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    game_mode: Res<GameMode>,
+    progress: Res<EndlessProgress>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !game_mode.is_endless() {
+        return;
+    }
+
+    commands.spawn((
+        Text::new(endless_label(&progress, current_language.language)),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 0.8, 1.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Percent(50.0),
+            top: Px(128.0),
+            margin: UiRect::left(Px(-100.0)),
+            ..default()
+        },
+        GameplayUI,
+        EndlessHudText,
+        Name::new("Endless HUD"),
+    ));
+}
+
+fn endless_label(progress: &EndlessProgress, language: Language) -> String {
+    get_text_with_args(
+        &ENDLESS_HUD,
+        language,
+        &[&(progress.difficulty + 1).to_string()],
+    )
+}
+
+fn update_endless_hud(
+    progress: Res<EndlessProgress>,
+    current_language: Res<CurrentLanguage>,
+    mut hud_texts: Query<&mut Text, With<EndlessHudText>>,
+) {
+    if !progress.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in hud_texts.iter_mut() {
+        *text = Text::new(endless_label(&progress, current_language.language));
+    }
+}
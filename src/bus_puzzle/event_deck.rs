@@ -0,0 +1,358 @@
+// src/bus_puzzle/event_deck.rs - 随机事件牌堆（开启 random_events 的关卡每次抽取不同的事件）
+
+use crate::bus_puzzle::{
+    get_text_with_args, show_contextual_tip, CurrentLanguage, DynamicEvent, EventType, GameState,
+    GameStateEnum, GridPos, Language, LevelData, ObjectiveType, PendingAutosave, TerrainType,
+    TipType, UIAssets, WeatherKind, EVENT_WARNING_NEW_DEMAND, EVENT_WARNING_ROAD_CLOSURE,
+    EVENT_WARNING_SEGMENT_FAILURE, EVENT_WARNING_STATION_OVERLOAD, EVENT_WARNING_SURGE,
+    EVENT_WARNING_WEATHER,
+};
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// 事件触发前多少秒发出预告
+const EVENT_WARNING_LEAD: f32 = 10.0;
+
+/// 预告提示显示时长
+const EVENT_WARNING_DURATION: f32 = 6.0;
+
+/// 抽取的事件在该时间段内触发（有时间限制的关卡会提前结束）
+const EVENT_WINDOW_START: f32 = 20.0;
+const EVENT_WINDOW_END: f32 = 100.0;
+
+/// 路段故障持续时间
+const SEGMENT_FAILURE_DURATION: f32 = 20.0;
+
+/// 客流激增持续时间
+const SURGE_DURATION: f32 = 15.0;
+
+/// 站点过载持续时间
+const OVERLOAD_DURATION: f32 = 15.0;
+
+/// 施工封路持续时间和封闭的格子数
+const ROAD_CLOSURE_DURATION: f32 = 25.0;
+const ROAD_CLOSURE_LENGTH: i32 = 3;
+
+/// 天气持续时间和影响范围（以中心格为圆心的方形半径）
+const WEATHER_DURATION: f32 = 30.0;
+const WEATHER_RADIUS: i32 = 2;
+
+// ============ 事件牌堆 ============
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EventCard {
+    SegmentFailure,
+    PassengerSurge,
+    StationOverload,
+    RoadClosure,
+    Weather,
+}
+
+impl EventCard {
+    /// 牌堆中每种事件的权重
+    const DECK: [(EventCard, u32); 5] = [
+        (EventCard::PassengerSurge, 3),
+        (EventCard::SegmentFailure, 2),
+        (EventCard::StationOverload, 2),
+        (EventCard::RoadClosure, 2),
+        (EventCard::Weather, 2),
+    ];
+
+    fn draw(rng: &mut StdRng) -> Self {
+        let total: u32 = Self::DECK.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for (card, weight) in Self::DECK {
+            if roll < weight {
+                return card;
+            }
+            roll -= weight;
+        }
+        Self::DECK[0].0
+    }
+
+    /// 根据关卡内容生成具体事件，没有合适目标时返回 None
+    fn to_event(self, level_data: &LevelData, rng: &mut StdRng) -> Option<DynamicEvent> {
+        let (width, height) = level_data.grid_size;
+        let is_buildable = |pos: &GridPos| {
+            pos.x >= 0
+                && pos.y >= 0
+                && pos.x < width as i32
+                && pos.y < height as i32
+                && matches!(level_data.terrain.get(pos), None | Some(TerrainType::Empty))
+                && level_data
+                    .stations
+                    .iter()
+                    .all(|station| station.position != *pos)
+        };
+        let candidates: Vec<GridPos> = (0..width as i32)
+            .flat_map(|x| (0..height as i32).map(move |y| GridPos::new(x, y)))
+            .filter(is_buildable)
+            .collect();
+
+        match self {
+            EventCard::SegmentFailure => {
+                let pos = *candidates.get(rng.gen_range(0..candidates.len().max(1)))?;
+                Some(DynamicEvent {
+                    trigger_time: 0.0,
+                    event_type: EventType::SegmentFailure(pos),
+                    duration: Some(SEGMENT_FAILURE_DURATION),
+                })
+            }
+            EventCard::PassengerSurge => {
+                let demands = &level_data.passenger_demands;
+                let demand = demands.get(rng.gen_range(0..demands.len().max(1)))?;
+                Some(DynamicEvent {
+                    trigger_time: 0.0,
+                    event_type: EventType::SurgePassengers(demand.color, rng.gen_range(1.5..2.5)),
+                    duration: Some(SURGE_DURATION),
+                })
+            }
+            EventCard::StationOverload => {
+                let stations = &level_data.stations;
+                let station = stations.get(rng.gen_range(0..stations.len().max(1)))?;
+                Some(DynamicEvent {
+                    trigger_time: 0.0,
+                    event_type: EventType::StationOverload(station.name.clone()),
+                    duration: Some(OVERLOAD_DURATION),
+                })
+            }
+            EventCard::RoadClosure => {
+                // 从随机格子出发沿横向或纵向封闭一段可建设的道路
+                let start = *candidates.get(rng.gen_range(0..candidates.len().max(1)))?;
+                let (dx, dy) = if rng.r#gen() { (1, 0) } else { (0, 1) };
+                let tiles: Vec<GridPos> = (0..ROAD_CLOSURE_LENGTH)
+                    .map(|step| GridPos::new(start.x + dx * step, start.y + dy * step))
+                    .take_while(is_buildable)
+                    .collect();
+                Some(DynamicEvent {
+                    trigger_time: 0.0,
+                    event_type: EventType::RoadClosure(tiles),
+                    duration: Some(ROAD_CLOSURE_DURATION),
+                })
+            }
+            EventCard::Weather => {
+                // 以随机格子为中心笼罩一片区域，站点和地形格子同样受影响
+                let center = *candidates.get(rng.gen_range(0..candidates.len().max(1)))?;
+                let kind = WeatherKind::ALL[rng.gen_range(0..WeatherKind::ALL.len())];
+                let tiles: Vec<GridPos> = (-WEATHER_RADIUS..=WEATHER_RADIUS)
+                    .flat_map(|dx| {
+                        (-WEATHER_RADIUS..=WEATHER_RADIUS)
+                            .map(move |dy| GridPos::new(center.x + dx, center.y + dy))
+                    })
+                    .filter(|pos| {
+                        pos.x >= 0 && pos.y >= 0 && pos.x < width as i32 && pos.y < height as i32
+                    })
+                    .collect();
+                Some(DynamicEvent {
+                    trigger_time: 0.0,
+                    event_type: EventType::Weather(kind, tiles),
+                    duration: Some(WEATHER_DURATION),
+                })
+            }
+        }
+    }
+}
+
+/// 本关抽取的事件（种子记录在日志中，便于复现）和已经预告过的事件
+#[derive(Resource, Default)]
+pub struct EventDeck {
+    pub seed: u64,
+    /// 玩家输入的分享种子（关卡 id, 种子），重新开始时沿用，回到主菜单后清除
+    pub shared_seed: Option<(String, u64)>,
+    announced: Vec<usize>, // 已预告的 dynamic_events 下标
+}
+
+// ============ 随机事件插件 ============
+
+pub struct EventDeckPlugin;
+
+impl Plugin for EventDeckPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventDeck>().add_systems(
+            Update,
+            announce_upcoming_events.run_if(in_state(GameStateEnum::Playing)),
+        );
+    }
+}
+
+/// 关卡加载后抽取随机事件（在 apply_level_mutators 之后执行，位置已按镜像模式翻转）。
+/// 恢复存档时沿用存档的种子，天气和封路与离开时一致
+pub fn draw_level_events(
+    mut deck: ResMut<EventDeck>,
+    mut game_state: ResMut<GameState>,
+    pending: Res<PendingAutosave>,
+) {
+    deck.announced.clear();
+
+    let Some(level_data) = &mut game_state.current_level else {
+        return;
+    };
+    if !level_data.random_events {
+        return;
+    }
+
+    let saved_seed = pending
+        .0
+        .as_ref()
+        .filter(|data| data.level_id == level_data.id)
+        .and_then(|data| data.event_seed);
+    deck.seed = match (saved_seed, &deck.shared_seed) {
+        (Some(seed), _) => seed,
+        (None, Some((level_id, seed))) if *level_id == level_data.id => *seed,
+        _ => rand::thread_rng().r#gen(),
+    };
+    let drawn = draw_events(level_data, deck.seed);
+
+    info!(
+        "🃏 随机事件牌堆 (种子 {}): {:?}",
+        deck.seed,
+        drawn
+            .iter()
+            .map(|event| (event.trigger_time as u32, &event.event_type))
+            .collect::<Vec<_>>()
+    );
+    level_data.dynamic_events.extend(drawn);
+}
+
+/// 用给定种子抽取事件，同一关卡和种子总是得到相同结果（只能使用种子生成的随机数）
+pub fn draw_events(level_data: &LevelData, seed: u64) -> Vec<DynamicEvent> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // 有时间限制的关卡，事件要在结束前触发
+    let window_end = level_data
+        .objectives
+        .iter()
+        .find_map(|objective| match objective.condition_type {
+            ObjectiveType::TimeLimit(limit) => Some(limit - 5.0),
+            _ => None,
+        })
+        .unwrap_or(EVENT_WINDOW_END)
+        .max(EVENT_WINDOW_START + 1.0);
+
+    let count = rng.gen_range(2..=3);
+    let mut drawn: Vec<DynamicEvent> = (0..count)
+        .filter_map(|_| {
+            let mut event = EventCard::draw(&mut rng).to_event(level_data, &mut rng)?;
+            event.trigger_time = rng.gen_range(EVENT_WINDOW_START..window_end);
+            Some(event)
+        })
+        .collect();
+    drawn.sort_by(|a, b| a.trigger_time.total_cmp(&b.trigger_time));
+    drawn
+}
+
+// ============ 事件预告 ============
+
+fn announce_upcoming_events(
+    mut commands: Commands,
+    mut deck: ResMut<EventDeck>,
+    game_state: Res<GameState>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    let game_time = game_state.game_time;
+
+    for (index, event) in level_data.dynamic_events.iter().enumerate() {
+        // 恢复存档时已经触发过的事件不再预告
+        if game_time < event.trigger_time - EVENT_WARNING_LEAD
+            || game_time >= event.trigger_time
+            || deck.announced.contains(&index)
+        {
+            continue;
+        }
+
+        deck.announced.push(index);
+        let seconds_left = (event.trigger_time - game_time).ceil().to_string();
+        let warning = event_warning(level_data, event, &seconds_left, current_language.language);
+
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &warning,
+            TipType::Warning,
+            EVENT_WARNING_DURATION,
+        );
+        info!("⏰ 事件预告: {}", warning);
+    }
+}
+
+fn event_warning(
+    level_data: &LevelData,
+    event: &DynamicEvent,
+    seconds_left: &str,
+    language: Language,
+) -> String {
+    let station_name = |name: &str| {
+        level_data
+            .stations
+            .iter()
+            .find(|station| station.name == name)
+            .map(|station| station.get_localized_name(language))
+            .unwrap_or_else(|| name.to_string())
+    };
+
+    match &event.event_type {
+        EventType::SegmentFailure(pos) => get_text_with_args(
+            &EVENT_WARNING_SEGMENT_FAILURE,
+            language,
+            &[&pos.x.to_string(), &pos.y.to_string(), seconds_left],
+        ),
+        EventType::SurgePassengers(color, multiplier) => {
+            let origin = level_data
+                .passenger_demands
+                .iter()
+                .find(|demand| demand.color == *color)
+                .map(|demand| station_name(&demand.origin))
+                .unwrap_or_default();
+            get_text_with_args(
+                &EVENT_WARNING_SURGE,
+                language,
+                &[&origin, seconds_left, &format!("{:.1}", multiplier)],
+            )
+        }
+        EventType::NewDemand(demand) => get_text_with_args(
+            &EVENT_WARNING_NEW_DEMAND,
+            language,
+            &[
+                &station_name(&demand.origin),
+                &station_name(&demand.destination),
+                seconds_left,
+            ],
+        ),
+        EventType::StationOverload(name) => get_text_with_args(
+            &EVENT_WARNING_STATION_OVERLOAD,
+            language,
+            &[&station_name(name), seconds_left],
+        ),
+        EventType::RoadClosure(tiles) => {
+            let first = tiles.first().copied().unwrap_or(GridPos::new(0, 0));
+            get_text_with_args(
+                &EVENT_WARNING_ROAD_CLOSURE,
+                language,
+                &[
+                    &first.x.to_string(),
+                    &first.y.to_string(),
+                    seconds_left,
+                    &tiles.len().to_string(),
+                    &format!("{:.0}", event.duration.unwrap_or_default()),
+                ],
+            )
+        }
+        EventType::Weather(kind, tiles) => {
+            let first = tiles.first().copied().unwrap_or(GridPos::new(0, 0));
+            get_text_with_args(
+                &EVENT_WARNING_WEATHER,
+                language,
+                &[
+                    &kind.localized_name(language),
+                    &first.x.to_string(),
+                    &first.y.to_string(),
+                    seconds_left,
+                ],
+            )
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::bus_puzzle::{GridPos, PassengerColor, RouteSegmentType};
+use crate::bus_puzzle::{GameScore, GridPos, NetworkLayer, PassengerColor, RouteSegmentType};
 use bevy::prelude::*;
 
 #[derive(Event)]
@@ -7,11 +7,27 @@ pub struct SegmentPlacedEvent {
     pub position: GridPos,
     pub segment_type: RouteSegmentType,
     pub rotation: u32,
+    pub layer: NetworkLayer,
 }
 
 #[derive(Event)]
 pub struct SegmentRemovedEvent {
     pub position: GridPos,
+    pub layer: NetworkLayer,
+}
+
+/// 直线段升级为快速路线
+#[derive(Event)]
+pub struct SegmentUpgradedEvent {
+    pub position: GridPos,
+}
+
+/// 已放置的路线段被旋转
+#[derive(Event)]
+pub struct SegmentRotatedEvent {
+    pub position: GridPos,
+    pub rotation: u32,
+    pub layer: NetworkLayer,
 }
 
 #[derive(Event)]
@@ -25,6 +41,12 @@ pub struct LevelCompletedEvent {
     pub completion_time: f32,
 }
 
+/// 分数发生变化时发送，携带完整的分数明细
+#[derive(Event)]
+pub struct ScoreChangedEvent {
+    pub breakdown: GameScore,
+}
+
 #[derive(Event)]
 pub struct InventoryUpdatedEvent {
     pub segment_type: RouteSegmentType,
@@ -37,6 +59,7 @@ pub struct PassengerSpawnedEvent {
     pub color: PassengerColor,
     pub origin: String,
     pub destination: String,
+    pub group_size: u32,
 }
 
 #[derive(Event)]
@@ -46,3 +69,27 @@ pub struct PassengerArrivedEvent {
     pub travel_time: f32,
     pub transfers: u32,
 }
+
+#[derive(Event)]
+#[allow(dead_code)]
+pub struct PassengerGaveUpEvent {
+    pub color: PassengerColor,
+    pub origin: String,
+    pub destination: String, // 放弃时正要前往的站点（多段行程为中转站）
+}
+
+/// 屏幕按钮请求旋转路线段（触屏布局），position 为空时旋转预览
+#[derive(Event)]
+pub struct RotateSegmentRequestEvent {
+    pub position: Option<GridPos>,
+}
+
+/// 屏幕按钮请求拆除路线段（触屏布局）
+#[derive(Event)]
+pub struct RemoveSegmentRequestEvent {
+    pub position: GridPos,
+}
+
+/// 退出前要求所有存档系统立即写盘
+#[derive(Event)]
+pub struct FlushSavesEvent;
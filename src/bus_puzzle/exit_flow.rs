@@ -0,0 +1,75 @@
+// src/bus_puzzle/exit_flow.rs - 退出确认与退出流程
+
+use crate::bus_puzzle::{
+    ButtonComponent, ButtonType, ConfirmAction, ConfirmDialogUI, ConfirmRequestEvent,
+    DialogConfirmedEvent, FlushSavesEvent,
+};
+use bevy::{prelude::*, window::WindowCloseRequested};
+
+// ============ 退出流程资源 ============
+
+/// 已确认退出，等待存档刷新完成后发送 AppExit
+#[derive(Resource, Default)]
+pub struct PendingAppExit(pub bool);
+
+// ============ 退出流程插件 ============
+
+pub struct ExitFlowPlugin;
+
+impl Plugin for ExitFlowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingAppExit>()
+            .add_systems(Update, (handle_quit_buttons, handle_window_close_requests))
+            // 存档刷新系统在 PostUpdate 响应 FlushSavesEvent，这里放在 Last 保证之后才退出
+            .add_systems(Last, complete_app_exit);
+    }
+}
+
+// ============ 退出流程系统 ============
+
+fn handle_quit_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut confirmed_events: EventReader<DialogConfirmedEvent>,
+    mut confirm_requests: EventWriter<ConfirmRequestEvent>,
+    mut pending_exit: ResMut<PendingAppExit>,
+    mut flush_events: EventWriter<FlushSavesEvent>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::QuitGame
+        {
+            confirm_requests.write(ConfirmRequestEvent(ConfirmAction::QuitGame));
+        }
+    }
+
+    if confirmed_events
+        .read()
+        .any(|DialogConfirmedEvent(action)| *action == ConfirmAction::QuitGame)
+    {
+        info!("确认退出游戏，正在保存...");
+        flush_events.write(FlushSavesEvent);
+        pending_exit.0 = true;
+    }
+}
+
+/// 拦截窗口关闭请求，改为弹出退出确认框
+fn handle_window_close_requests(
+    mut close_requests: EventReader<WindowCloseRequested>,
+    dialogs: Query<&ConfirmDialogUI>,
+    mut confirm_requests: EventWriter<ConfirmRequestEvent>,
+) {
+    let quit_dialog_open = dialogs
+        .iter()
+        .any(|dialog| dialog.action == ConfirmAction::QuitGame);
+    if close_requests.read().last().is_some() && !quit_dialog_open {
+        info!("收到窗口关闭请求，等待确认");
+        confirm_requests.write(ConfirmRequestEvent(ConfirmAction::QuitGame));
+    }
+}
+
+fn complete_app_exit(pending_exit: Res<PendingAppExit>, mut app_exit_events: EventWriter<AppExit>) {
+    if pending_exit.0 {
+        info!("👋 存档已保存，退出游戏");
+        app_exit_events.write(AppExit::Success);
+    }
+}
@@ -0,0 +1,128 @@
+// src/bus_puzzle/failure_forecast.rs - 失败预警（即将因乘客放弃或超时失败时，提前弹出脉动横幅并播放警告音）
+
+use crate::bus_puzzle::{
+    format_time, get_text_with_args, show_warning_banner, AgentState, AudioAssets, AudioBus,
+    AudioSettings, CurrentLanguage, GameMode, GameState, GameStateEnum, ObjectiveType,
+    PathfindingAgent, UIAssets, FAILURE_WARNING_GAVE_UP, FAILURE_WARNING_TIME,
+    MAX_GAVE_UP_PASSENGERS,
+};
+use bevy::prelude::*;
+
+/// 放弃人数达到此值时预警（比失败条件少一人）
+const GAVE_UP_WARNING_COUNT: u32 = MAX_GAVE_UP_PASSENGERS - 1;
+
+/// 时间限制用掉此比例时预警
+const TIME_WARNING_FRACTION: f32 = 0.8;
+
+/// 预警横幅显示时长（秒）
+const WARNING_BANNER_SECS: f32 = 4.0;
+
+/// 预警音量相对失败音效的比例
+const WARNING_VOLUME: f32 = 0.6;
+
+/// 本关已经发出的预警，避免同一个阈值反复提醒
+#[derive(Resource, Default)]
+pub struct FailureForecast {
+    /// 放弃人数已达到预警阈值；回落到阈值以下后重新布防
+    gave_up_warned: bool,
+    time_warned: bool,
+}
+
+// ============ 失败预警插件 ============
+
+pub struct FailureForecastPlugin;
+
+impl Plugin for FailureForecastPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FailureForecast>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_failure_forecast)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_failure_forecast)
+            .add_systems(
+                Update,
+                forecast_level_failure.run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn reset_failure_forecast(mut forecast: ResMut<FailureForecast>) {
+    *forecast = FailureForecast::default();
+}
+
+/// 在 `check_level_failure_conditions` 触发之前检查各失败条件是否逼近阈值
+fn forecast_level_failure(
+    mut commands: Commands,
+    mut forecast: ResMut<FailureForecast>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    passengers: Query<&PathfindingAgent>,
+    ui_assets: Res<UIAssets>,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let language = current_language.language;
+    let mut warnings = Vec::new();
+
+    // 与失败判定使用同一口径：当前处于放弃状态的乘客数
+    let gave_up_count = passengers
+        .iter()
+        .filter(|agent| matches!(agent.state, AgentState::GaveUp))
+        .count() as u32;
+
+    if gave_up_count < GAVE_UP_WARNING_COUNT {
+        forecast.gave_up_warned = false;
+    } else if !forecast.gave_up_warned && gave_up_count <= MAX_GAVE_UP_PASSENGERS {
+        forecast.gave_up_warned = true;
+        warnings.push(get_text_with_args(
+            &FAILURE_WARNING_GAVE_UP,
+            language,
+            &[
+                &gave_up_count.to_string(),
+                &MAX_GAVE_UP_PASSENGERS.to_string(),
+            ],
+        ));
+    }
+
+    // 无尽模式没有时间限制
+    if !forecast.time_warned
+        && !game_mode.is_endless()
+        && let Some(level_data) = &game_state.current_level
+    {
+        let time_limit =
+            level_data
+                .objectives
+                .iter()
+                .find_map(|objective| match objective.condition_type {
+                    ObjectiveType::TimeLimit(limit) => Some(limit),
+                    _ => None,
+                });
+
+        if let Some(limit) = time_limit
+            && game_state.game_time >= limit * TIME_WARNING_FRACTION
+            && game_state.game_time <= limit
+        {
+            forecast.time_warned = true;
+            warnings.push(get_text_with_args(
+                &FAILURE_WARNING_TIME,
+                language,
+                &[&format_time(limit - game_state.game_time)],
+            ));
+        }
+    }
+
+    if warnings.is_empty() {
+        return;
+    }
+
+    for warning in &warnings {
+        show_warning_banner(&mut commands, &ui_assets, warning, WARNING_BANNER_SECS);
+        warn!("失败预警：{}", warning);
+    }
+
+    if !audio_settings.is_muted {
+        commands.spawn((
+            AudioPlayer::new(audio_assets.error_sound.clone()),
+            audio_settings.sound(AudioBus::World, WARNING_VOLUME),
+        ));
+    }
+}
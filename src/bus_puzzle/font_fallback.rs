@@ -0,0 +1,98 @@
+// src/bus_puzzle/font_fallback.rs - 字体后备链（切换到某种语言时才加载它的字体链，缺失的字形由链上其他字体补上）
+
+use crate::bus_puzzle::{CurrentLanguage, UIAssets};
+use bevy::{
+    platform::collections::HashSet,
+    prelude::{Val::Px, *},
+};
+
+/// 引用后备字体的不可见文字。bevy 只把文字实际用到的字体交给排版引擎，
+/// 排版遇到当前字体缺少的字形时才能在这些字体中找到
+#[derive(Component)]
+pub struct FallbackFontAnchor {
+    path: &'static str,
+}
+
+// ============ 字体后备插件 ============
+
+pub struct FontFallbackPlugin;
+
+impl Plugin for FontFallbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (load_language_fonts, register_fallback_fonts).chain(),
+        );
+    }
+}
+
+/// 当前语言字体链中尚未请求的字体开始加载
+fn load_language_fonts(
+    current_language: Res<CurrentLanguage>,
+    asset_server: Res<AssetServer>,
+    ui_assets: Option<ResMut<UIAssets>>,
+) {
+    let Some(mut ui_assets) = ui_assets else {
+        return;
+    };
+    let language = current_language.language;
+    if language
+        .font_chain()
+        .all(|path| ui_assets.language_fonts.contains_key(path))
+    {
+        return;
+    }
+
+    for path in language.font_chain() {
+        ui_assets
+            .language_fonts
+            .entry(path)
+            .or_insert_with(|| asset_server.load(path));
+    }
+    debug!("🔤 加载 {:?} 的字体链", language);
+}
+
+/// 加载完成的字体各挂一个锚点；可选字体不存在时只提示一次
+fn register_fallback_fonts(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ui_assets: Option<Res<UIAssets>>,
+    anchors: Query<&FallbackFontAnchor>,
+    mut skipped: Local<HashSet<&'static str>>,
+) {
+    let Some(ui_assets) = ui_assets else {
+        return;
+    };
+
+    for (path, font) in ui_assets.language_fonts.iter() {
+        if anchors.iter().any(|anchor| anchor.path == *path) || skipped.contains(path) {
+            continue;
+        }
+        if asset_server.load_state(font).is_failed() {
+            info!("🔤 字体 assets/{} 不可用，已从字体链中跳过", path);
+            skipped.insert(path);
+            continue;
+        }
+        if !asset_server.is_loaded_with_dependencies(font) {
+            continue;
+        }
+
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Px(0.0),
+                height: Px(0.0),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            Text::new(" "),
+            TextFont {
+                font: font.clone(),
+                font_size: 1.0,
+                ..default()
+            },
+            FallbackFontAnchor { path },
+            Name::new(format!("Fallback Font: {}", path)),
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+// src/bus_puzzle/frame_limit.rs - 帧率上限与菜单省电模式
+
+use crate::bus_puzzle::{get_text, GameOptions, GameStateEnum, Language, FRAME_LIMIT_UNCAPPED};
+use bevy::{
+    prelude::*,
+    winit::{UpdateMode, WinitSettings},
+};
+use std::time::Duration;
+
+/// 省电模式下菜单无输入时的刷新间隔
+const MENU_IDLE_WAIT: Duration = Duration::from_millis(250);
+
+// ============ 帧率上限 ============
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FrameLimit {
+    Fps30,
+    Fps60,
+    #[default]
+    Uncapped,
+}
+
+impl FrameLimit {
+    pub fn next(self) -> Self {
+        match self {
+            FrameLimit::Fps30 => FrameLimit::Fps60,
+            FrameLimit::Fps60 => FrameLimit::Uncapped,
+            FrameLimit::Uncapped => FrameLimit::Fps30,
+        }
+    }
+
+    pub fn fps(self) -> Option<u32> {
+        match self {
+            FrameLimit::Fps30 => Some(30),
+            FrameLimit::Fps60 => Some(60),
+            FrameLimit::Uncapped => None,
+        }
+    }
+
+    pub fn label(self, language: Language) -> String {
+        match self.fps() {
+            Some(fps) => format!("{} FPS", fps),
+            None => get_text(&FRAME_LIMIT_UNCAPPED, language),
+        }
+    }
+
+    fn frame_time(self) -> Option<Duration> {
+        self.fps()
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+}
+
+// ============ 帧率控制插件 ============
+
+pub struct FrameLimitPlugin;
+
+impl Plugin for FrameLimitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_winit_settings);
+
+        // 网页端由浏览器驱动帧循环，不能阻塞主线程，改用 WinitSettings 的等待间隔
+        #[cfg(not(target_family = "wasm"))]
+        app.add_systems(Last, limit_frame_rate);
+    }
+}
+
+/// 菜单（以及暂停、结算界面）画面基本静止，省电模式下只在有输入时刷新
+fn update_winit_settings(
+    current_state: Res<State<GameStateEnum>>,
+    game_options: Res<GameOptions>,
+    mut winit_settings: ResMut<WinitSettings>,
+) {
+    if !current_state.is_changed() && !game_options.is_changed() {
+        return;
+    }
+
+    let in_menu = !matches!(
+        current_state.get(),
+        GameStateEnum::Splash | GameStateEnum::Loading | GameStateEnum::Playing
+    );
+
+    winit_settings.focused_mode = if game_options.battery_saver && in_menu {
+        UpdateMode::reactive_low_power(MENU_IDLE_WAIT)
+    } else {
+        frame_limited_update_mode(game_options.frame_limit)
+    };
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn frame_limited_update_mode(_frame_limit: FrameLimit) -> UpdateMode {
+    UpdateMode::Continuous
+}
+
+#[cfg(target_family = "wasm")]
+fn frame_limited_update_mode(frame_limit: FrameLimit) -> UpdateMode {
+    match frame_limit.frame_time() {
+        Some(frame_time) => UpdateMode::reactive(frame_time),
+        None => UpdateMode::Continuous,
+    }
+}
+
+/// 帧末尾补足剩余时间，使两帧之间不短于目标帧时长
+#[cfg(not(target_family = "wasm"))]
+fn limit_frame_rate(
+    game_options: Res<GameOptions>,
+    mut last_frame_end: Local<Option<std::time::Instant>>,
+) {
+    if let (Some(frame_time), Some(last)) = (game_options.frame_limit.frame_time(), *last_frame_end)
+    {
+        let elapsed = last.elapsed();
+        if elapsed < frame_time {
+            std::thread::sleep(frame_time - elapsed);
+        }
+    }
+
+    *last_frame_end = Some(std::time::Instant::now());
+}
@@ -0,0 +1,133 @@
+// src/bus_puzzle/fullscreen.rs - 全屏切换（F11 或屏幕按钮；网页版请求浏览器全屏，可在 itch 内嵌页面中使用）
+
+#[cfg(not(target_family = "wasm"))]
+use crate::bus_puzzle::GameOptions;
+use crate::bus_puzzle::{
+    get_text, ButtonComponent, ButtonType, CurrentLanguage, GameStateEnum, UIAssets, FULLSCREEN,
+};
+use bevy::prelude::{Val::Px, *};
+#[cfg(not(target_family = "wasm"))]
+use bevy::window::{MonitorSelection, WindowMode};
+
+/// 切换全屏的按键
+const FULLSCREEN_KEY: KeyCode = KeyCode::F11;
+
+// ============ 全屏插件 ============
+
+pub struct FullscreenPlugin;
+
+impl Plugin for FullscreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameStateEnum::MainMenu),
+            |commands: Commands, ui_assets: Res<UIAssets>, language: Res<CurrentLanguage>| {
+                spawn_fullscreen_button(commands, ui_assets, language, GameStateEnum::MainMenu);
+            },
+        )
+        .add_systems(
+            OnEnter(GameStateEnum::Paused),
+            |commands: Commands, ui_assets: Res<UIAssets>, language: Res<CurrentLanguage>| {
+                spawn_fullscreen_button(commands, ui_assets, language, GameStateEnum::Paused);
+            },
+        )
+        .add_systems(Update, handle_fullscreen_toggle);
+    }
+}
+
+/// 主菜单和暂停菜单右上角的全屏按钮
+fn spawn_fullscreen_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    state: GameStateEnum,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(180.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::ToggleFullscreen,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            ZIndex(3500), // 在暂停菜单（3000）之上，设置菜单（4000）之下
+            StateScoped(state),
+            Name::new("Fullscreen Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&FULLSCREEN, current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_fullscreen_toggle(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    #[cfg(not(target_family = "wasm"))] mut game_options: ResMut<GameOptions>,
+) {
+    // Shift+F11 留给寻路调试信息
+    let key_pressed = keyboard_input.just_pressed(FULLSCREEN_KEY)
+        && !keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let button_pressed = button_query.iter().any(|(interaction, button_component)| {
+        *interaction == Interaction::Pressed
+            && button_component.button_type == ButtonType::ToggleFullscreen
+    });
+    if !key_pressed && !button_pressed {
+        return;
+    }
+
+    // 桌面版切换显示模式，由设置菜单同步到窗口并写入用户设置
+    #[cfg(not(target_family = "wasm"))]
+    {
+        game_options.window_mode = match game_options.window_mode {
+            WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            _ => WindowMode::Windowed,
+        };
+        info!("🖥️ 窗口模式: {:?}", game_options.window_mode);
+    }
+
+    #[cfg(target_family = "wasm")]
+    toggle_browser_fullscreen();
+}
+
+/// 浏览器只允许在用户操作后请求全屏；按键和点击都在同一帧内处理，满足该限制
+#[cfg(target_family = "wasm")]
+fn toggle_browser_fullscreen() {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    if document.fullscreen_element().is_some() {
+        document.exit_fullscreen();
+        info!("🖥️ 退出浏览器全屏");
+        return;
+    }
+
+    // 对整个页面请求全屏，画布会随父元素自动铺满
+    let Some(root) = document.document_element() else {
+        return;
+    };
+    if root.request_fullscreen().is_err() {
+        // itch 等内嵌页面需要 iframe 允许全屏
+        warn!("浏览器拒绝了全屏请求");
+    } else {
+        info!("🖥️ 进入浏览器全屏");
+    }
+}
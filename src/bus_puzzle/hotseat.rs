@@ -0,0 +1,285 @@
+// src/bus_puzzle/hotseat.rs - 双人热座合作模式（轮流放置路线段，共享预算）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, set_hud_text_args, show_contextual_tip, ui_audio::GameplayUI,
+    ui_audio::MainMenuUI, ButtonComponent, ButtonType, CurrentLanguage, GameStateEnum, Language,
+    LocalizedTextComponent, SimClock, TipType, UIAssets, HOTSEAT_MODE, HOTSEAT_TURN,
+    HOTSEAT_TURN_CHANGE,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+
+/// 每位玩家每回合的时长（秒）
+pub const HOTSEAT_TURN_DURATION: f32 = 20.0;
+
+// ============ 热座模式资源 ============
+
+#[derive(Resource)]
+pub struct HotSeatMode {
+    pub enabled: bool,
+    pub current_player: u8,  // 1 或 2
+    pub turn_time_left: f32, // 当前回合剩余时间
+}
+
+impl Default for HotSeatMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            current_player: 1,
+            turn_time_left: HOTSEAT_TURN_DURATION,
+        }
+    }
+}
+
+impl HotSeatMode {
+    pub fn reset_turns(&mut self) {
+        self.current_player = 1;
+        self.turn_time_left = HOTSEAT_TURN_DURATION;
+    }
+
+    fn next_turn(&mut self) {
+        self.current_player = if self.current_player == 1 { 2 } else { 1 };
+        self.turn_time_left = HOTSEAT_TURN_DURATION;
+    }
+
+    fn player_color(&self) -> Color {
+        match self.current_player {
+            1 => Color::srgb(0.2, 0.5, 0.9),
+            _ => Color::srgb(0.9, 0.4, 0.2),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HotSeatToggleText;
+
+#[derive(Component)]
+pub struct HotSeatChip;
+
+#[derive(Component)]
+pub struct HotSeatChipText;
+
+// ============ 热座模式插件 ============
+
+pub struct HotSeatPlugin;
+
+impl Plugin for HotSeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HotSeatMode>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_hotseat_toggle)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_hotseat_turns)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_hotseat_turns)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_hotseat_chip)
+            .add_systems(
+                Update,
+                (handle_hotseat_buttons, update_hotseat_toggle_text)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(
+                Update,
+                (tick_hotseat_turns, update_hotseat_chip)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+// ============ 主菜单开关 ============
+
+fn setup_hotseat_toggle(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    hotseat: Res<HotSeatMode>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(20.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::ToggleHotSeat,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            MainMenuUI,
+            Name::new("Hot-seat Toggle"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(hotseat_toggle_label(&hotseat, current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                HotSeatToggleText,
+            ));
+        });
+}
+
+fn hotseat_toggle_label(hotseat: &HotSeatMode, language: Language) -> String {
+    let check = if hotseat.enabled { "[x]" } else { "[ ]" };
+    format!("{} {}", check, get_text(&HOTSEAT_MODE, language))
+}
+
+fn handle_hotseat_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut hotseat: ResMut<HotSeatMode>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::ToggleHotSeat
+        {
+            hotseat.enabled = !hotseat.enabled;
+            info!("👥 热座模式: {}", hotseat.enabled);
+        }
+    }
+}
+
+fn update_hotseat_toggle_text(
+    hotseat: Res<HotSeatMode>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<&mut Text, With<HotSeatToggleText>>,
+) {
+    if !hotseat.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in toggle_texts.iter_mut() {
+        *text = Text::new(hotseat_toggle_label(&hotseat, current_language.language));
+    }
+}
+
+// ============ 回合计时 ============
+
+fn reset_hotseat_turns(mut hotseat: ResMut<HotSeatMode>) {
+    hotseat.reset_turns();
+}
+
+/// 按模拟时间计时：暂停或关卡简报期间回合不流逝，加速时回合同样变快
+fn tick_hotseat_turns(
+    mut commands: Commands,
+    sim_clock: Res<SimClock>,
+    mut hotseat: ResMut<HotSeatMode>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !hotseat.enabled {
+        return;
+    }
+
+    hotseat.turn_time_left -= sim_clock.delta_secs();
+    if hotseat.turn_time_left > 0.0 {
+        return;
+    }
+
+    hotseat.next_turn();
+    info!("👥 轮到玩家 {}", hotseat.current_player);
+
+    show_contextual_tip(
+        &mut commands,
+        &ui_assets,
+        &get_text_with_args(
+            &HOTSEAT_TURN_CHANGE,
+            current_language.language,
+            &[&hotseat.current_player.to_string()],
+        ),
+        TipType::Controls,
+        2.0,
+    );
+}
+
+// ============ 玩家指示标签 ============
+
+fn spawn_hotseat_chip(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    hotseat: Res<HotSeatMode>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !hotseat.enabled {
+        return;
+    }
+
+    let label = LocalizedTextComponent::with_args(&HOTSEAT_TURN, hotseat_chip_args(&hotseat));
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Percent(50.0),
+                top: Px(10.0),
+                margin: UiRect::left(Px(-80.0)),
+                width: Px(160.0),
+                padding: UiRect::axes(Px(10.0), Px(4.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(hotseat.player_color()),
+            BorderRadius::all(Px(12.0)),
+            GameplayUI,
+            HotSeatChip,
+            Name::new("Hot-seat Player Chip"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label.get_text(current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                label,
+                HotSeatChipText,
+            ));
+        });
+}
+
+/// 玩家编号和按整秒显示的剩余时间
+fn hotseat_chip_args(hotseat: &HotSeatMode) -> Vec<String> {
+    vec![
+        hotseat.current_player.to_string(),
+        hotseat.turn_time_left.ceil().max(0.0).to_string(),
+    ]
+}
+
+/// 只在换人、剩余秒数变化或切换语言时改写标签
+fn update_hotseat_chip(
+    hotseat: Res<HotSeatMode>,
+    current_language: Res<CurrentLanguage>,
+    mut chips: Query<&mut BackgroundColor, With<HotSeatChip>>,
+    mut chip_texts: Query<(&mut LocalizedTextComponent, &mut Text), With<HotSeatChipText>>,
+) {
+    if !hotseat.enabled || (!hotseat.is_changed() && !current_language.is_changed()) {
+        return;
+    }
+
+    for mut background in chips.iter_mut() {
+        background.set_if_neq(BackgroundColor(hotseat.player_color()));
+    }
+
+    for (mut localized, mut text) in chip_texts.iter_mut() {
+        set_hud_text_args(
+            &mut localized,
+            &mut text,
+            hotseat_chip_args(&hotseat),
+            current_language.language,
+            current_language.is_changed(),
+        );
+    }
+}
@@ -0,0 +1,632 @@
+// src/bus_puzzle/input_map.rs - 可重新绑定的快捷键（旋转、拆除、语言、镜头移动、暂停、热力图、游戏速度和快速存档）
+
+use crate::bus_puzzle::{
+    get_text,
+    settings_menu::{spawn_settings_button, spawn_settings_label},
+    ButtonComponent, ButtonType, CurrentLanguage, GameStateEnum, Language, LocalizedText,
+    LocalizedTextComponent, SettingsMenuUI, UIAssets, BACK, CONTROLS_HELP, KEY_BINDINGS,
+    KEY_BINDINGS_HINT, KEY_CAMERA_DOWN, KEY_CAMERA_LEFT, KEY_CAMERA_RIGHT, KEY_CAMERA_UP,
+    KEY_PAUSE, KEY_PRESS_NEW, KEY_QUICKLOAD, KEY_QUICKSAVE, KEY_REMOVE, KEY_ROTATE, KEY_SIM_FASTER,
+    KEY_SIM_SLOWER, KEY_SIM_STEP, KEY_TOGGLE_LANGUAGE, KEY_WAIT_HEATMAP, RESET_KEY_BINDINGS,
+};
+use bevy::{
+    input::InputSystem,
+    platform::collections::HashMap,
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
+};
+
+// ============ 快捷键动作 ============
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    Rotate,
+    Remove,
+    ToggleLanguage,
+    CameraUp,
+    CameraDown,
+    CameraLeft,
+    CameraRight,
+    Pause,
+    ToggleWaitHeatmap,
+    SimSlower,
+    SimFaster,
+    SimStep,
+    Quicksave,
+    Quickload,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 14] = [
+        InputAction::Rotate,
+        InputAction::Remove,
+        InputAction::ToggleLanguage,
+        InputAction::CameraUp,
+        InputAction::CameraDown,
+        InputAction::CameraLeft,
+        InputAction::CameraRight,
+        InputAction::Pause,
+        InputAction::ToggleWaitHeatmap,
+        InputAction::SimSlower,
+        InputAction::SimFaster,
+        InputAction::SimStep,
+        InputAction::Quicksave,
+        InputAction::Quickload,
+    ];
+
+    pub fn default_key(self) -> KeyCode {
+        match self {
+            InputAction::Rotate => KeyCode::KeyR,
+            InputAction::Remove => KeyCode::KeyX,
+            InputAction::ToggleLanguage => KeyCode::KeyL,
+            InputAction::CameraUp => KeyCode::KeyW,
+            InputAction::CameraDown => KeyCode::KeyS,
+            InputAction::CameraLeft => KeyCode::KeyA,
+            InputAction::CameraRight => KeyCode::KeyD,
+            InputAction::Pause => KeyCode::Escape,
+            // Tab 保留给按钮焦点切换（读屏和确认对话框都依赖它），所以默认用 H（Heat）
+            InputAction::ToggleWaitHeatmap => KeyCode::KeyH,
+            InputAction::SimSlower => KeyCode::Minus,
+            InputAction::SimFaster => KeyCode::Equal,
+            InputAction::SimStep => KeyCode::Period,
+            InputAction::Quicksave => KeyCode::F5,
+            InputAction::Quickload => KeyCode::F9,
+        }
+    }
+
+    /// 不可更改的备用键（方向键、Delete 等），与绑定的按键同时生效
+    pub fn fixed_key(self) -> Option<KeyCode> {
+        match self {
+            InputAction::Rotate => Some(KeyCode::Space),
+            InputAction::Remove => Some(KeyCode::Delete),
+            InputAction::CameraUp => Some(KeyCode::ArrowUp),
+            InputAction::CameraDown => Some(KeyCode::ArrowDown),
+            InputAction::CameraLeft => Some(KeyCode::ArrowLeft),
+            InputAction::CameraRight => Some(KeyCode::ArrowRight),
+            InputAction::SimSlower => Some(KeyCode::NumpadSubtract),
+            InputAction::SimFaster => Some(KeyCode::NumpadAdd),
+            InputAction::ToggleLanguage
+            | InputAction::Pause
+            | InputAction::ToggleWaitHeatmap
+            | InputAction::SimStep
+            | InputAction::Quicksave
+            | InputAction::Quickload => None,
+        }
+    }
+
+    /// 用户设置文件中的键名
+    pub fn config_key(self) -> &'static str {
+        match self {
+            InputAction::Rotate => "rotate",
+            InputAction::Remove => "remove",
+            InputAction::ToggleLanguage => "toggle_language",
+            InputAction::CameraUp => "camera_up",
+            InputAction::CameraDown => "camera_down",
+            InputAction::CameraLeft => "camera_left",
+            InputAction::CameraRight => "camera_right",
+            InputAction::Pause => "pause",
+            InputAction::ToggleWaitHeatmap => "toggle_wait_heatmap",
+            InputAction::SimSlower => "sim_slower",
+            InputAction::SimFaster => "sim_faster",
+            InputAction::SimStep => "sim_step",
+            InputAction::Quicksave => "quicksave",
+            InputAction::Quickload => "quickload",
+        }
+    }
+
+    pub fn from_config_key(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.config_key() == name)
+    }
+
+    pub fn label(self) -> &'static LocalizedText {
+        match self {
+            InputAction::Rotate => &KEY_ROTATE,
+            InputAction::Remove => &KEY_REMOVE,
+            InputAction::ToggleLanguage => &KEY_TOGGLE_LANGUAGE,
+            InputAction::CameraUp => &KEY_CAMERA_UP,
+            InputAction::CameraDown => &KEY_CAMERA_DOWN,
+            InputAction::CameraLeft => &KEY_CAMERA_LEFT,
+            InputAction::CameraRight => &KEY_CAMERA_RIGHT,
+            InputAction::Pause => &KEY_PAUSE,
+            InputAction::ToggleWaitHeatmap => &KEY_WAIT_HEATMAP,
+            InputAction::SimSlower => &KEY_SIM_SLOWER,
+            InputAction::SimFaster => &KEY_SIM_FASTER,
+            InputAction::SimStep => &KEY_SIM_STEP,
+            InputAction::Quicksave => &KEY_QUICKSAVE,
+            InputAction::Quickload => &KEY_QUICKLOAD,
+        }
+    }
+}
+
+// ============ 按键映射资源 ============
+
+/// 所有游戏快捷键都通过该资源查询，设置菜单中可以重新绑定
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputMap {
+    bindings: HashMap<InputAction, KeyCode>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            bindings: InputAction::ALL
+                .into_iter()
+                .map(|action| (action, action.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl InputMap {
+    pub fn key(&self, action: InputAction) -> KeyCode {
+        self.bindings
+            .get(&action)
+            .copied()
+            .unwrap_or(action.default_key())
+    }
+
+    /// 绑定新按键；该键已被其他动作使用时两者互换，保证每个键只对应一个动作
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) {
+        let previous = self.key(action);
+        if let Some(other) = InputAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.key(*other) == key)
+        {
+            self.bindings.insert(other, previous);
+        }
+        self.bindings.insert(action, key);
+    }
+
+    pub fn pressed(&self, keyboard_input: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keyboard_input.pressed(self.key(action))
+            || action
+                .fixed_key()
+                .is_some_and(|key| keyboard_input.pressed(key))
+    }
+
+    pub fn just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keyboard_input.just_pressed(self.key(action))
+            || action
+                .fixed_key()
+                .is_some_and(|key| keyboard_input.just_pressed(key))
+    }
+
+    /// 界面上显示的按键名称，例如 R、Esc、↑
+    pub fn key_name(&self, action: InputAction) -> String {
+        key_display_name(self.key(action))
+    }
+}
+
+// ============ 按键名称 ============
+
+/// 可以绑定的按键；F5/F9 之外的功能键、方向键、Tab、空格和 1-4 已有固定用途，不在其中
+pub const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Escape,
+    KeyCode::Backspace,
+    KeyCode::Enter,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Insert,
+    KeyCode::F5,
+    KeyCode::F9,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Slash,
+    KeyCode::Backslash,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Backquote,
+];
+
+/// 设置文件中保存 KeyCode 的变体名（KeyR、Escape）
+pub fn key_config_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+pub fn parse_key_config_name(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| key_config_name(*key) == name)
+}
+
+pub fn key_display_name(key: KeyCode) -> String {
+    let name = key_config_name(key);
+    match key {
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Space => "Space".to_string(),
+        KeyCode::ArrowUp => "↑".to_string(),
+        KeyCode::ArrowDown => "↓".to_string(),
+        KeyCode::ArrowLeft => "←".to_string(),
+        KeyCode::ArrowRight => "→".to_string(),
+        KeyCode::Minus => "-".to_string(),
+        KeyCode::Equal => "=".to_string(),
+        KeyCode::Period => ".".to_string(),
+        KeyCode::NumpadSubtract => "Num-".to_string(),
+        KeyCode::NumpadAdd => "Num+".to_string(),
+        _ => name
+            .strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(&name)
+            .to_string(),
+    }
+}
+
+/// 设置菜单操作说明中的按键参数：旋转、拆除、镜头移动、暂停、语言
+pub fn controls_help_args(input_map: &InputMap) -> Vec<String> {
+    let camera_keys = [
+        InputAction::CameraUp,
+        InputAction::CameraLeft,
+        InputAction::CameraDown,
+        InputAction::CameraRight,
+    ]
+    .map(|action| input_map.key_name(action))
+    .concat();
+
+    vec![
+        input_map.key_name(InputAction::Rotate),
+        input_map.key_name(InputAction::Remove),
+        camera_keys,
+        input_map.key_name(InputAction::Pause),
+        input_map.key_name(InputAction::ToggleLanguage),
+    ]
+}
+
+// ============ 按键绑定界面组件 ============
+
+/// 按键绑定面板，作为设置菜单的子实体随设置菜单一起关闭
+#[derive(Component)]
+pub struct KeyBindingsUI;
+
+/// 绑定按钮上显示的按键名称
+#[derive(Component)]
+pub struct KeyBindingText(pub InputAction);
+
+/// 设置菜单中的操作说明，按键绑定变化时刷新
+#[derive(Component)]
+pub struct ControlsHelpText;
+
+/// 正在等待玩家按下新按键的动作
+#[derive(Resource, Default)]
+pub struct KeyRebinding {
+    pub waiting: Option<InputAction>,
+}
+
+// ============ 按键映射插件 ============
+
+pub struct InputMapPlugin;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<KeyRebinding>()
+            // 在所有 Update 系统之前读取新按键，并吞掉这次按键，避免同时触发原有功能
+            .add_systems(
+                PreUpdate,
+                capture_rebind_key
+                    .after(InputSystem)
+                    .run_if(in_state(GameStateEnum::MainMenu).or(in_state(GameStateEnum::Paused))),
+            )
+            .add_systems(
+                Update,
+                (handle_key_binding_buttons, update_key_binding_texts)
+                    .run_if(in_state(GameStateEnum::MainMenu).or(in_state(GameStateEnum::Paused))),
+            )
+            .add_systems(OnExit(GameStateEnum::MainMenu), cancel_rebinding)
+            .add_systems(OnExit(GameStateEnum::Paused), cancel_rebinding);
+    }
+}
+
+// ============ 按键绑定面板 ============
+
+fn spawn_key_bindings_panel(
+    commands: &mut Commands,
+    settings_menu: Entity,
+    ui_assets: &UIAssets,
+    input_map: &InputMap,
+    language: Language,
+) {
+    commands.entity(settings_menu).with_children(|parent| {
+        parent
+            .spawn((
+                Node {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ZIndex(1), // 叠加在设置面板之上
+                KeyBindingsUI,
+                Name::new("Key Bindings Panel"),
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            width: Px(760.0),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Px(8.0),
+                            padding: UiRect::all(Px(24.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+                        Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.4, 0.4, 0.6)),
+                    ))
+                    .with_children(|parent| {
+                        spawn_settings_label(parent, ui_assets, &KEY_BINDINGS, language, 24.0);
+                        parent.spawn((
+                            Text::new(get_text(&KEY_BINDINGS_HINT, language)),
+                            LocalizedTextComponent::new(&KEY_BINDINGS_HINT),
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                        ));
+
+                        // 动作较多，分两列排列，避免面板超出窗口高度
+                        parent
+                            .spawn(Node {
+                                width: Percent(100.0),
+                                flex_direction: FlexDirection::Row,
+                                flex_wrap: FlexWrap::Wrap,
+                                justify_content: JustifyContent::SpaceBetween,
+                                row_gap: Px(8.0),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for action in InputAction::ALL {
+                                    spawn_key_binding_row(
+                                        parent, ui_assets, input_map, action, language,
+                                    );
+                                }
+                            });
+
+                        spawn_settings_button(
+                            parent,
+                            ui_assets,
+                            ButtonType::ResetKeyBindings,
+                            Px(200.0),
+                            (
+                                Text::new(get_text(&RESET_KEY_BINDINGS, language)),
+                                LocalizedTextComponent::new(&RESET_KEY_BINDINGS),
+                            ),
+                        );
+                        spawn_settings_button(
+                            parent,
+                            ui_assets,
+                            ButtonType::CloseKeyBindings,
+                            Px(200.0),
+                            (
+                                Text::new(get_text(&BACK, language)),
+                                LocalizedTextComponent::new(&BACK),
+                            ),
+                        );
+                    });
+            });
+    });
+}
+
+fn spawn_key_binding_row(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    input_map: &InputMap,
+    action: InputAction,
+    language: Language,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::SpaceBetween,
+            width: Percent(48.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(action.label(), language)),
+                LocalizedTextComponent::new(action.label()),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            spawn_settings_button(
+                parent,
+                ui_assets,
+                ButtonType::RebindKey(action),
+                Px(140.0),
+                (
+                    key_binding_text(input_map, action, None, language),
+                    KeyBindingText(action),
+                ),
+            );
+        });
+}
+
+fn key_binding_text(
+    input_map: &InputMap,
+    action: InputAction,
+    waiting: Option<InputAction>,
+    language: Language,
+) -> Text {
+    if waiting == Some(action) {
+        return Text::new(get_text(&KEY_PRESS_NEW, language));
+    }
+
+    Text::new(match action.fixed_key() {
+        Some(fixed_key) => format!(
+            "{} / {}",
+            input_map.key_name(action),
+            key_display_name(fixed_key)
+        ),
+        None => input_map.key_name(action),
+    })
+}
+
+// ============ 按键绑定系统 ============
+
+fn handle_key_binding_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    settings_menus: Query<Entity, With<SettingsMenuUI>>,
+    key_binding_panels: Query<Entity, With<KeyBindingsUI>>,
+    mut input_map: ResMut<InputMap>,
+    mut rebinding: ResMut<KeyRebinding>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::OpenKeyBindings if key_binding_panels.is_empty() => {
+                let Some(settings_menu) = settings_menus.iter().next() else {
+                    continue;
+                };
+                spawn_key_bindings_panel(
+                    &mut commands,
+                    settings_menu,
+                    &ui_assets,
+                    &input_map,
+                    current_language.language,
+                );
+            }
+            ButtonType::CloseKeyBindings => {
+                rebinding.waiting = None;
+                for entity in key_binding_panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ButtonType::RebindKey(action) => {
+                info!("等待为 {:?} 绑定新按键", action);
+                rebinding.waiting = Some(action);
+            }
+            ButtonType::ResetKeyBindings => {
+                rebinding.waiting = None;
+                *input_map = InputMap::default();
+                info!("⌨️ 按键绑定已恢复默认");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 等待绑定时读取下一个按键；Esc 取消（暂停键保持原绑定）
+fn capture_rebind_key(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<KeyRebinding>,
+    mut input_map: ResMut<InputMap>,
+) {
+    let Some(action) = rebinding.waiting else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        keyboard_input.clear_just_pressed(KeyCode::Escape);
+        rebinding.waiting = None;
+        info!("取消按键绑定");
+        return;
+    }
+
+    let Some(key) = BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| keyboard_input.just_pressed(*key))
+    else {
+        return;
+    };
+
+    keyboard_input.clear_just_pressed(key);
+    input_map.rebind(action, key);
+    rebinding.waiting = None;
+    info!("⌨️ {:?} 绑定到 {}", action, key_display_name(key));
+}
+
+fn cancel_rebinding(mut rebinding: ResMut<KeyRebinding>) {
+    rebinding.waiting = None;
+}
+
+fn update_key_binding_texts(
+    input_map: Res<InputMap>,
+    rebinding: Res<KeyRebinding>,
+    current_language: Res<CurrentLanguage>,
+    mut binding_texts: Query<(&KeyBindingText, &mut Text)>,
+    mut help_texts: Query<
+        (&mut LocalizedTextComponent, &mut Text),
+        (With<ControlsHelpText>, Without<KeyBindingText>),
+    >,
+) {
+    if !input_map.is_changed() && !rebinding.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for (binding, mut text) in binding_texts.iter_mut() {
+        *text = key_binding_text(
+            &input_map,
+            binding.0,
+            rebinding.waiting,
+            current_language.language,
+        );
+    }
+
+    // 参数也要更新，之后切换语言时本地化系统才能用上新按键
+    if input_map.is_changed() {
+        for (mut localized, mut text) in help_texts.iter_mut() {
+            *localized =
+                LocalizedTextComponent::with_args(&CONTROLS_HELP, controls_help_args(&input_map));
+            *text = Text::new(localized.get_text(current_language.language));
+        }
+    }
+}
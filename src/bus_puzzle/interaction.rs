@@ -2,23 +2,85 @@
 
 // 使用相对路径引用同模块下的其他文件
 use crate::bus_puzzle::{
-    world_to_grid, AgentState, ButtonComponent, ButtonType, CameraController, CurrentLanguage,
-    DraggableSegment, GameState, GameStateEnum, GridPos, InputState, InventoryCountText,
-    InventorySlot, InventoryUpdatedEvent, Language, LevelCompletedEvent, LevelManager,
-    ObjectiveCompletedEvent, ObjectiveCondition, ObjectiveTracker, ObjectiveType, PathNode,
-    PathfindingAgent, PlacedSegment, RotationHintUI, RouteSegment, RouteSegmentType,
-    SegmentPlacedEvent, SegmentPreview, SegmentRemovedEvent,
+    build_pathfinding_graph, find_optimal_path, find_optimal_path_via, get_passenger_color,
+    get_text, get_text_with_args, is_valid_placement_on_layer, line_color, line_number,
+    objectives_enabled, passenger_color_name, shortcut_modifier_pressed, show_contextual_tip,
+    simulation_running, world_to_grid, ActiveChallenge, AgentState, BusPathfindingAgent, BusState,
+    BusVehicle, ButtonComponent, ButtonType, CameraController, CurrentLanguage, CustomLevel,
+    DraggableSegment, EconomyMode, ElevatedSegment, ExpressSegment, GameState, GameStateEnum,
+    GridPos, InputAction, InputMap, InputState, InventoryCountText, InventorySlot,
+    InventoryUpdatedEvent, Language, LevelCompletedEvent, LevelManager, LevelMutators,
+    NetworkLayer, ObjectiveCompletedEvent, ObjectiveCondition, ObjectiveTracker, ObjectiveType,
+    OnBus, PathfindingAgent, PathfindingGraph, PlacedSegment, RemoveSegmentRequestEvent,
+    RotateSegmentRequestEvent, RotationHintUI, RouteDrawing, RouteLines, RouteSegment,
+    RouteSegmentType, SegmentPlacedEvent, SegmentPreview, SegmentRemovedEvent, SegmentRotatedEvent,
+    SegmentSelection, SegmentUpgradedEvent, SelectedBus, SimClock, StationEntity, ThemeRegistry,
+    TipType, UIAssets, WaitingForBus, BUS_HOVER_LOAD, BUS_HOVER_NEXT_STOP, BUS_HOVER_TITLE,
+    BUS_STATE_AT_STOP, BUS_STATE_DRIVING, BUS_STATE_IDLE, BUS_STATE_TURNING, DEFAULT_FONT_PATH,
+    EFFECT_Z, ELEVATED_SEGMENT_Z, EXPRESS_LINE, EXPRESS_UPGRADED, EXPRESS_UPGRADE_COST,
+    INSPECT_PINNED, INSPECT_PIN_HINT, INSPECT_STATE_ARRIVED, INSPECT_STATE_GAVE_UP,
+    INSPECT_STATE_RIDING, INSPECT_STATE_TRANSFERRING, INSPECT_STATE_WAITING, INSUFFICIENT_FUNDS,
+    PARTIAL_REFUND_INFO, PASSENGER_HOVER_NO_PATH, PASSENGER_HOVER_PATIENCE, PASSENGER_HOVER_ROUTE,
+    PASSENGER_HOVER_VIA, REMOVALS_EXHAUSTED_WARNING, REMOVALS_LEFT_INFO, ROTATION_LOCKED_WARNING,
+    ROUTE_Z,
 };
 use bevy::{
-    input::mouse::MouseWheel,
+    input::{
+        mouse::{MouseScrollUnit, MouseWheel},
+        InputSystem,
+    },
     prelude::{Val::Px, *},
     window::PrimaryWindow,
 };
+use std::collections::HashSet;
+
+/// 快速路线的颜色，与悬停高亮叠加前的基础颜色
+const EXPRESS_SEGMENT_COLOR: Color = Color::srgb(1.0, 0.75, 0.35);
 
 // 悬停提示组件
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
 pub struct HoverTooltip;
 
+// 悬停乘客时高亮的起点、终点和规划路径格子
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct PassengerRouteHighlight;
+
+// 拖动绘制路线时沿路径显示的预览
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct RouteDrawingPreview;
+
+// 放置预览时试算出的乘客路径（虚线）
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct GhostPathPreview;
+
+// 从库存栏拖出路线段时跟随光标的半透明图标
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct InventoryDragGhost;
+
+/// 拖动中滚轮每滚过这么多像素（触控板）旋转 90°
+const DRAG_ROTATE_PIXELS: f32 = 60.0;
+
+/// 信息卡中耐心条的尺寸
+const PATIENCE_BAR_WIDTH: f32 = 160.0;
+const PATIENCE_BAR_HEIGHT: f32 = 8.0;
+
+/// 点击固定信息卡的乘客，再次点击它或点击空白处取消
+#[derive(Resource, Default)]
+pub struct InspectedPassenger(pub Option<Entity>);
+
+/// 试算路径虚线的线段长度和间隔（格子尺寸的比例）以及线宽
+const GHOST_DASH_LENGTH: f32 = 0.2;
+const GHOST_DASH_GAP: f32 = 0.12;
+const GHOST_DASH_WIDTH: f32 = 4.0;
+
+/// 镜头自动适配时地图四周留出的边距（格子数），避免边缘站点被界面面板遮住
+const CAMERA_FIT_MARGIN: f32 = 1.5;
+
 // ============ 插件定义 ============
 
 pub struct PuzzleInteractionPlugin;
@@ -27,17 +89,24 @@ impl Plugin for PuzzleInteractionPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(InputState::default())
             .insert_resource(CameraController::default())
+            .init_resource::<RouteDrawing>()
+            .init_resource::<InspectedPassenger>()
             .add_systems(
                 Update,
                 (
                     handle_segment_placement,
+                    handle_route_drawing,
+                    update_route_drawing_preview,
+                    handle_segment_upgrade,
                     handle_segment_rotation,
                     handle_segment_removal,
                     update_grid_preview,
-                    update_objectives,
+                    update_ghost_passenger_paths,
+                    update_objectives.run_if(objectives_enabled),
                     update_game_timer,
-                    handle_level_completion,
-                    handle_segment_hover_effects,       // 新增：悬停效果
+                    handle_level_completion.run_if(objectives_enabled),
+                    handle_segment_hover_effects, // 新增：悬停效果
+                    pin_inspected_passenger,
                     update_hover_tooltip,               // 新增：悬停提示
                     reset_preview_rotation_on_deselect, // 改进的取消选择
                     show_rotation_hint_ui,              // 中英文旋转提示UI
@@ -50,13 +119,33 @@ impl Plugin for PuzzleInteractionPlugin {
                     .run_if(in_state(GameStateEnum::Playing))
                     .run_if(not(is_paused)),
             )
+            // 松开左键时 handle_segment_placement 先放置，再结束拖放
+            .add_systems(
+                Update,
+                update_inventory_drag
+                    .after(handle_segment_placement)
+                    .run_if(in_state(GameStateEnum::Playing))
+                    .run_if(not(is_paused)),
+            )
+            // ESC 取消拖动绘制，需要在暂停和取消选择之前消耗按键
+            .add_systems(
+                PreUpdate,
+                cancel_route_drawing
+                    .after(InputSystem)
+                    .run_if(in_state(GameStateEnum::Playing)),
+            )
+            // 加载关卡后重新适配镜头
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_camera_fit)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_camera_fit)
             // 新增：在状态变化时清理选择
             .add_systems(OnEnter(GameStateEnum::Paused), clear_segment_selection)
+            .add_systems(OnExit(GameStateEnum::Playing), clear_inspected_passenger)
             .add_systems(OnEnter(GameStateEnum::MainMenu), clear_segment_selection)
             .add_systems(OnEnter(GameStateEnum::Loading), clear_segment_selection)
             .add_systems(
                 Update,
                 (
+                    fit_camera_to_level.run_if(simulation_running),
                     handle_camera_controls,
                     update_mouse_world_position,
                     // 将 handle_button_interactions 移动到全局，但添加状态检查
@@ -93,94 +182,162 @@ fn update_mouse_world_position(
     let window = windows.single()?;
     let (camera, camera_transform) = camera_query.single()?;
 
-    if let Some(cursor_pos) = window.cursor_position() {
-        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-            input_state.mouse_world_pos = world_pos.extend(0.0);
+    if let Some(cursor_pos) = window.cursor_position()
+        && let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos)
+    {
+        input_state.mouse_world_pos = world_pos.extend(0.0);
+
+        if let Some(level_data) = &game_state.current_level {
+            let grid_pos = world_to_grid(
+                input_state.mouse_world_pos,
+                level_manager.tile_size,
+                level_data.grid_size.0,
+                level_data.grid_size.1,
+            );
+            input_state.grid_cursor_pos = Some(grid_pos);
 
-            if let Some(level_data) = &game_state.current_level {
-                let grid_pos = world_to_grid(
+            // F10 调试坐标转换精度
+            if keyboard_input.just_pressed(KeyCode::F10) {
+                debug_coordinate_conversion(
                     input_state.mouse_world_pos,
                     level_manager.tile_size,
                     level_data.grid_size.0,
                     level_data.grid_size.1,
                 );
-                input_state.grid_cursor_pos = Some(grid_pos);
-
-                // F10 调试坐标转换精度
-                if keyboard_input.just_pressed(KeyCode::F10) {
-                    debug_coordinate_conversion(
-                        input_state.mouse_world_pos,
-                        level_manager.tile_size,
-                        level_data.grid_size.0,
-                        level_data.grid_size.1,
-                    );
 
-                    // 显示当前鼠标位置信息
-                    info!("鼠标调试信息:");
-                    info!("  屏幕坐标: {:?}", cursor_pos);
-                    info!("  世界坐标: {:?}", input_state.mouse_world_pos);
-                    info!("  网格坐标: {:?}", grid_pos);
-
-                    // 计算网格中心的世界坐标
-                    let grid_center_world = grid_pos.to_world_pos(
-                        level_manager.tile_size,
-                        level_data.grid_size.0,
-                        level_data.grid_size.1,
-                    );
-                    info!("  网格中心世界坐标: {:?}", grid_center_world);
+                // 显示当前鼠标位置信息
+                info!("鼠标调试信息:");
+                info!("  屏幕坐标: {:?}", cursor_pos);
+                info!("  世界坐标: {:?}", input_state.mouse_world_pos);
+                info!("  网格坐标: {:?}", grid_pos);
 
-                    let distance = input_state.mouse_world_pos.distance(grid_center_world);
-                    info!("  鼠标距离网格中心: {:.2} 像素", distance);
+                // 计算网格中心的世界坐标
+                let grid_center_world = grid_pos.to_world_pos(
+                    level_manager.tile_size,
+                    level_data.grid_size.0,
+                    level_data.grid_size.1,
+                );
+                info!("  网格中心世界坐标: {:?}", grid_center_world);
 
-                    if distance > level_manager.tile_size * 0.3 {
-                        warn!("  ⚠️ 鼠标距离网格中心较远，可能存在坐标转换问题");
-                    } else {
-                        info!("  ✅ 坐标转换正常");
-                    }
+                let distance = input_state.mouse_world_pos.distance(grid_center_world);
+                info!("  鼠标距离网格中心: {:.2} 像素", distance);
+
+                if distance > level_manager.tile_size * 0.3 {
+                    warn!("  ⚠️ 鼠标距离网格中心较远，可能存在坐标转换问题");
+                } else {
+                    info!("  ✅ 坐标转换正常");
                 }
-            } else {
-                let grid_pos =
-                    world_to_grid(input_state.mouse_world_pos, level_manager.tile_size, 10, 8);
-                input_state.grid_cursor_pos = Some(grid_pos);
             }
+        } else {
+            let grid_pos =
+                world_to_grid(input_state.mouse_world_pos, level_manager.tile_size, 10, 8);
+            input_state.grid_cursor_pos = Some(grid_pos);
         }
     }
 
     Ok(())
 }
 
+fn reset_camera_fit(mut camera_controller: ResMut<CameraController>) {
+    camera_controller.bounds = None;
+}
+
+/// 关卡加载后计算地图范围，移动并缩放镜头使整张地图可见
+fn fit_camera_to_level(
+    mut camera_controller: ResMut<CameraController>,
+    mut camera_query: Query<&mut Transform, With<Camera>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    game_state: Res<GameState>,
+    level_manager: Res<LevelManager>,
+) -> Result {
+    if camera_controller.bounds.is_some() {
+        return Ok(());
+    }
+    let Some(level_data) = &game_state.current_level else {
+        return Ok(());
+    };
+    let window = windows.single()?;
+    let mut camera_transform = camera_query.single_mut()?;
+
+    // 网格以原点为中心
+    let (grid_width, grid_height) = level_data.grid_size;
+    let map_size = Vec2::new(grid_width as f32, grid_height as f32) * level_manager.tile_size;
+    let bounds = Rect::from_center_size(
+        Vec2::ZERO,
+        map_size + Vec2::splat(CAMERA_FIT_MARGIN * 2.0 * level_manager.tile_size),
+    );
+
+    camera_controller.bounds = Some(bounds);
+    camera_controller.zoom = CameraController::fit_zoom(bounds, window.size())
+        .clamp(camera_controller.min_zoom, camera_controller.max_zoom);
+    camera_transform.scale = Vec3::splat(camera_controller.zoom);
+    camera_transform.translation = bounds.center().extend(camera_transform.translation.z);
+
+    info!(
+        "📷 镜头适配关卡 {}: 缩放 {:.2}",
+        level_data.id, camera_controller.zoom
+    );
+    Ok(())
+}
+
 fn handle_camera_controls(
     mut camera_controller: ResMut<CameraController>,
     mut camera_query: Query<&mut Transform, With<Camera>>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut input_state: ResMut<InputState>,
+    mut scroll_accumulator: Local<f32>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     time: Res<Time>,
 ) -> Result {
     let mut camera_transform = camera_query.single_mut()?;
+    let window_size = windows.single()?.size();
     let dt = time.delta_secs();
 
-    // 处理缩放
+    // 处理缩放，不能缩小到超出整张地图
+    let max_zoom = camera_controller
+        .bounds
+        .map_or(camera_controller.max_zoom, |bounds| {
+            CameraController::fit_zoom(bounds, window_size)
+                .clamp(camera_controller.min_zoom, camera_controller.max_zoom)
+        });
     for wheel_event in mouse_wheel_events.read() {
+        // 从库存栏拖动路线段时滚轮用于旋转，不缩放镜头
+        if input_state.is_dragging {
+            *scroll_accumulator += match wheel_event.unit {
+                MouseScrollUnit::Line => wheel_event.y.signum() * DRAG_ROTATE_PIXELS,
+                MouseScrollUnit::Pixel => wheel_event.y,
+            };
+            while scroll_accumulator.abs() >= DRAG_ROTATE_PIXELS {
+                let step = if *scroll_accumulator > 0.0 { 90 } else { 270 };
+                input_state.preview_rotation = (input_state.preview_rotation + step) % 360;
+                *scroll_accumulator -= DRAG_ROTATE_PIXELS.copysign(*scroll_accumulator);
+            }
+            continue;
+        }
+        *scroll_accumulator = 0.0;
+
         camera_controller.zoom *= 1.0 - wheel_event.y * camera_controller.zoom_speed;
         camera_controller.zoom = camera_controller
             .zoom
-            .clamp(camera_controller.min_zoom, camera_controller.max_zoom);
+            .clamp(camera_controller.min_zoom, max_zoom);
 
         camera_transform.scale = Vec3::splat(camera_controller.zoom);
     }
 
     // 处理平移
     let mut movement = Vec3::ZERO;
-    if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
+    if input_map.pressed(&keyboard_input, InputAction::CameraUp) {
         movement.y += 1.0;
     }
-    if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
+    if input_map.pressed(&keyboard_input, InputAction::CameraDown) {
         movement.y -= 1.0;
     }
-    if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
+    if input_map.pressed(&keyboard_input, InputAction::CameraLeft) {
         movement.x -= 1.0;
     }
-    if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
+    if input_map.pressed(&keyboard_input, InputAction::CameraRight) {
         movement.x += 1.0;
     }
 
@@ -190,6 +347,12 @@ fn handle_camera_controls(
             movement * camera_controller.pan_speed * dt * camera_controller.zoom;
     }
 
+    // 镜头不能移到地图以外的空白区域
+    let clamped =
+        camera_controller.clamp_to_bounds(camera_transform.translation.truncate(), window_size);
+    camera_transform.translation.x = clamped.x;
+    camera_transform.translation.y = clamped.y;
+
     Ok(())
 }
 
@@ -199,10 +362,11 @@ fn handle_button_interactions(
     button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
     mut input_state: ResMut<InputState>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
-    mut app_exit_events: EventWriter<AppExit>,
     mut level_manager: ResMut<LevelManager>,
     game_state: Res<GameState>,
     current_state: Res<State<GameStateEnum>>,
+    active_challenge: Res<ActiveChallenge>,
+    custom_level: Res<CustomLevel>,
 ) {
     for (interaction, button_component) in button_query.iter() {
         if matches!(*interaction, Interaction::Pressed) {
@@ -211,9 +375,6 @@ fn handle_button_interactions(
                 ButtonType::StartGame => {
                     next_state.set(GameStateEnum::Playing);
                 }
-                ButtonType::QuitGame => {
-                    app_exit_events.write(AppExit::Success);
-                }
                 ButtonType::PauseGame => {
                     next_state.set(GameStateEnum::Paused);
                 }
@@ -226,10 +387,15 @@ fn handle_button_interactions(
                 ButtonType::MainMenu => {
                     next_state.set(GameStateEnum::MainMenu);
                 }
+                ButtonType::NextLevel
+                    if active_challenge.0.is_some() || custom_level.0.is_some() =>
+                {
+                    next_state.set(GameStateEnum::MainMenu);
+                }
                 ButtonType::NextLevel => {
                     info!("next level");
-                    level_manager.current_level_index += 1;
-                    if level_manager.current_level_index < level_manager.available_levels.len() {
+                    if level_manager.has_next_level() {
+                        level_manager.current_level_index += 1;
                         next_state.set(GameStateEnum::Loading);
                     } else {
                         next_state.set(GameStateEnum::MainMenu);
@@ -269,110 +435,550 @@ fn handle_segment_placement(
     mut game_state: ResMut<GameState>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     mut segment_placed_events: EventWriter<SegmentPlacedEvent>,
     mut inventory_updated_events: EventWriter<InventoryUpdatedEvent>,
     level_manager: Res<LevelManager>,
+    route_drawing: Res<RouteDrawing>,
+    economy: Res<EconomyMode>,
+    ui_buttons: Query<&Interaction, With<Button>>,
 ) {
-    if mouse_button_input.just_released(MouseButton::Left) {
-        if let (Some(segment_type), Some(grid_pos)) =
+    // 点击UI按钮（例如触屏底部库存栏）时不放置路线段
+    if ui_buttons
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    // 拖过多个格子时由 handle_route_drawing 沿路径放置
+    if route_drawing.path.len() > 1 {
+        return;
+    }
+
+    if mouse_button_input.just_released(MouseButton::Left)
+        && let (Some(segment_type), Some(grid_pos)) =
             (input_state.selected_segment, input_state.grid_cursor_pos)
-        {
-            if is_valid_placement(&game_state, grid_pos, &segment_type) {
-                if let Some(&available_count) = game_state.player_inventory.get(&segment_type) {
-                    if available_count > 0 {
-                        // 使用预览旋转角度
-                        let rotation = input_state.preview_rotation;
-                        let cost = segment_type.get_cost();
-
-                        let entity = spawn_route_segment(
-                            &mut commands,
-                            &asset_server,
-                            grid_pos,
-                            segment_type,
-                            rotation,
-                            &level_manager,
-                        );
-
-                        game_state.placed_segments.insert(
-                            grid_pos,
-                            PlacedSegment {
-                                segment_type,
-                                rotation,
-                                entity,
-                                cost,
-                            },
-                        );
-
-                        game_state.total_cost += cost;
-                        *game_state.player_inventory.get_mut(&segment_type).unwrap() -= 1;
-
-                        segment_placed_events.write(SegmentPlacedEvent {
-                            position: grid_pos,
+    {
+        let layer = input_state.active_layer;
+        if is_valid_placement_on_layer(&game_state, grid_pos, &segment_type, layer) {
+            if let Some(&available_count) = game_state.player_inventory.get(&segment_type) {
+                // 经营模式的库存按地面价格折算，高架段还要再确认余额
+                let cost = layer.segment_cost(segment_type);
+                if available_count > 0 && economy.can_afford(&game_state, cost) {
+                    // 使用预览旋转角度
+                    let rotation = input_state.preview_rotation;
+
+                    insert_placed_segment(
+                        &mut commands,
+                        &mut game_state,
+                        &asset_server,
+                        &theme,
+                        &level_manager,
+                        layer,
+                        grid_pos,
+                        PlacedSegment {
                             segment_type,
                             rotation,
-                        });
+                            entity: Entity::PLACEHOLDER,
+                            cost,
+                            express: false,
+                        },
+                        &mut segment_placed_events,
+                    );
 
-                        inventory_updated_events.write(InventoryUpdatedEvent {
-                            segment_type,
-                            new_count: game_state.player_inventory[&segment_type],
-                        });
+                    game_state.total_cost += cost;
+                    *game_state.player_inventory.get_mut(&segment_type).unwrap() -= 1;
 
-                        info!(
-                            "在 {:?} 放置了 {:?}，旋转角度: {}°",
-                            grid_pos, segment_type, rotation
-                        );
+                    inventory_updated_events.write(InventoryUpdatedEvent {
+                        segment_type,
+                        new_count: game_state.player_inventory[&segment_type],
+                    });
 
-                        // 放置后重置预览旋转
-                        input_state.preview_rotation = 0;
-                    } else {
-                        warn!("库存不足：{:?}", segment_type);
-                    }
+                    info!(
+                        "在 {:?} 放置了 {:?}，旋转角度: {}°，图层: {:?}",
+                        grid_pos, segment_type, rotation, layer
+                    );
+
+                    // 放置后重置预览旋转
+                    input_state.preview_rotation = 0;
                 } else {
-                    warn!("没有 {:?} 类型的路线段", segment_type);
+                    warn!("库存或资金不足：{:?}", segment_type);
                 }
             } else {
-                warn!("无法在 {:?} 放置 {:?}", grid_pos, segment_type);
+                warn!("没有 {:?} 类型的路线段", segment_type);
+            }
+        } else {
+            warn!("无法在 {:?} 放置 {:?}", grid_pos, segment_type);
+        }
+    }
+}
+
+// ============ 拖动绘制路线 ============
+
+/// 选中直线段或弯道时按住左键拖动，沿经过的格子记录路径；松开后自动放置并选择正确的旋转
+fn handle_route_drawing(
+    mut commands: Commands,
+    mut route_drawing: ResMut<RouteDrawing>,
+    input_state: Res<InputState>,
+    mut game_state: ResMut<GameState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+    mut segment_placed_events: EventWriter<SegmentPlacedEvent>,
+    mut inventory_updated_events: EventWriter<InventoryUpdatedEvent>,
+    economy: Res<EconomyMode>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+) {
+    if mouse_button_input.just_released(MouseButton::Left) {
+        let path = std::mem::take(&mut route_drawing.path);
+        route_drawing.cancelled = false;
+        if path.len() < 2 {
+            return;
+        }
+
+        let layer = input_state.active_layer;
+        let mut placed = 0;
+        for index in 0..path.len() {
+            let grid_pos = path[index];
+            let Some((segment_type, rotation)) = route_piece_for(&path, index) else {
+                continue;
+            };
+
+            // 已有路线段、站点和无法建设的格子保持原样
+            let is_station = game_state.current_level.as_ref().is_some_and(|level_data| {
+                level_data
+                    .stations
+                    .iter()
+                    .any(|station| station.position == grid_pos)
+            });
+            if is_station
+                || !is_valid_placement_on_layer(&game_state, grid_pos, &segment_type, layer)
+            {
+                continue;
+            }
+
+            let available_count = game_state
+                .player_inventory
+                .get(&segment_type)
+                .copied()
+                .unwrap_or(0);
+            if available_count == 0 {
+                warn!(
+                    "库存不足：{:?}，路线绘制在 {:?} 停止",
+                    segment_type, grid_pos
+                );
+                break;
+            }
+
+            // 同一次拖动会消耗多种路线段，库存按帧折算，需逐段确认余额
+            let cost = layer.segment_cost(segment_type);
+            if !economy.can_afford(&game_state, cost) {
+                warn!(
+                    "资金不足：{:?}，路线绘制在 {:?} 停止",
+                    segment_type, grid_pos
+                );
+                break;
             }
+
+            insert_placed_segment(
+                &mut commands,
+                &mut game_state,
+                &asset_server,
+                &theme,
+                &level_manager,
+                layer,
+                grid_pos,
+                PlacedSegment {
+                    segment_type,
+                    rotation,
+                    entity: Entity::PLACEHOLDER,
+                    cost,
+                    express: false,
+                },
+                &mut segment_placed_events,
+            );
+            game_state.total_cost += cost;
+            *game_state.player_inventory.get_mut(&segment_type).unwrap() -= 1;
+            inventory_updated_events.write(InventoryUpdatedEvent {
+                segment_type,
+                new_count: game_state.player_inventory[&segment_type],
+            });
+            placed += 1;
         }
+
+        info!(
+            "沿 {} 格路径绘制路线，放置了 {} 个路线段",
+            path.len(),
+            placed
+        );
+        return;
+    }
+
+    let drawable = matches!(
+        input_state.selected_segment,
+        Some(RouteSegmentType::Straight | RouteSegmentType::Curve)
+    );
+    if !drawable || route_drawing.cancelled || !mouse_button_input.pressed(MouseButton::Left) {
+        if !route_drawing.path.is_empty() {
+            route_drawing.path.clear();
+        }
+        return;
+    }
+
+    let Some(cursor) = input_state.grid_cursor_pos else {
+        return;
+    };
+
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        // 从UI按钮上开始的拖动不绘制路线
+        if ui_buttons
+            .iter()
+            .all(|interaction| *interaction == Interaction::None)
+        {
+            route_drawing.path = vec![cursor];
+        }
+        return;
+    }
+
+    if route_drawing
+        .path
+        .last()
+        .is_some_and(|last| *last != cursor)
+    {
+        extend_route_path(&mut route_drawing.path, cursor);
+    }
+}
+
+/// 光标移到新格子时延长路径：回到路径上的格子时撤销到该格，跳格时先横向后纵向补齐
+fn extend_route_path(path: &mut Vec<GridPos>, cursor: GridPos) {
+    if let Some(index) = path.iter().position(|pos| *pos == cursor) {
+        path.truncate(index + 1);
+        return;
+    }
+
+    let Some(&last) = path.last() else {
+        return;
+    };
+    let mut current = last;
+    while current != cursor {
+        current = if current.x != cursor.x {
+            GridPos::new(current.x + (cursor.x - current.x).signum(), current.y)
+        } else {
+            GridPos::new(current.x, current.y + (cursor.y - current.y).signum())
+        };
+        // 路径不能与自身相交
+        if path.contains(&current) {
+            return;
+        }
+        path.push(current);
+    }
+}
+
+/// 根据前后相邻格子选择直线段或弯道及其旋转，路径两端沿相邻方向放直线段
+fn route_piece_for(path: &[GridPos], index: usize) -> Option<(RouteSegmentType, u32)> {
+    let position = path[index];
+    let offset_to = |other: GridPos| (other.x - position.x, other.y - position.y);
+    let previous = index.checked_sub(1).map(|i| path[i]);
+    let next = path.get(index + 1).copied();
+
+    let (from, to) = match (previous, next) {
+        (Some(previous), Some(next)) => (offset_to(previous), offset_to(next)),
+        (Some(neighbor), None) | (None, Some(neighbor)) => {
+            let (dx, dy) = offset_to(neighbor);
+            ((dx, dy), (-dx, -dy))
+        }
+        (None, None) => return None,
+    };
+
+    [RouteSegmentType::Straight, RouteSegmentType::Curve]
+        .into_iter()
+        .find_map(|segment_type| {
+            [0, 90, 180, 270]
+                .into_iter()
+                .find(|rotation| {
+                    let offsets = segment_type.get_connection_offsets(*rotation);
+                    offsets.contains(&from) && offsets.contains(&to)
+                })
+                .map(|rotation| (segment_type, rotation))
+        })
+}
+
+/// 拖动过程中按 ESC 放弃本次绘制，按键不再触发取消选择或暂停
+fn cancel_route_drawing(
+    mut route_drawing: ResMut<RouteDrawing>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+) {
+    if route_drawing.path.is_empty() || !input_map.just_pressed(&keyboard_input, InputAction::Pause)
+    {
+        return;
+    }
+
+    route_drawing.path.clear();
+    route_drawing.cancelled = true;
+    keyboard_input.clear_just_pressed(input_map.key(InputAction::Pause));
+    info!("已取消路线绘制");
+}
+
+fn update_route_drawing_preview(
+    mut commands: Commands,
+    route_drawing: Res<RouteDrawing>,
+    input_state: Res<InputState>,
+    game_state: Res<GameState>,
+    existing_previews: Query<Entity, With<RouteDrawingPreview>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+) {
+    if !route_drawing.is_changed() {
+        return;
+    }
+
+    for entity in existing_previews.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if route_drawing.path.len() < 2 {
+        return;
+    }
+
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    for (index, grid_pos) in route_drawing.path.iter().enumerate() {
+        let Some((segment_type, rotation)) = route_piece_for(&route_drawing.path, index) else {
+            continue;
+        };
+
+        let color = if is_valid_placement_on_layer(
+            &game_state,
+            *grid_pos,
+            &segment_type,
+            input_state.active_layer,
+        ) {
+            Color::srgba(0.3, 0.7, 1.0, 0.6) // 蓝色半透明：将放置
+        } else {
+            Color::srgba(1.0, 1.0, 1.0, 0.2) // 淡色：已有路线段或无法建设，跳过
+        };
+
+        let world_pos = grid_pos.to_world_pos(
+            level_manager.tile_size,
+            level_data.grid_size.0,
+            level_data.grid_size.1,
+        );
+        commands.spawn((
+            Sprite {
+                image: asset_server.load(theme.route(&segment_type)),
+                color,
+                ..default()
+            },
+            Transform::from_translation(world_pos + Vec3::Z * 0.1).with_rotation(
+                Quat::from_rotation_z((rotation as f32) * std::f32::consts::PI / 180.0),
+            ),
+            RouteDrawingPreview,
+        ));
     }
 }
 
+/// 未选中库存路线段时，点击已放置的直线段将其升级为快速路线
+pub fn handle_segment_upgrade(
+    mut commands: Commands,
+    input_state: Res<InputState>,
+    selection: Res<SegmentSelection>,
+    mut game_state: ResMut<GameState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    economy: Res<EconomyMode>,
+    mut upgraded_events: EventWriter<SegmentUpgradedEvent>,
+) {
+    // 高架路线段本身就是快速路线，不需要升级
+    if !mouse_button_input.just_released(MouseButton::Left)
+        || input_state.selected_segment.is_some()
+        || selection.is_busy()
+        || input_state.active_layer == NetworkLayer::Elevated
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    let Some(grid_pos) = input_state.grid_cursor_pos else {
+        return;
+    };
+    if !game_state
+        .placed_segments
+        .get(&grid_pos)
+        .is_some_and(|placed| placed.segment_type == RouteSegmentType::Straight && !placed.express)
+    {
+        return;
+    }
+
+    // 经营模式下升级费用从资金中扣除
+    if !economy.can_afford(&game_state, EXPRESS_UPGRADE_COST) {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(&INSUFFICIENT_FUNDS, current_language.language),
+            TipType::Warning,
+            2.0,
+        );
+        return;
+    }
+
+    if !upgrade_to_express(&mut commands, &mut game_state, grid_pos) {
+        return;
+    }
+    upgraded_events.write(SegmentUpgradedEvent { position: grid_pos });
+
+    show_contextual_tip(
+        &mut commands,
+        &ui_assets,
+        &get_text_with_args(
+            &EXPRESS_UPGRADED,
+            current_language.language,
+            &[&EXPRESS_UPGRADE_COST.to_string()],
+        ),
+        TipType::Strategy,
+        2.0,
+    );
+    info!(
+        "🚄 {:?} 的直线段升级为快速路线，花费 {}",
+        grid_pos, EXPRESS_UPGRADE_COST
+    );
+}
+
+/// 生成路线段实体、登记到图层并发出放置事件；entity 字段由新实体填充。
+/// 手动放置、拖动绘制、剪贴板和回放共用，花费和库存由调用方处理
+pub fn insert_placed_segment(
+    commands: &mut Commands,
+    game_state: &mut GameState,
+    asset_server: &Res<AssetServer>,
+    theme: &ThemeRegistry,
+    level_manager: &LevelManager,
+    layer: NetworkLayer,
+    pos: GridPos,
+    mut placed: PlacedSegment,
+    placed_events: &mut EventWriter<SegmentPlacedEvent>,
+) {
+    placed.entity = spawn_route_segment(
+        commands,
+        asset_server,
+        theme,
+        pos,
+        placed.segment_type,
+        placed.rotation,
+        level_manager,
+        layer,
+    );
+    if placed.express {
+        commands.entity(placed.entity).insert(ExpressSegment);
+    }
+
+    placed_events.write(SegmentPlacedEvent {
+        position: pos,
+        segment_type: placed.segment_type,
+        rotation: placed.rotation,
+        layer,
+    });
+    game_state.segments_on_mut(layer).insert(pos, placed);
+}
+
+/// 把地面层的直线段升级为快速路线并计入花费；不是未升级的直线段时返回 false
+pub fn upgrade_to_express(
+    commands: &mut Commands,
+    game_state: &mut GameState,
+    pos: GridPos,
+) -> bool {
+    let Some(placed_segment) = game_state
+        .placed_segments
+        .get_mut(&pos)
+        .filter(|placed| placed.segment_type == RouteSegmentType::Straight && !placed.express)
+    else {
+        return false;
+    };
+
+    placed_segment.express = true;
+    placed_segment.cost += EXPRESS_UPGRADE_COST;
+    commands
+        .entity(placed_segment.entity)
+        .insert(ExpressSegment);
+    game_state.total_cost += EXPRESS_UPGRADE_COST;
+    true
+}
+
 fn handle_segment_rotation(
+    mut commands: Commands,
     mut input_state: ResMut<InputState>,
     mut game_state: ResMut<GameState>,
     mut route_segments: Query<(&mut Transform, &mut RouteSegment)>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     keyboard_input: Res<ButtonInput<KeyCode>>, // 新增：键盘输入
+    input_map: Res<InputMap>,
+    mut rotate_requests: EventReader<RotateSegmentRequestEvent>,
+    mut segment_rotated_events: EventWriter<SegmentRotatedEvent>,
+    mutators: Res<LevelMutators>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
 ) {
+    // 触屏旋转按钮带有目标格子，优先于光标位置
+    let touch_request = rotate_requests
+        .read()
+        .last()
+        .map(|request| request.position);
+
     let should_rotate = mouse_button_input.just_pressed(MouseButton::Right)
-        || keyboard_input.just_pressed(KeyCode::KeyR)
-        || keyboard_input.just_pressed(KeyCode::Space); // 多种旋转方式
-
-    if should_rotate {
-        if let Some(grid_pos) = input_state.grid_cursor_pos {
-            // 检查是否有已放置的路线段
-            if let Some(placed_segment) = game_state.placed_segments.get_mut(&grid_pos) {
-                // 旋转已放置的路线段
-                placed_segment.rotation = (placed_segment.rotation + 90) % 360;
-
-                if let Ok((mut transform, mut route_segment)) =
-                    route_segments.get_mut(placed_segment.entity)
-                {
-                    route_segment.rotation = placed_segment.rotation;
-                    transform.rotation = Quat::from_rotation_z(
-                        (placed_segment.rotation as f32) * std::f32::consts::PI / 180.0,
-                    );
-                }
+        || input_map.just_pressed(&keyboard_input, InputAction::Rotate) // 绑定的按键或空格
+        || touch_request.is_some();
 
-                info!(
-                    "旋转已放置路线段到 {} 度，位置 {:?}",
-                    placed_segment.rotation, grid_pos
+    if should_rotate && let Some(grid_pos) = touch_request.flatten().or(input_state.grid_cursor_pos)
+    {
+        // 检查当前图层上是否有已放置的路线段
+        let layer = input_state.active_layer;
+        if let Some(placed_segment) = game_state.segments_on_mut(layer).get_mut(&grid_pos) {
+            // 规则变体：放置后禁止旋转
+            if !mutators.allows_rotation() {
+                show_contextual_tip(
+                    &mut commands,
+                    &ui_assets,
+                    &get_text(&ROTATION_LOCKED_WARNING, current_language.language),
+                    TipType::Warning,
+                    2.0,
                 );
-            } else if input_state.selected_segment.is_some() {
-                // 旋转预览中的路线段
-                input_state.preview_rotation = (input_state.preview_rotation + 90) % 360;
-                info!("旋转预览路线段到 {} 度", input_state.preview_rotation);
+                return;
             }
+
+            // 旋转已放置的路线段
+            placed_segment.rotation = (placed_segment.rotation + 90) % 360;
+
+            if let Ok((mut transform, mut route_segment)) =
+                route_segments.get_mut(placed_segment.entity)
+            {
+                route_segment.rotation = placed_segment.rotation;
+                transform.rotation = Quat::from_rotation_z(
+                    (placed_segment.rotation as f32) * std::f32::consts::PI / 180.0,
+                );
+            }
+
+            segment_rotated_events.write(SegmentRotatedEvent {
+                position: grid_pos,
+                rotation: placed_segment.rotation,
+                layer,
+            });
+
+            info!(
+                "旋转已放置路线段到 {} 度，位置 {:?}",
+                placed_segment.rotation, grid_pos
+            );
+        } else if input_state.selected_segment.is_some() {
+            // 旋转预览中的路线段
+            input_state.preview_rotation = (input_state.preview_rotation + 90) % 360;
+            info!("旋转预览路线段到 {} 度", input_state.preview_rotation);
         }
     }
 }
@@ -381,33 +987,264 @@ fn handle_segment_removal(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
     input_state: Res<InputState>,
     mut segment_removed_events: EventWriter<SegmentRemovedEvent>,
     mut inventory_updated_events: EventWriter<InventoryUpdatedEvent>,
+    mut remove_requests: EventReader<RemoveSegmentRequestEvent>,
+    mut mutators: ResMut<LevelMutators>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Delete) || keyboard_input.just_pressed(KeyCode::KeyX) {
-        if let Some(grid_pos) = input_state.grid_cursor_pos {
-            if let Some(placed_segment) = game_state.placed_segments.remove(&grid_pos) {
-                commands.entity(placed_segment.entity).despawn();
-
-                *game_state
-                    .player_inventory
-                    .entry(placed_segment.segment_type)
-                    .or_insert(0) += 1;
-                game_state.total_cost -= placed_segment.cost;
-
-                segment_removed_events.write(SegmentRemovedEvent { position: grid_pos });
-                inventory_updated_events.write(InventoryUpdatedEvent {
-                    segment_type: placed_segment.segment_type,
-                    new_count: game_state.player_inventory[&placed_segment.segment_type],
-                });
+    let touch_request = remove_requests
+        .read()
+        .last()
+        .map(|request| request.position);
+
+    // Ctrl+X 是框选移动，不拆除光标下的路线段
+    let remove_pressed = input_map.just_pressed(&keyboard_input, InputAction::Remove)
+        && !shortcut_modifier_pressed(&keyboard_input);
+    if (remove_pressed || touch_request.is_some())
+        && let Some(grid_pos) = touch_request.or(input_state.grid_cursor_pos)
+    {
+        let layer = input_state.active_layer;
+        if !game_state.segments_on(layer).contains_key(&grid_pos) {
+            return;
+        }
+
+        // 规则变体：限制拆除次数
+        if !mutators.try_use_removal() {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text(&REMOVALS_EXHAUSTED_WARNING, current_language.language),
+                TipType::Warning,
+                2.0,
+            );
+            return;
+        }
+
+        if mutators.limited_removals {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text_with_args(
+                    &REMOVALS_LEFT_INFO,
+                    current_language.language,
+                    &[&mutators.removals_left().to_string()],
+                ),
+                TipType::Controls,
+                2.0,
+            );
+        }
 
-                info!("移除了在 {:?} 的路线段", grid_pos);
+        if let Some(placed_segment) = game_state.segments_on_mut(layer).remove(&grid_pos) {
+            commands.entity(placed_segment.entity).despawn();
+
+            *game_state
+                .player_inventory
+                .entry(placed_segment.segment_type)
+                .or_insert(0) += 1;
+
+            // 按关卡的退款规则退还成本，后期关卡拆除会有损失
+            let refund =
+                game_state
+                    .current_level
+                    .as_ref()
+                    .map_or(placed_segment.cost, |level_data| {
+                        level_data
+                            .scoring
+                            .refund_policy
+                            .refund_for(placed_segment.cost)
+                    });
+            game_state.total_cost -= refund;
+            if refund < placed_segment.cost {
+                show_contextual_tip(
+                    &mut commands,
+                    &ui_assets,
+                    &get_text_with_args(
+                        &PARTIAL_REFUND_INFO,
+                        current_language.language,
+                        &[&refund.to_string(), &placed_segment.cost.to_string()],
+                    ),
+                    TipType::Warning,
+                    2.0,
+                );
             }
+
+            segment_removed_events.write(SegmentRemovedEvent {
+                position: grid_pos,
+                layer,
+            });
+            inventory_updated_events.write(InventoryUpdatedEvent {
+                segment_type: placed_segment.segment_type,
+                new_count: game_state.player_inventory[&placed_segment.segment_type],
+            });
+
+            info!("移除了在 {:?} 的路线段", grid_pos);
         }
     }
 }
 
+/// 试算刷新的依据：预览位置、类型、旋转，以及路网和乘客数量
+type GhostPreviewKey = (GridPos, RouteSegmentType, u32, usize, u32, usize);
+
+/// 预览路线段时在临时寻路图上试算：假设放下这一段，候车乘客会怎么走，
+/// 用虚线画出经过新路线段的路径，放置前就能看到效果
+fn update_ghost_passenger_paths(
+    mut commands: Commands,
+    input_state: Res<InputState>,
+    game_state: Res<GameState>,
+    route_lines: Res<RouteLines>,
+    level_manager: Res<LevelManager>,
+    route_segments: Query<(&RouteSegment, Has<ExpressSegment>), Without<ElevatedSegment>>,
+    elevated_segments: Query<&RouteSegment, With<ElevatedSegment>>,
+    stations: Query<&StationEntity>,
+    agents: Query<&PathfindingAgent>,
+    existing_ghosts: Query<Entity, With<GhostPathPreview>>,
+    mut last_key: Local<Option<GhostPreviewKey>>,
+) {
+    let preview = input_state
+        .selected_segment
+        .zip(input_state.grid_cursor_pos)
+        .filter(|(segment_type, grid_pos)| {
+            is_valid_placement_with_rotation(
+                &game_state,
+                *grid_pos,
+                segment_type,
+                input_state.preview_rotation,
+                input_state.active_layer,
+            )
+        });
+
+    // 预览和路网都没变时沿用上次的试算结果
+    let key = preview.map(|(segment_type, grid_pos)| {
+        (
+            grid_pos,
+            segment_type,
+            input_state.preview_rotation,
+            game_state.segment_count(),
+            route_lines.revision,
+            agents.iter().len(),
+        )
+    });
+    if key == *last_key {
+        return;
+    }
+    *last_key = key;
+
+    for entity in existing_ghosts.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let (Some((segment_type, grid_pos)), Some(level_data)) = (preview, &game_state.current_level)
+    else {
+        return;
+    };
+
+    // 预览段加入当前图层的临时寻路图，地面路线段按当前绘制线路分组
+    let ghost_segment = RouteSegment {
+        grid_pos,
+        segment_type,
+        rotation: input_state.preview_rotation,
+        is_active: true,
+    };
+    let ghost_on_elevated = input_state.active_layer == NetworkLayer::Elevated;
+    let mut ghost_lines = route_lines.clone();
+    if !ghost_on_elevated {
+        ghost_lines
+            .assignments
+            .insert(grid_pos, route_lines.active_line);
+    }
+
+    let mut ghost_graph = PathfindingGraph::default();
+    build_pathfinding_graph(
+        &mut ghost_graph,
+        stations.iter().map(|station| &station.station_data),
+        route_segments
+            .iter()
+            .chain((!ghost_on_elevated).then_some((&ghost_segment, false))),
+        elevated_segments
+            .iter()
+            .chain(ghost_on_elevated.then_some(&ghost_segment)),
+        &ghost_lines,
+    );
+
+    let tile_size = level_manager.tile_size;
+    let (grid_width, grid_height) = level_data.grid_size;
+    let mut drawn_trips = HashSet::new();
+
+    for agent in agents.iter() {
+        if agent.state != AgentState::WaitingAtStation {
+            continue;
+        }
+
+        // 起终点相同的乘客只画一条
+        let trip = (
+            agent.origin.clone(),
+            agent.via.clone(),
+            agent.destination.clone(),
+        );
+        if drawn_trips.contains(&trip) {
+            continue;
+        }
+
+        let path = match &agent.via {
+            Some(via) => {
+                find_optimal_path_via(&ghost_graph, &agent.origin, via, &agent.destination)
+            }
+            None => find_optimal_path(&ghost_graph, &agent.origin, &agent.destination),
+        };
+        let Some(path) = path.filter(|path| path.iter().any(|node| node.position == grid_pos))
+        else {
+            continue;
+        };
+        drawn_trips.insert(trip);
+
+        let color = get_passenger_color(agent.color).with_alpha(0.85);
+        for pair in path.windows(2) {
+            let start = pair[0]
+                .position
+                .to_world_pos(tile_size, grid_width, grid_height);
+            let end = pair[1]
+                .position
+                .to_world_pos(tile_size, grid_width, grid_height);
+            spawn_dashed_line(&mut commands, start, end, tile_size, color);
+        }
+    }
+}
+
+fn spawn_dashed_line(
+    commands: &mut Commands,
+    start: Vec3,
+    end: Vec3,
+    tile_size: f32,
+    color: Color,
+) {
+    let delta = (end - start).truncate();
+    let length = delta.length();
+    if length <= f32::EPSILON {
+        return;
+    }
+
+    let direction = delta / length;
+    let rotation = Quat::from_rotation_z(direction.y.atan2(direction.x));
+    let dash_length = GHOST_DASH_LENGTH * tile_size;
+    let step = dash_length + GHOST_DASH_GAP * tile_size;
+
+    let mut offset = 0.0;
+    while offset < length {
+        let dash = dash_length.min(length - offset);
+        let center = start.truncate() + direction * (offset + dash / 2.0);
+        commands.spawn((
+            Sprite::from_color(color, Vec2::new(dash, GHOST_DASH_WIDTH)),
+            Transform::from_translation(center.extend(EFFECT_Z)).with_rotation(rotation),
+            GhostPathPreview,
+        ));
+        offset += step;
+    }
+}
+
 // 改进的预览验证函数，支持旋转预览
 fn update_grid_preview(
     mut commands: Commands,
@@ -415,7 +1252,9 @@ fn update_grid_preview(
     game_state: Res<GameState>,
     existing_previews: Query<Entity, With<SegmentPreview>>,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     level_manager: Res<LevelManager>,
+    ui_assets: Res<UIAssets>,
 ) {
     // 清除现有预览
     for entity in existing_previews.iter() {
@@ -427,11 +1266,13 @@ fn update_grid_preview(
         (input_state.selected_segment, input_state.grid_cursor_pos)
     {
         // 使用增强的验证函数，考虑旋转角度
+        let layer = input_state.active_layer;
         let is_valid = is_valid_placement_with_rotation(
             &game_state,
             grid_pos,
             &segment_type,
             input_state.preview_rotation,
+            layer,
         );
 
         // 获取世界坐标
@@ -457,20 +1298,49 @@ fn update_grid_preview(
             (input_state.preview_rotation as f32) * std::f32::consts::PI / 180.0,
         );
 
+        // 放置后的成本变化，超出 MaxCost 预算时标红
+        let cost = layer.segment_cost(segment_type);
+        let over_budget = game_state
+            .current_level
+            .as_ref()
+            .and_then(|level_data| level_data.max_cost())
+            .is_some_and(|max_cost| game_state.total_cost + cost > max_cost);
+        let cost_color = if over_budget {
+            Color::srgb(1.0, 0.3, 0.3)
+        } else {
+            Color::srgb(1.0, 1.0, 0.8)
+        };
+
         // 生成主预览
-        commands.spawn((
-            Sprite {
-                image: asset_server.load(segment_type.get_texture_path()),
-                color: base_color,
-                ..default()
-            },
-            Transform::from_translation(world_pos + Vec3::Z * 0.1).with_rotation(rotation_quat),
-            SegmentPreview {
-                segment_type,
-                rotation: input_state.preview_rotation,
-                target_position: grid_pos,
-            },
-        ));
+        commands
+            .spawn((
+                Sprite {
+                    image: asset_server.load(theme.route(&segment_type)),
+                    color: base_color,
+                    ..default()
+                },
+                Transform::from_translation(world_pos + Vec3::Z * 0.1).with_rotation(rotation_quat),
+                SegmentPreview {
+                    segment_type,
+                    rotation: input_state.preview_rotation,
+                    target_position: grid_pos,
+                },
+            ))
+            .with_children(|parent| {
+                // 成本标签显示在光标右上角，抵消预览的旋转保持文字水平
+                let offset = Vec3::new(0.5, 0.5, 0.0) * level_manager.tile_size;
+                parent.spawn((
+                    Text2d::new(format!("+{}", cost)),
+                    TextFont {
+                        font: ui_assets.font.clone(),
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(cost_color),
+                    Transform::from_translation(rotation_quat.inverse() * offset + Vec3::Z)
+                        .with_rotation(rotation_quat.inverse()),
+                ));
+            });
 
         // 可选：显示连接点预览（小圆点）
         let connection_positions =
@@ -489,7 +1359,7 @@ fn update_grid_preview(
 
             // 检查这个连接点是否有效
             let connection_valid = game_state
-                .placed_segments
+                .segments_on(layer)
                 .get(&conn_pos)
                 .map(|seg| {
                     seg.segment_type
@@ -525,9 +1395,10 @@ fn update_grid_preview(
 fn reset_preview_rotation_on_deselect(
     mut input_state: ResMut<InputState>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
 ) {
-    // 按 ESC 键取消选择并重置旋转
-    if keyboard_input.just_pressed(KeyCode::Escape) {
+    // 按暂停键（默认 ESC）取消选择并重置旋转
+    if input_map.just_pressed(&keyboard_input, InputAction::Pause) {
         input_state.selected_segment = None;
         input_state.preview_rotation = 0;
         info!("取消选择，重置预览旋转");
@@ -607,10 +1478,8 @@ fn update_objectives(
     }
 }
 
-fn update_game_timer(mut game_state: ResMut<GameState>, time: Res<Time>) {
-    if !game_state.is_paused {
-        game_state.game_time += time.delta_secs();
-    }
+fn update_game_timer(mut game_state: ResMut<GameState>, sim_clock: Res<SimClock>) {
+    game_state.game_time += sim_clock.delta_secs();
 }
 
 fn handle_level_completion(
@@ -714,6 +1583,11 @@ pub fn is_valid_placement(
         return false;
     }
 
+    // 封路期间不能建设
+    if game_state.closed_tiles.contains(&position) {
+        return false;
+    }
+
     // 检查地形限制
     if let Some(level_data) = &game_state.current_level {
         if let Some(terrain_type) = level_data.terrain.get(&position) {
@@ -743,13 +1617,15 @@ pub fn is_valid_placement(
     true
 }
 
-fn spawn_route_segment(
+pub fn spawn_route_segment(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
+    theme: &ThemeRegistry,
     position: GridPos,
     segment_type: RouteSegmentType,
     rotation: u32,
     level_manager: &LevelManager,
+    layer: NetworkLayer,
 ) -> Entity {
     let world_pos = if let Some(level_data) = &level_manager.current_level {
         position.to_world_pos(
@@ -761,29 +1637,35 @@ fn spawn_route_segment(
         position.to_world_pos(level_manager.tile_size, 10, 8)
     };
 
-    let texture_path = segment_type.get_texture_path();
+    let texture_path = theme.route(&segment_type);
+    let z = match layer {
+        NetworkLayer::Ground => 0.5,
+        NetworkLayer::Elevated => ELEVATED_SEGMENT_Z,
+    };
 
-    commands
-        .spawn((
-            Sprite::from_image(asset_server.load(texture_path)),
-            Transform::from_translation(world_pos + Vec3::Z * 0.5).with_rotation(
-                Quat::from_rotation_z((rotation as f32) * std::f32::consts::PI / 180.0),
-            ),
-            RouteSegment {
-                grid_pos: position,
-                segment_type,
-                rotation,
-                is_active: true,
-            },
-            DraggableSegment {
-                segment_type,
-                rotation,
-                is_being_dragged: false,
-                is_placed: true,
-                cost: segment_type.get_cost(),
-            },
-        ))
-        .id()
+    let mut entity = commands.spawn((
+        Sprite::from_image(asset_server.load(texture_path)),
+        Transform::from_translation(world_pos + Vec3::Z * z).with_rotation(Quat::from_rotation_z(
+            (rotation as f32) * std::f32::consts::PI / 180.0,
+        )),
+        RouteSegment {
+            grid_pos: position,
+            segment_type,
+            rotation,
+            is_active: true,
+        },
+        DraggableSegment {
+            segment_type,
+            rotation,
+            is_being_dragged: false,
+            is_placed: true,
+            cost: layer.segment_cost(segment_type),
+        },
+    ));
+    if layer == NetworkLayer::Elevated {
+        entity.insert(ElevatedSegment);
+    }
+    entity.id()
 }
 
 fn check_objective_completion(
@@ -799,9 +1681,9 @@ fn check_objective_completion(
         ObjectiveType::MaxTransfers(max_transfers) => passengers
             .iter()
             .filter(|agent| matches!(agent.state, AgentState::Arrived))
-            .all(|agent| count_transfers_in_path(&agent.current_path) <= *max_transfers),
+            .all(|agent| agent.transfers() <= *max_transfers),
         ObjectiveType::MaxSegments(max_segments) => {
-            game_state.placed_segments.len() <= (*max_segments as usize)
+            game_state.segment_count() <= (*max_segments as usize)
         }
         ObjectiveType::MaxCost(max_cost) => game_state.total_cost <= *max_cost,
         ObjectiveType::TimeLimit(time_limit) => game_state.game_time <= *time_limit,
@@ -811,25 +1693,17 @@ fn check_objective_completion(
         ObjectiveType::PassengerSatisfaction(min_satisfaction) => {
             calculate_passenger_satisfaction(passengers) >= *min_satisfaction
         }
-    }
-}
-
-fn count_transfers_in_path(path: &[PathNode]) -> u32 {
-    let mut transfers = 0;
-    let mut current_route_id = None;
-
-    for node in path {
-        if let Some(route_id) = &node.route_id {
-            if let Some(prev_route_id) = &current_route_id {
-                if route_id != prev_route_id {
-                    transfers += 1;
-                }
-            }
-            current_route_id = Some(route_id.clone());
+        ObjectiveType::DeliverColor(color, count) => {
+            game_state.passenger_stats.arrived_count(*color) >= *count
+        }
+        ObjectiveType::MaxAverageWait(max_wait) => {
+            // 所有已生成的乘客都结束行程后才结算平均等待时间
+            let stats = &game_state.passenger_stats;
+            stats.total_arrived > 0
+                && stats.total_arrived + stats.total_gave_up == stats.total_spawned
+                && stats.average_wait_time() <= *max_wait
         }
     }
-
-    transfers
 }
 
 pub fn calculate_network_efficiency(
@@ -902,28 +1776,60 @@ fn calculate_passenger_satisfaction(passengers: &Query<&PathfindingAgent>) -> f3
 fn handle_segment_hover_effects(
     input_state: Res<InputState>,
     game_state: Res<GameState>,
-    mut route_segments: Query<(&mut Sprite, &RouteSegment)>,
+    mut route_segments: Query<(
+        &mut Sprite,
+        &RouteSegment,
+        Has<ExpressSegment>,
+        Has<ElevatedSegment>,
+    )>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    route_lines: Res<RouteLines>,
 ) {
+    let active_layer = input_state.active_layer;
+
+    // 路线段按所属线路着色，快速路线再混入金色；
+    // 高架路线段用高架快线的颜色，在地面图层时半透明，露出下方的地面路线段
+    let base_color = |segment: &RouteSegment, is_express: bool, is_elevated: bool| {
+        if is_elevated {
+            let alpha = if active_layer == NetworkLayer::Elevated {
+                1.0
+            } else {
+                0.45
+            };
+            return line_color(EXPRESS_LINE).with_alpha(alpha);
+        }
+
+        let line_tint = route_lines.segment_tint(segment.grid_pos);
+        if is_express {
+            line_tint.mix(&EXPRESS_SEGMENT_COLOR, 0.5)
+        } else {
+            line_tint
+        }
+    };
+
     // 获取鼠标当前网格位置
     let mouse_grid_pos = if let Some(grid_pos) = input_state.grid_cursor_pos {
         grid_pos
     } else {
         // 没有有效鼠标位置，重置所有路线段颜色
-        for (mut sprite, _) in route_segments.iter_mut() {
-            sprite.color = Color::WHITE;
+        for (mut sprite, segment, is_express, is_elevated) in route_segments.iter_mut() {
+            sprite.color = base_color(segment, is_express, is_elevated);
         }
         return;
     };
 
     // 检测特殊键状态
-    let is_delete_mode =
-        keyboard_input.pressed(KeyCode::Delete) || keyboard_input.pressed(KeyCode::KeyX);
+    let is_delete_mode = input_map.pressed(&keyboard_input, InputAction::Remove);
     let has_selected_segment = input_state.selected_segment.is_some();
 
-    for (mut sprite, segment) in route_segments.iter_mut() {
-        let is_hovered = segment.grid_pos == mouse_grid_pos;
-        let is_placed_segment = game_state.placed_segments.contains_key(&segment.grid_pos);
+    for (mut sprite, segment, is_express, is_elevated) in route_segments.iter_mut() {
+        // 只高亮当前图层上的路线段
+        let on_active_layer = is_elevated == (active_layer == NetworkLayer::Elevated);
+        let is_hovered = on_active_layer && segment.grid_pos == mouse_grid_pos;
+        let is_placed_segment = game_state
+            .segments_on(active_layer)
+            .contains_key(&segment.grid_pos);
 
         if is_hovered && is_placed_segment && !has_selected_segment {
             // 鼠标悬停在已放置的路线段上
@@ -936,37 +1842,190 @@ fn handle_segment_hover_effects(
             }
         } else {
             // 重置为正常颜色
-            sprite.color = Color::WHITE;
+            sprite.color = base_color(segment, is_express, is_elevated);
         }
     }
 }
 
-/// 更新悬停提示工具栏
+/// 乘客信息卡需要的乘客数据：乘车中的乘客带有 OnBus，等车的乘客带有 WaitingForBus
+type InspectablePassengers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static PathfindingAgent,
+        &'static Transform,
+        &'static Sprite,
+        Option<&'static OnBus>,
+        Has<WaitingForBus>,
+    ),
+>;
+
+type InspectableBuses<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static BusVehicle,
+        &'static BusPathfindingAgent,
+        &'static Transform,
+        &'static Sprite,
+    ),
+>;
+
+/// 在世界精灵上拾取：鼠标落在精灵范围内，多个重叠时取中心最近的一个
+fn pick_world_sprite<'a, T>(
+    candidates: impl Iterator<Item = (T, &'a Transform, &'a Sprite)>,
+    cursor: Vec2,
+) -> Option<T> {
+    candidates
+        .filter_map(|(item, transform, sprite)| {
+            let size = sprite.custom_size? * transform.scale.truncate();
+            let center = transform.translation.truncate();
+            Rect::from_center_size(center, size)
+                .contains(cursor)
+                .then(|| (item, center.distance(cursor)))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(item, _)| item)
+}
+
+fn clear_inspected_passenger(mut inspected: ResMut<InspectedPassenger>) {
+    inspected.0 = None;
+}
+
+/// 未选中库存路线段时点击乘客固定其信息卡；点击同一乘客或空白处取消
+fn pin_inspected_passenger(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_state: Res<InputState>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    passengers: Query<(Entity, &Transform, &Sprite), With<PathfindingAgent>>,
+    mut inspected: ResMut<InspectedPassenger>,
+) {
+    // 乘客到达或放弃后取消固定
+    if inspected
+        .0
+        .is_some_and(|entity| !passengers.contains(entity))
+    {
+        inspected.0 = None;
+    }
+
+    if !mouse_button_input.just_released(MouseButton::Left)
+        || input_state.selected_segment.is_some()
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    let picked = pick_world_sprite(passengers.iter(), input_state.mouse_world_pos.truncate());
+    inspected.0 = match picked {
+        Some(entity) if inspected.0 != Some(entity) => Some(entity),
+        _ => None,
+    };
+}
+
+/// 更新悬停提示工具栏：悬停的乘客、公交车优先，其次是固定的乘客和选中的公交车，最后是路线段
 fn update_hover_tooltip(
     mut commands: Commands,
     input_state: Res<InputState>,
     game_state: Res<GameState>,
     existing_tooltips: Query<Entity, With<HoverTooltip>>,
+    existing_highlights: Query<Entity, With<PassengerRouteHighlight>>,
+    (passengers, buses): (InspectablePassengers, InspectableBuses),
+    (inspected, selected_bus): (Res<InspectedPassenger>, Res<SelectedBus>),
+    level_manager: Res<LevelManager>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
     asset_server: Res<AssetServer>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
 ) {
-    // 清除现有的提示
-    for entity in existing_tooltips.iter() {
+    // 清除现有的提示和路线高亮
+    for entity in existing_tooltips.iter().chain(existing_highlights.iter()) {
         commands.entity(entity).despawn();
     }
 
+    let language = current_language.language;
+    let cursor = input_state.mouse_world_pos.truncate();
+    let hovering = input_state.selected_segment.is_none();
+
+    let hovered_passenger = hovering
+        .then(|| {
+            pick_world_sprite(
+                passengers
+                    .iter()
+                    .map(|(entity, _, transform, sprite, ..)| (entity, transform, sprite)),
+                cursor,
+            )
+        })
+        .flatten();
+    let shown_passenger = hovered_passenger.or(inspected.0);
+    if let Some((_, agent, _, _, on_bus, waiting)) =
+        shown_passenger.and_then(|entity| passengers.get(entity).ok())
+    {
+        spawn_passenger_route_highlights(&mut commands, &game_state, agent, &level_manager);
+        spawn_passenger_tooltip(
+            &mut commands,
+            &ui_assets,
+            &game_state,
+            agent,
+            on_bus,
+            waiting,
+            shown_passenger == inspected.0,
+            language,
+        );
+        return;
+    }
+
+    let hovered_bus = hovering
+        .then(|| {
+            pick_world_sprite(
+                buses
+                    .iter()
+                    .map(|(entity, _, _, transform, sprite)| (entity, transform, sprite)),
+                cursor,
+            )
+        })
+        .flatten();
+    if let Some((_, bus_vehicle, bus_agent, _, sprite)) = hovered_bus
+        .or(selected_bus.0)
+        .and_then(|entity| buses.get(entity).ok())
+    {
+        spawn_bus_path_highlights(
+            &mut commands,
+            &game_state,
+            bus_agent,
+            sprite,
+            &level_manager,
+        );
+        spawn_bus_tooltip(
+            &mut commands,
+            &ui_assets,
+            &game_state,
+            bus_vehicle,
+            bus_agent,
+            language,
+        );
+        return;
+    }
+
     // 检查是否悬停在已放置的路线段上
-    if let Some(grid_pos) = input_state.grid_cursor_pos {
-        if let Some(placed_segment) = game_state.placed_segments.get(&grid_pos) {
-            // 如果没有选中其他路线段，显示操作提示
-            if input_state.selected_segment.is_none() {
-                spawn_hover_tooltip(
-                    &mut commands,
-                    &asset_server,
-                    placed_segment,
-                    &keyboard_input,
-                );
-            }
+    if let Some(grid_pos) = input_state.grid_cursor_pos
+        && let Some(placed_segment) = game_state
+            .segments_on(input_state.active_layer)
+            .get(&grid_pos)
+    {
+        // 如果没有选中其他路线段，显示操作提示
+        if input_state.selected_segment.is_none() {
+            spawn_hover_tooltip(
+                &mut commands,
+                &asset_server,
+                placed_segment,
+                &keyboard_input,
+                &input_map,
+            );
         }
     }
 }
@@ -977,14 +2036,21 @@ fn spawn_hover_tooltip(
     asset_server: &Res<AssetServer>,
     placed_segment: &PlacedSegment,
     keyboard_input: &Res<ButtonInput<KeyCode>>,
+    input_map: &InputMap,
 ) {
     // 确定提示文本和颜色
-    let (tooltip_text, tooltip_color) =
-        if keyboard_input.pressed(KeyCode::Delete) || keyboard_input.pressed(KeyCode::KeyX) {
-            ("按 Delete 或 X 删除此路线段", Color::srgb(1.0, 0.6, 0.6))
-        } else {
-            ("右键旋转 | Delete/X 删除", Color::srgb(1.0, 1.0, 0.8))
-        };
+    let remove_key = input_map.key_name(InputAction::Remove);
+    let (tooltip_text, tooltip_color) = if input_map.pressed(keyboard_input, InputAction::Remove) {
+        (
+            format!("按 Delete 或 {} 删除此路线段", remove_key),
+            Color::srgb(1.0, 0.6, 0.6),
+        )
+    } else {
+        (
+            format!("右键旋转 | Delete/{} 删除", remove_key),
+            Color::srgb(1.0, 1.0, 0.8),
+        )
+    };
 
     // 创建提示框
     commands
@@ -1008,7 +2074,7 @@ fn spawn_hover_tooltip(
                     placed_segment.segment_type, placed_segment.rotation, placed_segment.cost
                 )),
                 TextFont {
-                    font: asset_server.load("fonts/quan.ttf"),
+                    font: asset_server.load(DEFAULT_FONT_PATH),
                     font_size: 14.0,
                     ..default()
                 },
@@ -1019,7 +2085,7 @@ fn spawn_hover_tooltip(
             parent.spawn((
                 Text::new(tooltip_text),
                 TextFont {
-                    font: asset_server.load("fonts/quan.ttf"),
+                    font: asset_server.load(DEFAULT_FONT_PATH),
                     font_size: 12.0,
                     ..default()
                 },
@@ -1028,6 +2094,306 @@ fn spawn_hover_tooltip(
         });
 }
 
+/// 高亮乘客剩余的规划路径，以及起点、中转站和终点
+fn spawn_passenger_route_highlights(
+    commands: &mut Commands,
+    game_state: &GameState,
+    agent: &PathfindingAgent,
+    level_manager: &LevelManager,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    let (width, height) = level_data.grid_size;
+    let tile_size = level_manager.tile_size;
+    let color = get_passenger_color(agent.color);
+
+    let mut spawn_highlight = |grid_pos: GridPos, size: f32, alpha: f32| {
+        let mut world_pos = grid_pos.to_world_pos(tile_size, width, height);
+        world_pos.z = ROUTE_Z + 0.5;
+        commands.spawn((
+            Sprite::from_color(color.with_alpha(alpha), Vec2::splat(size)),
+            Transform::from_translation(world_pos),
+            PassengerRouteHighlight,
+            Name::new("Passenger Route Highlight"),
+        ));
+    };
+
+    for node in agent.current_path.iter().skip(agent.current_step) {
+        spawn_highlight(node.position, tile_size * 0.5, 0.35);
+    }
+
+    // 站点高亮略大于站点精灵，作为外圈显示
+    let stop_names = [
+        Some(agent.origin.as_str()),
+        agent.via.as_deref(),
+        Some(agent.destination.as_str()),
+    ];
+    for station in &level_data.stations {
+        if stop_names.contains(&Some(station.name.as_str())) {
+            spawn_highlight(station.position, tile_size * 1.1, 0.5);
+        }
+    }
+}
+
+/// 生成乘客信息卡（与路线段提示同一位置，同一时间只显示一个）
+#[allow(clippy::too_many_arguments)]
+fn spawn_passenger_tooltip(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    game_state: &GameState,
+    agent: &PathfindingAgent,
+    on_bus: Option<&OnBus>,
+    waiting: bool,
+    pinned: bool,
+    language: Language,
+) {
+    let station_name = |name: &str| localized_station_name(game_state, name, language);
+
+    let mut lines = vec![get_text_with_args(
+        &PASSENGER_HOVER_ROUTE,
+        language,
+        &[
+            &get_text(passenger_color_name(agent.color), language),
+            &station_name(&agent.origin),
+            &station_name(&agent.destination),
+        ],
+    )];
+    if let Some(via) = &agent.via {
+        lines.push(get_text_with_args(
+            &PASSENGER_HOVER_VIA,
+            language,
+            &[&station_name(via)],
+        ));
+    }
+
+    // 乘车和等车以组件为准，其余按乘客自身的状态
+    let state = match (on_bus, &agent.state) {
+        (Some(on_bus), _) => get_text_with_args(
+            &INSPECT_STATE_RIDING,
+            language,
+            &[&station_name(&on_bus.target_station)],
+        ),
+        (None, AgentState::Arrived) => get_text(&INSPECT_STATE_ARRIVED, language),
+        (None, AgentState::GaveUp) => get_text(&INSPECT_STATE_GAVE_UP, language),
+        (None, AgentState::Transferring) if !waiting => get_text_with_args(
+            &INSPECT_STATE_TRANSFERRING,
+            language,
+            &[&station_name(&agent.origin)],
+        ),
+        _ => get_text_with_args(
+            &INSPECT_STATE_WAITING,
+            language,
+            &[&station_name(&agent.origin)],
+        ),
+    };
+    lines.push(state);
+    if agent.current_path.is_empty() && on_bus.is_none() {
+        lines.push(get_text(&PASSENGER_HOVER_NO_PATH, language));
+    }
+
+    let patience_ratio = if agent.max_patience > 0.0 {
+        (agent.patience / agent.max_patience).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let patience_color = if patience_ratio < 0.3 {
+        Color::srgb(1.0, 0.5, 0.5)
+    } else {
+        Color::srgb(0.7, 1.0, 0.7)
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Px(120.0),
+                left: Px(20.0),
+                padding: UiRect::all(Px(8.0)),
+                row_gap: Px(4.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            HoverTooltip,
+            Name::new("Passenger Hover Tooltip"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(lines.join("\n")),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            ));
+
+            parent.spawn((
+                Text::new(get_text_with_args(
+                    &PASSENGER_HOVER_PATIENCE,
+                    language,
+                    &[&format!("{:.0}", patience_ratio * 100.0)],
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(patience_color),
+            ));
+
+            // 耐心条
+            parent
+                .spawn((
+                    Node {
+                        width: Px(PATIENCE_BAR_WIDTH),
+                        height: Px(PATIENCE_BAR_HEIGHT),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.15)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(patience_ratio * 100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(patience_color),
+                    ));
+                });
+
+            parent.spawn((
+                Text::new(get_text(
+                    if pinned {
+                        &INSPECT_PINNED
+                    } else {
+                        &INSPECT_PIN_HINT
+                    },
+                    language,
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+/// 高亮公交车剩余的规划路径，颜色与公交车一致
+fn spawn_bus_path_highlights(
+    commands: &mut Commands,
+    game_state: &GameState,
+    bus_agent: &BusPathfindingAgent,
+    sprite: &Sprite,
+    level_manager: &LevelManager,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    let (width, height) = level_data.grid_size;
+    let tile_size = level_manager.tile_size;
+
+    for node in bus_agent.current_path.iter().skip(bus_agent.current_step) {
+        let mut world_pos = node.position.to_world_pos(tile_size, width, height);
+        world_pos.z = ROUTE_Z + 0.5;
+        commands.spawn((
+            Sprite::from_color(sprite.color.with_alpha(0.35), Vec2::splat(tile_size * 0.5)),
+            Transform::from_translation(world_pos),
+            PassengerRouteHighlight,
+            Name::new("Bus Path Highlight"),
+        ));
+    }
+}
+
+/// 生成公交车信息卡：所属线路、运行状态、载客和下一站
+fn spawn_bus_tooltip(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    game_state: &GameState,
+    bus_vehicle: &BusVehicle,
+    bus_agent: &BusPathfindingAgent,
+    language: Language,
+) {
+    let line = bus_agent
+        .line_id
+        .as_deref()
+        .and_then(line_number)
+        .map_or_else(|| "-".to_string(), |line| line.to_string());
+    let state = match bus_vehicle.state {
+        BusState::Traveling => &BUS_STATE_DRIVING,
+        BusState::AtStop | BusState::Loading => &BUS_STATE_AT_STOP,
+        BusState::TurningAround => &BUS_STATE_TURNING,
+        BusState::Idle => &BUS_STATE_IDLE,
+    };
+
+    let lines = [
+        get_text_with_args(&BUS_HOVER_TITLE, language, &[&line]),
+        get_text(state, language),
+        get_text_with_args(
+            &BUS_HOVER_LOAD,
+            language,
+            &[
+                &bus_vehicle.current_passengers.len().to_string(),
+                &bus_vehicle.capacity.to_string(),
+            ],
+        ),
+        get_text_with_args(
+            &BUS_HOVER_NEXT_STOP,
+            language,
+            &[&localized_station_name(
+                game_state,
+                &bus_agent.target_station,
+                language,
+            )],
+        ),
+    ];
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Px(120.0),
+                left: Px(20.0),
+                padding: UiRect::all(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            HoverTooltip,
+            Name::new("Bus Hover Tooltip"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(lines.join("\n")),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            ));
+        });
+}
+
+fn localized_station_name(game_state: &GameState, name: &str, language: Language) -> String {
+    game_state
+        .current_level
+        .as_ref()
+        .and_then(|level_data| {
+            level_data
+                .stations
+                .iter()
+                .find(|station| station.name == name)
+        })
+        .map_or_else(
+            || name.to_string(),
+            |station| station.get_localized_name(language),
+        )
+}
+
 /// 新增：坐标转换调试函数
 fn debug_coordinate_conversion(world_pos: Vec3, tile_size: f32, grid_width: u32, grid_height: u32) {
     info!("=== 坐标转换调试 ===");
@@ -1102,9 +2468,10 @@ fn is_valid_placement_with_rotation(
     position: GridPos,
     segment_type: &RouteSegmentType,
     rotation: u32,
+    layer: NetworkLayer,
 ) -> bool {
     // 基础验证
-    if !is_valid_placement(game_state, position, segment_type) {
+    if !is_valid_placement_on_layer(game_state, position, segment_type, layer) {
         return false;
     }
 
@@ -1116,7 +2483,7 @@ fn is_valid_placement_with_rotation(
 
     // 检查连接点是否与现有路线段匹配
     for conn_pos in connection_positions {
-        if let Some(existing_segment) = game_state.placed_segments.get(&conn_pos) {
+        if let Some(existing_segment) = game_state.segments_on(layer).get(&conn_pos) {
             // 检查现有路线段是否有朝向当前位置的连接口
             if !existing_segment.segment_type.has_connection_to(
                 conn_pos,
@@ -1169,31 +2536,103 @@ fn handle_inventory_selection(
     game_state: Res<GameState>,
 ) {
     for (interaction, button_component) in button_query.iter() {
-        if matches!(*interaction, Interaction::Pressed) {
-            if let ButtonType::InventorySlot(segment_type) = &button_component.button_type {
-                let available_count = game_state
-                    .player_inventory
-                    .get(segment_type)
-                    .copied()
-                    .unwrap_or(0);
-
-                if available_count > 0 {
-                    // 如果选择了不同的路线段类型，重置旋转
-                    if input_state.selected_segment != Some(*segment_type) {
-                        input_state.preview_rotation = 0;
-                        info!("选择新路线段类型，重置旋转角度");
-                    }
-
-                    input_state.selected_segment = Some(*segment_type);
-                    info!("选择路线段: {:?}", segment_type);
-                } else {
-                    warn!("库存不足: {:?}", segment_type);
+        if matches!(*interaction, Interaction::Pressed)
+            && let ButtonType::InventorySlot(segment_type) = &button_component.button_type
+        {
+            let available_count = game_state
+                .player_inventory
+                .get(segment_type)
+                .copied()
+                .unwrap_or(0);
+
+            if available_count > 0 {
+                // 如果选择了不同的路线段类型，重置旋转
+                if input_state.selected_segment != Some(*segment_type) {
+                    input_state.preview_rotation = 0;
+                    info!("选择新路线段类型，重置旋转角度");
                 }
+
+                input_state.selected_segment = Some(*segment_type);
+                // 按住不放即开始拖放，在槽位上松开则保持点击选中
+                input_state.is_dragging = true;
+                info!("选择路线段: {:?}", segment_type);
+            } else {
+                warn!("库存不足: {:?}", segment_type);
             }
         }
     }
 }
 
+/// 从库存栏拖出路线段：图标跟随光标，悬停在格子上时由放置预览接管；
+/// 松开左键结束拖放，落在网格上的放置由 handle_segment_placement 完成
+fn update_inventory_drag(
+    mut commands: Commands,
+    mut input_state: ResMut<InputState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    mut ghosts: Query<(&mut Transform, &mut Visibility), With<InventoryDragGhost>>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
+    if !input_state.is_dragging {
+        return;
+    }
+
+    let Some(segment_type) = input_state.selected_segment else {
+        input_state.is_dragging = false;
+        return;
+    };
+
+    if !mouse_button_input.pressed(MouseButton::Left) {
+        input_state.is_dragging = false;
+        if let Some(entity) = input_state.drag_entity.take() {
+            commands.entity(entity).despawn();
+        }
+        // 在库存槽位上松开是普通的点击选中；拖到别处松开后不再保持选中
+        if ui_buttons
+            .iter()
+            .all(|interaction| *interaction == Interaction::None)
+        {
+            input_state.selected_segment = None;
+            input_state.preview_rotation = 0;
+            info!("结束拖放: {:?}", segment_type);
+        }
+        return;
+    }
+
+    let rotation = Quat::from_rotation_z((input_state.preview_rotation as f32).to_radians());
+    let translation = input_state.mouse_world_pos.truncate().extend(EFFECT_Z);
+    let visibility = if input_state.grid_cursor_pos.is_some() {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    };
+
+    if let Some((mut transform, mut ghost_visibility)) = input_state
+        .drag_entity
+        .and_then(|entity| ghosts.get_mut(entity).ok())
+    {
+        transform.translation = translation;
+        transform.rotation = rotation;
+        *ghost_visibility = visibility;
+    } else {
+        let entity = commands
+            .spawn((
+                Sprite {
+                    image: asset_server.load(theme.route(&segment_type)),
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.6),
+                    ..default()
+                },
+                Transform::from_translation(translation).with_rotation(rotation),
+                visibility,
+                InventoryDragGhost,
+                Name::new("Inventory Drag Ghost"),
+            ))
+            .id();
+        input_state.drag_entity = Some(entity);
+    }
+}
+
 // 最终推荐实现：直接集成的版本
 fn show_rotation_hint_ui(
     mut commands: Commands,
@@ -1228,14 +2667,14 @@ fn show_rotation_hint_ui(
             .with_children(|parent| {
                 // 主要操作提示行
                 let main_hint = match current_language.language {
-                    Language::English => format!(
-                        "🔄 Right/R/Space to Rotate (Current: {}°) | 📍 Left Click to Place | ❌ ESC to Cancel",
-                        input_state.preview_rotation
-                    ),
                     Language::Chinese => format!(
                         "🔄 右键/R键/空格旋转 (当前: {}°) | 📍 左键放置 | ❌ ESC取消",
                         input_state.preview_rotation
                     ),
+                    _ => format!(
+                        "🔄 Right/R/Space to Rotate (Current: {}°) | 📍 Left Click to Place | ❌ ESC to Cancel",
+                        input_state.preview_rotation
+                    ),
                 };
 
                 parent.spawn((
@@ -1254,8 +2693,8 @@ fn show_rotation_hint_ui(
 
                 // 快速旋转提示行
                 let quick_hint = match current_language.language {
-                    Language::English => "💡 Tip: Press 1-4 for quick rotation (0°/90°/180°/270°)",
                     Language::Chinese => "💡 提示: 按数字键1-4快速旋转 (0°/90°/180°/270°)",
+                    _ => "💡 Tip: Press 1-4 for quick rotation (0°/90°/180°/270°)",
                 };
 
                 parent.spawn((
@@ -1284,21 +2723,21 @@ fn update_rotation_hint_text(
             if text.0.contains("🔄") {
                 // 主要操作提示
                 let new_text = match current_language.language {
-                    Language::English => format!(
-                        "🔄 Right/R/Space to Rotate (Current: {}°) | 📍 Left Click to Place | ❌ ESC to Cancel",
-                        input_state.preview_rotation
-                    ),
                     Language::Chinese => format!(
                         "🔄 右键/R键/空格旋转 (当前: {}°) | 📍 左键放置 | ❌ ESC取消",
                         input_state.preview_rotation
                     ),
+                    _ => format!(
+                        "🔄 Right/R/Space to Rotate (Current: {}°) | 📍 Left Click to Place | ❌ ESC to Cancel",
+                        input_state.preview_rotation
+                    ),
                 };
                 *text = Text::new(new_text);
             } else if text.0.contains("💡") {
                 // 快速旋转提示
                 let new_text = match current_language.language {
-                    Language::English => "💡 Tip: Press 1-4 for quick rotation (0°/90°/180°/270°)",
                     Language::Chinese => "💡 提示: 按数字键1-4快速旋转 (0°/90°/180°/270°)",
+                    _ => "💡 Tip: Press 1-4 for quick rotation (0°/90°/180°/270°)",
                 };
                 *text = Text::new(new_text);
             }
@@ -1318,14 +2757,14 @@ fn update_rotation_angle_display(
             if text.0.contains("🔄") {
                 // 更新主要操作提示中的角度显示
                 let new_text = match current_language.language {
-                    Language::English => format!(
-                        "🔄 Right/R/Space to Rotate (Current: {}°) | 📍 Left Click to Place | ❌ ESC to Cancel",
-                        input_state.preview_rotation
-                    ),
                     Language::Chinese => format!(
                         "🔄 右键/R键/空格旋转 (当前: {}°) | 📍 左键放置 | ❌ ESC取消",
                         input_state.preview_rotation
                     ),
+                    _ => format!(
+                        "🔄 Right/R/Space to Rotate (Current: {}°) | 📍 Left Click to Place | ❌ ESC to Cancel",
+                        input_state.preview_rotation
+                    ),
                 };
                 *text = Text::new(new_text);
             }
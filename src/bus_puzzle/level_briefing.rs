@@ -0,0 +1,304 @@
+// src/bus_puzzle/level_briefing.rs - 关卡简报（开始前展示关卡名称、描述、目标和可用路线段，点击开始后才开始模拟）
+
+use crate::bus_puzzle::{
+    get_text, load_current_level, ButtonComponent, ButtonType, CurrentLanguage, GameState,
+    GameStateEnum, Language, LevelData, TerrainBrush, UIAssets, BRIEFING_START, OBJECTIVES,
+    ROUTE_SEGMENTS,
+};
+use bevy::{
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
+    ui::FocusPolicy,
+};
+
+const BRIEFING_ACCENT: Color = Color::srgb(1.0, 0.85, 0.3);
+
+// ============ 简报资源 ============
+
+/// 关卡加载后先显示简报：计时和乘客生成暂停，点击“开始”后才开始
+#[derive(Resource, Default)]
+pub struct LevelBriefing {
+    pub showing: bool,
+    briefed_level: Option<String>, // 重新开始同一关时不再重复显示
+}
+
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct LevelBriefingPanel;
+
+// ============ 关卡简报插件 ============
+
+pub struct LevelBriefingPlugin;
+
+impl Plugin for LevelBriefingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelBriefing>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), forget_briefed_level)
+            .add_systems(
+                OnExit(GameStateEnum::MainMenu),
+                begin_level_briefing
+                    .after(load_current_level)
+                    .run_if(not(in_state(GameStateEnum::Replay))),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::Loading),
+                begin_level_briefing.after(load_current_level),
+            )
+            .add_systems(
+                Update,
+                (handle_briefing_start, refresh_briefing_panel)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+/// 从主菜单进入的关卡总是显示简报
+fn forget_briefed_level(mut briefing: ResMut<LevelBriefing>) {
+    briefing.showing = false;
+    briefing.briefed_level = None;
+}
+
+fn begin_level_briefing(mut briefing: ResMut<LevelBriefing>, mut game_state: ResMut<GameState>) {
+    let Some(level_id) = game_state
+        .current_level
+        .as_ref()
+        .map(|level_data| level_data.id.clone())
+    else {
+        return;
+    };
+
+    // 重试或从暂停菜单重新开始同一关时直接开始
+    if briefing.briefed_level.as_ref() == Some(&level_id) {
+        briefing.showing = false;
+        return;
+    }
+
+    briefing.briefed_level = Some(level_id);
+    briefing.showing = true;
+    game_state.is_paused = true;
+    info!("📋 显示关卡简报，等待开始");
+}
+
+// ============ 开始按钮 ============
+
+/// 点击“开始”关闭简报；沙盒关卡仍停在地形编辑阶段
+fn handle_briefing_start(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut briefing: ResMut<LevelBriefing>,
+    mut game_state: ResMut<GameState>,
+    terrain_brush: Res<TerrainBrush>,
+) {
+    if !briefing.showing {
+        return;
+    }
+
+    let start_pressed = button_query.iter().any(|(interaction, button)| {
+        matches!(*interaction, Interaction::Pressed)
+            && matches!(button.button_type, ButtonType::StartLevel)
+    });
+    if !start_pressed {
+        return;
+    }
+
+    briefing.showing = false;
+    game_state.is_paused = terrain_brush.editing;
+    info!("▶️ 关闭关卡简报，开始关卡");
+}
+
+// ============ 简报面板 ============
+
+fn refresh_briefing_panel(
+    mut commands: Commands,
+    briefing: Res<LevelBriefing>,
+    panels: Query<Entity, With<LevelBriefingPanel>>,
+    game_state: Res<GameState>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    // 从暂停菜单返回时面板已随状态切换清理，需要重新生成
+    let missing = briefing.showing && panels.is_empty();
+    if !briefing.is_changed() && !current_language.is_changed() && !missing {
+        return;
+    }
+
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if let (true, Some(level_data)) = (briefing.showing, &game_state.current_level) {
+        spawn_briefing_panel(
+            &mut commands,
+            &ui_assets,
+            level_data,
+            current_language.language,
+        );
+    }
+}
+
+fn spawn_briefing_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    level_data: &LevelData,
+    language: Language,
+) {
+    let text = |content: String, size: f32, color: Color| {
+        (
+            Text::new(content),
+            TextFont {
+                font: ui_assets.font.clone(),
+                font_size: size,
+                ..default()
+            },
+            TextColor(color),
+        )
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            FocusPolicy::Block, // 简报关闭前不能操作下面的界面
+            ZIndex(3000),
+            LevelBriefingPanel,
+            Name::new("Level Briefing"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(480.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(14.0),
+                        padding: UiRect::all(Px(28.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.12, 0.12, 0.2)),
+                    Outline::new(Px(2.0), Val::ZERO, BRIEFING_ACCENT),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(text(
+                        level_data.get_localized_name(language),
+                        28.0,
+                        BRIEFING_ACCENT,
+                    ));
+                    parent.spawn((
+                        text(
+                            level_data.get_localized_description(language),
+                            16.0,
+                            Color::srgb(0.85, 0.85, 0.9),
+                        ),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+
+                    // 目标列表
+                    parent
+                        .spawn(Node {
+                            width: Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Px(4.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(text(get_text(&OBJECTIVES, language), 18.0, Color::WHITE));
+                            for objective in &level_data.objectives {
+                                parent.spawn(text(
+                                    format!("• {}", objective.get_localized_description(language)),
+                                    14.0,
+                                    Color::srgb(0.8, 0.9, 0.8),
+                                ));
+                            }
+                        });
+
+                    // 可用路线段及数量
+                    parent
+                        .spawn(Node {
+                            width: Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Px(6.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(text(
+                                get_text(&ROUTE_SEGMENTS, language),
+                                18.0,
+                                Color::WHITE,
+                            ));
+                            parent
+                                .spawn(Node {
+                                    flex_direction: FlexDirection::Row,
+                                    flex_wrap: FlexWrap::Wrap,
+                                    column_gap: Px(14.0),
+                                    row_gap: Px(6.0),
+                                    ..default()
+                                })
+                                .with_children(|parent| {
+                                    for segment in &level_data.available_segments {
+                                        parent
+                                            .spawn(Node {
+                                                align_items: AlignItems::Center,
+                                                column_gap: Px(4.0),
+                                                ..default()
+                                            })
+                                            .with_children(|parent| {
+                                                if let Some(icon) = ui_assets
+                                                    .segment_icons
+                                                    .get(&segment.segment_type)
+                                                {
+                                                    parent.spawn((
+                                                        ImageNode::new(icon.clone()),
+                                                        Node {
+                                                            width: Px(32.0),
+                                                            height: Px(32.0),
+                                                            ..default()
+                                                        },
+                                                    ));
+                                                }
+                                                parent.spawn(text(
+                                                    format!("×{}", segment.count),
+                                                    16.0,
+                                                    Color::WHITE,
+                                                ));
+                                            });
+                                    }
+                                });
+                        });
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Px(180.0),
+                                height: Px(48.0),
+                                margin: UiRect::top(Px(8.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.2, 0.55, 0.3)),
+                            ButtonComponent {
+                                button_type: ButtonType::StartLevel,
+                                is_hovered: false,
+                                is_pressed: false,
+                            },
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(text(
+                                get_text(&BRIEFING_START, language),
+                                20.0,
+                                Color::WHITE,
+                            ));
+                        });
+                });
+        });
+}
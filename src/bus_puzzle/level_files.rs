@@ -0,0 +1,336 @@
+// src/bus_puzzle/level_files.rs - 自定义关卡分享（导出为 .lastlevel 文件，从 custom_levels/ 文件夹读取）
+
+use crate::bus_puzzle::{
+    find_text, get_text, terrain_color, ButtonComponent, ButtonType, CurrentLanguage, GameState,
+    GameStateEnum, GridPos, Language, LevelData, LocalizedText, TerrainType, LEVEL_EXPORTED,
+    LEVEL_EXPORT_FAILED, STATION_COLOR,
+};
+use bevy::{
+    asset::RenderAssetUsages,
+    image::ImageSampler,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 放置自定义关卡文件的文件夹
+#[cfg(not(target_family = "wasm"))]
+pub const CUSTOM_LEVELS_DIR: &str = "custom_levels";
+
+pub const LEVEL_FILE_EXTENSION: &str = "lastlevel";
+
+/// 关卡文件格式版本，读取时拒绝更新版本写出的文件
+const LEVEL_FILE_VERSION: u32 = 1;
+
+// ============ 关卡文件格式 ============
+
+/// 一个可分享的关卡文件：关卡数据 + 元信息 + 缩略图（JSON）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelFile {
+    pub version: u32,
+    pub metadata: LevelFileMetadata,
+    pub level: LevelData,
+    pub thumbnail: LevelThumbnail,
+    /// 从 custom_levels/ 读取时的文件路径，用于删除关卡
+    #[serde(skip)]
+    pub source_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelFileMetadata {
+    pub name: String,        // 导出时语言下的关卡名称
+    pub description: String, // 导出时语言下的关卡描述
+    pub exported_at: String,
+    pub game_version: String,
+}
+
+/// 每个格子一个像素的缩略图，像素按行（从上到下）存为 "rrggbb" 十六进制串
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelThumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: String,
+}
+
+impl LevelThumbnail {
+    pub fn from_level(level_data: &LevelData) -> Self {
+        let (width, height) = level_data.grid_size;
+        let mut pixels = String::with_capacity((width * height * 6) as usize);
+
+        // 网格 y 轴向上，图片行向下
+        for y in (0..height as i32).rev() {
+            for x in 0..width as i32 {
+                let pos = GridPos::new(x, y);
+                let color = if level_data
+                    .stations
+                    .iter()
+                    .any(|station| station.position == pos)
+                {
+                    STATION_COLOR
+                } else {
+                    terrain_color(level_data.terrain.get(&pos).unwrap_or(&TerrainType::Empty))
+                };
+                pixels.push_str(&format!("{:02x}{:02x}{:02x}", color[0], color[1], color[2]));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// 转换为界面可显示的图片，像素数据不完整时返回 None
+    pub fn to_image(&self) -> Option<Image> {
+        let pixel_count = (self.width * self.height) as usize;
+        if pixel_count == 0 || self.pixels.len() != pixel_count * 6 {
+            return None;
+        }
+
+        let mut data = Vec::with_capacity(pixel_count * 4);
+        for index in 0..pixel_count * 3 {
+            let hex = self.pixels.get(index * 2..index * 2 + 2)?;
+            data.push(u8::from_str_radix(hex, 16).ok()?);
+            if index % 3 == 2 {
+                data.push(255);
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: self.width,
+                height: self.height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        // 放大显示时保持格子边缘清晰
+        image.sampler = ImageSampler::nearest();
+        Some(image)
+    }
+}
+
+impl LevelFile {
+    pub fn new(level_data: &LevelData, language: Language) -> Self {
+        Self {
+            version: LEVEL_FILE_VERSION,
+            metadata: LevelFileMetadata {
+                name: level_data.get_localized_name(language),
+                description: level_data.get_localized_description(language),
+                exported_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            level: level_data.clone(),
+            thumbnail: LevelThumbnail::from_level(level_data),
+            source_path: None,
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let file: LevelFile = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        if file.version > LEVEL_FILE_VERSION {
+            return Err(format!(
+                "level file version {} is newer than supported version {}",
+                file.version, LEVEL_FILE_VERSION
+            ));
+        }
+        Ok(file)
+    }
+}
+
+// ============ 序列化辅助 ============
+
+/// 本地化文本按键名写入文件，读取时在内置文本中查找；找不到的键退回到原始文字
+pub mod localized_key {
+    use super::{find_text, LocalizedText};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        text: &Option<&'static LocalizedText>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        text.map(|text| text.key).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<&'static LocalizedText>, D::Error> {
+        Ok(Option::<String>::deserialize(deserializer)?.and_then(|key| find_text(&key)))
+    }
+}
+
+/// 以格子坐标为键的表写成 [坐标, 值] 列表（JSON 对象的键只能是字符串），按坐标排序保证输出稳定
+pub mod grid_map {
+    use super::GridPos;
+    use bevy::platform::collections::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<V: Serialize, S: Serializer>(
+        map: &HashMap<GridPos, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = map.iter().collect();
+        entries.sort_by_key(|(pos, _)| (pos.y, pos.x));
+        entries.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<GridPos, V>, D::Error> {
+        Ok(Vec::<(GridPos, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+// ============ 文件读写 ============
+
+fn level_file_name(level_id: &str) -> String {
+    format!("{}.{}", file_stem_for_id(level_id), LEVEL_FILE_EXTENSION)
+}
+
+/// 文件名只保留字母、数字、下划线和连字符（关卡文件和录像文件共用）
+pub fn file_stem_for_id(level_id: &str) -> String {
+    level_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// 导出关卡，返回写入的路径
+pub fn export_level_file(level_file: &LevelFile) -> Result<String, String> {
+    let content = serde_json::to_string_pretty(level_file).map_err(|e| e.to_string())?;
+    let file_name = level_file_name(&level_file.level.id);
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        std::fs::create_dir_all(CUSTOM_LEVELS_DIR).map_err(|e| e.to_string())?;
+        let path = std::path::Path::new(CUSTOM_LEVELS_DIR).join(file_name);
+        std::fs::write(&path, content).map_err(|e| e.to_string())?;
+        Ok(path.display().to_string())
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        info!("WASM环境：关卡文件输出到日志 {}\n{}", file_name, content);
+        Ok(file_name)
+    }
+}
+
+/// 读取 custom_levels/ 中的所有关卡文件，按文件名排序；无法解析的文件跳过
+pub fn scan_custom_levels() -> Vec<LevelFile> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let Ok(entries) = std::fs::read_dir(CUSTOM_LEVELS_DIR) else {
+            return Vec::new();
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == LEVEL_FILE_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let result = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|content| LevelFile::parse(&content));
+                match result {
+                    Ok(level_file) => Some(LevelFile {
+                        source_path: Some(path),
+                        ..level_file
+                    }),
+                    Err(e) => {
+                        warn!("跳过无法读取的关卡文件 {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        Vec::new()
+    }
+}
+
+/// 删除从 custom_levels/ 读取的关卡文件
+pub fn delete_custom_level(level_file: &LevelFile) -> Result<(), String> {
+    let Some(path) = &level_file.source_path else {
+        return Err("level file was not loaded from disk".to_string());
+    };
+    std::fs::remove_file(path).map_err(|e| e.to_string())
+}
+
+// ============ 关卡文件插件 ============
+
+pub struct LevelFilesPlugin;
+
+impl Plugin for LevelFilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_export_level_button.run_if(in_state(GameStateEnum::Paused)),
+        );
+    }
+}
+
+/// 暂停菜单中导出当前关卡，结果显示在按钮上
+fn handle_export_level_button(
+    button_query: Query<
+        (&Interaction, &ButtonComponent, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut button_texts: Query<&mut Text>,
+    game_state: Res<GameState>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component, children) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed)
+            || button_component.button_type != ButtonType::ExportLevelFile
+        {
+            continue;
+        }
+
+        let result = game_state
+            .current_level
+            .as_ref()
+            .ok_or_else(|| "no level loaded".to_string())
+            .and_then(|level_data| {
+                export_level_file(&LevelFile::new(level_data, current_language.language))
+            });
+        let result_text: &LocalizedText = match &result {
+            Ok(path) => {
+                info!("📦 关卡已导出: {}", path);
+                &LEVEL_EXPORTED
+            }
+            Err(e) => {
+                warn!("导出关卡失败: {}", e);
+                &LEVEL_EXPORT_FAILED
+            }
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = button_texts.get_mut(child) {
+                *text = Text::new(get_text(result_text, current_language.language));
+            }
+        }
+    }
+}
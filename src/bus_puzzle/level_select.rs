@@ -0,0 +1,586 @@
+// src/bus_puzzle/level_select.rs - 主菜单关卡选择面板（缩略图网格、星级、最佳成绩，奖励关卡按星星总数解锁）
+
+use crate::bus_puzzle::{
+    create_builtin_level, delete_custom_level, get_text, get_text_with_args, scan_custom_levels,
+    star_string, ActiveChallenge, ButtonComponent, ButtonType, ConfirmAction, ConfirmRequestEvent,
+    CurrentLanguage, CustomLevel, DialogConfirmedEvent, GameStateEnum, Language, LevelFile,
+    LevelManager, LevelThumbnail, PendingAutosave, UIAssets, BACK, CHALLENGE_PLAY, CONFIRM_DELETE,
+    CUSTOM_LEVELS_HINT, CUSTOM_LEVEL_INFO, LEVEL_BEST_RECORD, LEVEL_LOCKED, LEVEL_NOT_CLEARED,
+    LEVEL_REQUIRES_STARS, LEVEL_SELECT, LEVEL_SELECT_CAMPAIGN, LEVEL_SELECT_CUSTOM,
+    LEVEL_TOTAL_STARS, NO_CUSTOM_LEVELS,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+
+/// 自定义关卡缩略图的显示倍数（每个格子的像素数）
+const THUMBNAIL_SCALE: f32 = 5.0;
+
+/// 战役关卡卡片宽度，缩略图按关卡网格比例缩放到这个宽度
+const LEVEL_CARD_WIDTH: f32 = 180.0;
+const LEVEL_CARD_THUMBNAIL_WIDTH: f32 = LEVEL_CARD_WIDTH - 16.0;
+
+/// 未解锁关卡的缩略图压暗显示
+const LOCKED_THUMBNAIL_TINT: Color = Color::srgb(0.3, 0.3, 0.35);
+
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::MainMenu))]
+pub struct LevelSelectUI;
+
+/// 关卡选择面板的分页
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelSelectTab {
+    Campaign,
+    Custom,
+}
+
+/// 打开自定义分页时从 custom_levels/ 读取的关卡文件
+#[derive(Resource, Default)]
+pub struct CustomLevelFiles(pub Vec<LevelFile>);
+
+// ============ 关卡选择插件 ============
+
+pub struct LevelSelectPlugin;
+
+impl Plugin for LevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CustomLevelFiles>().add_systems(
+            Update,
+            handle_level_select_buttons.run_if(in_state(GameStateEnum::MainMenu)),
+        );
+    }
+}
+
+fn handle_level_select_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    panels: Query<Entity, With<LevelSelectUI>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    mut level_manager: ResMut<LevelManager>,
+    mut pending_autosave: ResMut<PendingAutosave>,
+    mut custom_level: ResMut<CustomLevel>,
+    mut active_challenge: ResMut<ActiveChallenge>,
+    mut custom_files: ResMut<CustomLevelFiles>,
+    mut images: ResMut<Assets<Image>>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+    mut confirm_requests: EventWriter<ConfirmRequestEvent>,
+    mut confirmed_events: EventReader<DialogConfirmedEvent>,
+) {
+    for DialogConfirmedEvent(action) in confirmed_events.read() {
+        let ConfirmAction::DeleteCustomLevel(index) = *action else {
+            continue;
+        };
+        let Some(level_file) = custom_files.0.get(index) else {
+            continue;
+        };
+        match delete_custom_level(level_file) {
+            Ok(()) => info!("🗑️ 已删除自定义关卡: {}", level_file.metadata.name),
+            Err(e) => warn!("删除自定义关卡失败 {}: {}", level_file.metadata.name, e),
+        }
+
+        custom_files.0 = scan_custom_levels();
+        for entity in panels.iter() {
+            commands.entity(entity).despawn();
+        }
+        spawn_level_select_panel(
+            &mut commands,
+            &ui_assets,
+            &level_manager,
+            &custom_files,
+            &mut images,
+            LevelSelectTab::Custom,
+            current_language.language,
+        );
+    }
+
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::OpenLevelSelect if panels.is_empty() => {
+                spawn_level_select_panel(
+                    &mut commands,
+                    &ui_assets,
+                    &level_manager,
+                    &custom_files,
+                    &mut images,
+                    LevelSelectTab::Campaign,
+                    current_language.language,
+                );
+            }
+            ButtonType::LevelSelectTab(tab) => {
+                // 每次打开自定义分页都重新扫描文件夹，新放入的文件无需重启
+                if tab == LevelSelectTab::Custom {
+                    custom_files.0 = scan_custom_levels();
+                    info!("📂 找到 {} 个自定义关卡", custom_files.0.len());
+                }
+                for entity in panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+                spawn_level_select_panel(
+                    &mut commands,
+                    &ui_assets,
+                    &level_manager,
+                    &custom_files,
+                    &mut images,
+                    tab,
+                    current_language.language,
+                );
+            }
+            ButtonType::PlayCustomLevelFile(index) => {
+                let Some(level_file) = custom_files.0.get(index) else {
+                    continue;
+                };
+                pending_autosave.0 = None;
+                active_challenge.0 = None;
+                custom_level.0 = Some(level_file.level.clone());
+                next_state.set(GameStateEnum::Playing);
+                info!("📂 开始自定义关卡: {}", level_file.metadata.name);
+            }
+            ButtonType::DeleteCustomLevelFile(index) => {
+                confirm_requests
+                    .write(ConfirmRequestEvent(ConfirmAction::DeleteCustomLevel(index)));
+            }
+            ButtonType::CloseLevelSelect => {
+                for entity in panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ButtonType::SelectLevel(index) if level_manager.is_level_unlocked(index) => {
+                level_manager.current_level_index = index;
+                // 手动选择的关卡从头开始，不恢复自动存档
+                pending_autosave.0 = None;
+                custom_level.0 = None;
+                next_state.set(GameStateEnum::Playing);
+                info!(
+                    "📋 选择关卡 {} ({})",
+                    index + 1,
+                    level_manager.available_levels[index]
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+fn spawn_level_select_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    level_manager: &LevelManager,
+    custom_files: &CustomLevelFiles,
+    images: &mut Assets<Image>,
+    tab: LevelSelectTab,
+    language: Language,
+) {
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                top: Px(0.0),
+                left: Px(0.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(4000),
+            LevelSelectUI,
+            Name::new("Level Select Panel"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(LEVEL_CARD_WIDTH * 3.0 + 68.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(10.0),
+                        padding: UiRect::all(Px(24.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.9, 0.7, 0.2)),
+                ))
+                .with_children(|parent| {
+                    spawn_panel_text(
+                        parent,
+                        ui_assets,
+                        get_text(&LEVEL_SELECT, language),
+                        28.0,
+                        Color::srgb(0.9, 0.9, 0.3),
+                    );
+
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Px(10.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for (tab_type, label) in [
+                                (LevelSelectTab::Campaign, &LEVEL_SELECT_CAMPAIGN),
+                                (LevelSelectTab::Custom, &LEVEL_SELECT_CUSTOM),
+                            ] {
+                                spawn_tab_button(
+                                    parent,
+                                    ui_assets,
+                                    get_text(label, language),
+                                    tab_type,
+                                    tab_type == tab,
+                                );
+                            }
+                        });
+
+                    match tab {
+                        LevelSelectTab::Campaign => {
+                            spawn_panel_text(
+                                parent,
+                                ui_assets,
+                                get_text_with_args(
+                                    &LEVEL_TOTAL_STARS,
+                                    language,
+                                    &[&level_manager.total_stars().to_string()],
+                                ),
+                                16.0,
+                                Color::srgb(0.9, 0.7, 0.2),
+                            );
+
+                            parent
+                                .spawn(Node {
+                                    width: Percent(100.0),
+                                    flex_direction: FlexDirection::Row,
+                                    flex_wrap: FlexWrap::Wrap,
+                                    justify_content: JustifyContent::Center,
+                                    column_gap: Px(10.0),
+                                    row_gap: Px(10.0),
+                                    ..default()
+                                })
+                                .with_children(|parent| {
+                                    for index in 0..level_manager.available_levels.len() {
+                                        spawn_level_card(
+                                            parent,
+                                            ui_assets,
+                                            images,
+                                            level_manager,
+                                            index,
+                                            language,
+                                        );
+                                    }
+                                });
+                        }
+                        LevelSelectTab::Custom => {
+                            spawn_panel_text(
+                                parent,
+                                ui_assets,
+                                get_text(&CUSTOM_LEVELS_HINT, language),
+                                13.0,
+                                Color::srgb(0.8, 0.8, 0.8),
+                            );
+
+                            if custom_files.0.is_empty() {
+                                spawn_panel_text(
+                                    parent,
+                                    ui_assets,
+                                    get_text(&NO_CUSTOM_LEVELS, language),
+                                    16.0,
+                                    Color::srgb(0.6, 0.6, 0.6),
+                                );
+                            }
+
+                            for (index, level_file) in custom_files.0.iter().enumerate() {
+                                spawn_custom_level_row(
+                                    parent, ui_assets, images, level_file, index, language,
+                                );
+                            }
+                        }
+                    }
+
+                    spawn_panel_button(
+                        parent,
+                        ui_assets,
+                        get_text(&BACK, language),
+                        ButtonType::CloseLevelSelect,
+                    );
+                });
+        });
+}
+
+/// 战役关卡卡片：关卡布局缩略图、名称、星级、成绩或解锁条件，解锁后可直接开始
+fn spawn_level_card(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    images: &mut Assets<Image>,
+    level_manager: &LevelManager,
+    index: usize,
+    language: Language,
+) {
+    let level_id = &level_manager.available_levels[index];
+    let Some(level) = create_builtin_level(level_id, language) else {
+        return;
+    };
+
+    let unlocked = level_manager.is_level_unlocked(index);
+    let record = level_manager.level_records.get(level_id);
+    let stars = record.map_or(0, |record| record.stars);
+
+    let status = match (unlocked, level_manager.required_stars(index), record) {
+        (false, Some(required), _) => get_text_with_args(
+            &LEVEL_REQUIRES_STARS,
+            language,
+            &[
+                &required.to_string(),
+                &level_manager.total_stars().to_string(),
+            ],
+        ),
+        (false, None, _) => get_text(&LEVEL_LOCKED, language),
+        (true, _, Some(record)) => get_text_with_args(
+            &LEVEL_BEST_RECORD,
+            language,
+            &[
+                &record.best_score.to_string(),
+                &format!("{:.1}", record.best_time),
+            ],
+        ),
+        (true, _, None) => get_text(&LEVEL_NOT_CLEARED, language),
+    };
+
+    let name_color = if unlocked {
+        Color::WHITE
+    } else {
+        Color::srgb(0.5, 0.5, 0.5)
+    };
+
+    // 缩略图直接由内置关卡数据渲染（地形 + 站点），与导出的关卡文件一致
+    let thumbnail = LevelThumbnail::from_level(&level);
+    let thumbnail_height =
+        LEVEL_CARD_THUMBNAIL_WIDTH * thumbnail.height as f32 / thumbnail.width.max(1) as f32;
+
+    parent
+        .spawn((
+            Node {
+                width: Px(LEVEL_CARD_WIDTH),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(4.0),
+                padding: UiRect::all(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+        ))
+        .with_children(|parent| {
+            if let Some(image) = thumbnail.to_image() {
+                let mut image_node = ImageNode::new(images.add(image));
+                if !unlocked {
+                    image_node = image_node.with_color(LOCKED_THUMBNAIL_TINT);
+                }
+                parent.spawn((
+                    image_node,
+                    Node {
+                        width: Px(LEVEL_CARD_THUMBNAIL_WIDTH),
+                        height: Px(thumbnail_height),
+                        ..default()
+                    },
+                ));
+            }
+
+            spawn_panel_text(
+                parent,
+                ui_assets,
+                format!("{}. {}", index + 1, level.get_localized_name(language)),
+                16.0,
+                name_color,
+            );
+            spawn_panel_text(
+                parent,
+                ui_assets,
+                star_string(stars),
+                16.0,
+                Color::srgb(1.0, 0.85, 0.2),
+            );
+            spawn_panel_text(parent, ui_assets, status, 12.0, Color::srgb(0.8, 0.8, 0.8));
+
+            if unlocked {
+                spawn_panel_button(
+                    parent,
+                    ui_assets,
+                    get_text(&CHALLENGE_PLAY, language),
+                    ButtonType::SelectLevel(index),
+                );
+            }
+        });
+}
+
+fn spawn_custom_level_row(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    images: &mut Assets<Image>,
+    level_file: &LevelFile,
+    index: usize,
+    language: Language,
+) {
+    let thumbnail = &level_file.thumbnail;
+
+    parent
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Px(12.0),
+                padding: UiRect::all(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+        ))
+        .with_children(|parent| {
+            if let Some(image) = thumbnail.to_image() {
+                parent.spawn((
+                    ImageNode::new(images.add(image)),
+                    Node {
+                        width: Px(thumbnail.width as f32 * THUMBNAIL_SCALE),
+                        height: Px(thumbnail.height as f32 * THUMBNAIL_SCALE),
+                        ..default()
+                    },
+                ));
+            }
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    flex_grow: 1.0,
+                    row_gap: Px(4.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_panel_text(
+                        parent,
+                        ui_assets,
+                        level_file.metadata.name.clone(),
+                        18.0,
+                        Color::WHITE,
+                    );
+                    spawn_panel_text(
+                        parent,
+                        ui_assets,
+                        get_text_with_args(
+                            &CUSTOM_LEVEL_INFO,
+                            language,
+                            &[
+                                &level_file.level.difficulty.to_string(),
+                                &level_file.level.stations.len().to_string(),
+                            ],
+                        ),
+                        12.0,
+                        Color::srgb(0.8, 0.8, 0.8),
+                    );
+                });
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(6.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_panel_button(
+                        parent,
+                        ui_assets,
+                        get_text(&CHALLENGE_PLAY, language),
+                        ButtonType::PlayCustomLevelFile(index),
+                    );
+                    spawn_panel_button(
+                        parent,
+                        ui_assets,
+                        get_text(&CONFIRM_DELETE, language),
+                        ButtonType::DeleteCustomLevelFile(index),
+                    );
+                });
+        });
+}
+
+fn spawn_tab_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    tab: LevelSelectTab,
+    selected: bool,
+) {
+    let background = if selected {
+        Color::srgb(0.5, 0.4, 0.15)
+    } else {
+        Color::srgb(0.25, 0.25, 0.35)
+    };
+
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(160.0),
+                height: Px(36.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(background),
+            ButtonComponent {
+                button_type: ButtonType::LevelSelectTab(tab),
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_panel_text(parent, ui_assets, text, 16.0, Color::WHITE);
+        });
+}
+
+fn spawn_panel_text(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    font_size: f32,
+    color: Color,
+) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn spawn_panel_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    button_type: ButtonType,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(110.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_panel_text(parent, ui_assets, text, 16.0, Color::WHITE);
+        });
+}
@@ -0,0 +1,469 @@
+// src/bus_puzzle/level_stats.rs - 关卡统计（游戏过程中采样，结算后显示乘客、成本曲线和分数构成）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ButtonComponent, ButtonType, CurrentLanguage, GameScore,
+    GameState, GameStateEnum, Language, LevelCompleteData, UIAssets, CLOSE, LEVEL_STATISTICS,
+    SCORE_PART_BASE, SCORE_PART_COMBO, SCORE_PART_CONTRACTS, SCORE_PART_COST,
+    SCORE_PART_EFFICIENCY, SCORE_PART_SPEED, STATS_ARRIVED, STATS_COST_OVER_TIME, STATS_GAVE_UP,
+    STATS_PASSENGERS_OVER_TIME, STATS_SCORE_BREAKDOWN, STATS_SPAWNED,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+
+/// 每隔多少秒游戏时间采样一次
+const TELEMETRY_SAMPLE_SECS: f32 = 2.0;
+
+/// 图表最多显示的列数，长关卡的采样会按比例抽取
+const MAX_CHART_COLUMNS: usize = 60;
+
+const CHART_WIDTH: f32 = 560.0;
+const CHART_HEIGHT: f32 = 120.0;
+const CHART_POINT_SIZE: f32 = 5.0;
+
+const SPAWNED_COLOR: Color = Color::srgb(0.4, 0.7, 1.0);
+const ARRIVED_COLOR: Color = Color::srgb(0.3, 0.9, 0.4);
+const GAVE_UP_COLOR: Color = Color::srgb(0.95, 0.3, 0.3);
+const COST_COLOR: Color = Color::srgb(1.0, 0.6, 0.4);
+const SCORE_COLOR: Color = Color::srgb(0.9, 0.9, 0.3);
+
+/// 某一时刻的关卡数据（乘客人数为累计值）
+#[derive(Debug, Clone, Copy)]
+pub struct TelemetrySample {
+    pub time: f32,
+    pub spawned: u32,
+    pub arrived: u32,
+    pub gave_up: u32,
+    pub cost: u32,
+}
+
+impl TelemetrySample {
+    fn from_game_state(game_state: &GameState) -> Self {
+        let stats = &game_state.passenger_stats;
+        Self {
+            time: game_state.game_time,
+            spawned: stats.total_spawned,
+            arrived: stats.total_arrived,
+            gave_up: stats.total_gave_up,
+            cost: game_state.total_cost,
+        }
+    }
+}
+
+/// 本关游戏过程中的采样数据
+#[derive(Resource, Default)]
+pub struct LevelTelemetry {
+    pub samples: Vec<TelemetrySample>,
+}
+
+impl LevelTelemetry {
+    fn record(&mut self, game_state: &GameState) {
+        self.samples
+            .push(TelemetrySample::from_game_state(game_state));
+    }
+
+    /// 按图表列数均匀抽取采样，始终保留最后一个
+    fn chart_samples(&self) -> Vec<TelemetrySample> {
+        let count = self.samples.len();
+        if count <= MAX_CHART_COLUMNS {
+            return self.samples.clone();
+        }
+        (0..MAX_CHART_COLUMNS)
+            .map(|column| self.samples[column * (count - 1) / (MAX_CHART_COLUMNS - 1)])
+            .collect()
+    }
+}
+
+/// 统计界面根节点
+#[derive(Component)]
+pub struct LevelStatsUI;
+
+// ============ 关卡统计插件 ============
+
+pub struct LevelStatsPlugin;
+
+impl Plugin for LevelStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTelemetry>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_level_telemetry)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_level_telemetry)
+            .add_systems(OnEnter(GameStateEnum::LevelComplete), record_final_sample)
+            .add_systems(
+                Update,
+                sample_level_telemetry.run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(
+                Update,
+                handle_level_stats_buttons.run_if(in_state(GameStateEnum::LevelComplete)),
+            );
+    }
+}
+
+fn reset_level_telemetry(mut telemetry: ResMut<LevelTelemetry>) {
+    telemetry.samples.clear();
+}
+
+/// 按游戏时间采样，暂停和加速都不影响采样间隔
+fn sample_level_telemetry(game_state: Res<GameState>, mut telemetry: ResMut<LevelTelemetry>) {
+    let due = telemetry
+        .samples
+        .last()
+        .is_none_or(|last| game_state.game_time - last.time >= TELEMETRY_SAMPLE_SECS);
+    if due {
+        telemetry.record(&game_state);
+    }
+}
+
+/// 过关时补上最后一个采样，图表终点与结算数据一致
+fn record_final_sample(game_state: Res<GameState>, mut telemetry: ResMut<LevelTelemetry>) {
+    telemetry.record(&game_state);
+}
+
+fn handle_level_stats_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    stats_ui: Query<Entity, With<LevelStatsUI>>,
+    telemetry: Res<LevelTelemetry>,
+    level_complete_data: Res<LevelCompleteData>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::OpenLevelStats if stats_ui.is_empty() => {
+                spawn_level_stats_ui(
+                    &mut commands,
+                    &ui_assets,
+                    &telemetry,
+                    &level_complete_data.score_breakdown,
+                    current_language.language,
+                );
+            }
+            ButtonType::CloseLevelStats => {
+                for entity in stats_ui.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ============ 统计界面 ============
+
+fn spawn_level_stats_ui(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    telemetry: &LevelTelemetry,
+    score: &GameScore,
+    language: Language,
+) {
+    let samples = telemetry.chart_samples();
+    let last = samples.last().copied();
+
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            ZIndex(3000),
+            StateScoped(GameStateEnum::LevelComplete),
+            LevelStatsUI,
+            Name::new("Level Stats UI"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(CHART_WIDTH + 60.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(8.0),
+                        padding: UiRect::all(Px(30.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.95)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.3, 0.7, 0.9)),
+                ))
+                .with_children(|parent| {
+                    spawn_stats_text(
+                        parent,
+                        ui_assets,
+                        get_text(&LEVEL_STATISTICS, language),
+                        28.0,
+                        Color::WHITE,
+                    );
+
+                    // 乘客曲线
+                    spawn_stats_text(
+                        parent,
+                        ui_assets,
+                        get_text(&STATS_PASSENGERS_OVER_TIME, language),
+                        16.0,
+                        SCORE_COLOR,
+                    );
+                    let passenger_max = samples
+                        .iter()
+                        .map(|sample| sample.spawned.max(sample.arrived).max(sample.gave_up))
+                        .max()
+                        .unwrap_or(0);
+                    spawn_line_chart(
+                        parent,
+                        &[
+                            (
+                                samples.iter().map(|sample| sample.spawned).collect(),
+                                SPAWNED_COLOR,
+                            ),
+                            (
+                                samples.iter().map(|sample| sample.arrived).collect(),
+                                ARRIVED_COLOR,
+                            ),
+                            (
+                                samples.iter().map(|sample| sample.gave_up).collect(),
+                                GAVE_UP_COLOR,
+                            ),
+                        ],
+                        passenger_max,
+                    );
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Px(20.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            let legend = [
+                                (&STATS_SPAWNED, last.map_or(0, |s| s.spawned), SPAWNED_COLOR),
+                                (&STATS_ARRIVED, last.map_or(0, |s| s.arrived), ARRIVED_COLOR),
+                                (&STATS_GAVE_UP, last.map_or(0, |s| s.gave_up), GAVE_UP_COLOR),
+                            ];
+                            for (key, value, color) in legend {
+                                spawn_stats_text(
+                                    parent,
+                                    ui_assets,
+                                    get_text_with_args(key, language, &[&value.to_string()]),
+                                    14.0,
+                                    color,
+                                );
+                            }
+                        });
+
+                    // 成本柱状图
+                    let cost_max = samples.iter().map(|sample| sample.cost).max().unwrap_or(0);
+                    spawn_stats_text(
+                        parent,
+                        ui_assets,
+                        get_text_with_args(
+                            &STATS_COST_OVER_TIME,
+                            language,
+                            &[&cost_max.to_string()],
+                        ),
+                        16.0,
+                        SCORE_COLOR,
+                    );
+                    spawn_bar_chart(
+                        parent,
+                        &samples.iter().map(|sample| sample.cost).collect::<Vec<_>>(),
+                        cost_max,
+                    );
+
+                    // 分数构成
+                    spawn_stats_text(
+                        parent,
+                        ui_assets,
+                        get_text(&STATS_SCORE_BREAKDOWN, language),
+                        16.0,
+                        SCORE_COLOR,
+                    );
+                    spawn_score_breakdown(parent, ui_assets, score, language);
+
+                    spawn_stats_button(parent, ui_assets, get_text(&CLOSE, language));
+                });
+        });
+}
+
+/// 图表底板：相对定位，数据点和柱子在其中绝对定位
+fn spawn_chart_frame(
+    parent: &mut ChildSpawnerCommands<'_>,
+    contents: impl FnOnce(&mut ChildSpawnerCommands<'_>),
+) {
+    parent
+        .spawn((
+            Node {
+                width: Px(CHART_WIDTH),
+                height: Px(CHART_HEIGHT),
+                position_type: PositionType::Relative,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.05)),
+            Outline::new(Px(1.0), Val::ZERO, Color::srgba(1.0, 1.0, 1.0, 0.2)),
+        ))
+        .with_children(contents);
+}
+
+/// 数据点在图表中的高度比例
+fn chart_ratio(value: u32, max: u32) -> f32 {
+    if max == 0 {
+        0.0
+    } else {
+        value as f32 / max as f32
+    }
+}
+
+/// 折线图：每个采样画一个点，多条曲线共用纵轴
+fn spawn_line_chart(parent: &mut ChildSpawnerCommands<'_>, series: &[(Vec<u32>, Color)], max: u32) {
+    spawn_chart_frame(parent, |chart| {
+        for (values, color) in series {
+            let step = CHART_WIDTH / values.len().saturating_sub(1).max(1) as f32;
+            for (index, value) in values.iter().enumerate() {
+                let ratio = chart_ratio(*value, max);
+                chart.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Px(index as f32 * step - CHART_POINT_SIZE / 2.0),
+                        bottom: Px(ratio * CHART_HEIGHT - CHART_POINT_SIZE / 2.0),
+                        width: Px(CHART_POINT_SIZE),
+                        height: Px(CHART_POINT_SIZE),
+                        ..default()
+                    },
+                    BackgroundColor(*color),
+                ));
+            }
+        }
+    });
+}
+
+/// 柱状图：每个采样一根柱子
+fn spawn_bar_chart(parent: &mut ChildSpawnerCommands<'_>, values: &[u32], max: u32) {
+    spawn_chart_frame(parent, |chart| {
+        let width = CHART_WIDTH / values.len().max(1) as f32;
+        for (index, value) in values.iter().enumerate() {
+            chart.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Px(index as f32 * width),
+                    bottom: Px(0.0),
+                    width: Px((width - 1.0).max(1.0)),
+                    height: Percent(chart_ratio(*value, max) * 100.0),
+                    ..default()
+                },
+                BackgroundColor(COST_COLOR),
+            ));
+        }
+    });
+}
+
+/// 分数构成：每项一行横条，长度按占基础分与奖励总和的比例
+fn spawn_score_breakdown(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    score: &GameScore,
+    language: Language,
+) {
+    let parts = [
+        (&SCORE_PART_BASE, score.base_points),
+        (&SCORE_PART_EFFICIENCY, score.efficiency_bonus),
+        (&SCORE_PART_SPEED, score.speed_bonus),
+        (&SCORE_PART_COST, score.cost_bonus),
+        (&SCORE_PART_COMBO, score.combo_bonus),
+        (&SCORE_PART_CONTRACTS, score.contract_bonus),
+    ];
+    let max = parts.iter().map(|(_, value)| *value).max().unwrap_or(0);
+
+    for (key, value) in parts {
+        parent
+            .spawn(Node {
+                width: Px(CHART_WIDTH),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Px(8.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(Node {
+                        width: Px(100.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        spawn_stats_text(
+                            parent,
+                            ui_assets,
+                            get_text(key, language),
+                            13.0,
+                            Color::WHITE,
+                        );
+                    });
+                parent.spawn((
+                    Node {
+                        width: Px(chart_ratio(value, max) * (CHART_WIDTH - 180.0)),
+                        height: Px(12.0),
+                        ..default()
+                    },
+                    BackgroundColor(SCORE_COLOR),
+                ));
+                spawn_stats_text(parent, ui_assets, value.to_string(), 13.0, Color::WHITE);
+            });
+    }
+
+    spawn_stats_text(
+        parent,
+        ui_assets,
+        format!("×{:.2} = {}", score.multiplier, score.total_score),
+        14.0,
+        SCORE_COLOR,
+    );
+}
+
+fn spawn_stats_text(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    font_size: f32,
+    color: Color,
+) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn spawn_stats_button(parent: &mut ChildSpawnerCommands<'_>, ui_assets: &UIAssets, label: String) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(200.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::top(Px(10.0)),
+                ..default()
+            },
+            ImageNode::new(ui_assets.button_texture.clone()),
+            ButtonComponent {
+                button_type: ButtonType::CloseLevelStats,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_stats_text(parent, ui_assets, label, 18.0, Color::WHITE);
+        });
+}
@@ -1,35 +1,68 @@
 // src/bus_puzzle/level_system.rs
 
 use crate::bus_puzzle::{
-    get_passenger_color, get_text, get_text_with_args, AgentState, CurrentLanguage, GameState,
-    GameStateEnum, GridPos, GridTile, Language, LevelCompletedEvent, LevelManager, LocalizedText,
-    PassengerColor, PassengerEntity, PassengerSpawnedEvent, PathfindingAgent, RouteSegment,
-    RouteSegmentType, StationEntity, StationType, TerrainType, CENTRAL_HUB,
-    DEFAULT_PASSENGER_PATIENCE, DEFAULT_TILE_SIZE, LEVEL_MULTIPLE, LEVEL_TRANSFER, LEVEL_TUTORIAL,
-    MULTIPLE_DESCRIPTION, NORTHEAST_STATION, NORTH_STATION, OBJECTIVE_CONNECT_ALL,
-    OBJECTIVE_MAX_COST, OBJECTIVE_MAX_SEGMENTS, OBJECTIVE_MAX_TRANSFERS,
-    OBJECTIVE_PASSENGER_SATISFACTION, OBJECTIVE_TIME_LIMIT, PASSENGER_Z, ROUTE_Z,
-    SOUTHEAST_STATION, SOUTH_STATION, START_STATION, STATION_A, STATION_B, STATION_C, STATION_Z,
-    TARGET_STATION_A, TARGET_STATION_B, TARGET_STATION_C, TERRAIN_Z, TIME_PRESSURE_DESCRIPTION,
-    TRANSFER_DESCRIPTION, TRANSFER_HUB, TUTORIAL_DESCRIPTION,
+    get_passenger_color, get_text, get_text_with_args, grid_map, localized_key, simulation_running,
+    spawn_terrain_chunks, AgentState, CurrentLanguage, DemandCurve, GameState, GameStateEnum,
+    GridPos, Language, LevelCompletedEvent, LevelManager, LocalizedText, PassengerColor,
+    PassengerEntity, PassengerSpawnedEvent, PathfindingAgent, PatienceBar, PatienceBarFill,
+    RouteSegment, RouteSegmentType, ScenarioStep, SimClock, StationEntity, StationOverloads,
+    StationType, StationUpgrades, TerrainType, ThemeRegistry, TimeCycle, ViaIcon, WeatherKind,
+    BONUS_LEAN_NETWORK, BONUS_LEAN_NETWORK_DESCRIPTION, BONUS_RUSH_HOUR,
+    BONUS_RUSH_HOUR_DESCRIPTION, CENTRAL_HUB, COLOR_BLUE, COLOR_GREEN, COLOR_ORANGE, COLOR_PURPLE,
+    COLOR_RED, COLOR_YELLOW, DEFAULT_PASSENGER_PATIENCE, DEFAULT_TILE_SIZE, DEPOT_STATION,
+    LEVEL_MULTIPLE, LEVEL_TRANSFER, LEVEL_TUTORIAL, MULTIPLE_DESCRIPTION, NORTHEAST_STATION,
+    NORTH_STATION, OBJECTIVE_CONNECT_ALL, OBJECTIVE_MAX_COST, OBJECTIVE_MAX_SEGMENTS,
+    OBJECTIVE_MAX_TRANSFERS, OBJECTIVE_PASSENGER_SATISFACTION, OBJECTIVE_TIME_LIMIT,
+    PASSENGER_PATIENCE_BAR_HEIGHT, PASSENGER_PATIENCE_BAR_OFFSET_Y, PASSENGER_PATIENCE_BAR_WIDTH,
+    PASSENGER_Z, ROUTE_Z, SOUTHEAST_STATION, SOUTH_STATION, START_STATION, STATION_A, STATION_B,
+    STATION_C, STATION_Z, TARGET_STATION_A, TARGET_STATION_B, TARGET_STATION_C,
+    TIME_PRESSURE_DESCRIPTION, TRANSFER_DESCRIPTION, TRANSFER_HUB, TUTORIAL_DESCRIPTION,
 };
 use bevy::{platform::collections::HashMap, prelude::*};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// 成组乘客中其余成员相对主图标的位置
+const GROUP_MEMBER_OFFSETS: [Vec2; 3] = [
+    Vec2::new(-14.0, -10.0),
+    Vec2::new(14.0, -10.0),
+    Vec2::new(0.0, -18.0),
+];
+
+/// 没有持续时间的站点过载事件默认持续的秒数
+const DEFAULT_OVERLOAD_DURATION: f32 = 20.0;
+
+/// 过载站点的颜色
+const OVERLOADED_STATION_COLOR: Color = Color::srgb(1.0, 0.45, 0.45);
+
+/// 车场的颜色（与终点站共用贴图）
+const DEPOT_STATION_COLOR: Color = Color::srgb(0.55, 0.7, 1.0);
+
+/// 站点平时的颜色
+fn station_base_color(station_type: &StationType) -> Color {
+    match station_type {
+        StationType::Depot => DEPOT_STATION_COLOR,
+        _ => Color::WHITE,
+    }
+}
 
 // ============ 关卡数据结构 ============
 
 // 直接在现有的LevelData中添加本地化支持
-#[derive(Debug, Clone, PartialEq, Resource)]
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
 pub struct LevelData {
     pub id: String,
     // 保留原有字段作为默认值，添加可选的本地化键
-    pub name: String,                             // 保留：用作fallback或默认语言
-    pub description: String,                      // 保留：用作fallback
+    pub name: String,        // 保留：用作fallback或默认语言
+    pub description: String, // 保留：用作fallback
+    #[serde(with = "localized_key")]
     pub name_key: Option<&'static LocalizedText>, // 新增：可选的本地化键
+    #[serde(with = "localized_key")]
     pub description_key: Option<&'static LocalizedText>, // 新增：可选的本地化键
 
     pub difficulty: u32,
     pub grid_size: (u32, u32),
+    #[serde(with = "grid_map")]
     pub terrain: HashMap<GridPos, TerrainType>,
     pub stations: Vec<Station>,                  // 保持现有结构
     pub passenger_demands: Vec<PassengerDemand>, // 保持现有结构
@@ -37,39 +70,56 @@ pub struct LevelData {
     pub objectives: Vec<ObjectiveCondition>, // 保持现有结构
     pub preset_routes: Vec<PresetRoute>,
     pub dynamic_events: Vec<DynamicEvent>,
+    pub random_events: bool, // 开启后每次进入关卡从事件牌堆随机抽取 2-3 个事件
+    #[serde(default)]
+    pub manual_fleet: bool, // 手动车队：公交车用预算在车场购买并拖到线路上，不再自动生成
+    #[serde(default)]
+    pub time_cycle: Option<TimeCycle>, // 昼夜循环：乘客按需求的早晚高峰曲线生成
+    #[serde(default)]
+    pub scenario: Vec<ScenarioStep>, // 剧情时间线：按顺序检查条件并执行动作
     pub scoring: ScoringConfig,
 }
 
 // 现有的Station结构也保持不变，但添加本地化支持
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Station {
     pub position: GridPos,
     pub station_type: StationType,
-    pub name: String,                             // 保留：作为默认值
+    pub name: String, // 保留：作为默认值
+    #[serde(with = "localized_key")]
     pub name_key: Option<&'static LocalizedText>, // 新增：可选的本地化键
     pub capacity: u32,
     pub passenger_types: Vec<PassengerColor>,
 }
 
 // PassengerDemand同样处理
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PassengerDemand {
     pub color: PassengerColor,
-    pub origin: String,                                  // 保留：作为默认值
-    pub destination: String,                             // 保留：作为默认值
-    pub origin_key: Option<&'static LocalizedText>,      // 新增：可选的本地化键
+    pub origin: String,      // 保留：作为默认值
+    pub destination: String, // 保留：作为默认值
+    #[serde(with = "localized_key")]
+    pub origin_key: Option<&'static LocalizedText>, // 新增：可选的本地化键
+    #[serde(with = "localized_key")]
     pub destination_key: Option<&'static LocalizedText>, // 新增：可选的本地化键
     pub spawn_rate: f32,
     pub patience: f32,
     pub spawn_time_range: Option<(f32, f32)>,
     pub total_count: Option<u32>,
-    pub spawned_count: u32,
+    #[serde(skip)]
+    pub spawned_count: u32, // 运行时计数，不写入关卡文件
+    pub via: Option<String>, // 必须途经的中转站
+    #[serde(default)]
+    pub group_size: Option<(u32, u32)>, // 成组出行的人数范围（如一家 2–4 人），为空时单人出行
+    #[serde(default)]
+    pub demand_curve: Option<DemandCurve>, // 早晚高峰曲线，仅在开启昼夜循环的关卡生效
 }
 
 // ObjectiveCondition同样处理
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectiveCondition {
-    pub description: String,                             // 保留：作为默认值
+    pub description: String, // 保留：作为默认值
+    #[serde(with = "localized_key")]
     pub description_key: Option<&'static LocalizedText>, // 新增：可选的本地化键
     pub condition_type: ObjectiveType,
 }
@@ -80,7 +130,7 @@ impl LevelData {
     /// 获取本地化的关卡名称
     pub fn get_localized_name(&self, language: Language) -> String {
         if let Some(name_key) = self.name_key {
-            name_key.get(language).to_string()
+            name_key.get(language)
         } else {
             self.name.clone() // fallback到原始名称
         }
@@ -89,11 +139,21 @@ impl LevelData {
     /// 获取本地化的关卡描述
     pub fn get_localized_description(&self, language: Language) -> String {
         if let Some(desc_key) = self.description_key {
-            desc_key.get(language).to_string()
+            desc_key.get(language)
         } else {
             self.description.clone() // fallback到原始描述
         }
     }
+
+    /// MaxCost 目标的预算上限（没有该目标时为 None）
+    pub fn max_cost(&self) -> Option<u32> {
+        self.objectives
+            .iter()
+            .find_map(|objective| match objective.condition_type {
+                ObjectiveType::MaxCost(limit) => Some(limit),
+                _ => None,
+            })
+    }
 }
 
 #[allow(dead_code)]
@@ -101,7 +161,7 @@ impl Station {
     /// 获取本地化的站点名称
     pub fn get_localized_name(&self, language: Language) -> String {
         if let Some(name_key) = self.name_key {
-            name_key.get(language).to_string()
+            name_key.get(language)
         } else {
             self.name.clone() // fallback到原始名称
         }
@@ -113,7 +173,7 @@ impl PassengerDemand {
     /// 获取本地化的起点名称
     pub fn get_localized_origin(&self, language: Language) -> String {
         if let Some(origin_key) = self.origin_key {
-            origin_key.get(language).to_string()
+            origin_key.get(language)
         } else {
             self.origin.clone()
         }
@@ -122,11 +182,34 @@ impl PassengerDemand {
     /// 获取本地化的终点名称
     pub fn get_localized_destination(&self, language: Language) -> String {
         if let Some(dest_key) = self.destination_key {
-            dest_key.get(language).to_string()
+            dest_key.get(language)
         } else {
             self.destination.clone()
         }
     }
+
+    /// 本次生成的人数：成组出行时在范围内随机，不超过剩余名额
+    pub fn roll_group_size(&self, rng: &mut impl Rng) -> u32 {
+        let size = self.group_size.map_or(1, |(min, max)| {
+            let min = min.max(1);
+            rng.gen_range(min..=max.max(min))
+        });
+        match self.total_count {
+            Some(total) => size.min(total.saturating_sub(self.spawned_count)).max(1),
+            None => size,
+        }
+    }
+}
+
+pub fn passenger_color_name(color: PassengerColor) -> &'static LocalizedText {
+    match color {
+        PassengerColor::Red => &COLOR_RED,
+        PassengerColor::Blue => &COLOR_BLUE,
+        PassengerColor::Green => &COLOR_GREEN,
+        PassengerColor::Yellow => &COLOR_YELLOW,
+        PassengerColor::Purple => &COLOR_PURPLE,
+        PassengerColor::Orange => &COLOR_ORANGE,
+    }
 }
 
 #[allow(dead_code)]
@@ -135,7 +218,7 @@ impl ObjectiveCondition {
     pub fn get_localized_description(&self, language: Language) -> String {
         if let Some(desc_key) = self.description_key {
             match &self.condition_type {
-                ObjectiveType::ConnectAllPassengers => desc_key.get(language).to_string(),
+                ObjectiveType::ConnectAllPassengers => desc_key.get(language),
                 ObjectiveType::MaxTransfers(count) => {
                     get_text_with_args(desc_key, language, &[&count.to_string()])
                 }
@@ -152,7 +235,18 @@ impl ObjectiveCondition {
                     let percentage = (*satisfaction * 100.0) as u32;
                     get_text_with_args(desc_key, language, &[&percentage.to_string()])
                 }
-                _ => desc_key.get(language).to_string(),
+                ObjectiveType::DeliverColor(color, count) => get_text_with_args(
+                    desc_key,
+                    language,
+                    &[
+                        &count.to_string(),
+                        &get_text(passenger_color_name(*color), language),
+                    ],
+                ),
+                ObjectiveType::MaxAverageWait(seconds) => {
+                    get_text_with_args(desc_key, language, &[&format!("{:.0}", seconds)])
+                }
+                _ => desc_key.get(language),
             }
         } else {
             self.description.clone() // fallback到原始描述
@@ -162,7 +256,7 @@ impl ObjectiveCondition {
 
 // ============ 优化后的关卡创建函数 ============
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AvailableSegment {
     pub segment_type: RouteSegmentType,
     pub count: u32,
@@ -170,7 +264,7 @@ pub struct AvailableSegment {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ObjectiveType {
     ConnectAllPassengers,
     MaxTransfers(u32),
@@ -179,15 +273,17 @@ pub enum ObjectiveType {
     MinEfficiency(f32),
     TimeLimit(f32),
     PassengerSatisfaction(f32),
+    DeliverColor(PassengerColor, u32), // 指定颜色的乘客到达人数
+    MaxAverageWait(f32),               // 乘客平均等车时间（秒）
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PresetRoute {
     pub segments: Vec<(GridPos, RouteSegmentType, u32)>,
     pub is_removable: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DynamicEvent {
     pub trigger_time: f32,
     pub event_type: EventType,
@@ -195,20 +291,63 @@ pub struct DynamicEvent {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EventType {
     SegmentFailure(GridPos),
     SurgePassengers(PassengerColor, f32),
     NewDemand(PassengerDemand),
     StationOverload(String),
+    RoadClosure(Vec<GridPos>),
+    Weather(WeatherKind, Vec<GridPos>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScoringConfig {
     pub base_points: u32,
     pub efficiency_bonus: u32,
     pub speed_bonus: u32,
     pub cost_bonus: u32,
+    pub speed_time_limit: f32, // 在该时间内完成可获得速度奖励
+    pub cost_threshold: u32,   // 总成本不超过该值可获得成本奖励
+    pub multiplier: f32,       // 关卡自身的分数倍率
+    pub refund_policy: RefundPolicy,
+}
+
+/// 拆除路线段时退还多少成本；路线段本身总是回到库存，避免关卡无解
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RefundPolicy {
+    #[default]
+    Full,
+    Percentage(u32), // 按成本的百分比退还，向下取整
+    None,
+}
+
+impl RefundPolicy {
+    pub fn refund_for(self, cost: u32) -> u32 {
+        match self {
+            RefundPolicy::Full => cost,
+            RefundPolicy::Percentage(percent) => cost * percent.min(100) / 100,
+            RefundPolicy::None => 0,
+        }
+    }
+}
+
+impl ScoringConfig {
+    pub fn speed_bonus_for(&self, game_time: f32) -> u32 {
+        if game_time < self.speed_time_limit {
+            self.speed_bonus
+        } else {
+            0
+        }
+    }
+
+    pub fn cost_bonus_for(&self, total_cost: u32) -> u32 {
+        if total_cost <= self.cost_threshold {
+            self.cost_bonus
+        } else {
+            0
+        }
+    }
 }
 
 // ============ 地图生成插件 ============
@@ -218,6 +357,7 @@ pub struct LevelGenerationPlugin;
 impl Plugin for LevelGenerationPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<LevelManager>()
+            .init_resource::<StationOverloads>()
             .add_systems(Startup, setup_debug_level)
             .add_systems(
                 Update,
@@ -229,7 +369,9 @@ impl Plugin for LevelGenerationPlugin {
                         handle_level_events,
                     )
                         .run_if(in_state(GameStateEnum::Playing)),
-                    handle_dynamic_events.run_if(in_state(GameStateEnum::Playing)),
+                    (handle_dynamic_events, highlight_overloaded_stations)
+                        .chain()
+                        .run_if(simulation_running),
                     debug_passenger_spawning,
                     manual_spawn_passenger_debug.run_if(in_state(GameStateEnum::Playing)),
                 )
@@ -239,22 +381,22 @@ impl Plugin for LevelGenerationPlugin {
 }
 
 fn sync_level_data(mut level_manager: ResMut<LevelManager>, game_state: Res<GameState>) {
-    if let Some(level_data) = &game_state.current_level {
-        if level_manager
+    if let Some(level_data) = &game_state.current_level
+        && level_manager
             .current_level
             .as_ref()
             .is_none_or(|current| current.id != level_data.id)
-        {
-            level_manager.current_level = Some(level_data.clone());
-            info!("同步关卡数据: {}", level_data.name);
-        }
+    {
+        level_manager.current_level = Some(level_data.clone());
+        info!("同步关卡数据: {}", level_data.name);
     }
 }
 
 fn update_passenger_spawning(
-    time: Res<Time>,
+    sim_clock: Res<SimClock>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     mut game_state: ResMut<GameState>,
 ) {
     // 重要：使用游戏时间而不是系统时间来判断乘客生成
@@ -264,31 +406,44 @@ fn update_passenger_spawning(
 
         // 提前获取不可变借用的数据
         let level_data_ref = level_data.clone();
+        let hour = level_data.time_cycle.map(|cycle| cycle.hour_at(game_time));
 
         for demand in level_data.passenger_demands.iter_mut() {
             // 检查是否达到生成上限
-            if let Some(total_count) = demand.total_count {
-                if demand.spawned_count >= total_count {
-                    continue; // 跳过这个需求
-                }
+            if let Some(total_count) = demand.total_count
+                && demand.spawned_count >= total_count
+            {
+                continue; // 跳过这个需求
             }
 
             // 检查时间窗口 - 使用游戏时间
-            if let Some((start, end)) = demand.spawn_time_range {
-                if game_time < start || game_time > end {
-                    continue;
-                }
+            if let Some((start, end)) = demand.spawn_time_range
+                && (game_time < start || game_time > end)
+            {
+                continue;
             }
 
-            // 计算生成概率
-            let spawn_chance = demand.spawn_rate * time.delta_secs();
+            // 计算生成概率，昼夜循环关卡按当前时刻的高峰曲线调整
+            let curve_multiplier = match (hour, demand.demand_curve) {
+                (Some(hour), Some(curve)) => curve.multiplier(hour),
+                _ => 1.0,
+            };
+            let spawn_chance = demand.spawn_rate * curve_multiplier * sim_clock.delta_secs();
             let random_value = rng.r#gen::<f32>();
 
             if random_value < spawn_chance {
-                // 在生成前增加计数
-                demand.spawned_count += 1;
-
-                spawn_passenger_with_icon(&mut commands, &asset_server, demand, &level_data_ref);
+                // 在生成前增加计数，成组乘客按人数计
+                let group_size = demand.roll_group_size(&mut rng);
+                demand.spawned_count += group_size;
+
+                spawn_passenger_with_icon(
+                    &mut commands,
+                    &asset_server,
+                    &theme,
+                    demand,
+                    &level_data_ref,
+                    group_size,
+                );
 
                 info!(
                     "生成乘客 {:?}: {}/{:?} (游戏时间: {:.1}s)",
@@ -355,12 +510,14 @@ fn debug_passenger_spawning(
     }
 }
 
-// 使用图标的乘客生成函数
-fn spawn_passenger_with_icon(
+// 使用图标的乘客生成函数。成组乘客是一个实体，其余成员以小图标围在旁边，一起上下车
+pub fn spawn_passenger_with_icon(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
+    theme: &ThemeRegistry,
     demand: &PassengerDemand,
     level_data: &LevelData,
+    group_size: u32,
 ) {
     if let Some(origin_station) = level_data.stations.iter().find(|s| s.name == demand.origin) {
         let tile_size = DEFAULT_TILE_SIZE;
@@ -371,81 +528,160 @@ fn spawn_passenger_with_icon(
 
         let passenger_world_pos = Vec3::new(world_pos.x, world_pos.y, PASSENGER_Z);
 
-        // 根据乘客颜色选择对应的图标纹理
-        let texture_path = match demand.color {
-            PassengerColor::Red => "textures/passengers/red.png",
-            PassengerColor::Blue => "textures/passengers/blue.png",
-            PassengerColor::Green => "textures/passengers/green.png",
-            PassengerColor::Yellow => "textures/passengers/yellow.png",
-            PassengerColor::Purple => "textures/passengers/purple.png",
-            PassengerColor::Orange => "textures/passengers/orange.png",
+        let agent = PathfindingAgent {
+            color: demand.color,
+            origin: demand.origin.clone(),
+            destination: demand.destination.clone(),
+            current_path: Vec::new(),
+            current_step: 0,
+            state: AgentState::WaitingAtStation,
+            patience: demand.patience,
+            max_patience: demand.patience,
+            waiting_time: 0.0,
+            via: demand.via.clone(),
+            lines_ridden: Vec::new(),
+            group_size,
         };
-
-        // 尝试加载乘客图标纹理，并设置颜色作为回退方案
-        let texture_handle = asset_server.load(texture_path);
-        let passenger_color = get_passenger_color(demand.color);
-
-        let _entity = commands
-            .spawn((
-                Name::new(format!(
-                    "Passenger {:?} {} -> {}",
-                    demand.color, demand.origin, demand.destination
-                )),
-                Sprite {
-                    image: texture_handle,
-                    custom_size: Some(Vec2::new(32.0, 32.0)), // 设置合适的大小
-                    color: passenger_color, // 使用颜色作为着色，如果纹理加载失败会显示纯色方块
-                    ..default()
-                },
-                Transform::from_translation(passenger_world_pos),
-                PassengerEntity {
-                    color: demand.color,
-                    origin: demand.origin.clone(),
-                    destination: demand.destination.clone(),
-                    current_patience: demand.patience,
-                    path: Vec::new(),
-                },
-                PathfindingAgent {
-                    color: demand.color,
-                    origin: demand.origin.clone(),
-                    destination: demand.destination.clone(),
-                    current_path: Vec::new(),
-                    current_step: 0,
-                    state: AgentState::WaitingAtStation,
-                    patience: demand.patience,
-                    max_patience: demand.patience,
-                    waiting_time: 0.0,
-                },
-            ))
-            .id();
-
-        info!(
-            "生成乘客图标: {:?} {} -> {} (纹理: {})",
-            demand.color, demand.origin, demand.destination, texture_path
+        spawn_passenger_entity(
+            commands,
+            asset_server,
+            theme,
+            agent,
+            passenger_world_pos,
+            level_data,
         );
+
         commands.send_event(PassengerSpawnedEvent {
             color: demand.color,
             origin: demand.origin.clone(),
             destination: demand.destination.clone(),
+            group_size,
         });
     } else {
         error!("找不到起点站: {}", demand.origin);
     }
 }
 
+/// 生成乘客实体（含同行成员和中转站图标），新生成乘客和恢复存档共用
+pub fn spawn_passenger_entity(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    theme: &ThemeRegistry,
+    agent: PathfindingAgent,
+    world_pos: Vec3,
+    level_data: &LevelData,
+) -> Entity {
+    // 根据乘客颜色选择对应的图标纹理
+    let texture_path = theme.passenger(agent.color);
+
+    // 尝试加载乘客图标纹理，并设置颜色作为回退方案
+    let texture_handle = asset_server.load(&texture_path);
+    let passenger_color = get_passenger_color(agent.color);
+    let group_size = agent.group_size;
+    let via_station = agent
+        .via
+        .as_ref()
+        .and_then(|via| level_data.stations.iter().find(|s| s.name == *via));
+
+    info!(
+        "生成乘客图标: {:?} {} -> {} (纹理: {})",
+        agent.color, agent.origin, agent.destination, texture_path
+    );
+
+    let entity = commands
+        .spawn((
+            Name::new(format!(
+                "Passenger {:?} {} -> {}",
+                agent.color, agent.origin, agent.destination
+            )),
+            Sprite {
+                image: texture_handle,
+                custom_size: Some(Vec2::new(32.0, 32.0)), // 设置合适的大小
+                color: passenger_color, // 使用颜色作为着色，如果纹理加载失败会显示纯色方块
+                ..default()
+            },
+            Transform::from_translation(world_pos),
+            PassengerEntity {
+                color: agent.color,
+                origin: agent.origin.clone(),
+                destination: agent.destination.clone(),
+                current_patience: agent.patience,
+                path: Vec::new(),
+            },
+            agent,
+        ))
+        .id();
+
+    for member in 1..group_size {
+        let offset = GROUP_MEMBER_OFFSETS[(member as usize - 1) % GROUP_MEMBER_OFFSETS.len()];
+        commands.entity(entity).with_child((
+            Sprite {
+                image: asset_server.load(&texture_path),
+                custom_size: Some(Vec2::splat(20.0)),
+                color: passenger_color,
+                ..default()
+            },
+            Transform::from_translation(offset.extend(-0.05)),
+            Name::new("Group Member"),
+        ));
+    }
+
+    // 多段行程：头顶显示中转站图标，到达中转站后移除
+    if let Some(via_station) = via_station {
+        commands.entity(entity).with_child((
+            Sprite {
+                image: asset_server.load(theme.station(&via_station.station_type)),
+                custom_size: Some(Vec2::splat(16.0)),
+                ..default()
+            },
+            Transform::from_xyz(14.0, 16.0, 0.1),
+            ViaIcon,
+            Name::new("Via Icon"),
+        ));
+    }
+
+    // 耐心条默认隐藏，由 update_patience_bars 在耐心不足时显示
+    let bar_size = Vec2::new(PASSENGER_PATIENCE_BAR_WIDTH, PASSENGER_PATIENCE_BAR_HEIGHT);
+    commands.entity(entity).with_children(|parent| {
+        parent
+            .spawn((
+                Sprite::from_color(Color::srgba(0.0, 0.0, 0.0, 0.6), bar_size),
+                Transform::from_xyz(0.0, PASSENGER_PATIENCE_BAR_OFFSET_Y, 0.2),
+                Visibility::Hidden,
+                PatienceBar,
+                Name::new("Patience Bar"),
+            ))
+            .with_child((
+                Sprite::from_color(Color::WHITE, bar_size),
+                Transform::from_xyz(0.0, 0.0, 0.01),
+                PatienceBarFill,
+            ));
+    });
+
+    entity
+}
+
 fn manual_spawn_passenger_debug(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     game_state: Res<GameState>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::F3) {
-        if let Some(level_data) = &game_state.current_level {
-            if let Some(demand) = level_data.passenger_demands.first() {
-                spawn_passenger_with_icon(&mut commands, &asset_server, demand, level_data);
-                info!("手动生成测试乘客: {:?}", demand.color);
-            }
-        }
+    if keyboard_input.just_pressed(KeyCode::F3)
+        && let Some(level_data) = &game_state.current_level
+        && let Some(demand) = level_data.passenger_demands.first()
+    {
+        let group_size = demand.roll_group_size(&mut rand::thread_rng());
+        spawn_passenger_with_icon(
+            &mut commands,
+            &asset_server,
+            &theme,
+            demand,
+            level_data,
+            group_size,
+        );
+        info!("手动生成测试乘客: {:?}", demand.color);
     }
 }
 
@@ -469,88 +705,181 @@ fn setup_debug_level(
     info!("设置教学关卡作为默认关卡");
 }
 
+/// 按游戏时间触发关卡事件。记录上一帧的时间，只在越过触发点的那一帧执行一次，
+/// 有持续时间的事件在结束时恢复（路段修复、客流回落、站点解除过载）
 fn handle_dynamic_events(
-    time: Res<Time>,
-    level_manager: Res<LevelManager>,
+    mut game_state: ResMut<GameState>,
+    mut overloads: ResMut<StationOverloads>,
+    mut last_time: Local<f32>,
     mut route_segments: Query<&mut RouteSegment>,
 ) {
-    if let Some(level_data) = &level_manager.current_level {
-        let current_time = time.elapsed_secs();
-
-        for event in &level_data.dynamic_events {
-            if (current_time - event.trigger_time).abs() < 0.1 {
-                match &event.event_type {
-                    EventType::SegmentFailure(pos) => {
-                        for mut segment in route_segments.iter_mut() {
-                            if segment.grid_pos == *pos {
-                                segment.is_active = false;
-                            }
+    let current_time = game_state.game_time;
+    // 重新开始关卡后游戏时间归零
+    if current_time < *last_time {
+        *last_time = 0.0;
+        if !overloads.0.is_empty() {
+            overloads.0.clear();
+        }
+    }
+    let previous_time = *last_time;
+    *last_time = current_time;
+
+    let Some(level_data) = &mut game_state.current_level else {
+        return;
+    };
+    let crossed = |time: f32| previous_time < time && time <= current_time;
+
+    // 过载到期的站点恢复上车（只在有站点到期时修改，避免每帧触发变更检测）
+    if overloads
+        .0
+        .values()
+        .any(|relief_time| current_time >= *relief_time)
+    {
+        overloads.0.retain(|station_name, relief_time| {
+            let overloaded = current_time < *relief_time;
+            if !overloaded {
+                info!("站点恢复正常: {}", station_name);
+            }
+            overloaded
+        });
+    }
+
+    for event in level_data.dynamic_events.clone() {
+        if let Some(duration) = event.duration
+            && crossed(event.trigger_time + duration)
+        {
+            match &event.event_type {
+                EventType::SegmentFailure(pos) => {
+                    for mut segment in route_segments.iter_mut() {
+                        if segment.grid_pos == *pos {
+                            segment.is_active = true;
                         }
                     }
-                    EventType::SurgePassengers(color, multiplier) => {
-                        info!("客流激增: {:?} 乘客增加 {}倍", color, multiplier);
-                    }
-                    EventType::NewDemand(demand) => {
-                        info!("新需求出现: {} -> {}", demand.origin, demand.destination);
-                    }
-                    EventType::StationOverload(station_name) => {
-                        info!("站点过载: {}", station_name);
+                    info!("路段恢复: {:?}", pos);
+                }
+                EventType::SurgePassengers(color, multiplier) if *multiplier > 0.0 => {
+                    scale_spawn_rate(level_data, *color, 1.0 / multiplier);
+                    info!("客流回落: {:?}", color);
+                }
+                _ => {}
+            }
+        }
+
+        if !crossed(event.trigger_time) {
+            continue;
+        }
+
+        match event.event_type {
+            EventType::SegmentFailure(pos) => {
+                for mut segment in route_segments.iter_mut() {
+                    if segment.grid_pos == pos {
+                        segment.is_active = false;
                     }
                 }
+                info!("路段故障: {:?}", pos);
             }
+            EventType::SurgePassengers(color, multiplier) => {
+                scale_spawn_rate(level_data, color, multiplier);
+                info!("客流激增: {:?} 乘客增加 {}倍", color, multiplier);
+            }
+            EventType::NewDemand(mut demand) => {
+                // 有持续时间的新需求只在事件期间生成乘客
+                if demand.spawn_time_range.is_none()
+                    && let Some(duration) = event.duration
+                {
+                    demand.spawn_time_range =
+                        Some((event.trigger_time, event.trigger_time + duration));
+                }
+                demand.spawned_count = 0;
+                info!("新需求出现: {} -> {}", demand.origin, demand.destination);
+                level_data.passenger_demands.push(demand);
+            }
+            EventType::StationOverload(station_name) => {
+                let relief_time =
+                    current_time + event.duration.unwrap_or(DEFAULT_OVERLOAD_DURATION);
+                info!(
+                    "站点过载: {}，{:.0} 秒内无法上车",
+                    station_name,
+                    relief_time - current_time
+                );
+                overloads.0.insert(station_name, relief_time);
+            }
+            EventType::RoadClosure(tiles) => {
+                // 封路的格子和屏障由 road_closures 按游戏时间同步
+                info!("道路封闭: {:?}", tiles);
+            }
+            EventType::Weather(kind, tiles) => {
+                // 天气影响的格子由 weather 按游戏时间同步
+                info!("天气变化: {:?} 影响 {} 个格子", kind, tiles.len());
+            }
+        }
+    }
+}
+
+/// 按倍数调整某种颜色乘客的生成速度
+fn scale_spawn_rate(level_data: &mut LevelData, color: PassengerColor, factor: f32) {
+    for demand in &mut level_data.passenger_demands {
+        if demand.color == color {
+            demand.spawn_rate *= factor;
         }
     }
 }
 
+/// 过载的站点染成红色，恢复后还原
+fn highlight_overloaded_stations(
+    overloads: Res<StationOverloads>,
+    mut stations: Query<(&StationEntity, &mut Sprite)>,
+) {
+    if !overloads.is_changed() {
+        return;
+    }
+
+    for (station, mut sprite) in stations.iter_mut() {
+        sprite.color = if overloads.is_overloaded(&station.station_data.name) {
+            OVERLOADED_STATION_COLOR
+        } else {
+            station_base_color(&station.station_data.station_type)
+        };
+    }
+}
+
 // ============ 地图生成核心函数 ============
 
 pub fn generate_level_map(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
+    theme: &ThemeRegistry,
     level_data: &LevelData,
     tile_size: f32,
 ) {
     let (width, height) = level_data.grid_size;
 
-    // 生成地形网格
-    for x in 0..width as i32 {
-        for y in 0..height as i32 {
-            let grid_pos = GridPos::new(x, y);
-            let mut world_pos = grid_pos.to_world_pos(tile_size, width, height);
-            world_pos.z = TERRAIN_Z;
-
-            let terrain_type = level_data
-                .terrain
-                .get(&grid_pos)
-                .cloned()
-                .unwrap_or(TerrainType::Empty);
-
-            let texture_path = get_terrain_texture(&terrain_type);
-
-            commands.spawn((
-                Sprite::from_image(asset_server.load(texture_path)),
-                Transform::from_translation(world_pos),
-                GridTile {
-                    grid_pos,
-                    terrain_type,
-                },
-            ));
-        }
-    }
+    // 生成地形（按区块合并渲染）
+    spawn_terrain_chunks(
+        commands,
+        &level_data.terrain,
+        level_data.grid_size,
+        tile_size,
+    );
 
     // 生成站点
     for station in &level_data.stations {
         let mut world_pos = station.position.to_world_pos(tile_size, width, height);
         world_pos.z = STATION_Z;
 
-        let texture_path = get_station_texture(&station.station_type);
+        let texture_path = theme.station(&station.station_type);
 
         commands.spawn((
-            Sprite::from_image(asset_server.load(texture_path)),
+            Sprite {
+                image: asset_server.load(texture_path),
+                color: station_base_color(&station.station_type),
+                ..default()
+            },
             Transform::from_translation(world_pos),
             StationEntity {
                 station_data: station.clone(),
                 current_passengers: 0,
+                upgrades: StationUpgrades::default(),
             },
         ));
     }
@@ -561,7 +890,7 @@ pub fn generate_level_map(
             let mut world_pos = pos.to_world_pos(tile_size, width, height);
             world_pos.z = ROUTE_Z;
 
-            let texture_path = segment_type.get_texture_path();
+            let texture_path = theme.route(segment_type);
 
             commands.spawn((
                 Sprite::from_image(asset_server.load(texture_path)),
@@ -581,26 +910,21 @@ pub fn generate_level_map(
     info!("地图生成完成");
 }
 
-fn get_terrain_texture(terrain_type: &TerrainType) -> &'static str {
-    match terrain_type {
-        TerrainType::Empty => "textures/terrain/grass.png",
-        TerrainType::Building => "textures/terrain/building.png",
-        TerrainType::Water => "textures/terrain/water.png",
-        TerrainType::Park => "textures/terrain/park.png",
-        TerrainType::Mountain => "textures/terrain/mountain.png",
-    }
-}
+// ============ 示例关卡创建函数 ============
 
-fn get_station_texture(station_type: &StationType) -> &'static str {
-    match station_type {
-        StationType::BusStop => "textures/stations/bus_stop.png",
-        StationType::TransferHub => "textures/stations/transfer_hub.png",
-        StationType::Terminal => "textures/stations/terminal.png",
+/// 按关卡ID创建内置关卡
+pub fn create_builtin_level(level_id: &str, language: Language) -> Option<LevelData> {
+    match level_id {
+        "tutorial_01" => Some(create_tutorial_level(language)),
+        "level_02_transfer" => Some(create_transfer_level(language)),
+        "level_03_multiple_routes" => Some(create_multiple_routes_level(language)),
+        "level_04_time_pressure" => Some(create_time_pressure_level(language)),
+        "bonus_01_rush_hour" => Some(create_bonus_rush_hour_level(language)),
+        "bonus_02_lean_network" => Some(create_bonus_lean_network_level(language)),
+        _ => None,
     }
 }
 
-// ============ 示例关卡创建函数 ============
-
 pub fn create_tutorial_level(current_language: Language) -> LevelData {
     let mut terrain = HashMap::new();
 
@@ -655,6 +979,9 @@ pub fn create_tutorial_level(current_language: Language) -> LevelData {
             spawn_time_range: None,
             total_count: Some(3),
             spawned_count: 0,
+            via: None,
+            group_size: None,
+            demand_curve: None,
         }],
         available_segments: vec![
             AvailableSegment {
@@ -685,11 +1012,19 @@ pub fn create_tutorial_level(current_language: Language) -> LevelData {
         }],
         preset_routes: vec![],
         dynamic_events: vec![],
+        random_events: false,
+        manual_fleet: false,
+        time_cycle: None,
+        scenario: Vec::new(),
         scoring: ScoringConfig {
             base_points: 100,
             efficiency_bonus: 50,
             speed_bonus: 25,
             cost_bonus: 25,
+            speed_time_limit: 60.0,
+            cost_threshold: 10,
+            multiplier: 1.0,
+            refund_policy: RefundPolicy::Full,
         },
     }
 }
@@ -764,6 +1099,9 @@ pub fn create_transfer_level(language: Language) -> LevelData {
                 spawn_time_range: Some((3.0, 20.0)),
                 total_count: Some(2),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
             PassengerDemand {
                 color: PassengerColor::Blue,
@@ -776,6 +1114,9 @@ pub fn create_transfer_level(language: Language) -> LevelData {
                 spawn_time_range: Some((8.0, 25.0)),
                 total_count: Some(2),
                 spawned_count: 0,
+                via: Some(get_text(&TRANSFER_HUB, language)), // 必须在换乘中心中转
+                group_size: None,
+                demand_curve: None,
             },
         ],
         available_segments: vec![
@@ -809,11 +1150,19 @@ pub fn create_transfer_level(language: Language) -> LevelData {
         ],
         preset_routes: vec![],
         dynamic_events: vec![],
+        random_events: false,
+        manual_fleet: false,
+        time_cycle: None,
+        scenario: Vec::new(),
         scoring: ScoringConfig {
             base_points: 200,
             efficiency_bonus: 100,
             speed_bonus: 50,
             cost_bonus: 50,
+            speed_time_limit: 60.0,
+            cost_threshold: 15,
+            multiplier: 1.0,
+            refund_policy: RefundPolicy::Full,
         },
     }
 }
@@ -896,6 +1245,9 @@ pub fn create_multiple_routes_level(language: Language) -> LevelData {
                 spawn_time_range: Some((5.0, 30.0)),
                 total_count: Some(3),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
             PassengerDemand {
                 color: PassengerColor::Blue,
@@ -908,6 +1260,9 @@ pub fn create_multiple_routes_level(language: Language) -> LevelData {
                 spawn_time_range: Some((8.0, 35.0)),
                 total_count: Some(3),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
             PassengerDemand {
                 color: PassengerColor::Green,
@@ -920,6 +1275,9 @@ pub fn create_multiple_routes_level(language: Language) -> LevelData {
                 spawn_time_range: Some((10.0, 40.0)),
                 total_count: Some(2),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
             PassengerDemand {
                 color: PassengerColor::Yellow,
@@ -930,8 +1288,11 @@ pub fn create_multiple_routes_level(language: Language) -> LevelData {
                 spawn_rate: 0.3,
                 patience: 200.0,
                 spawn_time_range: Some((12.0, 45.0)),
-                total_count: Some(2),
+                total_count: Some(4),
                 spawned_count: 0,
+                via: None,
+                group_size: Some((2, 4)), // 一家人结伴出行
+                demand_curve: None,
             },
         ],
         available_segments: vec![
@@ -980,11 +1341,19 @@ pub fn create_multiple_routes_level(language: Language) -> LevelData {
         ],
         preset_routes: vec![],
         dynamic_events: vec![],
+        random_events: true,
+        manual_fleet: false,
+        time_cycle: None,
+        scenario: Vec::new(),
         scoring: ScoringConfig {
             base_points: 300,
             efficiency_bonus: 150,
             speed_bonus: 75,
             cost_bonus: 75,
+            speed_time_limit: 60.0,
+            cost_threshold: 25,
+            multiplier: 1.0,
+            refund_policy: RefundPolicy::Percentage(50),
         },
     }
 }
@@ -1063,6 +1432,9 @@ pub fn create_time_pressure_level(language: Language) -> LevelData {
                 spawn_time_range: Some((2.0, 15.0)),
                 total_count: Some(4),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
             PassengerDemand {
                 color: PassengerColor::Blue,
@@ -1075,6 +1447,9 @@ pub fn create_time_pressure_level(language: Language) -> LevelData {
                 spawn_time_range: Some((5.0, 20.0)),
                 total_count: Some(4),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
             PassengerDemand {
                 color: PassengerColor::Green,
@@ -1087,6 +1462,9 @@ pub fn create_time_pressure_level(language: Language) -> LevelData {
                 spawn_time_range: Some((8.0, 25.0)),
                 total_count: Some(4),
                 spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
             },
         ],
         available_segments: vec![
@@ -1130,29 +1508,110 @@ pub fn create_time_pressure_level(language: Language) -> LevelData {
         ],
         preset_routes: vec![],
         dynamic_events: vec![],
+        random_events: true,
+        manual_fleet: false,
+        time_cycle: None,
+        scenario: Vec::new(),
         scoring: ScoringConfig {
             base_points: 500,
             efficiency_bonus: 200,
             speed_bonus: 150,
             cost_bonus: 100,
+            speed_time_limit: 60.0,
+            cost_threshold: 20,
+            multiplier: 1.0,
+            refund_policy: RefundPolicy::None,
         },
     }
 }
 
+// ============ 奖励关卡 ============
+
+/// 奖励关卡 ID 及解锁所需的星星总数，排在普通关卡之后
+pub const BONUS_LEVELS: [(&str, u32); 2] =
+    [("bonus_01_rush_hour", 8), ("bonus_02_lean_network", 11)];
+
+/// 奖励关卡：多条路线的高峰版，乘客来得更快、耐心更短，从清晨开始经历早晚高峰
+pub fn create_bonus_rush_hour_level(language: Language) -> LevelData {
+    let mut level = create_multiple_routes_level(language);
+    level.id = "bonus_01_rush_hour".to_string();
+    level.name = get_text(&BONUS_RUSH_HOUR, language);
+    level.description = get_text(&BONUS_RUSH_HOUR_DESCRIPTION, language);
+    level.name_key = Some(&BONUS_RUSH_HOUR);
+    level.description_key = Some(&BONUS_RUSH_HOUR_DESCRIPTION);
+    level.difficulty = 5;
+
+    // 一半需求是早高峰的通勤，另一半是晚高峰的返程
+    for (index, demand) in level.passenger_demands.iter_mut().enumerate() {
+        demand.spawn_rate *= 1.6;
+        demand.patience *= 0.75;
+        demand.total_count = demand.total_count.map(|count| count + count / 2);
+        demand.demand_curve = Some(if index % 2 == 0 {
+            DemandCurve {
+                morning_peak: 2.5,
+                evening_peak: 1.0,
+                off_peak: 0.4,
+            }
+        } else {
+            DemandCurve {
+                morning_peak: 1.0,
+                evening_peak: 2.5,
+                off_peak: 0.4,
+            }
+        });
+    }
+    level.time_cycle = Some(TimeCycle {
+        day_length_secs: 240.0,
+        start_hour: 6.0,
+    });
+    level.scoring.multiplier = 1.5;
+    level
+}
+
+/// 奖励关卡：换乘关卡的精简版，路线段数量减半且成本要求更严，公交车需要从车场购买
+pub fn create_bonus_lean_network_level(language: Language) -> LevelData {
+    let mut level = create_transfer_level(language);
+    level.id = "bonus_02_lean_network".to_string();
+    level.name = get_text(&BONUS_LEAN_NETWORK, language);
+    level.description = get_text(&BONUS_LEAN_NETWORK_DESCRIPTION, language);
+    level.name_key = Some(&BONUS_LEAN_NETWORK);
+    level.description_key = Some(&BONUS_LEAN_NETWORK_DESCRIPTION);
+    level.difficulty = 5;
+
+    for segment in &mut level.available_segments {
+        segment.count = segment.count.div_ceil(2);
+    }
+    level.scoring.cost_threshold = level.scoring.cost_threshold * 2 / 3;
+    level.scoring.multiplier = 1.5;
+    level.scoring.refund_policy = RefundPolicy::None;
+
+    level.stations.push(Station {
+        position: GridPos::new(1, 7),
+        station_type: StationType::Depot,
+        name: get_text(&DEPOT_STATION, language),
+        name_key: Some(&DEPOT_STATION),
+        capacity: 0,
+        passenger_types: vec![],
+    });
+    level.manual_fleet = true;
+    level
+}
+
 fn handle_passenger_spawn(
     mut passenger_spawned_event: EventReader<PassengerSpawnedEvent>,
     mut game_state: ResMut<GameState>,
 ) {
-    for _spawned_passenger in passenger_spawned_event.read() {
+    for spawned_passenger in passenger_spawned_event.read() {
         // 注意：不要在这里增加计数，因为在 update_passenger_spawning 中已经增加了
-        // 只更新总体统计
-        game_state.passenger_stats.total_spawned += 1;
+        // 只更新总体统计，成组乘客按人数计
+        game_state.passenger_stats.total_spawned += spawned_passenger.group_size;
     }
 }
 
 fn handle_level_events(
     mut level_completed_events: EventReader<LevelCompletedEvent>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
+    game_state: Res<GameState>,
     // level_manager: Res<LevelManager>,
 ) {
     for event in level_completed_events.read() {
@@ -1162,11 +1621,17 @@ fn handle_level_events(
         );
 
         // 计算评级
-        let rating = calculate_level_rating(event.final_score, event.completion_time);
-        info!("Level rating: {}", rating);
+        if let Some(level_data) = &game_state.current_level {
+            let stars = calculate_level_stars(
+                event.final_score,
+                event.completion_time,
+                game_state.total_cost,
+                &level_data.scoring,
+            );
+            info!("Level rating: {}", calculate_level_rating(stars));
+        }
 
-        // 可以在这里保存成绩到本地存储
-        // save_level_completion(level_manager.current_level_index, event);
+        // 成绩由 SaveGamePlugin 写入存档
 
         // 切换到完成界面
         next_state.set(GameStateEnum::LevelComplete);
@@ -1175,14 +1640,32 @@ fn handle_level_events(
 
 // ============ 辅助函数 ============
 
-fn calculate_level_rating(score: u32, completion_time: f32) -> &'static str {
-    if score >= 300 && completion_time <= 60.0 {
-        "★★★ Perfect!"
-    } else if score >= 200 && completion_time <= 120.0 {
-        "★★ Great!"
-    } else if score >= 100 {
-        "★ Good"
-    } else {
-        "Complete"
+/// 根据分数、用时和成本计算星级（1-3）：通关 1 星，在速度奖励时限内且分数达标再得 1 星，成本不超过成本奖励阈值再得 1 星
+pub fn calculate_level_stars(
+    score: u32,
+    completion_time: f32,
+    total_cost: u32,
+    scoring: &ScoringConfig,
+) -> u32 {
+    let mut stars = 1;
+    if score >= scoring.base_points && completion_time <= scoring.speed_time_limit {
+        stars += 1;
     }
+    if total_cost <= scoring.cost_threshold {
+        stars += 1;
+    }
+    stars
+}
+
+fn calculate_level_rating(stars: u32) -> &'static str {
+    match stars {
+        3 => "★★★ Perfect!",
+        2 => "★★ Great!",
+        _ => "★ Good",
+    }
+}
+
+/// 星级显示，例如 ★★☆
+pub fn star_string(stars: u32) -> String {
+    (0..3).map(|i| if i < stars { '★' } else { '☆' }).collect()
 }
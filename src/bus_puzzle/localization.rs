@@ -1,8 +1,29 @@
 use crate::bus_puzzle::LanguageToggleText;
 #[allow(dead_code)]
-// src/bus_puzzle/localization.rs - 本地化系统核心
-use bevy::prelude::*;
+// src/bus_puzzle/localization.rs - 本地化系统核心（中英文内置，其他语言从 assets/locales 下的翻译文件加载）
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    platform::collections::HashMap,
+    prelude::*,
+    window::PrimaryWindow,
+};
 use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, RwLock};
+
+/// 翻译文件所在目录（相对 assets）
+const LOCALES_DIR: &str = "locales";
+
+/// 内置默认字体（含中文字形）
+pub const DEFAULT_FONT_PATH: &str = "fonts/quan.ttf";
+
+/// 所有语言共用的后备字体链：当前字体缺少某个字形时，排版会依次在链上的字体中查找。
+/// 除默认字体外都是可选的，放进 assets/fonts 即可生效，文件不存在时跳过
+pub const FALLBACK_FONT_CHAIN: &[&str] = &[
+    DEFAULT_FONT_PATH,
+    "fonts/NotoSans-Regular.ttf",   // 拉丁、西里尔、希腊字母
+    "fonts/NotoSansKR-Regular.ttf", // 韩文
+];
+
 // ============ 语言枚举 ============
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -10,13 +31,41 @@ pub enum Language {
     #[default]
     English,
     Chinese,
+    Japanese,
+    Spanish,
+    German,
+    Arabic,
+    Hebrew,
+}
+
+/// 文字书写方向，决定菜单和 HUD 面板是否镜像排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
 }
 
 impl Language {
+    /// 语言切换按钮按此顺序循环
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::Chinese,
+        Language::Japanese,
+        Language::Spanish,
+        Language::German,
+        Language::Arabic,
+        Language::Hebrew,
+    ];
+
     pub fn code(&self) -> &'static str {
         match self {
             Language::English => "en",
             Language::Chinese => "zh",
+            Language::Japanese => "ja",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::Arabic => "ar",
+            Language::Hebrew => "he",
         }
     }
 
@@ -24,31 +73,164 @@ impl Language {
         match self {
             Language::English => "English",
             Language::Chinese => "中文",
+            Language::Japanese => "日本語",
+            Language::Spanish => "Español",
+            Language::German => "Deutsch",
+            Language::Arabic => "العربية",
+            Language::Hebrew => "עברית",
         }
     }
+
+    pub fn next(&self) -> Language {
+        let index = Self::ALL.iter().position(|language| language == self);
+        Self::ALL[index.map_or(0, |index| (index + 1) % Self::ALL.len())]
+    }
+
+    /// 中英文文本直接写在代码里，其他语言需要加载翻译文件
+    pub fn is_builtin(&self) -> bool {
+        matches!(self, Language::English | Language::Chinese)
+    }
+
+    pub fn direction(&self) -> TextDirection {
+        match self {
+            Language::Arabic | Language::Hebrew => TextDirection::RightToLeft,
+            _ => TextDirection::LeftToRight,
+        }
+    }
+
+    /// 该语言优先使用的字体，排在后备字体链之前
+    pub fn preferred_fonts(&self) -> &'static [&'static str] {
+        match self {
+            Language::Japanese => &["fonts/NotoSansJP-Regular.ttf"],
+            Language::Arabic => &["fonts/NotoSansArabic-Regular.ttf"],
+            Language::Hebrew => &["fonts/NotoSansHebrew-Regular.ttf"],
+            _ => &[],
+        }
+    }
+
+    /// 该语言完整的字体链：第一个可用的字体作为界面字体，其余用于补缺失的字形
+    pub fn font_chain(&self) -> impl Iterator<Item = &'static str> {
+        self.preferred_fonts()
+            .iter()
+            .chain(FALLBACK_FONT_CHAIN)
+            .copied()
+    }
 }
 
 // ============ 本地化文本结构 ============
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LocalizedText {
+    pub key: &'static str, // 翻译文件中的键，与常量名相同
     pub en: &'static str,
     pub zh: &'static str,
 }
 
 impl LocalizedText {
-    pub const fn new(en: &'static str, zh: &'static str) -> Self {
-        Self { en, zh }
+    pub const fn new(key: &'static str, en: &'static str, zh: &'static str) -> Self {
+        Self { key, en, zh }
     }
 
-    pub fn get(&self, language: Language) -> &'static str {
+    /// 优先使用翻译文件，缺少的条目回退到内置文本（其他语言回退到英文）
+    pub fn get(&self, language: Language) -> String {
+        if let Some(text) = translated_text(self.key, language) {
+            return text;
+        }
+
         match language {
-            Language::English => self.en,
             Language::Chinese => self.zh,
+            _ => self.en,
         }
+        .to_string()
     }
 }
 
+/// 按键查找内置文本
+pub fn find_text(key: &str) -> Option<&'static LocalizedText> {
+    static TEXTS_BY_KEY: LazyLock<HashMap<&'static str, &'static LocalizedText>> =
+        LazyLock::new(|| ALL_TEXTS.iter().map(|text| (text.key, *text)).collect());
+
+    TEXTS_BY_KEY.get(key).copied()
+}
+
+// ============ 翻译文件 ============
+
+/// 已加载的翻译。get_text 在各处被直接调用、拿不到资源，所以放在全局表里
+static TRANSLATIONS: LazyLock<RwLock<HashMap<Language, HashMap<String, String>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::default()));
+
+fn translated_text(key: &str, language: Language) -> Option<String> {
+    TRANSLATIONS.read().ok()?.get(&language)?.get(key).cloned()
+}
+
+/// 一种语言的翻译文件，每行一条：KEY = "文本"，# 开头为注释
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct TranslationFile {
+    pub entries: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct TranslationFileLoader;
+
+impl AssetLoader for TranslationFileLoader {
+    type Asset = TranslationFile;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let content = String::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(parse_translation_file(&content))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["toml"]
+    }
+}
+
+/// 逐行解析，无法识别的行跳过；值中支持 \n、\" 和 \\ 转义
+fn parse_translation_file(content: &str) -> TranslationFile {
+    let entries = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+            Some((key.trim().to_string(), unescape(value)))
+        })
+        .collect();
+    TranslationFile { entries }
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 各语言翻译文件的句柄
+#[derive(Resource, Default)]
+pub struct LocaleFiles(Vec<(Language, Handle<TranslationFile>)>);
+
 // ============ 当前语言资源 ============
 
 #[derive(Resource, Debug, Clone)]
@@ -66,40 +248,40 @@ impl Default for CurrentLanguage {
 
 // ============ 本地化组件 ============
 
+/// 按键查找文本，语言切换或翻译文件加载后自动刷新
 #[derive(Component, Debug, Clone)]
 pub struct LocalizedTextComponent {
-    pub text_key: &'static LocalizedText,
+    pub key: &'static str,
     pub format_args: Option<Vec<String>>, // 支持格式化参数
 }
 
 impl LocalizedTextComponent {
     pub fn new(text_key: &'static LocalizedText) -> Self {
         Self {
-            text_key,
+            key: text_key.key,
             format_args: None,
         }
     }
 
     pub fn with_args(text_key: &'static LocalizedText, args: Vec<String>) -> Self {
         Self {
-            text_key,
+            key: text_key.key,
             format_args: Some(args),
         }
     }
 
     pub fn get_text(&self, language: Language) -> String {
-        let base_text = self.text_key.get(language);
-
-        if let Some(args) = &self.format_args {
-            // 简单的参数替换：{0}, {1}, {2}...
-            let mut result = base_text.to_string();
-            for (i, arg) in args.iter().enumerate() {
-                let placeholder = format!("{{{}}}", i);
-                result = result.replace(&placeholder, arg);
+        let Some(text_key) = find_text(self.key) else {
+            warn!("未知的本地化键: {}", self.key);
+            return self.key.to_string();
+        };
+
+        match &self.format_args {
+            Some(args) => {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                get_text_with_args(text_key, language, &args)
             }
-            result
-        } else {
-            base_text.to_string()
+            None => get_text(text_key, language),
         }
     }
 }
@@ -114,134 +296,1374 @@ pub struct LanguageChangedEvent {
 // ============ 文本常量定义 ============
 
 // 主菜单
-pub const GAME_TITLE: LocalizedText = LocalizedText::new("Last Stop", "下一站");
-pub const START_GAME: LocalizedText = LocalizedText::new("Start Game", "开始游戏");
-pub const QUIT_GAME: LocalizedText = LocalizedText::new("Quit Game", "退出游戏");
-pub const LANGUAGE_SETTING: LocalizedText = LocalizedText::new("Language", "语言设置");
+pub const GAME_TITLE: LocalizedText = LocalizedText::new("GAME_TITLE", "Last Stop", "下一站");
+pub const START_GAME: LocalizedText = LocalizedText::new("START_GAME", "Start Game", "开始游戏");
+pub const CONTINUE_GAME: LocalizedText =
+    LocalizedText::new("CONTINUE_GAME", "Continue", "继续上次进度");
+pub const QUIT_GAME: LocalizedText = LocalizedText::new("QUIT_GAME", "Quit Game", "退出游戏");
+pub const LANGUAGE_SETTING: LocalizedText =
+    LocalizedText::new("LANGUAGE_SETTING", "Language", "语言设置");
+pub const QUIT_CONFIRM: LocalizedText =
+    LocalizedText::new("QUIT_CONFIRM", "Quit the game?", "确定要退出游戏吗？");
+pub const CONFIRM_QUIT: LocalizedText = LocalizedText::new("CONFIRM_QUIT", "Quit", "退出");
+pub const CANCEL: LocalizedText = LocalizedText::new("CANCEL", "Cancel", "取消");
+pub const CONFIRM_RESTART_LEVEL: LocalizedText = LocalizedText::new(
+    "CONFIRM_RESTART_LEVEL",
+    "Restart this level?\nAll progress in this attempt will be lost.",
+    "确定要重新开始吗？\n本次的所有进度都会丢失。",
+);
+pub const CONFIRM_MAIN_MENU: LocalizedText = LocalizedText::new(
+    "CONFIRM_MAIN_MENU",
+    "Leave this level and return to the main menu?",
+    "确定要离开本关并返回主菜单吗？",
+);
+pub const CONFIRM_DELETE_LEVEL: LocalizedText = LocalizedText::new(
+    "CONFIRM_DELETE_LEVEL",
+    "Delete this custom level?\nThe file will be removed from custom_levels/.",
+    "确定要删除这个自定义关卡吗？\n文件将从 custom_levels/ 中移除。",
+);
+pub const CONFIRM_DELETE: LocalizedText = LocalizedText::new("CONFIRM_DELETE", "Delete", "删除");
+pub const EXPORT_RESULTS: LocalizedText =
+    LocalizedText::new("EXPORT_RESULTS", "Export CSV", "导出CSV");
+pub const RESULTS_EXPORTED: LocalizedText = LocalizedText::new(
+    "RESULTS_EXPORTED",
+    "✓ Saved to results.csv",
+    "✓ 已保存到 results.csv",
+);
+pub const RESULTS_EXPORT_FAILED: LocalizedText =
+    LocalizedText::new("RESULTS_EXPORT_FAILED", "Export failed", "导出失败");
+pub const LEVEL_STATISTICS: LocalizedText =
+    LocalizedText::new("LEVEL_STATISTICS", "📊 Statistics", "📊 详细统计");
+pub const STATS_PASSENGERS_OVER_TIME: LocalizedText = LocalizedText::new(
+    "STATS_PASSENGERS_OVER_TIME",
+    "Passengers over time",
+    "乘客随时间变化",
+);
+pub const STATS_SPAWNED: LocalizedText =
+    LocalizedText::new("STATS_SPAWNED", "● Spawned {0}", "● 出现 {0}");
+pub const STATS_ARRIVED: LocalizedText =
+    LocalizedText::new("STATS_ARRIVED", "● Arrived {0}", "● 到达 {0}");
+pub const STATS_GAVE_UP: LocalizedText =
+    LocalizedText::new("STATS_GAVE_UP", "● Gave up {0}", "● 放弃 {0}");
+pub const STATS_COST_OVER_TIME: LocalizedText = LocalizedText::new(
+    "STATS_COST_OVER_TIME",
+    "Cost over time (max {0})",
+    "成本随时间变化（最高 {0}）",
+);
+pub const STATS_SCORE_BREAKDOWN: LocalizedText =
+    LocalizedText::new("STATS_SCORE_BREAKDOWN", "Score breakdown", "分数构成");
+pub const SCORE_PART_BASE: LocalizedText = LocalizedText::new("SCORE_PART_BASE", "Base", "基础");
+pub const SCORE_PART_EFFICIENCY: LocalizedText =
+    LocalizedText::new("SCORE_PART_EFFICIENCY", "Efficiency", "效率");
+pub const SCORE_PART_SPEED: LocalizedText = LocalizedText::new("SCORE_PART_SPEED", "Speed", "速度");
+pub const SCORE_PART_COST: LocalizedText = LocalizedText::new("SCORE_PART_COST", "Cost", "成本");
+pub const SCORE_PART_COMBO: LocalizedText = LocalizedText::new("SCORE_PART_COMBO", "Combo", "连击");
+pub const SCORE_PART_CONTRACTS: LocalizedText =
+    LocalizedText::new("SCORE_PART_CONTRACTS", "Contracts", "合约");
+pub const IMPORT_CITY: LocalizedText = LocalizedText::new("IMPORT_CITY", "Import City", "导入城市");
+pub const IMPORTED_CITY: LocalizedText =
+    LocalizedText::new("IMPORTED_CITY", "Imported City", "导入的城市");
+pub const IMPORTED_CITY_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "IMPORTED_CITY_DESCRIPTION",
+    "Rebuild the bus network of {0}: {1} stops, {2} routes",
+    "重建 {0} 的公交网络：{1} 个站点，{2} 条线路",
+);
+pub const IMPORT_FAILED: LocalizedText =
+    LocalizedText::new("IMPORT_FAILED", "⚠️ Import failed: {0}", "⚠️ 导入失败: {0}");
+pub const TOUCH_ROTATE: LocalizedText = LocalizedText::new("TOUCH_ROTATE", "⟳ Rotate", "⟳ 旋转");
+pub const TOUCH_DELETE: LocalizedText = LocalizedText::new("TOUCH_DELETE", "✖ Delete", "✖ 拆除");
+pub const MUTATORS: LocalizedText = LocalizedText::new("MUTATORS", "Mutators", "规则变体");
+pub const MUTATOR_NO_ROTATION: LocalizedText =
+    LocalizedText::new("MUTATOR_NO_ROTATION", "No Rotation", "禁止旋转");
+pub const MUTATOR_LIMITED_REMOVALS: LocalizedText =
+    LocalizedText::new("MUTATOR_LIMITED_REMOVALS", "Only 3 Removals", "仅限3次拆除");
+pub const MUTATOR_NO_CURVES: LocalizedText =
+    LocalizedText::new("MUTATOR_NO_CURVES", "No Curves", "禁用弯道");
+pub const MIRROR_MODE: LocalizedText = LocalizedText::new("MIRROR_MODE", "Mirror Mode", "镜像模式");
+pub const MIRROR_PROGRESS: LocalizedText = LocalizedText::new(
+    "MIRROR_PROGRESS",
+    "Mirrored cleared: {0}/{1}",
+    "镜像通关: {0}/{1}",
+);
+pub const HOTSEAT_MODE: LocalizedText =
+    LocalizedText::new("HOTSEAT_MODE", "Hot-seat Co-op (2P)", "双人热座合作");
+pub const HOTSEAT_TURN: LocalizedText =
+    LocalizedText::new("HOTSEAT_TURN", "Player {0} · {1}s", "玩家 {0} · {1}秒");
+pub const HOTSEAT_TURN_CHANGE: LocalizedText = LocalizedText::new(
+    "HOTSEAT_TURN_CHANGE",
+    "👥 Player {0}'s turn!",
+    "👥 轮到玩家 {0}！",
+);
+pub const SIDE_CONTRACTS: LocalizedText =
+    LocalizedText::new("SIDE_CONTRACTS", "Side Contracts", "限时合约");
+pub const FULLSCREEN: LocalizedText =
+    LocalizedText::new("FULLSCREEN", "Fullscreen (F11)", "全屏 (F11)");
+pub const CONTRACTS_PANEL: LocalizedText =
+    LocalizedText::new("CONTRACTS_PANEL", "📜 Contracts", "📜 合约");
+pub const CONTRACT_LINE: LocalizedText = LocalizedText::new(
+    "CONTRACT_LINE",
+    "Deliver {0} {1} passengers within {2}s (+{3})",
+    "{2}秒内运送{0}名{1}乘客（+{3}）",
+);
+pub const CONTRACT_OFFERED: LocalizedText =
+    LocalizedText::new("CONTRACT_OFFERED", "📜 New contract: {0}", "📜 新合约：{0}");
+pub const CONTRACT_COMPLETED: LocalizedText = LocalizedText::new(
+    "CONTRACT_COMPLETED",
+    "✅ Contract complete: {0}, +{1} points",
+    "✅ 合约完成：{0}，+{1}分",
+);
+pub const CONTRACT_FAILED: LocalizedText = LocalizedText::new(
+    "CONTRACT_FAILED",
+    "⌛ Contract expired: {0}",
+    "⌛ 合约超时：{0}",
+);
+pub const SCORE_ATTACK_MODE: LocalizedText =
+    LocalizedText::new("SCORE_ATTACK_MODE", "Score Attack", "分数冲刺");
+pub const COMBO_COUNTER: LocalizedText = LocalizedText::new(
+    "COMBO_COUNTER",
+    "🔥 Combo {0} · x{1} · +{2}",
+    "🔥 连击 {0} · x{1} · +{2}",
+);
+pub const ECONOMY_MODE: LocalizedText =
+    LocalizedText::new("ECONOMY_MODE", "Economy Mode", "经营模式");
+pub const ENDLESS_MODE: LocalizedText =
+    LocalizedText::new("ENDLESS_MODE", "Endless Mode", "无尽模式");
+pub const ENDLESS_HUD: LocalizedText = LocalizedText::new(
+    "ENDLESS_HUD",
+    "♾️ Endless · Difficulty {0}",
+    "♾️ 无尽模式 · 难度 {0}",
+);
+pub const ECONOMY_HUD: LocalizedText = LocalizedText::new(
+    "ECONOMY_HUD",
+    "💰 Funds: {0}  Income: +{1}  Expenses: -{2}  Upkeep: -{3}/10s",
+    "💰 资金: {0}  收入: +{1}  支出: -{2}  养护: -{3}/10秒",
+);
+pub const INSUFFICIENT_FUNDS: LocalizedText =
+    LocalizedText::new("INSUFFICIENT_FUNDS", "💸 Not enough funds", "💸 资金不足");
+
+pub const STATION_INFO: LocalizedText = LocalizedText::new(
+    "STATION_INFO",
+    "🚏 {0}  Waiting: {1}/{2}",
+    "🚏 {0}  候车: {1}/{2}",
+);
+pub const STATION_CAPACITY_UPGRADE: LocalizedText =
+    LocalizedText::new("STATION_CAPACITY_UPGRADE", "Expand capacity", "扩建站台");
+pub const STATION_SHELTER_UPGRADE: LocalizedText =
+    LocalizedText::new("STATION_SHELTER_UPGRADE", "Shelter", "候车亭");
+pub const STATION_ACCESSIBILITY_UPGRADE: LocalizedText = LocalizedText::new(
+    "STATION_ACCESSIBILITY_UPGRADE",
+    "Accessible boarding",
+    "无障碍上车",
+);
+pub const STATION_UPGRADED: LocalizedText = LocalizedText::new(
+    "STATION_UPGRADED",
+    "🚏 Built: {0} (cost +{1})",
+    "🚏 已建成：{0}（成本 +{1}）",
+);
+pub const BUS_LOOP_CIRCULAR: LocalizedText =
+    LocalizedText::new("BUS_LOOP_CIRCULAR", "🔁 Circular loop: {0}", "🔁 环线: {0}");
+pub const BUS_LOOP_SHUTTLE: LocalizedText =
+    LocalizedText::new("BUS_LOOP_SHUTTLE", "↔ Back and forth: {0}", "↔ 往返: {0}");
+pub const EVENT_WARNING_SEGMENT_FAILURE: LocalizedText = LocalizedText::new(
+    "EVENT_WARNING_SEGMENT_FAILURE",
+    "⚠️ Track at ({0}, {1}) will fail in {2}s",
+    "⚠️ {2} 秒后 ({0}, {1}) 处的路段将发生故障",
+);
+pub const EVENT_WARNING_SURGE: LocalizedText = LocalizedText::new(
+    "EVENT_WARNING_SURGE",
+    "⚠️ Rush hour at {0} in {1}s (×{2} passengers)",
+    "⚠️ {1} 秒后 {0} 将迎来客流高峰（×{2}）",
+);
+pub const EVENT_WARNING_STATION_OVERLOAD: LocalizedText = LocalizedText::new(
+    "EVENT_WARNING_STATION_OVERLOAD",
+    "⚠️ {0} will be overloaded in {1}s",
+    "⚠️ {1} 秒后 {0} 将会过载",
+);
+pub const EVENT_WARNING_NEW_DEMAND: LocalizedText = LocalizedText::new(
+    "EVENT_WARNING_NEW_DEMAND",
+    "⚠️ New demand {0} → {1} in {2}s",
+    "⚠️ {2} 秒后将出现新需求：{0} → {1}",
+);
+pub const EVENT_WARNING_ROAD_CLOSURE: LocalizedText = LocalizedText::new(
+    "EVENT_WARNING_ROAD_CLOSURE",
+    "🚧 Road works near ({0}, {1}) in {2}s: {3} tiles closed for {4}s, reroute your lines",
+    "🚧 {2} 秒后 ({0}, {1}) 附近施工封路：{3} 个格子封闭 {4} 秒，请调整线路",
+);
+pub const ROAD_REOPENED: LocalizedText = LocalizedText::new(
+    "ROAD_REOPENED",
+    "✅ Road works finished, tiles reopened",
+    "✅ 施工结束，道路重新开放",
+);
+pub const SELECTION_INFO: LocalizedText = LocalizedText::new(
+    "SELECTION_INFO",
+    "✂️ {0} segments selected · Ctrl+C copy · Ctrl+X move · Esc clear",
+    "✂️ 已选中 {0} 个路线段 · Ctrl+C 复制 · Ctrl+X 移动 · Esc 取消",
+);
+pub const CLIPBOARD_COPIED: LocalizedText = LocalizedText::new(
+    "CLIPBOARD_COPIED",
+    "📋 Copied {0} segments · Ctrl+V to paste",
+    "📋 已复制 {0} 个路线段 · Ctrl+V 粘贴",
+);
+pub const CLIPBOARD_PASTE_HINT: LocalizedText = LocalizedText::new(
+    "CLIPBOARD_PASTE_HINT",
+    "📋 Click a tile to paste {0} segments there (it becomes the bottom-left corner) · Esc to cancel",
+    "📋 点击格子粘贴 {0} 个路线段（该格为左下角）· Esc 取消",
+);
+pub const CLIPBOARD_MOVE_HINT: LocalizedText = LocalizedText::new(
+    "CLIPBOARD_MOVE_HINT",
+    "📦 Click a tile to move {0} segments there (it becomes the bottom-left corner) · Esc to cancel",
+    "📦 点击格子把 {0} 个路线段移到那里（该格为左下角）· Esc 取消",
+);
+pub const CLIPBOARD_BLOCKED: LocalizedText = LocalizedText::new(
+    "CLIPBOARD_BLOCKED",
+    "⚠️ Can't place here: terrain, stations or other segments are in the way",
+    "⚠️ 无法放在这里：地形、站点或其他路线段挡住了",
+);
+pub const CLIPBOARD_NO_INVENTORY: LocalizedText = LocalizedText::new(
+    "CLIPBOARD_NO_INVENTORY",
+    "⚠️ Not enough segments in the inventory to paste",
+    "⚠️ 库存中的路线段不够粘贴",
+);
+pub const UNDO_DONE: LocalizedText = LocalizedText::new("UNDO_DONE", "↩️ Undone", "↩️ 已撤销");
+pub const UNDO_FAILED: LocalizedText = LocalizedText::new(
+    "UNDO_FAILED",
+    "⚠️ Can't undo: those segments have changed since",
+    "⚠️ 无法撤销：这些路线段之后已被改动",
+);
+pub const BLUEPRINT_NEED_SELECTION: LocalizedText = LocalizedText::new(
+    "BLUEPRINT_NEED_SELECTION",
+    "Shift+drag to select segments first, then press Ctrl+B to save them as a blueprint",
+    "先按住 Shift 拖动框选路线段，再按 Ctrl+B 保存为蓝图",
+);
+pub const BLUEPRINT_DEFAULT_NAME: LocalizedText =
+    LocalizedText::new("BLUEPRINT_DEFAULT_NAME", "Blueprint {0}", "蓝图 {0}");
+pub const BLUEPRINT_NAME_PROMPT: LocalizedText = LocalizedText::new(
+    "BLUEPRINT_NAME_PROMPT",
+    "Name this blueprint ({0} segments), then press Enter",
+    "为这个蓝图命名（{0} 个路线段），按回车保存",
+);
+pub const BLUEPRINT_SAVE: LocalizedText = LocalizedText::new("BLUEPRINT_SAVE", "Save", "保存");
+pub const BLUEPRINT_SAVED: LocalizedText = LocalizedText::new(
+    "BLUEPRINT_SAVED",
+    "📐 Blueprint \"{0}\" saved ({1} segments), press B to open the library",
+    "📐 已保存蓝图「{0}」（{1} 个路线段），按 B 打开蓝图库",
+);
+pub const BLUEPRINT_LIBRARY: LocalizedText =
+    LocalizedText::new("BLUEPRINT_LIBRARY", "📐 Blueprints", "📐 蓝图库");
+pub const BLUEPRINT_LIBRARY_EMPTY: LocalizedText = LocalizedText::new(
+    "BLUEPRINT_LIBRARY_EMPTY",
+    "No blueprints yet. Select segments and press Ctrl+B to save one.",
+    "还没有蓝图。框选路线段后按 Ctrl+B 保存。",
+);
+pub const BLUEPRINT_SEGMENTS: LocalizedText =
+    LocalizedText::new("BLUEPRINT_SEGMENTS", "{0} segments", "{0} 个路线段");
+pub const BLUEPRINT_STAMP: LocalizedText = LocalizedText::new("BLUEPRINT_STAMP", "Stamp", "放置");
+pub const BLUEPRINT_STAMP_HINT: LocalizedText = LocalizedText::new(
+    "BLUEPRINT_STAMP_HINT",
+    "Click a tile to stamp \"{0}\" with its bottom-left corner there (Esc to cancel)",
+    "点击格子放置「{0}」，该格为左下角（ESC 取消）",
+);
+pub const CONFIRM_DELETE_BLUEPRINT: LocalizedText = LocalizedText::new(
+    "CONFIRM_DELETE_BLUEPRINT",
+    "Delete this blueprint?\nIt will be removed from all levels.",
+    "确定要删除这个蓝图吗？\n所有关卡中都将无法再使用它。",
+);
+pub const REPAIR_HINT: LocalizedText = LocalizedText::new(
+    "REPAIR_HINT",
+    "💡 A passenger gave up: one segment at ({0}, {1}) would connect {2} to {3}",
+    "💡 有乘客放弃了：在 ({0}, {1}) 放一个路线段就能连通 {2} 和 {3}",
+);
+pub const EVENT_WARNING_WEATHER: LocalizedText = LocalizedText::new(
+    "EVENT_WARNING_WEATHER",
+    "⚠️ {0} expected near ({1}, {2}) in {3}s",
+    "⚠️ {3} 秒后 ({1}, {2}) 附近将出现{0}",
+);
+pub const WEATHER_STARTED: LocalizedText = LocalizedText::new(
+    "WEATHER_STARTED",
+    "{0} near ({1}, {2}) for {3}s: buses slow down and waiting passengers lose patience faster",
+    "{0}：({1}, {2}) 附近持续 {3} 秒，公交车减速，候车乘客更快失去耐心",
+);
+pub const WEATHER_CLEARED: LocalizedText =
+    LocalizedText::new("WEATHER_CLEARED", "🌤 The weather has cleared", "🌤 天气转晴");
+pub const WEATHER_RAIN: LocalizedText = LocalizedText::new("WEATHER_RAIN", "🌧 Rain", "🌧 降雨");
+pub const WEATHER_SNOW: LocalizedText = LocalizedText::new("WEATHER_SNOW", "❄ Snow", "❄ 降雪");
+pub const WEATHER_FOG: LocalizedText = LocalizedText::new("WEATHER_FOG", "🌫 Fog", "🌫 大雾");
+pub const TERRAIN_BRUSH: LocalizedText =
+    LocalizedText::new("TERRAIN_BRUSH", "🖌️ Terrain Brush", "🖌️ 地形画笔");
+pub const TERRAIN_EMPTY: LocalizedText = LocalizedText::new("TERRAIN_EMPTY", "Grass", "草地");
+pub const TERRAIN_WATER: LocalizedText = LocalizedText::new("TERRAIN_WATER", "Water", "水域");
+pub const TERRAIN_MOUNTAIN: LocalizedText =
+    LocalizedText::new("TERRAIN_MOUNTAIN", "Mountain", "山地");
+pub const TERRAIN_BUILDING: LocalizedText =
+    LocalizedText::new("TERRAIN_BUILDING", "Building", "建筑");
+pub const BRUSH_SIZE: LocalizedText =
+    LocalizedText::new("BRUSH_SIZE", "Brush: {0}×{0}", "笔刷: {0}×{0}");
+pub const START_SIMULATION: LocalizedText =
+    LocalizedText::new("START_SIMULATION", "▶ Start simulation", "▶ 开始模拟");
+pub const BRIEFING_START: LocalizedText = LocalizedText::new("BRIEFING_START", "▶ Start", "▶ 开始");
+pub const STATION_BURIED: LocalizedText = LocalizedText::new(
+    "STATION_BURIED",
+    "⚠️ {0} is buried by terrain, clear a tile next to it",
+    "⚠️ {0} 被地形围住了，请清理它旁边的格子",
+);
+pub const ROUTE_MANAGEMENT: LocalizedText =
+    LocalizedText::new("ROUTE_MANAGEMENT", "🚌 Lines", "🚌 线路管理");
+pub const ROUTE_NAME: LocalizedText =
+    LocalizedText::new("ROUTE_NAME", "Line {0}: {1}", "线路 {0}：{1}");
+pub const ROUTE_BUS_COUNT: LocalizedText =
+    LocalizedText::new("ROUTE_BUS_COUNT", "Buses: {0}", "车辆: {0}");
+pub const ROUTE_FREQUENCY: LocalizedText =
+    LocalizedText::new("ROUTE_FREQUENCY", "Frequency: {0}", "班次: {0}");
+pub const FREQUENCY_LOW: LocalizedText = LocalizedText::new("FREQUENCY_LOW", "Low", "低");
+pub const FREQUENCY_NORMAL: LocalizedText =
+    LocalizedText::new("FREQUENCY_NORMAL", "Normal", "正常");
+pub const FREQUENCY_HIGH: LocalizedText = LocalizedText::new("FREQUENCY_HIGH", "High", "高");
+pub const ROUTE_OPERATING_COST: LocalizedText = LocalizedText::new(
+    "ROUTE_OPERATING_COST",
+    "Operating cost: {0} per minute",
+    "运营费用: 每分钟 {0}",
+);
+pub const NO_ROUTES: LocalizedText = LocalizedText::new(
+    "NO_ROUTES",
+    "No lines yet, connect two stations first",
+    "还没有线路，请先连接两个站点",
+);
+pub const ACTIVE_LINE: LocalizedText =
+    LocalizedText::new("ACTIVE_LINE", "🎨 Drawing Line {0}", "🎨 正在绘制线路 {0}");
+pub const LINE_ASSIGNED: LocalizedText = LocalizedText::new(
+    "LINE_ASSIGNED",
+    "Segment moved to Line {0}",
+    "路线段已归入线路 {0}",
+);
+pub const ROUTE_LINES_HINT: LocalizedText = LocalizedText::new(
+    "ROUTE_LINES_HINT",
+    "🎨 New segments join the selected line (V to switch, G to move the hovered segment). Buses stay on their own line",
+    "🎨 新路线段会归入当前线路（按 V 切换，按 G 把鼠标下的路线段改到当前线路），公交车只在自己的线路上行驶",
+);
+pub const BUY_BUS: LocalizedText = LocalizedText::new(
+    "BUY_BUS",
+    "🚌 Buy Bus (-{0}) · Depot: {1}",
+    "🚌 购买公交车 (-{0}) · 车场: {1}",
+);
+pub const BUS_PURCHASED: LocalizedText = LocalizedText::new(
+    "BUS_PURCHASED",
+    "🚌 Bus purchased! Drag it from the depot onto a line",
+    "🚌 已购买公交车！把它从车场拖到线路上",
+);
+pub const BUS_ASSIGNED: LocalizedText = LocalizedText::new(
+    "BUS_ASSIGNED",
+    "🚌 Bus assigned to Line {0}",
+    "🚌 公交车已分配到线路 {0}",
+);
+pub const LINE_FLEET_FULL: LocalizedText = LocalizedText::new(
+    "LINE_FLEET_FULL",
+    "Line {0} already runs the maximum of {1} buses",
+    "线路 {0} 的车辆已达上限 {1} 辆",
+);
+pub const RUSH_HOUR: LocalizedText = LocalizedText::new("RUSH_HOUR", "🚦 Rush Hour", "🚦 高峰时段");
+pub const GAME_SPEED: LocalizedText =
+    LocalizedText::new("GAME_SPEED", "⏩ Speed {0}x", "⏩ 速度 {0}x");
+pub const GAME_SPEED_STEP: LocalizedText =
+    LocalizedText::new("GAME_SPEED_STEP", "Step ({0})", "单步 ({0})");
+pub const GAME_SPEED_STEP_HINT: LocalizedText = LocalizedText::new(
+    "GAME_SPEED_STEP_HINT",
+    "⏸ Step mode: press {0} to advance one tick, {1} / {2} to resume",
+    "⏸ 单步模式：按 {0} 前进一步，按 {1} / {2} 恢复运行",
+);
+pub const NETWORK_LAYER_GROUND: LocalizedText = LocalizedText::new(
+    "NETWORK_LAYER_GROUND",
+    "Layer: Ground (E)",
+    "图层：地面 (E)",
+);
+pub const NETWORK_LAYER_ELEVATED: LocalizedText = LocalizedText::new(
+    "NETWORK_LAYER_ELEVATED",
+    "Layer: Elevated (E)",
+    "图层：高架 (E)",
+);
+pub const ELEVATED_LAYER_HINT: LocalizedText = LocalizedText::new(
+    "ELEVATED_LAYER_HINT",
+    "🚝 Elevated layer: straights and curves cost {0}x, pass over roads and terrain, and meet the ground network only at stations",
+    "🚝 高架层：直线段和弯道费用为 {0} 倍，可跨越道路和地形，只在站点与地面路网换乘",
+);
+pub const CLOSE: LocalizedText = LocalizedText::new("CLOSE", "Close", "关闭");
+pub const SCORE_MULTIPLIER: LocalizedText =
+    LocalizedText::new("SCORE_MULTIPLIER", "Score ×{0}", "分数 ×{0}");
 
 // 游戏界面
-pub const SCORE: LocalizedText = LocalizedText::new("Score: {0}", "分数: {0}");
-pub const TIME: LocalizedText = LocalizedText::new("Time: {0}", "时间: {0}");
-pub const COST: LocalizedText = LocalizedText::new("Cost: {0}", "成本: {0}");
-pub const PASSENGERS: LocalizedText = LocalizedText::new("Passengers: {0}", "乘客: {0}");
-pub const PAUSE: LocalizedText = LocalizedText::new("Pause", "暂停");
-pub const ROUTE_SEGMENTS: LocalizedText = LocalizedText::new("Route Segments", "路线段");
-pub const OBJECTIVES: LocalizedText = LocalizedText::new("Objectives", "目标");
-pub const PASSENGER_STATUS: LocalizedText = LocalizedText::new("Passenger Status", "乘客状态");
+pub const SCORE: LocalizedText = LocalizedText::new("SCORE", "Score: {0}", "分数: {0}");
+pub const TIME: LocalizedText = LocalizedText::new("TIME", "Time: {0}", "时间: {0}");
+pub const COST: LocalizedText = LocalizedText::new("COST", "Cost: {0}", "成本: {0}");
+pub const PASSENGERS: LocalizedText =
+    LocalizedText::new("PASSENGERS", "Passengers: {0}", "乘客: {0}");
+pub const PAUSE: LocalizedText = LocalizedText::new("PAUSE", "Pause", "暂停");
+pub const ROUTE_SEGMENTS: LocalizedText =
+    LocalizedText::new("ROUTE_SEGMENTS", "Route Segments", "路线段");
+pub const OBJECTIVES: LocalizedText = LocalizedText::new("OBJECTIVES", "Objectives", "目标");
+pub const PASSENGER_STATUS: LocalizedText =
+    LocalizedText::new("PASSENGER_STATUS", "Passenger Status", "乘客状态");
 
 // 暂停菜单
-pub const GAME_PAUSED: LocalizedText = LocalizedText::new("Game Paused", "游戏暂停");
-pub const RESUME_GAME: LocalizedText = LocalizedText::new("Resume Game", "继续游戏");
-pub const RESTART_LEVEL: LocalizedText = LocalizedText::new("Restart Level", "重新开始");
-pub const MAIN_MENU: LocalizedText = LocalizedText::new("Main Menu", "主菜单");
+pub const GAME_PAUSED: LocalizedText = LocalizedText::new("GAME_PAUSED", "Game Paused", "游戏暂停");
+pub const RESUME_GAME: LocalizedText = LocalizedText::new("RESUME_GAME", "Resume Game", "继续游戏");
+pub const RESTART_LEVEL: LocalizedText =
+    LocalizedText::new("RESTART_LEVEL", "Restart Level", "重新开始");
+pub const MAIN_MENU: LocalizedText = LocalizedText::new("MAIN_MENU", "Main Menu", "主菜单");
+
+// 设置菜单
+pub const SETTINGS: LocalizedText = LocalizedText::new("SETTINGS", "Settings", "设置");
+pub const AUDIO_SETTING: LocalizedText = LocalizedText::new("AUDIO_SETTING", "Audio", "音频");
+pub const CONTROLS_SETTING: LocalizedText =
+    LocalizedText::new("CONTROLS_SETTING", "Controls", "操作");
+pub const GAMEPLAY_SETTING: LocalizedText =
+    LocalizedText::new("GAMEPLAY_SETTING", "Gameplay", "游戏");
+pub const VIDEO_SETTING: LocalizedText = LocalizedText::new("VIDEO_SETTING", "Video", "画面");
+pub const WINDOW_MODE: LocalizedText =
+    LocalizedText::new("WINDOW_MODE", "Display: {0}", "显示模式: {0}");
+pub const WINDOW_MODE_WINDOWED: LocalizedText =
+    LocalizedText::new("WINDOW_MODE_WINDOWED", "Windowed", "窗口");
+pub const WINDOW_MODE_BORDERLESS: LocalizedText =
+    LocalizedText::new("WINDOW_MODE_BORDERLESS", "Borderless", "无边框全屏");
+pub const WINDOW_MODE_FULLSCREEN: LocalizedText =
+    LocalizedText::new("WINDOW_MODE_FULLSCREEN", "Fullscreen", "全屏");
+pub const VSYNC: LocalizedText = LocalizedText::new("VSYNC", "VSync", "垂直同步");
+pub const UI_SCALE: LocalizedText =
+    LocalizedText::new("UI_SCALE", "UI scale: {0}%", "界面缩放: {0}%");
+pub const PAUSE_ON_FOCUS_LOSS: LocalizedText = LocalizedText::new(
+    "PAUSE_ON_FOCUS_LOSS",
+    "Pause when unfocused",
+    "失去焦点时暂停",
+);
+pub const FRAME_LIMIT: LocalizedText =
+    LocalizedText::new("FRAME_LIMIT", "Frame limit: {0}", "帧率上限: {0}");
+pub const FRAME_LIMIT_UNCAPPED: LocalizedText =
+    LocalizedText::new("FRAME_LIMIT_UNCAPPED", "Uncapped", "不限");
+pub const THEME_SETTING: LocalizedText =
+    LocalizedText::new("THEME_SETTING", "Texture theme: {0}", "贴图主题: {0}");
+pub const THEME_DEFAULT: LocalizedText = LocalizedText::new("THEME_DEFAULT", "Classic", "经典");
+pub const SHARE_SEED: LocalizedText = LocalizedText::new("SHARE_SEED", "Seed: {0}", "种子: {0}");
+pub const COPY_SEED: LocalizedText = LocalizedText::new("COPY_SEED", "Copy", "复制");
+pub const SEED_COPIED: LocalizedText = LocalizedText::new("SEED_COPIED", "✓ Copied", "✓ 已复制");
+pub const SEED_COPY_FAILED: LocalizedText =
+    LocalizedText::new("SEED_COPY_FAILED", "Copy failed", "复制失败");
+pub const PLAY_SEED: LocalizedText = LocalizedText::new("PLAY_SEED", "Play seed...", "输入种子...");
+pub const SEED_INPUT_HINT: LocalizedText = LocalizedText::new(
+    "SEED_INPUT_HINT",
+    "Enter a seed shared by a friend (e.g. 3-1A2B3C)\nto play the same random events",
+    "输入好友分享的种子（如 3-1A2B3C）\n挑战相同的随机事件",
+);
+pub const SEED_INVALID: LocalizedText =
+    LocalizedText::new("SEED_INVALID", "Invalid seed", "种子无效");
+pub const START_SEED: LocalizedText = LocalizedText::new("START_SEED", "Play", "开始");
+pub const WATCH_REPLAY: LocalizedText =
+    LocalizedText::new("WATCH_REPLAY", "Watch replay", "观看回放");
+pub const REPLAY_STATUS: LocalizedText =
+    LocalizedText::new("REPLAY_STATUS", "Replay {0}  {1}x", "回放 {0}  {1}x");
+pub const REPLAY_PAUSED: LocalizedText =
+    LocalizedText::new("REPLAY_PAUSED", "Replay {0}  paused", "回放 {0}  已暂停");
+pub const REPLAY_FINISHED: LocalizedText =
+    LocalizedText::new("REPLAY_FINISHED", "Replay finished", "回放结束");
+pub const REPLAY_SLOWER: LocalizedText =
+    LocalizedText::new("REPLAY_SLOWER", "Slower (-)", "减速 (-)");
+pub const REPLAY_FASTER: LocalizedText =
+    LocalizedText::new("REPLAY_FASTER", "Faster (+)", "加速 (+)");
+pub const REPLAY_PAUSE: LocalizedText = LocalizedText::new("REPLAY_PAUSE", "Pause (P)", "暂停 (P)");
+pub const REPLAY_EXIT: LocalizedText =
+    LocalizedText::new("REPLAY_EXIT", "Exit (Esc)", "退出 (Esc)");
+pub const DYSLEXIA_FONT: LocalizedText = LocalizedText::new(
+    "DYSLEXIA_FONT",
+    "Dyslexia-friendly font",
+    "易读字体（仅英文）",
+);
+pub const VISUAL_AUDIO_CUES: LocalizedText = LocalizedText::new(
+    "VISUAL_AUDIO_CUES",
+    "Show visual sound cues",
+    "显示音效视觉提示",
+);
+pub const REDUCED_MOTION: LocalizedText =
+    LocalizedText::new("REDUCED_MOTION", "Reduced motion", "减少动态效果");
+pub const BATTERY_SAVER: LocalizedText =
+    LocalizedText::new("BATTERY_SAVER", "Battery saver in menus", "菜单省电模式");
+pub const TEXT_TO_SPEECH: LocalizedText =
+    LocalizedText::new("TEXT_TO_SPEECH", "Read menus aloud", "朗读菜单和提示");
+pub const MASTER_VOLUME: LocalizedText =
+    LocalizedText::new("MASTER_VOLUME", "Master: {0}%", "总音量: {0}%");
+pub const MUSIC_VOLUME: LocalizedText =
+    LocalizedText::new("MUSIC_VOLUME", "Music: {0}%", "音乐: {0}%");
+pub const UI_VOLUME: LocalizedText =
+    LocalizedText::new("UI_VOLUME", "Interface: {0}%", "界面音效: {0}%");
+pub const WORLD_VOLUME: LocalizedText =
+    LocalizedText::new("WORLD_VOLUME", "World: {0}%", "场景音效: {0}%");
+pub const UI_SOUNDS_SETTING: LocalizedText =
+    LocalizedText::new("UI_SOUNDS_SETTING", "UI Sounds", "界面提示音");
+pub const HOVER_VOLUME: LocalizedText =
+    LocalizedText::new("HOVER_VOLUME", "Hover: {0}%", "悬停音: {0}%");
+pub const DISABLED_VOLUME: LocalizedText =
+    LocalizedText::new("DISABLED_VOLUME", "Unavailable: {0}%", "不可用提示: {0}%");
+pub const SOUND_ON: LocalizedText = LocalizedText::new("SOUND_ON", "Sound: On", "声音: 开");
+pub const SOUND_OFF: LocalizedText = LocalizedText::new("SOUND_OFF", "Sound: Off", "声音: 关");
+pub const BACK: LocalizedText = LocalizedText::new("BACK", "Back", "返回");
+pub const CONTROLS_HELP: LocalizedText = LocalizedText::new("CONTROLS_HELP", 
+    "Left click: place / upgrade straight to Express  |  {0} / Right click: rotate  |  {1} / Delete: remove\n{2}: move camera  |  Wheel: zoom  |  {3}: pause  |  {4}: language  |  F1: tips\nTab: focus button  |  Enter: press  |  I: read status",
+    "左键: 放置 / 升级直线为快速路线  |  {0} / 右键: 旋转  |  {1} / Delete: 移除\n{2}: 移动镜头  |  滚轮: 缩放  |  {3}: 暂停  |  {4}: 语言  |  F1: 提示\nTab: 切换按钮  |  Enter: 按下  |  I: 朗读状态",
+);
+pub const KEY_BINDINGS: LocalizedText =
+    LocalizedText::new("KEY_BINDINGS", "Key bindings", "按键绑定");
+pub const KEY_BINDINGS_HINT: LocalizedText = LocalizedText::new(
+    "KEY_BINDINGS_HINT",
+    "Click an action, then press the new key (Esc to cancel)",
+    "点击动作后按下新按键（Esc 取消）",
+);
+pub const KEY_PRESS_NEW: LocalizedText =
+    LocalizedText::new("KEY_PRESS_NEW", "Press a key...", "请按键...");
+pub const RESET_KEY_BINDINGS: LocalizedText =
+    LocalizedText::new("RESET_KEY_BINDINGS", "Reset defaults", "恢复默认");
+pub const KEY_ROTATE: LocalizedText = LocalizedText::new("KEY_ROTATE", "Rotate", "旋转");
+pub const KEY_REMOVE: LocalizedText = LocalizedText::new("KEY_REMOVE", "Remove", "移除");
+pub const KEY_TOGGLE_LANGUAGE: LocalizedText =
+    LocalizedText::new("KEY_TOGGLE_LANGUAGE", "Switch language", "切换语言");
+pub const KEY_CAMERA_UP: LocalizedText =
+    LocalizedText::new("KEY_CAMERA_UP", "Camera up", "镜头上移");
+pub const KEY_CAMERA_DOWN: LocalizedText =
+    LocalizedText::new("KEY_CAMERA_DOWN", "Camera down", "镜头下移");
+pub const KEY_CAMERA_LEFT: LocalizedText =
+    LocalizedText::new("KEY_CAMERA_LEFT", "Camera left", "镜头左移");
+pub const KEY_CAMERA_RIGHT: LocalizedText =
+    LocalizedText::new("KEY_CAMERA_RIGHT", "Camera right", "镜头右移");
+pub const KEY_PAUSE: LocalizedText = LocalizedText::new("KEY_PAUSE", "Pause", "暂停");
+pub const KEY_WAIT_HEATMAP: LocalizedText =
+    LocalizedText::new("KEY_WAIT_HEATMAP", "Wait heatmap", "等待热力图");
+pub const KEY_SIM_SLOWER: LocalizedText = LocalizedText::new("KEY_SIM_SLOWER", "Slower", "减速");
+pub const KEY_SIM_FASTER: LocalizedText = LocalizedText::new("KEY_SIM_FASTER", "Faster", "加速");
+pub const KEY_SIM_STEP: LocalizedText = LocalizedText::new("KEY_SIM_STEP", "Step", "单步");
+pub const KEY_QUICKSAVE: LocalizedText =
+    LocalizedText::new("KEY_QUICKSAVE", "Quicksave", "快速存档");
+pub const KEY_QUICKLOAD: LocalizedText =
+    LocalizedText::new("KEY_QUICKLOAD", "Quickload", "快速读档");
 
 // 关卡完成
-pub const LEVEL_COMPLETE: LocalizedText = LocalizedText::new("Level Complete!", "关卡完成！");
+pub const LEVEL_COMPLETE: LocalizedText =
+    LocalizedText::new("LEVEL_COMPLETE", "Level Complete!", "关卡完成！");
 pub const CONGRATULATIONS: LocalizedText =
-    LocalizedText::new("🎉 Congratulations!", "🎉 恭喜通关！");
-pub const FINAL_SCORE: LocalizedText = LocalizedText::new("Final Score: {0}", "最终得分: {0}");
+    LocalizedText::new("CONGRATULATIONS", "🎉 Congratulations!", "🎉 恭喜通关！");
+pub const FINAL_SCORE: LocalizedText =
+    LocalizedText::new("FINAL_SCORE", "Final Score: {0}", "最终得分: {0}");
 pub const SCORE_BREAKDOWN: LocalizedText = LocalizedText::new(
-    "Score Details: Base:{0} Efficiency:+{1} Speed:+{2} Cost:+{3}",
-    "分数明细: 基础:{0} 效率:+{1} 速度:+{2} 成本:+{3}",
+    "SCORE_BREAKDOWN",
+    "Score Details: Base:{0} Efficiency:+{1} Speed:+{2} Cost:+{3} Combo:+{4} Contracts:+{5} ×{6}",
+    "分数明细: 基础:{0} 效率:+{1} 速度:+{2} 成本:+{3} 连击:+{4} 合约:+{5} ×{6}",
+);
+pub const COMPLETION_TIME: LocalizedText =
+    LocalizedText::new("COMPLETION_TIME", "Time: {0}", "用时: {0}");
+pub const TOTAL_COST: LocalizedText =
+    LocalizedText::new("TOTAL_COST", "Total Cost: {0}", "总成本: {0}");
+pub const NEXT_LEVEL: LocalizedText = LocalizedText::new("NEXT_LEVEL", "Next Level", "下一关");
+pub const RETRY: LocalizedText = LocalizedText::new("RETRY", "Retry", "重新挑战");
+pub const QUICKSAVED: LocalizedText = LocalizedText::new(
+    "QUICKSAVED",
+    "💾 Quicksaved at {0} (F9 to load)",
+    "💾 已快速存档：{0}（F9 读取）",
 );
-pub const COMPLETION_TIME: LocalizedText = LocalizedText::new("Time: {0}", "用时: {0}");
-pub const TOTAL_COST: LocalizedText = LocalizedText::new("Total Cost: {0}", "总成本: {0}");
-pub const NEXT_LEVEL: LocalizedText = LocalizedText::new("Next Level", "下一关");
-pub const RETRY: LocalizedText = LocalizedText::new("Retry", "重新挑战");
-pub const ALL_LEVELS_COMPLETE: LocalizedText =
-    LocalizedText::new("You've completed all levels!", "您已完成所有关卡！");
-pub const THANK_YOU: LocalizedText = LocalizedText::new("Thank you for playing!", "感谢游玩！");
+pub const QUICKLOADED: LocalizedText = LocalizedText::new(
+    "QUICKLOADED",
+    "📂 Quicksave loaded: {0}",
+    "📂 已读取快速存档：{0}",
+);
+pub const NO_QUICKSAVE: LocalizedText = LocalizedText::new(
+    "NO_QUICKSAVE",
+    "No quicksave for this level yet (F5 to save)",
+    "本关还没有快速存档（F5 保存）",
+);
+pub const RETRY_KEEP_NETWORK: LocalizedText = LocalizedText::new(
+    "RETRY_KEEP_NETWORK",
+    "Retry keeping network",
+    "保留路网重试",
+);
+pub const NETWORK_RESTORED: LocalizedText = LocalizedText::new(
+    "NETWORK_RESTORED",
+    "🔁 Rebuilt your network: {0} segments",
+    "🔁 已重建路网：{0} 个路线段",
+);
+pub const NETWORK_RESTORE_SKIPPED: LocalizedText = LocalizedText::new(
+    "NETWORK_RESTORE_SKIPPED",
+    "🔁 Rebuilt {0} segments; {1} skipped (not enough inventory or tile blocked)",
+    "🔁 已重建 {0} 个路线段，{1} 个因库存不足或位置被占用而跳过",
+);
+pub const ALL_LEVELS_COMPLETE: LocalizedText = LocalizedText::new(
+    "ALL_LEVELS_COMPLETE",
+    "You've completed all levels!",
+    "您已完成所有关卡！",
+);
+pub const THANK_YOU: LocalizedText =
+    LocalizedText::new("THANK_YOU", "Thank you for playing!", "感谢游玩！");
 
 // 游戏失败
-pub const MISSION_FAILED: LocalizedText = LocalizedText::new("❌ Mission Failed", "❌ 任务失败");
+pub const MISSION_FAILED: LocalizedText =
+    LocalizedText::new("MISSION_FAILED", "❌ Mission Failed", "❌ 任务失败");
 pub const FAILURE_REASON: LocalizedText =
-    LocalizedText::new("Failure Reason: {0}", "失败原因: {0}");
-pub const GAME_STATISTICS: LocalizedText = LocalizedText::new("Game Statistics:", "本次游戏统计:");
-pub const SCORE_EARNED: LocalizedText = LocalizedText::new("Score Earned: {0}", "获得分数: {0}");
-pub const GAME_DURATION: LocalizedText = LocalizedText::new("Game Duration: {0}", "游戏时长: {0}");
-pub const PASSENGERS_GAVE_UP: LocalizedText =
-    LocalizedText::new("Passengers Gave Up: {0}", "放弃的乘客: {0}");
-pub const DONT_GIVE_UP: LocalizedText =
-    LocalizedText::new("Don't give up, try again!", "不要灰心，再试一次！");
+    LocalizedText::new("FAILURE_REASON", "Failure Reason: {0}", "失败原因: {0}");
+pub const GAME_STATISTICS: LocalizedText =
+    LocalizedText::new("GAME_STATISTICS", "Game Statistics:", "本次游戏统计:");
+pub const SCORE_EARNED: LocalizedText =
+    LocalizedText::new("SCORE_EARNED", "Score Earned: {0}", "获得分数: {0}");
+pub const GAME_DURATION: LocalizedText =
+    LocalizedText::new("GAME_DURATION", "Game Duration: {0}", "游戏时长: {0}");
+pub const PASSENGERS_GAVE_UP: LocalizedText = LocalizedText::new(
+    "PASSENGERS_GAVE_UP",
+    "Passengers Gave Up: {0}",
+    "放弃的乘客: {0}",
+);
+pub const DONT_GIVE_UP: LocalizedText = LocalizedText::new(
+    "DONT_GIVE_UP",
+    "Don't give up, try again!",
+    "不要灰心，再试一次！",
+);
 
 // 乘客状态
-pub const WAITING: LocalizedText = LocalizedText::new("Waiting: {0}", "等待: {0}");
-pub const ARRIVED: LocalizedText = LocalizedText::new("Arrived: {0}", "到达: {0}");
-pub const GAVE_UP: LocalizedText = LocalizedText::new("Gave Up: {0}", "放弃: {0}");
+pub const WAITING: LocalizedText = LocalizedText::new("WAITING", "Waiting: {0}", "等待: {0}");
+pub const ARRIVED: LocalizedText = LocalizedText::new("ARRIVED", "Arrived: {0}", "到达: {0}");
+pub const GAVE_UP: LocalizedText = LocalizedText::new("GAVE_UP", "Gave Up: {0}", "放弃: {0}");
 
 // 关卡信息
-pub const LEVEL_TUTORIAL: LocalizedText = LocalizedText::new("First Connection", "第一次连接");
-pub const LEVEL_TRANSFER: LocalizedText = LocalizedText::new("Learn Transfers", "学会换乘");
-pub const LEVEL_MULTIPLE: LocalizedText = LocalizedText::new("Multiple Routes", "多条路线");
-pub const LEVEL_TIME_PRESSURE: LocalizedText = LocalizedText::new("Time Challenge", "时间挑战");
+pub const LEVEL_TUTORIAL: LocalizedText =
+    LocalizedText::new("LEVEL_TUTORIAL", "First Connection", "第一次连接");
+pub const LEVEL_TRANSFER: LocalizedText =
+    LocalizedText::new("LEVEL_TRANSFER", "Learn Transfers", "学会换乘");
+pub const LEVEL_MULTIPLE: LocalizedText =
+    LocalizedText::new("LEVEL_MULTIPLE", "Multiple Routes", "多条路线");
+pub const LEVEL_TIME_PRESSURE: LocalizedText =
+    LocalizedText::new("LEVEL_TIME_PRESSURE", "Time Challenge", "时间挑战");
+pub const BONUS_RUSH_HOUR: LocalizedText =
+    LocalizedText::new("BONUS_RUSH_HOUR", "Bonus: Rush Hour", "奖励关：早高峰");
+pub const BONUS_RUSH_HOUR_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "BONUS_RUSH_HOUR_DESCRIPTION",
+    "More passengers, less patience — keep every route flowing",
+    "乘客更多、耐心更短，让每条路线都保持畅通",
+);
+pub const BONUS_LEAN_NETWORK: LocalizedText = LocalizedText::new(
+    "BONUS_LEAN_NETWORK",
+    "Bonus: Lean Network",
+    "奖励关：精简线网",
+);
+pub const BONUS_LEAN_NETWORK_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "BONUS_LEAN_NETWORK_DESCRIPTION",
+    "Half the route pieces and a tighter budget — every tile counts",
+    "路线段减半、预算更紧，每一格都要精打细算",
+);
+
+// 关卡选择
+pub const LEVEL_SELECT: LocalizedText =
+    LocalizedText::new("LEVEL_SELECT", "Select Level", "选择关卡");
+pub const LEVEL_SELECT_CAMPAIGN: LocalizedText =
+    LocalizedText::new("LEVEL_SELECT_CAMPAIGN", "Campaign", "主线关卡");
+pub const LEVEL_SELECT_CUSTOM: LocalizedText =
+    LocalizedText::new("LEVEL_SELECT_CUSTOM", "Custom Levels", "自定义关卡");
+pub const CUSTOM_LEVELS_HINT: LocalizedText = LocalizedText::new(
+    "CUSTOM_LEVELS_HINT",
+    "Drop .lastlevel files into the custom_levels/ folder, or export a level from the pause menu",
+    "把 .lastlevel 文件放入 custom_levels/ 文件夹，或在暂停菜单中导出关卡",
+);
+pub const NO_CUSTOM_LEVELS: LocalizedText = LocalizedText::new(
+    "NO_CUSTOM_LEVELS",
+    "No custom levels found",
+    "没有找到自定义关卡",
+);
+pub const CUSTOM_LEVEL_INFO: LocalizedText = LocalizedText::new(
+    "CUSTOM_LEVEL_INFO",
+    "Difficulty {0} · {1} stations",
+    "难度 {0} · {1} 个站点",
+);
+pub const EXPORT_LEVEL: LocalizedText =
+    LocalizedText::new("EXPORT_LEVEL", "Export Level", "导出关卡");
+pub const LEVEL_EXPORTED: LocalizedText = LocalizedText::new(
+    "LEVEL_EXPORTED",
+    "✓ Saved to custom_levels/",
+    "✓ 已保存到 custom_levels/",
+);
+pub const LEVEL_EXPORT_FAILED: LocalizedText =
+    LocalizedText::new("LEVEL_EXPORT_FAILED", "Export failed", "导出失败");
+pub const LEVEL_TOTAL_STARS: LocalizedText =
+    LocalizedText::new("LEVEL_TOTAL_STARS", "Total stars: {0} ★", "星星总数: {0} ★");
+pub const LEVEL_BEST_RECORD: LocalizedText =
+    LocalizedText::new("LEVEL_BEST_RECORD", "Best: {0} · {1}s", "最佳: {0} · {1}秒");
+pub const LEVEL_NOT_CLEARED: LocalizedText =
+    LocalizedText::new("LEVEL_NOT_CLEARED", "Not cleared yet", "尚未通关");
+pub const LEVEL_LOCKED: LocalizedText = LocalizedText::new(
+    "LEVEL_LOCKED",
+    "🔒 Clear the previous level",
+    "🔒 通关上一关后解锁",
+);
+pub const LEVEL_REQUIRES_STARS: LocalizedText = LocalizedText::new(
+    "LEVEL_REQUIRES_STARS",
+    "🔒 Requires {0} ★ ({1}/{0})",
+    "🔒 需要 {0} ★（{1}/{0}）",
+);
+
+// 每周挑战
+pub const WEEKLY_CHALLENGES: LocalizedText =
+    LocalizedText::new("WEEKLY_CHALLENGES", "Weekly Challenges", "每周挑战");
+pub const CHALLENGE_WEEK: LocalizedText =
+    LocalizedText::new("CHALLENGE_WEEK", "{0} Week {1}", "{0}年第{1}周");
+pub const CHALLENGE_BEST_SCORE: LocalizedText = LocalizedText::new(
+    "CHALLENGE_BEST_SCORE",
+    "Best this week: {0}",
+    "本周最佳: {0}",
+);
+pub const CHALLENGE_UNPLAYED: LocalizedText =
+    LocalizedText::new("CHALLENGE_UNPLAYED", "Not played this week", "本周未挑战");
+pub const CHALLENGE_PLAY: LocalizedText = LocalizedText::new("CHALLENGE_PLAY", "Play", "挑战");
+pub const NEW_BADGE: LocalizedText = LocalizedText::new("NEW_BADGE", "NEW", "新");
+pub const CHALLENGE_RIVER_CROSSING: LocalizedText =
+    LocalizedText::new("CHALLENGE_RIVER_CROSSING", "River Crossing", "跨河通勤");
+pub const CHALLENGE_DOWNTOWN_MAZE: LocalizedText =
+    LocalizedText::new("CHALLENGE_DOWNTOWN_MAZE", "Downtown Maze", "市中心迷宫");
+pub const CHALLENGE_RUSH_HOUR: LocalizedText =
+    LocalizedText::new("CHALLENGE_RUSH_HOUR", "Rush Hour", "早高峰");
+pub const CHALLENGE_SHOESTRING: LocalizedText =
+    LocalizedText::new("CHALLENGE_SHOESTRING", "Shoestring Budget", "紧张预算");
+pub const CHALLENGE_TWIN_HUBS: LocalizedText =
+    LocalizedText::new("CHALLENGE_TWIN_HUBS", "Twin Hubs", "双枢纽");
+pub const CHALLENGE_MOUNTAIN_PASS: LocalizedText =
+    LocalizedText::new("CHALLENGE_MOUNTAIN_PASS", "Mountain Pass", "山口通道");
+pub const CHALLENGE_RIVER_CROSSING_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "CHALLENGE_RIVER_CROSSING_DESCRIPTION",
+    "Bridge the river on a tight budget",
+    "在有限预算内架桥跨越河流",
+);
+pub const CHALLENGE_DOWNTOWN_MAZE_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "CHALLENGE_DOWNTOWN_MAZE_DESCRIPTION",
+    "Weave through dense blocks with few segments",
+    "用尽量少的路线段穿过密集街区",
+);
+pub const CHALLENGE_RUSH_HOUR_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "CHALLENGE_RUSH_HOUR_DESCRIPTION",
+    "Impatient crowds arrive fast, connect them in time",
+    "乘客来得快又没耐心，尽快把他们送到",
+);
+pub const CHALLENGE_SHOESTRING_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "CHALLENGE_SHOESTRING_DESCRIPTION",
+    "Only a handful of segments, every piece counts",
+    "路线段极少，每一块都很关键",
+);
+pub const CHALLENGE_TWIN_HUBS_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "CHALLENGE_TWIN_HUBS_DESCRIPTION",
+    "Route four lines through two transfer hubs",
+    "通过两个换乘枢纽连接四条线路",
+);
+pub const CHALLENGE_MOUNTAIN_PASS_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "CHALLENGE_MOUNTAIN_PASS_DESCRIPTION",
+    "Tunnel through the range to reach the valley",
+    "打通山脉隧道，抵达山谷",
+);
 
 // 站点名称
-pub const STATION_A: LocalizedText = LocalizedText::new("Station A", "A站");
-pub const STATION_B: LocalizedText = LocalizedText::new("Station B", "B站");
-pub const STATION_C: LocalizedText = LocalizedText::new("Station C", "C站");
-pub const TRANSFER_HUB: LocalizedText = LocalizedText::new("Transfer Hub", "中转站");
-pub const NORTH_STATION: LocalizedText = LocalizedText::new("North Station", "北站");
-pub const SOUTH_STATION: LocalizedText = LocalizedText::new("South Station", "南站");
-pub const NORTHEAST_STATION: LocalizedText = LocalizedText::new("Northeast Station", "东北站");
-pub const SOUTHEAST_STATION: LocalizedText = LocalizedText::new("Southeast Station", "东南站");
-pub const CENTRAL_HUB: LocalizedText = LocalizedText::new("Central Hub", "中央枢纽");
-pub const START_STATION: LocalizedText = LocalizedText::new("Start Station", "起点站");
-pub const TARGET_STATION_A: LocalizedText = LocalizedText::new("Target Station A", "目标站A");
-pub const TARGET_STATION_B: LocalizedText = LocalizedText::new("Target Station B", "目标站B");
-pub const TARGET_STATION_C: LocalizedText = LocalizedText::new("Target Station C", "目标站C");
+pub const STATION_A: LocalizedText = LocalizedText::new("STATION_A", "Station A", "A站");
+pub const STATION_B: LocalizedText = LocalizedText::new("STATION_B", "Station B", "B站");
+pub const STATION_C: LocalizedText = LocalizedText::new("STATION_C", "Station C", "C站");
+pub const TRANSFER_HUB: LocalizedText =
+    LocalizedText::new("TRANSFER_HUB", "Transfer Hub", "中转站");
+pub const NORTH_STATION: LocalizedText =
+    LocalizedText::new("NORTH_STATION", "North Station", "北站");
+pub const SOUTH_STATION: LocalizedText =
+    LocalizedText::new("SOUTH_STATION", "South Station", "南站");
+pub const NORTHEAST_STATION: LocalizedText =
+    LocalizedText::new("NORTHEAST_STATION", "Northeast Station", "东北站");
+pub const SOUTHEAST_STATION: LocalizedText =
+    LocalizedText::new("SOUTHEAST_STATION", "Southeast Station", "东南站");
+pub const CENTRAL_HUB: LocalizedText = LocalizedText::new("CENTRAL_HUB", "Central Hub", "中央枢纽");
+pub const DEPOT_STATION: LocalizedText =
+    LocalizedText::new("DEPOT_STATION", "Bus Depot", "公交车场");
+pub const START_STATION: LocalizedText =
+    LocalizedText::new("START_STATION", "Start Station", "起点站");
+pub const TARGET_STATION_A: LocalizedText =
+    LocalizedText::new("TARGET_STATION_A", "Target Station A", "目标站A");
+pub const TARGET_STATION_B: LocalizedText =
+    LocalizedText::new("TARGET_STATION_B", "Target Station B", "目标站B");
+pub const TARGET_STATION_C: LocalizedText =
+    LocalizedText::new("TARGET_STATION_C", "Target Station C", "目标站C");
 
 // 目标描述
 pub const OBJECTIVE_CONNECT_ALL: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_CONNECT_ALL",
     "Connect all passengers to destinations",
     "连接所有乘客到目的地",
 );
-pub const OBJECTIVE_MAX_TRANSFERS: LocalizedText =
-    LocalizedText::new("Maximum {0} transfers", "最多使用{0}次换乘");
-pub const OBJECTIVE_MAX_SEGMENTS: LocalizedText =
-    LocalizedText::new("Use at most {0} route segments", "最多使用{0}个路线段");
+pub const OBJECTIVE_MAX_TRANSFERS: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_MAX_TRANSFERS",
+    "Maximum {0} transfers",
+    "最多使用{0}次换乘",
+);
+pub const OBJECTIVE_MAX_SEGMENTS: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_MAX_SEGMENTS",
+    "Use at most {0} route segments",
+    "最多使用{0}个路线段",
+);
 pub const OBJECTIVE_MAX_COST: LocalizedText =
-    LocalizedText::new("Total cost ≤ {0}", "总成本不超过{0}");
-pub const OBJECTIVE_TIME_LIMIT: LocalizedText =
-    LocalizedText::new("Complete within {0} seconds", "在{0}秒内完成");
-pub const OBJECTIVE_PASSENGER_SATISFACTION: LocalizedText =
-    LocalizedText::new("Passenger satisfaction ≥ {0}%", "乘客满意度达到{0}%");
+    LocalizedText::new("OBJECTIVE_MAX_COST", "Total cost ≤ {0}", "总成本不超过{0}");
+pub const OBJECTIVE_TIME_LIMIT: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_TIME_LIMIT",
+    "Complete within {0} seconds",
+    "在{0}秒内完成",
+);
+pub const OBJECTIVE_PASSENGER_SATISFACTION: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_PASSENGER_SATISFACTION",
+    "Passenger satisfaction ≥ {0}%",
+    "乘客满意度达到{0}%",
+);
+pub const OBJECTIVE_DELIVER_COLOR: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_DELIVER_COLOR",
+    "Deliver {0} {1} passengers",
+    "运送{0}名{1}乘客",
+);
+pub const OBJECTIVE_MAX_AVERAGE_WAIT: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_MAX_AVERAGE_WAIT",
+    "⏱ Average wait ≤ {0}s",
+    "⏱ 平均等车时间不超过{0}秒",
+);
+
+// 乘客颜色
+pub const COLOR_RED: LocalizedText = LocalizedText::new("COLOR_RED", "red", "红色");
+pub const COLOR_BLUE: LocalizedText = LocalizedText::new("COLOR_BLUE", "blue", "蓝色");
+pub const COLOR_GREEN: LocalizedText = LocalizedText::new("COLOR_GREEN", "green", "绿色");
+pub const COLOR_YELLOW: LocalizedText = LocalizedText::new("COLOR_YELLOW", "yellow", "黄色");
+pub const COLOR_PURPLE: LocalizedText = LocalizedText::new("COLOR_PURPLE", "purple", "紫色");
+pub const COLOR_ORANGE: LocalizedText = LocalizedText::new("COLOR_ORANGE", "orange", "橙色");
 
 // 关卡描述
 pub const TUTORIAL_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "TUTORIAL_DESCRIPTION",
     "Learn basic route connection by transporting red passengers from Station A to Station B",
     "学习基本的路线连接操作，将红色乘客从A站送到B站",
 );
 pub const TRANSFER_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "TRANSFER_DESCRIPTION",
     "Learn to use the transfer system by connecting different routes through transfer stations",
     "学习使用换乘系统，通过中转站连接不同的路线",
 );
 pub const MULTIPLE_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "MULTIPLE_DESCRIPTION",
     "Manage multiple independent routes and optimize the entire transportation network",
     "管理多条独立路线，优化整个交通网络",
 );
 pub const TIME_PRESSURE_DESCRIPTION: LocalizedText = LocalizedText::new(
+    "TIME_PRESSURE_DESCRIPTION",
     "Quickly build an efficient transportation network within limited time",
     "在有限时间内快速建设高效的交通网络",
 );
 
 // 游戏提示和警告信息
 pub const PASSENGERS_GAVE_UP_WARNING: LocalizedText = LocalizedText::new(
+    "PASSENGERS_GAVE_UP_WARNING",
     "⚠️ {0} passengers have given up! Check route connections",
     "⚠️ 已有{0}位乘客放弃！检查路线连接",
 );
 
 pub const PASSENGERS_WAITING_HINT: LocalizedText = LocalizedText::new(
+    "PASSENGERS_WAITING_HINT",
     "💡 Many passengers are waiting, press F4 to discover bus routes",
     "💡 很多乘客在等车，按F4发现公交路线",
 );
 
 pub const BUS_ROUTES_READY_INFO: LocalizedText = LocalizedText::new(
+    "BUS_ROUTES_READY_INFO",
     "🚌 Routes are built, waiting for buses to start operating",
     "🚌 路线已建好，等待公交车开始运营",
 );
 
-pub const BUDGET_WARNING: LocalizedText =
-    LocalizedText::new("💰 Budget Warning: {0}/{1}", "💰 预算警告: {0}/{1}");
+pub const ROTATION_LOCKED_WARNING: LocalizedText = LocalizedText::new(
+    "ROTATION_LOCKED_WARNING",
+    "🔒 Mutator: placed segments cannot be rotated",
+    "🔒 规则变体：已放置的路线段不能旋转",
+);
+pub const REMOVALS_EXHAUSTED_WARNING: LocalizedText = LocalizedText::new(
+    "REMOVALS_EXHAUSTED_WARNING",
+    "🔒 Mutator: no removals left",
+    "🔒 规则变体：拆除次数已用完",
+);
+pub const REMOVALS_LEFT_INFO: LocalizedText = LocalizedText::new(
+    "REMOVALS_LEFT_INFO",
+    "Removals left: {0}",
+    "剩余拆除次数: {0}",
+);
+pub const PARTIAL_REFUND_INFO: LocalizedText = LocalizedText::new(
+    "PARTIAL_REFUND_INFO",
+    "♻ Refunded {0} of {1} (this level's refund rule)",
+    "♻ 退还 {0}/{1}（本关的拆除退款规则）",
+);
+pub const EXPRESS_UPGRADED: LocalizedText = LocalizedText::new(
+    "EXPRESS_UPGRADED",
+    "🚄 Upgraded to Express (cost +{0})",
+    "🚄 已升级为快速路线（成本 +{0}）",
+);
+
+pub const SCREEN_READER_STATUS: LocalizedText = LocalizedText::new(
+    "SCREEN_READER_STATUS",
+    "Score {0}, time {1}, objectives {2} of {3} complete",
+    "得分 {0}，时间 {1}，已完成目标 {2}/{3}",
+);
+pub const OBJECTIVE_COMPLETED_ANNOUNCEMENT: LocalizedText = LocalizedText::new(
+    "OBJECTIVE_COMPLETED_ANNOUNCEMENT",
+    "Objective complete",
+    "目标已完成",
+);
+pub const LEVEL_COMPLETE_ANNOUNCEMENT: LocalizedText = LocalizedText::new(
+    "LEVEL_COMPLETE_ANNOUNCEMENT",
+    "Level complete, final score {0}, time {1}",
+    "关卡完成，最终得分 {0}，用时 {1}",
+);
+
+pub const CUE_PASSENGER_ARRIVED: LocalizedText = LocalizedText::new(
+    "CUE_PASSENGER_ARRIVED",
+    "🔔 Chime: passenger arrived",
+    "🔔 提示音：乘客到达",
+);
+pub const CUE_ERROR: LocalizedText =
+    LocalizedText::new("CUE_ERROR", "❌ Buzz: failure", "❌ 错误音：失败");
+pub const CUE_OBJECTIVE_COMPLETE: LocalizedText = LocalizedText::new(
+    "CUE_OBJECTIVE_COMPLETE",
+    "🎵 Fanfare: objective complete",
+    "🎵 提示音：目标完成",
+);
+
+pub const BUDGET_WARNING: LocalizedText = LocalizedText::new(
+    "BUDGET_WARNING",
+    "💰 Budget Warning: {0}/{1}",
+    "💰 预算警告: {0}/{1}",
+);
+
+pub const FAILURE_WARNING_GAVE_UP: LocalizedText = LocalizedText::new(
+    "FAILURE_WARNING_GAVE_UP",
+    "⚠️ {0} passengers have given up (limit {1})! Pick up waiting passengers soon",
+    "⚠️ 已有 {0} 名乘客放弃（上限 {1}），尽快接走等车的乘客！",
+);
+pub const FAILURE_WARNING_TIME: LocalizedText = LocalizedText::new(
+    "FAILURE_WARNING_TIME",
+    "⏰ Only {0} left before the time limit!",
+    "⏰ 距离时间限制只剩 {0}！",
+);
+
+pub const TIP_HISTORY: LocalizedText =
+    LocalizedText::new("TIP_HISTORY", "📜 Tip History", "📜 提示历史");
+pub const TIP_HISTORY_EMPTY: LocalizedText =
+    LocalizedText::new("TIP_HISTORY_EMPTY", "No tips shown yet", "暂无提示记录");
+
+pub const COVERAGE_HEATMAP_HINT: LocalizedText = LocalizedText::new(
+    "COVERAGE_HEATMAP_HINT",
+    "🗺️ Coverage: green = served, yellow = nearby, red = far from any route (C to hide)",
+    "🗺️ 覆盖范围：绿色=已覆盖，黄色=较近，红色=远离所有路线（按 C 关闭）",
+);
+pub const WAIT_HEATMAP_HINT: LocalizedText = LocalizedText::new(
+    "WAIT_HEATMAP_HINT",
+    "🔥 Waiting: green = flowing, yellow = slow, red = long waits or crowded stops ({0} to hide)",
+    "🔥 等待情况：绿色=通畅，黄色=偏慢，红色=等待过久或站台拥挤（按 {0} 关闭）",
+);
+
+pub const PASSENGER_HOVER_ROUTE: LocalizedText = LocalizedText::new(
+    "PASSENGER_HOVER_ROUTE",
+    "{0} passenger: {1} → {2}",
+    "{0}乘客: {1} → {2}",
+);
+pub const PASSENGER_HOVER_VIA: LocalizedText =
+    LocalizedText::new("PASSENGER_HOVER_VIA", "Transfer at {0}", "在 {0} 换乘");
+pub const PASSENGER_HOVER_PATIENCE: LocalizedText =
+    LocalizedText::new("PASSENGER_HOVER_PATIENCE", "Patience: {0}%", "耐心: {0}%");
+pub const PASSENGER_HOVER_NO_PATH: LocalizedText = LocalizedText::new(
+    "PASSENGER_HOVER_NO_PATH",
+    "No route found yet",
+    "暂无可用路线",
+);
+pub const INSPECT_STATE_WAITING: LocalizedText = LocalizedText::new(
+    "INSPECT_STATE_WAITING",
+    "⏳ Waiting at {0}",
+    "⏳ 在 {0} 等车",
+);
+pub const INSPECT_STATE_RIDING: LocalizedText = LocalizedText::new(
+    "INSPECT_STATE_RIDING",
+    "🚌 On board, heading to {0}",
+    "🚌 乘车中，前往 {0}",
+);
+pub const INSPECT_STATE_TRANSFERRING: LocalizedText = LocalizedText::new(
+    "INSPECT_STATE_TRANSFERRING",
+    "🔁 Transferring at {0}",
+    "🔁 在 {0} 换乘中",
+);
+pub const INSPECT_STATE_ARRIVED: LocalizedText =
+    LocalizedText::new("INSPECT_STATE_ARRIVED", "✅ Arrived", "✅ 已到达");
+pub const INSPECT_STATE_GAVE_UP: LocalizedText =
+    LocalizedText::new("INSPECT_STATE_GAVE_UP", "😠 Gave up", "😠 已放弃");
+pub const INSPECT_PIN_HINT: LocalizedText = LocalizedText::new(
+    "INSPECT_PIN_HINT",
+    "Click to pin this card",
+    "点击固定此信息卡",
+);
+pub const INSPECT_PINNED: LocalizedText = LocalizedText::new(
+    "INSPECT_PINNED",
+    "📌 Pinned, click elsewhere to close",
+    "📌 已固定，点击空白处关闭",
+);
+pub const BUS_HOVER_TITLE: LocalizedText =
+    LocalizedText::new("BUS_HOVER_TITLE", "🚌 Line {0} bus", "🚌 线路 {0} 公交车");
+pub const BUS_HOVER_LOAD: LocalizedText =
+    LocalizedText::new("BUS_HOVER_LOAD", "Passengers: {0}/{1}", "载客: {0}/{1}");
+pub const BUS_HOVER_NEXT_STOP: LocalizedText =
+    LocalizedText::new("BUS_HOVER_NEXT_STOP", "Next stop: {0}", "下一站: {0}");
+pub const BUS_STATE_DRIVING: LocalizedText =
+    LocalizedText::new("BUS_STATE_DRIVING", "Driving", "行驶中");
+pub const BUS_STATE_AT_STOP: LocalizedText =
+    LocalizedText::new("BUS_STATE_AT_STOP", "Stopped at a station", "停站上下客");
+pub const BUS_STATE_TURNING: LocalizedText =
+    LocalizedText::new("BUS_STATE_TURNING", "Turning around", "终点调头");
+pub const BUS_STATE_IDLE: LocalizedText = LocalizedText::new("BUS_STATE_IDLE", "Idle", "空闲");
+
+// ============ 文本注册表 ============
+
+/// 所有内置文本，按键查找时使用（新增文本后需要加入这里）
+pub const ALL_TEXTS: &[&LocalizedText] = &[
+    &GAME_TITLE,
+    &START_GAME,
+    &CONTINUE_GAME,
+    &QUIT_GAME,
+    &LANGUAGE_SETTING,
+    &QUIT_CONFIRM,
+    &CONFIRM_QUIT,
+    &CONFIRM_RESTART_LEVEL,
+    &CONFIRM_MAIN_MENU,
+    &CONFIRM_DELETE_LEVEL,
+    &CONFIRM_DELETE,
+    &CANCEL,
+    &EXPORT_RESULTS,
+    &RESULTS_EXPORTED,
+    &RESULTS_EXPORT_FAILED,
+    &LEVEL_STATISTICS,
+    &STATS_PASSENGERS_OVER_TIME,
+    &STATS_SPAWNED,
+    &STATS_ARRIVED,
+    &STATS_GAVE_UP,
+    &STATS_COST_OVER_TIME,
+    &STATS_SCORE_BREAKDOWN,
+    &SCORE_PART_BASE,
+    &SCORE_PART_EFFICIENCY,
+    &SCORE_PART_SPEED,
+    &SCORE_PART_COST,
+    &SCORE_PART_COMBO,
+    &SCORE_PART_CONTRACTS,
+    &IMPORT_CITY,
+    &IMPORTED_CITY,
+    &IMPORTED_CITY_DESCRIPTION,
+    &IMPORT_FAILED,
+    &TOUCH_ROTATE,
+    &TOUCH_DELETE,
+    &MUTATORS,
+    &MUTATOR_NO_ROTATION,
+    &MUTATOR_LIMITED_REMOVALS,
+    &MUTATOR_NO_CURVES,
+    &MIRROR_MODE,
+    &MIRROR_PROGRESS,
+    &HOTSEAT_MODE,
+    &HOTSEAT_TURN,
+    &HOTSEAT_TURN_CHANGE,
+    &SIDE_CONTRACTS,
+    &FULLSCREEN,
+    &CONTRACTS_PANEL,
+    &CONTRACT_LINE,
+    &CONTRACT_OFFERED,
+    &CONTRACT_COMPLETED,
+    &CONTRACT_FAILED,
+    &SCORE_ATTACK_MODE,
+    &COMBO_COUNTER,
+    &ECONOMY_MODE,
+    &ENDLESS_MODE,
+    &ENDLESS_HUD,
+    &ECONOMY_HUD,
+    &INSUFFICIENT_FUNDS,
+    &STATION_INFO,
+    &STATION_CAPACITY_UPGRADE,
+    &STATION_SHELTER_UPGRADE,
+    &STATION_ACCESSIBILITY_UPGRADE,
+    &STATION_UPGRADED,
+    &BUS_LOOP_CIRCULAR,
+    &BUS_LOOP_SHUTTLE,
+    &EVENT_WARNING_SEGMENT_FAILURE,
+    &EVENT_WARNING_SURGE,
+    &EVENT_WARNING_STATION_OVERLOAD,
+    &EVENT_WARNING_NEW_DEMAND,
+    &EVENT_WARNING_ROAD_CLOSURE,
+    &ROAD_REOPENED,
+    &BLUEPRINT_NEED_SELECTION,
+    &BLUEPRINT_DEFAULT_NAME,
+    &BLUEPRINT_NAME_PROMPT,
+    &BLUEPRINT_SAVE,
+    &BLUEPRINT_SAVED,
+    &BLUEPRINT_LIBRARY,
+    &BLUEPRINT_LIBRARY_EMPTY,
+    &BLUEPRINT_SEGMENTS,
+    &BLUEPRINT_STAMP,
+    &BLUEPRINT_STAMP_HINT,
+    &CONFIRM_DELETE_BLUEPRINT,
+    &REPAIR_HINT,
+    &SELECTION_INFO,
+    &CLIPBOARD_COPIED,
+    &CLIPBOARD_PASTE_HINT,
+    &CLIPBOARD_MOVE_HINT,
+    &CLIPBOARD_BLOCKED,
+    &CLIPBOARD_NO_INVENTORY,
+    &UNDO_DONE,
+    &UNDO_FAILED,
+    &EVENT_WARNING_WEATHER,
+    &WEATHER_STARTED,
+    &WEATHER_CLEARED,
+    &WEATHER_RAIN,
+    &WEATHER_SNOW,
+    &WEATHER_FOG,
+    &TERRAIN_BRUSH,
+    &TERRAIN_EMPTY,
+    &TERRAIN_WATER,
+    &TERRAIN_MOUNTAIN,
+    &TERRAIN_BUILDING,
+    &BRUSH_SIZE,
+    &START_SIMULATION,
+    &BRIEFING_START,
+    &STATION_BURIED,
+    &ROUTE_MANAGEMENT,
+    &ROUTE_NAME,
+    &ROUTE_BUS_COUNT,
+    &ROUTE_FREQUENCY,
+    &FREQUENCY_LOW,
+    &FREQUENCY_NORMAL,
+    &FREQUENCY_HIGH,
+    &ROUTE_OPERATING_COST,
+    &NO_ROUTES,
+    &ACTIVE_LINE,
+    &LINE_ASSIGNED,
+    &ROUTE_LINES_HINT,
+    &BUY_BUS,
+    &BUS_PURCHASED,
+    &BUS_ASSIGNED,
+    &LINE_FLEET_FULL,
+    &RUSH_HOUR,
+    &GAME_SPEED,
+    &GAME_SPEED_STEP,
+    &GAME_SPEED_STEP_HINT,
+    &NETWORK_LAYER_GROUND,
+    &NETWORK_LAYER_ELEVATED,
+    &ELEVATED_LAYER_HINT,
+    &CLOSE,
+    &SCORE_MULTIPLIER,
+    &SCORE,
+    &TIME,
+    &COST,
+    &PASSENGERS,
+    &PAUSE,
+    &ROUTE_SEGMENTS,
+    &OBJECTIVES,
+    &PASSENGER_STATUS,
+    &GAME_PAUSED,
+    &RESUME_GAME,
+    &RESTART_LEVEL,
+    &MAIN_MENU,
+    &SETTINGS,
+    &AUDIO_SETTING,
+    &CONTROLS_SETTING,
+    &GAMEPLAY_SETTING,
+    &VIDEO_SETTING,
+    &WINDOW_MODE,
+    &WINDOW_MODE_WINDOWED,
+    &WINDOW_MODE_BORDERLESS,
+    &WINDOW_MODE_FULLSCREEN,
+    &VSYNC,
+    &UI_SCALE,
+    &PAUSE_ON_FOCUS_LOSS,
+    &FRAME_LIMIT,
+    &FRAME_LIMIT_UNCAPPED,
+    &THEME_SETTING,
+    &THEME_DEFAULT,
+    &SHARE_SEED,
+    &COPY_SEED,
+    &SEED_COPIED,
+    &SEED_COPY_FAILED,
+    &PLAY_SEED,
+    &SEED_INPUT_HINT,
+    &SEED_INVALID,
+    &START_SEED,
+    &WATCH_REPLAY,
+    &REPLAY_STATUS,
+    &REPLAY_PAUSED,
+    &REPLAY_FINISHED,
+    &REPLAY_SLOWER,
+    &REPLAY_FASTER,
+    &REPLAY_PAUSE,
+    &REPLAY_EXIT,
+    &DYSLEXIA_FONT,
+    &VISUAL_AUDIO_CUES,
+    &REDUCED_MOTION,
+    &BATTERY_SAVER,
+    &TEXT_TO_SPEECH,
+    &MASTER_VOLUME,
+    &MUSIC_VOLUME,
+    &UI_VOLUME,
+    &WORLD_VOLUME,
+    &UI_SOUNDS_SETTING,
+    &HOVER_VOLUME,
+    &DISABLED_VOLUME,
+    &SOUND_ON,
+    &SOUND_OFF,
+    &BACK,
+    &CONTROLS_HELP,
+    &KEY_BINDINGS,
+    &KEY_BINDINGS_HINT,
+    &KEY_PRESS_NEW,
+    &RESET_KEY_BINDINGS,
+    &KEY_ROTATE,
+    &KEY_REMOVE,
+    &KEY_TOGGLE_LANGUAGE,
+    &KEY_CAMERA_UP,
+    &KEY_CAMERA_DOWN,
+    &KEY_CAMERA_LEFT,
+    &KEY_CAMERA_RIGHT,
+    &KEY_PAUSE,
+    &KEY_WAIT_HEATMAP,
+    &KEY_SIM_SLOWER,
+    &KEY_SIM_FASTER,
+    &KEY_SIM_STEP,
+    &KEY_QUICKSAVE,
+    &KEY_QUICKLOAD,
+    &LEVEL_COMPLETE,
+    &CONGRATULATIONS,
+    &FINAL_SCORE,
+    &SCORE_BREAKDOWN,
+    &COMPLETION_TIME,
+    &TOTAL_COST,
+    &NEXT_LEVEL,
+    &RETRY,
+    &QUICKSAVED,
+    &QUICKLOADED,
+    &NO_QUICKSAVE,
+    &RETRY_KEEP_NETWORK,
+    &NETWORK_RESTORED,
+    &NETWORK_RESTORE_SKIPPED,
+    &ALL_LEVELS_COMPLETE,
+    &THANK_YOU,
+    &MISSION_FAILED,
+    &FAILURE_REASON,
+    &GAME_STATISTICS,
+    &SCORE_EARNED,
+    &GAME_DURATION,
+    &PASSENGERS_GAVE_UP,
+    &DONT_GIVE_UP,
+    &WAITING,
+    &ARRIVED,
+    &GAVE_UP,
+    &LEVEL_TUTORIAL,
+    &LEVEL_TRANSFER,
+    &LEVEL_MULTIPLE,
+    &LEVEL_TIME_PRESSURE,
+    &BONUS_RUSH_HOUR,
+    &BONUS_RUSH_HOUR_DESCRIPTION,
+    &BONUS_LEAN_NETWORK,
+    &BONUS_LEAN_NETWORK_DESCRIPTION,
+    &LEVEL_SELECT,
+    &LEVEL_SELECT_CAMPAIGN,
+    &LEVEL_SELECT_CUSTOM,
+    &CUSTOM_LEVELS_HINT,
+    &NO_CUSTOM_LEVELS,
+    &CUSTOM_LEVEL_INFO,
+    &EXPORT_LEVEL,
+    &LEVEL_EXPORTED,
+    &LEVEL_EXPORT_FAILED,
+    &LEVEL_TOTAL_STARS,
+    &LEVEL_BEST_RECORD,
+    &LEVEL_NOT_CLEARED,
+    &LEVEL_LOCKED,
+    &LEVEL_REQUIRES_STARS,
+    &WEEKLY_CHALLENGES,
+    &CHALLENGE_WEEK,
+    &CHALLENGE_BEST_SCORE,
+    &CHALLENGE_UNPLAYED,
+    &CHALLENGE_PLAY,
+    &NEW_BADGE,
+    &CHALLENGE_RIVER_CROSSING,
+    &CHALLENGE_DOWNTOWN_MAZE,
+    &CHALLENGE_RUSH_HOUR,
+    &CHALLENGE_SHOESTRING,
+    &CHALLENGE_TWIN_HUBS,
+    &CHALLENGE_MOUNTAIN_PASS,
+    &CHALLENGE_RIVER_CROSSING_DESCRIPTION,
+    &CHALLENGE_DOWNTOWN_MAZE_DESCRIPTION,
+    &CHALLENGE_RUSH_HOUR_DESCRIPTION,
+    &CHALLENGE_SHOESTRING_DESCRIPTION,
+    &CHALLENGE_TWIN_HUBS_DESCRIPTION,
+    &CHALLENGE_MOUNTAIN_PASS_DESCRIPTION,
+    &STATION_A,
+    &STATION_B,
+    &STATION_C,
+    &TRANSFER_HUB,
+    &NORTH_STATION,
+    &SOUTH_STATION,
+    &NORTHEAST_STATION,
+    &SOUTHEAST_STATION,
+    &CENTRAL_HUB,
+    &DEPOT_STATION,
+    &START_STATION,
+    &TARGET_STATION_A,
+    &TARGET_STATION_B,
+    &TARGET_STATION_C,
+    &OBJECTIVE_CONNECT_ALL,
+    &OBJECTIVE_MAX_TRANSFERS,
+    &OBJECTIVE_MAX_SEGMENTS,
+    &OBJECTIVE_MAX_COST,
+    &OBJECTIVE_TIME_LIMIT,
+    &OBJECTIVE_PASSENGER_SATISFACTION,
+    &OBJECTIVE_DELIVER_COLOR,
+    &OBJECTIVE_MAX_AVERAGE_WAIT,
+    &COLOR_RED,
+    &COLOR_BLUE,
+    &COLOR_GREEN,
+    &COLOR_YELLOW,
+    &COLOR_PURPLE,
+    &COLOR_ORANGE,
+    &TUTORIAL_DESCRIPTION,
+    &TRANSFER_DESCRIPTION,
+    &MULTIPLE_DESCRIPTION,
+    &TIME_PRESSURE_DESCRIPTION,
+    &PASSENGERS_GAVE_UP_WARNING,
+    &PASSENGERS_WAITING_HINT,
+    &BUS_ROUTES_READY_INFO,
+    &ROTATION_LOCKED_WARNING,
+    &REMOVALS_EXHAUSTED_WARNING,
+    &REMOVALS_LEFT_INFO,
+    &PARTIAL_REFUND_INFO,
+    &EXPRESS_UPGRADED,
+    &SCREEN_READER_STATUS,
+    &OBJECTIVE_COMPLETED_ANNOUNCEMENT,
+    &LEVEL_COMPLETE_ANNOUNCEMENT,
+    &CUE_PASSENGER_ARRIVED,
+    &CUE_ERROR,
+    &CUE_OBJECTIVE_COMPLETE,
+    &BUDGET_WARNING,
+    &FAILURE_WARNING_GAVE_UP,
+    &FAILURE_WARNING_TIME,
+    &TIP_HISTORY,
+    &TIP_HISTORY_EMPTY,
+    &COVERAGE_HEATMAP_HINT,
+    &WAIT_HEATMAP_HINT,
+    &PASSENGER_HOVER_ROUTE,
+    &PASSENGER_HOVER_VIA,
+    &PASSENGER_HOVER_PATIENCE,
+    &PASSENGER_HOVER_NO_PATH,
+    &INSPECT_STATE_WAITING,
+    &INSPECT_STATE_RIDING,
+    &INSPECT_STATE_TRANSFERRING,
+    &INSPECT_STATE_ARRIVED,
+    &INSPECT_STATE_GAVE_UP,
+    &INSPECT_PIN_HINT,
+    &INSPECT_PINNED,
+    &BUS_HOVER_TITLE,
+    &BUS_HOVER_LOAD,
+    &BUS_HOVER_NEXT_STOP,
+    &BUS_STATE_DRIVING,
+    &BUS_STATE_AT_STOP,
+    &BUS_STATE_TURNING,
+    &BUS_STATE_IDLE,
+];
 
 // ============ 本地化系统插件 ============
 
@@ -250,16 +1672,82 @@ pub struct LocalizationPlugin;
 impl Plugin for LocalizationPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CurrentLanguage>()
+            .init_resource::<LocaleFiles>()
+            .init_asset::<TranslationFile>()
+            .init_asset_loader::<TranslationFileLoader>()
             .add_event::<LanguageChangedEvent>()
+            .add_systems(Startup, load_locale_files)
             .add_systems(
                 Update,
-                (update_localized_texts, handle_language_change_events),
+                (
+                    apply_locale_files,
+                    update_localized_texts,
+                    handle_language_change_events,
+                    update_window_title,
+                ),
             );
     }
 }
 
 // ============ 系统函数 ============
 
+/// 加载中英文以外语言的翻译文件（assets/locales/<语言代码>.toml）
+fn load_locale_files(asset_server: Res<AssetServer>, mut locale_files: ResMut<LocaleFiles>) {
+    locale_files.0 = Language::ALL
+        .into_iter()
+        .filter(|language| !language.is_builtin())
+        .map(|language| {
+            let path = format!("{}/{}.toml", LOCALES_DIR, language.code());
+            (language, asset_server.load(path))
+        })
+        .collect();
+}
+
+/// 翻译文件加载或热重载后写入翻译表，当前语言的文本立即刷新
+fn apply_locale_files(
+    mut asset_events: EventReader<AssetEvent<TranslationFile>>,
+    locale_files: Res<LocaleFiles>,
+    translation_files: Res<Assets<TranslationFile>>,
+    mut current_language: ResMut<CurrentLanguage>,
+    mut localized_texts: Query<(&LocalizedTextComponent, &mut Text)>,
+) {
+    for event in asset_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        let Some((language, file)) = locale_files
+            .0
+            .iter()
+            .find(|(_, handle)| handle.id() == *id)
+            .and_then(|(language, handle)| Some((*language, translation_files.get(handle)?)))
+        else {
+            continue;
+        };
+
+        for key in file.entries.keys().filter(|key| find_text(key).is_none()) {
+            warn!("{} 翻译文件中有未知的键: {}", language.code(), key);
+        }
+        if let Ok(mut translations) = TRANSLATIONS.write() {
+            translations.insert(language, file.entries.clone());
+        }
+        info!(
+            "🌐 已加载 {} 翻译: {}/{} 条",
+            language.display_name(),
+            file.entries.len(),
+            ALL_TEXTS.len()
+        );
+
+        if language == current_language.language {
+            // 其他按语言刷新的界面也跟着重建
+            current_language.set_changed();
+            for (localized, mut text) in localized_texts.iter_mut() {
+                *text = Text::new(localized.get_text(language));
+            }
+        }
+    }
+}
+
 /// 更新所有本地化文本
 fn update_localized_texts(
     current_language: Res<CurrentLanguage>,
@@ -295,27 +1783,37 @@ fn handle_language_change_events(
 
         // 更新语言切换按钮文本
         for mut text in toggle_texts.iter_mut() {
-            let next_language_text = match event.new_language {
-                Language::English => "中文",
-                Language::Chinese => "English",
-            };
-            *text = Text::new(next_language_text);
+            *text = Text::new(event.new_language.next().display_name());
         }
 
         info!("Language changed to: {:?}", event.new_language);
     }
 }
 
+/// 窗口标题跟随当前语言
+fn update_window_title(
+    current_language: Res<CurrentLanguage>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !current_language.is_changed() {
+        return;
+    }
+
+    for mut window in windows.iter_mut() {
+        window.title = get_text(&GAME_TITLE, current_language.language);
+    }
+}
+
 // ============ 辅助函数 ============
 
 /// 获取本地化文本的便捷函数
 pub fn get_text(text_key: &LocalizedText, language: Language) -> String {
-    text_key.get(language).to_string()
+    text_key.get(language)
 }
 
 /// 获取带参数的本地化文本
 pub fn get_text_with_args(text_key: &LocalizedText, language: Language, args: &[&str]) -> String {
-    let mut result = text_key.get(language).to_string();
+    let mut result = text_key.get(language);
     for (i, arg) in args.iter().enumerate() {
         let placeholder = format!("{{{}}}", i);
         result = result.replace(&placeholder, arg);
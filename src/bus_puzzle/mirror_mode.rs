@@ -0,0 +1,344 @@
+// src/bus_puzzle/mirror_mode.rs - 镜像模式（水平翻转现有关卡，成绩单独记录）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ui_audio::MainMenuUI, ActiveChallenge, ButtonComponent,
+    ButtonType, CurrentLanguage, EventType, FlushSavesEvent, GameState, GameStateEnum, GridPos,
+    Language, LevelCompletedEvent, LevelData, LevelManager, RouteSegmentType, UIAssets,
+    MIRROR_MODE, MIRROR_PROGRESS,
+};
+use bevy::{
+    platform::collections::HashMap,
+    prelude::{Val::Px, *},
+};
+
+#[cfg(not(target_family = "wasm"))]
+const MIRROR_SCORES_FILE: &str = "mirror_scores.txt";
+
+// ============ 镜像模式资源 ============
+
+/// 镜像模式开关及镜像关卡的最佳成绩（与普通关卡分开记录）
+#[derive(Resource, Default)]
+pub struct MirrorMode {
+    pub enabled: bool,
+    pub best_scores: HashMap<String, u32>, // 关卡ID -> 镜像版本最佳分数
+}
+
+impl MirrorMode {
+    /// 记录镜像关卡成绩，返回是否刷新了最佳成绩
+    pub fn record_score(&mut self, level_id: &str, score: u32) -> bool {
+        let best = self.best_scores.entry(level_id.to_string()).or_insert(0);
+        if score > *best {
+            *best = score;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn serialize(&self) -> String {
+        let mut lines: Vec<String> = self
+            .best_scores
+            .iter()
+            .map(|(level_id, score)| format!("{}={}", level_id, score))
+            .collect();
+        lines.sort();
+        lines.join("\n")
+    }
+
+    fn deserialize(&mut self, content: &str) {
+        for line in content.lines() {
+            if let Some((level_id, score)) = line.trim().split_once('=')
+                && let Ok(score) = score.parse::<u32>()
+            {
+                self.best_scores.insert(level_id.to_string(), score);
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct MirrorToggleText;
+
+#[derive(Component)]
+pub struct MirrorProgressText;
+
+// ============ 镜像模式插件 ============
+
+pub struct MirrorModePlugin;
+
+impl Plugin for MirrorModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MirrorMode>()
+            .add_systems(Startup, load_mirror_scores)
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_mirror_panel)
+            .add_systems(
+                Update,
+                (handle_mirror_buttons, update_mirror_texts)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(Update, record_mirror_scores)
+            .add_systems(PostUpdate, flush_mirror_scores); // 退出前保存镜像成绩
+    }
+}
+
+// ============ 关卡镜像 ============
+
+/// 水平翻转关卡：地形、站点、预设路线和动态事件的位置都沿 x 轴镜像
+pub fn mirror_level_data(level_data: &LevelData) -> LevelData {
+    let width = level_data.grid_size.0 as i32;
+    let mirror_pos = |pos: GridPos| GridPos::new(width - 1 - pos.x, pos.y);
+
+    let mut mirrored = level_data.clone();
+
+    mirrored.terrain = level_data
+        .terrain
+        .iter()
+        .map(|(pos, terrain)| (mirror_pos(*pos), terrain.clone()))
+        .collect();
+
+    for station in &mut mirrored.stations {
+        station.position = mirror_pos(station.position);
+    }
+
+    for preset_route in &mut mirrored.preset_routes {
+        for (pos, segment_type, rotation) in &mut preset_route.segments {
+            *pos = mirror_pos(*pos);
+            *rotation = mirror_rotation(segment_type, *rotation);
+        }
+    }
+
+    for event in &mut mirrored.dynamic_events {
+        match &mut event.event_type {
+            EventType::SegmentFailure(pos) => *pos = mirror_pos(*pos),
+            EventType::RoadClosure(tiles) => {
+                for pos in tiles.iter_mut() {
+                    *pos = mirror_pos(*pos);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    mirrored
+}
+
+/// 找到镜像后连接方向一致的旋转角度
+fn mirror_rotation(segment_type: &RouteSegmentType, rotation: u32) -> u32 {
+    let mut target: Vec<(i32, i32)> = segment_type
+        .get_connection_offsets(rotation)
+        .into_iter()
+        .map(|(dx, dy)| (-dx, dy))
+        .collect();
+    target.sort();
+
+    [0, 90, 180, 270]
+        .into_iter()
+        .find(|candidate| {
+            let mut offsets = segment_type.get_connection_offsets(*candidate);
+            offsets.sort();
+            offsets == target
+        })
+        .unwrap_or(rotation)
+}
+
+// ============ 成绩记录与持久化 ============
+
+fn load_mirror_scores(mut mirror_mode: ResMut<MirrorMode>) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Ok(content) = std::fs::read_to_string(MIRROR_SCORES_FILE) {
+            mirror_mode.deserialize(&content);
+        } else {
+            info!("未找到镜像成绩文件");
+        }
+    }
+
+    info!("镜像关卡成绩: {} 个关卡", mirror_mode.best_scores.len());
+}
+
+fn save_mirror_scores(mirror_mode: &MirrorMode) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(MIRROR_SCORES_FILE, mirror_mode.serialize()) {
+            warn!("保存镜像成绩失败: {}", e);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        let _ = mirror_mode.serialize();
+        info!("WASM环境：镜像成绩已更新到内存");
+    }
+}
+
+fn record_mirror_scores(
+    mut level_completed_events: EventReader<LevelCompletedEvent>,
+    active_challenge: Res<ActiveChallenge>,
+    game_state: Res<GameState>,
+    mut mirror_mode: ResMut<MirrorMode>,
+) {
+    for event in level_completed_events.read() {
+        // 每周挑战不参与镜像模式
+        if !mirror_mode.enabled || active_challenge.0.is_some() {
+            continue;
+        }
+
+        let Some(level_data) = &game_state.current_level else {
+            continue;
+        };
+
+        let level_id = level_data.id.clone();
+        if mirror_mode.record_score(&level_id, event.final_score) {
+            info!("🪞 镜像关卡 {} 新纪录: {}", level_id, event.final_score);
+        }
+        save_mirror_scores(&mirror_mode);
+    }
+}
+
+fn flush_mirror_scores(
+    mut flush_events: EventReader<FlushSavesEvent>,
+    mirror_mode: Res<MirrorMode>,
+) {
+    if flush_events.read().last().is_some() {
+        save_mirror_scores(&mirror_mode);
+    }
+}
+
+// ============ 主菜单镜像模式面板 ============
+
+fn setup_mirror_panel(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    mirror_mode: Res<MirrorMode>,
+    level_manager: Res<LevelManager>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let language = current_language.language;
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                bottom: Px(20.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Px(6.0),
+                padding: UiRect::all(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+            MainMenuUI,
+            Name::new("Mirror Mode Panel"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Px(200.0),
+                        height: Px(32.0),
+                        justify_content: JustifyContent::FlexStart,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::horizontal(Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    ButtonComponent {
+                        button_type: ButtonType::ToggleMirrorMode,
+                        is_hovered: false,
+                        is_pressed: false,
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(mirror_toggle_label(&mirror_mode, language)),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        MirrorToggleText,
+                    ));
+                });
+
+            parent.spawn((
+                Text::new(mirror_progress_label(
+                    &mirror_mode,
+                    &level_manager,
+                    language,
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.2, 0.8, 0.2)),
+                MirrorProgressText,
+            ));
+        });
+}
+
+fn mirror_toggle_label(mirror_mode: &MirrorMode, language: Language) -> String {
+    let check = if mirror_mode.enabled { "[x]" } else { "[ ]" };
+    format!("{} {}", check, get_text(&MIRROR_MODE, language))
+}
+
+fn mirror_progress_label(
+    mirror_mode: &MirrorMode,
+    level_manager: &LevelManager,
+    language: Language,
+) -> String {
+    let cleared = level_manager
+        .available_levels
+        .iter()
+        .filter(|level_id| mirror_mode.best_scores.contains_key(*level_id))
+        .count();
+    get_text_with_args(
+        &MIRROR_PROGRESS,
+        language,
+        &[
+            &cleared.to_string(),
+            &level_manager.available_levels.len().to_string(),
+        ],
+    )
+}
+
+fn handle_mirror_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut mirror_mode: ResMut<MirrorMode>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::ToggleMirrorMode
+        {
+            mirror_mode.enabled = !mirror_mode.enabled;
+            info!("🪞 镜像模式: {}", mirror_mode.enabled);
+        }
+    }
+}
+
+fn update_mirror_texts(
+    mirror_mode: Res<MirrorMode>,
+    level_manager: Res<LevelManager>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<&mut Text, (With<MirrorToggleText>, Without<MirrorProgressText>)>,
+    mut progress_texts: Query<&mut Text, With<MirrorProgressText>>,
+) {
+    if !mirror_mode.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in toggle_texts.iter_mut() {
+        *text = Text::new(mirror_toggle_label(&mirror_mode, current_language.language));
+    }
+
+    for mut text in progress_texts.iter_mut() {
+        *text = Text::new(mirror_progress_label(
+            &mirror_mode,
+            &level_manager,
+            current_language.language,
+        ));
+    }
+}
@@ -1,47 +1,176 @@
 // 模块声明
+pub mod agent_lod;
+pub mod autosave;
+pub mod blueprints;
+pub mod bus_depot;
 pub mod bus_pathfinding_system;
 pub mod bus_system;
 pub mod components;
 pub mod config;
+pub mod confirm_dialog;
 pub mod connection_system;
+pub mod contracts;
+pub mod coverage_heatmap;
+pub mod day_night;
 pub mod debug_info;
+#[cfg(not(target_family = "wasm"))]
+#[cfg(test)]
+mod determinism;
+pub mod economy;
+pub mod elevated_layer;
+pub mod endless_mode;
+pub mod event_deck;
 pub mod events;
+pub mod exit_flow;
+pub mod failure_forecast;
+pub mod font_fallback;
+pub mod frame_limit;
+pub mod fullscreen;
+pub mod hotseat;
+pub mod input_map;
 pub mod interaction;
+pub mod level_briefing;
+pub mod level_files;
+pub mod level_select;
+pub mod level_stats;
 pub mod level_system;
 #[allow(dead_code)]
 pub mod localization;
+pub mod mirror_mode;
+pub mod music;
+pub mod mutators;
+pub mod network_retry;
+pub mod objective_banner;
 pub mod passenger_boarding_system;
 pub mod passenger_movement_debug;
 pub mod pathfinding;
+pub mod quicksave;
+pub mod repair_hints;
+pub mod replay;
 pub mod resources;
+pub mod results_export;
+pub mod road_closures;
+pub mod route_lines;
+pub mod route_schedule;
+pub mod rtl_layout;
+pub mod save_game;
+pub mod scenario;
+pub mod score_attack;
+pub mod screen_reader;
+pub mod scripting;
+pub mod seed_share;
+pub mod segment_clipboard;
+pub mod settings_menu;
+pub mod sim_clock;
 pub mod smart_bus_generation;
 pub mod splash;
+pub mod state_cleanup;
+pub mod station_labels;
+pub mod station_upgrades;
+#[cfg(target_family = "wasm")]
+pub mod tab_visibility;
+pub mod terrain_brush;
+pub mod terrain_chunks;
+pub mod text_to_speech;
+pub mod themes;
+pub mod timelapse;
 pub mod tips_system;
+pub mod touch_ui;
+pub mod transit_import;
 pub mod ui_audio;
+pub mod user_config;
 pub mod utils;
-
-use bevy::{
-    audio::{PlaybackMode, Volume},
-    platform::collections::HashMap,
-};
+pub mod visual_cues;
+pub mod wait_heatmap;
+pub mod weather;
+#[cfg(target_family = "wasm")]
+pub mod web_storage;
+pub mod weekly_challenge;
+pub mod window_settings;
+
+use bevy::platform::collections::HashMap;
 // 重新导出主要类型
+pub use agent_lod::*;
+pub use autosave::*;
+pub use blueprints::*;
+pub use bus_depot::*;
 pub use bus_pathfinding_system::*;
 pub use bus_system::*;
 pub use components::*;
 pub use config::*;
+pub use confirm_dialog::*;
+pub use contracts::*;
+pub use coverage_heatmap::*;
+pub use day_night::*;
 pub use debug_info::*;
+#[cfg(not(target_family = "wasm"))]
+pub use economy::*;
+pub use elevated_layer::*;
+pub use endless_mode::*;
+pub use event_deck::*;
 pub use events::*;
+pub use exit_flow::*;
+pub use failure_forecast::*;
+pub use font_fallback::*;
+pub use frame_limit::*;
+pub use fullscreen::*;
+pub use hotseat::*;
+pub use input_map::*;
 pub use interaction::*;
+pub use level_briefing::*;
+pub use level_files::*;
+pub use level_select::*;
+pub use level_stats::*;
 pub use level_system::*;
 // 新增：导出乘客上下车系统
 pub use localization::*;
+pub use mirror_mode::*;
+pub use music::*;
+pub use mutators::*;
+pub use network_retry::*;
+pub use objective_banner::*;
 pub use passenger_boarding_system::*;
 pub use passenger_movement_debug::*;
 pub use pathfinding::*;
+pub use quicksave::*;
+pub use repair_hints::*;
+pub use replay::*;
 pub use resources::*;
+pub use results_export::*;
+pub use road_closures::*;
+pub use route_lines::*;
+pub use route_schedule::*;
+pub use rtl_layout::*;
+pub use save_game::*;
+pub use scenario::*;
+pub use score_attack::*;
+pub use screen_reader::*;
+pub use scripting::*;
+pub use seed_share::*;
+pub use segment_clipboard::*;
+pub use settings_menu::*;
+pub use sim_clock::*;
+pub use state_cleanup::*;
+pub use station_labels::*;
+pub use station_upgrades::*;
+#[cfg(target_family = "wasm")]
+pub use tab_visibility::*;
+pub use terrain_brush::*;
+pub use terrain_chunks::*;
+pub use text_to_speech::*;
+pub use themes::*;
+pub use timelapse::*;
 pub use tips_system::*;
+pub use touch_ui::*;
+pub use transit_import::*;
 pub use ui_audio::*;
+pub use user_config::*;
 pub use utils::*;
+pub use visual_cues::*;
+pub use wait_heatmap::*;
+pub use weather::*;
+pub use weekly_challenge::*;
+pub use window_settings::*;
 
 use crate::bus_puzzle::smart_bus_generation::SmartBusGenerationPlugin;
 use crate::bus_puzzle::{
@@ -71,25 +200,135 @@ impl Plugin for BusPuzzleGamePlugin {
             SmartBusGenerationPlugin,
             LocalizedTipsSystemPlugin,
             LocalizationPlugin, // 新增：提示系统
+            SettingsMenuPlugin,
+            ExitFlowPlugin,
+        ));
+        app.add_plugins((
+            WeeklyChallengePlugin,
+            MutatorPlugin,
+            MirrorModePlugin,
+            HotSeatPlugin,
+            ScoreAttackPlugin,
+            AutosavePlugin,
+            TimelapsePlugin,
+            ResultsExportPlugin,
+            TransitImportPlugin,
+            TouchUiPlugin,
+            WindowSettingsPlugin,
+            FrameLimitPlugin,
+            ScreenReaderPlugin,
+            VisualCuesPlugin,
+            EconomyPlugin,
+        ));
+        app.add_plugins((
+            StationUpgradePlugin,
+            EventDeckPlugin,
+            TerrainBrushPlugin,
+            ContractsPlugin,
+            RoadClosuresPlugin,
+            ObjectiveBannerPlugin,
+            CoverageHeatmapPlugin,
+            StationLabelsPlugin,
+            FullscreenPlugin,
+            ScriptingPlugin,
+            ThemePlugin,
+            SeedSharePlugin,
+            SaveGamePlugin,
+            ReplayPlugin,
+        ));
+        app.add_plugins((
+            UserConfigPlugin,
+            InputMapPlugin,
+            EndlessModePlugin,
+            LevelSelectPlugin,
+            TerrainChunkPlugin,
+            RouteSchedulePlugin,
+            WaitHeatmapPlugin,
+            RouteLinesPlugin,
+            LevelStatsPlugin,
+            LevelFilesPlugin,
+            SimClockPlugin,
+            ElevatedLayerPlugin,
+            AgentLodPlugin,
+            FailureForecastPlugin,
+            BusDepotPlugin,
+        ));
+        app.add_plugins((
+            DayNightPlugin,
+            WeatherPlugin,
+            ScenarioPlugin,
+            RepairHintsPlugin,
+            ConfirmDialogPlugin,
+            SegmentClipboardPlugin,
+            BlueprintsPlugin,
+            MusicPlugin,
+            TextToSpeechPlugin,
+            FontFallbackPlugin,
+            RtlLayoutPlugin,
+            LevelBriefingPlugin,
+            NetworkRetryPlugin,
+            QuicksavePlugin,
         ));
 
+        // 网页版：标签页切到后台时暂停
+        #[cfg(target_family = "wasm")]
+        app.add_plugins(TabVisibilityPlugin);
+
         app.init_resource::<GameState>()
             .init_resource::<CurrentLanguage>()
-            .init_state::<GameStateEnum>();
+            .init_state::<GameStateEnum>()
+            // 带 StateScoped 的界面实体在离开对应状态时自动清理
+            .enable_state_scoped_entities::<GameStateEnum>();
 
         app.add_event::<SegmentPlacedEvent>()
             .add_event::<SegmentRemovedEvent>()
+            .add_event::<SegmentRotatedEvent>()
+            .add_event::<SegmentUpgradedEvent>()
             .add_event::<ObjectiveCompletedEvent>()
             .add_event::<LevelCompletedEvent>()
+            .add_event::<ScoreChangedEvent>()
             .add_event::<InventoryUpdatedEvent>()
             .add_event::<PassengerSpawnedEvent>()
             .add_event::<PassengerArrivedEvent>()
-            .add_event::<LanguageChangedEvent>();
+            .add_event::<PassengerGaveUpEvent>()
+            .add_event::<LanguageChangedEvent>()
+            .add_event::<RotateSegmentRequestEvent>()
+            .add_event::<RemoveSegmentRequestEvent>()
+            .add_event::<FlushSavesEvent>();
 
         app.add_systems(Startup, (initialize_game, load_language_settings))
             .add_systems(OnEnter(GameStateEnum::MainMenu), load_language_settings)
-            .add_systems(OnExit(GameStateEnum::MainMenu), load_current_level)
-            .add_systems(OnEnter(GameStateEnum::Loading), load_current_level)
+            .add_systems(
+                OnExit(GameStateEnum::MainMenu),
+                (
+                    select_autosaved_level,
+                    load_current_level,
+                    apply_level_mutators,
+                    draw_level_events,
+                    apply_game_mode,
+                    restore_autosave,
+                )
+                    .chain()
+                    // 恢复的线路分组不能被重置
+                    .after(reset_route_lines)
+                    .after(reset_fleet)
+                    .after(reset_scenario)
+                    .after(reset_contracts)
+                    .after(reset_economy)
+                    .after(reset_route_schedules)
+                    // 从主菜单直接进入回放时由 ReplayPlugin 加载关卡
+                    .run_if(not(in_state(GameStateEnum::Replay))),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::Loading),
+                (
+                    load_current_level,
+                    apply_level_mutators,
+                    draw_level_events,
+                    apply_game_mode,
+                )
+                    .chain(),
+            )
             .add_systems(OnExit(GameStateEnum::Loading), cleanup_loading_state)
             // .add_systems(OnEnter(GameStateEnum::MainMenu), default_game_state)
             .add_systems(
@@ -98,7 +337,6 @@ impl Plugin for BusPuzzleGamePlugin {
                     update_game_score,
                     check_level_failure_conditions,
                     handle_language_toggle_globally, // 新增：全局语言切换处理
-                    save_language_settings,
                 )
                     .run_if(in_state(GameStateEnum::Playing)),
             )
@@ -109,51 +347,6 @@ impl Plugin for BusPuzzleGamePlugin {
     }
 }
 
-/// 清理游戏世界中的所有实体
-fn cleanup_game_world(
-    commands: &mut Commands,
-    tiles: Query<Entity, With<GridTile>>,
-    stations: Query<Entity, With<StationEntity>>,
-    segments: Query<Entity, With<RouteSegment>>,
-    passengers: Query<Entity, With<PathfindingAgent>>,
-    previews: Query<Entity, With<SegmentPreview>>,
-    buses: Query<Entity, With<BusVehicle>>, // 新增：清理公交车
-) {
-    info!("清理游戏世界实体...");
-
-    // 清理地形瓦片
-    for entity in tiles.iter() {
-        commands.entity(entity).despawn();
-    }
-
-    // 清理站点
-    for entity in stations.iter() {
-        commands.entity(entity).despawn();
-    }
-
-    // 清理路线段
-    for entity in segments.iter() {
-        commands.entity(entity).despawn();
-    }
-
-    // 清理乘客
-    for entity in passengers.iter() {
-        commands.entity(entity).despawn();
-    }
-
-    // 清理预览
-    for entity in previews.iter() {
-        commands.entity(entity).despawn();
-    }
-
-    // 清理公交车
-    for entity in buses.iter() {
-        commands.entity(entity).despawn();
-    }
-
-    info!("游戏世界清理完成");
-}
-
 /// 重置游戏状态
 fn reset_game_state(game_state: &mut GameState, level_data: &LevelData, system_time: f32) {
     info!("重置游戏状态...");
@@ -173,6 +366,8 @@ fn reset_game_state(game_state: &mut GameState, level_data: &LevelData, system_t
 
     // 清理已放置的路线段
     game_state.placed_segments.clear();
+    game_state.elevated_segments.clear();
+    game_state.closed_tiles.clear();
 
     // 重置计分和计时
     game_state.total_cost = 0;
@@ -185,11 +380,7 @@ fn reset_game_state(game_state: &mut GameState, level_data: &LevelData, system_t
     game_state.objectives_completed = vec![false; level_data.objectives.len()];
 
     // 重置乘客统计
-    game_state.passenger_stats = PassengerStats {
-        total_spawned: 0,
-        total_arrived: 0,
-        total_gave_up: 0,
-    };
+    game_state.passenger_stats = PassengerStats::default();
 
     // 重置库存
     let mut inventory = HashMap::new();
@@ -203,34 +394,14 @@ fn reset_game_state(game_state: &mut GameState, level_data: &LevelData, system_t
 
 // ============ 语言设置管理 ============
 
-/// 加载语言设置（从本地存储或默认设置）
+/// 加载语言设置（从用户设置或默认设置），语言变化由 UserConfigPlugin 保存
 fn load_language_settings(
-    #[cfg(not(target_family = "wasm"))] mut current_language: ResMut<CurrentLanguage>,
-    #[cfg(target_family = "wasm")] current_language: Res<CurrentLanguage>,
+    mut current_language: ResMut<CurrentLanguage>,
     mut ev: EventWriter<LanguageChangedEvent>,
 ) {
-    // 尝试从本地存储加载语言设置
-    #[cfg(not(target_family = "wasm"))]
-    {
-        use std::fs;
-        if let Ok(language_file) = fs::read_to_string("language_setting.txt") {
-            match language_file.trim() {
-                "en" => current_language.language = Language::English,
-                "zh" => current_language.language = Language::Chinese,
-                _ => {
-                    info!("无效的语言设置，使用默认语言");
-                }
-            }
-        } else {
-            info!("未找到语言设置文件，使用默认语言");
-        }
-    }
-
-    // WASM环境下从localStorage加载（如果支持的话）
-    #[cfg(target_family = "wasm")]
-    {
-        // 在WASM环境下，可以尝试使用web_sys访问localStorage
-        info!("WASM环境：使用默认语言设置");
+    match saved_language() {
+        Some(language) => current_language.language = language,
+        None => info!("未找到语言设置，使用默认语言"),
     }
 
     info!("当前语言设置: {:?}", current_language.language);
@@ -239,38 +410,11 @@ fn load_language_settings(
     });
 }
 
-/// 保存语言设置到本地存储
-fn save_language_settings(
-    current_language: Res<CurrentLanguage>,
-    mut last_saved_language: Local<Option<Language>>,
-) {
-    // 只有当语言真正改变时才保存
-    if Some(current_language.language) != *last_saved_language {
-        *last_saved_language = Some(current_language.language);
-
-        #[cfg(not(target_family = "wasm"))]
-        {
-            use std::fs;
-            let language_code = current_language.language.code();
-            if let Err(e) = fs::write("language_setting.txt", language_code) {
-                warn!("保存语言设置失败: {}", e);
-            } else {
-                info!("语言设置已保存: {}", language_code);
-            }
-        }
-
-        #[cfg(target_family = "wasm")]
-        {
-            // WASM环境下的保存逻辑（如果需要的话）
-            info!("WASM环境：语言设置已更新到内存");
-        }
-    }
-}
-
 /// 全局语言切换处理（在所有状态下都生效）
 fn handle_language_toggle_globally(
     button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
     current_language: Res<CurrentLanguage>,
     mut language_events: EventWriter<LanguageChangedEvent>,
     mut toggle_texts: Query<&mut Text, With<LanguageToggleText>>,
@@ -279,36 +423,32 @@ fn handle_language_toggle_globally(
 
     // 检查按钮点击
     for (interaction, button_component) in button_query.iter() {
-        if matches!(*interaction, Interaction::Pressed) {
-            if let ButtonType::ToggleLanguage = button_component.button_type {
-                should_toggle = true;
-                break;
-            }
+        if matches!(*interaction, Interaction::Pressed)
+            && let ButtonType::ToggleLanguage = button_component.button_type
+        {
+            should_toggle = true;
+            break;
         }
     }
 
-    // 检查快捷键 L 键切换语言
-    if keyboard_input.just_pressed(KeyCode::KeyL) {
+    // 检查切换语言快捷键（默认 L 键）
+    if input_map.just_pressed(&keyboard_input, InputAction::ToggleLanguage) {
         should_toggle = true;
-        info!("通过L键切换语言");
+        info!(
+            "通过 {} 键切换语言",
+            input_map.key_name(InputAction::ToggleLanguage)
+        );
     }
 
     if should_toggle {
-        let new_language = match current_language.language {
-            Language::English => Language::Chinese,
-            Language::Chinese => Language::English,
-        };
+        let new_language = current_language.language.next();
 
         // 发送语言切换事件
         language_events.write(LanguageChangedEvent { new_language });
 
         // 立即更新语言切换按钮的文本
         for mut text in toggle_texts.iter_mut() {
-            let next_language_text = match new_language {
-                Language::English => "中文",
-                Language::Chinese => "English",
-            };
-            *text = Text::new(next_language_text);
+            *text = Text::new(new_language.next().display_name());
         }
 
         info!("语言已切换到: {:?}", new_language);
@@ -322,6 +462,7 @@ fn initialize_game(
     mut level_manager: ResMut<LevelManager>,
     mut game_state: ResMut<GameState>,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     current_language: Res<CurrentLanguage>, // 新增：获取当前语言
     time: Res<Time>,
 ) {
@@ -338,6 +479,7 @@ fn initialize_game(
     generate_level_map(
         &mut commands,
         &asset_server,
+        &theme,
         &tutorial_level,
         level_manager.tile_size,
     );
@@ -367,16 +509,17 @@ fn load_current_level(
     current_language: Res<CurrentLanguage>, // 新增：获取当前语言
     mut next_state: ResMut<NextState<GameStateEnum>>,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     mut pathfinding_graph: ResMut<PathfindingGraph>,
     mut level_complete_data: ResMut<LevelCompleteData>,
     time: Res<Time>,
+    (active_challenge, mirror_mode, custom_level): (
+        Res<ActiveChallenge>,
+        Res<MirrorMode>,
+        Res<CustomLevel>,
+    ),
     // 清理现有的游戏实体
-    existing_tiles: Query<Entity, With<GridTile>>,
-    existing_stations: Query<Entity, With<StationEntity>>,
-    existing_segments: Query<Entity, With<RouteSegment>>,
-    existing_passengers: Query<Entity, With<PathfindingAgent>>,
-    existing_previews: Query<Entity, With<SegmentPreview>>,
-    existing_buses: Query<Entity, With<BusVehicle>>,
+    level_entities: Query<Entity, With<LevelEntity>>,
 ) {
     info!(
         "开始加载关卡，当前索引: {}, 语言: {:?}",
@@ -386,44 +529,48 @@ fn load_current_level(
     // 重置关卡完成数据
     level_complete_data.final_score = 0;
     level_complete_data.completion_time = 0.0;
+    level_complete_data.score_breakdown = GameScore::default();
 
     // 清理所有现有的游戏实体
-    cleanup_game_world(
-        &mut commands,
-        existing_tiles,
-        existing_stations,
-        existing_segments,
-        existing_passengers,
-        existing_previews,
-        existing_buses,
-    );
+    despawn_level_entities(&mut commands, &level_entities);
 
     // 重置寻路图
     pathfinding_graph.connections.clear();
     pathfinding_graph.nodes.clear();
+    pathfinding_graph.elevated_connections.clear();
+    pathfinding_graph.elevated_nodes.clear();
     pathfinding_graph.station_lookup.clear();
     pathfinding_graph.route_network.clear();
 
-    // 获取本地化关卡数据
-    let level_data = if let Some(level_id) = level_manager
+    // 获取本地化关卡数据（自定义关卡、每周挑战优先）
+    let level_data = if let Some(custom_level) = &custom_level.0 {
+        custom_level.clone()
+    } else if let Some(challenge_level) = active_challenge
+        .0
+        .and_then(|challenge_id| create_challenge_level(challenge_id, current_language.language))
+    {
+        challenge_level
+    } else if let Some(level_id) = level_manager
         .available_levels
         .get(level_manager.current_level_index)
     {
-        match level_id.as_str() {
-            "tutorial_01" => create_tutorial_level(current_language.language),
-            "level_02_transfer" => create_transfer_level(current_language.language),
-            "level_03_multiple_routes" => create_multiple_routes_level(current_language.language),
-            "level_04_time_pressure" => create_time_pressure_level(current_language.language),
-            _ => {
-                warn!("未知关卡ID: {}, 使用教学关卡", level_id);
-                create_tutorial_level(current_language.language)
-            }
-        }
+        create_builtin_level(level_id, current_language.language).unwrap_or_else(|| {
+            warn!("未知关卡ID: {}, 使用教学关卡", level_id);
+            create_tutorial_level(current_language.language)
+        })
     } else {
         warn!("无效的关卡索引: {}", level_manager.current_level_index);
         return;
     };
 
+    // 镜像模式只作用于普通关卡
+    let level_data = if mirror_mode.enabled && active_challenge.0.is_none() {
+        info!("🪞 镜像模式：水平翻转关卡 {}", level_data.id);
+        mirror_level_data(&level_data)
+    } else {
+        level_data
+    };
+
     // 重置游戏状态
     reset_game_state(&mut game_state, &level_data, time.elapsed_secs());
 
@@ -431,6 +578,7 @@ fn load_current_level(
     generate_level_map(
         &mut commands,
         &asset_server,
+        &theme,
         &level_data,
         level_manager.tile_size,
     );
@@ -445,41 +593,60 @@ fn cleanup_loading_state() {
     info!("清理加载状态");
 }
 
-fn update_game_score(mut game_state: ResMut<GameState>, passengers: Query<&PathfindingAgent>) {
-    if let Some(level_data) = &game_state.current_level {
-        let base_points = level_data.scoring.base_points;
-
-        let network_efficiency = calculate_network_efficiency(&game_state, &passengers);
-        let efficiency_bonus =
-            (network_efficiency * level_data.scoring.efficiency_bonus as f32) as u32;
-
-        let speed_bonus = if game_state.game_time < 60.0 {
-            level_data.scoring.speed_bonus
-        } else {
-            0
-        };
-
-        let cost_threshold = match level_data.id.as_str() {
-            "tutorial_01" => 10,
-            "level_02_transfer" => 15,
-            "level_03_multiple_routes" => 25,
-            "level_04_time_pressure" => 20,
-            _ => 15,
-        };
-
-        let cost_bonus = if game_state.total_cost <= cost_threshold {
-            level_data.scoring.cost_bonus
-        } else {
-            0
-        };
-
-        game_state.score = GameScore {
-            base_points,
-            efficiency_bonus,
-            speed_bonus,
-            cost_bonus,
-            total_score: base_points + efficiency_bonus + speed_bonus + cost_bonus,
-        };
+fn update_game_score(
+    mut game_state: ResMut<GameState>,
+    passengers: Query<&PathfindingAgent>,
+    mutators: Res<LevelMutators>,
+    score_attack: Res<ScoreAttackMode>,
+    contracts: Res<ContractBoard>,
+    mut score_changed_events: EventWriter<ScoreChangedEvent>,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    let scoring = &level_data.scoring;
+
+    let base_points = scoring.base_points;
+
+    let network_efficiency = calculate_network_efficiency(&game_state, &passengers);
+    let efficiency_bonus = (network_efficiency * scoring.efficiency_bonus as f32) as u32;
+
+    let speed_bonus = scoring.speed_bonus_for(game_state.game_time);
+    let cost_bonus = scoring.cost_bonus_for(game_state.total_cost);
+
+    // 分数冲刺模式的连击加分
+    let combo_bonus = if score_attack.enabled {
+        score_attack.bonus_points
+    } else {
+        0
+    };
+
+    // 关卡倍率与规则变体倍率叠乘
+    let multiplier = scoring.multiplier * mutators.score_multiplier();
+    let total_score = ((base_points
+        + efficiency_bonus
+        + speed_bonus
+        + cost_bonus
+        + combo_bonus
+        + contracts.bonus_points) as f32
+        * multiplier) as u32;
+
+    let score = GameScore {
+        base_points,
+        efficiency_bonus,
+        speed_bonus,
+        cost_bonus,
+        combo_bonus,
+        contract_bonus: contracts.bonus_points,
+        multiplier,
+        total_score,
+    };
+
+    if score != game_state.score {
+        score_changed_events.write(ScoreChangedEvent {
+            breakdown: score.clone(),
+        });
+        game_state.score = score;
     }
 }
 
@@ -490,6 +657,8 @@ fn check_level_failure_conditions(
     mut commands: Commands,
     audio_assets: Res<AudioAssets>,
     audio_settings: Res<AudioSettings>,
+    game_mode: Res<GameMode>,
+    economy: Res<EconomyMode>,
 ) {
     let gave_up_count = passengers
         .iter()
@@ -497,7 +666,7 @@ fn check_level_failure_conditions(
         .count() as u32;
 
     // 乘客放弃失败条件
-    if gave_up_count > 3 {
+    if gave_up_count > MAX_GAVE_UP_PASSENGERS {
         commands.insert_resource(GameOverData {
             reason: format!("太多乘客放弃了行程 ({} 人)", gave_up_count),
             final_score: game_state.score.total_score,
@@ -508,13 +677,7 @@ fn check_level_failure_conditions(
         if !audio_settings.is_muted {
             commands.spawn((
                 AudioPlayer::new(audio_assets.error_sound.clone()),
-                PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    volume: Volume::Linear(
-                        audio_settings.sfx_volume * audio_settings.master_volume * 1.2,
-                    ),
-                    ..default()
-                },
+                audio_settings.sound(AudioBus::World, 1.2),
             ));
         }
 
@@ -523,38 +686,59 @@ fn check_level_failure_conditions(
         return;
     }
 
+    // 经营模式资金耗尽失败条件（无尽模式同样适用）
+    if economy.is_bankrupt(&game_state) {
+        let balance = economy.balance(&game_state);
+        commands.insert_resource(GameOverData {
+            reason: format!("资金耗尽，经营破产 ({})", balance),
+            final_score: game_state.score.total_score,
+            game_time: game_state.game_time,
+            passengers_gave_up: gave_up_count,
+        });
+
+        if !audio_settings.is_muted {
+            commands.spawn((
+                AudioPlayer::new(audio_assets.error_sound.clone()),
+                audio_settings.sound(AudioBus::World, 1.2),
+            ));
+        }
+
+        next_state.set(GameStateEnum::GameOver);
+        warn!("游戏失败：经营破产 (余额 {})", balance);
+        return;
+    }
+
+    // 无尽模式没有时间限制，只有乘客放弃过多才会结束
+    if game_mode.is_endless() {
+        return;
+    }
+
     // 时间限制失败条件
     if let Some(level_data) = &game_state.current_level {
         for objective in &level_data.objectives {
-            if let ObjectiveType::TimeLimit(time_limit) = &objective.condition_type {
-                if game_state.game_time > *time_limit {
-                    commands.insert_resource(GameOverData {
-                        reason: format!(
-                            "时间超限 ({:.1}s / {:.1}s)",
-                            game_state.game_time, time_limit
-                        ),
-                        final_score: game_state.score.total_score,
-                        game_time: game_state.game_time,
-                        passengers_gave_up: gave_up_count,
-                    });
-
-                    if !audio_settings.is_muted {
-                        commands.spawn((
-                            AudioPlayer::new(audio_assets.error_sound.clone()),
-                            PlaybackSettings {
-                                mode: PlaybackMode::Despawn,
-                                volume: Volume::Linear(
-                                    audio_settings.sfx_volume * audio_settings.master_volume * 1.2,
-                                ),
-                                ..default()
-                            },
-                        ));
-                    }
-
-                    next_state.set(GameStateEnum::GameOver);
-                    warn!("游戏失败：时间超限 ({:.1}s)", game_state.game_time);
-                    return;
+            if let ObjectiveType::TimeLimit(time_limit) = &objective.condition_type
+                && game_state.game_time > *time_limit
+            {
+                commands.insert_resource(GameOverData {
+                    reason: format!(
+                        "时间超限 ({:.1}s / {:.1}s)",
+                        game_state.game_time, time_limit
+                    ),
+                    final_score: game_state.score.total_score,
+                    game_time: game_state.game_time,
+                    passengers_gave_up: gave_up_count,
+                });
+
+                if !audio_settings.is_muted {
+                    commands.spawn((
+                        AudioPlayer::new(audio_assets.error_sound.clone()),
+                        audio_settings.sound(AudioBus::World, 1.2),
+                    ));
                 }
+
+                next_state.set(GameStateEnum::GameOver);
+                warn!("游戏失败：时间超限 ({:.1}s)", game_state.game_time);
+                return;
             }
         }
     }
@@ -0,0 +1,215 @@
+// src/bus_puzzle/music.rs - 背景音乐控制（按游戏状态切换曲目并交叉淡入淡出，曲目定义在 assets/audio/soundtrack.music.json）
+
+use crate::bus_puzzle::{AudioBus, AudioSettings, BackgroundMusic, GameStateEnum, MusicDucking};
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+use serde::Deserialize;
+
+const MUSIC_MANIFEST_PATH: &str = "audio/soundtrack.music.json";
+
+// ============ 曲目清单 ============
+
+/// 曲目清单：每首曲目在哪些游戏状态下播放，切换时的淡入淡出时长
+#[derive(Asset, TypePath, Debug, Deserialize)]
+pub struct MusicManifest {
+    pub crossfade_secs: f32,
+    pub tracks: Vec<MusicTrackDefinition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MusicTrackDefinition {
+    pub name: String,
+    pub path: String,
+    #[serde(default = "default_track_volume")]
+    pub volume: f32, // 相对音乐音量的倍率
+    pub states: Vec<GameStateEnum>,
+}
+
+fn default_track_volume() -> f32 {
+    1.0
+}
+
+impl MusicManifest {
+    /// 当前状态对应的曲目序号；没有曲目的状态（如启动画面）保持安静
+    fn track_for(&self, state: &GameStateEnum) -> Option<usize> {
+        self.tracks
+            .iter()
+            .position(|track| track.states.contains(state))
+    }
+}
+
+#[derive(Default)]
+struct MusicManifestLoader;
+
+impl AssetLoader for MusicManifestLoader {
+    type Asset = MusicManifest;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["music.json"]
+    }
+}
+
+// ============ 播放状态 ============
+
+/// 曲目清单句柄和当前应当播放的曲目
+#[derive(Resource, Default)]
+pub struct MusicController {
+    manifest: Handle<MusicManifest>,
+    current: Option<usize>,
+}
+
+/// 正在播放的曲目；fade 为淡入淡出进度，淡出到 0 后销毁
+#[derive(Component)]
+pub struct MusicTrack {
+    index: usize,
+    fade: f32,
+    fading_out: bool,
+}
+
+// ============ 背景音乐插件 ============
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicController>()
+            .init_asset::<MusicManifest>()
+            .init_asset_loader::<MusicManifestLoader>()
+            .add_systems(Startup, load_music_manifest)
+            .add_systems(
+                Update,
+                (reload_music_manifest, switch_music_track, crossfade_music).chain(),
+            );
+    }
+}
+
+fn load_music_manifest(asset_server: Res<AssetServer>, mut controller: ResMut<MusicController>) {
+    controller.manifest = asset_server.load(MUSIC_MANIFEST_PATH);
+}
+
+/// 清单热重载后重新选择曲目，新的定义立即生效
+fn reload_music_manifest(
+    mut asset_events: EventReader<AssetEvent<MusicManifest>>,
+    mut controller: ResMut<MusicController>,
+) {
+    let modified = asset_events.read().any(
+        |event| matches!(event, AssetEvent::Modified { id } if *id == controller.manifest.id()),
+    );
+    if modified {
+        controller.current = None;
+        info!("🎵 曲目清单已更新");
+    }
+}
+
+/// 状态对应的曲目变化时，旧曲目开始淡出，新曲目从静音开始淡入
+fn switch_music_track(
+    mut commands: Commands,
+    mut controller: ResMut<MusicController>,
+    manifests: Res<Assets<MusicManifest>>,
+    asset_server: Res<AssetServer>,
+    current_state: Res<State<GameStateEnum>>,
+    mut tracks: Query<&mut MusicTrack>,
+) {
+    let Some(manifest) = manifests.get(&controller.manifest) else {
+        return;
+    };
+    let desired = manifest.track_for(current_state.get());
+    let playing = tracks
+        .iter()
+        .any(|track| !track.fading_out && Some(track.index) == desired);
+    if desired == controller.current && (desired.is_none() || playing) {
+        return;
+    }
+
+    for mut track in tracks.iter_mut() {
+        track.fading_out = true;
+    }
+    controller.current = desired;
+
+    let Some(index) = desired else {
+        return;
+    };
+    let definition = &manifest.tracks[index];
+    commands.spawn((
+        AudioPlayer::new(asset_server.load(&definition.path)),
+        PlaybackSettings {
+            mode: PlaybackMode::Loop,
+            volume: Volume::Linear(0.0),
+            ..default()
+        },
+        AudioBus::Music,
+        BackgroundMusic,
+        MusicTrack {
+            index,
+            fade: 0.0,
+            fading_out: false,
+        },
+        Name::new(format!("Music: {}", definition.name)),
+    ));
+    info!(
+        "🎵 切换背景音乐: {} ({:?})",
+        definition.name,
+        current_state.get()
+    );
+}
+
+/// 推进淡入淡出并按音乐音量、曲目倍率和音乐压低设置实际音量
+fn crossfade_music(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    controller: Res<MusicController>,
+    manifests: Res<Assets<MusicManifest>>,
+    audio_settings: Res<AudioSettings>,
+    music_ducking: Res<MusicDucking>,
+    mut tracks: Query<(Entity, &mut MusicTrack, Option<&mut AudioSink>)>,
+) {
+    let Some(manifest) = manifests.get(&controller.manifest) else {
+        return;
+    };
+    // 用真实时间推进，回放倍速或暂停时淡入淡出速度不变
+    let step = if manifest.crossfade_secs > 0.0 {
+        time.delta_secs() / manifest.crossfade_secs
+    } else {
+        1.0
+    };
+    let music_volume = audio_settings.bus_volume(AudioBus::Music) * music_ducking.factor;
+
+    for (entity, mut track, sink) in tracks.iter_mut() {
+        track.fade = if track.fading_out {
+            (track.fade - step).max(0.0)
+        } else {
+            (track.fade + step).min(1.0)
+        };
+        if track.fading_out && track.fade <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // 播放器在加载完音频后才有 AudioSink
+        let Some(mut sink) = sink else {
+            continue;
+        };
+        let gain = manifest
+            .tracks
+            .get(track.index)
+            .map_or(1.0, |definition| definition.volume);
+        sink.set_volume(Volume::Linear(music_volume * gain * track.fade));
+    }
+}
@@ -0,0 +1,285 @@
+// src/bus_puzzle/mutators.rs - 规则变体（增加限制，换取分数加成）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ui_audio::MainMenuUI, ButtonComponent, ButtonType,
+    CurrentLanguage, GameState, GameStateEnum, Language, LocalizedText, RouteSegmentType, UIAssets,
+    MUTATORS, MUTATOR_LIMITED_REMOVALS, MUTATOR_NO_CURVES, MUTATOR_NO_ROTATION, SCORE_MULTIPLIER,
+};
+use bevy::prelude::{Val::Px, *};
+
+/// 开启“限制拆除”后每关允许的拆除次数
+pub const MUTATOR_MAX_REMOVALS: u32 = 3;
+
+/// 每个开启的规则变体增加的分数倍率
+const MUTATOR_SCORE_BONUS: f32 = 0.25;
+
+// ============ 规则变体资源 ============
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MutatorKind {
+    NoRotation,      // 放置后不能旋转
+    LimitedRemovals, // 只能拆除3次
+    NoCurves,        // 禁用弯道
+}
+
+impl MutatorKind {
+    pub const ALL: [MutatorKind; 3] = [
+        MutatorKind::NoRotation,
+        MutatorKind::LimitedRemovals,
+        MutatorKind::NoCurves,
+    ];
+
+    fn label(&self) -> &'static LocalizedText {
+        match self {
+            MutatorKind::NoRotation => &MUTATOR_NO_ROTATION,
+            MutatorKind::LimitedRemovals => &MUTATOR_LIMITED_REMOVALS,
+            MutatorKind::NoCurves => &MUTATOR_NO_CURVES,
+        }
+    }
+}
+
+/// 开始关卡前在主菜单选择的规则变体
+#[derive(Resource, Default)]
+pub struct LevelMutators {
+    pub no_rotation: bool,
+    pub limited_removals: bool,
+    pub no_curves: bool,
+    pub removals_used: u32, // 本关已使用的拆除次数
+}
+
+impl LevelMutators {
+    pub fn is_enabled(&self, kind: MutatorKind) -> bool {
+        match kind {
+            MutatorKind::NoRotation => self.no_rotation,
+            MutatorKind::LimitedRemovals => self.limited_removals,
+            MutatorKind::NoCurves => self.no_curves,
+        }
+    }
+
+    pub fn toggle(&mut self, kind: MutatorKind) {
+        match kind {
+            MutatorKind::NoRotation => self.no_rotation = !self.no_rotation,
+            MutatorKind::LimitedRemovals => self.limited_removals = !self.limited_removals,
+            MutatorKind::NoCurves => self.no_curves = !self.no_curves,
+        }
+    }
+
+    pub fn score_multiplier(&self) -> f32 {
+        let enabled = MutatorKind::ALL
+            .iter()
+            .filter(|kind| self.is_enabled(**kind))
+            .count();
+        1.0 + enabled as f32 * MUTATOR_SCORE_BONUS
+    }
+
+    /// 已放置的路线段是否还能旋转
+    pub fn allows_rotation(&self) -> bool {
+        !self.no_rotation
+    }
+
+    /// 尝试消耗一次拆除次数，次数用完时返回 false
+    pub fn try_use_removal(&mut self) -> bool {
+        if !self.limited_removals {
+            return true;
+        }
+        if self.removals_used >= MUTATOR_MAX_REMOVALS {
+            return false;
+        }
+        self.removals_used += 1;
+        true
+    }
+
+    pub fn removals_left(&self) -> u32 {
+        MUTATOR_MAX_REMOVALS.saturating_sub(self.removals_used)
+    }
+}
+
+#[derive(Component)]
+pub struct MutatorToggleText {
+    pub kind: MutatorKind,
+}
+
+#[derive(Component)]
+pub struct MutatorMultiplierText;
+
+// ============ 规则变体插件 ============
+
+pub struct MutatorPlugin;
+
+impl Plugin for MutatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelMutators>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_mutator_panel)
+            .add_systems(
+                Update,
+                (handle_mutator_buttons, update_mutator_texts)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            );
+    }
+}
+
+/// 关卡加载后应用规则变体（在 load_current_level 之后执行）
+pub fn apply_level_mutators(
+    mut mutators: ResMut<LevelMutators>,
+    mut game_state: ResMut<GameState>,
+) {
+    mutators.removals_used = 0;
+
+    if mutators.no_curves {
+        game_state.player_inventory.remove(&RouteSegmentType::Curve);
+        if let Some(level_data) = &mut game_state.current_level {
+            level_data
+                .available_segments
+                .retain(|segment| segment.segment_type != RouteSegmentType::Curve);
+        }
+    }
+
+    if mutators.score_multiplier() > 1.0 {
+        info!(
+            "规则变体已启用: 禁止旋转={}, 限制拆除={}, 禁用弯道={}，分数倍率 ×{:.2}",
+            mutators.no_rotation,
+            mutators.limited_removals,
+            mutators.no_curves,
+            mutators.score_multiplier()
+        );
+    }
+}
+
+// ============ 主菜单规则变体面板 ============
+
+fn setup_mutator_panel(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    mutators: Res<LevelMutators>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let language = current_language.language;
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Px(20.0),
+                bottom: Px(20.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Px(6.0),
+                padding: UiRect::all(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+            MainMenuUI,
+            Name::new("Mutator Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&MUTATORS, language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+            ));
+
+            for kind in MutatorKind::ALL {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Px(200.0),
+                            height: Px(32.0),
+                            justify_content: JustifyContent::FlexStart,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::horizontal(Px(8.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                        ButtonComponent {
+                            button_type: ButtonType::ToggleMutator(kind),
+                            is_hovered: false,
+                            is_pressed: false,
+                        },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(mutator_toggle_label(&mutators, kind, language)),
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            MutatorToggleText { kind },
+                        ));
+                    });
+            }
+
+            parent.spawn((
+                Text::new(multiplier_label(&mutators, language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.2, 0.8, 0.2)),
+                MutatorMultiplierText,
+            ));
+        });
+}
+
+fn mutator_toggle_label(mutators: &LevelMutators, kind: MutatorKind, language: Language) -> String {
+    let check = if mutators.is_enabled(kind) {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    format!("{} {}", check, get_text(kind.label(), language))
+}
+
+fn multiplier_label(mutators: &LevelMutators, language: Language) -> String {
+    get_text_with_args(
+        &SCORE_MULTIPLIER,
+        language,
+        &[&format!("{:.2}", mutators.score_multiplier())],
+    )
+}
+
+fn handle_mutator_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut mutators: ResMut<LevelMutators>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        if let ButtonType::ToggleMutator(kind) = button_component.button_type {
+            mutators.toggle(kind);
+            info!("规则变体 {:?}: {}", kind, mutators.is_enabled(kind));
+        }
+    }
+}
+
+fn update_mutator_texts(
+    mutators: Res<LevelMutators>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<(&MutatorToggleText, &mut Text), Without<MutatorMultiplierText>>,
+    mut multiplier_texts: Query<&mut Text, With<MutatorMultiplierText>>,
+) {
+    if !mutators.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for (toggle, mut text) in toggle_texts.iter_mut() {
+        *text = Text::new(mutator_toggle_label(
+            &mutators,
+            toggle.kind,
+            current_language.language,
+        ));
+    }
+
+    for mut text in multiplier_texts.iter_mut() {
+        *text = Text::new(multiplier_label(&mutators, current_language.language));
+    }
+}
@@ -0,0 +1,252 @@
+// src/bus_puzzle/network_retry.rs - 保留路网重试（失败后重新开始同一关，并按库存重建失败时已放置的路线段）
+
+use crate::bus_puzzle::{
+    apply_game_mode, get_text_with_args, is_valid_placement_on_layer, reset_economy,
+    reset_route_lines, show_contextual_tip, spawn_route_segment, ButtonComponent, ButtonType,
+    CurrentLanguage, EconomyMode, ExpressSegment, GameState, GameStateEnum, GridPos, LevelManager,
+    NetworkLayer, PlacedSegment, RouteLines, RouteSegmentType, ThemeRegistry, TipType, UIAssets,
+    NETWORK_RESTORED, NETWORK_RESTORE_SKIPPED,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ============ 路网快照 ============
+
+/// 一个路线段的纯数据形式，不含实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSegment {
+    pub layer: NetworkLayer,
+    pub position: GridPos,
+    pub segment_type: RouteSegmentType,
+    pub rotation: u32,
+    pub cost: u32, // 含快速路线升级的花费
+    pub express: bool,
+    pub line: u32,
+}
+
+/// 失败时的路网：两个图层上的路线段及其线路
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkSnapshot {
+    pub level_id: String,
+    pub segments: Vec<SnapshotSegment>,
+}
+
+impl NetworkSnapshot {
+    fn capture(game_state: &GameState, route_lines: &RouteLines) -> Option<Self> {
+        let level_data = game_state.current_level.as_ref()?;
+
+        let mut segments = Vec::new();
+        for layer in [NetworkLayer::Ground, NetworkLayer::Elevated] {
+            let mut layer_segments: Vec<_> = game_state
+                .segments_on(layer)
+                .iter()
+                .map(|(pos, placed)| SnapshotSegment {
+                    layer,
+                    position: *pos,
+                    segment_type: placed.segment_type,
+                    rotation: placed.rotation,
+                    cost: placed.cost,
+                    express: placed.express,
+                    line: route_lines.line_of(*pos),
+                })
+                .collect();
+            // 库存不够时按固定顺序取舍，每次重试的结果一致
+            layer_segments.sort_by_key(|segment| (segment.position.x, segment.position.y));
+            segments.extend(layer_segments);
+        }
+
+        Some(Self {
+            level_id: level_data.id.clone(),
+            segments,
+        })
+    }
+}
+
+/// 等待在下一次关卡加载时重建的路网，以及上次重建的结果（已恢复数、跳过数）
+#[derive(Resource, Default)]
+pub struct NetworkRetry {
+    pending: Option<NetworkSnapshot>,
+    report: Option<(usize, usize)>,
+}
+
+// ============ 保留路网重试插件 ============
+
+pub struct NetworkRetryPlugin;
+
+impl Plugin for NetworkRetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetworkRetry>()
+            .add_systems(
+                Update,
+                handle_retry_with_network.run_if(in_state(GameStateEnum::GameOver)),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::Loading),
+                restore_network_snapshot
+                    .after(apply_game_mode)
+                    .after(reset_route_lines)
+                    .after(reset_economy),
+            )
+            .add_systems(OnEnter(GameStateEnum::Playing), show_restore_report)
+            .add_systems(OnEnter(GameStateEnum::MainMenu), discard_network_snapshot);
+    }
+}
+
+/// 点击按钮时记录失败时的路网，然后像普通重试一样重新加载关卡
+fn handle_retry_with_network(
+    button_query: Query<&ButtonComponent, (Changed<ButtonComponent>, With<Button>)>,
+    game_state: Res<GameState>,
+    route_lines: Res<RouteLines>,
+    mut retry: ResMut<NetworkRetry>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    let pressed = button_query.iter().any(|button| {
+        button.is_pressed && matches!(button.button_type, ButtonType::RetryKeepingNetwork)
+    });
+    if !pressed {
+        return;
+    }
+
+    retry.pending = NetworkSnapshot::capture(&game_state, &route_lines);
+    info!(
+        "🔁 保留路网重试: {} 个路线段",
+        retry
+            .pending
+            .as_ref()
+            .map_or(0, |snapshot| snapshot.segments.len())
+    );
+    next_state.set(GameStateEnum::Loading);
+}
+
+fn discard_network_snapshot(mut retry: ResMut<NetworkRetry>) {
+    retry.pending = None;
+    retry.report = None;
+}
+
+/// 在关卡重置之后执行：逐个重建路线段并像手动放置一样扣减库存、计入花费。
+/// 库存或资金不够、位置被封路或站点占用的路线段跳过
+fn restore_network_snapshot(
+    mut commands: Commands,
+    mut retry: ResMut<NetworkRetry>,
+    mut game_state: ResMut<GameState>,
+    mut level_manager: ResMut<LevelManager>,
+    mut route_lines: ResMut<RouteLines>,
+    economy: Res<EconomyMode>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
+    let Some(snapshot) = retry.pending.take() else {
+        return;
+    };
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    if level_data.id != snapshot.level_id {
+        info!("路网快照属于其他关卡，跳过恢复");
+        return;
+    }
+
+    // 路线段坐标依赖 LevelManager 中的网格尺寸，这里提前同步（sync_level_data 要到 Update 才运行）
+    level_manager.current_level = Some(level_data.clone());
+
+    let mut restored = 0;
+    for segment in &snapshot.segments {
+        let is_station = game_state.current_level.as_ref().is_some_and(|level_data| {
+            level_data
+                .stations
+                .iter()
+                .any(|station| station.position == segment.position)
+        });
+        let in_stock = if economy.enabled {
+            // 经济模式下库存每帧按余额重新计算，这里直接检查资金
+            economy.can_afford(&game_state, segment.cost)
+        } else {
+            game_state
+                .player_inventory
+                .get(&segment.segment_type)
+                .is_some_and(|count| *count > 0)
+        };
+        if is_station
+            || !in_stock
+            || !is_valid_placement_on_layer(
+                &game_state,
+                segment.position,
+                &segment.segment_type,
+                segment.layer,
+            )
+        {
+            continue;
+        }
+
+        let entity = spawn_route_segment(
+            &mut commands,
+            &asset_server,
+            &theme,
+            segment.position,
+            segment.segment_type,
+            segment.rotation,
+            &level_manager,
+            segment.layer,
+        );
+        if segment.express {
+            commands.entity(entity).insert(ExpressSegment);
+        }
+        game_state.segments_on_mut(segment.layer).insert(
+            segment.position,
+            PlacedSegment {
+                segment_type: segment.segment_type,
+                rotation: segment.rotation,
+                entity,
+                cost: segment.cost,
+                express: segment.express,
+            },
+        );
+        if segment.layer == NetworkLayer::Ground {
+            route_lines
+                .assignments
+                .insert(segment.position, segment.line);
+        }
+
+        game_state.total_cost += segment.cost;
+        if let Some(count) = game_state.player_inventory.get_mut(&segment.segment_type) {
+            *count = count.saturating_sub(1);
+        }
+        restored += 1;
+    }
+
+    // 修订号变化后按重建的线路生成公交车；寻路图下一帧根据 RouteSegment 重建
+    route_lines.revision += 1;
+
+    let skipped = snapshot.segments.len() - restored;
+    retry.report = Some((restored, skipped));
+    info!("🔁 已重建路网: {} 个路线段, 跳过 {} 个", restored, skipped);
+}
+
+fn show_restore_report(
+    mut commands: Commands,
+    mut retry: ResMut<NetworkRetry>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let Some((restored, skipped)) = retry.report.take() else {
+        return;
+    };
+
+    let language = current_language.language;
+    let (text, tip_type) = if skipped > 0 {
+        (
+            get_text_with_args(
+                &NETWORK_RESTORE_SKIPPED,
+                language,
+                &[&restored.to_string(), &skipped.to_string()],
+            ),
+            TipType::Warning,
+        )
+    } else {
+        (
+            get_text_with_args(&NETWORK_RESTORED, language, &[&restored.to_string()]),
+            TipType::Controls,
+        )
+    };
+    show_contextual_tip(&mut commands, &ui_assets, &text, tip_type, 4.0);
+}
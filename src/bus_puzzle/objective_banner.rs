@@ -0,0 +1,212 @@
+// src/bus_puzzle/objective_banner.rs - 目标完成横幅（滑入显示完成的目标描述，带打勾动画）
+
+use crate::bus_puzzle::{
+    get_text, CurrentLanguage, GameState, GameStateEnum, MirroredLayout, ObjectiveCompletedEvent,
+    UIAssets, OBJECTIVE_COMPLETED_ANNOUNCEMENT,
+};
+use bevy::prelude::{Val::Px, *};
+
+/// 横幅各阶段时长（秒）
+const SLIDE_IN_SECS: f32 = 0.35;
+const HOLD_SECS: f32 = 2.5;
+const SLIDE_OUT_SECS: f32 = 0.35;
+
+/// 打勾动画在滑入结束后开始，持续时长
+const CHECK_POP_SECS: f32 = 0.3;
+
+/// 横幅尺寸与位置：停在屏幕右侧，多个横幅同时出现时向下堆叠
+const BANNER_WIDTH: f32 = 320.0;
+const BANNER_RIGHT: f32 = 20.0;
+const BANNER_TOP: f32 = 90.0;
+const BANNER_SPACING: f32 = 70.0;
+
+const BANNER_COLOR: Color = Color::srgb(0.2, 0.8, 0.3);
+
+/// 目标完成横幅
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct ObjectiveBanner {
+    elapsed: f32,
+    slot: usize,
+}
+
+/// 横幅左侧的对勾，滑入后弹出放大
+#[derive(Component)]
+pub struct ObjectiveBannerCheck;
+
+// ============ 目标完成横幅插件 ============
+
+pub struct ObjectiveBannerPlugin;
+
+impl Plugin for ObjectiveBannerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_objective_banners, animate_objective_banners)
+                .chain()
+                .run_if(in_state(GameStateEnum::Playing)),
+        );
+    }
+}
+
+fn spawn_objective_banners(
+    mut commands: Commands,
+    mut objective_events: EventReader<ObjectiveCompletedEvent>,
+    game_state: Res<GameState>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    banners: Query<&ObjectiveBanner>,
+) {
+    let Some(level_data) = &game_state.current_level else {
+        objective_events.clear();
+        return;
+    };
+
+    let language = current_language.language;
+    let mut used_slots: Vec<usize> = banners.iter().map(|banner| banner.slot).collect();
+
+    for event in objective_events.read() {
+        let Some(objective) = level_data.objectives.get(event.objective_index) else {
+            continue;
+        };
+
+        // 取第一个空闲的位置，避免同一帧完成多个目标时横幅重叠
+        let slot = (0..).find(|slot| !used_slots.contains(slot)).unwrap_or(0);
+        used_slots.push(slot);
+
+        spawn_banner(
+            &mut commands,
+            &ui_assets,
+            &get_text(&OBJECTIVE_COMPLETED_ANNOUNCEMENT, language),
+            &objective.get_localized_description(language),
+            slot,
+        );
+        info!("🏁 目标完成横幅: {}", objective.description);
+    }
+}
+
+fn spawn_banner(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    title: &str,
+    description: &str,
+    slot: usize,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(-BANNER_WIDTH),
+                top: Px(BANNER_TOP + slot as f32 * BANNER_SPACING),
+                width: Px(BANNER_WIDTH),
+                padding: UiRect::all(Px(10.0)),
+                column_gap: Px(10.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Outline::new(Px(2.0), Val::ZERO, BANNER_COLOR),
+            ZIndex(250),
+            ObjectiveBanner { elapsed: 0.0, slot },
+            Name::new("Objective Banner"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("✔"),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(BANNER_COLOR),
+                Transform::from_scale(Vec3::ZERO),
+                ObjectiveBannerCheck,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    flex_shrink: 1.0,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(title),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(BANNER_COLOR),
+                    ));
+                    parent.spawn((
+                        Text::new(description),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+// ============ 横幅动画 ============
+
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// 带回弹的放大曲线，末尾略微超过 1 再回落
+fn ease_out_back(t: f32) -> f32 {
+    const OVERSHOOT: f32 = 1.70158;
+    let t = t - 1.0;
+    1.0 + (OVERSHOOT + 1.0) * t.powi(3) + OVERSHOOT * t.powi(2)
+}
+
+fn animate_objective_banners(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut banners: Query<(
+        Entity,
+        &mut ObjectiveBanner,
+        &mut Node,
+        &Children,
+        Has<MirroredLayout>,
+    )>,
+    mut checks: Query<&mut Transform, With<ObjectiveBannerCheck>>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut banner, mut node, children, mirrored) in banners.iter_mut() {
+        banner.elapsed += dt;
+        let elapsed = banner.elapsed;
+
+        // 滑入 → 停留 → 滑出
+        let shown = if elapsed < SLIDE_IN_SECS {
+            ease_out_cubic(elapsed / SLIDE_IN_SECS)
+        } else if elapsed < SLIDE_IN_SECS + HOLD_SECS {
+            1.0
+        } else if elapsed < SLIDE_IN_SECS + HOLD_SECS + SLIDE_OUT_SECS {
+            1.0 - ease_out_cubic((elapsed - SLIDE_IN_SECS - HOLD_SECS) / SLIDE_OUT_SECS)
+        } else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let offset = Px(-BANNER_WIDTH + (BANNER_WIDTH + BANNER_RIGHT) * shown);
+        // 从右往左的语言下横幅已镜像，改从左侧滑入
+        if mirrored {
+            node.left = offset;
+        } else {
+            node.right = offset;
+        }
+
+        let check_progress = ((elapsed - SLIDE_IN_SECS) / CHECK_POP_SECS).clamp(0.0, 1.0);
+        for child in children.iter() {
+            if let Ok(mut transform) = checks.get_mut(child) {
+                transform.scale = Vec3::splat(ease_out_back(check_progress));
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 // src/bus_puzzle/passenger_boarding_system.rs - 乘客上下车系统
 
 use crate::bus_puzzle::{
-    AgentState, BusPathfindingAgent, BusPathfindingState, BusVehicle, GameStateEnum, LevelManager,
-    PathfindingAgent, StationEntity, PASSENGER_Z,
+    simulation_running, ActiveWeather, AgentState, BusPathfindingAgent, BusPathfindingState,
+    BusVehicle, LevelManager, PathfindingAgent, PatienceBar, PatienceBarFill, SimClock,
+    StationEntity, StationOverloads, ViaIcon, PASSENGER_PATIENCE_BAR_THRESHOLD,
+    PASSENGER_PATIENCE_BAR_WIDTH, PASSENGER_Z,
 };
 use bevy::prelude::*;
 
@@ -35,10 +37,11 @@ impl Plugin for PassengerBoardingPlugin {
                 handle_passenger_boarding,
                 handle_passenger_alighting,
                 update_passengers_on_bus,
+                update_patience_bars,
                 debug_passenger_boarding,
             )
                 .chain()
-                .run_if(in_state(GameStateEnum::Playing)),
+                .run_if(simulation_running),
         );
     }
 }
@@ -79,19 +82,22 @@ fn update_waiting_passengers(
                 // 将乘客直接移动到起点站（简化处理）
                 passenger_transform.translation = station_world_pos + Vec3::Z * PASSENGER_Z;
 
-                // 检查是否有合适的公交车
-                let has_suitable_bus = check_suitable_bus(&agent.destination, &buses);
+                // 检查是否有合适的公交车（多段行程先前往中转站）
+                let has_suitable_bus = check_suitable_bus(agent.next_stop(), &buses);
 
                 // 给乘客添加等车组件
                 commands.entity(entity).insert(WaitingForBus {
-                    target_station: agent.destination.clone(),
+                    target_station: agent.next_stop().to_string(),
                     wait_time: 0.0,
                     has_suitable_bus,
                 });
 
                 info!(
                     "乘客 {:?} 在 {} 等车前往 {} (有合适公交车: {})",
-                    agent.color, agent.origin, agent.destination, has_suitable_bus
+                    agent.color,
+                    agent.origin,
+                    agent.next_stop(),
+                    has_suitable_bus
                 );
             } else {
                 warn!("找不到起点站: {}", agent.origin);
@@ -136,22 +142,35 @@ fn handle_passenger_boarding(
         &mut BusPathfindingAgent,
         &Transform,
     )>,
-    time: Res<Time>,
+    stations: Query<&StationEntity>,
+    overloads: Res<StationOverloads>,
+    weather: Res<ActiveWeather>,
+    sim_clock: Res<SimClock>,
 ) {
-    let dt = time.delta_secs();
+    let dt = sim_clock.delta_secs();
 
     for (passenger_entity, mut waiting, mut agent, passenger_transform) in
         waiting_passengers.iter_mut()
     {
         waiting.wait_time += dt;
 
-        // 减缓等车时的耐心消耗
-        agent.patience -= dt * 0.02; // 进一步减少耐心消耗
+        // 减缓等车时的耐心消耗（候车亭更慢，超员和恶劣天气更快）
+        let drain_multiplier = stations
+            .iter()
+            .find(|station| station.station_data.name == agent.origin)
+            .map_or(1.0, |station| {
+                station.patience_drain_multiplier()
+                    * weather.patience_drain_multiplier(station.station_data.position)
+            });
+        agent.patience -= dt * 0.02 * drain_multiplier; // 进一步减少耐心消耗
+
+        // 过载的站点恢复前无法上车，乘客只能继续等待
+        let station_overloaded = overloads.is_overloaded(&agent.origin);
 
         // 检查附近是否有合适的公交车到站
         for (bus_entity, mut bus_vehicle, bus_agent, bus_transform) in buses.iter_mut() {
             // 检查公交车是否在站点停靠
-            if bus_agent.state != BusPathfindingState::AtStation {
+            if station_overloaded || bus_agent.state != BusPathfindingState::AtStation {
                 continue;
             }
 
@@ -172,8 +191,10 @@ fn handle_passenger_boarding(
                 continue;
             }
 
-            // 检查公交车是否还有座位
-            if bus_vehicle.current_passengers.len() >= bus_vehicle.capacity as usize {
+            // 检查公交车是否还有座位，成组乘客需要整组都能坐下
+            if bus_vehicle.current_passengers.len() + agent.group_size as usize
+                > bus_vehicle.capacity as usize
+            {
                 if waiting.wait_time % 5.0 < dt {
                     // 每5秒提示一次，避免日志过多
                     info!(
@@ -187,9 +208,19 @@ fn handle_passenger_boarding(
                 continue;
             }
 
-            // 乘客上车！
-            bus_vehicle.current_passengers.push(passenger_entity);
+            // 乘客上车！成组乘客每人占一个座位（同一实体按人数记录），下车时一并移除
+            // 记录所乘线路，换乘次数按线路变化统计
+            bus_vehicle.current_passengers.extend(std::iter::repeat_n(
+                passenger_entity,
+                agent.group_size as usize,
+            ));
             agent.state = AgentState::Traveling;
+            agent.lines_ridden.push(
+                bus_agent
+                    .line_id
+                    .clone()
+                    .unwrap_or_else(|| bus_agent.route_id.clone()),
+            );
 
             info!(
                 "🚌 乘客 {:?} 上车成功！车辆: {} 目的地: {} 载客: {}/{}",
@@ -205,7 +236,7 @@ fn handle_passenger_boarding(
             commands.entity(passenger_entity).insert(OnBus {
                 bus_entity,
                 target_station: waiting.target_station.clone(),
-                boarding_time: time.elapsed_secs(),
+                boarding_time: sim_clock.elapsed_secs(),
             });
 
             break;
@@ -234,6 +265,7 @@ fn handle_passenger_alighting(
     mut buses: Query<(Entity, &mut BusVehicle, &BusPathfindingAgent, &Transform), With<BusVehicle>>,
     level_manager: Res<LevelManager>,
     stations: Query<&StationEntity>,
+    via_icons: Query<(Entity, &ChildOf), With<ViaIcon>>,
 ) {
     for (passenger_entity, on_bus, mut agent, mut passenger_transform) in
         passengers_on_bus.iter_mut()
@@ -252,6 +284,32 @@ fn handle_passenger_alighting(
                 // 获取当前站点名称
                 let current_station_name = &bus_agent.target_station;
 
+                // 多段行程：在中转站下车，重新排队等待前往终点的公交车
+                if current_station_name == &on_bus.target_station
+                    && agent.via.as_ref() == Some(current_station_name)
+                {
+                    bus_vehicle
+                        .current_passengers
+                        .retain(|&id| id != passenger_entity);
+
+                    agent.origin = current_station_name.clone();
+                    agent.via = None;
+                    agent.state = AgentState::WaitingAtStation;
+                    commands.entity(passenger_entity).remove::<OnBus>();
+
+                    for (icon, child_of) in via_icons.iter() {
+                        if child_of.parent() == passenger_entity {
+                            commands.entity(icon).despawn();
+                        }
+                    }
+
+                    info!(
+                        "🔁 乘客 {:?} 在 {} 中转，继续前往 {}",
+                        agent.color, current_station_name, agent.destination
+                    );
+                    continue;
+                }
+
                 // 如果当前站点是乘客的目的地
                 if current_station_name == &on_bus.target_station {
                     // 乘客下车！
@@ -315,17 +373,17 @@ fn handle_passenger_alighting(
 
 fn update_passengers_on_bus(
     passengers_on_bus: Query<(&OnBus, &PathfindingAgent)>,
-    time: Res<Time>,
+    sim_clock: Res<SimClock>,
 ) {
     // 每10秒更新一次乘车统计（避免日志过多）
-    if time.elapsed_secs() % 10.0 < 0.1 {
+    if sim_clock.elapsed_secs() % 10.0 < 0.1 {
         let total_riding = passengers_on_bus.iter().count();
         if total_riding > 0 {
             trace!("当前乘车乘客数: {}", total_riding);
 
             // 可以添加更详细的统计
             for (on_bus, agent) in passengers_on_bus.iter() {
-                let travel_time = time.elapsed_secs() - on_bus.boarding_time;
+                let travel_time = sim_clock.elapsed_secs() - on_bus.boarding_time;
                 trace!(
                     "乘客 {:?} 乘车 {:.1}s 前往 {}",
                     agent.color,
@@ -337,6 +395,55 @@ fn update_passengers_on_bus(
     }
 }
 
+// ============ 耐心条 ============
+
+/// 等车乘客耐心低于阈值时显示头顶的耐心条：从满格开始缩短，颜色由黄转红
+fn update_patience_bars(
+    passengers: Query<(&PathfindingAgent, &Children, Has<OnBus>)>,
+    mut bars: Query<(&mut Visibility, &Children), With<PatienceBar>>,
+    mut fills: Query<(&mut Transform, &mut Sprite), With<PatienceBarFill>>,
+) {
+    for (agent, children, on_bus) in passengers.iter() {
+        let ratio = if agent.max_patience > 0.0 {
+            (agent.patience / agent.max_patience).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let waiting = !on_bus
+            && matches!(
+                agent.state,
+                AgentState::WaitingAtStation | AgentState::Transferring
+            );
+        let shown = waiting && ratio < PASSENGER_PATIENCE_BAR_THRESHOLD;
+
+        for child in children.iter() {
+            let Ok((mut visibility, bar_children)) = bars.get_mut(child) else {
+                continue;
+            };
+            visibility.set_if_neq(if shown {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            if !shown {
+                continue;
+            }
+
+            // 填充部分靠左对齐缩短
+            let fill_ratio = ratio / PASSENGER_PATIENCE_BAR_THRESHOLD;
+            let color = Color::srgb(1.0, 0.2, 0.15).mix(&Color::srgb(1.0, 0.85, 0.2), fill_ratio);
+            for fill in bar_children.iter() {
+                if let Ok((mut transform, mut sprite)) = fills.get_mut(fill) {
+                    transform.scale.x = fill_ratio;
+                    transform.translation.x =
+                        -PASSENGER_PATIENCE_BAR_WIDTH * (1.0 - fill_ratio) / 2.0;
+                    sprite.color = color;
+                }
+            }
+        }
+    }
+}
+
 // ============ 调试系统 ============
 
 fn debug_passenger_boarding(
@@ -59,13 +59,15 @@ fn debug_passenger_states(
     }
 }
 
-// F11 - 调试寻路图状态
+// Shift+F11 - 调试寻路图状态（单独的 F11 用于切换全屏）
 fn debug_pathfinding_status(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     pathfinding_graph: Res<PathfindingGraph>,
     passengers: Query<&PathfindingAgent>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::F11) {
+    if keyboard_input.just_pressed(KeyCode::F11)
+        && keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+    {
         info!("=== 寻路系统状态 ===");
         info!("寻路图节点数: {}", pathfinding_graph.nodes.len());
         info!("寻路图连接数: {}", pathfinding_graph.connections.len());
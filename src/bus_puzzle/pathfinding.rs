@@ -8,9 +8,11 @@ use std::{
 };
 
 use super::{
-    get_neighbors, AgentState, Connection, ConnectionType, GameState, GameStateEnum, GraphNode,
-    GraphNodeType, GridPos, LevelManager, PathfindingAgent, PathfindingGraph, RouteSegment,
-    RouteSegmentType, StationEntity,
+    get_neighbors, line_id, simulation_running, ActiveWeather, AgentLod, AgentState, Connection,
+    ConnectionType, ElevatedSegment, ExpressSegment, GameState, GraphNode, GraphNodeType, GridPos,
+    LevelManager, NetworkLayer, PassengerArrivedEvent, PassengerGaveUpEvent, PathfindingAgent,
+    PathfindingGraph, RouteLines, RouteSegment, RouteSegmentType, SimClock, Station, StationEntity,
+    EXPRESS_CONNECTION_COST, EXPRESS_LINE,
 };
 
 // ============ 寻路相关组件 ============
@@ -21,6 +23,8 @@ pub struct PathNode {
     pub node_type: PathNodeType,
     pub estimated_wait_time: f32,
     pub route_id: Option<String>,
+    #[serde(default)]
+    pub layer: NetworkLayer,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -32,26 +36,31 @@ pub enum PathNodeType {
 
 // ============ A* 寻路算法节点 ============
 
+/// 寻路图中的节点：格子坐标 + 图层（地面和高架可以在同一格）
+type NodeKey = (GridPos, NetworkLayer);
+
 #[derive(Debug, Clone, PartialEq)]
 struct AStarNode {
     position: GridPos,
+    layer: NetworkLayer,
     g_cost: f32,
     h_cost: f32,
     f_cost: f32,
-    parent: Option<GridPos>,
+    parent: Option<NodeKey>,
     route_changes: u32,
 }
 
 impl AStarNode {
     fn new(
-        position: GridPos,
+        (position, layer): NodeKey,
         g_cost: f32,
         h_cost: f32,
-        parent: Option<GridPos>,
+        parent: Option<NodeKey>,
         route_changes: u32,
     ) -> Self {
         Self {
             position,
+            layer,
             g_cost,
             h_cost,
             f_cost: g_cost + h_cost,
@@ -59,6 +68,10 @@ impl AStarNode {
             route_changes,
         }
     }
+
+    fn key(&self) -> NodeKey {
+        (self.position, self.layer)
+    }
 }
 
 impl Eq for AStarNode {}
@@ -96,7 +109,7 @@ impl Plugin for PathfindingPlugin {
                     cleanup_finished_passengers,
                 )
                     .chain()
-                    .run_if(in_state(GameStateEnum::Playing)),
+                    .run_if(simulation_running),
             );
     }
 }
@@ -105,62 +118,26 @@ impl Plugin for PathfindingPlugin {
 
 fn update_pathfinding_graph(
     mut pathfinding_graph: ResMut<PathfindingGraph>,
-    route_segments: Query<&RouteSegment>,
+    route_segments: Query<(&RouteSegment, Has<ExpressSegment>), Without<ElevatedSegment>>,
+    elevated_segments: Query<&RouteSegment, With<ElevatedSegment>>,
     stations: Query<&StationEntity>,
+    route_lines: Res<RouteLines>,
+    weather: Res<ActiveWeather>,
     keyboard_input: Res<ButtonInput<KeyCode>>, // 添加键盘输入以便调试
 ) {
-    // 简化：每次都重建整个图
-    pathfinding_graph.connections.clear();
-    pathfinding_graph.nodes.clear();
-    pathfinding_graph.station_lookup.clear();
-
-    // 添加站点节点
-    for station_entity in stations.iter() {
-        let station = &station_entity.station_data;
-        let pos = station.position;
-
-        pathfinding_graph.nodes.insert(
-            pos,
-            GraphNode {
-                position: pos,
-                node_type: GraphNodeType::Station,
-                station_name: Some(station.name.clone()),
-                is_accessible: true,
-            },
-        );
-
-        pathfinding_graph
-            .station_lookup
-            .insert(station.name.clone(), pos);
-    }
+    let _span = info_span!("update_pathfinding_graph").entered();
 
-    // 添加路线段节点
-    let mut route_segments_by_pos = HashMap::new();
-    for segment in route_segments.iter() {
-        if segment.is_active {
-            let pos = segment.grid_pos;
-            route_segments_by_pos.insert(pos, segment);
-
-            pathfinding_graph.nodes.insert(
-                pos,
-                GraphNode {
-                    position: pos,
-                    node_type: match segment.segment_type {
-                        RouteSegmentType::Cross | RouteSegmentType::TSplit => {
-                            GraphNodeType::Intersection
-                        }
-                        _ => GraphNodeType::RouteSegment,
-                    },
-                    station_name: None,
-                    is_accessible: true,
-                },
-            );
-        }
-    }
-
-    // 建立连接关系
-    create_route_connections_improved(&mut pathfinding_graph, &route_segments_by_pos);
-    create_station_connections_improved(&mut pathfinding_graph, &route_segments_by_pos);
+    // 简化：每次都重建整个图
+    let route_segments_by_pos = build_pathfinding_graph(
+        &mut pathfinding_graph,
+        stations
+            .iter()
+            .map(|station_entity| &station_entity.station_data),
+        route_segments.iter(),
+        elevated_segments.iter(),
+        &route_lines,
+    );
+    apply_weather_connection_costs(&mut pathfinding_graph, &weather);
 
     // 按F8显示详细的连接调试信息
     if keyboard_input.just_pressed(KeyCode::F8) {
@@ -245,9 +222,148 @@ fn update_pathfinding_graph(
     }
 }
 
+/// 用站点和激活的路线段重建寻路图，返回按位置索引的地面路线段。
+/// 放置预览也用它在临时图上试算乘客路径
+pub fn build_pathfinding_graph<'a>(
+    pathfinding_graph: &mut PathfindingGraph,
+    stations: impl Iterator<Item = &'a Station>,
+    route_segments: impl Iterator<Item = (&'a RouteSegment, bool)>,
+    elevated_segments: impl Iterator<Item = &'a RouteSegment>,
+    route_lines: &RouteLines,
+) -> HashMap<GridPos, &'a RouteSegment> {
+    pathfinding_graph.connections.clear();
+    pathfinding_graph.nodes.clear();
+    pathfinding_graph.elevated_connections.clear();
+    pathfinding_graph.elevated_nodes.clear();
+    pathfinding_graph.station_lookup.clear();
+
+    // 添加站点节点
+    for station in stations {
+        let pos = station.position;
+
+        pathfinding_graph.nodes.insert(
+            pos,
+            GraphNode {
+                position: pos,
+                node_type: GraphNodeType::Station,
+                station_name: Some(station.name.clone()),
+                is_accessible: true,
+            },
+        );
+
+        pathfinding_graph
+            .station_lookup
+            .insert(station.name.clone(), pos);
+    }
+
+    // 添加路线段节点
+    let mut route_segments_by_pos = HashMap::new();
+    let mut express_positions = HashSet::new();
+    for (segment, is_express) in route_segments {
+        if segment.is_active {
+            let pos = segment.grid_pos;
+            route_segments_by_pos.insert(pos, segment);
+            if is_express {
+                express_positions.insert(pos);
+            }
+
+            pathfinding_graph.nodes.insert(
+                pos,
+                GraphNode {
+                    position: pos,
+                    node_type: match segment.segment_type {
+                        RouteSegmentType::Cross | RouteSegmentType::TSplit => {
+                            GraphNodeType::Intersection
+                        }
+                        _ => GraphNodeType::RouteSegment,
+                    },
+                    station_name: None,
+                    is_accessible: true,
+                },
+            );
+        }
+    }
+
+    // 添加高架层节点
+    let mut elevated_by_pos = HashMap::new();
+    for segment in elevated_segments.filter(|segment| segment.is_active) {
+        elevated_by_pos.insert(segment.grid_pos, segment);
+        pathfinding_graph.elevated_nodes.insert(
+            segment.grid_pos,
+            GraphNode {
+                position: segment.grid_pos,
+                node_type: GraphNodeType::RouteSegment,
+                station_name: None,
+                is_accessible: true,
+            },
+        );
+    }
+
+    // 建立连接关系
+    create_route_connections_improved(pathfinding_graph, &route_segments_by_pos, route_lines);
+    create_station_connections_improved(pathfinding_graph, &route_segments_by_pos, route_lines);
+    apply_express_connection_costs(pathfinding_graph, &express_positions);
+    create_elevated_connections(pathfinding_graph, &elevated_by_pos);
+
+    route_segments_by_pos
+}
+
+/// 高架路线段之间只按端口相连，不与地面路线段形成路口，全部属于高架快线；
+/// 朝向站点的端口连到地面层的站点，站点是两层之间唯一的换乘点
+fn create_elevated_connections(
+    pathfinding_graph: &mut PathfindingGraph,
+    elevated_by_pos: &HashMap<GridPos, &RouteSegment>,
+) {
+    let route_id = Some(line_id(EXPRESS_LINE));
+    let station_positions: HashSet<GridPos> =
+        pathfinding_graph.station_lookup.values().copied().collect();
+
+    for (pos, segment) in elevated_by_pos {
+        let from = (*pos, NetworkLayer::Elevated);
+
+        for connection_pos in segment
+            .segment_type
+            .get_connection_positions(*pos, segment.rotation)
+        {
+            if elevated_by_pos.get(&connection_pos).is_some_and(|target| {
+                target
+                    .segment_type
+                    .has_connection_to(connection_pos, *pos, target.rotation)
+            }) {
+                // 对面的路线段遍历时会建立反向连接
+                add_layer_connection(
+                    pathfinding_graph,
+                    from,
+                    (connection_pos, NetworkLayer::Elevated),
+                    ConnectionType::BusRoute,
+                    route_id.clone(),
+                );
+            } else if station_positions.contains(&connection_pos) {
+                let station = (connection_pos, NetworkLayer::Ground);
+                add_layer_connection(
+                    pathfinding_graph,
+                    from,
+                    station,
+                    ConnectionType::BusRoute,
+                    route_id.clone(),
+                );
+                add_layer_connection(
+                    pathfinding_graph,
+                    station,
+                    from,
+                    ConnectionType::Walk,
+                    route_id.clone(),
+                );
+            }
+        }
+    }
+}
+
+/// 同一线路的相邻路线段之间是公交连接；不同线路相接处是换乘连接，不属于任何线路
 fn create_route_connections_improved(
     pathfinding_graph: &mut PathfindingGraph,
     route_segments_by_pos: &HashMap<GridPos, &RouteSegment>,
+    route_lines: &RouteLines,
 ) {
     trace!(
         "开始创建路线段连接，共 {} 个路线段",
@@ -274,19 +390,29 @@ fn create_route_connections_improved(
                     *pos,
                     target_segment.rotation,
                 ) {
+                    let line = route_lines.line_of(*pos);
+                    let (connection_type, route_id) = if route_lines.line_of(connection_pos) == line
+                    {
+                        (ConnectionType::BusRoute, Some(line_id(line)))
+                    } else {
+                        (ConnectionType::Transfer, None)
+                    };
+
                     // 创建双向连接
                     add_connection_if_not_exists(
                         pathfinding_graph,
                         *pos,
                         connection_pos,
-                        ConnectionType::BusRoute,
+                        connection_type.clone(),
+                        route_id.clone(),
                     );
 
                     add_connection_if_not_exists(
                         pathfinding_graph,
                         connection_pos,
                         *pos,
-                        ConnectionType::BusRoute,
+                        connection_type,
+                        route_id,
                     );
 
                     trace!("双向连接建立: {:?} <-> {:?}", pos, connection_pos);
@@ -308,6 +434,7 @@ fn create_route_connections_improved(
                     *pos,
                     connection_pos,
                     ConnectionType::BusRoute,
+                    Some(line_id(route_lines.line_of(*pos))),
                 );
 
                 trace!("连接到站点: {:?} -> {:?}", pos, connection_pos);
@@ -316,27 +443,96 @@ fn create_route_connections_improved(
     }
 }
 
+/// 驶入快速路线的公交连接成本更低，寻路会优先经过升级后的路段
+fn apply_express_connection_costs(
+    pathfinding_graph: &mut PathfindingGraph,
+    express_positions: &HashSet<GridPos>,
+) {
+    if express_positions.is_empty() {
+        return;
+    }
+
+    for connections in pathfinding_graph.connections.values_mut() {
+        for connection in connections.iter_mut() {
+            if connection.connection_type == ConnectionType::BusRoute
+                && express_positions.contains(&connection.to)
+            {
+                connection.cost = EXPRESS_CONNECTION_COST;
+            }
+        }
+    }
+}
+
+/// 驶入受天气影响的地面格子时按天气放大成本，公交车的路线规划会尽量绕开恶劣天气
+fn apply_weather_connection_costs(
+    pathfinding_graph: &mut PathfindingGraph,
+    weather: &ActiveWeather,
+) {
+    if weather.tiles.is_empty() {
+        return;
+    }
+
+    for connections in pathfinding_graph.connections.values_mut() {
+        for connection in connections.iter_mut() {
+            if connection.connection_type != ConnectionType::Walk
+                && connection.to_layer == NetworkLayer::Ground
+                && let Some(kind) = weather.at(connection.to)
+            {
+                connection.cost *= kind.cost_multiplier();
+            }
+        }
+    }
+}
+
 // 避免重复连接的辅助函数
 fn add_connection_if_not_exists(
     pathfinding_graph: &mut PathfindingGraph,
     from: GridPos,
     to: GridPos,
     connection_type: ConnectionType,
+    route_id: Option<String>,
 ) {
-    let connections = pathfinding_graph.connections.entry(from).or_default();
+    add_layer_connection(
+        pathfinding_graph,
+        (from, NetworkLayer::Ground),
+        (to, NetworkLayer::Ground),
+        connection_type,
+        route_id,
+    );
+}
+
+fn add_layer_connection(
+    pathfinding_graph: &mut PathfindingGraph,
+    (from, from_layer): NodeKey,
+    (to, to_layer): NodeKey,
+    connection_type: ConnectionType,
+    route_id: Option<String>,
+) {
+    let connections = pathfinding_graph
+        .connections_on_mut(from_layer)
+        .entry(from)
+        .or_default();
 
     // 检查是否已经存在这个连接
-    if !connections.iter().any(|conn| conn.to == to) {
+    if !connections
+        .iter()
+        .any(|conn| conn.to == to && conn.to_layer == to_layer)
+    {
         let cost = match connection_type {
             ConnectionType::Walk => 0.5,
+            // 高架层全线都是快速路线
+            ConnectionType::BusRoute if to_layer == NetworkLayer::Elevated => {
+                EXPRESS_CONNECTION_COST
+            }
             ConnectionType::BusRoute => 1.0,
             ConnectionType::Transfer => 2.0,
         };
 
         connections.push(Connection {
             to,
+            to_layer,
             cost,
-            route_id: Some(format!("route_{}", from.x + from.y)),
+            route_id,
             connection_type,
         });
     }
@@ -345,6 +541,7 @@ fn add_connection_if_not_exists(
 pub fn create_station_connections_improved(
     pathfinding_graph: &mut PathfindingGraph,
     route_segments_by_pos: &HashMap<GridPos, &RouteSegment>,
+    route_lines: &RouteLines,
 ) {
     let station_lookup: Vec<_> = pathfinding_graph
         .station_lookup
@@ -361,12 +558,15 @@ pub fn create_station_connections_improved(
             if let Some(segment) = route_segments_by_pos.get(&adj_pos) {
                 // 检查路线段是否有朝向站点的连接点
                 if segment_can_connect_to_station(segment, station_pos) {
+                    let route_id = Some(line_id(route_lines.line_of(adj_pos)));
+
                     // 站点到路线段
                     add_connection_if_not_exists(
                         pathfinding_graph,
                         station_pos,
                         adj_pos,
                         ConnectionType::Walk,
+                        route_id.clone(),
                     );
 
                     // 路线段到站点
@@ -375,6 +575,7 @@ pub fn create_station_connections_improved(
                         adj_pos,
                         station_pos,
                         ConnectionType::Walk,
+                        route_id,
                     );
 
                     trace!(
@@ -406,10 +607,21 @@ fn segment_can_connect_to_station(segment: &RouteSegment, station_pos: GridPos)
 }
 
 fn find_paths_for_new_passengers(
-    // pathfinding_graph: Res<PathfindingGraph>,
+    pathfinding_graph: Res<PathfindingGraph>,
     mut passengers: Query<&mut PathfindingAgent, Added<PathfindingAgent>>,
 ) {
     for mut agent in passengers.iter_mut() {
+        // 多段行程只检查当前网络能否经过中转站到达终点，乘车仍由公交车完成
+        if let Some(via) = &agent.via {
+            let reachable =
+                find_optimal_path_via(&pathfinding_graph, &agent.origin, via, &agent.destination)
+                    .is_some();
+            info!(
+                "乘客 {:?} 多段行程: {} -> {} -> {} (当前可达: {})",
+                agent.color, agent.origin, via, agent.destination, reachable
+            );
+        }
+
         // 禁用乘客寻路：让乘客只能等车，不能自己寻路
         info!(
             "乘客 {:?} 生成，设置为等车模式: {} -> {}",
@@ -431,12 +643,14 @@ fn find_paths_for_new_passengers(
 }
 
 fn update_passenger_movement(
-    time: Res<Time>,
-    mut passengers: Query<(&mut PathfindingAgent, &mut Transform)>,
+    sim_clock: Res<SimClock>,
+    mut passengers: Query<(&mut PathfindingAgent, &mut Transform, &mut AgentLod)>,
     level_manager: Res<LevelManager>,
+    stations: Query<&StationEntity>,
+    weather: Res<ActiveWeather>,
     keyboard_input: Res<ButtonInput<KeyCode>>, // 添加键盘输入用于调试
 ) {
-    let dt = time.delta_secs();
+    let sim_dt = sim_clock.delta_secs();
     let _tile_size = level_manager.tile_size;
 
     let (_grid_width, _grid_height) = if let Some(level_data) = &level_manager.current_level {
@@ -448,7 +662,7 @@ fn update_passenger_movement(
     // F7 - 调试乘客移动状态
     if keyboard_input.just_pressed(KeyCode::F7) {
         info!("=== 乘客移动调试 (寻路已禁用) ===");
-        for (agent, transform) in passengers.iter() {
+        for (agent, transform, _) in passengers.iter() {
             info!("乘客 {:?}:", agent.color);
             info!(
                 "  位置: {:.1}, {:.1}",
@@ -468,16 +682,39 @@ fn update_passenger_movement(
         }
     }
 
+    // 每帧只查一次各站点的耐心消耗倍率，避免每位乘客都遍历站点
+    let drain_multipliers: HashMap<&str, f32> = stations
+        .iter()
+        .map(|station| {
+            (
+                station.station_data.name.as_str(),
+                station.patience_drain_multiplier()
+                    * weather.patience_drain_multiplier(station.station_data.position),
+            )
+        })
+        .collect();
+
     // 禁用乘客自主移动 - 乘客只能等车，不能自己移动
-    for (mut agent, mut transform) in passengers.iter_mut() {
-        // 确保乘客在正确的Z层级
-        transform.translation.z = crate::bus_puzzle::PASSENGER_Z;
+    for (mut agent, mut transform, mut lod) in passengers.iter_mut() {
+        // 远景乘客攒够时间才结算一次
+        let Some(dt) = lod.tick(sim_dt) else {
+            continue;
+        };
+
+        // 确保乘客在正确的Z层级（只在不同时写入，避免每帧触发变换传播）
+        if transform.translation.z != crate::bus_puzzle::PASSENGER_Z {
+            transform.translation.z = crate::bus_puzzle::PASSENGER_Z;
+        }
 
         match agent.state {
             AgentState::WaitingAtStation => {
                 agent.waiting_time += dt;
                 // 减缓耐心消耗速度
-                agent.patience -= dt * 0.05; // 进一步减慢耐心消耗
+                let drain_multiplier = drain_multipliers
+                    .get(agent.origin.as_str())
+                    .copied()
+                    .unwrap_or(1.0);
+                agent.patience -= dt * 0.05 * drain_multiplier; // 进一步减慢耐心消耗
 
                 // 清空任何可能存在的寻路路径
                 if !agent.current_path.is_empty() {
@@ -549,18 +786,39 @@ fn cleanup_finished_passengers(
     mut commands: Commands,
     passengers: Query<(Entity, &PathfindingAgent)>,
     mut game_state: ResMut<GameState>,
+    mut arrived_events: EventWriter<PassengerArrivedEvent>,
+    mut gave_up_events: EventWriter<PassengerGaveUpEvent>,
 ) {
     for (entity, agent) in passengers.iter() {
         match agent.state {
             AgentState::Arrived => {
                 info!("乘客 {:?} 成功到达目的地", agent.color);
-                game_state.passenger_stats.total_arrived += 1;
+                // 成组乘客整组到达，按人数统计，每位成员各发一次事件
+                let stats = &mut game_state.passenger_stats;
+                stats.total_arrived += agent.group_size;
+                *stats.arrived_by_color.entry(agent.color).or_insert(0) += agent.group_size;
+                // 等车时间只在站台累计，上车后不清零
+                stats.total_wait_time += agent.waiting_time * agent.group_size as f32;
+                for _ in 0..agent.group_size {
+                    arrived_events.write(PassengerArrivedEvent {
+                        color: agent.color,
+                        travel_time: agent.waiting_time, // 乘客未记录出发时间，以累计等待时间代替
+                        transfers: agent.transfers(),
+                    });
+                }
                 commands.entity(entity).despawn();
             }
             AgentState::GaveUp => {
                 warn!("乘客 {:?} 因耐心耗尽而放弃", agent.color);
                 commands.entity(entity).despawn();
-                game_state.passenger_stats.total_gave_up += 1;
+                game_state.passenger_stats.total_gave_up += agent.group_size;
+                for _ in 0..agent.group_size {
+                    gave_up_events.write(PassengerGaveUpEvent {
+                        color: agent.color,
+                        origin: agent.origin.clone(),
+                        destination: agent.next_stop().to_string(),
+                    });
+                }
             }
             _ => {}
         }
@@ -573,6 +831,27 @@ pub fn find_optimal_path(
     graph: &PathfindingGraph,
     origin: &str,
     destination: &str,
+) -> Option<Vec<PathNode>> {
+    let _span = info_span!("find_optimal_path", origin, destination).entered();
+    find_path(graph, origin, destination, None)
+}
+
+/// 只沿指定线路行驶的路径（公交车不会驶入其他线路的路线段）
+pub fn find_optimal_path_on_line(
+    graph: &PathfindingGraph,
+    origin: &str,
+    destination: &str,
+    line_id: &str,
+) -> Option<Vec<PathNode>> {
+    let _span = info_span!("find_optimal_path_on_line", origin, destination, line_id).entered();
+    find_path(graph, origin, destination, Some(line_id))
+}
+
+fn find_path(
+    graph: &PathfindingGraph,
+    origin: &str,
+    destination: &str,
+    line_id: Option<&str>,
 ) -> Option<Vec<PathNode>> {
     let start_pos = *graph.station_lookup.get(origin)?;
     let end_pos = *graph.station_lookup.get(destination)?;
@@ -583,26 +862,37 @@ pub fn find_optimal_path(
             node_type: PathNodeType::Station(destination.to_string()),
             estimated_wait_time: 0.0,
             route_id: None,
+            layer: NetworkLayer::Ground,
         }]);
     }
 
+    // 站点都在地面层
+    let start_key = (start_pos, NetworkLayer::Ground);
+    let end_key = (end_pos, NetworkLayer::Ground);
+
     let mut open_set = BinaryHeap::new();
     let mut closed_set = HashSet::new();
     let mut came_from = HashMap::new();
 
-    let start_node = AStarNode::new(start_pos, 0.0, heuristic(start_pos, end_pos), None, 0);
+    let start_node = AStarNode::new(start_key, 0.0, heuristic(start_pos, end_pos), None, 0);
     open_set.push(start_node);
 
     while let Some(current) = open_set.pop() {
-        if current.position == end_pos {
-            return Some(reconstruct_path(came_from, current.position, graph));
+        let current_key = current.key();
+        if current_key == end_key {
+            return Some(reconstruct_path(came_from, current_key, graph));
         }
 
-        closed_set.insert(current.position);
+        closed_set.insert(current_key);
 
-        if let Some(connections) = graph.connections.get(&current.position) {
+        if let Some(connections) = graph.connections_from(current.position, current.layer) {
             for connection in connections {
-                if closed_set.contains(&connection.to) {
+                let neighbor_key = (connection.to, connection.to_layer);
+                if closed_set.contains(&neighbor_key) {
+                    continue;
+                }
+
+                if line_id.is_some_and(|line_id| connection.route_id.as_deref() != Some(line_id)) {
                     continue;
                 }
 
@@ -616,20 +906,20 @@ pub fn find_optimal_path(
                     current.g_cost + connection.cost + (route_changes as f32 * 3.0);
 
                 let neighbor = AStarNode::new(
-                    connection.to,
+                    neighbor_key,
                     tentative_g_cost,
                     heuristic(connection.to, end_pos),
-                    Some(current.position),
+                    Some(current_key),
                     route_changes,
                 );
 
                 let should_add = open_set
                     .iter()
-                    .find(|node| node.position == connection.to)
+                    .find(|node| node.key() == neighbor_key)
                     .is_none_or(|existing| neighbor.f_cost < existing.f_cost);
 
                 if should_add {
-                    came_from.insert(connection.to, current.position);
+                    came_from.insert(neighbor_key, current_key);
                     open_set.push(neighbor);
                 }
             }
@@ -639,19 +929,49 @@ pub fn find_optimal_path(
     None
 }
 
+/// 必须途经中转站的路径：分两段寻路后拼接，中转站节点只保留一次
+pub fn find_optimal_path_via(
+    graph: &PathfindingGraph,
+    origin: &str,
+    via: &str,
+    destination: &str,
+) -> Option<Vec<PathNode>> {
+    let _span = info_span!("find_optimal_path_via", origin, via, destination).entered();
+
+    let mut path = find_optimal_path(graph, origin, via)?;
+    let second_leg = find_optimal_path(graph, via, destination)?;
+
+    // 在中转站需要换车
+    if let Some(via_node) = path.last_mut() {
+        via_node.node_type = PathNodeType::TransferPoint;
+    }
+    path.extend(second_leg.into_iter().skip(1));
+    Some(path)
+}
+
 fn heuristic(pos1: GridPos, pos2: GridPos) -> f32 {
     ((pos1.x - pos2.x).abs() + (pos1.y - pos2.y).abs()) as f32
 }
 
 fn reconstruct_path(
-    came_from: HashMap<GridPos, GridPos>,
-    mut current: GridPos,
+    came_from: HashMap<NodeKey, NodeKey>,
+    mut current: NodeKey,
     graph: &PathfindingGraph,
 ) -> Vec<PathNode> {
     let mut path = Vec::new();
 
     while let Some(&parent) = came_from.get(&current) {
-        if let Some(node) = graph.nodes.get(&current) {
+        // 记录驶入该节点时所在的线路，用于统计换乘
+        let route_id = graph
+            .connections_from(parent.0, parent.1)
+            .and_then(|connections| {
+                connections
+                    .iter()
+                    .find(|connection| (connection.to, connection.to_layer) == current)
+                    .and_then(|connection| connection.route_id.clone())
+            });
+
+        if let Some(node) = graph.node(current.0, current.1) {
             let node_type = match &node.node_type {
                 GraphNodeType::Station => {
                     PathNodeType::Station(node.station_name.clone().unwrap_or_default())
@@ -661,22 +981,24 @@ fn reconstruct_path(
             };
 
             path.push(PathNode {
-                position: current,
+                position: current.0,
                 node_type,
                 estimated_wait_time: 1.0,
-                route_id: None,
+                route_id,
+                layer: current.1,
             });
         }
         current = parent;
     }
 
     // 添加起点
-    if let Some(node) = graph.nodes.get(&current) {
+    if let Some(node) = graph.node(current.0, current.1) {
         path.push(PathNode {
-            position: current,
+            position: current.0,
             node_type: PathNodeType::Station(node.station_name.clone().unwrap_or_default()),
             estimated_wait_time: 0.0,
             route_id: None,
+            layer: current.1,
         });
     }
 
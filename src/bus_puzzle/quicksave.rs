@@ -0,0 +1,206 @@
+// src/bus_puzzle/quicksave.rs - 快速存档（关卡中 F5 保存当前进度，F9 读取，方便在时间紧的关卡里尝试冒险的布局；按键可重新绑定）
+
+use crate::bus_puzzle::{
+    apply_game_mode, format_time, get_text, get_text_with_args, reset_contracts, reset_economy,
+    reset_fleet, reset_route_lines, reset_route_schedules, reset_scenario, restore_autosave,
+    show_contextual_tip, AutosaveData, CurrentLanguage, Fleet, GameState, GameStateEnum,
+    InputAction, InputMap, MirrorMode, PendingAutosave, RouteLines, SavedBuses, SavedModes,
+    SavedPassengers, SavedStations, ScenarioProgress, SimClock, TipType, UIAssets, NO_QUICKSAVE,
+    QUICKLOADED, QUICKSAVED,
+};
+use bevy::prelude::*;
+
+#[cfg(not(target_family = "wasm"))]
+const QUICKSAVE_FILE: &str = "quicksave.json";
+
+/// 快速存档槽位，格式与自动存档相同；原生平台同时写入文件，重新启动游戏后仍可读取
+#[derive(Resource, Default)]
+pub struct Quicksave {
+    slot: Option<AutosaveData>,
+    loaded: bool, // 读档后进入关卡时提示一次
+}
+
+// ============ 快速存档插件 ============
+
+pub struct QuicksavePlugin;
+
+impl Plugin for QuicksavePlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(not(target_family = "wasm"))]
+        app.add_systems(Startup, load_quicksave_file);
+
+        app.init_resource::<Quicksave>()
+            .add_systems(
+                Update,
+                (
+                    quicksave.run_if(in_state(GameStateEnum::Playing)),
+                    // 失败后也能读档，直接回到存档时的局面
+                    quickload.run_if(
+                        in_state(GameStateEnum::Playing).or(in_state(GameStateEnum::GameOver)),
+                    ),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::Loading),
+                // 读档时 PendingAutosave 里放的是快速存档，其余情况下这里为空，不做任何事
+                restore_autosave
+                    .after(apply_game_mode)
+                    .after(reset_route_lines)
+                    .after(reset_fleet)
+                    .after(reset_scenario)
+                    // 经营收支、合约和线路调度在进入 Loading 时清空，要在之后恢复
+                    .after(reset_economy)
+                    .after(reset_contracts)
+                    .after(reset_route_schedules),
+            )
+            .add_systems(OnEnter(GameStateEnum::Playing), show_quickload_tip);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn load_quicksave_file(mut quicksave: ResMut<Quicksave>) {
+    if let Ok(content) = std::fs::read_to_string(QUICKSAVE_FILE) {
+        quicksave.slot = AutosaveData::deserialize(&content);
+        if quicksave.slot.is_none() {
+            warn!("快速存档格式错误，已忽略");
+        }
+    }
+}
+
+fn quicksave(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut quicksave: ResMut<Quicksave>,
+    (game_state, mirror_mode, route_lines, fleet, scenario): (
+        Res<GameState>,
+        Res<MirrorMode>,
+        Res<RouteLines>,
+        Res<Fleet>,
+        Res<ScenarioProgress>,
+    ),
+    modes: SavedModes,
+    passengers: SavedPassengers,
+    buses: SavedBuses,
+    stations: SavedStations,
+    sim_clock: Res<SimClock>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !input_map.just_pressed(&keyboard_input, InputAction::Quicksave) {
+        return;
+    }
+    let Some(data) = AutosaveData::capture(
+        &game_state,
+        mirror_mode.enabled,
+        &route_lines,
+        &fleet,
+        &scenario,
+        &modes,
+        &passengers,
+        &buses,
+        &stations,
+        &sim_clock,
+    ) else {
+        return;
+    };
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(QUICKSAVE_FILE, data.serialize()) {
+            warn!("写入快速存档失败: {}", e);
+        }
+    }
+
+    show_contextual_tip(
+        &mut commands,
+        &ui_assets,
+        &get_text_with_args(
+            &QUICKSAVED,
+            current_language.language,
+            &[&format_time(data.game_time)],
+        ),
+        TipType::Controls,
+        2.0,
+    );
+    info!(
+        "💾 快速存档: {} ({} 个路线段, {} 名乘客, {} 辆公交车, {:.0}s)",
+        data.level_id,
+        data.segments.len(),
+        data.passengers.len(),
+        data.buses.len(),
+        data.game_time
+    );
+    quicksave.slot = Some(data);
+}
+
+/// 重新加载关卡，在关卡重置之后由 restore_autosave 按快速存档重建
+fn quickload(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut quicksave: ResMut<Quicksave>,
+    mut pending: ResMut<PendingAutosave>,
+    game_state: Res<GameState>,
+    mirror_mode: Res<MirrorMode>,
+    current_state: Res<State<GameStateEnum>>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !input_map.just_pressed(&keyboard_input, InputAction::Quickload) {
+        return;
+    }
+
+    let level_id = game_state
+        .current_level
+        .as_ref()
+        .map(|level_data| level_data.id.as_str());
+    let Some(data) = quicksave.slot.as_ref().filter(|data| {
+        Some(data.level_id.as_str()) == level_id && data.mirrored == mirror_mode.enabled
+    }) else {
+        // 失败界面盖住了提示，只在关卡中显示
+        if *current_state.get() == GameStateEnum::Playing {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text(&NO_QUICKSAVE, current_language.language),
+                TipType::Warning,
+                2.0,
+            );
+        }
+        return;
+    };
+
+    info!(
+        "📂 读取快速存档: {} ({:.0}s)",
+        data.level_id, data.game_time
+    );
+    pending.0 = Some(data.clone());
+    quicksave.loaded = true;
+    next_state.set(GameStateEnum::Loading);
+}
+
+fn show_quickload_tip(
+    mut commands: Commands,
+    mut quicksave: ResMut<Quicksave>,
+    game_state: Res<GameState>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !std::mem::take(&mut quicksave.loaded) {
+        return;
+    }
+
+    show_contextual_tip(
+        &mut commands,
+        &ui_assets,
+        &get_text_with_args(
+            &QUICKLOADED,
+            current_language.language,
+            &[&format_time(game_state.game_time)],
+        ),
+        TipType::Controls,
+        2.0,
+    );
+}
@@ -0,0 +1,226 @@
+// src/bus_puzzle/repair_hints.rs - 修路提示（乘客放弃时找出只差一个路线段的缺口并闪烁提示）
+
+use crate::bus_puzzle::{
+    get_neighbors, get_text_with_args, is_valid_placement, show_contextual_tip, simulation_running,
+    CurrentLanguage, GameState, GridPos, LevelData, LevelEntity, LevelManager, NetworkLayer,
+    PassengerGaveUpEvent, PathfindingGraph, RouteSegmentType, SimClock, TipType, UIAssets,
+    EFFECT_Z, REPAIR_HINT,
+};
+use bevy::{platform::collections::HashSet, prelude::*};
+use std::collections::VecDeque;
+
+/// 提示格子闪烁的总时长和频率
+const HINT_DURATION: f32 = 6.0;
+const HINT_FLASH_HZ: f32 = 2.0;
+
+const HINT_COLOR: Color = Color::srgb(1.0, 0.9, 0.2);
+
+/// 缺口格子上闪烁的提示方块，同一时间只保留一个
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct RepairHint {
+    remaining: f32,
+}
+
+// ============ 修路提示插件 ============
+
+pub struct RepairHintsPlugin;
+
+impl Plugin for RepairHintsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (suggest_repair_on_give_up, flash_repair_hints)
+                .chain()
+                .run_if(simulation_running),
+        );
+    }
+}
+
+/// 乘客放弃时，如果起点和目的地之间的路网只差一个格子，在那个格子上闪烁提示
+fn suggest_repair_on_give_up(
+    mut commands: Commands,
+    mut gave_up_events: EventReader<PassengerGaveUpEvent>,
+    game_state: Res<GameState>,
+    pathfinding_graph: Res<PathfindingGraph>,
+    level_manager: Res<LevelManager>,
+    hints: Query<(), With<RepairHint>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    // 成组乘客会发出多条相同的事件，每个行程只检查一次
+    let trips: HashSet<(String, String)> = gave_up_events
+        .read()
+        .map(|event| (event.origin.clone(), event.destination.clone()))
+        .collect();
+    if trips.is_empty() || !hints.is_empty() {
+        return;
+    }
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    for (origin, destination) in &trips {
+        let (Some(origin_pos), Some(destination_pos)) = (
+            pathfinding_graph.station_lookup.get(origin),
+            pathfinding_graph.station_lookup.get(destination),
+        ) else {
+            continue;
+        };
+        let Some(tile) = find_repair_tile(
+            &game_state,
+            level_data,
+            &pathfinding_graph,
+            *origin_pos,
+            *destination_pos,
+        ) else {
+            continue;
+        };
+
+        let (width, height) = level_data.grid_size;
+        let mut world_pos = tile.to_world_pos(level_manager.tile_size, width, height);
+        world_pos.z = EFFECT_Z;
+        commands.spawn((
+            Sprite::from_color(HINT_COLOR, Vec2::splat(level_manager.tile_size * 0.9)),
+            Transform::from_translation(world_pos),
+            RepairHint {
+                remaining: HINT_DURATION,
+            },
+            Name::new("Repair Hint"),
+        ));
+
+        let language = current_language.language;
+        let station_name = |name: &str| {
+            level_data
+                .stations
+                .iter()
+                .find(|station| station.name == name)
+                .map_or_else(|| name.to_string(), |s| s.get_localized_name(language))
+        };
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &REPAIR_HINT,
+                language,
+                &[
+                    &tile.x.to_string(),
+                    &tile.y.to_string(),
+                    &station_name(origin),
+                    &station_name(destination),
+                ],
+            ),
+            TipType::Strategy,
+            HINT_DURATION,
+        );
+        info!(
+            "💡 修路提示: {} -> {} 缺口在 {:?}",
+            origin, destination, tile
+        );
+        return;
+    }
+}
+
+/// 一步修补搜索：分别从起点和目的地出发在地面路网上扩展，找一个空格子，
+/// 它同时与两边可达的某个节点相邻且对方朝它开口，放一个路线段即可连通。
+/// 两边本来就连通（乘客是等车太久放弃的）时返回 None
+fn find_repair_tile(
+    game_state: &GameState,
+    level_data: &LevelData,
+    pathfinding_graph: &PathfindingGraph,
+    origin_pos: GridPos,
+    destination_pos: GridPos,
+) -> Option<GridPos> {
+    let from_origin = reachable_from(pathfinding_graph, origin_pos);
+    if from_origin.contains(&destination_pos) {
+        return None;
+    }
+    let from_destination = reachable_from(pathfinding_graph, destination_pos);
+
+    let station_positions: HashSet<GridPos> = level_data
+        .stations
+        .iter()
+        .map(|station| station.position)
+        .collect();
+    // 站点四面都能接入；路线段只有朝向该格子的端口才能接上
+    let opens_toward = |node: GridPos, tile: GridPos| {
+        station_positions.contains(&node)
+            || game_state.placed_segments.get(&node).is_some_and(|placed| {
+                placed
+                    .segment_type
+                    .has_connection_to(node, tile, placed.rotation)
+            })
+    };
+    let buildable = |tile: GridPos| {
+        !station_positions.contains(&tile)
+            && [
+                RouteSegmentType::Straight,
+                RouteSegmentType::Bridge,
+                RouteSegmentType::Tunnel,
+            ]
+            .iter()
+            .any(|segment_type| is_valid_placement(game_state, tile, segment_type))
+    };
+
+    let mut candidates: Vec<GridPos> = from_origin
+        .iter()
+        .flat_map(|node| {
+            get_neighbors(*node)
+                .into_iter()
+                .filter(move |tile| opens_toward(*node, *tile))
+        })
+        .filter(|tile| {
+            buildable(*tile)
+                && get_neighbors(*tile)
+                    .into_iter()
+                    .any(|node| from_destination.contains(&node) && opens_toward(node, *tile))
+        })
+        .collect();
+
+    // 多个缺口时选离起点和终点总距离最短的，结果与遍历顺序无关
+    let distance = |a: &GridPos, b: &GridPos| (a.x - b.x).abs() + (a.y - b.y).abs();
+    candidates.sort_by_key(|tile| {
+        (
+            distance(tile, &origin_pos) + distance(tile, &destination_pos),
+            tile.x,
+            tile.y,
+        )
+    });
+    candidates.first().copied()
+}
+
+/// 地面路网中从指定节点出发可达的所有格子
+fn reachable_from(pathfinding_graph: &PathfindingGraph, start: GridPos) -> HashSet<GridPos> {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(pos) = queue.pop_front() {
+        let Some(connections) = pathfinding_graph.connections.get(&pos) else {
+            continue;
+        };
+        for connection in connections {
+            if connection.to_layer == NetworkLayer::Ground && visited.insert(connection.to) {
+                queue.push_back(connection.to);
+            }
+        }
+    }
+    visited
+}
+
+/// 提示方块闪烁，到时自动消失；按模拟时间计时，暂停时停在当前状态
+fn flash_repair_hints(
+    mut commands: Commands,
+    sim_clock: Res<SimClock>,
+    mut hints: Query<(Entity, &mut RepairHint, &mut Sprite)>,
+) {
+    for (entity, mut hint, mut sprite) in hints.iter_mut() {
+        hint.remaining -= sim_clock.delta_secs();
+        if hint.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let phase = (hint.remaining * HINT_FLASH_HZ * std::f32::consts::TAU).sin();
+        sprite.color = HINT_COLOR.with_alpha(0.35 + 0.3 * phase);
+    }
+}
@@ -0,0 +1,871 @@
+// src/bus_puzzle/replay.rs - 录像录制与回放（记录放置、拆除、旋转和乘客生成，通关后按可调速度回放）
+
+use crate::bus_puzzle::{
+    create_builtin_level, despawn_level_entities, draw_events, format_time, generate_level_map,
+    get_text, get_text_with_args, insert_placed_segment, mirror_level_data, reset_game_state,
+    spawn_passenger_with_icon, upgrade_to_express, ActiveChallenge, ButtonComponent, ButtonType,
+    CurrentLanguage, EventDeck, GameState, GameStateEnum, GridPos, InputAction, InputMap, Language,
+    LevelData, LevelEntity, LevelManager, LocalizedText, LocalizedTextComponent, MirrorMode,
+    NetworkLayer, PassengerColor, PassengerSpawnedEvent, PathfindingGraph, PlacedSegment,
+    RouteSegment, RouteSegmentType, SegmentPlacedEvent, SegmentRemovedEvent, SegmentRotatedEvent,
+    SegmentUpgradedEvent, SimClock, ThemeRegistry, UIAssets, EXPRESS_UPGRADE_COST, REPLAY_EXIT,
+    REPLAY_FASTER, REPLAY_FINISHED, REPLAY_PAUSE, REPLAY_PAUSED, REPLAY_SLOWER, REPLAY_STATUS,
+    WATCH_REPLAY,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+use serde::{Deserialize, Serialize};
+
+/// 命令行参数：`last-stop --replay <录像文件>` 启动后直接回放
+#[cfg(not(target_family = "wasm"))]
+const REPLAY_ARG: &str = "--replay";
+
+/// 通关录像的保存目录，只保留最近的 MAX_SAVED_REPLAYS 个
+#[cfg(not(target_family = "wasm"))]
+const REPLAYS_DIR: &str = "replays";
+#[cfg(not(target_family = "wasm"))]
+const MAX_SAVED_REPLAYS: usize = 20;
+
+/// 可选的回放速度
+const REPLAY_SPEEDS: [f32; 6] = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+/// 默认回放速度在 REPLAY_SPEEDS 中的下标
+const DEFAULT_SPEED_INDEX: usize = 1;
+
+// ============ 录像数据 ============
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayAction {
+    // 旧录像没有图层字段，都在地面层
+    Place {
+        position: GridPos,
+        segment_type: RouteSegmentType,
+        rotation: u32,
+        #[serde(default)]
+        layer: NetworkLayer,
+        #[serde(default)]
+        express: bool, // 搬移或恢复存档时保留的快速路线升级
+    },
+    Upgrade {
+        position: GridPos,
+    },
+    Remove {
+        position: GridPos,
+        #[serde(default)]
+        layer: NetworkLayer,
+    },
+    Rotate {
+        position: GridPos,
+        rotation: u32,
+        #[serde(default)]
+        layer: NetworkLayer,
+    },
+    SpawnPassenger {
+        color: PassengerColor,
+        origin: String,
+        destination: String,
+        #[serde(default = "single_passenger")]
+        group_size: u32, // 旧录像没有此字段，按单人处理
+    },
+}
+
+fn single_passenger() -> u32 {
+    1
+}
+
+/// 一条带游戏时间的操作记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub time: f32,
+    pub action: ReplayAction,
+}
+
+/// 录像文件内容。内置关卡可以从关卡ID、镜像标记和事件种子重建，
+/// 自定义关卡和每周挑战只能在本次运行中回放
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub level_id: String,
+    pub mirrored: bool,
+    pub event_seed: Option<u64>,
+    pub duration: f32,
+    pub entries: Vec<ReplayEntry>,
+    #[serde(skip)]
+    level: Option<LevelData>,
+}
+
+impl Replay {
+    fn push(&mut self, time: f32, action: ReplayAction) {
+        self.entries.push(ReplayEntry { time, action });
+    }
+
+    /// 回放使用的关卡数据
+    fn level_data(&self, language: Language) -> Option<LevelData> {
+        if let Some(level_data) = &self.level {
+            return Some(level_data.clone());
+        }
+
+        let mut level_data = create_builtin_level(&self.level_id, language)?;
+        if self.mirrored {
+            level_data = mirror_level_data(&level_data);
+        }
+        if level_data.random_events
+            && let Some(seed) = self.event_seed
+        {
+            let drawn = draw_events(&level_data, seed);
+            level_data.dynamic_events.extend(drawn);
+        }
+        Some(level_data)
+    }
+}
+
+// ============ 录制与回放资源 ============
+
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    current: Option<Replay>,
+    /// 最近一次通关的录像，结算界面可直接回放
+    last: Option<Replay>,
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Option<Replay>,
+    clock: f32,
+    next_entry: usize,
+    speed_index: usize,
+    paused: bool,
+    finished: bool,
+    /// 命令行指定的录像，进入主菜单后自动开始
+    start_from_menu: bool,
+}
+
+impl Default for ReplayPlayback {
+    fn default() -> Self {
+        Self {
+            replay: None,
+            clock: 0.0,
+            next_entry: 0,
+            speed_index: DEFAULT_SPEED_INDEX,
+            paused: false,
+            finished: false,
+            start_from_menu: false,
+        }
+    }
+}
+
+impl ReplayPlayback {
+    fn speed(&self) -> f32 {
+        REPLAY_SPEEDS[self.speed_index]
+    }
+
+    /// 排队一段录像，进入主菜单后自动开始回放（命令行录像和确定性测试使用）
+    #[cfg(not(target_family = "wasm"))]
+    pub fn queue(&mut self, replay: Replay) {
+        self.replay = Some(replay);
+        self.start_from_menu = true;
+    }
+}
+
+#[derive(Component)]
+pub struct ReplayStatusText;
+
+/// 模拟系统（寻路、公交车、乘客上下车、动态事件）的运行条件：正常游戏和回放时都要运行
+pub fn simulation_running(state: Res<State<GameStateEnum>>) -> bool {
+    matches!(state.get(), GameStateEnum::Playing | GameStateEnum::Replay)
+}
+
+// ============ 录像插件 ============
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .init_resource::<ReplayPlayback>()
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_recorder)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_recorder)
+            .add_systems(
+                Update,
+                record_replay_actions.run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::LevelComplete),
+                (finish_recording, setup_watch_replay_button).chain(),
+            )
+            .add_systems(
+                Update,
+                handle_watch_replay_button.run_if(in_state(GameStateEnum::LevelComplete)),
+            )
+            .add_systems(OnEnter(GameStateEnum::MainMenu), start_command_line_replay)
+            .add_systems(OnEnter(GameStateEnum::Replay), start_replay)
+            .add_systems(
+                Update,
+                (handle_replay_controls, advance_replay, update_replay_status)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Replay)),
+            )
+            .add_systems(OnExit(GameStateEnum::Replay), stop_replay);
+
+        #[cfg(not(target_family = "wasm"))]
+        app.add_systems(Startup, load_command_line_replay);
+    }
+}
+
+// ============ 录制 ============
+
+fn reset_recorder(mut recorder: ResMut<ReplayRecorder>) {
+    recorder.current = None;
+}
+
+/// 关卡开始后的第一帧建立录像；恢复存档时已有的路线段记为开局放置
+fn begin_recording(
+    game_state: &GameState,
+    mirror_mode: &MirrorMode,
+    active_challenge: &ActiveChallenge,
+    deck: &EventDeck,
+) -> Option<Replay> {
+    let level_data = game_state.current_level.as_ref()?;
+
+    let mut replay = Replay {
+        level_id: level_data.id.clone(),
+        mirrored: mirror_mode.enabled && active_challenge.0.is_none(),
+        event_seed: level_data.random_events.then_some(deck.seed),
+        duration: 0.0,
+        entries: Vec::new(),
+        level: Some(level_data.clone()),
+    };
+
+    for layer in [NetworkLayer::Ground, NetworkLayer::Elevated] {
+        let mut segments: Vec<_> = game_state.segments_on(layer).iter().collect();
+        segments.sort_by_key(|(pos, _)| (pos.x, pos.y));
+        for (pos, placed) in segments {
+            replay.push(
+                0.0,
+                ReplayAction::Place {
+                    position: *pos,
+                    segment_type: placed.segment_type,
+                    rotation: placed.rotation,
+                    layer,
+                    express: placed.express,
+                },
+            );
+        }
+    }
+
+    Some(replay)
+}
+
+fn record_replay_actions(
+    mut recorder: ResMut<ReplayRecorder>,
+    game_state: Res<GameState>,
+    mirror_mode: Res<MirrorMode>,
+    active_challenge: Res<ActiveChallenge>,
+    deck: Res<EventDeck>,
+    mut placed_events: EventReader<SegmentPlacedEvent>,
+    mut rotated_events: EventReader<SegmentRotatedEvent>,
+    mut removed_events: EventReader<SegmentRemovedEvent>,
+    mut upgraded_events: EventReader<SegmentUpgradedEvent>,
+    mut spawned_events: EventReader<PassengerSpawnedEvent>,
+) {
+    if recorder.current.is_none() {
+        recorder.current = begin_recording(&game_state, &mirror_mode, &active_challenge, &deck);
+    }
+    let Some(replay) = &mut recorder.current else {
+        return;
+    };
+    let time = game_state.game_time;
+
+    for event in placed_events.read() {
+        replay.push(
+            time,
+            ReplayAction::Place {
+                position: event.position,
+                segment_type: event.segment_type,
+                rotation: event.rotation,
+                layer: event.layer,
+                express: game_state
+                    .segments_on(event.layer)
+                    .get(&event.position)
+                    .is_some_and(|placed| placed.express),
+            },
+        );
+    }
+    for event in upgraded_events.read() {
+        replay.push(
+            time,
+            ReplayAction::Upgrade {
+                position: event.position,
+            },
+        );
+    }
+    for event in rotated_events.read() {
+        replay.push(
+            time,
+            ReplayAction::Rotate {
+                position: event.position,
+                rotation: event.rotation,
+                layer: event.layer,
+            },
+        );
+    }
+    for event in removed_events.read() {
+        replay.push(
+            time,
+            ReplayAction::Remove {
+                position: event.position,
+                layer: event.layer,
+            },
+        );
+    }
+    for event in spawned_events.read() {
+        replay.push(
+            time,
+            ReplayAction::SpawnPassenger {
+                color: event.color,
+                origin: event.origin.clone(),
+                destination: event.destination.clone(),
+                group_size: event.group_size,
+            },
+        );
+    }
+}
+
+fn finish_recording(mut recorder: ResMut<ReplayRecorder>, game_state: Res<GameState>) {
+    let Some(mut replay) = recorder.current.take() else {
+        return;
+    };
+    replay.duration = game_state.game_time;
+
+    #[cfg(not(target_family = "wasm"))]
+    match save_replay_file(&replay) {
+        Ok(path) => info!("📼 录像已保存: {} ({} 条操作)", path, replay.entries.len()),
+        Err(e) => warn!("保存录像失败: {}", e),
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        info!(
+            "WASM环境：录像只保留在内存中 ({}，{} 条操作)",
+            replay.level_id,
+            replay.entries.len()
+        );
+    }
+
+    recorder.last = Some(replay);
+}
+
+/// 写入 replays/ 目录，然后删除超出数量上限的旧录像，返回写入的路径
+#[cfg(not(target_family = "wasm"))]
+fn save_replay_file(replay: &Replay) -> Result<String, String> {
+    let content = serde_json::to_string(replay).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(REPLAYS_DIR).map_err(|e| e.to_string())?;
+    let path = std::path::Path::new(REPLAYS_DIR).join(format!(
+        "replay_{}_{}.json",
+        crate::bus_puzzle::file_stem_for_id(&replay.level_id),
+        chrono::Utc::now().format("%Y%m%d_%H%M%S")
+    ));
+    std::fs::write(&path, content).map_err(|e| e.to_string())?;
+    prune_old_replays();
+    Ok(path.display().to_string())
+}
+
+/// 按修改时间从旧到新删除，只保留最近的 MAX_SAVED_REPLAYS 个录像
+#[cfg(not(target_family = "wasm"))]
+fn prune_old_replays() {
+    let Ok(entries) = std::fs::read_dir(REPLAYS_DIR) else {
+        return;
+    };
+
+    let mut replays: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .collect();
+    replays.sort();
+
+    let excess = replays.len().saturating_sub(MAX_SAVED_REPLAYS);
+    for (_, path) in replays.into_iter().take(excess) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => info!("🗑️ 删除旧录像: {}", path.display()),
+            Err(e) => warn!("删除旧录像失败 {}: {}", path.display(), e),
+        }
+    }
+}
+
+// ============ 进入回放 ============
+
+fn setup_watch_replay_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    recorder: Res<ReplayRecorder>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if recorder.last.is_none() {
+        return;
+    }
+
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(20.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::WatchReplay,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            ZIndex(2100), // 结果界面（2000）之上
+            StateScoped(GameStateEnum::LevelComplete),
+            Name::new("Watch Replay Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&WATCH_REPLAY, current_language.language)),
+                LocalizedTextComponent::new(&WATCH_REPLAY),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn handle_watch_replay_button(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    recorder: Res<ReplayRecorder>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    let pressed = button_query.iter().any(|(interaction, button_component)| {
+        *interaction == Interaction::Pressed
+            && button_component.button_type == ButtonType::WatchReplay
+    });
+    if pressed && let Some(replay) = &recorder.last {
+        playback.replay = Some(replay.clone());
+        next_state.set(GameStateEnum::Replay);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn load_command_line_replay(mut playback: ResMut<ReplayPlayback>) {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(path) = args
+        .iter()
+        .position(|arg| arg == REPLAY_ARG)
+        .and_then(|index| args.get(index + 1))
+    else {
+        return;
+    };
+
+    let replay = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<Replay>(&content).map_err(|e| e.to_string()));
+    match replay {
+        Ok(replay) => {
+            info!("📼 读取录像 {}: 关卡 {}", path, replay.level_id);
+            playback.queue(replay);
+        }
+        Err(e) => warn!("读取录像失败 {}: {}", path, e),
+    }
+}
+
+fn start_command_line_replay(
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    if playback.start_from_menu {
+        playback.start_from_menu = false;
+        next_state.set(GameStateEnum::Replay);
+    }
+}
+
+fn start_replay(
+    mut commands: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    mut game_state: ResMut<GameState>,
+    mut level_manager: ResMut<LevelManager>,
+    mut pathfinding_graph: ResMut<PathfindingGraph>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut sim_clock: ResMut<SimClock>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    time: Res<Time>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+) {
+    let Some(level_data) = playback
+        .replay
+        .as_ref()
+        .and_then(|replay| replay.level_data(current_language.language))
+    else {
+        warn!("录像中的关卡无法重建，返回主菜单");
+        next_state.set(GameStateEnum::MainMenu);
+        return;
+    };
+
+    despawn_level_entities(&mut commands, &level_entities);
+    pathfinding_graph.connections.clear();
+    pathfinding_graph.nodes.clear();
+    pathfinding_graph.elevated_connections.clear();
+    pathfinding_graph.elevated_nodes.clear();
+    pathfinding_graph.station_lookup.clear();
+    pathfinding_graph.route_network.clear();
+
+    reset_game_state(&mut game_state, &level_data, time.elapsed_secs());
+    level_manager.current_level = Some(level_data.clone());
+    generate_level_map(
+        &mut commands,
+        &asset_server,
+        &theme,
+        &level_data,
+        level_manager.tile_size,
+    );
+
+    playback.clock = 0.0;
+    playback.next_entry = 0;
+    playback.paused = false;
+    playback.finished = false;
+    virtual_time.set_relative_speed(playback.speed());
+    // 上一局的倍速或单步状态不能带进回放
+    *sim_clock = SimClock::default();
+
+    spawn_replay_hud(&mut commands, &ui_assets, current_language.language);
+    info!("📼 开始回放: {}", level_data.id);
+}
+
+fn stop_replay(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.set_relative_speed(1.0);
+    virtual_time.unpause();
+}
+
+// ============ 回放界面 ============
+
+fn spawn_replay_hud(commands: &mut Commands, ui_assets: &UIAssets, language: Language) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Px(10.0),
+                left: Percent(50.0),
+                margin: UiRect::left(Px(-300.0)),
+                width: Px(600.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(8.0),
+                padding: UiRect::all(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.2, 0.85)),
+            ZIndex(1500),
+            StateScoped(GameStateEnum::Replay),
+            Name::new("Replay HUD"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+                ReplayStatusText,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Px(10.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (button_type, text_key) in [
+                        (ButtonType::ReplaySlower, &REPLAY_SLOWER),
+                        (ButtonType::ReplayFaster, &REPLAY_FASTER),
+                        (ButtonType::ReplayTogglePause, &REPLAY_PAUSE),
+                        (ButtonType::ExitReplay, &REPLAY_EXIT),
+                    ] {
+                        spawn_replay_button(parent, ui_assets, button_type, text_key, language);
+                    }
+                });
+        });
+}
+
+fn spawn_replay_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    text_key: &'static LocalizedText,
+    language: Language,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(130.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(text_key, language)),
+                LocalizedTextComponent::new(text_key),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 按钮和快捷键：减速/加速键（默认 - / =）调整速度，P 暂停，Esc 退出到主菜单
+fn handle_replay_controls(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    let pressed = |button_type: ButtonType| {
+        button_query.iter().any(|(interaction, button_component)| {
+            *interaction == Interaction::Pressed && button_component.button_type == button_type
+        })
+    };
+
+    if pressed(ButtonType::ExitReplay) || keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameStateEnum::MainMenu);
+        return;
+    }
+
+    let slower = pressed(ButtonType::ReplaySlower)
+        || input_map.just_pressed(&keyboard_input, InputAction::SimSlower);
+    let faster = pressed(ButtonType::ReplayFaster)
+        || input_map.just_pressed(&keyboard_input, InputAction::SimFaster);
+    if slower {
+        playback.speed_index = playback.speed_index.saturating_sub(1);
+    }
+    if faster {
+        playback.speed_index = (playback.speed_index + 1).min(REPLAY_SPEEDS.len() - 1);
+    }
+    if slower || faster {
+        virtual_time.set_relative_speed(playback.speed());
+        info!("📼 回放速度: {}x", playback.speed());
+    }
+
+    if !playback.finished
+        && (pressed(ButtonType::ReplayTogglePause) || keyboard_input.just_pressed(KeyCode::KeyP))
+    {
+        playback.paused = !playback.paused;
+        if playback.paused {
+            virtual_time.pause();
+        } else {
+            virtual_time.unpause();
+        }
+    }
+}
+
+fn update_replay_status(
+    playback: Res<ReplayPlayback>,
+    current_language: Res<CurrentLanguage>,
+    mut status_texts: Query<&mut Text, With<ReplayStatusText>>,
+) {
+    let language = current_language.language;
+    let clock = format_time(playback.clock);
+    let status = if playback.finished {
+        get_text(&REPLAY_FINISHED, language)
+    } else if playback.paused {
+        get_text_with_args(&REPLAY_PAUSED, language, &[&clock])
+    } else {
+        get_text_with_args(
+            &REPLAY_STATUS,
+            language,
+            &[&clock, &playback.speed().to_string()],
+        )
+    };
+
+    for mut text in status_texts.iter_mut() {
+        if text.0 != status {
+            text.0 = status.clone();
+        }
+    }
+}
+
+// ============ 回放推进 ============
+
+/// 回放时钟跟随虚拟时间（已按回放速度缩放），到点的操作依次重现
+fn advance_replay(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut game_state: ResMut<GameState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut route_segments: Query<(&mut Transform, &mut RouteSegment)>,
+    mut placed_events: EventWriter<SegmentPlacedEvent>,
+    mut removed_events: EventWriter<SegmentRemovedEvent>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+) {
+    if playback.finished {
+        return;
+    }
+
+    playback.clock += time.delta_secs();
+    let clock = playback.clock;
+    game_state.game_time = clock;
+
+    let (due, duration) = {
+        let Some(replay) = &playback.replay else {
+            return;
+        };
+        let due: Vec<ReplayAction> = replay.entries[playback.next_entry..]
+            .iter()
+            .take_while(|entry| entry.time <= clock)
+            .map(|entry| entry.action.clone())
+            .collect();
+        (due, replay.duration)
+    };
+    playback.next_entry += due.len();
+
+    for action in due {
+        match action {
+            ReplayAction::Place {
+                position,
+                segment_type,
+                rotation,
+                layer,
+                express,
+            } => {
+                if game_state.segments_on(layer).contains_key(&position) {
+                    continue;
+                }
+                let cost = layer.segment_cost(segment_type)
+                    + if express { EXPRESS_UPGRADE_COST } else { 0 };
+                insert_placed_segment(
+                    &mut commands,
+                    &mut game_state,
+                    &asset_server,
+                    &theme,
+                    &level_manager,
+                    layer,
+                    position,
+                    PlacedSegment {
+                        segment_type,
+                        rotation,
+                        entity: Entity::PLACEHOLDER,
+                        cost,
+                        express,
+                    },
+                    &mut placed_events,
+                );
+                game_state.total_cost += cost;
+            }
+            ReplayAction::Upgrade { position } => {
+                upgrade_to_express(&mut commands, &mut game_state, position);
+            }
+            ReplayAction::Remove { position, layer } => {
+                if let Some(placed_segment) = game_state.segments_on_mut(layer).remove(&position) {
+                    commands.entity(placed_segment.entity).despawn();
+                    let refund = game_state.current_level.as_ref().map_or(
+                        placed_segment.cost,
+                        |level_data| {
+                            level_data
+                                .scoring
+                                .refund_policy
+                                .refund_for(placed_segment.cost)
+                        },
+                    );
+                    game_state.total_cost -= refund;
+                    removed_events.write(SegmentRemovedEvent { position, layer });
+                }
+            }
+            ReplayAction::Rotate {
+                position,
+                rotation,
+                layer,
+            } => {
+                if let Some(placed_segment) = game_state.segments_on_mut(layer).get_mut(&position) {
+                    placed_segment.rotation = rotation;
+                    if let Ok((mut transform, mut route_segment)) =
+                        route_segments.get_mut(placed_segment.entity)
+                    {
+                        route_segment.rotation = rotation;
+                        transform.rotation =
+                            Quat::from_rotation_z((rotation as f32) * std::f32::consts::PI / 180.0);
+                    }
+                }
+            }
+            ReplayAction::SpawnPassenger {
+                color,
+                origin,
+                destination,
+                group_size,
+            } => {
+                let Some(level_data) = &game_state.current_level else {
+                    continue;
+                };
+                let Some(demand) = level_data.passenger_demands.iter().find(|demand| {
+                    demand.color == color
+                        && demand.origin == origin
+                        && demand.destination == destination
+                }) else {
+                    warn!(
+                        "回放中找不到乘客需求: {:?} {} -> {}",
+                        color, origin, destination
+                    );
+                    continue;
+                };
+                spawn_passenger_with_icon(
+                    &mut commands,
+                    &asset_server,
+                    &theme,
+                    demand,
+                    level_data,
+                    group_size,
+                );
+            }
+        }
+    }
+
+    let all_applied = playback
+        .replay
+        .as_ref()
+        .is_none_or(|replay| playback.next_entry >= replay.entries.len());
+    if all_applied && clock >= duration {
+        playback.finished = true;
+        virtual_time.pause();
+        info!("📼 回放结束 ({})", format_time(clock));
+    }
+}
@@ -1,8 +1,14 @@
-use crate::bus_puzzle::{GridPos, LevelData, RouteSegmentType, DEFAULT_TILE_SIZE};
-use bevy::{platform::collections::HashMap, prelude::*};
+use crate::bus_puzzle::{
+    GridPos, LevelData, NetworkLayer, PassengerColor, RouteSegmentType, BONUS_LEVELS,
+    DEFAULT_TILE_SIZE,
+};
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
 
 // 游戏状态
-#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default, serde::Deserialize)]
 #[states(scoped_entities)]
 pub enum GameStateEnum {
     #[default]
@@ -13,6 +19,7 @@ pub enum GameStateEnum {
     Paused,
     LevelComplete,
     GameOver,
+    Replay, // 回放已通关的关卡
 }
 
 #[derive(Resource, Default)]
@@ -20,6 +27,7 @@ pub struct GameState {
     pub current_level: Option<LevelData>,
     pub player_inventory: HashMap<RouteSegmentType, u32>,
     pub placed_segments: HashMap<GridPos, PlacedSegment>,
+    pub elevated_segments: HashMap<GridPos, PlacedSegment>, // 高架层的路线段，可以与地面路线段在同一格
     pub total_cost: u32,
     pub game_time: f32,
     pub level_start_time: f32, // 新增：关卡开始时的系统时间
@@ -27,6 +35,29 @@ pub struct GameState {
     pub objectives_completed: Vec<bool>,
     pub score: GameScore,
     pub passenger_stats: PassengerStats,
+    pub closed_tiles: HashSet<GridPos>, // 封路期间不能放置路线段的格子
+}
+
+impl GameState {
+    /// 指定图层上已放置的路线段
+    pub fn segments_on(&self, layer: NetworkLayer) -> &HashMap<GridPos, PlacedSegment> {
+        match layer {
+            NetworkLayer::Ground => &self.placed_segments,
+            NetworkLayer::Elevated => &self.elevated_segments,
+        }
+    }
+
+    pub fn segments_on_mut(&mut self, layer: NetworkLayer) -> &mut HashMap<GridPos, PlacedSegment> {
+        match layer {
+            NetworkLayer::Ground => &mut self.placed_segments,
+            NetworkLayer::Elevated => &mut self.elevated_segments,
+        }
+    }
+
+    /// 两个图层上的路线段总数
+    pub fn segment_count(&self) -> usize {
+        self.placed_segments.len() + self.elevated_segments.len()
+    }
 }
 
 #[derive(Default)]
@@ -34,6 +65,22 @@ pub struct PassengerStats {
     pub total_spawned: u32,
     pub total_arrived: u32,
     pub total_gave_up: u32,
+    pub arrived_by_color: HashMap<PassengerColor, u32>, // 按颜色统计的到达人数
+    pub total_wait_time: f32,                           // 已到达乘客在站台的累计等待时间
+}
+
+impl PassengerStats {
+    pub fn arrived_count(&self, color: PassengerColor) -> u32 {
+        self.arrived_by_color.get(&color).copied().unwrap_or(0)
+    }
+
+    pub fn average_wait_time(&self) -> f32 {
+        if self.total_arrived == 0 {
+            0.0
+        } else {
+            self.total_wait_time / self.total_arrived as f32
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -41,15 +88,20 @@ pub struct PlacedSegment {
     pub segment_type: RouteSegmentType,
     pub rotation: u32,
     pub entity: Entity,
-    pub cost: u32,
+    pub cost: u32,     // 包含升级费用，拆除时一并退还
+    pub express: bool, // 已升级为快速路线
 }
 
-#[derive(Resource, Default)]
+/// 分数明细，各项加分之和乘以倍率得到总分
+#[derive(Resource, Default, Clone, Debug, PartialEq)]
 pub struct GameScore {
     pub base_points: u32,
     pub efficiency_bonus: u32,
     pub speed_bonus: u32,
     pub cost_bonus: u32,
+    pub combo_bonus: u32,    // 分数冲刺连击
+    pub contract_bonus: u32, // 限时合约
+    pub multiplier: f32,     // 关卡倍率 × 规则变体倍率
     pub total_score: u32,
 }
 
@@ -62,6 +114,24 @@ pub struct InputState {
     pub is_dragging: bool,
     pub drag_entity: Option<Entity>,
     pub grid_cursor_pos: Option<GridPos>,
+    pub active_layer: NetworkLayer, // 放置、旋转、拆除作用的图层
+}
+
+/// 拖动绘制路线：按住左键经过的格子，松开时沿路径自动放置直线段和弯道
+#[derive(Resource, Default)]
+pub struct RouteDrawing {
+    pub path: Vec<GridPos>,
+    pub cancelled: bool, // 按 ESC 取消后，松开左键前不再记录路径
+}
+
+/// 过载的站点及恢复时间（游戏时间），恢复前乘客无法在该站上车
+#[derive(Resource, Default)]
+pub struct StationOverloads(pub HashMap<String, f32>);
+
+impl StationOverloads {
+    pub fn is_overloaded(&self, station_name: &str) -> bool {
+        self.0.contains_key(station_name)
+    }
 }
 
 #[derive(Resource)]
@@ -71,6 +141,7 @@ pub struct CameraController {
     pub max_zoom: f32,
     pub pan_speed: f32,
     pub zoom_speed: f32,
+    pub bounds: Option<Rect>, // 当前地图（含边距）的世界坐标范围，加载新关卡后重新计算
 }
 
 impl Default for CameraController {
@@ -81,10 +152,39 @@ impl Default for CameraController {
             max_zoom: 3.0,
             pan_speed: 500.0,
             zoom_speed: 0.1,
+            bounds: None,
         }
     }
 }
 
+impl CameraController {
+    /// 整张地图刚好放进窗口时的缩放
+    pub fn fit_zoom(bounds: Rect, window_size: Vec2) -> f32 {
+        (bounds.width() / window_size.x).max(bounds.height() / window_size.y)
+    }
+
+    /// 限制镜头位置：视野小于地图时不能移出地图，大于地图时居中
+    pub fn clamp_to_bounds(&self, translation: Vec2, window_size: Vec2) -> Vec2 {
+        let Some(bounds) = self.bounds else {
+            return translation;
+        };
+
+        let half_view = window_size * self.zoom / 2.0;
+        let clamp_axis = |value: f32, min: f32, max: f32, half: f32| {
+            if max - min <= half * 2.0 {
+                (min + max) / 2.0
+            } else {
+                value.clamp(min + half, max - half)
+            }
+        };
+
+        Vec2::new(
+            clamp_axis(translation.x, bounds.min.x, bounds.max.x, half_view.x),
+            clamp_axis(translation.y, bounds.min.y, bounds.max.y, half_view.y),
+        )
+    }
+}
+
 #[derive(Resource)]
 #[allow(dead_code)]
 pub struct LevelManager {
@@ -93,7 +193,45 @@ pub struct LevelManager {
     pub available_levels: Vec<String>,
     pub current_level_index: usize,
     pub unlocked_levels: Vec<bool>,
-    pub level_scores: HashMap<String, u32>,
+    pub level_records: HashMap<String, LevelRecord>, // 关卡ID -> 最佳成绩（由存档系统持久化）
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelRecord {
+    pub best_score: u32,
+    pub best_time: f32,
+    pub stars: u32, // 0-3 星
+}
+
+impl LevelManager {
+    /// 所有关卡获得的星星总数
+    pub fn total_stars(&self) -> u32 {
+        self.level_records.values().map(|record| record.stars).sum()
+    }
+
+    /// 奖励关卡解锁所需的星星总数，普通关卡返回 None
+    pub fn required_stars(&self, index: usize) -> Option<u32> {
+        let level_id = self.available_levels.get(index)?;
+        BONUS_LEVELS
+            .iter()
+            .find(|(bonus_id, _)| bonus_id == level_id)
+            .map(|(_, stars)| *stars)
+    }
+
+    /// 普通关卡按通关进度解锁，奖励关卡按星星总数解锁
+    pub fn is_level_unlocked(&self, index: usize) -> bool {
+        match self.required_stars(index) {
+            Some(stars) => self.total_stars() >= stars,
+            None => self.unlocked_levels.get(index).copied().unwrap_or(false),
+        }
+    }
+
+    /// 下一关存在且已解锁（奖励关卡星星不足时视为没有下一关）
+    pub fn has_next_level(&self) -> bool {
+        let next_index = self.current_level_index + 1;
+        next_index < self.available_levels.len()
+            && (self.required_stars(next_index).is_none() || self.is_level_unlocked(next_index))
+    }
 }
 
 impl Default for LevelManager {
@@ -106,10 +244,12 @@ impl Default for LevelManager {
                 "level_02_transfer".to_string(),
                 "level_03_multiple_routes".to_string(),
                 "level_04_time_pressure".to_string(),
+                BONUS_LEVELS[0].0.to_string(),
+                BONUS_LEVELS[1].0.to_string(),
             ],
             current_level_index: 0,
-            unlocked_levels: vec![true, false, false, false], // 只有第一关解锁
-            level_scores: HashMap::new(),
+            unlocked_levels: vec![true, false, false, false, false, false], // 只有第一关解锁
+            level_records: HashMap::new(),
         }
     }
 }
@@ -121,6 +261,40 @@ pub struct PathfindingGraph {
     pub connections: HashMap<GridPos, Vec<Connection>>,
     pub station_lookup: HashMap<String, GridPos>,
     pub route_network: HashMap<String, RouteInfo>,
+    // 高架层的节点和连接，与地面层按格子坐标分开存放；站点只在地面层
+    pub elevated_nodes: HashMap<GridPos, GraphNode>,
+    pub elevated_connections: HashMap<GridPos, Vec<Connection>>,
+}
+
+impl PathfindingGraph {
+    pub fn node(&self, position: GridPos, layer: NetworkLayer) -> Option<&GraphNode> {
+        match layer {
+            NetworkLayer::Ground => self.nodes.get(&position),
+            NetworkLayer::Elevated => self.elevated_nodes.get(&position),
+        }
+    }
+
+    /// 指定图层上从某个节点出发的连接
+    pub fn connections_from(
+        &self,
+        position: GridPos,
+        layer: NetworkLayer,
+    ) -> Option<&Vec<Connection>> {
+        match layer {
+            NetworkLayer::Ground => self.connections.get(&position),
+            NetworkLayer::Elevated => self.elevated_connections.get(&position),
+        }
+    }
+
+    pub fn connections_on_mut(
+        &mut self,
+        layer: NetworkLayer,
+    ) -> &mut HashMap<GridPos, Vec<Connection>> {
+        match layer {
+            NetworkLayer::Ground => &mut self.connections,
+            NetworkLayer::Elevated => &mut self.elevated_connections,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -143,6 +317,7 @@ pub enum GraphNodeType {
 #[allow(dead_code)]
 pub struct Connection {
     pub to: GridPos,
+    pub to_layer: NetworkLayer, // 目标节点所在图层，站点与高架路线段之间的连接跨层
     pub cost: f32,
     pub route_id: Option<String>,
     pub connection_type: ConnectionType,
@@ -167,12 +342,12 @@ pub struct RouteInfo {
 
 #[derive(Resource)]
 pub struct AudioAssets {
-    pub background_music: Handle<AudioSource>,
     pub segment_place_sound: Handle<AudioSource>,
     pub segment_remove_sound: Handle<AudioSource>,
     pub passenger_arrive_sound: Handle<AudioSource>,
     pub objective_complete_sound: Handle<AudioSource>,
     pub level_complete_sound: Handle<AudioSource>,
     pub button_click_sound: Handle<AudioSource>,
+    pub button_hover_sound: Handle<AudioSource>,
     pub error_sound: Handle<AudioSource>,
 }
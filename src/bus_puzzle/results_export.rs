@@ -0,0 +1,148 @@
+// src/bus_puzzle/results_export.rs - 关卡结果导出为 CSV
+
+use crate::bus_puzzle::{
+    get_text, share_code, ButtonComponent, ButtonType, CurrentLanguage, EventDeck, GameState,
+    GameStateEnum, LevelCompleteData, LevelManager, LocalizedTextComponent, RESULTS_EXPORTED,
+    RESULTS_EXPORT_FAILED,
+};
+use bevy::prelude::*;
+
+#[cfg(not(target_family = "wasm"))]
+const RESULTS_CSV_FILE: &str = "results.csv";
+
+const RESULTS_CSV_HEADER: &str =
+    "timestamp,level_id,score,time_seconds,cost,passengers_delivered,passengers_gave_up,seed";
+
+// ============ 结果导出插件 ============
+
+pub struct ResultsExportPlugin;
+
+impl Plugin for ResultsExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_export_results_button.run_if(in_state(GameStateEnum::LevelComplete)),
+        );
+    }
+}
+
+// ============ CSV 生成 ============
+
+fn results_csv_row(
+    game_state: &GameState,
+    level_complete_data: &LevelCompleteData,
+    seed: Option<String>,
+) -> String {
+    let level_id = game_state
+        .current_level
+        .as_ref()
+        .map(|level| level.id.as_str())
+        .unwrap_or("unknown");
+
+    let final_score = if level_complete_data.final_score > 0 {
+        level_complete_data.final_score
+    } else {
+        game_state.score.total_score
+    };
+
+    let completion_time = if level_complete_data.completion_time > 0.0 {
+        level_complete_data.completion_time
+    } else {
+        game_state.game_time
+    };
+
+    // seed 列记录随机事件的分享码；没有随机事件的关卡留空
+    format!(
+        "{},{},{},{:.1},{},{},{},{}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        csv_field(level_id),
+        final_score,
+        completion_time,
+        game_state.total_cost,
+        game_state.passenger_stats.total_arrived,
+        game_state.passenger_stats.total_gave_up,
+        csv_field(&seed.unwrap_or_default()),
+    )
+}
+
+/// 按 RFC 4180 转义文本字段：含逗号、引号或换行时加引号，内部引号写两次。
+/// 导入的关卡 ID 可能含有这些字符
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn append_results_row(row: &str) -> std::io::Result<()> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        use std::io::Write;
+
+        let is_new_file = !std::path::Path::new(RESULTS_CSV_FILE).exists();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(RESULTS_CSV_FILE)?;
+
+        if is_new_file {
+            writeln!(file, "{}", RESULTS_CSV_HEADER)?;
+        }
+        writeln!(file, "{}", row)?;
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        info!(
+            "WASM环境：结果CSV输出到日志\n{}\n{}",
+            RESULTS_CSV_HEADER, row
+        );
+    }
+
+    Ok(())
+}
+
+// ============ 结果界面按钮 ============
+
+fn handle_export_results_button(
+    button_query: Query<
+        (&Interaction, &ButtonComponent, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut button_texts: Query<(&mut LocalizedTextComponent, &mut Text)>,
+    game_state: Res<GameState>,
+    level_complete_data: Res<LevelCompleteData>,
+    level_manager: Res<LevelManager>,
+    deck: Res<EventDeck>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component, children) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed)
+            || button_component.button_type != ButtonType::ExportResultsCsv
+        {
+            continue;
+        }
+
+        let seed = share_code(&level_manager, &game_state, &deck);
+        let row = results_csv_row(&game_state, &level_complete_data, seed);
+        let result_key = match append_results_row(&row) {
+            Ok(()) => {
+                info!("📄 关卡结果已导出: {}", row);
+                &RESULTS_EXPORTED
+            }
+            Err(e) => {
+                warn!("导出关卡结果失败: {}", e);
+                &RESULTS_EXPORT_FAILED
+            }
+        };
+
+        // 在按钮上显示导出结果
+        for child in children.iter() {
+            if let Ok((mut localized, mut text)) = button_texts.get_mut(child) {
+                localized.key = result_key.key;
+                *text = Text::new(get_text(result_key, current_language.language));
+            }
+        }
+    }
+}
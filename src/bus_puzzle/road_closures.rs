@@ -0,0 +1,152 @@
+// src/bus_puzzle/road_closures.rs - 施工封路（关卡中途封闭一片格子，玩家需要绕行）
+
+use crate::bus_puzzle::{
+    get_text, show_contextual_tip, simulation_running, CurrentLanguage, ElevatedSegment, EventType,
+    GameState, GridPos, LevelEntity, LevelManager, RouteSegment, TipType, UIAssets, EFFECT_Z,
+    ROAD_REOPENED,
+};
+use bevy::{platform::collections::HashSet, prelude::*};
+
+/// 屏障底色（施工橙）和斜纹颜色
+const BARRIER_COLOR: Color = Color::srgba(0.95, 0.5, 0.1, 0.75);
+const BARRIER_STRIPE_COLOR: Color = Color::srgba(0.95, 0.95, 0.95, 0.9);
+
+/// 封路格子上的屏障精灵
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct RoadBarrier {
+    pub grid_pos: GridPos,
+}
+
+// ============ 施工封路插件 ============
+
+pub struct RoadClosuresPlugin;
+
+impl Plugin for RoadClosuresPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_road_closures.run_if(simulation_running));
+    }
+}
+
+/// 按游戏时间计算当前应封闭的格子（恢复存档、重新开始后也能得到正确状态）
+fn active_closures(game_state: &GameState) -> HashSet<GridPos> {
+    let Some(level_data) = &game_state.current_level else {
+        return HashSet::new();
+    };
+    let game_time = game_state.game_time;
+
+    level_data
+        .dynamic_events
+        .iter()
+        .filter(|event| {
+            game_time >= event.trigger_time
+                && event
+                    .duration
+                    .is_none_or(|duration| game_time < event.trigger_time + duration)
+        })
+        .filter_map(|event| match &event.event_type {
+            EventType::RoadClosure(tiles) => Some(tiles.iter().copied()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+// ============ 封路同步 ============
+
+/// 封路时停用格子上的路线段（不退款），解封后恢复；寻路图每帧重建，会自动绕行
+fn sync_road_closures(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    level_manager: Res<LevelManager>,
+    mut route_segments: Query<&mut RouteSegment, Without<ElevatedSegment>>, // 高架层不受封路影响
+    barriers: Query<(Entity, &RoadBarrier)>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let closed = active_closures(&game_state);
+    if closed == game_state.closed_tiles {
+        return;
+    }
+
+    let newly_closed: Vec<GridPos> = closed
+        .iter()
+        .filter(|pos| !game_state.closed_tiles.contains(*pos))
+        .copied()
+        .collect();
+    let reopened: Vec<GridPos> = game_state
+        .closed_tiles
+        .iter()
+        .filter(|pos| !closed.contains(*pos))
+        .copied()
+        .collect();
+
+    for mut segment in route_segments.iter_mut() {
+        if newly_closed.contains(&segment.grid_pos) {
+            segment.is_active = false;
+        } else if reopened.contains(&segment.grid_pos) {
+            segment.is_active = true;
+        }
+    }
+
+    for (entity, barrier) in barriers.iter() {
+        if reopened.contains(&barrier.grid_pos) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    if let Some(level_data) = &game_state.current_level {
+        let (width, height) = level_data.grid_size;
+        for pos in &newly_closed {
+            spawn_road_barrier(&mut commands, *pos, level_manager.tile_size, width, height);
+        }
+    }
+
+    if !newly_closed.is_empty() {
+        info!("🚧 道路封闭: {:?}", newly_closed);
+    }
+    if !reopened.is_empty() {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(&ROAD_REOPENED, current_language.language),
+            TipType::Strategy,
+            3.0,
+        );
+        info!("✅ 道路解封: {:?}", reopened);
+    }
+
+    game_state.closed_tiles = closed;
+}
+
+fn spawn_road_barrier(
+    commands: &mut Commands,
+    grid_pos: GridPos,
+    tile_size: f32,
+    width: u32,
+    height: u32,
+) {
+    let mut world_pos = grid_pos.to_world_pos(tile_size, width, height);
+    world_pos.z = EFFECT_Z;
+
+    commands
+        .spawn((
+            Sprite::from_color(BARRIER_COLOR, Vec2::splat(tile_size * 0.9)),
+            Transform::from_translation(world_pos),
+            RoadBarrier { grid_pos },
+            Name::new("Road Barrier"),
+        ))
+        .with_children(|parent| {
+            // 两道斜纹组成施工路障的 X 形
+            for angle in [45.0_f32, -45.0] {
+                parent.spawn((
+                    Sprite::from_color(
+                        BARRIER_STRIPE_COLOR,
+                        Vec2::new(tile_size * 1.1, tile_size * 0.12),
+                    ),
+                    Transform::from_xyz(0.0, 0.0, 0.01)
+                        .with_rotation(Quat::from_rotation_z(angle.to_radians())),
+                ));
+            }
+        });
+}
@@ -0,0 +1,275 @@
+// src/bus_puzzle/route_lines.rs - 命名线路（把路线段分组为带颜色的线路，公交车只在自己的线路上行驶）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, shortcut_modifier_pressed, show_contextual_tip,
+    ui_audio::GameplayUI, ButtonComponent, ButtonType, CurrentLanguage, GameState, GameStateEnum,
+    GridPos, InputState, NetworkLayer, SegmentPlacedEvent, SegmentRemovedEvent, TipType, UIAssets,
+    ACTIVE_LINE, LINE_ASSIGNED, ROUTE_LINES_HINT,
+};
+use bevy::{
+    platform::collections::HashMap,
+    prelude::{Val::Px, *},
+};
+
+/// 切换当前绘制线路的按键（Variant）
+const LINE_CYCLE_KEY: KeyCode = KeyCode::KeyV;
+
+/// 把鼠标下已放置的路线段改到当前线路的按键（Group）
+const LINE_ASSIGN_KEY: KeyCode = KeyCode::KeyG;
+
+/// 未分组的路线段（旧存档、回放）归入第一条线路
+pub const DEFAULT_LINE: u32 = 1;
+
+/// 高架层的路线段组成一条独立的快线，不参与线路分组
+pub const EXPRESS_LINE: u32 = 0;
+
+/// 高架快线的颜色
+const EXPRESS_LINE_COLOR: Color = Color::srgb(0.25, 0.85, 0.9);
+
+/// 线路颜色：线路 1 红色、线路 2 蓝色……
+pub const LINE_COLORS: [Color; 6] = [
+    Color::srgb(0.9, 0.2, 0.2),    // 红色
+    Color::srgb(0.2, 0.4, 0.95),   // 蓝色
+    Color::srgb(0.2, 0.8, 0.3),    // 绿色
+    Color::srgb(0.95, 0.8, 0.15),  // 黄色
+    Color::srgb(0.65, 0.3, 0.85),  // 紫色
+    Color::srgb(0.95, 0.55, 0.15), // 橙色
+];
+
+/// 路线段贴图混入线路颜色的比例，保留贴图本身的细节
+const SEGMENT_TINT_STRENGTH: f32 = 0.55;
+
+/// 线路编号从 1 开始，0 是高架快线
+pub fn line_color(line: u32) -> Color {
+    if line == EXPRESS_LINE {
+        return EXPRESS_LINE_COLOR;
+    }
+    LINE_COLORS[(line.max(1) as usize - 1) % LINE_COLORS.len()]
+}
+
+/// 寻路图和乘客记录中使用的线路 ID
+pub fn line_id(line: u32) -> String {
+    format!("line_{}", line)
+}
+
+/// 从线路 ID 取回线路编号
+pub fn line_number(line_id: &str) -> Option<u32> {
+    line_id.strip_prefix("line_")?.parse().ok()
+}
+
+/// 路线段所属的线路，以及当前新放置的路线段使用的线路
+#[derive(Resource, Clone)]
+pub struct RouteLines {
+    pub active_line: u32,
+    pub assignments: HashMap<GridPos, u32>,
+    pub revision: u32,     // 已放置的路线段改线路时递增
+    pub bus_revision: u32, // 公交车最近一次按哪个修订号生成
+}
+
+impl Default for RouteLines {
+    fn default() -> Self {
+        Self {
+            active_line: DEFAULT_LINE,
+            assignments: HashMap::default(),
+            revision: 0,
+            bus_revision: 0,
+        }
+    }
+}
+
+impl RouteLines {
+    pub fn line_of(&self, pos: GridPos) -> u32 {
+        self.assignments.get(&pos).copied().unwrap_or(DEFAULT_LINE)
+    }
+
+    /// 路线段贴图的颜色
+    pub fn segment_tint(&self, pos: GridPos) -> Color {
+        Color::WHITE.mix(&line_color(self.line_of(pos)), SEGMENT_TINT_STRENGTH)
+    }
+
+    /// 已放置的路线段用到的线路，按编号排序
+    pub fn lines_in_use(&self, placed: impl Iterator<Item = GridPos>) -> Vec<u32> {
+        let mut lines: Vec<u32> = placed.map(|pos| self.line_of(pos)).collect();
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+    }
+
+    fn cycle_active_line(&mut self) {
+        self.active_line = self.active_line % LINE_COLORS.len() as u32 + 1;
+    }
+}
+
+/// 显示当前线路的按钮文字
+#[derive(Component)]
+pub struct ActiveLineText;
+
+// ============ 命名线路插件 ============
+
+pub struct RouteLinesPlugin;
+
+impl Plugin for RouteLinesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RouteLines>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_route_lines)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_route_lines)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_active_line_button)
+            .add_systems(
+                Update,
+                (
+                    track_segment_lines,
+                    handle_line_input,
+                    update_active_line_button,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+pub fn reset_route_lines(mut lines: ResMut<RouteLines>) {
+    *lines = RouteLines::default();
+}
+
+/// 新放置的路线段加入当前线路，拆除时移除记录
+fn track_segment_lines(
+    mut placed_events: EventReader<SegmentPlacedEvent>,
+    mut removed_events: EventReader<SegmentRemovedEvent>,
+    mut lines: ResMut<RouteLines>,
+) {
+    // 高架路线段固定属于高架快线
+    for event in placed_events
+        .read()
+        .filter(|event| event.layer == NetworkLayer::Ground)
+    {
+        let active_line = lines.active_line;
+        lines.assignments.insert(event.position, active_line);
+    }
+    for event in removed_events
+        .read()
+        .filter(|event| event.layer == NetworkLayer::Ground)
+    {
+        lines.assignments.remove(&event.position);
+    }
+}
+
+fn handle_line_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    input_state: Res<InputState>,
+    game_state: Res<GameState>,
+    mut lines: ResMut<RouteLines>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let language = current_language.language;
+    let button_pressed = button_query.iter().any(|(interaction, button)| {
+        matches!(*interaction, Interaction::Pressed)
+            && matches!(button.button_type, ButtonType::CycleRouteLine)
+    });
+
+    // Ctrl+V 用于粘贴路线段
+    let cycle_pressed =
+        keyboard_input.just_pressed(LINE_CYCLE_KEY) && !shortcut_modifier_pressed(&keyboard_input);
+    if cycle_pressed || button_pressed {
+        lines.cycle_active_line();
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(&ROUTE_LINES_HINT, language),
+            TipType::Controls,
+            3.0,
+        );
+        info!("🎨 当前线路: {}", lines.active_line);
+    }
+
+    if keyboard_input.just_pressed(LINE_ASSIGN_KEY) {
+        let Some(pos) = input_state
+            .grid_cursor_pos
+            .filter(|pos| game_state.placed_segments.contains_key(pos))
+        else {
+            return;
+        };
+
+        let active_line = lines.active_line;
+        if lines.line_of(pos) != active_line {
+            lines.assignments.insert(pos, active_line);
+            lines.revision += 1;
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text_with_args(&LINE_ASSIGNED, language, &[&active_line.to_string()]),
+                TipType::Controls,
+                2.0,
+            );
+            info!("🎨 路线段 {:?} 改为线路 {}", pos, active_line);
+        }
+    }
+}
+
+fn spawn_active_line_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    lines: Res<RouteLines>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(10.0),
+                top: Px(550.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.2, 0.3)),
+            ZIndex(200),
+            GameplayUI,
+            ButtonComponent {
+                button_type: ButtonType::CycleRouteLine,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            Name::new("Active Line Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text_with_args(
+                    &ACTIVE_LINE,
+                    current_language.language,
+                    &[&lines.active_line.to_string()],
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(line_color(lines.active_line)),
+                ActiveLineText,
+            ));
+        });
+}
+
+fn update_active_line_button(
+    lines: Res<RouteLines>,
+    current_language: Res<CurrentLanguage>,
+    mut texts: Query<(&mut Text, &mut TextColor), With<ActiveLineText>>,
+) {
+    if !lines.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for (mut text, mut color) in texts.iter_mut() {
+        *text = Text::new(get_text_with_args(
+            &ACTIVE_LINE,
+            current_language.language,
+            &[&lines.active_line.to_string()],
+        ));
+        color.0 = line_color(lines.active_line);
+    }
+}
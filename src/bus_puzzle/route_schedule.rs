@@ -0,0 +1,631 @@
+// src/bus_puzzle/route_schedule.rs - 线路调度（每条线路的车辆数和发车频率，超出免费额度的运营按分钟计入成本）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, line_color, manual_fleet, simulation_running,
+    smart_bus_generation::spawn_smart_bus, ui_audio::GameplayUI, BusPathfindingManager,
+    BusRouteInfo, BusVehicle, ButtonComponent, ButtonType, CurrentLanguage, GameState,
+    GameStateEnum, Language, LevelManager, LocalizedText, PathfindingGraph, SimClock,
+    StationEntity, ThemeRegistry, UIAssets, CLOSE, FREQUENCY_HIGH, FREQUENCY_LOW, FREQUENCY_NORMAL,
+    NO_ROUTES, ROUTE_BUS_COUNT, ROUTE_FREQUENCY, ROUTE_MANAGEMENT, ROUTE_NAME,
+    ROUTE_OPERATING_COST,
+};
+use bevy::{
+    platform::collections::HashMap,
+    prelude::{Val::Px, *},
+};
+use serde::{Deserialize, Serialize};
+
+/// 每条线路最多的车辆数
+pub const MAX_BUSES_PER_ROUTE: u32 = 4;
+
+/// 每条线路免费的运营量，默认调度（1 辆车、正常频率）不产生运营费用
+const FREE_OPERATING_UNITS: u32 = 2;
+
+// ============ 线路调度数据 ============
+
+/// 发车频率，频率越高停靠越短、周转越快，运营费用也越高
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BusFrequency {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl BusFrequency {
+    /// 每站停靠时间（秒）
+    pub fn dwell_time(self) -> f32 {
+        match self {
+            BusFrequency::Low => 5.0,
+            BusFrequency::Normal => 3.0,
+            BusFrequency::High => 1.5,
+        }
+    }
+
+    /// 每辆车的运营量
+    fn operating_units(self) -> u32 {
+        match self {
+            BusFrequency::Low => 1,
+            BusFrequency::Normal => 2,
+            BusFrequency::High => 3,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            BusFrequency::Low => BusFrequency::Normal,
+            BusFrequency::Normal => BusFrequency::High,
+            BusFrequency::High => BusFrequency::Low,
+        }
+    }
+
+    fn label(self) -> &'static LocalizedText {
+        match self {
+            BusFrequency::Low => &FREQUENCY_LOW,
+            BusFrequency::Normal => &FREQUENCY_NORMAL,
+            BusFrequency::High => &FREQUENCY_HIGH,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RouteSchedule {
+    pub bus_count: u32,
+    pub frequency: BusFrequency,
+}
+
+impl Default for RouteSchedule {
+    fn default() -> Self {
+        Self {
+            bus_count: 1,
+            frequency: BusFrequency::Normal,
+        }
+    }
+}
+
+impl RouteSchedule {
+    /// 每分钟的运营费用
+    pub fn operating_cost_per_minute(&self) -> u32 {
+        (self.bus_count * self.frequency.operating_units()).saturating_sub(FREE_OPERATING_UNITS)
+    }
+}
+
+/// 线路的站点序列作为调度的键，线路重新生成后编号会变，站点不变
+pub fn route_key(stations: &[String]) -> String {
+    stations.join(" → ")
+}
+
+/// 本关各线路的调度设置
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct RouteSchedules {
+    pub schedules: HashMap<String, RouteSchedule>,
+}
+
+impl RouteSchedules {
+    pub fn get(&self, stations: &[String]) -> RouteSchedule {
+        self.schedules
+            .get(&route_key(stations))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 当前所有线路每分钟的运营费用
+    pub fn total_cost_per_minute(&self, bus_manager: &BusPathfindingManager) -> u32 {
+        bus_manager
+            .bus_routes
+            .values()
+            .map(|route| self.get(&route.stations).operating_cost_per_minute())
+            .sum()
+    }
+}
+
+/// 线路管理面板是否打开
+#[derive(Resource, Default)]
+pub struct RoutePanelOpen(pub bool);
+
+#[derive(Component)]
+pub struct RouteSchedulePanel;
+
+// ============ 线路调度插件 ============
+
+pub struct RouteSchedulePlugin;
+
+impl Plugin for RouteSchedulePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RouteSchedules>()
+            .init_resource::<RoutePanelOpen>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_route_schedules)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_route_schedules)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_route_panel_button)
+            .add_systems(OnExit(GameStateEnum::Playing), close_route_panel)
+            .add_systems(
+                Update,
+                (
+                    handle_route_schedule_buttons,
+                    apply_route_schedules,
+                    refresh_route_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            )
+            // 录像不记录运营支出，回放时不扣费
+            .add_systems(
+                Update,
+                charge_operating_costs
+                    .run_if(simulation_running)
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+/// 新关卡的线路从默认调度开始
+pub fn reset_route_schedules(
+    mut schedules: ResMut<RouteSchedules>,
+    mut bus_manager: ResMut<BusPathfindingManager>,
+) {
+    schedules.schedules.clear();
+    bus_manager.bus_routes.clear();
+}
+
+fn close_route_panel(mut panel_open: ResMut<RoutePanelOpen>) {
+    panel_open.0 = false;
+}
+
+/// 面板按线路编号排列
+fn sorted_routes(bus_manager: &BusPathfindingManager) -> Vec<&BusRouteInfo> {
+    let mut routes: Vec<_> = bus_manager.bus_routes.values().collect();
+    routes.sort_by(|a, b| (a.line, &a.route_id).cmp(&(b.line, &b.route_id)));
+    routes
+}
+
+// ============ 运营费用 ============
+
+/// 按游戏时间累计运营费用，满 1 时计入总成本（经营模式下从资金中扣除）
+fn charge_operating_costs(
+    schedules: Res<RouteSchedules>,
+    bus_manager: Res<BusPathfindingManager>,
+    mut game_state: ResMut<GameState>,
+    mut accrued: Local<f32>,
+    sim_clock: Res<SimClock>,
+) {
+    let per_minute = schedules.total_cost_per_minute(&bus_manager);
+    if per_minute == 0 {
+        return;
+    }
+
+    *accrued += per_minute as f32 * sim_clock.delta_secs() / 60.0;
+    if *accrued >= 1.0 {
+        let whole = accrued.floor();
+        *accrued -= whole;
+        game_state.total_cost += whole as u32;
+    }
+}
+
+// ============ 调度调整 ============
+
+fn handle_route_schedule_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    level_manager: Res<LevelManager>,
+    bus_manager: Res<BusPathfindingManager>,
+    mut schedules: ResMut<RouteSchedules>,
+    mut panel_open: ResMut<RoutePanelOpen>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        let index = match button_component.button_type {
+            ButtonType::ToggleRoutePanel => {
+                panel_open.0 = !panel_open.0;
+                continue;
+            }
+            ButtonType::CloseRoutePanel => {
+                panel_open.0 = false;
+                continue;
+            }
+            // 手动车队模式下车辆从车场购买，不能在这里增减
+            ButtonType::RouteBusesDown(_) | ButtonType::RouteBusesUp(_)
+                if manual_fleet(&level_manager) =>
+            {
+                continue;
+            }
+            ButtonType::RouteBusesDown(index)
+            | ButtonType::RouteBusesUp(index)
+            | ButtonType::CycleRouteFrequency(index) => index,
+            _ => continue,
+        };
+
+        let Some(route) = sorted_routes(&bus_manager).get(index).copied() else {
+            continue;
+        };
+        let schedule = schedules
+            .schedules
+            .entry(route_key(&route.stations))
+            .or_default();
+
+        match button_component.button_type {
+            ButtonType::RouteBusesDown(_) => {
+                schedule.bus_count = schedule.bus_count.saturating_sub(1).max(1);
+            }
+            ButtonType::RouteBusesUp(_) => {
+                schedule.bus_count = (schedule.bus_count + 1).min(MAX_BUSES_PER_ROUTE);
+            }
+            _ => schedule.frequency = schedule.frequency.next(),
+        }
+
+        info!(
+            "🚌 线路 {} 调度: {} 辆车，频率 {:?}，运营费用 {}/分钟",
+            route.route_id,
+            schedule.bus_count,
+            schedule.frequency,
+            schedule.operating_cost_per_minute()
+        );
+    }
+}
+
+/// 调度变化时直接增减车辆、更新停靠时间，不重建整个公交系统（车上的乘客不受影响）
+fn apply_route_schedules(
+    mut commands: Commands,
+    schedules: Res<RouteSchedules>,
+    mut bus_manager: ResMut<BusPathfindingManager>,
+    mut buses: Query<(Entity, &mut BusVehicle)>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+    pathfinding_graph: Res<PathfindingGraph>,
+) {
+    if !schedules.is_changed() || schedules.is_added() {
+        return;
+    }
+
+    let manual_fleet = manual_fleet(&level_manager);
+
+    for route in bus_manager.bus_routes.values_mut() {
+        let schedule = schedules.get(&route.stations);
+
+        let mut route_buses: Vec<_> = buses
+            .iter_mut()
+            .filter(|(_, bus)| bus.route_id == route.route_id)
+            .collect();
+        for (_, bus) in route_buses.iter_mut() {
+            bus.dwell_time = schedule.frequency.dwell_time();
+        }
+
+        // 手动车队模式下车辆数由车场分配决定
+        if manual_fleet {
+            continue;
+        }
+        route.max_vehicles = schedule.bus_count;
+
+        let current = route_buses.len() as u32;
+        if current > schedule.bus_count {
+            // 优先撤下载客最少的车
+            route_buses.sort_by_key(|(_, bus)| bus.current_passengers.len());
+            for (entity, bus) in route_buses
+                .iter()
+                .take((current - schedule.bus_count) as usize)
+            {
+                info!("🚌 线路 {} 撤下公交车 {}", route.route_id, bus.vehicle_id);
+                commands.entity(*entity).despawn();
+            }
+        } else if current < schedule.bus_count {
+            // 新车使用还没被占用的序号，避免与现有车辆重名
+            let mut bus_index = 0;
+            for _ in current..schedule.bus_count {
+                while route_buses.iter().any(|(_, bus)| {
+                    bus.vehicle_id == format!("智能公交_{}_{}", route.route_id, bus_index + 1)
+                }) {
+                    bus_index += 1;
+                }
+                spawn_smart_bus(
+                    &mut commands,
+                    &asset_server,
+                    &theme,
+                    &level_manager,
+                    &pathfinding_graph,
+                    route,
+                    schedule,
+                    bus_index,
+                );
+                bus_index += 1;
+            }
+        }
+    }
+}
+
+// ============ 线路管理面板 ============
+
+fn spawn_route_panel_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(10.0),
+                top: Px(500.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ZIndex(200),
+            GameplayUI,
+            ButtonComponent {
+                button_type: ButtonType::ToggleRoutePanel,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            Name::new("Route Panel Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&ROUTE_MANAGEMENT, current_language.language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 面板开关、调度或线路列表变化时重建面板
+fn refresh_route_panel(
+    mut commands: Commands,
+    panel_open: Res<RoutePanelOpen>,
+    schedules: Res<RouteSchedules>,
+    bus_manager: Res<BusPathfindingManager>,
+    stations: Query<&StationEntity>,
+    panels: Query<Entity, With<RouteSchedulePanel>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    level_manager: Res<LevelManager>,
+    mut shown_routes: Local<Vec<(String, u32)>>,
+) {
+    let routes = sorted_routes(&bus_manager);
+    let route_ids: Vec<(String, u32)> = routes
+        .iter()
+        .map(|route| (route.route_id.clone(), route.max_vehicles))
+        .collect();
+
+    if !panel_open.is_changed()
+        && !schedules.is_changed()
+        && !current_language.is_changed()
+        && route_ids == *shown_routes
+    {
+        return;
+    }
+    *shown_routes = route_ids;
+
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if panel_open.0 {
+        spawn_route_panel(
+            &mut commands,
+            &ui_assets,
+            &routes,
+            &schedules,
+            &bus_manager,
+            &stations,
+            manual_fleet(&level_manager),
+            current_language.language,
+        );
+    }
+}
+
+fn spawn_route_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    routes: &[&BusRouteInfo],
+    schedules: &RouteSchedules,
+    bus_manager: &BusPathfindingManager,
+    stations: &Query<&StationEntity>,
+    manual_fleet: bool,
+    language: Language,
+) {
+    // 线路里记录的是站点内部名称，显示时换成本地化名称
+    let station_names: HashMap<&str, String> = stations
+        .iter()
+        .map(|station| {
+            (
+                station.station_data.name.as_str(),
+                station.station_data.get_localized_name(language),
+            )
+        })
+        .collect();
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(300.0),
+                top: Px(90.0),
+                width: Px(340.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Px(10.0)),
+                row_gap: Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.95)),
+            Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.3, 0.7, 0.9)),
+            ZIndex(250),
+            GameplayUI,
+            RouteSchedulePanel,
+            Name::new("Route Schedule Panel"),
+        ))
+        .with_children(|parent| {
+            spawn_panel_text(
+                parent,
+                ui_assets,
+                get_text(&ROUTE_MANAGEMENT, language),
+                16.0,
+                Color::srgb(0.9, 0.9, 0.3),
+            );
+
+            if routes.is_empty() {
+                spawn_panel_text(
+                    parent,
+                    ui_assets,
+                    get_text(&NO_ROUTES, language),
+                    13.0,
+                    Color::srgb(0.8, 0.8, 0.8),
+                );
+            }
+
+            for (index, route) in routes.iter().enumerate() {
+                let schedule = schedules.get(&route.stations);
+                let stops = route
+                    .stations
+                    .iter()
+                    .map(|name| {
+                        station_names
+                            .get(name.as_str())
+                            .cloned()
+                            .unwrap_or_else(|| name.clone())
+                    })
+                    .collect::<Vec<_>>();
+
+                spawn_panel_text(
+                    parent,
+                    ui_assets,
+                    get_text_with_args(
+                        &ROUTE_NAME,
+                        language,
+                        &[&route.line.to_string(), &route_key(&stops)],
+                    ),
+                    14.0,
+                    line_color(route.line),
+                );
+
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Px(6.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        // 手动车队模式下只显示车辆数，车辆从车场购买
+                        if !manual_fleet {
+                            spawn_panel_button(
+                                parent,
+                                ui_assets,
+                                ButtonType::RouteBusesDown(index),
+                                "-".to_string(),
+                            );
+                        }
+                        spawn_panel_text(
+                            parent,
+                            ui_assets,
+                            get_text_with_args(
+                                &ROUTE_BUS_COUNT,
+                                language,
+                                &[&route.max_vehicles.to_string()],
+                            ),
+                            13.0,
+                            Color::WHITE,
+                        );
+                        if !manual_fleet {
+                            spawn_panel_button(
+                                parent,
+                                ui_assets,
+                                ButtonType::RouteBusesUp(index),
+                                "+".to_string(),
+                            );
+                        }
+                        spawn_panel_button(
+                            parent,
+                            ui_assets,
+                            ButtonType::CycleRouteFrequency(index),
+                            get_text_with_args(
+                                &ROUTE_FREQUENCY,
+                                language,
+                                &[&get_text(schedule.frequency.label(), language)],
+                            ),
+                        );
+                        spawn_panel_text(
+                            parent,
+                            ui_assets,
+                            format!("-{}", schedule.operating_cost_per_minute()),
+                            13.0,
+                            Color::srgb(1.0, 0.6, 0.4),
+                        );
+                    });
+            }
+
+            spawn_panel_text(
+                parent,
+                ui_assets,
+                get_text_with_args(
+                    &ROUTE_OPERATING_COST,
+                    language,
+                    &[&schedules.total_cost_per_minute(bus_manager).to_string()],
+                ),
+                14.0,
+                Color::srgb(1.0, 0.6, 0.4),
+            );
+
+            spawn_panel_button(
+                parent,
+                ui_assets,
+                ButtonType::CloseRoutePanel,
+                get_text(&CLOSE, language),
+            );
+        });
+}
+
+fn spawn_panel_text(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    font_size: f32,
+    color: Color,
+) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn spawn_panel_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    label: String,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Px(28.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.3, 0.45)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_panel_text(parent, ui_assets, label, 13.0, Color::WHITE);
+        });
+}
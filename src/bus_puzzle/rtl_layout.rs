@@ -0,0 +1,90 @@
+// src/bus_puzzle/rtl_layout.rs - 从右往左的界面布局（当前语言从右往左书写时镜像菜单和 HUD 面板）
+
+use crate::bus_puzzle::{CurrentLanguage, TextDirection};
+use bevy::{prelude::*, ui::UiSystem};
+
+/// 已按从右往左镜像过的界面节点。镜像是对称操作，切回从左往右的语言时再镜像一次即可还原
+#[derive(Component)]
+pub struct MirroredLayout;
+
+// ============ 从右往左布局插件 ============
+
+pub struct RtlLayoutPlugin;
+
+impl Plugin for RtlLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            // 新建的界面在同一帧布局前就完成镜像，不会闪一下从左往右的样子
+            mirror_ui_layout.before(UiSystem::Layout),
+        );
+    }
+}
+
+/// 让每个界面节点的镜像状态与当前语言的书写方向一致
+fn mirror_ui_layout(
+    mut commands: Commands,
+    current_language: Res<CurrentLanguage>,
+    mut nodes: Query<(
+        Entity,
+        &mut Node,
+        Option<&mut TextLayout>,
+        Has<MirroredLayout>,
+    )>,
+) {
+    let right_to_left = current_language.language.direction() == TextDirection::RightToLeft;
+
+    let mut count = 0;
+    for (entity, mut node, text_layout, mirrored) in nodes.iter_mut() {
+        if mirrored == right_to_left {
+            continue;
+        }
+
+        mirror_node(&mut node);
+        if let Some(mut text_layout) = text_layout {
+            text_layout.justify = match text_layout.justify {
+                JustifyText::Left => JustifyText::Right,
+                JustifyText::Right => JustifyText::Left,
+                justify => justify,
+            };
+        }
+        if right_to_left {
+            commands.entity(entity).insert(MirroredLayout);
+        } else {
+            commands.entity(entity).remove::<MirroredLayout>();
+        }
+        count += 1;
+    }
+
+    if count > 0 && current_language.is_changed() {
+        info!(
+            "↔️ 界面方向: {:?}（{} 个节点）",
+            current_language.language.direction(),
+            count
+        );
+    }
+}
+
+/// 左右对调：定位、外边距、内边距和边框交换左右两侧，横向排列反向，
+/// 纵向排列的交叉轴（水平方向）对齐也对调
+fn mirror_node(node: &mut Node) {
+    std::mem::swap(&mut node.left, &mut node.right);
+    std::mem::swap(&mut node.margin.left, &mut node.margin.right);
+    std::mem::swap(&mut node.padding.left, &mut node.padding.right);
+    std::mem::swap(&mut node.border.left, &mut node.border.right);
+
+    node.flex_direction = match node.flex_direction {
+        FlexDirection::Row => FlexDirection::RowReverse,
+        FlexDirection::RowReverse => FlexDirection::Row,
+        direction => {
+            node.align_items = match node.align_items {
+                AlignItems::Start => AlignItems::End,
+                AlignItems::End => AlignItems::Start,
+                AlignItems::FlexStart => AlignItems::FlexEnd,
+                AlignItems::FlexEnd => AlignItems::FlexStart,
+                align => align,
+            };
+            direction
+        }
+    };
+}
@@ -0,0 +1,222 @@
+// src/bus_puzzle/save_game.rs - 关卡进度存档（解锁关卡、最佳分数、最佳用时、星级和蓝图库），启动时读取，每次通关后写入
+
+use crate::bus_puzzle::{
+    calculate_level_stars, decode_blueprint, encode_blueprint, ActiveChallenge, BlueprintLibrary,
+    CustomLevel, FlushSavesEvent, GameState, LevelCompletedEvent, LevelManager, LevelRecord,
+    MirrorMode,
+};
+use bevy::prelude::*;
+
+#[cfg(not(target_family = "wasm"))]
+const SAVE_GAME_FILE: &str = "save_game.txt";
+
+#[cfg(target_family = "wasm")]
+const SAVE_GAME_KEY: &str = "save_game";
+
+/// 解锁进度所在行的键名，其余每行是一个关卡的成绩或一个蓝图
+const UNLOCKED_KEY: &str = "unlocked";
+const BLUEPRINT_KEY: &str = "blueprint";
+
+// ============ 存档插件 ============
+
+pub struct SaveGamePlugin;
+
+impl Plugin for SaveGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_save_game)
+            .add_systems(Update, record_level_completion)
+            .add_systems(PostUpdate, flush_save_game); // 退出前保存进度
+    }
+}
+
+// ============ 存档序列化 ============
+
+/// 第一行为解锁进度（unlocked=1,1,0,0），之后每行为 关卡ID=最佳分数,最佳用时,星级，
+/// 最后按保存顺序每行一个蓝图（blueprint=名称|路线段;路线段）
+fn serialize_save_game(level_manager: &LevelManager, blueprints: &BlueprintLibrary) -> String {
+    let unlocked = level_manager
+        .unlocked_levels
+        .iter()
+        .map(|unlocked| if *unlocked { "1" } else { "0" })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut records: Vec<String> = level_manager
+        .level_records
+        .iter()
+        .map(|(level_id, record)| {
+            format!(
+                "{}={},{:.1},{}",
+                level_id, record.best_score, record.best_time, record.stars
+            )
+        })
+        .collect();
+    records.sort();
+
+    std::iter::once(format!("{}={}", UNLOCKED_KEY, unlocked))
+        .chain(records)
+        .chain(
+            blueprints
+                .blueprints
+                .iter()
+                .map(|blueprint| format!("{}={}", BLUEPRINT_KEY, encode_blueprint(blueprint))),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 逐行解析，无法识别的行直接跳过
+fn deserialize_save_game(
+    content: &str,
+    level_manager: &mut LevelManager,
+    blueprints: &mut BlueprintLibrary,
+) {
+    for line in content.lines() {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+
+        if key == UNLOCKED_KEY {
+            // 关卡数量可能随版本变化，只覆盖两边都有的部分；第一关始终解锁
+            for (unlocked, saved) in level_manager
+                .unlocked_levels
+                .iter_mut()
+                .zip(value.split(','))
+            {
+                *unlocked |= saved.trim() == "1";
+            }
+            continue;
+        }
+
+        if key == BLUEPRINT_KEY {
+            if let Some(blueprint) = decode_blueprint(value) {
+                blueprints.store(blueprint);
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+        if let [score, time, stars] = fields[..]
+            && let (Ok(best_score), Ok(best_time), Ok(stars)) =
+                (score.parse(), time.parse(), stars.parse::<u32>())
+        {
+            level_manager.level_records.insert(
+                key.to_string(),
+                LevelRecord {
+                    best_score,
+                    best_time,
+                    stars: stars.min(3),
+                },
+            );
+        }
+    }
+}
+
+// ============ 读取与写入 ============
+
+fn load_save_game(
+    mut level_manager: ResMut<LevelManager>,
+    mut blueprints: ResMut<BlueprintLibrary>,
+) {
+    #[cfg(not(target_family = "wasm"))]
+    let content = std::fs::read_to_string(SAVE_GAME_FILE).ok();
+
+    #[cfg(target_family = "wasm")]
+    let content = crate::bus_puzzle::web_storage::read(SAVE_GAME_KEY);
+
+    let Some(content) = content else {
+        info!("未找到存档，从第一关开始");
+        return;
+    };
+
+    deserialize_save_game(&content, &mut level_manager, &mut blueprints);
+    info!(
+        "💾 已读取存档: 解锁 {:?}，{} 个关卡有成绩，{} 个蓝图",
+        level_manager.unlocked_levels,
+        level_manager.level_records.len(),
+        blueprints.blueprints.len()
+    );
+}
+
+pub fn write_save_game(level_manager: &LevelManager, blueprints: &BlueprintLibrary) {
+    let content = serialize_save_game(level_manager, blueprints);
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(SAVE_GAME_FILE, content) {
+            warn!("保存游戏进度失败: {}", e);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    crate::bus_puzzle::web_storage::write(SAVE_GAME_KEY, &content);
+}
+
+/// 通关后更新最佳成绩并解锁下一关，立即写入存档
+fn record_level_completion(
+    mut level_completed_events: EventReader<LevelCompletedEvent>,
+    active_challenge: Res<ActiveChallenge>,
+    custom_level: Res<CustomLevel>,
+    mirror_mode: Res<MirrorMode>,
+    game_state: Res<GameState>,
+    mut level_manager: ResMut<LevelManager>,
+    blueprints: Res<BlueprintLibrary>,
+) {
+    for event in level_completed_events.read() {
+        // 每周挑战、自定义关卡和镜像关卡有各自的成绩记录
+        if active_challenge.0.is_some() || custom_level.0.is_some() || mirror_mode.enabled {
+            continue;
+        }
+
+        let Some(level_data) = &game_state.current_level else {
+            continue;
+        };
+        let index = level_manager.current_level_index;
+        if level_manager.available_levels.get(index) != Some(&level_data.id) {
+            continue;
+        }
+
+        let stars = calculate_level_stars(
+            event.final_score,
+            event.completion_time,
+            game_state.total_cost,
+            &level_data.scoring,
+        );
+        let record = level_manager
+            .level_records
+            .entry(level_data.id.clone())
+            .or_insert(LevelRecord {
+                best_score: 0,
+                best_time: event.completion_time,
+                stars: 0,
+            });
+        if event.final_score > record.best_score {
+            record.best_score = event.final_score;
+            info!(
+                "🏅 关卡 {} 新的最高分: {}",
+                level_data.id, event.final_score
+            );
+        }
+        record.best_time = record.best_time.min(event.completion_time);
+        record.stars = record.stars.max(stars);
+
+        // 通关即解锁下一关，直接回到主菜单也不会丢失进度（奖励关卡按星星总数解锁）
+        if level_manager.required_stars(index + 1).is_none()
+            && let Some(next_unlocked) = level_manager.unlocked_levels.get_mut(index + 1)
+        {
+            *next_unlocked = true;
+        }
+
+        write_save_game(&level_manager, &blueprints);
+    }
+}
+
+fn flush_save_game(
+    mut flush_events: EventReader<FlushSavesEvent>,
+    level_manager: Res<LevelManager>,
+    blueprints: Res<BlueprintLibrary>,
+) {
+    if flush_events.read().last().is_some() {
+        write_save_game(&level_manager, &blueprints);
+    }
+}
@@ -0,0 +1,155 @@
+// src/bus_puzzle/scenario.rs - 剧情时间线（关卡文件里按顺序编排的“条件 → 动作”步骤，无需改代码即可编写剧情关卡）
+
+use crate::bus_puzzle::{
+    show_contextual_tip, simulation_running, DynamicEvent, EventType, GameState, GameStateEnum,
+    GridPos, LevelData, PassengerDemand, TipType, UIAssets, MIN_EVENT_DELAY,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 剧情消息显示时长
+const SCENARIO_TIP_DURATION: f32 = 6.0;
+
+// ============ 时间线数据 ============
+
+/// 时间线中的一步：上一步触发后开始检查条件，满足时依次执行动作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioStep {
+    pub when: ScenarioCondition,
+    pub actions: Vec<ScenarioAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScenarioCondition {
+    Time(f32),              // 游戏时间达到指定秒数
+    Delay(f32),             // 上一步触发（第一步为开局）后经过指定秒数
+    PassengersArrived(u32), // 到达乘客数达到指定值
+    PassengersGaveUp(u32),  // 放弃乘客数达到指定值
+    CostAbove(u32),         // 建设成本超过指定值
+    SegmentsPlaced(u32),    // 已放置的路线段数达到指定值
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScenarioAction {
+    ShowMessage(String),
+    SpawnDemand(PassengerDemand),
+    DisableSegment(GridPos),
+    /// 任意关卡事件，持续时间为空时事件不会自动结束
+    Event {
+        event_type: EventType,
+        duration: Option<f32>,
+    },
+}
+
+impl ScenarioAction {
+    /// 除消息外的动作都转换为关卡事件，由 handle_dynamic_events 统一处理
+    fn to_event(&self, fired_at: f32) -> Option<DynamicEvent> {
+        let (event_type, duration) = match self {
+            ScenarioAction::ShowMessage(_) => return None,
+            ScenarioAction::SpawnDemand(demand) => (EventType::NewDemand(demand.clone()), None),
+            ScenarioAction::DisableSegment(pos) => (EventType::SegmentFailure(*pos), None),
+            ScenarioAction::Event {
+                event_type,
+                duration,
+            } => (event_type.clone(), *duration),
+        };
+        Some(DynamicEvent {
+            trigger_time: fired_at + MIN_EVENT_DELAY,
+            event_type,
+            duration,
+        })
+    }
+}
+
+impl ScenarioCondition {
+    fn is_met(&self, game_state: &GameState, previous_fired_at: f32) -> bool {
+        let stats = &game_state.passenger_stats;
+        match self {
+            ScenarioCondition::Time(time) => game_state.game_time >= *time,
+            ScenarioCondition::Delay(delay) => game_state.game_time >= previous_fired_at + delay,
+            ScenarioCondition::PassengersArrived(count) => stats.total_arrived >= *count,
+            ScenarioCondition::PassengersGaveUp(count) => stats.total_gave_up >= *count,
+            ScenarioCondition::CostAbove(cost) => game_state.total_cost > *cost,
+            ScenarioCondition::SegmentsPlaced(count) => {
+                game_state.segment_count() >= *count as usize
+            }
+        }
+    }
+}
+
+/// 时间线进度：已触发的各步骤的游戏时间（按顺序），自动存档时一并保存
+#[derive(Resource, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioProgress {
+    pub fired_at: Vec<f32>,
+}
+
+/// 把某一步的动作写入关卡事件（恢复存档时也用它重建已触发步骤的事件）
+pub fn push_step_events(level_data: &mut LevelData, step_index: usize, fired_at: f32) {
+    let Some(step) = level_data.scenario.get(step_index) else {
+        return;
+    };
+    let events: Vec<DynamicEvent> = step
+        .actions
+        .iter()
+        .filter_map(|action| action.to_event(fired_at))
+        .collect();
+    level_data.dynamic_events.extend(events);
+}
+
+// ============ 剧情时间线插件 ============
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScenarioProgress>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_scenario)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_scenario)
+            .add_systems(Update, advance_scenario.run_if(simulation_running));
+    }
+}
+
+pub fn reset_scenario(mut progress: ResMut<ScenarioProgress>) {
+    *progress = ScenarioProgress::default();
+}
+
+/// 每帧最多触发一步，保证相邻步骤的 Delay 从上一步的触发时间算起
+fn advance_scenario(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut progress: ResMut<ScenarioProgress>,
+    ui_assets: Res<UIAssets>,
+) {
+    let step_index = progress.fired_at.len();
+    let Some(step) = game_state
+        .current_level
+        .as_ref()
+        .and_then(|level_data| level_data.scenario.get(step_index))
+    else {
+        return;
+    };
+
+    let previous_fired_at = progress.fired_at.last().copied().unwrap_or(0.0);
+    if !step.when.is_met(&game_state, previous_fired_at) {
+        return;
+    }
+
+    for action in &step.actions {
+        if let ScenarioAction::ShowMessage(message) = action {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                message,
+                TipType::Strategy,
+                SCENARIO_TIP_DURATION,
+            );
+        }
+    }
+
+    let fired_at = game_state.game_time;
+    info!("🎬 剧情第 {} 步触发: {:?}", step_index + 1, step.when);
+    if let Some(level_data) = &mut game_state.current_level {
+        push_step_events(level_data, step_index, fired_at);
+    }
+    progress.fired_at.push(fired_at);
+}
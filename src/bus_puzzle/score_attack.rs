@@ -0,0 +1,279 @@
+// src/bus_puzzle/score_attack.rs - 分数冲刺模式（连续到达累积连击倍率）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ui_audio::GameplayUI, ui_audio::MainMenuUI, ButtonComponent,
+    ButtonType, CurrentLanguage, GameStateEnum, Language, PassengerArrivedEvent,
+    PassengerGaveUpEvent, SimClock, UIAssets, COMBO_COUNTER, SCORE_ATTACK_MODE,
+};
+use bevy::prelude::{
+    Val::{Percent, Px},
+    *,
+};
+
+/// 两次到达间隔不超过该时间（秒）才算连击
+pub const COMBO_WINDOW: f32 = 4.0;
+
+/// 每位到达乘客的基础连击分
+const COMBO_BASE_POINTS: u32 = 10;
+
+/// 每连击多少次倍率 +1
+const COMBO_STEP: u32 = 3;
+
+/// 连击倍率上限
+const MAX_COMBO_MULTIPLIER: u32 = 5;
+
+// ============ 分数冲刺资源 ============
+
+#[derive(Resource, Default)]
+pub struct ScoreAttackMode {
+    pub enabled: bool,
+    pub combo: u32,           // 当前连击数
+    pub best_combo: u32,      // 本关最高连击
+    pub combo_time_left: f32, // 连击窗口剩余时间
+    pub bonus_points: u32,    // 本关累计连击加分
+}
+
+impl ScoreAttackMode {
+    pub fn combo_multiplier(&self) -> u32 {
+        (1 + self.combo / COMBO_STEP).min(MAX_COMBO_MULTIPLIER)
+    }
+
+    pub fn reset_level(&mut self) {
+        self.combo = 0;
+        self.best_combo = 0;
+        self.combo_time_left = 0.0;
+        self.bonus_points = 0;
+    }
+
+    fn register_arrival(&mut self) {
+        self.combo = if self.combo_time_left > 0.0 {
+            self.combo + 1
+        } else {
+            1
+        };
+        self.combo_time_left = COMBO_WINDOW;
+        self.best_combo = self.best_combo.max(self.combo);
+        self.bonus_points += COMBO_BASE_POINTS * self.combo_multiplier();
+    }
+
+    fn break_combo(&mut self) {
+        self.combo = 0;
+        self.combo_time_left = 0.0;
+    }
+}
+
+#[derive(Component)]
+pub struct ScoreAttackToggleText;
+
+#[derive(Component)]
+pub struct ComboText;
+
+// ============ 分数冲刺插件 ============
+
+pub struct ScoreAttackPlugin;
+
+impl Plugin for ScoreAttackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScoreAttackMode>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_score_attack_toggle)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_score_attack)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_score_attack)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_combo_hud)
+            .add_systems(
+                Update,
+                (handle_score_attack_buttons, update_score_attack_toggle_text)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(
+                Update,
+                (update_combo, update_combo_hud)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+// ============ 主菜单开关 ============
+
+fn setup_score_attack_toggle(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    score_attack: Res<ScoreAttackMode>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(60.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::ToggleScoreAttack,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            MainMenuUI,
+            Name::new("Score Attack Toggle"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(score_attack_toggle_label(
+                    &score_attack,
+                    current_language.language,
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                ScoreAttackToggleText,
+            ));
+        });
+}
+
+fn score_attack_toggle_label(score_attack: &ScoreAttackMode, language: Language) -> String {
+    let check = if score_attack.enabled { "[x]" } else { "[ ]" };
+    format!("{} {}", check, get_text(&SCORE_ATTACK_MODE, language))
+}
+
+fn handle_score_attack_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut score_attack: ResMut<ScoreAttackMode>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if matches!(*interaction, Interaction::Pressed)
+            && button_component.button_type == ButtonType::ToggleScoreAttack
+        {
+            score_attack.enabled = !score_attack.enabled;
+            info!("🔥 分数冲刺模式: {}", score_attack.enabled);
+        }
+    }
+}
+
+fn update_score_attack_toggle_text(
+    score_attack: Res<ScoreAttackMode>,
+    current_language: Res<CurrentLanguage>,
+    mut toggle_texts: Query<&mut Text, With<ScoreAttackToggleText>>,
+) {
+    if !score_attack.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in toggle_texts.iter_mut() {
+        *text = Text::new(score_attack_toggle_label(
+            &score_attack,
+            current_language.language,
+        ));
+    }
+}
+
+// ============ 连击计算 ============
+
+fn reset_score_attack(mut score_attack: ResMut<ScoreAttackMode>) {
+    score_attack.reset_level();
+}
+
+fn update_combo(
+    sim_clock: Res<SimClock>,
+    mut score_attack: ResMut<ScoreAttackMode>,
+    mut arrived_events: EventReader<PassengerArrivedEvent>,
+    mut gave_up_events: EventReader<PassengerGaveUpEvent>,
+) {
+    if !score_attack.enabled {
+        arrived_events.clear();
+        gave_up_events.clear();
+        return;
+    }
+
+    if score_attack.combo_time_left > 0.0 {
+        score_attack.combo_time_left -= sim_clock.delta_secs();
+        if score_attack.combo_time_left <= 0.0 {
+            score_attack.break_combo();
+        }
+    }
+
+    for _ in arrived_events.read() {
+        score_attack.register_arrival();
+    }
+
+    if gave_up_events.read().last().is_some() && score_attack.combo > 0 {
+        info!("💔 乘客放弃，连击中断（{} 连击）", score_attack.combo);
+        score_attack.break_combo();
+    }
+}
+
+// ============ 连击显示 ============
+
+fn spawn_combo_hud(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    score_attack: Res<ScoreAttackMode>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !score_attack.enabled {
+        return;
+    }
+
+    commands.spawn((
+        Text::new(combo_label(&score_attack, current_language.language)),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.6, 0.1)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Percent(50.0),
+            top: Px(44.0),
+            margin: UiRect::left(Px(-80.0)),
+            ..default()
+        },
+        GameplayUI,
+        ComboText,
+        Name::new("Combo Counter"),
+    ));
+}
+
+fn combo_label(score_attack: &ScoreAttackMode, language: Language) -> String {
+    get_text_with_args(
+        &COMBO_COUNTER,
+        language,
+        &[
+            &score_attack.combo.to_string(),
+            &score_attack.combo_multiplier().to_string(),
+            &score_attack.bonus_points.to_string(),
+        ],
+    )
+}
+
+fn update_combo_hud(
+    score_attack: Res<ScoreAttackMode>,
+    current_language: Res<CurrentLanguage>,
+    mut combo_texts: Query<(&mut Text, &mut TextColor), With<ComboText>>,
+) {
+    if !score_attack.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for (mut text, mut color) in combo_texts.iter_mut() {
+        *text = Text::new(combo_label(&score_attack, current_language.language));
+        // 连击窗口快结束时变暗提示
+        color.0 = if score_attack.combo_time_left > COMBO_WINDOW * 0.3 {
+            Color::srgb(1.0, 0.6, 0.1)
+        } else {
+            Color::srgb(0.6, 0.6, 0.6)
+        };
+    }
+}
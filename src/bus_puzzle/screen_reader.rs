@@ -0,0 +1,367 @@
+// src/bus_puzzle/screen_reader.rs - 读屏辅助（原生平台走 AccessKit，网页端写入 ARIA live 区域）
+
+use crate::bus_puzzle::{
+    format_time, get_text, get_text_with_args, CostText, CurrentLanguage, GameOverData, GameState,
+    GameStateEnum, LevelCompletedEvent, ObjectiveCompletedEvent, PassengerCountText, ScoreText,
+    TimerText, FAILURE_REASON, LEVEL_COMPLETE_ANNOUNCEMENT, MISSION_FAILED,
+    OBJECTIVE_COMPLETED_ANNOUNCEMENT, SCREEN_READER_STATUS,
+};
+use accesskit::{Live, Node as AccessNode, Role};
+use bevy::{
+    a11y::AccessibilityNode,
+    input_focus::InputFocus,
+    prelude::{Val::Px, *},
+    ui::UiSystem,
+};
+
+#[cfg(target_family = "wasm")]
+const ARIA_LIVE_REGION_ID: &str = "last-stop-announcer";
+
+/// 键盘焦点的高亮颜色
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+// ============ 读屏组件与事件 ============
+
+/// 需要读屏软件朗读的一句话
+#[derive(Event)]
+pub struct ScreenReaderAnnouncement(pub String);
+
+/// 不可见的播报区域，AccessKit 把它作为 live region 暴露给读屏软件
+#[derive(Component)]
+pub struct AnnouncementRegion;
+
+/// 键盘焦点高亮，只移除由本系统添加的描边
+#[derive(Component)]
+pub struct KeyboardFocusOutline;
+
+// ============ 读屏辅助插件 ============
+
+pub struct ScreenReaderPlugin;
+
+impl Plugin for ScreenReaderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputFocus>()
+            .add_event::<ScreenReaderAnnouncement>()
+            .add_systems(Startup, spawn_announcement_region)
+            .add_systems(OnEnter(GameStateEnum::GameOver), announce_game_over)
+            .add_systems(
+                PreUpdate,
+                // 在 UI 交互计算之后模拟按下，按钮处理系统在同一帧的 Update 中就能看到
+                activate_focused_button.after(UiSystem::Focus),
+            )
+            .add_systems(
+                Update,
+                (
+                    focus_hovered_button,
+                    cycle_button_focus,
+                    announce_focus_change,
+                    announce_status,
+                    announce_objectives,
+                    announce_level_complete,
+                    refresh_button_labels,
+                    annotate_hud_values,
+                    publish_announcements,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn spawn_announcement_region(mut commands: Commands) {
+    let mut node = AccessNode::new(Role::Status);
+    node.set_live(Live::Polite);
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Px(0.0),
+            height: Px(0.0),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        AccessibilityNode(node),
+        AnnouncementRegion,
+        Name::new("Screen Reader Announcer"),
+    ));
+}
+
+// ============ 键盘焦点 ============
+
+fn button_label(entity: Entity, children: &Query<&Children>, texts: &Query<&Text>) -> String {
+    children
+        .iter_descendants(entity)
+        .filter_map(|child| texts.get(child).ok())
+        .map(|text| text.0.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 鼠标悬停的按钮同时获得焦点，读屏软件会朗读它
+fn focus_hovered_button(
+    buttons: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    mut focus: ResMut<InputFocus>,
+) {
+    for (entity, interaction) in buttons.iter() {
+        if *interaction == Interaction::Hovered && focus.0 != Some(entity) {
+            focus.set(entity);
+        }
+    }
+}
+
+/// Tab / Shift+Tab 按屏幕位置（从上到下、从左到右）切换按钮焦点，并给焦点按钮加上描边
+fn cycle_button_focus(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    buttons: Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Button>>,
+    outlined: Query<Entity, With<KeyboardFocusOutline>>,
+    mut focus: ResMut<InputFocus>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+
+    let mut ordered: Vec<(Entity, Vec3)> = buttons
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation()))
+        .collect();
+    if ordered.is_empty() {
+        return;
+    }
+    ordered.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let backwards = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let current = focus
+        .0
+        .and_then(|focused| ordered.iter().position(|(entity, _)| *entity == focused));
+    let next = match (current, backwards) {
+        (Some(index), false) => (index + 1) % ordered.len(),
+        (Some(index), true) => (index + ordered.len() - 1) % ordered.len(),
+        (None, false) => 0,
+        (None, true) => ordered.len() - 1,
+    };
+
+    for entity in outlined.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Outline, KeyboardFocusOutline)>();
+    }
+
+    let entity = ordered[next].0;
+    commands.entity(entity).insert((
+        Outline::new(Px(3.0), Px(2.0), FOCUS_OUTLINE_COLOR),
+        KeyboardFocusOutline,
+    ));
+    focus.set(entity);
+}
+
+/// Enter 按下当前焦点按钮
+fn activate_focused_button(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    focus: Res<InputFocus>,
+    mut buttons: Query<&mut Interaction, With<Button>>,
+) {
+    if !keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        return;
+    }
+
+    if let Some(mut interaction) = focus.0.and_then(|entity| buttons.get_mut(entity).ok()) {
+        *interaction = Interaction::Pressed;
+    }
+}
+
+/// 焦点变化时朗读按钮文字
+fn announce_focus_change(
+    focus: Res<InputFocus>,
+    buttons: Query<(), With<Button>>,
+    children: Query<&Children>,
+    texts: Query<&Text>,
+    mut announcements: EventWriter<ScreenReaderAnnouncement>,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+
+    let Some(entity) = focus.0.filter(|entity| buttons.contains(*entity)) else {
+        return;
+    };
+
+    let label = button_label(entity, &children, &texts);
+    if !label.is_empty() {
+        announcements.write(ScreenReaderAnnouncement(label));
+    }
+}
+
+// ============ 状态播报 ============
+
+/// I 键朗读当前得分、时间和目标进度
+fn announce_status(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<GameStateEnum>>,
+    game_state: Res<GameState>,
+    current_language: Res<CurrentLanguage>,
+    mut announcements: EventWriter<ScreenReaderAnnouncement>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyI)
+        || !matches!(
+            current_state.get(),
+            GameStateEnum::Playing | GameStateEnum::Paused
+        )
+    {
+        return;
+    }
+
+    let completed = game_state
+        .objectives_completed
+        .iter()
+        .filter(|done| **done)
+        .count();
+
+    announcements.write(ScreenReaderAnnouncement(get_text_with_args(
+        &SCREEN_READER_STATUS,
+        current_language.language,
+        &[
+            &game_state.score.total_score.to_string(),
+            &format_time(game_state.game_time),
+            &completed.to_string(),
+            &game_state.objectives_completed.len().to_string(),
+        ],
+    )));
+}
+
+fn announce_objectives(
+    mut objective_events: EventReader<ObjectiveCompletedEvent>,
+    current_language: Res<CurrentLanguage>,
+    mut announcements: EventWriter<ScreenReaderAnnouncement>,
+) {
+    for _ in objective_events.read() {
+        announcements.write(ScreenReaderAnnouncement(get_text(
+            &OBJECTIVE_COMPLETED_ANNOUNCEMENT,
+            current_language.language,
+        )));
+    }
+}
+
+fn announce_level_complete(
+    mut level_completed_events: EventReader<LevelCompletedEvent>,
+    current_language: Res<CurrentLanguage>,
+    mut announcements: EventWriter<ScreenReaderAnnouncement>,
+) {
+    for event in level_completed_events.read() {
+        announcements.write(ScreenReaderAnnouncement(get_text_with_args(
+            &LEVEL_COMPLETE_ANNOUNCEMENT,
+            current_language.language,
+            &[
+                &event.final_score.to_string(),
+                &format_time(event.completion_time),
+            ],
+        )));
+    }
+}
+
+/// 失败界面出现时朗读标题和失败原因
+fn announce_game_over(
+    game_over_data: Res<GameOverData>,
+    current_language: Res<CurrentLanguage>,
+    mut announcements: EventWriter<ScreenReaderAnnouncement>,
+) {
+    let language = current_language.language;
+    announcements.write(ScreenReaderAnnouncement(format!(
+        "{} {}",
+        get_text(&MISSION_FAILED, language),
+        get_text_with_args(&FAILURE_REASON, language, &[game_over_data.reason.as_str()]),
+    )));
+}
+
+// ============ 无障碍节点更新 ============
+
+/// bevy_ui 只在按钮创建时计算标签，文字变化（例如切换语言）后需要重新同步
+fn refresh_button_labels(
+    changed_texts: Query<&ChildOf, Changed<Text>>,
+    mut buttons: Query<&mut AccessibilityNode, With<Button>>,
+    children: Query<&Children>,
+    texts: Query<&Text>,
+) {
+    for child_of in changed_texts.iter() {
+        let button = child_of.parent();
+        if let Ok(mut accessible) = buttons.get_mut(button) {
+            accessible.set_label(button_label(button, &children, &texts));
+        }
+    }
+}
+
+/// 把 HUD 数值（得分、时间、花费、乘客数）作为只读标签暴露给读屏软件
+fn annotate_hud_values(
+    mut commands: Commands,
+    mut hud_texts: Query<
+        (Entity, &Text, Option<&mut AccessibilityNode>),
+        (
+            Changed<Text>,
+            Or<(
+                With<ScoreText>,
+                With<TimerText>,
+                With<CostText>,
+                With<PassengerCountText>,
+            )>,
+        ),
+    >,
+) {
+    for (entity, text, accessible) in hud_texts.iter_mut() {
+        match accessible {
+            Some(mut accessible) => accessible.set_value(text.0.clone()),
+            None => {
+                let mut node = AccessNode::new(Role::Label);
+                node.set_value(text.0.clone());
+                commands.entity(entity).insert(AccessibilityNode(node));
+            }
+        }
+    }
+}
+
+fn publish_announcements(
+    mut announcement_events: EventReader<ScreenReaderAnnouncement>,
+    mut regions: Query<&mut AccessibilityNode, With<AnnouncementRegion>>,
+) {
+    let Some(announcement) = announcement_events.read().last() else {
+        return;
+    };
+
+    debug!("🔊 读屏播报: {}", announcement.0);
+    for mut region in regions.iter_mut() {
+        region.set_value(announcement.0.clone());
+    }
+
+    #[cfg(target_family = "wasm")]
+    announce_to_dom(&announcement.0);
+}
+
+/// 网页端写入视觉隐藏的 aria-live 区域
+#[cfg(target_family = "wasm")]
+fn announce_to_dom(text: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    let element = match document.get_element_by_id(ARIA_LIVE_REGION_ID) {
+        Some(element) => element,
+        None => {
+            let Ok(element) = document.create_element("div") else {
+                return;
+            };
+            element.set_id(ARIA_LIVE_REGION_ID);
+            let _ = element.set_attribute("role", "status");
+            let _ = element.set_attribute("aria-live", "polite");
+            let _ = element.set_attribute(
+                "style",
+                "position:absolute;width:1px;height:1px;overflow:hidden;clip:rect(0 0 0 0);",
+            );
+            if let Some(body) = document.body() {
+                let _ = body.append_child(&element);
+            }
+            element
+        }
+    };
+
+    element.set_text_content(Some(text));
+}
@@ -0,0 +1,493 @@
+// src/bus_puzzle/scripting.rs - Rhai 脚本钩子（scripts/*.rhai 订阅游戏事件并注入动态事件或乘客需求，无需重新编译）
+
+use crate::bus_puzzle::{
+    show_contextual_tip, DynamicEvent, EventType, GameState, GameStateEnum, GridPos,
+    PassengerArrivedEvent, PassengerColor, PassengerDemand, SegmentPlacedEvent, TipType, UIAssets,
+    WeatherKind,
+};
+use bevy::prelude::*;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, FuncArgs, Scope, AST};
+use std::sync::{Arc, Mutex};
+
+/// 脚本目录（相对于游戏运行目录），按文件名顺序加载其中的 .rhai 文件
+#[cfg(not(target_family = "wasm"))]
+const SCRIPTS_DIR: &str = "scripts";
+
+/// 单次钩子调用允许执行的操作数，防止脚本死循环卡住游戏
+const SCRIPT_MAX_OPERATIONS: u64 = 50_000;
+
+/// 脚本和剧情时间线注入的事件至少延后这么久触发，保证 handle_dynamic_events 能越过触发点
+pub const MIN_EVENT_DELAY: f32 = 0.1;
+
+/// 脚本生成的乘客需求在该时间段内按 DEMAND_SPAWN_RATE 陆续出现
+const DEMAND_SPAWN_WINDOW: f32 = 60.0;
+const DEMAND_SPAWN_RATE: f32 = 0.5;
+
+/// 脚本提示显示时长
+const SCRIPT_TIP_DURATION: f32 = 5.0;
+
+// 脚本可以定义的钩子函数
+const HOOK_LEVEL_START: &str = "on_level_start"; // (level_id)
+const HOOK_TICK: &str = "on_tick"; // (game_seconds)，每秒游戏时间调用一次
+const HOOK_SEGMENT_PLACED: &str = "on_segment_placed"; // (x, y, segment_type)
+const HOOK_PASSENGER_ARRIVED: &str = "on_passenger_arrived"; // (color, travel_time)
+
+// ============ 脚本命令 ============
+
+/// 脚本通过宿主函数提交的操作，钩子返回后在系统中统一应用到当前关卡
+#[derive(Debug)]
+enum ScriptCommand {
+    Event {
+        event_type: EventType,
+        delay: f32,
+        duration: Option<f32>,
+    },
+    Demand {
+        origin: String,
+        destination: String,
+        color: PassengerColor,
+        count: u32,
+        patience: f32,
+    },
+    Tip(String),
+}
+
+#[derive(Clone, Default)]
+struct ScriptQueue(Arc<Mutex<Vec<ScriptCommand>>>);
+
+impl ScriptQueue {
+    fn push(&self, command: ScriptCommand) {
+        if let Ok(mut commands) = self.0.lock() {
+            commands.push(command);
+        }
+    }
+
+    fn drain(&self) -> Vec<ScriptCommand> {
+        self.0
+            .lock()
+            .map(|mut commands| std::mem::take(&mut *commands))
+            .unwrap_or_default()
+    }
+}
+
+/// 脚本里的数字可能是整数也可能是小数，统一转成秒数
+fn to_f32(value: &Dynamic) -> f32 {
+    value
+        .as_float()
+        .map(|value| value as f32)
+        .or_else(|_| value.as_int().map(|value| value as f32))
+        .unwrap_or(0.0)
+}
+
+fn parse_color(name: &str) -> Option<PassengerColor> {
+    match name.to_ascii_lowercase().as_str() {
+        "red" => Some(PassengerColor::Red),
+        "blue" => Some(PassengerColor::Blue),
+        "green" => Some(PassengerColor::Green),
+        "yellow" => Some(PassengerColor::Yellow),
+        "purple" => Some(PassengerColor::Purple),
+        "orange" => Some(PassengerColor::Orange),
+        _ => None,
+    }
+}
+
+fn color_name(color: PassengerColor) -> &'static str {
+    match color {
+        PassengerColor::Red => "red",
+        PassengerColor::Blue => "blue",
+        PassengerColor::Green => "green",
+        PassengerColor::Yellow => "yellow",
+        PassengerColor::Purple => "purple",
+        PassengerColor::Orange => "orange",
+    }
+}
+
+fn parse_weather(name: &str) -> Option<WeatherKind> {
+    match name.to_ascii_lowercase().as_str() {
+        "rain" => Some(WeatherKind::Rain),
+        "snow" => Some(WeatherKind::Snow),
+        "fog" => Some(WeatherKind::Fog),
+        _ => None,
+    }
+}
+
+/// 把形如 [[x, y], [x, y]] 的数组解析成格子，忽略格式不对的元素
+fn parse_tiles(tiles: Array) -> Vec<GridPos> {
+    tiles
+        .into_iter()
+        .filter_map(|tile| {
+            let tile = tile.try_cast::<Array>()?;
+            let x = tile.first()?.as_int().ok()?;
+            let y = tile.get(1)?.as_int().ok()?;
+            Some(GridPos::new(x as i32, y as i32))
+        })
+        .collect()
+}
+
+/// 注册脚本可调用的宿主函数，它们只把命令放进队列
+fn register_host_functions(engine: &mut Engine, queue: &ScriptQueue) {
+    let q = queue.clone();
+    engine.register_fn(
+        "segment_failure",
+        move |x: i64, y: i64, delay: Dynamic, duration: Dynamic| {
+            q.push(ScriptCommand::Event {
+                event_type: EventType::SegmentFailure(GridPos::new(x as i32, y as i32)),
+                delay: to_f32(&delay),
+                duration: Some(to_f32(&duration)),
+            });
+        },
+    );
+
+    let q = queue.clone();
+    engine.register_fn(
+        "passenger_surge",
+        move |color: &str, multiplier: Dynamic, delay: Dynamic, duration: Dynamic| {
+            let Some(color) = parse_color(color) else {
+                warn!("脚本: 未知的乘客颜色 {}", color);
+                return;
+            };
+            q.push(ScriptCommand::Event {
+                event_type: EventType::SurgePassengers(color, to_f32(&multiplier)),
+                delay: to_f32(&delay),
+                duration: Some(to_f32(&duration)),
+            });
+        },
+    );
+
+    let q = queue.clone();
+    engine.register_fn(
+        "station_overload",
+        move |station: &str, delay: Dynamic, duration: Dynamic| {
+            q.push(ScriptCommand::Event {
+                event_type: EventType::StationOverload(station.to_string()),
+                delay: to_f32(&delay),
+                duration: Some(to_f32(&duration)),
+            });
+        },
+    );
+
+    // tiles 形如 [[x, y], [x, y]]
+    let q = queue.clone();
+    engine.register_fn(
+        "road_closure",
+        move |tiles: Array, delay: Dynamic, duration: Dynamic| {
+            let tiles = parse_tiles(tiles);
+            if tiles.is_empty() {
+                warn!("脚本: road_closure 没有有效的格子");
+                return;
+            }
+            q.push(ScriptCommand::Event {
+                event_type: EventType::RoadClosure(tiles),
+                delay: to_f32(&delay),
+                duration: Some(to_f32(&duration)),
+            });
+        },
+    );
+
+    // kind 为 "rain"、"snow" 或 "fog"
+    let q = queue.clone();
+    engine.register_fn(
+        "weather",
+        move |kind: &str, tiles: Array, delay: Dynamic, duration: Dynamic| {
+            let Some(kind) = parse_weather(kind) else {
+                warn!("脚本: 未知的天气 {}", kind);
+                return;
+            };
+            let tiles = parse_tiles(tiles);
+            if tiles.is_empty() {
+                warn!("脚本: weather 没有有效的格子");
+                return;
+            }
+            q.push(ScriptCommand::Event {
+                event_type: EventType::Weather(kind, tiles),
+                delay: to_f32(&delay),
+                duration: Some(to_f32(&duration)),
+            });
+        },
+    );
+
+    let q = queue.clone();
+    engine.register_fn(
+        "spawn_demand",
+        move |origin: &str, destination: &str, color: &str, count: i64, patience: Dynamic| {
+            let Some(color) = parse_color(color) else {
+                warn!("脚本: 未知的乘客颜色 {}", color);
+                return;
+            };
+            q.push(ScriptCommand::Demand {
+                origin: origin.to_string(),
+                destination: destination.to_string(),
+                color,
+                count: count.max(1) as u32,
+                patience: to_f32(&patience),
+            });
+        },
+    );
+
+    let q = queue.clone();
+    engine.register_fn("show_tip", move |text: &str| {
+        q.push(ScriptCommand::Tip(text.to_string()));
+    });
+}
+
+// ============ 脚本宿主 ============
+
+struct LoadedScript {
+    name: String,
+    ast: AST,
+    /// 钩子中的 this，每关重置，脚本用它保存计数等状态
+    state: Dynamic,
+}
+
+#[derive(Resource)]
+pub struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<LoadedScript>,
+    queue: ScriptQueue,
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        let queue = ScriptQueue::default();
+        let mut engine = Engine::new();
+        engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+        engine.on_print(|text| info!("📜 {}", text));
+        register_host_functions(&mut engine, &queue);
+
+        Self {
+            engine,
+            scripts: Vec::new(),
+            queue,
+        }
+    }
+}
+
+impl ScriptHost {
+    /// 编译脚本，出错时记录日志并跳过该脚本
+    fn load(&mut self, name: &str, source: &str) {
+        match self.engine.compile(source) {
+            Ok(ast) => {
+                let hooks: Vec<&str> = ast.iter_functions().map(|f| f.name).collect();
+                info!("📜 已加载脚本 {}，钩子: {:?}", name, hooks);
+                self.scripts.push(LoadedScript {
+                    name: name.to_string(),
+                    ast,
+                    state: Dynamic::from_map(Default::default()),
+                });
+            }
+            Err(e) => warn!("脚本 {} 编译失败: {}", name, e),
+        }
+    }
+
+    fn reset_state(&mut self) {
+        for script in &mut self.scripts {
+            script.state = Dynamic::from_map(Default::default());
+        }
+    }
+
+    /// 调用所有定义了该钩子的脚本，单个脚本出错不影响其他脚本
+    fn call_hook(&mut self, hook: &str, args: impl FuncArgs + Clone) {
+        for script in &mut self.scripts {
+            if !script.ast.iter_functions().any(|f| f.name == hook) {
+                continue;
+            }
+
+            let options = CallFnOptions::new()
+                .eval_ast(false)
+                .bind_this_ptr(&mut script.state);
+            if let Err(e) = self.engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &script.ast,
+                hook,
+                args.clone(),
+            ) {
+                warn!("脚本 {} 的 {} 出错: {}", script.name, hook, e);
+            }
+        }
+    }
+}
+
+// ============ 脚本插件 ============
+
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScriptHost>()
+            .add_systems(Startup, load_scripts)
+            .add_systems(
+                Update,
+                run_script_hooks.run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn load_scripts(mut host: ResMut<ScriptHost>) {
+    let Ok(entries) = std::fs::read_dir(SCRIPTS_DIR) else {
+        return;
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match std::fs::read_to_string(&path) {
+            Ok(source) => host.load(&path.display().to_string(), &source),
+            Err(e) => warn!("无法读取脚本 {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// 网页版没有本地文件，暂不支持加载脚本
+#[cfg(target_family = "wasm")]
+fn load_scripts(_host: ResMut<ScriptHost>) {}
+
+fn run_script_hooks(
+    mut commands: Commands,
+    mut host: ResMut<ScriptHost>,
+    mut game_state: ResMut<GameState>,
+    mut placed_events: EventReader<SegmentPlacedEvent>,
+    mut arrived_events: EventReader<PassengerArrivedEvent>,
+    ui_assets: Res<UIAssets>,
+    mut last_level: Local<Option<String>>,
+    mut last_time: Local<f32>,
+) {
+    if host.scripts.is_empty() {
+        placed_events.clear();
+        arrived_events.clear();
+        return;
+    }
+
+    let Some(level_id) = game_state
+        .current_level
+        .as_ref()
+        .map(|level| level.id.clone())
+    else {
+        return;
+    };
+    let game_time = game_state.game_time;
+
+    // 切换关卡或重新开始（游戏时间归零）时视为新的一关
+    if last_level.as_ref() != Some(&level_id) || game_time < *last_time {
+        host.reset_state();
+        host.call_hook(HOOK_LEVEL_START, (level_id.clone(),));
+        *last_level = Some(level_id);
+        *last_time = 0.0;
+    }
+
+    if game_time.floor() > last_time.floor() {
+        host.call_hook(HOOK_TICK, (game_time.floor() as i64,));
+    }
+    *last_time = game_time;
+
+    for event in placed_events.read() {
+        host.call_hook(
+            HOOK_SEGMENT_PLACED,
+            (
+                event.position.x as i64,
+                event.position.y as i64,
+                format!("{:?}", event.segment_type),
+            ),
+        );
+    }
+
+    for event in arrived_events.read() {
+        host.call_hook(
+            HOOK_PASSENGER_ARRIVED,
+            (
+                color_name(event.color).to_string(),
+                event.travel_time as f64,
+            ),
+        );
+    }
+
+    for command in host.queue.drain() {
+        apply_script_command(&mut commands, &mut game_state, &ui_assets, command);
+    }
+}
+
+fn apply_script_command(
+    commands: &mut Commands,
+    game_state: &mut GameState,
+    ui_assets: &UIAssets,
+    command: ScriptCommand,
+) {
+    let game_time = game_state.game_time;
+    let Some(level_data) = &mut game_state.current_level else {
+        return;
+    };
+    let has_station = |name: &str| level_data.stations.iter().any(|s| s.name == name);
+
+    match command {
+        ScriptCommand::Event {
+            event_type,
+            delay,
+            duration,
+        } => {
+            if let EventType::StationOverload(name) = &event_type
+                && !has_station(name)
+            {
+                warn!("脚本: 站点 {} 不存在", name);
+                return;
+            }
+
+            // 追加到末尾，事件预告和触发都按游戏时间处理
+            let event = DynamicEvent {
+                trigger_time: game_time + delay.max(MIN_EVENT_DELAY),
+                event_type,
+                duration,
+            };
+            info!("📜 脚本注入事件: {:?}", event);
+            level_data.dynamic_events.push(event);
+        }
+        ScriptCommand::Demand {
+            origin,
+            destination,
+            color,
+            count,
+            patience,
+        } => {
+            if let Some(missing) = [&origin, &destination]
+                .into_iter()
+                .find(|name| !has_station(name))
+            {
+                warn!("脚本: 站点 {} 不存在", missing);
+                return;
+            }
+
+            info!(
+                "📜 脚本新增需求: {} -> {} ({:?} x{})",
+                origin, destination, color, count
+            );
+            level_data.passenger_demands.push(PassengerDemand {
+                color,
+                origin,
+                destination,
+                origin_key: None,
+                destination_key: None,
+                spawn_rate: DEMAND_SPAWN_RATE,
+                patience,
+                spawn_time_range: Some((game_time, game_time + DEMAND_SPAWN_WINDOW)),
+                total_count: Some(count),
+                spawned_count: 0,
+                via: None,
+                group_size: None,
+                demand_curve: None,
+            });
+        }
+        ScriptCommand::Tip(text) => {
+            show_contextual_tip(
+                commands,
+                ui_assets,
+                &text,
+                TipType::Strategy,
+                SCRIPT_TIP_DURATION,
+            );
+        }
+    }
+}
@@ -0,0 +1,659 @@
+// src/bus_puzzle/seed_share.rs - 分享随机事件种子（结果界面显示并复制种子，主菜单输入好友的种子挑战同一局）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, ui_audio::MainMenuUI, ButtonComponent, ButtonType,
+    CurrentLanguage, CustomLevel, EventDeck, GameState, GameStateEnum, Language, LevelManager,
+    LocalizedText, LocalizedTextComponent, PendingAutosave, UIAssets, CANCEL, COPY_SEED, PLAY_SEED,
+    SEED_COPIED, SEED_COPY_FAILED, SEED_INPUT_HINT, SEED_INVALID, SHARE_SEED, START_SEED,
+};
+use bevy::{
+    input::keyboard::{Key, KeyboardInput},
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
+};
+
+/// 分享码最长字符数（关卡序号 + "-" + 16 位十六进制种子）
+const MAX_CODE_LENGTH: usize = 20;
+
+// ============ 分享码 ============
+
+/// 分享码格式为 "关卡序号-十六进制种子"，例如 3-1A2B3C4D。
+/// 只有开启随机事件的内置关卡才有分享码，地图本身是固定的，种子决定抽到的事件
+pub fn share_code(
+    level_manager: &LevelManager,
+    game_state: &GameState,
+    deck: &EventDeck,
+) -> Option<String> {
+    let level_data = game_state.current_level.as_ref()?;
+    let index = level_manager.current_level_index;
+    if !level_data.random_events || level_manager.available_levels.get(index)? != &level_data.id {
+        return None;
+    }
+    Some(format!("{}-{:X}", index + 1, deck.seed))
+}
+
+/// 解析分享码，返回关卡下标和种子
+fn parse_share_code(code: &str, level_count: usize) -> Option<(usize, u64)> {
+    let (level, seed) = code.trim().split_once('-')?;
+    let index = level.parse::<usize>().ok()?.checked_sub(1)?;
+    if index >= level_count {
+        return None;
+    }
+    let seed = u64::from_str_radix(seed, 16).ok()?;
+    Some((index, seed))
+}
+
+// ============ 系统剪贴板 ============
+
+#[cfg(not(target_family = "wasm"))]
+fn copy_text(text: &str) -> bool {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", "$input | Set-Clipboard"]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("pbcopy")
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Command::new("wl-copy")
+    } else {
+        let mut command = Command::new("xclip");
+        command.args(["-selection", "clipboard"]);
+        command
+    };
+
+    let Ok(mut child) = command.stdin(Stdio::piped()).spawn() else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    written && child.wait().is_ok_and(|status| status.success())
+}
+
+/// 浏览器剪贴板接口是异步的，这里只发出请求
+#[cfg(target_family = "wasm")]
+fn copy_text(text: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    let _ = window.navigator().clipboard().write_text(text);
+    true
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn paste_text() -> Option<String> {
+    use std::process::Command;
+
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", "Get-Clipboard"]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("pbpaste")
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let mut command = Command::new("wl-paste");
+        command.arg("--no-newline");
+        command
+    } else {
+        let mut command = Command::new("xclip");
+        command.args(["-selection", "clipboard", "-o"]);
+        command
+    };
+
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 网页版读取剪贴板需要异步权限请求，粘贴交给玩家手动输入
+#[cfg(target_family = "wasm")]
+fn paste_text() -> Option<String> {
+    None
+}
+
+// ============ 组件 ============
+
+/// 结果界面底部的种子面板
+#[derive(Component)]
+pub struct SeedSharePanel;
+
+/// 主菜单中的种子输入面板
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::MainMenu))]
+pub struct SeedInputPanel;
+
+#[derive(Component)]
+pub struct SeedInputText;
+
+#[derive(Component)]
+pub struct SeedInputError;
+
+/// 种子输入框中的内容
+#[derive(Resource, Default)]
+pub struct SeedInput {
+    pub text: String,
+    pub invalid: bool,
+}
+
+// ============ 种子分享插件 ============
+
+pub struct SeedSharePlugin;
+
+impl Plugin for SeedSharePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeedInput>()
+            .add_systems(
+                OnEnter(GameStateEnum::MainMenu),
+                (clear_shared_seed, setup_play_seed_button),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::LevelComplete),
+                |commands: Commands,
+                 ui_assets: Res<UIAssets>,
+                 level_manager: Res<LevelManager>,
+                 game_state: Res<GameState>,
+                 deck: Res<EventDeck>,
+                 language: Res<CurrentLanguage>| {
+                    spawn_seed_share_panel(
+                        commands,
+                        ui_assets,
+                        level_manager,
+                        game_state,
+                        deck,
+                        language,
+                        GameStateEnum::LevelComplete,
+                    );
+                },
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::GameOver),
+                |commands: Commands,
+                 ui_assets: Res<UIAssets>,
+                 level_manager: Res<LevelManager>,
+                 game_state: Res<GameState>,
+                 deck: Res<EventDeck>,
+                 language: Res<CurrentLanguage>| {
+                    spawn_seed_share_panel(
+                        commands,
+                        ui_assets,
+                        level_manager,
+                        game_state,
+                        deck,
+                        language,
+                        GameStateEnum::GameOver,
+                    );
+                },
+            )
+            .add_systems(
+                Update,
+                handle_copy_seed_button.run_if(
+                    in_state(GameStateEnum::LevelComplete).or(in_state(GameStateEnum::GameOver)),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_seed_input_buttons,
+                    type_seed_input,
+                    update_seed_input_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            );
+    }
+}
+
+fn clear_shared_seed(mut deck: ResMut<EventDeck>) {
+    deck.shared_seed = None;
+}
+
+// ============ 结果界面 ============
+
+fn spawn_seed_share_panel(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    level_manager: Res<LevelManager>,
+    game_state: Res<GameState>,
+    deck: Res<EventDeck>,
+    current_language: Res<CurrentLanguage>,
+    state: GameStateEnum,
+) {
+    let Some(code) = share_code(&level_manager, &game_state, &deck) else {
+        return;
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Px(20.0),
+                left: Percent(50.0),
+                margin: UiRect::left(Px(-170.0)),
+                width: Px(340.0),
+                height: Px(44.0),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(12.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.2, 0.9)),
+            ZIndex(2100), // 结果界面（2000）之上
+            SeedSharePanel,
+            StateScoped(state),
+            Name::new("Seed Share Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text_with_args(
+                    &SHARE_SEED,
+                    current_language.language,
+                    &[&code],
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+            ));
+            spawn_small_button(
+                parent,
+                &ui_assets,
+                ButtonType::CopySeed,
+                &COPY_SEED,
+                current_language.language,
+            );
+        });
+}
+
+fn handle_copy_seed_button(
+    button_query: Query<
+        (&Interaction, &ButtonComponent, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut button_texts: Query<(&mut LocalizedTextComponent, &mut Text)>,
+    level_manager: Res<LevelManager>,
+    game_state: Res<GameState>,
+    deck: Res<EventDeck>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component, children) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed)
+            || button_component.button_type != ButtonType::CopySeed
+        {
+            continue;
+        }
+        let Some(code) = share_code(&level_manager, &game_state, &deck) else {
+            continue;
+        };
+
+        let result_key = if copy_text(&code) {
+            info!("📋 种子已复制: {}", code);
+            &SEED_COPIED
+        } else {
+            warn!("无法复制种子到剪贴板: {}", code);
+            &SEED_COPY_FAILED
+        };
+
+        for child in children.iter() {
+            if let Ok((mut localized, mut text)) = button_texts.get_mut(child) {
+                localized.key = result_key.key;
+                *text = Text::new(get_text(result_key, current_language.language));
+            }
+        }
+    }
+}
+
+// ============ 主菜单种子输入 ============
+
+fn setup_play_seed_button(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                top: Px(220.0),
+                width: Px(220.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type: ButtonType::OpenSeedInput,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            MainMenuUI,
+            Name::new("Play Seed Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&PLAY_SEED, current_language.language)),
+                LocalizedTextComponent::new(&PLAY_SEED),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_seed_input_panel(commands: &mut Commands, ui_assets: &UIAssets, language: Language) {
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(4000),
+            SeedInputPanel,
+            Name::new("Seed Input Panel"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(420.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(12.0),
+                        padding: UiRect::all(Px(24.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.4, 0.4, 0.6)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(get_text(&SEED_INPUT_HINT, language)),
+                        LocalizedTextComponent::new(&SEED_INPUT_HINT),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+
+                    // 输入框
+                    parent
+                        .spawn((
+                            Node {
+                                width: Px(300.0),
+                                height: Px(40.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.05, 0.05, 0.1)),
+                            Outline::new(Px(1.0), Val::ZERO, Color::srgb(0.5, 0.5, 0.7)),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("_"),
+                                TextFont {
+                                    font: ui_assets.font.clone(),
+                                    font_size: 22.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                                SeedInputText,
+                            ));
+                        });
+
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.4, 0.4)),
+                        SeedInputError,
+                    ));
+
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Px(16.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_small_button(
+                                parent,
+                                ui_assets,
+                                ButtonType::ConfirmSeedInput,
+                                &START_SEED,
+                                language,
+                            );
+                            spawn_small_button(
+                                parent,
+                                ui_assets,
+                                ButtonType::CancelSeedInput,
+                                &CANCEL,
+                                language,
+                            );
+                        });
+                });
+        });
+}
+
+fn spawn_small_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    text_key: &'static LocalizedText,
+    language: Language,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(110.0),
+                height: Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(text_key, language)),
+                LocalizedTextComponent::new(text_key),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 用输入的分享码开始关卡，分享码无效时在面板上提示
+fn start_seeded_level(
+    seed_input: &mut SeedInput,
+    level_manager: &mut LevelManager,
+    deck: &mut EventDeck,
+    pending_autosave: &mut PendingAutosave,
+    custom_level: &mut CustomLevel,
+    next_state: &mut NextState<GameStateEnum>,
+) {
+    let Some((index, seed)) =
+        parse_share_code(&seed_input.text, level_manager.available_levels.len())
+    else {
+        seed_input.invalid = true;
+        return;
+    };
+
+    level_manager.current_level_index = index;
+    deck.shared_seed = Some((level_manager.available_levels[index].clone(), seed));
+    // 分享的对局从头开始，不恢复自动存档
+    pending_autosave.0 = None;
+    custom_level.0 = None;
+    next_state.set(GameStateEnum::Playing);
+    info!("🌱 使用分享种子开始关卡 {}: {:X}", index + 1, seed);
+}
+
+fn handle_seed_input_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    panels: Query<Entity, With<SeedInputPanel>>,
+    mut seed_input: ResMut<SeedInput>,
+    mut level_manager: ResMut<LevelManager>,
+    mut deck: ResMut<EventDeck>,
+    mut pending_autosave: ResMut<PendingAutosave>,
+    mut custom_level: ResMut<CustomLevel>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::OpenSeedInput if panels.is_empty() => {
+                *seed_input = SeedInput::default();
+                spawn_seed_input_panel(&mut commands, &ui_assets, current_language.language);
+            }
+            ButtonType::ConfirmSeedInput => start_seeded_level(
+                &mut seed_input,
+                &mut level_manager,
+                &mut deck,
+                &mut pending_autosave,
+                &mut custom_level,
+                &mut next_state,
+            ),
+            ButtonType::CancelSeedInput => {
+                for entity in panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 面板打开时接收键盘输入：十六进制字符和 "-"，Backspace 删除，Enter 确认，Esc 关闭，Ctrl+V 粘贴
+fn type_seed_input(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    panels: Query<Entity, With<SeedInputPanel>>,
+    mut seed_input: ResMut<SeedInput>,
+    mut level_manager: ResMut<LevelManager>,
+    mut deck: ResMut<EventDeck>,
+    mut pending_autosave: ResMut<PendingAutosave>,
+    mut custom_level: ResMut<CustomLevel>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    if panels.is_empty() {
+        keyboard_events.clear();
+        return;
+    }
+
+    let modifier_pressed = keyboard_input.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let accepts = |c: char| c.is_ascii_hexdigit() || c == '-';
+
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(text) if modifier_pressed && text.eq_ignore_ascii_case("v") => {
+                if let Some(pasted) = paste_text() {
+                    seed_input.text = pasted.chars().filter(|c| accepts(*c)).collect();
+                    seed_input.text.truncate(MAX_CODE_LENGTH);
+                    seed_input.invalid = false;
+                }
+            }
+            Key::Character(text) if !modifier_pressed => {
+                for c in text.chars().filter(|c| accepts(*c)) {
+                    if seed_input.text.len() < MAX_CODE_LENGTH {
+                        seed_input.text.push(c.to_ascii_uppercase());
+                    }
+                }
+                seed_input.invalid = false;
+            }
+            Key::Backspace => {
+                seed_input.text.pop();
+                seed_input.invalid = false;
+            }
+            Key::Enter => start_seeded_level(
+                &mut seed_input,
+                &mut level_manager,
+                &mut deck,
+                &mut pending_autosave,
+                &mut custom_level,
+                &mut next_state,
+            ),
+            Key::Escape => {
+                for entity in panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_seed_input_text(
+    seed_input: Res<SeedInput>,
+    current_language: Res<CurrentLanguage>,
+    mut input_texts: Query<&mut Text, (With<SeedInputText>, Without<SeedInputError>)>,
+    mut error_texts: Query<&mut Text, (With<SeedInputError>, Without<SeedInputText>)>,
+) {
+    if !seed_input.is_changed() && !current_language.is_changed() {
+        return;
+    }
+
+    for mut text in input_texts.iter_mut() {
+        text.0 = format!("{}_", seed_input.text);
+    }
+    for mut text in error_texts.iter_mut() {
+        text.0 = if seed_input.invalid {
+            get_text(&SEED_INVALID, current_language.language)
+        } else {
+            String::new()
+        };
+    }
+}
@@ -0,0 +1,736 @@
+// src/bus_puzzle/segment_clipboard.rs - 框选已放置的路线段，整体移动或复制粘贴（Ctrl+Z 撤销）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, handle_segment_upgrade, insert_placed_segment,
+    is_valid_placement_on_layer, show_contextual_tip, CurrentLanguage, EconomyMode, GameState,
+    GameStateEnum, GridPos, InputAction, InputMap, InputState, InventoryUpdatedEvent, LevelManager,
+    NetworkLayer, PlacedSegment, RouteSegmentType, SegmentPlacedEvent, SegmentRemovedEvent,
+    ThemeRegistry, TipType, UIAssets, CLIPBOARD_BLOCKED, CLIPBOARD_COPIED, CLIPBOARD_MOVE_HINT,
+    CLIPBOARD_NO_INVENTORY, CLIPBOARD_PASTE_HINT, EFFECT_Z, SELECTION_INFO, UNDO_DONE, UNDO_FAILED,
+};
+use bevy::{
+    input::InputSystem,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+const COPY_KEY: KeyCode = KeyCode::KeyC;
+const MOVE_KEY: KeyCode = KeyCode::KeyX;
+const PASTE_KEY: KeyCode = KeyCode::KeyV;
+const UNDO_KEY: KeyCode = KeyCode::KeyZ;
+
+/// 最多保留的撤销步数
+const UNDO_LIMIT: usize = 20;
+
+const SELECTION_COLOR: Color = Color::srgba(1.0, 0.85, 0.2, 0.35);
+const PLACEMENT_VALID_COLOR: Color = Color::srgba(0.0, 1.0, 0.0, 0.6);
+const PLACEMENT_INVALID_COLOR: Color = Color::srgba(1.0, 0.0, 0.0, 0.6);
+
+/// 剪贴板中的路线段，位置相对框选区域的左下角
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipboardSegment {
+    pub offset: GridPos,
+    pub segment_type: RouteSegmentType,
+    pub rotation: u32,
+}
+
+/// 正在用光标放置剪贴板内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardMode {
+    Paste,
+    Move,
+}
+
+/// 一次可撤销的批量编辑
+#[derive(Debug, Clone)]
+enum ClipboardEdit {
+    Paste {
+        layer: NetworkLayer,
+        positions: Vec<GridPos>,
+    },
+    Move {
+        layer: NetworkLayer,
+        moves: Vec<(GridPos, GridPos)>, // (原位置, 新位置)
+    },
+}
+
+/// 框选、剪贴板和撤销记录，每关重置
+#[derive(Resource, Default)]
+pub struct SegmentSelection {
+    box_start: Option<GridPos>, // Shift + 左键拖动框选的起点
+    box_end: Option<GridPos>,
+    pub selected: Vec<GridPos>,
+    layer: NetworkLayer,
+    clipboard: Vec<ClipboardSegment>,
+    pub mode: Option<ClipboardMode>,
+    undo_stack: Vec<ClipboardEdit>,
+}
+
+impl SegmentSelection {
+    /// 正在框选或放置剪贴板时，左键不再触发升级等单格操作
+    pub fn is_busy(&self) -> bool {
+        self.box_start.is_some() || self.mode.is_some()
+    }
+
+    /// 选中的路线段，位置换算为相对选区左下角的偏移
+    pub fn selected_segments(&self, game_state: &GameState) -> Vec<ClipboardSegment> {
+        let min_x = self.selected.iter().map(|pos| pos.x).min().unwrap_or(0);
+        let min_y = self.selected.iter().map(|pos| pos.y).min().unwrap_or(0);
+        let segments = game_state.segments_on(self.layer);
+        self.selected
+            .iter()
+            .filter_map(|pos| {
+                segments.get(pos).map(|placed| ClipboardSegment {
+                    offset: GridPos::new(pos.x - min_x, pos.y - min_y),
+                    segment_type: placed.segment_type,
+                    rotation: placed.rotation,
+                })
+            })
+            .collect()
+    }
+
+    /// 用给定的路线段替换剪贴板，并进入粘贴模式（蓝图盖章也走这条路径）
+    pub fn start_paste(&mut self, segments: Vec<ClipboardSegment>, layer: NetworkLayer) {
+        self.clipboard = segments;
+        self.layer = layer;
+        self.selected.clear();
+        self.mode = Some(ClipboardMode::Paste);
+    }
+
+    fn push_undo(&mut self, edit: ClipboardEdit) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(edit);
+    }
+
+    /// 放置剪贴板时各路线段的目标格子，光标所在格子对应左下角
+    fn targets(&self, anchor: GridPos) -> impl Iterator<Item = (GridPos, &ClipboardSegment)> {
+        self.clipboard.iter().map(move |segment| {
+            (
+                GridPos::new(anchor.x + segment.offset.x, anchor.y + segment.offset.y),
+                segment,
+            )
+        })
+    }
+}
+
+/// 框选和剪贴板的预览方块
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct ClipboardPreview;
+
+/// Ctrl（macOS 上为 Cmd）是否按下；单键快捷键在组合键时应当让路
+pub fn shortcut_modifier_pressed(keyboard_input: &ButtonInput<KeyCode>) -> bool {
+    keyboard_input.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ])
+}
+
+// ============ 路线段剪贴板插件 ============
+
+pub struct SegmentClipboardPlugin;
+
+impl Plugin for SegmentClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SegmentSelection>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_segment_selection)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_segment_selection)
+            // 与拖动绘制相同，ESC 先用于取消框选和放置，不触发暂停
+            .add_systems(
+                PreUpdate,
+                cancel_clipboard_mode
+                    .after(InputSystem)
+                    .run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_box_selection,
+                    handle_clipboard_keys,
+                    place_clipboard,
+                    update_clipboard_preview,
+                )
+                    .chain()
+                    .after(handle_segment_upgrade)
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn reset_segment_selection(mut selection: ResMut<SegmentSelection>) {
+    *selection = SegmentSelection::default();
+}
+
+fn cancel_clipboard_mode(
+    mut selection: ResMut<SegmentSelection>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+) {
+    if !input_map.just_pressed(&keyboard_input, InputAction::Pause)
+        || (!selection.is_busy() && selection.selected.is_empty())
+    {
+        return;
+    }
+
+    selection.box_start = None;
+    selection.mode = None;
+    selection.selected.clear();
+    keyboard_input.clear_just_pressed(input_map.key(InputAction::Pause));
+    info!("已取消框选");
+}
+
+// ============ 框选 ============
+
+/// 未选中库存路线段时，按住 Shift 拖动左键框选当前图层上的路线段
+fn handle_box_selection(
+    mut commands: Commands,
+    mut selection: ResMut<SegmentSelection>,
+    input_state: Res<InputState>,
+    game_state: Res<GameState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if selection.mode.is_some() || game_state.is_paused {
+        return;
+    }
+
+    if let Some(cursor) = input_state.grid_cursor_pos {
+        selection.box_end = Some(cursor);
+    }
+
+    let Some(start) = selection.box_start else {
+        let shift_pressed = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        if shift_pressed
+            && mouse_button_input.just_pressed(MouseButton::Left)
+            && input_state.selected_segment.is_none()
+            && ui_buttons
+                .iter()
+                .all(|interaction| *interaction == Interaction::None)
+            && let Some(cursor) = input_state.grid_cursor_pos
+        {
+            selection.box_start = Some(cursor);
+            selection.box_end = Some(cursor);
+            selection.selected.clear();
+        }
+        return;
+    };
+
+    if mouse_button_input.pressed(MouseButton::Left) {
+        return;
+    }
+
+    let end = selection.box_end.unwrap_or(start);
+    let layer = input_state.active_layer;
+    let mut selected: Vec<GridPos> = game_state
+        .segments_on(layer)
+        .keys()
+        .filter(|pos| {
+            (start.x.min(end.x)..=start.x.max(end.x)).contains(&pos.x)
+                && (start.y.min(end.y)..=start.y.max(end.y)).contains(&pos.y)
+        })
+        .copied()
+        .collect();
+    selected.sort_by_key(|pos| (pos.x, pos.y));
+
+    selection.box_start = None;
+    selection.layer = layer;
+    selection.selected = selected;
+
+    if !selection.selected.is_empty() {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &SELECTION_INFO,
+                current_language.language,
+                &[&selection.selected.len().to_string()],
+            ),
+            TipType::Controls,
+            3.0,
+        );
+        info!("框选了 {} 个路线段", selection.selected.len());
+    }
+}
+
+// ============ 复制、移动、粘贴、撤销 ============
+
+fn handle_clipboard_keys(
+    mut commands: Commands,
+    mut selection: ResMut<SegmentSelection>,
+    mut input_state: ResMut<InputState>,
+    mut game_state: ResMut<GameState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+    mut segment_events: (
+        EventWriter<SegmentPlacedEvent>,
+        EventWriter<SegmentRemovedEvent>,
+        EventWriter<InventoryUpdatedEvent>,
+    ),
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !shortcut_modifier_pressed(&keyboard_input) || game_state.is_paused {
+        return;
+    }
+    let language = current_language.language;
+
+    let copy = keyboard_input.just_pressed(COPY_KEY);
+    let cut = keyboard_input.just_pressed(MOVE_KEY);
+    if (copy || cut) && !selection.selected.is_empty() {
+        selection.clipboard = selection.selected_segments(&game_state);
+
+        let count = selection.clipboard.len().to_string();
+        let (text, mode) = if cut {
+            (&CLIPBOARD_MOVE_HINT, Some(ClipboardMode::Move))
+        } else {
+            (&CLIPBOARD_COPIED, None)
+        };
+        selection.mode = mode;
+        input_state.selected_segment = None;
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(text, language, &[&count]),
+            TipType::Controls,
+            3.0,
+        );
+        info!("剪贴板: {} 个路线段，模式 {:?}", count, selection.mode);
+    }
+
+    if keyboard_input.just_pressed(PASTE_KEY) && !selection.clipboard.is_empty() {
+        selection.mode = Some(ClipboardMode::Paste);
+        input_state.selected_segment = None;
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &CLIPBOARD_PASTE_HINT,
+                language,
+                &[&selection.clipboard.len().to_string()],
+            ),
+            TipType::Controls,
+            3.0,
+        );
+    }
+
+    if keyboard_input.just_pressed(UNDO_KEY)
+        && let Some(edit) = selection.undo_stack.pop()
+    {
+        let undone = match &edit {
+            ClipboardEdit::Paste { layer, positions } => {
+                for pos in positions {
+                    remove_pasted_segment(
+                        &mut commands,
+                        &mut game_state,
+                        *layer,
+                        *pos,
+                        &mut segment_events,
+                    );
+                }
+                true
+            }
+            ClipboardEdit::Move { layer, moves } => {
+                let reversed: Vec<(GridPos, GridPos)> =
+                    moves.iter().map(|(from, to)| (*to, *from)).collect();
+                relocate_segments(
+                    &mut commands,
+                    &mut game_state,
+                    &asset_server,
+                    &theme,
+                    &level_manager,
+                    *layer,
+                    &reversed,
+                    &mut segment_events,
+                )
+            }
+        };
+
+        selection.selected.clear();
+        let text = if undone { &UNDO_DONE } else { &UNDO_FAILED };
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(text, language),
+            if undone {
+                TipType::Controls
+            } else {
+                TipType::Warning
+            },
+            2.0,
+        );
+        info!("撤销 {:?}: {}", edit, undone);
+    }
+}
+
+/// 左键点击格子，把剪贴板内容放到以该格为左下角的位置
+fn place_clipboard(
+    mut commands: Commands,
+    mut selection: ResMut<SegmentSelection>,
+    input_state: Res<InputState>,
+    mut game_state: ResMut<GameState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+    mut segment_events: (
+        EventWriter<SegmentPlacedEvent>,
+        EventWriter<SegmentRemovedEvent>,
+        EventWriter<InventoryUpdatedEvent>,
+    ),
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    economy: Res<EconomyMode>,
+) {
+    let Some(mode) = selection.mode else {
+        return;
+    };
+    if game_state.is_paused
+        || !mouse_button_input.just_released(MouseButton::Left)
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+    let Some(anchor) = input_state.grid_cursor_pos else {
+        return;
+    };
+    let language = current_language.language;
+    let layer = selection.layer;
+
+    let blocked_tip = |commands: &mut Commands, text| {
+        show_contextual_tip(
+            commands,
+            &ui_assets,
+            &get_text(text, language),
+            TipType::Warning,
+            2.0,
+        );
+    };
+
+    match mode {
+        ClipboardMode::Move => {
+            let min_x = selection
+                .selected
+                .iter()
+                .map(|pos| pos.x)
+                .min()
+                .unwrap_or(0);
+            let min_y = selection
+                .selected
+                .iter()
+                .map(|pos| pos.y)
+                .min()
+                .unwrap_or(0);
+            let moves: Vec<(GridPos, GridPos)> = selection
+                .selected
+                .iter()
+                .map(|pos| {
+                    (
+                        *pos,
+                        GridPos::new(anchor.x + pos.x - min_x, anchor.y + pos.y - min_y),
+                    )
+                })
+                .collect();
+
+            if !relocate_segments(
+                &mut commands,
+                &mut game_state,
+                &asset_server,
+                &theme,
+                &level_manager,
+                layer,
+                &moves,
+                &mut segment_events,
+            ) {
+                blocked_tip(&mut commands, &CLIPBOARD_BLOCKED);
+                return;
+            }
+
+            info!("移动了 {} 个路线段到 {:?}", moves.len(), anchor);
+            selection.selected = moves.iter().map(|(_, to)| *to).collect();
+            selection.push_undo(ClipboardEdit::Move { layer, moves });
+        }
+        ClipboardMode::Paste => {
+            let targets: Vec<(GridPos, ClipboardSegment)> = selection
+                .targets(anchor)
+                .map(|(pos, segment)| (pos, *segment))
+                .collect();
+
+            if !targets
+                .iter()
+                .all(|(pos, segment)| can_place(&game_state, *pos, &segment.segment_type, layer))
+            {
+                blocked_tip(&mut commands, &CLIPBOARD_BLOCKED);
+                return;
+            }
+
+            let mut needed: HashMap<RouteSegmentType, u32> = HashMap::default();
+            for (_, segment) in &targets {
+                *needed.entry(segment.segment_type).or_insert(0) += 1;
+            }
+            // 经营模式下库存按单个地面段折算，整批粘贴还要确认余额够付总价
+            let paste_cost: u32 = targets
+                .iter()
+                .map(|(_, segment)| layer.segment_cost(segment.segment_type))
+                .sum();
+            if needed.iter().any(|(segment_type, count)| {
+                game_state
+                    .player_inventory
+                    .get(segment_type)
+                    .copied()
+                    .unwrap_or(0)
+                    < *count
+            }) || !economy.can_afford(&game_state, paste_cost)
+            {
+                blocked_tip(&mut commands, &CLIPBOARD_NO_INVENTORY);
+                return;
+            }
+
+            // 粘贴出的是普通路线段，快速路线升级不会被复制
+            for (pos, segment) in &targets {
+                let cost = layer.segment_cost(segment.segment_type);
+                insert_placed_segment(
+                    &mut commands,
+                    &mut game_state,
+                    &asset_server,
+                    &theme,
+                    &level_manager,
+                    layer,
+                    *pos,
+                    PlacedSegment {
+                        segment_type: segment.segment_type,
+                        rotation: segment.rotation,
+                        entity: Entity::PLACEHOLDER,
+                        cost,
+                        express: false,
+                    },
+                    &mut segment_events.0,
+                );
+                game_state.total_cost += cost;
+                let count = game_state
+                    .player_inventory
+                    .entry(segment.segment_type)
+                    .or_insert(0);
+                *count -= 1;
+                let new_count = *count;
+                segment_events.2.write(InventoryUpdatedEvent {
+                    segment_type: segment.segment_type,
+                    new_count,
+                });
+            }
+
+            info!("粘贴了 {} 个路线段到 {:?}", targets.len(), anchor);
+            let positions: Vec<GridPos> = targets.iter().map(|(pos, _)| *pos).collect();
+            selection.selected = positions.clone();
+            selection.push_undo(ClipboardEdit::Paste { layer, positions });
+        }
+    }
+
+    selection.mode = None;
+}
+
+/// 与单格放置相同的规则，另外不能压在站点上
+fn can_place(
+    game_state: &GameState,
+    pos: GridPos,
+    segment_type: &RouteSegmentType,
+    layer: NetworkLayer,
+) -> bool {
+    let is_station = game_state.current_level.as_ref().is_some_and(|level_data| {
+        level_data
+            .stations
+            .iter()
+            .any(|station| station.position == pos)
+    });
+    !is_station && is_valid_placement_on_layer(game_state, pos, segment_type, layer)
+}
+
+/// 整体搬移路线段（保留旋转、成本和快速路线升级），库存和成本不变。
+/// 先腾出原位置再检查目标，任何一格不能放置时恢复原状并返回 false
+fn relocate_segments(
+    commands: &mut Commands,
+    game_state: &mut GameState,
+    asset_server: &Res<AssetServer>,
+    theme: &ThemeRegistry,
+    level_manager: &LevelManager,
+    layer: NetworkLayer,
+    moves: &[(GridPos, GridPos)],
+    segment_events: &mut (
+        EventWriter<SegmentPlacedEvent>,
+        EventWriter<SegmentRemovedEvent>,
+        EventWriter<InventoryUpdatedEvent>,
+    ),
+) -> bool {
+    let sources: HashSet<GridPos> = moves.iter().map(|(from, _)| *from).collect();
+    if moves.is_empty()
+        || sources.len() != moves.len()
+        || !sources
+            .iter()
+            .all(|from| game_state.segments_on(layer).contains_key(from))
+    {
+        return false;
+    }
+
+    let lifted: Vec<(GridPos, GridPos, PlacedSegment)> = moves
+        .iter()
+        .filter_map(|(from, to)| {
+            game_state
+                .segments_on_mut(layer)
+                .remove(from)
+                .map(|placed| (*from, *to, placed))
+        })
+        .collect();
+
+    let fits = lifted
+        .iter()
+        .all(|(_, to, placed)| can_place(game_state, *to, &placed.segment_type, layer));
+    if !fits {
+        for (from, _, placed) in lifted {
+            game_state.segments_on_mut(layer).insert(from, placed);
+        }
+        return false;
+    }
+
+    for (from, to, placed) in lifted {
+        commands.entity(placed.entity).despawn();
+        segment_events.1.write(SegmentRemovedEvent {
+            position: from,
+            layer,
+        });
+        insert_placed_segment(
+            commands,
+            game_state,
+            asset_server,
+            theme,
+            level_manager,
+            layer,
+            to,
+            placed,
+            &mut segment_events.0,
+        );
+    }
+    true
+}
+
+/// 撤销粘贴：拆除粘贴出的路线段，全额退还库存和成本
+fn remove_pasted_segment(
+    commands: &mut Commands,
+    game_state: &mut GameState,
+    layer: NetworkLayer,
+    pos: GridPos,
+    segment_events: &mut (
+        EventWriter<SegmentPlacedEvent>,
+        EventWriter<SegmentRemovedEvent>,
+        EventWriter<InventoryUpdatedEvent>,
+    ),
+) {
+    let Some(placed) = game_state.segments_on_mut(layer).remove(&pos) else {
+        return;
+    };
+    commands.entity(placed.entity).despawn();
+    game_state.total_cost = game_state.total_cost.saturating_sub(placed.cost);
+    let count = game_state
+        .player_inventory
+        .entry(placed.segment_type)
+        .or_insert(0);
+    *count += 1;
+    let new_count = *count;
+
+    segment_events.1.write(SegmentRemovedEvent {
+        position: pos,
+        layer,
+    });
+    segment_events.2.write(InventoryUpdatedEvent {
+        segment_type: placed.segment_type,
+        new_count,
+    });
+}
+
+// ============ 预览 ============
+
+/// 高亮框选范围和选中的路线段；放置剪贴板时在光标处显示半透明路线段，绿色可放置、红色受阻
+fn update_clipboard_preview(
+    mut commands: Commands,
+    selection: Res<SegmentSelection>,
+    input_state: Res<InputState>,
+    game_state: Res<GameState>,
+    existing_previews: Query<Entity, With<ClipboardPreview>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    level_manager: Res<LevelManager>,
+) {
+    for entity in existing_previews.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+    let (width, height) = level_data.grid_size;
+    let tile_size = level_manager.tile_size;
+    let world_pos = |pos: GridPos| {
+        let mut world_pos = pos.to_world_pos(tile_size, width, height);
+        world_pos.z = EFFECT_Z;
+        world_pos
+    };
+
+    let highlighted: Vec<GridPos> = match selection.box_start {
+        Some(start) => {
+            let end = selection.box_end.unwrap_or(start);
+            (start.x.min(end.x)..=start.x.max(end.x))
+                .flat_map(|x| (start.y.min(end.y)..=start.y.max(end.y)).map(move |y| (x, y)))
+                .map(|(x, y)| GridPos::new(x, y))
+                .collect()
+        }
+        None if selection.mode != Some(ClipboardMode::Paste) => selection.selected.clone(),
+        None => Vec::new(),
+    };
+    for pos in highlighted {
+        commands.spawn((
+            Sprite::from_color(SELECTION_COLOR, Vec2::splat(tile_size)),
+            Transform::from_translation(world_pos(pos)),
+            ClipboardPreview,
+        ));
+    }
+
+    let (Some(mode), Some(anchor)) = (selection.mode, input_state.grid_cursor_pos) else {
+        return;
+    };
+    // 移动时原位置会先腾出，只检查目标格子本身是否被其他路线段占用
+    let moving: HashSet<GridPos> = match mode {
+        ClipboardMode::Move => selection.selected.iter().copied().collect(),
+        ClipboardMode::Paste => HashSet::default(),
+    };
+    for (pos, segment) in selection.targets(anchor) {
+        let valid = if moving.contains(&pos) {
+            true
+        } else {
+            can_place(&game_state, pos, &segment.segment_type, selection.layer)
+        };
+        commands.spawn((
+            Sprite {
+                image: asset_server.load(theme.route(&segment.segment_type)),
+                color: if valid {
+                    PLACEMENT_VALID_COLOR
+                } else {
+                    PLACEMENT_INVALID_COLOR
+                },
+                ..default()
+            },
+            Transform::from_translation(world_pos(pos) + Vec3::Z * 0.1).with_rotation(
+                Quat::from_rotation_z((segment.rotation as f32).to_radians()),
+            ),
+            ClipboardPreview,
+        ));
+    }
+}
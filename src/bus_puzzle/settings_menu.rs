@@ -0,0 +1,1003 @@
+// src/bus_puzzle/settings_menu.rs - 设置菜单（主菜单与暂停菜单共用）
+
+use crate::bus_puzzle::{
+    controls_help_args, get_text, get_text_with_args, AudioBus, AudioSettings, BackgroundMusic,
+    ButtonComponent, ButtonType, ControlsHelpText, CurrentLanguage, FallbackFontAnchor, FrameLimit,
+    GameStateEnum, InputMap, Language, LanguageToggleText, LocalizedText, LocalizedTextComponent,
+    SoundGain, ThemeRegistry, UIAssets, AUDIO_SETTING, BACK, BATTERY_SAVER, CONTROLS_HELP,
+    CONTROLS_SETTING, DISABLED_VOLUME, DYSLEXIA_FONT, FRAME_LIMIT, GAMEPLAY_SETTING, HOVER_VOLUME,
+    KEY_BINDINGS, LANGUAGE_SETTING, MASTER_VOLUME, MAX_UI_SCALE, MIN_UI_SCALE, MUSIC_VOLUME,
+    PAUSE_ON_FOCUS_LOSS, REDUCED_MOTION, SETTINGS, SOUND_OFF, SOUND_ON, TEXT_TO_SPEECH,
+    THEME_SETTING, UI_SCALE, UI_SOUNDS_SETTING, UI_VOLUME, VIDEO_SETTING, VISUAL_AUDIO_CUES, VSYNC,
+    WINDOW_MODE, WINDOW_MODE_BORDERLESS, WINDOW_MODE_FULLSCREEN, WINDOW_MODE_WINDOWED,
+    WORLD_VOLUME,
+};
+use bevy::{
+    audio::Volume,
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
+    ui::{FocusPolicy, RelativeCursorPosition},
+    window::{
+        MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowFocused, WindowMode,
+    },
+};
+
+/// 每次点击 +/- 调整的音量
+const VOLUME_STEP: f32 = 0.1;
+
+/// 音量滑块的尺寸和颜色
+const SLIDER_WIDTH: f32 = 140.0;
+const SLIDER_HEIGHT: f32 = 10.0;
+const SLIDER_TRACK_COLOR: Color = Color::srgb(0.12, 0.12, 0.2);
+const SLIDER_FILL_COLOR: Color = Color::srgb(0.45, 0.65, 1.0);
+
+/// 每次点击 +/- 调整的界面缩放
+const UI_SCALE_STEP: f32 = 0.1;
+
+/// 易读字体（OpenDyslexic，SIL OFL 许可）。字体只含拉丁字母，中文界面继续使用默认字体
+const DYSLEXIC_FONT_PATH: &str = "fonts/OpenDyslexic-Regular.otf";
+
+// ============ 设置菜单组件 ============
+
+#[derive(Component)]
+pub struct SettingsMenuUI;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AudioChannel {
+    Master,
+    Music,
+    Ui,
+    World,
+    Hover,
+    Disabled,
+}
+
+impl AudioChannel {
+    pub fn volume(self, audio_settings: &AudioSettings) -> f32 {
+        match self {
+            AudioChannel::Master => audio_settings.master_volume,
+            AudioChannel::Music => audio_settings.music_volume,
+            AudioChannel::Ui => audio_settings.ui_volume,
+            AudioChannel::World => audio_settings.world_volume,
+            AudioChannel::Hover => audio_settings.hover_volume,
+            AudioChannel::Disabled => audio_settings.disabled_volume,
+        }
+    }
+
+    fn volume_mut(self, audio_settings: &mut AudioSettings) -> &mut f32 {
+        match self {
+            AudioChannel::Master => &mut audio_settings.master_volume,
+            AudioChannel::Music => &mut audio_settings.music_volume,
+            AudioChannel::Ui => &mut audio_settings.ui_volume,
+            AudioChannel::World => &mut audio_settings.world_volume,
+            AudioChannel::Hover => &mut audio_settings.hover_volume,
+            AudioChannel::Disabled => &mut audio_settings.disabled_volume,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct VolumeText {
+    pub channel: AudioChannel,
+}
+
+/// 音量滑块的轨道，按住鼠标拖动即可调节
+#[derive(Component)]
+pub struct VolumeSlider {
+    pub channel: AudioChannel,
+}
+
+/// 滑块轨道中表示当前音量的填充条
+#[derive(Component)]
+pub struct VolumeSliderFill {
+    pub channel: AudioChannel,
+}
+
+#[derive(Component)]
+pub struct MuteToggleText;
+
+/// 游戏选项按钮上的文字，选项变化时刷新
+#[derive(Component, Clone, Copy)]
+pub enum OptionText {
+    PauseOnFocusLoss,
+    FrameLimit,
+    BatterySaver,
+    DyslexiaFont,
+    VisualAudioCues,
+    ReducedMotion,
+    TextToSpeech,
+    Theme,
+    WindowMode,
+    Vsync,
+    UiScale,
+}
+
+/// 设置菜单中的游戏选项
+#[derive(Resource)]
+pub struct GameOptions {
+    pub pause_on_focus_loss: bool, // 窗口失去焦点时自动暂停，避免限时关卡在后台失败
+    pub frame_limit: FrameLimit,
+    pub battery_saver: bool, // 菜单中只在有输入时刷新画面
+    pub dyslexia_font: bool,
+    pub visual_audio_cues: bool, // 音效触发时在屏幕上显示提示，方便听障玩家
+    pub reduced_motion: bool,    // 关闭弹跳、缩放、镜头抖动和粒子等动效
+    pub text_to_speech: bool,    // 用语音朗读焦点按钮和重要播报
+    pub window_mode: WindowMode,
+    pub vsync: bool,
+    pub ui_scale: f32,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            pause_on_focus_loss: true,
+            frame_limit: FrameLimit::default(),
+            battery_saver: false,
+            dyslexia_font: false,
+            visual_audio_cues: false,
+            reduced_motion: false,
+            text_to_speech: false,
+            window_mode: WindowMode::Windowed,
+            vsync: true,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl GameOptions {
+    /// 动画和特效系统播放动效前应先检查该选项，关闭时直接跳到最终状态
+    pub fn allows_motion(&self) -> bool {
+        !self.reduced_motion
+    }
+}
+
+// ============ 设置菜单插件 ============
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameOptions>()
+            .add_systems(
+                Update,
+                (
+                    handle_settings_buttons,
+                    drag_volume_sliders,
+                    close_settings_on_escape,
+                    update_settings_texts,
+                )
+                    .run_if(in_state(GameStateEnum::MainMenu).or(in_state(GameStateEnum::Paused))),
+            )
+            .add_systems(
+                Update,
+                pause_on_focus_loss.run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(Update, apply_audio_volumes) // 音量变化立即作用于正在播放的声音
+            .add_systems(Update, apply_video_options)
+            .add_systems(Update, apply_ui_font);
+    }
+}
+
+// ============ 设置菜单界面 ============
+
+fn spawn_settings_menu(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    audio_settings: &AudioSettings,
+    game_options: &GameOptions,
+    input_map: &InputMap,
+    theme: &ThemeRegistry,
+    language: Language,
+    opened_in: GameStateEnum,
+) {
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                top: Px(0.0),
+                left: Px(0.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(4000),       // 叠加在暂停菜单（3000）之上
+            FocusPolicy::Block, // 设置打开时不能点到下面菜单的按钮
+            SettingsMenuUI,
+            StateScoped(opened_in), // 主菜单和暂停菜单都能打开设置
+            Name::new("Settings Menu"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(1080.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(10.0),
+                        padding: UiRect::all(Px(24.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.4, 0.4, 0.6)),
+                ))
+                .with_children(|parent| {
+                    spawn_settings_label(parent, ui_assets, &SETTINGS, language, 28.0);
+
+                    // 选项较多，分三列显示，避免面板超出 720p 窗口
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::FlexStart,
+                            column_gap: Px(24.0),
+                            width: Percent(100.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(settings_column()).with_children(|parent| {
+                                spawn_audio_settings(parent, ui_assets, audio_settings, language);
+                            });
+                            parent.spawn(settings_column()).with_children(|parent| {
+                                spawn_video_settings(
+                                    parent,
+                                    ui_assets,
+                                    game_options,
+                                    theme,
+                                    language,
+                                );
+                            });
+                            parent.spawn(settings_column()).with_children(|parent| {
+                                spawn_gameplay_settings(
+                                    parent,
+                                    ui_assets,
+                                    game_options,
+                                    input_map,
+                                    theme,
+                                    language,
+                                );
+                            });
+                        });
+
+                    spawn_settings_button(
+                        parent,
+                        ui_assets,
+                        ButtonType::CloseSettings,
+                        Px(200.0),
+                        (
+                            Text::new(get_text(&BACK, language)),
+                            LocalizedTextComponent::new(&BACK),
+                        ),
+                    );
+                });
+        });
+}
+
+fn settings_column() -> Node {
+    Node {
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        row_gap: Px(10.0),
+        width: Percent(33.3),
+        ..default()
+    }
+}
+
+/// 左列：各通道音量、静音和界面提示音
+fn spawn_audio_settings(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    audio_settings: &AudioSettings,
+    language: Language,
+) {
+    spawn_settings_label(parent, ui_assets, &AUDIO_SETTING, language, 18.0);
+    for channel in [
+        AudioChannel::Master,
+        AudioChannel::Music,
+        AudioChannel::Ui,
+        AudioChannel::World,
+    ] {
+        spawn_volume_row(parent, ui_assets, audio_settings, channel, language);
+    }
+    spawn_settings_button(
+        parent,
+        ui_assets,
+        ButtonType::ToggleMute,
+        Px(200.0),
+        (mute_text(audio_settings, language), MuteToggleText),
+    );
+
+    // 界面提示音（相对界面音效音量）
+    spawn_settings_label(parent, ui_assets, &UI_SOUNDS_SETTING, language, 18.0);
+    for channel in [AudioChannel::Hover, AudioChannel::Disabled] {
+        spawn_volume_row(parent, ui_assets, audio_settings, channel, language);
+    }
+}
+
+/// 中列：显示模式、垂直同步、界面缩放、帧率、主题和语言
+fn spawn_video_settings(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    game_options: &GameOptions,
+    theme: &ThemeRegistry,
+    language: Language,
+) {
+    spawn_settings_label(parent, ui_assets, &VIDEO_SETTING, language, 18.0);
+    for (button_type, option) in [
+        (ButtonType::CycleWindowMode, OptionText::WindowMode),
+        (ButtonType::ToggleVsync, OptionText::Vsync),
+    ] {
+        spawn_settings_button(
+            parent,
+            ui_assets,
+            button_type,
+            Px(280.0),
+            (option_text(option, game_options, theme, language), option),
+        );
+    }
+    spawn_stepper_row(
+        parent,
+        ui_assets,
+        ButtonType::UiScaleDown,
+        ButtonType::UiScaleUp,
+        (
+            option_text(OptionText::UiScale, game_options, theme, language),
+            OptionText::UiScale,
+        ),
+    );
+    for (button_type, option) in [
+        (ButtonType::CycleFrameLimit, OptionText::FrameLimit),
+        (ButtonType::ToggleBatterySaver, OptionText::BatterySaver),
+        (ButtonType::CycleTheme, OptionText::Theme),
+    ] {
+        spawn_settings_button(
+            parent,
+            ui_assets,
+            button_type,
+            Px(280.0),
+            (option_text(option, game_options, theme, language), option),
+        );
+    }
+
+    // 语言
+    spawn_settings_label(parent, ui_assets, &LANGUAGE_SETTING, language, 18.0);
+    let next_language = language.next().display_name();
+    spawn_settings_button(
+        parent,
+        ui_assets,
+        ButtonType::ToggleLanguage,
+        Px(200.0),
+        (Text::new(next_language), LanguageToggleText),
+    );
+}
+
+/// 右列：游戏选项和操作说明
+fn spawn_gameplay_settings(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    game_options: &GameOptions,
+    input_map: &InputMap,
+    theme: &ThemeRegistry,
+    language: Language,
+) {
+    spawn_settings_label(parent, ui_assets, &GAMEPLAY_SETTING, language, 18.0);
+    for (button_type, option) in [
+        (
+            ButtonType::TogglePauseOnFocusLoss,
+            OptionText::PauseOnFocusLoss,
+        ),
+        (ButtonType::ToggleDyslexiaFont, OptionText::DyslexiaFont),
+        (
+            ButtonType::ToggleVisualAudioCues,
+            OptionText::VisualAudioCues,
+        ),
+        (ButtonType::ToggleReducedMotion, OptionText::ReducedMotion),
+        (ButtonType::ToggleTextToSpeech, OptionText::TextToSpeech),
+    ] {
+        spawn_settings_button(
+            parent,
+            ui_assets,
+            button_type,
+            Px(280.0),
+            (option_text(option, game_options, theme, language), option),
+        );
+    }
+
+    // 操作说明和按键绑定
+    spawn_settings_label(parent, ui_assets, &CONTROLS_SETTING, language, 18.0);
+    spawn_settings_button(
+        parent,
+        ui_assets,
+        ButtonType::OpenKeyBindings,
+        Px(200.0),
+        (
+            Text::new(get_text(&KEY_BINDINGS, language)),
+            LocalizedTextComponent::new(&KEY_BINDINGS),
+        ),
+    );
+    let controls_help =
+        LocalizedTextComponent::with_args(&CONTROLS_HELP, controls_help_args(input_map));
+    parent.spawn((
+        Text::new(controls_help.get_text(language)),
+        controls_help,
+        ControlsHelpText,
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        TextLayout::new_with_justify(JustifyText::Center),
+    ));
+}
+
+pub(crate) fn spawn_settings_label(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text_key: &'static LocalizedText,
+    language: Language,
+    font_size: f32,
+) {
+    parent.spawn((
+        Text::new(get_text(text_key, language)),
+        LocalizedTextComponent::new(text_key),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.9, 0.3)),
+    ));
+}
+
+/// 一行 -/+ 按钮，中间是音量文字和可拖动的滑块
+fn spawn_volume_row(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    audio_settings: &AudioSettings,
+    channel: AudioChannel,
+    language: Language,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Px(12.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_settings_button(
+                parent,
+                ui_assets,
+                ButtonType::VolumeDown(channel),
+                Px(40.0),
+                Text::new("-"),
+            );
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Px(4.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        volume_text(audio_settings, channel, language),
+                        VolumeText { channel },
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                    parent
+                        .spawn((
+                            Node {
+                                width: Px(SLIDER_WIDTH),
+                                height: Px(SLIDER_HEIGHT),
+                                ..default()
+                            },
+                            BackgroundColor(SLIDER_TRACK_COLOR),
+                            Outline::new(Px(1.0), Val::ZERO, Color::srgb(0.5, 0.5, 0.7)),
+                            Interaction::default(),
+                            RelativeCursorPosition::default(),
+                            VolumeSlider { channel },
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Node {
+                                    width: Percent(channel.volume(audio_settings) * 100.0),
+                                    height: Percent(100.0),
+                                    ..default()
+                                },
+                                BackgroundColor(SLIDER_FILL_COLOR),
+                                VolumeSliderFill { channel },
+                            ));
+                        });
+                });
+            spawn_settings_button(
+                parent,
+                ui_assets,
+                ButtonType::VolumeUp(channel),
+                Px(40.0),
+                Text::new("+"),
+            );
+        });
+}
+
+/// 一行 -/+ 按钮，中间显示当前数值
+fn spawn_stepper_row(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    down: ButtonType,
+    up: ButtonType,
+    label: impl Bundle,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Px(12.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_settings_button(parent, ui_assets, down, Px(40.0), Text::new("-"));
+            parent.spawn((
+                label,
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    width: Px(140.0),
+                    ..default()
+                },
+            ));
+            spawn_settings_button(parent, ui_assets, up, Px(40.0), Text::new("+"));
+        });
+}
+
+pub(crate) fn spawn_settings_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    width: Val,
+    label: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width,
+                height: Px(36.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                label,
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn volume_text(audio_settings: &AudioSettings, channel: AudioChannel, language: Language) -> Text {
+    let text_key = match channel {
+        AudioChannel::Master => &MASTER_VOLUME,
+        AudioChannel::Music => &MUSIC_VOLUME,
+        AudioChannel::Ui => &UI_VOLUME,
+        AudioChannel::World => &WORLD_VOLUME,
+        AudioChannel::Hover => &HOVER_VOLUME,
+        AudioChannel::Disabled => &DISABLED_VOLUME,
+    };
+    let percent = (channel.volume(audio_settings) * 100.0).round() as u32;
+    Text::new(get_text_with_args(
+        text_key,
+        language,
+        &[percent.to_string().as_str()],
+    ))
+}
+
+fn mute_text(audio_settings: &AudioSettings, language: Language) -> Text {
+    if audio_settings.is_muted {
+        Text::new(get_text(&SOUND_OFF, language))
+    } else {
+        Text::new(get_text(&SOUND_ON, language))
+    }
+}
+
+fn option_text(
+    option: OptionText,
+    game_options: &GameOptions,
+    theme: &ThemeRegistry,
+    language: Language,
+) -> Text {
+    let toggle = |enabled: bool, text_key: &LocalizedText| {
+        let check = if enabled { "[x]" } else { "[ ]" };
+        format!("{} {}", check, get_text(text_key, language))
+    };
+
+    Text::new(match option {
+        OptionText::PauseOnFocusLoss => {
+            toggle(game_options.pause_on_focus_loss, &PAUSE_ON_FOCUS_LOSS)
+        }
+        OptionText::FrameLimit => get_text_with_args(
+            &FRAME_LIMIT,
+            language,
+            &[&game_options.frame_limit.label(language)],
+        ),
+        OptionText::BatterySaver => toggle(game_options.battery_saver, &BATTERY_SAVER),
+        OptionText::DyslexiaFont => toggle(game_options.dyslexia_font, &DYSLEXIA_FONT),
+        OptionText::VisualAudioCues => toggle(game_options.visual_audio_cues, &VISUAL_AUDIO_CUES),
+        OptionText::ReducedMotion => toggle(game_options.reduced_motion, &REDUCED_MOTION),
+        OptionText::TextToSpeech => toggle(game_options.text_to_speech, &TEXT_TO_SPEECH),
+        OptionText::Theme => {
+            get_text_with_args(&THEME_SETTING, language, &[&theme.current_name(language)])
+        }
+        OptionText::WindowMode => {
+            let mode = match game_options.window_mode {
+                WindowMode::Windowed => &WINDOW_MODE_WINDOWED,
+                WindowMode::BorderlessFullscreen(_) => &WINDOW_MODE_BORDERLESS,
+                WindowMode::Fullscreen(..) => &WINDOW_MODE_FULLSCREEN,
+            };
+            get_text_with_args(&WINDOW_MODE, language, &[&mode.get(language)])
+        }
+        OptionText::Vsync => toggle(game_options.vsync, &VSYNC),
+        OptionText::UiScale => get_text_with_args(
+            &UI_SCALE,
+            language,
+            &[&((game_options.ui_scale * 100.0).round() as u32).to_string()],
+        ),
+    })
+}
+
+// ============ 设置菜单系统 ============
+
+fn handle_settings_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    settings_menus: Query<Entity, With<SettingsMenuUI>>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut game_options: ResMut<GameOptions>,
+    mut theme: ResMut<ThemeRegistry>,
+    input_map: Res<InputMap>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    state: Res<State<GameStateEnum>>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::OpenSettings if settings_menus.is_empty() => {
+                info!("打开设置菜单");
+                spawn_settings_menu(
+                    &mut commands,
+                    &ui_assets,
+                    &audio_settings,
+                    &game_options,
+                    &input_map,
+                    &theme,
+                    current_language.language,
+                    state.get().clone(),
+                );
+            }
+            ButtonType::CloseSettings => {
+                info!("关闭设置菜单");
+                for entity in settings_menus.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ButtonType::VolumeDown(channel) => {
+                adjust_volume(&mut audio_settings, channel, -VOLUME_STEP);
+            }
+            ButtonType::VolumeUp(channel) => {
+                adjust_volume(&mut audio_settings, channel, VOLUME_STEP);
+            }
+            ButtonType::ToggleMute => {
+                audio_settings.is_muted = !audio_settings.is_muted;
+                info!("静音: {}", audio_settings.is_muted);
+            }
+            ButtonType::TogglePauseOnFocusLoss => {
+                game_options.pause_on_focus_loss = !game_options.pause_on_focus_loss;
+                info!("失去焦点时暂停: {}", game_options.pause_on_focus_loss);
+            }
+            ButtonType::CycleFrameLimit => {
+                game_options.frame_limit = game_options.frame_limit.next();
+                info!("帧率上限: {:?}", game_options.frame_limit);
+            }
+            ButtonType::ToggleBatterySaver => {
+                game_options.battery_saver = !game_options.battery_saver;
+                info!("菜单省电模式: {}", game_options.battery_saver);
+            }
+            ButtonType::ToggleDyslexiaFont => {
+                game_options.dyslexia_font = !game_options.dyslexia_font;
+                info!("易读字体: {}", game_options.dyslexia_font);
+            }
+            ButtonType::ToggleVisualAudioCues => {
+                game_options.visual_audio_cues = !game_options.visual_audio_cues;
+                info!("音效视觉提示: {}", game_options.visual_audio_cues);
+            }
+            ButtonType::ToggleReducedMotion => {
+                game_options.reduced_motion = !game_options.reduced_motion;
+                info!("减少动态效果: {}", game_options.reduced_motion);
+            }
+            ButtonType::ToggleTextToSpeech => {
+                game_options.text_to_speech = !game_options.text_to_speech;
+                info!("语音朗读: {}", game_options.text_to_speech);
+            }
+            ButtonType::CycleTheme => {
+                theme.cycle();
+            }
+            ButtonType::CycleWindowMode => {
+                game_options.window_mode = next_window_mode(game_options.window_mode);
+                info!("窗口模式: {:?}", game_options.window_mode);
+            }
+            ButtonType::ToggleVsync => {
+                game_options.vsync = !game_options.vsync;
+                info!("垂直同步: {}", game_options.vsync);
+            }
+            ButtonType::UiScaleDown => {
+                adjust_ui_scale(&mut game_options, -UI_SCALE_STEP);
+            }
+            ButtonType::UiScaleUp => {
+                adjust_ui_scale(&mut game_options, UI_SCALE_STEP);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn adjust_volume(audio_settings: &mut AudioSettings, channel: AudioChannel, delta: f32) {
+    let volume = channel.volume_mut(audio_settings);
+    // 按步长取整，避免浮点误差累积
+    *volume = ((*volume + delta) * 10.0).round().clamp(0.0, 10.0) / 10.0;
+    info!("{:?} 音量调整为 {:.0}%", channel, *volume * 100.0);
+}
+
+fn adjust_ui_scale(game_options: &mut GameOptions, delta: f32) {
+    // 按步长取整，避免浮点误差累积
+    game_options.ui_scale = ((game_options.ui_scale + delta) * 10.0).round() / 10.0;
+    game_options.ui_scale = game_options.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    info!("界面缩放调整为 {:.0}%", game_options.ui_scale * 100.0);
+}
+
+/// 窗口 → 无边框全屏 → 独占全屏 → 窗口
+fn next_window_mode(mode: WindowMode) -> WindowMode {
+    match mode {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        WindowMode::BorderlessFullscreen(_) => {
+            WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+        }
+        WindowMode::Fullscreen(..) => WindowMode::Windowed,
+    }
+}
+
+/// 按住滑块拖动时按光标位置设置音量（取整到 1%），拖出轨道两端时取 0% 或 100%
+fn drag_volume_sliders(
+    sliders: Query<(&VolumeSlider, &Interaction, &RelativeCursorPosition)>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    for (slider, interaction, cursor) in sliders.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+
+        let volume = (position.x.clamp(0.0, 1.0) * 100.0).round() / 100.0;
+        // 只在数值变化时写入，避免按住不动也每帧触发保存和音量刷新
+        if slider.channel.volume(&audio_settings) != volume {
+            *slider.channel.volume_mut(&mut audio_settings) = volume;
+        }
+    }
+}
+
+/// 设置菜单打开时，ESC 只关闭设置菜单（暂停菜单的 ESC 处理会跳过）
+fn close_settings_on_escape(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings_menus: Query<Entity, With<SettingsMenuUI>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        for entity in settings_menus.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_settings_texts(
+    audio_settings: Res<AudioSettings>,
+    game_options: Res<GameOptions>,
+    theme: Res<ThemeRegistry>,
+    current_language: Res<CurrentLanguage>,
+    mut volume_texts: Query<
+        (&VolumeText, &mut Text),
+        (Without<MuteToggleText>, Without<OptionText>),
+    >,
+    mut mute_texts: Query<&mut Text, (With<MuteToggleText>, Without<OptionText>)>,
+    mut option_texts: Query<(&OptionText, &mut Text)>,
+    mut slider_fills: Query<(&VolumeSliderFill, &mut Node)>,
+) {
+    if !audio_settings.is_changed()
+        && !game_options.is_changed()
+        && !theme.is_changed()
+        && !current_language.is_changed()
+    {
+        return;
+    }
+
+    for (option, mut text) in option_texts.iter_mut() {
+        *text = option_text(*option, &game_options, &theme, current_language.language);
+    }
+
+    for (volume_text_component, mut text) in volume_texts.iter_mut() {
+        *text = volume_text(
+            &audio_settings,
+            volume_text_component.channel,
+            current_language.language,
+        );
+    }
+
+    for mut text in mute_texts.iter_mut() {
+        *text = mute_text(&audio_settings, current_language.language);
+    }
+
+    for (fill, mut node) in slider_fills.iter_mut() {
+        node.width = Percent(fill.channel.volume(&audio_settings) * 100.0);
+    }
+}
+
+/// 切到其他窗口时暂停关卡，回来后由玩家在暂停菜单中继续
+fn pause_on_focus_loss(
+    mut focus_events: EventReader<WindowFocused>,
+    game_options: Res<GameOptions>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    if lost_focus && game_options.pause_on_focus_loss {
+        info!("⏸️ 窗口失去焦点，自动暂停");
+        next_state.set(GameStateEnum::Paused);
+    }
+}
+
+/// 按通道音量和声音自身的倍率重新计算正在播放的音效；背景音乐的音量由 MusicPlugin 每帧设置
+fn apply_audio_volumes(
+    audio_settings: Res<AudioSettings>,
+    mut sinks: Query<(&mut AudioSink, &AudioBus, Option<&SoundGain>), Without<BackgroundMusic>>,
+) {
+    if !audio_settings.is_changed() {
+        return;
+    }
+
+    for (mut sink, bus, gain) in sinks.iter_mut() {
+        let gain = gain.map_or(1.0, |gain| gain.0);
+        sink.set_volume(Volume::Linear(audio_settings.bus_volume(*bus) * gain));
+    }
+}
+
+/// 显示模式、垂直同步和界面缩放变化后立即作用于主窗口
+fn apply_video_options(
+    game_options: Res<GameOptions>,
+    mut ui_scale: ResMut<UiScale>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !game_options.is_changed() {
+        return;
+    }
+
+    if ui_scale.0 != game_options.ui_scale {
+        ui_scale.0 = game_options.ui_scale;
+    }
+
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    if window.mode != game_options.window_mode {
+        window.mode = game_options.window_mode;
+    }
+    let present_mode = if game_options.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+/// 按设置和当前语言选择界面字体：易读字体优先，否则取语言字体链中第一个已加载的字体，
+/// 都还没加载完成时先用默认字体
+pub fn select_ui_font(
+    ui_assets: &UIAssets,
+    game_options: &GameOptions,
+    language: Language,
+    asset_server: &AssetServer,
+) -> Handle<Font> {
+    match &ui_assets.dyslexic_font {
+        Some(dyslexic_font)
+            if game_options.dyslexia_font
+                && language == Language::English
+                && asset_server.is_loaded_with_dependencies(dyslexic_font) =>
+        {
+            dyslexic_font.clone()
+        }
+        _ => language
+            .font_chain()
+            .filter_map(|path| ui_assets.language_fonts.get(path))
+            .find(|font| asset_server.is_loaded_with_dependencies(*font))
+            .unwrap_or(&ui_assets.default_font)
+            .clone(),
+    }
+}
+
+/// 字体设置或语言变化后，替换所有正在使用旧字体的文字
+fn apply_ui_font(
+    game_options: Res<GameOptions>,
+    current_language: Res<CurrentLanguage>,
+    asset_server: Res<AssetServer>,
+    ui_assets: Option<ResMut<UIAssets>>,
+    mut text_fonts: Query<&mut TextFont, Without<FallbackFontAnchor>>,
+    mut load_failure_reported: Local<bool>,
+) {
+    let Some(mut ui_assets) = ui_assets else {
+        return;
+    };
+
+    if game_options.dyslexia_font && ui_assets.dyslexic_font.is_none() {
+        ui_assets.dyslexic_font = Some(asset_server.load(DYSLEXIC_FONT_PATH));
+    }
+
+    if let Some(dyslexic_font) = &ui_assets.dyslexic_font
+        && !*load_failure_reported
+        && asset_server.load_state(dyslexic_font).is_failed()
+    {
+        warn!(
+            "易读字体加载失败，请确认 assets/{} 存在",
+            DYSLEXIC_FONT_PATH
+        );
+        *load_failure_reported = true;
+    }
+
+    let font = select_ui_font(
+        &ui_assets,
+        &game_options,
+        current_language.language,
+        &asset_server,
+    );
+    if font == ui_assets.font {
+        return;
+    }
+
+    for mut text_font in text_fonts.iter_mut() {
+        if text_font.font == ui_assets.font {
+            text_font.font = font.clone();
+        }
+    }
+    info!("🔤 界面字体已切换");
+    ui_assets.font = font;
+}
@@ -0,0 +1,314 @@
+// src/bus_puzzle/sim_clock.rs - 模拟时钟（可暂停、可缩放，所有玩法模拟系统共用）与游戏速度控制
+
+use crate::bus_puzzle::{
+    get_text_with_args, show_contextual_tip, ui_audio::GameplayUI, ButtonComponent, ButtonType,
+    CurrentLanguage, GameState, GameStateEnum, InputAction, InputMap, LocalizedTextComponent,
+    TipType, UIAssets, GAME_SPEED, GAME_SPEED_STEP, GAME_SPEED_STEP_HINT,
+};
+use bevy::prelude::{Val::Px, *};
+
+/// 可选的模拟速度
+pub const SIM_SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+
+const DEFAULT_SPEED_INDEX: usize = 1;
+
+/// 单步模式下每步推进的模拟秒数
+const SIM_STEP_SECS: f32 = 0.1;
+
+/// 玩法模拟使用的时钟：暂停时不前进，按 `speed` 缩放
+///
+/// 乘客移动、公交车、上下车、客流生成和动态事件都从这里读取时间，
+/// 界面动画、提示等仍使用 `Time`，暂停时照常播放。
+#[derive(Resource)]
+pub struct SimClock {
+    speed_index: usize,
+    stepping: bool,     // 单步模式：只在请求单步时前进
+    pending_steps: u32, // 尚未执行的单步次数
+    paused: bool,
+    delta: f32,
+    elapsed: f32,
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self {
+            speed_index: DEFAULT_SPEED_INDEX,
+            stepping: false,
+            pending_steps: 0,
+            paused: false,
+            delta: 0.0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl SimClock {
+    /// 本帧模拟前进的秒数，暂停时为 0
+    pub fn delta_secs(&self) -> f32 {
+        self.delta
+    }
+
+    /// 本关卡开始以来累计的模拟秒数
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn speed(&self) -> f32 {
+        SIM_SPEEDS[self.speed_index]
+    }
+
+    /// 切换到指定速度并退出单步模式
+    pub fn set_speed_index(&mut self, index: usize) {
+        self.speed_index = index.min(SIM_SPEEDS.len() - 1);
+        self.stepping = false;
+        self.pending_steps = 0;
+    }
+
+    /// 进入单步模式并前进一步
+    pub fn step(&mut self) {
+        self.stepping = true;
+        self.pending_steps += 1;
+    }
+}
+
+// ============ 模拟时钟插件 ============
+
+pub struct SimClockPlugin;
+
+impl Plugin for SimClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimClock>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_sim_clock)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_sim_clock)
+            .add_systems(PreUpdate, tick_sim_clock)
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_speed_controls)
+            .add_systems(
+                Update,
+                (handle_speed_input, update_speed_buttons)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn reset_sim_clock(mut clock: ResMut<SimClock>) {
+    *clock = SimClock::default();
+}
+
+/// 每帧开始时推进模拟时钟；暂停菜单、地形编辑等暂停状态下不前进
+fn tick_sim_clock(
+    time: Res<Time>,
+    state: Res<State<GameStateEnum>>,
+    game_state: Res<GameState>,
+    mut clock: ResMut<SimClock>,
+) {
+    clock.paused = game_state.is_paused
+        || !matches!(state.get(), GameStateEnum::Playing | GameStateEnum::Replay);
+
+    clock.delta = if clock.paused {
+        0.0
+    } else if clock.stepping {
+        if clock.pending_steps > 0 {
+            clock.pending_steps -= 1;
+            SIM_STEP_SECS
+        } else {
+            0.0
+        }
+    } else {
+        time.delta_secs() * clock.speed()
+    };
+    clock.elapsed += clock.delta;
+}
+
+// ============ 游戏速度控制 ============
+
+fn handle_speed_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut clock: ResMut<SimClock>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let language = current_language.language;
+    let mut speed_index = None;
+    let mut step = input_map.just_pressed(&keyboard_input, InputAction::SimStep);
+
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+        match button_component.button_type {
+            ButtonType::SetSimSpeed(index) => speed_index = Some(index),
+            ButtonType::StepSimulation => step = true,
+            _ => {}
+        }
+    }
+
+    // 单步模式下按减速/加速键先恢复到原来的速度
+    if input_map.just_pressed(&keyboard_input, InputAction::SimSlower) {
+        speed_index = Some(if clock.stepping {
+            clock.speed_index
+        } else {
+            clock.speed_index.saturating_sub(1)
+        });
+    }
+    if input_map.just_pressed(&keyboard_input, InputAction::SimFaster) {
+        speed_index = Some(if clock.stepping {
+            clock.speed_index
+        } else {
+            clock.speed_index + 1
+        });
+    }
+
+    if let Some(index) = speed_index {
+        clock.set_speed_index(index);
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(&GAME_SPEED, language, &[&clock.speed().to_string()]),
+            TipType::Controls,
+            1.5,
+        );
+        info!("⏩ 游戏速度: {}x", clock.speed());
+    } else if step {
+        if !clock.stepping {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text_with_args(
+                    &GAME_SPEED_STEP_HINT,
+                    language,
+                    &[
+                        &input_map.key_name(InputAction::SimStep),
+                        &input_map.key_name(InputAction::SimFaster),
+                        &input_map.key_name(InputAction::SimSlower),
+                    ],
+                ),
+                TipType::Controls,
+                4.0,
+            );
+        }
+        clock.step();
+    }
+}
+
+fn spawn_speed_controls(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    clock: Res<SimClock>,
+    input_map: Res<InputMap>,
+    current_language: Res<CurrentLanguage>,
+) {
+    // 暂停菜单中改键后回到关卡时重新生成，按钮上的按键名随之更新
+    let step_label = LocalizedTextComponent::with_args(
+        &GAME_SPEED_STEP,
+        vec![input_map.key_name(InputAction::SimStep)],
+    );
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(10.0),
+                top: Px(600.0),
+                flex_direction: FlexDirection::Row,
+                column_gap: Px(4.0),
+                ..default()
+            },
+            ZIndex(200),
+            GameplayUI,
+            Name::new("Speed Controls"),
+        ))
+        .with_children(|parent| {
+            for (index, speed) in SIM_SPEEDS.iter().enumerate() {
+                spawn_speed_button(
+                    parent,
+                    &ui_assets,
+                    ButtonType::SetSimSpeed(index),
+                    Text::new(format!("{}x", speed)),
+                    None,
+                    !clock.stepping && clock.speed_index == index,
+                );
+            }
+            spawn_speed_button(
+                parent,
+                &ui_assets,
+                ButtonType::StepSimulation,
+                Text::new(step_label.get_text(current_language.language)),
+                Some(step_label),
+                clock.stepping,
+            );
+        });
+}
+
+fn spawn_speed_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    text: Text,
+    localized: Option<LocalizedTextComponent>,
+    selected: bool,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Px(32.0),
+                min_width: Px(44.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(6.0)),
+                border: UiRect::all(Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.2, 0.3)),
+            BorderColor(speed_button_border(selected)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            let mut label = parent.spawn((
+                text,
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            if let Some(localized) = localized {
+                label.insert(localized);
+            }
+        });
+}
+
+/// 当前速度（或单步模式）按钮用金黄色边框标出
+fn speed_button_border(selected: bool) -> Color {
+    if selected {
+        Color::srgb(1.0, 0.8, 0.0)
+    } else {
+        Color::srgb(0.4, 0.4, 0.5)
+    }
+}
+
+fn update_speed_buttons(
+    clock: Res<SimClock>,
+    mut buttons: Query<(&ButtonComponent, &mut BorderColor)>,
+) {
+    if !clock.is_changed() {
+        return;
+    }
+
+    for (button_component, mut border_color) in buttons.iter_mut() {
+        let selected = match button_component.button_type {
+            ButtonType::SetSimSpeed(index) => !clock.stepping && clock.speed_index == index,
+            ButtonType::StepSimulation => clock.stepping,
+            _ => continue,
+        };
+        *border_color = BorderColor(speed_button_border(selected));
+    }
+}
@@ -2,13 +2,19 @@
 // 智能公交车生成系统
 
 use crate::bus_puzzle::{
-    find_optimal_path, BusPathfindingAgent, BusPathfindingManager, BusVehicle, GameStateEnum,
-    LevelManager, PathfindingGraph, RouteSegment, SegmentPlacedEvent, SegmentRemovedEvent,
-    StationEntity, PASSENGER_Z,
+    compute_bus_loop, find_optimal_path, find_optimal_path_on_line, line_color, line_id,
+    manual_fleet, route_key, simulation_running, BusDirection, BusPathfindingAgent,
+    BusPathfindingManager, BusPathfindingState, BusRouteInfo, BusState, BusVehicle, Fleet,
+    GameState, LevelManager, PathfindingGraph, RouteLines, RouteSchedule, RouteSchedules,
+    SegmentPlacedEvent, SegmentRemovedEvent, SimClock, StationEntity, ThemeRegistry, EXPRESS_LINE,
+    PASSENGER_Z,
 };
 use bevy::prelude::*;
 use std::collections::HashSet;
 
+/// 同一线路上多辆车之间的发车间隔（秒），避免几辆车挤在一起
+const BUS_STAGGER_SECS: f32 = 4.0;
+
 pub struct SmartBusGenerationPlugin;
 
 impl Plugin for SmartBusGenerationPlugin {
@@ -21,7 +27,7 @@ impl Plugin for SmartBusGenerationPlugin {
                 cleanup_invalid_buses,
             )
                 .chain()
-                .run_if(in_state(GameStateEnum::Playing)),
+                .run_if(simulation_running),
         );
     }
 }
@@ -33,22 +39,34 @@ fn auto_generate_buses_on_connection(
     mut bus_manager: ResMut<BusPathfindingManager>,
     pathfinding_graph: Res<PathfindingGraph>,
     stations: Query<&StationEntity>,
-    route_segments: Query<&RouteSegment>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
     level_manager: Res<LevelManager>,
+    (schedules, fleet): (Res<RouteSchedules>, Res<Fleet>),
+    (mut route_lines, game_state): (ResMut<RouteLines>, Res<GameState>),
     existing_buses: Query<Entity, With<BusVehicle>>,
-    mut last_trigger_time: Local<f32>,
-    time: Res<Time>,
+    mut cooldown: Local<f32>,
+    mut has_route_changes: Local<bool>,
+    sim_clock: Res<SimClock>,
 ) {
-    let has_route_changes = !segment_placed_events.is_empty() || !segment_removed_events.is_empty();
+    // 间隔内的变化留到下次生成，避免最后几次修改被忽略（快速回放时尤其明显）
+    // 路线段改到其他线路后同样需要重新生成（修订号随关卡重置，恢复存档时不会误触发）
+    *has_route_changes |= !segment_placed_events.is_empty()
+        || !segment_removed_events.is_empty()
+        || route_lines.revision != route_lines.bus_revision;
+    if route_lines.bus_revision != route_lines.revision {
+        route_lines.bus_revision = route_lines.revision;
+    }
 
     // 清空事件读取器
     segment_placed_events.clear();
     segment_removed_events.clear();
 
-    // 防止频繁重生成：最少间隔2秒
-    if has_route_changes && (time.elapsed_secs() - *last_trigger_time) > 2.0 {
+    // 防止频繁重生成：最少间隔2秒（模拟时间，加速时按比例缩短）
+    *cooldown = (*cooldown - sim_clock.delta_secs()).max(0.0);
+    if *has_route_changes && *cooldown <= 0.0 {
+        *has_route_changes = false;
         info!("🔄 检测到路线变化，重新生成公交车系统...");
 
         // 检查是否有有效的站点连接
@@ -64,14 +82,18 @@ fn auto_generate_buses_on_connection(
             generate_smart_bus_routes(
                 &mut commands,
                 &asset_server,
+                &theme,
                 &level_manager,
                 &mut bus_manager,
+                &schedules,
                 &pathfinding_graph,
                 &stations,
-                &route_segments,
+                &route_lines,
+                &game_state,
+                manual_fleet(&level_manager).then_some(&*fleet),
             );
 
-            *last_trigger_time = time.elapsed_secs();
+            *cooldown = 2.0;
             info!("✅ 公交车系统重新生成完成");
         } else {
             info!("❌ 连接的站点不足，暂不生成公交车");
@@ -86,36 +108,41 @@ fn manual_bus_generation_for_tutorial(
     mut bus_manager: ResMut<BusPathfindingManager>,
     pathfinding_graph: Res<PathfindingGraph>,
     stations: Query<&StationEntity>,
-    route_segments: Query<&RouteSegment>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+    (schedules, fleet): (Res<RouteSchedules>, Res<Fleet>),
+    (route_lines, game_state): (Res<RouteLines>, Res<GameState>),
     existing_buses: Query<Entity, With<BusVehicle>>,
 ) {
     // 在教学关卡中，按空格键手动生成公交车
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        if let Some(level_data) = &level_manager.current_level {
-            if level_data.id == "tutorial_01" {
-                info!("🎓 教学关卡：手动启动公交系统");
-
-                // 清理现有公交车
-                for bus_entity in existing_buses.iter() {
-                    commands.entity(bus_entity).despawn();
-                }
+    if keyboard_input.just_pressed(KeyCode::Space)
+        && let Some(level_data) = &level_manager.current_level
+        && level_data.id == "tutorial_01"
+    {
+        info!("🎓 教学关卡：手动启动公交系统");
+
+        // 清理现有公交车
+        for bus_entity in existing_buses.iter() {
+            commands.entity(bus_entity).despawn();
+        }
 
-                // 生成公交车
-                generate_smart_bus_routes(
-                    &mut commands,
-                    &asset_server,
-                    &level_manager,
-                    &mut bus_manager,
-                    &pathfinding_graph,
-                    &stations,
-                    &route_segments,
-                );
+        // 生成公交车
+        generate_smart_bus_routes(
+            &mut commands,
+            &asset_server,
+            &theme,
+            &level_manager,
+            &mut bus_manager,
+            &schedules,
+            &pathfinding_graph,
+            &stations,
+            &route_lines,
+            &game_state,
+            manual_fleet(&level_manager).then_some(&*fleet),
+        );
 
-                info!("✅ 教学关卡公交系统启动完成");
-            }
-        }
+        info!("✅ 教学关卡公交系统启动完成");
     }
 }
 
@@ -125,9 +152,9 @@ fn cleanup_invalid_buses(
     buses: Query<(Entity, &BusPathfindingAgent)>,
     pathfinding_graph: Res<PathfindingGraph>,
     mut cleanup_timer: Local<f32>,
-    time: Res<Time>,
+    sim_clock: Res<SimClock>,
 ) {
-    *cleanup_timer += time.delta_secs();
+    *cleanup_timer += sim_clock.delta_secs();
 
     // 每10秒检查一次
     if *cleanup_timer > 10.0 {
@@ -141,7 +168,13 @@ fn cleanup_invalid_buses(
                 let from = &bus_agent.stations_to_visit[i];
                 let to = &bus_agent.stations_to_visit[i + 1];
 
-                if find_optimal_path(&pathfinding_graph, from, to).is_none() {
+                let path = match &bus_agent.line_id {
+                    Some(line_id) => {
+                        find_optimal_path_on_line(&pathfinding_graph, from, to, line_id)
+                    }
+                    None => find_optimal_path(&pathfinding_graph, from, to),
+                };
+                if path.is_none() {
                     valid_route = false;
                     break;
                 }
@@ -178,49 +211,73 @@ fn analyze_station_connectivity(
     connected_stations
 }
 
-/// 智能生成公交路线
+/// 智能生成公交路线：每条命名线路单独分析，公交车只在自己的线路上行驶
+/// 手动车队模式下车辆数取自车场分配到该线路的车，全部投放到线路的第一条路线上
 fn generate_smart_bus_routes(
     commands: &mut Commands,
     asset_server: &AssetServer,
+    theme: &ThemeRegistry,
     level_manager: &LevelManager,
     bus_manager: &mut BusPathfindingManager,
+    schedules: &RouteSchedules,
     pathfinding_graph: &PathfindingGraph,
     stations: &Query<&StationEntity>,
-    _route_segments: &Query<&RouteSegment>,
+    route_lines: &RouteLines,
+    game_state: &GameState,
+    fleet: Option<&Fleet>,
 ) {
+    let _span = info_span!("generate_smart_bus_routes").entered();
+
     bus_manager.bus_routes.clear();
 
     let station_list: Vec<_> = stations.iter().collect();
-    let mut processed_stations = HashSet::new();
 
     info!("🧠 开始智能路线分析...");
 
-    for (i, start_station) in station_list.iter().enumerate() {
-        let start_name = &start_station.station_data.name;
-
-        if processed_stations.contains(start_name) {
-            continue;
-        }
+    // 高架层的路线段组成一条独立的快线
+    let mut lines = route_lines.lines_in_use(game_state.placed_segments.keys().copied());
+    if !game_state.elevated_segments.is_empty() {
+        lines.push(EXPRESS_LINE);
+    }
 
-        // 寻找从当前站点可达的其他站点
-        let mut route_stations = vec![start_name.clone()];
-        let mut current_station = start_name;
+    for line in lines {
+        let line_id = line_id(line);
+        // 站点可以同时属于多条线路，乘客在这些站点换乘
+        let mut processed_stations = HashSet::new();
+        let mut line_routes = 0;
 
-        for end_station in station_list.iter().skip(i + 1) {
-            let end_name = &end_station.station_data.name;
+        for (i, start_station) in station_list.iter().enumerate() {
+            let start_name = &start_station.station_data.name;
 
-            if processed_stations.contains(end_name) {
+            if processed_stations.contains(start_name) {
                 continue;
             }
 
-            // 使用寻路算法检查连通性
-            if let Some(path) = find_optimal_path(pathfinding_graph, current_station, end_name) {
-                if path.len() > 1 {
+            // 寻找从当前站点沿本线路可达的其他站点
+            let mut route_stations = vec![start_name.clone()];
+            let mut current_station = start_name;
+
+            for end_station in station_list.iter().skip(i + 1) {
+                let end_name = &end_station.station_data.name;
+
+                if processed_stations.contains(end_name) {
+                    continue;
+                }
+
+                // 使用寻路算法检查连通性
+                if let Some(path) = find_optimal_path_on_line(
+                    pathfinding_graph,
+                    current_station,
+                    end_name,
+                    &line_id,
+                ) && path.len() > 1
+                {
                     route_stations.push(end_name.clone());
                     current_station = end_name;
 
                     info!(
-                        "📍 发现连接: {} -> {}",
+                        "📍 线路 {} 发现连接: {} -> {}",
+                        line,
                         route_stations[route_stations.len() - 2],
                         end_name
                     );
@@ -231,47 +288,93 @@ fn generate_smart_bus_routes(
                     }
                 }
             }
-        }
 
-        // 创建公交路线和车辆
-        if route_stations.len() >= 2 {
-            let route_id = format!("智能路线_{}", bus_manager.bus_routes.len() + 1);
-
-            spawn_smart_bus(
-                commands,
-                asset_server,
-                level_manager,
-                pathfinding_graph,
-                &route_id,
-                &route_stations,
-            );
+            // 创建公交路线和车辆
+            if route_stations.len() >= 2 {
+                line_routes += 1;
+                let route_id = if line_routes == 1 {
+                    line_id.clone()
+                } else {
+                    format!("{}_{}", line_id, line_routes)
+                };
+                let schedule = schedules.get(&route_stations);
+                let bus_count = match fleet {
+                    Some(fleet) if line_routes == 1 => fleet.buses_on(line),
+                    Some(_) => 0,
+                    None => schedule.bus_count,
+                };
+                let is_circular =
+                    compute_bus_loop(pathfinding_graph, &route_stations, Some(&line_id))
+                        .is_some_and(|bus_loop| bus_loop.is_circular);
+                let route = BusRouteInfo {
+                    route_id: route_id.clone(),
+                    stations: route_stations.clone(),
+                    is_circular,
+                    max_vehicles: bus_count,
+                    line,
+                };
+
+                // 按线路调度生成车辆
+                for bus_index in 0..bus_count {
+                    spawn_smart_bus(
+                        commands,
+                        asset_server,
+                        theme,
+                        level_manager,
+                        pathfinding_graph,
+                        &route,
+                        schedule,
+                        bus_index,
+                    );
+                }
 
-            // 标记这些站点为已处理
-            for station_name in &route_stations {
-                processed_stations.insert(station_name.clone());
-            }
+                bus_manager.bus_routes.insert(route_id.clone(), route);
+
+                // 标记这些站点为已处理
+                for station_name in &route_stations {
+                    processed_stations.insert(station_name.clone());
+                }
 
-            info!("🚌 创建公交路线 {}: {:?}", route_id, route_stations);
+                info!(
+                    "🚌 创建公交路线 {} ({}): {} 辆车",
+                    route_id,
+                    route_key(&route_stations),
+                    bus_count
+                );
+            }
         }
     }
 
     info!("✅ 智能公交系统生成完成");
 }
 
-/// 生成智能公交车
-fn spawn_smart_bus(
+/// 生成智能公交车。第一辆车从起点出发，其余车辆轮流停在线路两端，按序号错开发车时间
+/// 每辆车分配固定的行驶圈，沿同一条路反复运行
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_smart_bus(
     commands: &mut Commands,
     asset_server: &AssetServer,
+    theme: &ThemeRegistry,
     level_manager: &LevelManager,
     pathfinding_graph: &PathfindingGraph,
-    route_id: &str,
-    stations: &[String],
+    route: &BusRouteInfo,
+    schedule: RouteSchedule,
+    bus_index: u32,
 ) {
+    let route_id = route.route_id.as_str();
+    let stations = &route.stations;
+    let line_id = line_id(route.line);
     if stations.is_empty() {
         return;
     }
 
-    let start_station = &stations[0];
+    // 奇数序号的车从终点站出发
+    let start_index = if bus_index % 2 == 1 {
+        stations.len() - 1
+    } else {
+        0
+    };
+    let start_station = &stations[start_index];
     if let Some(&start_pos) = pathfinding_graph.station_lookup.get(start_station) {
         let (grid_width, grid_height) = if let Some(level_data) = &level_manager.current_level {
             level_data.grid_size
@@ -283,65 +386,121 @@ fn spawn_smart_bus(
             start_pos.to_world_pos(level_manager.tile_size, grid_width, grid_height)
                 + Vec3::Z * (PASSENGER_Z + 0.1);
 
-        // 生成路线颜色
-        let route_colors = [
-            Color::srgb(1.0, 0.2, 0.2), // 红色
-            Color::srgb(0.2, 1.0, 0.2), // 绿色
-            Color::srgb(0.2, 0.2, 1.0), // 蓝色
-            Color::srgb(1.0, 1.0, 0.2), // 黄色
-        ];
-        let color_index = route_id.len() % route_colors.len();
-        let route_color = route_colors[color_index];
-
-        let vehicle_id = format!("智能公交_{}", route_id);
-
-        // 生成初始路径
-        let initial_target = if stations.len() > 1 {
-            stations[1].clone()
-        } else {
-            start_station.clone()
+        let vehicle_id = format!("智能公交_{}_{}", route_id, bus_index + 1);
+
+        let mut bus_vehicle = BusVehicle {
+            vehicle_id: vehicle_id.clone(),
+            route_id: route_id.to_string(),
+            capacity: 30,
+            current_passengers: Vec::new(),
+            current_stop_index: start_index,
+            direction: BusDirection::Forward,
+            state: BusState::Traveling,
+            speed: 80.0,
+            dwell_time: schedule.frequency.dwell_time(),
+            remaining_dwell: 0.0,
+            target_position: None,
         };
 
-        let initial_path = find_optimal_path(pathfinding_graph, start_station, &initial_target)
-            .unwrap_or_default();
-
-        commands.spawn((
-            Name::new(format!("Smart Bus {}", vehicle_id)),
-            Sprite {
-                image: asset_server.load("textures/bus.png"),
-                color: route_color,
-                custom_size: Some(Vec2::new(48.0, 48.0)),
-                ..default()
-            },
-            Transform::from_translation(spawn_world_pos),
-            BusVehicle {
-                vehicle_id: vehicle_id.clone(),
-                route_id: route_id.to_string(),
-                capacity: 30,
-                current_passengers: Vec::new(),
-                current_stop_index: 0,
-                direction: crate::bus_puzzle::BusDirection::Forward,
-                state: crate::bus_puzzle::BusState::Traveling,
-                speed: 80.0,
-                dwell_time: 3.0,
-                remaining_dwell: 0.0,
-                target_position: None,
-            },
+        let mut bus_loop = compute_bus_loop(pathfinding_graph, stations, Some(&line_id));
+
+        let agent = if bus_index == 0 {
+            // 生成初始路径
+            let initial_target = if stations.len() > 1 {
+                stations[1].clone()
+            } else {
+                start_station.clone()
+            };
+
+            let initial_path = match bus_loop.as_mut() {
+                Some(bus_loop) => bus_loop.take_next_leg(),
+                None => find_optimal_path_on_line(
+                    pathfinding_graph,
+                    start_station,
+                    &initial_target,
+                    &line_id,
+                )
+                .unwrap_or_default(),
+            };
+
             BusPathfindingAgent {
                 vehicle_id: vehicle_id.clone(),
                 route_id: route_id.to_string(),
                 current_path: initial_path,
                 current_step: 0,
                 target_station: initial_target,
-                state: crate::bus_puzzle::BusPathfindingState::Following,
+                state: BusPathfindingState::Following,
                 path_progress: 0.0,
                 next_station_index: 1,
                 stations_to_visit: stations.to_vec(),
-                direction: crate::bus_puzzle::BusDirection::Forward,
+                direction: BusDirection::Forward,
                 is_returning: false,
-            },
+                line_id: Some(line_id.clone()),
+            }
+        } else {
+            // 视为刚到达线路一端：停靠结束后调头驶向另一端
+            if let Some(bus_loop) = bus_loop.as_mut() {
+                bus_loop.next_leg = start_index;
+            }
+            bus_vehicle.state = BusState::AtStop;
+            bus_vehicle.remaining_dwell =
+                bus_vehicle.dwell_time + BUS_STAGGER_SECS * (bus_index / 2) as f32;
+
+            BusPathfindingAgent {
+                vehicle_id: vehicle_id.clone(),
+                route_id: route_id.to_string(),
+                current_path: Vec::new(),
+                current_step: 0,
+                target_station: start_station.clone(),
+                state: BusPathfindingState::AtStation,
+                path_progress: 0.0,
+                next_station_index: start_index,
+                stations_to_visit: stations.to_vec(),
+                direction: if start_index == 0 {
+                    BusDirection::Backward
+                } else {
+                    BusDirection::Forward
+                },
+                is_returning: start_index == 0,
+                line_id: Some(line_id.clone()),
+            }
+        };
+
+        let mut bus = commands.spawn(bus_bundle(
+            asset_server,
+            theme,
+            route.line,
+            spawn_world_pos,
+            bus_vehicle,
+            agent,
         ));
+        if let Some(bus_loop) = bus_loop {
+            bus.insert(bus_loop);
+        }
 
         info!("🚌 生成智能公交车: {} 路线: {}", vehicle_id, route_id);
     }
 }
+
+/// 公交车实体的组件，公交车使用所属线路的颜色（生成新车和恢复存档共用）
+pub fn bus_bundle(
+    asset_server: &AssetServer,
+    theme: &ThemeRegistry,
+    line: u32,
+    world_pos: Vec3,
+    bus_vehicle: BusVehicle,
+    agent: BusPathfindingAgent,
+) -> impl Bundle {
+    (
+        Name::new(format!("Smart Bus {}", bus_vehicle.vehicle_id)),
+        Sprite {
+            image: asset_server.load(theme.bus()),
+            color: line_color(line),
+            custom_size: Some(Vec2::new(48.0, 48.0)),
+            ..default()
+        },
+        Transform::from_translation(world_pos),
+        bus_vehicle,
+        agent,
+    )
+}
@@ -0,0 +1,21 @@
+// src/bus_puzzle/state_cleanup.rs - 统一的实体清理（按关卡加载）
+//
+// 界面实体使用 Bevy 的 `StateScoped<GameStateEnum>`，离开对应状态时自动清理（在 mod.rs 中启用）。
+// 各界面的标记组件用 `#[require]` 自动附带，新增的界面实体只要带上对应标记就不会遗漏
+
+use bevy::prelude::*;
+
+/// 属于当前关卡的游戏世界实体（地形、站点、路线段、乘客、公交车等），
+/// 加载新关卡时统一清理；暂停和结算界面期间保留
+#[derive(Component, Default)]
+pub struct LevelEntity;
+
+/// 清理上一关的游戏世界
+pub fn despawn_level_entities(
+    commands: &mut Commands,
+    level_entities: &Query<Entity, With<LevelEntity>>,
+) {
+    for entity in level_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,132 @@
+// src/bus_puzzle/station_labels.rs - 站点名称标签（显示在站点上方，随语言刷新、随缩放隐藏）
+
+use crate::bus_puzzle::{
+    CameraController, CurrentLanguage, GameStateEnum, LevelManager, StationEntity, UIAssets,
+    GAME_UI_Z, STATION_Z,
+};
+use bevy::{prelude::*, sprite::Anchor};
+
+/// 切换站点名称的按键（Names）
+const LABEL_TOGGLE_KEY: KeyCode = KeyCode::KeyN;
+
+/// 镜头缩小到超过该缩放值后隐藏标签，避免远景时文字挤在一起
+const LABEL_MAX_ZOOM: f32 = 1.8;
+
+const LABEL_FONT_SIZE: f32 = 14.0;
+
+/// 站点名称标签开关
+#[derive(Resource)]
+pub struct StationLabelSettings {
+    pub visible: bool,
+}
+
+impl Default for StationLabelSettings {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+/// 站点名称标签，作为站点的子实体随站点一起清理
+#[derive(Component)]
+pub struct StationLabel;
+
+// ============ 站点名称插件 ============
+
+pub struct StationLabelsPlugin;
+
+impl Plugin for StationLabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StationLabelSettings>().add_systems(
+            Update,
+            (
+                spawn_station_labels,
+                refresh_station_label_text,
+                toggle_station_labels,
+                update_station_label_visibility,
+            )
+                .chain()
+                .run_if(in_state(GameStateEnum::Playing)),
+        );
+    }
+}
+
+fn spawn_station_labels(
+    mut commands: Commands,
+    new_stations: Query<(Entity, &StationEntity), Added<StationEntity>>,
+    level_manager: Res<LevelManager>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (entity, station) in new_stations.iter() {
+        let label = commands
+            .spawn((
+                Text2d::new(
+                    station
+                        .station_data
+                        .get_localized_name(current_language.language),
+                ),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: LABEL_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Anchor::BottomCenter,
+                // 站点上方，层级高于乘客
+                Transform::from_xyz(0.0, level_manager.tile_size * 0.5, GAME_UI_Z - STATION_Z),
+                StationLabel,
+                Name::new("Station Label"),
+            ))
+            .id();
+        commands.entity(entity).add_child(label);
+    }
+}
+
+fn refresh_station_label_text(
+    current_language: Res<CurrentLanguage>,
+    stations: Query<(&StationEntity, &Children)>,
+    mut labels: Query<&mut Text2d, With<StationLabel>>,
+) {
+    if !current_language.is_changed() {
+        return;
+    }
+
+    for (station, children) in stations.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = labels.get_mut(child) {
+                text.0 = station
+                    .station_data
+                    .get_localized_name(current_language.language);
+            }
+        }
+    }
+}
+
+fn toggle_station_labels(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<StationLabelSettings>,
+) {
+    if keyboard_input.just_pressed(LABEL_TOGGLE_KEY) {
+        settings.visible = !settings.visible;
+        info!("🏷️ 站点名称: {}", settings.visible);
+    }
+}
+
+/// 标签随镜头缩放反向缩放，保持屏幕上的字号不变
+fn update_station_label_visibility(
+    settings: Res<StationLabelSettings>,
+    camera_controller: Res<CameraController>,
+    mut labels: Query<(&mut Visibility, &mut Transform), With<StationLabel>>,
+) {
+    let zoom = camera_controller.zoom;
+    let shown = settings.visible && zoom <= LABEL_MAX_ZOOM;
+
+    for (mut visibility, mut transform) in labels.iter_mut() {
+        visibility.set_if_neq(if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        transform.scale = Vec3::splat(zoom);
+    }
+}
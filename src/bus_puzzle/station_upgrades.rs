@@ -0,0 +1,435 @@
+// src/bus_puzzle/station_upgrades.rs - 站点升级（扩容、候车亭、无障碍上车）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, show_contextual_tip, ui_audio::GameplayUI, ButtonComponent,
+    ButtonType, CurrentLanguage, EconomyMode, GameState, GameStateEnum, GridPos, InputState,
+    Language, LocalizedText, PathfindingAgent, StationEntity, TipType, UIAssets, WaitingForBus,
+    CLOSE, INSUFFICIENT_FUNDS, STATION_ACCESSIBILITY_UPGRADE, STATION_CAPACITY_UPGRADE,
+    STATION_INFO, STATION_SHELTER_UPGRADE, STATION_UPGRADED,
+};
+use bevy::{
+    platform::collections::HashMap,
+    prelude::{Val::Px, *},
+};
+use serde::{Deserialize, Serialize};
+
+/// 每次扩容增加的候车容量
+const CAPACITY_UPGRADE_STEP: u32 = 10;
+
+/// 扩容费用
+const CAPACITY_UPGRADE_COST: u32 = 4;
+
+/// 候车亭费用
+const SHELTER_UPGRADE_COST: u32 = 3;
+
+/// 无障碍设施费用
+const ACCESSIBILITY_UPGRADE_COST: u32 = 3;
+
+/// 候车亭下乘客耐心消耗倍数
+const SHELTER_PATIENCE_FACTOR: f32 = 0.5;
+
+/// 候车人数超过站点容量时的耐心消耗倍数
+const OVERCROWDED_PATIENCE_FACTOR: f32 = 2.0;
+
+/// 无障碍站点上下客更快，公交车停靠时间按此倍数缩短
+const ACCESSIBLE_DWELL_SPEEDUP: f32 = 2.0;
+
+// ============ 站点升级数据 ============
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StationUpgrades {
+    pub capacity_level: u32, // 已扩容次数
+    pub shelter: bool,       // 候车亭：减缓等车乘客的耐心消耗
+    pub accessible: bool,    // 无障碍上车：缩短公交车停靠时间
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StationUpgradeKind {
+    Capacity,
+    Shelter,
+    Accessibility,
+}
+
+impl StationUpgradeKind {
+    pub fn cost(&self) -> u32 {
+        match self {
+            StationUpgradeKind::Capacity => CAPACITY_UPGRADE_COST,
+            StationUpgradeKind::Shelter => SHELTER_UPGRADE_COST,
+            StationUpgradeKind::Accessibility => ACCESSIBILITY_UPGRADE_COST,
+        }
+    }
+
+    fn label(&self) -> &'static LocalizedText {
+        match self {
+            StationUpgradeKind::Capacity => &STATION_CAPACITY_UPGRADE,
+            StationUpgradeKind::Shelter => &STATION_SHELTER_UPGRADE,
+            StationUpgradeKind::Accessibility => &STATION_ACCESSIBILITY_UPGRADE,
+        }
+    }
+
+    fn button_type(&self) -> ButtonType {
+        match self {
+            StationUpgradeKind::Capacity => ButtonType::UpgradeStationCapacity,
+            StationUpgradeKind::Shelter => ButtonType::UpgradeStationShelter,
+            StationUpgradeKind::Accessibility => ButtonType::UpgradeStationAccessibility,
+        }
+    }
+}
+
+impl StationEntity {
+    /// 候车亭减缓、超员加快耐心消耗
+    pub fn patience_drain_multiplier(&self) -> f32 {
+        let mut multiplier = 1.0;
+        if self.upgrades.shelter {
+            multiplier *= SHELTER_PATIENCE_FACTOR;
+        }
+        if self.current_passengers > self.station_data.capacity {
+            multiplier *= OVERCROWDED_PATIENCE_FACTOR;
+        }
+        multiplier
+    }
+
+    pub fn dwell_speedup(&self) -> f32 {
+        if self.upgrades.accessible {
+            ACCESSIBLE_DWELL_SPEEDUP
+        } else {
+            1.0
+        }
+    }
+
+    /// 已经购买过的一次性升级不能重复购买，扩容可以多次
+    fn can_upgrade(&self, kind: StationUpgradeKind) -> bool {
+        match kind {
+            StationUpgradeKind::Capacity => true,
+            StationUpgradeKind::Shelter => !self.upgrades.shelter,
+            StationUpgradeKind::Accessibility => !self.upgrades.accessible,
+        }
+    }
+
+    /// 恢复存档时重新应用已购买的升级（扩容同时增加候车容量）
+    pub fn restore_upgrades(&mut self, upgrades: StationUpgrades) {
+        self.station_data.capacity += upgrades.capacity_level * CAPACITY_UPGRADE_STEP;
+        self.upgrades = upgrades;
+    }
+
+    fn apply_upgrade(&mut self, kind: StationUpgradeKind) {
+        match kind {
+            StationUpgradeKind::Capacity => {
+                self.upgrades.capacity_level += 1;
+                self.station_data.capacity += CAPACITY_UPGRADE_STEP;
+            }
+            StationUpgradeKind::Shelter => self.upgrades.shelter = true,
+            StationUpgradeKind::Accessibility => self.upgrades.accessible = true,
+        }
+    }
+}
+
+/// 当前打开升级面板的站点
+#[derive(Resource, Default)]
+pub struct SelectedStation(pub Option<GridPos>);
+
+#[derive(Component)]
+pub struct StationUpgradePanel;
+
+// ============ 站点升级插件 ============
+
+pub struct StationUpgradePlugin;
+
+impl Plugin for StationUpgradePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedStation>()
+            .add_systems(OnExit(GameStateEnum::Playing), clear_selected_station)
+            .add_systems(
+                Update,
+                (
+                    count_waiting_passengers,
+                    select_station_on_click,
+                    handle_station_upgrade_buttons,
+                    refresh_station_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+fn clear_selected_station(mut selected: ResMut<SelectedStation>) {
+    selected.0 = None;
+}
+
+/// 同步每个站点的候车人数，用于判断是否超员
+fn count_waiting_passengers(
+    waiting: Query<&PathfindingAgent, With<WaitingForBus>>,
+    mut stations: Query<&mut StationEntity>,
+) {
+    let mut counts: HashMap<&str, u32> = HashMap::default();
+    for agent in waiting.iter() {
+        *counts.entry(agent.origin.as_str()).or_default() += 1;
+    }
+
+    for mut station in stations.iter_mut() {
+        let count = counts
+            .get(station.station_data.name.as_str())
+            .copied()
+            .unwrap_or(0);
+        if station.current_passengers != count {
+            station.current_passengers = count;
+        }
+    }
+}
+
+/// 未选中库存路线段时点击站点，打开（或切换）升级面板
+fn select_station_on_click(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_state: Res<InputState>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    stations: Query<&StationEntity>,
+    mut selected: ResMut<SelectedStation>,
+) {
+    if !mouse_button_input.just_released(MouseButton::Left)
+        || input_state.selected_segment.is_some()
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    let Some(grid_pos) = input_state.grid_cursor_pos else {
+        return;
+    };
+
+    if stations
+        .iter()
+        .any(|station| station.station_data.position == grid_pos)
+    {
+        selected.0 = Some(grid_pos);
+    } else if selected.0.is_some() {
+        selected.0 = None;
+    }
+}
+
+fn handle_station_upgrade_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut selected: ResMut<SelectedStation>,
+    mut stations: Query<&mut StationEntity>,
+    mut game_state: ResMut<GameState>,
+    economy: Res<EconomyMode>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        let kind = match button_component.button_type {
+            ButtonType::UpgradeStationCapacity => StationUpgradeKind::Capacity,
+            ButtonType::UpgradeStationShelter => StationUpgradeKind::Shelter,
+            ButtonType::UpgradeStationAccessibility => StationUpgradeKind::Accessibility,
+            ButtonType::CloseStationPanel => {
+                selected.0 = None;
+                continue;
+            }
+            _ => continue,
+        };
+
+        let Some(mut station) = stations
+            .iter_mut()
+            .find(|station| Some(station.station_data.position) == selected.0)
+        else {
+            continue;
+        };
+        if !station.can_upgrade(kind) {
+            continue;
+        }
+
+        if !economy.can_afford(&game_state, kind.cost()) {
+            show_contextual_tip(
+                &mut commands,
+                &ui_assets,
+                &get_text(&INSUFFICIENT_FUNDS, current_language.language),
+                TipType::Warning,
+                2.0,
+            );
+            continue;
+        }
+
+        station.apply_upgrade(kind);
+        game_state.total_cost += kind.cost();
+
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &STATION_UPGRADED,
+                current_language.language,
+                &[
+                    &get_text(kind.label(), current_language.language),
+                    &kind.cost().to_string(),
+                ],
+            ),
+            TipType::Strategy,
+            2.0,
+        );
+        info!(
+            "🚏 站点 {} 升级: {:?}，花费 {}",
+            station.station_data.name,
+            kind,
+            kind.cost()
+        );
+    }
+}
+
+// ============ 升级面板 ============
+
+/// 选中站点或站点数据变化时重建面板
+fn refresh_station_panel(
+    mut commands: Commands,
+    selected: Res<SelectedStation>,
+    stations: Query<Ref<StationEntity>>,
+    panels: Query<Entity, With<StationUpgradePanel>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let station = selected.0.and_then(|pos| {
+        stations
+            .iter()
+            .find(|station| station.station_data.position == pos)
+    });
+    let station_changed = station.as_ref().is_some_and(|station| station.is_changed());
+
+    if !selected.is_changed() && !station_changed && !current_language.is_changed() {
+        return;
+    }
+
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if let Some(station) = station {
+        spawn_station_panel(
+            &mut commands,
+            &ui_assets,
+            &station,
+            current_language.language,
+        );
+    }
+}
+
+fn station_display_name(station: &StationEntity, language: Language) -> String {
+    match station.station_data.name_key {
+        Some(name_key) => get_text(name_key, language),
+        None => station.station_data.name.clone(),
+    }
+}
+
+fn spawn_station_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    station: &StationEntity,
+    language: Language,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Px(220.0),
+                top: Px(120.0),
+                width: Px(260.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Px(10.0)),
+                row_gap: Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.95)),
+            Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.3, 0.7, 0.9)),
+            ZIndex(250),
+            GameplayUI,
+            StationUpgradePanel,
+            Name::new("Station Upgrade Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text_with_args(
+                    &STATION_INFO,
+                    language,
+                    &[
+                        &station_display_name(station, language),
+                        &station.current_passengers.to_string(),
+                        &station.station_data.capacity.to_string(),
+                    ],
+                )),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+            ));
+
+            for kind in [
+                StationUpgradeKind::Capacity,
+                StationUpgradeKind::Shelter,
+                StationUpgradeKind::Accessibility,
+            ] {
+                let available = station.can_upgrade(kind);
+                let check = if available { "[ ]" } else { "[x]" };
+                spawn_panel_button(
+                    parent,
+                    ui_assets,
+                    kind.button_type(),
+                    format!(
+                        "{} {} (-{})",
+                        check,
+                        get_text(kind.label(), language),
+                        kind.cost()
+                    ),
+                );
+            }
+
+            spawn_panel_button(
+                parent,
+                ui_assets,
+                ButtonType::CloseStationPanel,
+                get_text(&CLOSE, language),
+            );
+        });
+}
+
+fn spawn_panel_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    label: String,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Px(30.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.3, 0.45)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
@@ -0,0 +1,76 @@
+// src/bus_puzzle/tab_visibility.rs - 网页标签页切到后台时暂停游戏和声音，切回时恢复
+
+use crate::bus_puzzle::GameStateEnum;
+use bevy::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// 由 visibilitychange 回调写入，系统每帧读取
+static TAB_HIDDEN: AtomicBool = AtomicBool::new(false);
+
+// ============ 标签页可见性插件 ============
+
+pub struct TabVisibilityPlugin;
+
+impl Plugin for TabVisibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, listen_for_visibility_changes)
+            .add_systems(Update, sync_tab_visibility);
+    }
+}
+
+fn listen_for_visibility_changes() {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    let callback_document = document.clone();
+    let callback = Closure::<dyn FnMut()>::new(move || {
+        TAB_HIDDEN.store(callback_document.hidden(), Ordering::Relaxed);
+    });
+    if document
+        .add_event_listener_with_callback("visibilitychange", callback.as_ref().unchecked_ref())
+        .is_err()
+    {
+        warn!("无法监听标签页可见性变化");
+        return;
+    }
+    // 监听器在页面存续期间一直有效
+    callback.forget();
+}
+
+/// 后台标签页仍会以较低频率运行，这里暂停关卡避免限时关卡在后台失败，
+/// 并暂停所有正在播放的声音；只恢复由本系统暂停的关卡，不影响玩家手动暂停
+fn sync_tab_visibility(
+    current_state: Res<State<GameStateEnum>>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+    sinks: Query<&AudioSink>,
+    mut was_hidden: Local<bool>,
+    mut paused_by_tab: Local<bool>,
+) {
+    let hidden = TAB_HIDDEN.load(Ordering::Relaxed);
+    if hidden == *was_hidden {
+        return;
+    }
+    *was_hidden = hidden;
+
+    if hidden {
+        for sink in sinks.iter() {
+            sink.pause();
+        }
+        if *current_state.get() == GameStateEnum::Playing {
+            next_state.set(GameStateEnum::Paused);
+            *paused_by_tab = true;
+        }
+        info!("🙈 标签页进入后台，暂停游戏和声音");
+    } else {
+        for sink in sinks.iter() {
+            sink.play();
+        }
+        if *paused_by_tab && *current_state.get() == GameStateEnum::Paused {
+            next_state.set(GameStateEnum::Playing);
+        }
+        *paused_by_tab = false;
+        info!("👀 标签页回到前台，恢复游戏");
+    }
+}
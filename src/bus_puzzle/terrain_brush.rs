@@ -0,0 +1,374 @@
+// src/bus_puzzle/terrain_brush.rs - 沙盒地形画笔（开始模拟前绘制水域、山地和建筑）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, is_valid_placement, load_current_level, show_contextual_tip,
+    ui_audio::GameplayUI, ButtonComponent, ButtonType, CurrentLanguage, CustomLevel, GameState,
+    GameStateEnum, GridPos, InputState, Language, LevelManager, LocalizedText, TerrainChunk,
+    TerrainType, TipType, UIAssets, BRUSH_SIZE, START_SIMULATION, STATION_BURIED, TERRAIN_BRUSH,
+    TERRAIN_BUILDING, TERRAIN_EMPTY, TERRAIN_MOUNTAIN, TERRAIN_WATER,
+};
+use bevy::prelude::{Val::Px, *};
+
+/// 可选的笔刷尺寸（边长）
+const BRUSH_SIZES: [i32; 3] = [1, 3, 5];
+
+/// 可绘制的地形
+const BRUSH_TERRAINS: [TerrainType; 4] = [
+    TerrainType::Empty,
+    TerrainType::Water,
+    TerrainType::Mountain,
+    TerrainType::Building,
+];
+
+// ============ 地形画笔资源 ============
+
+/// 沙盒关卡进入后先处于编辑阶段：计时暂停、不能放置路线段，点击“开始模拟”后才开始
+#[derive(Resource)]
+pub struct TerrainBrush {
+    pub editing: bool,
+    pub terrain: TerrainType,
+    pub size_index: usize,
+}
+
+impl Default for TerrainBrush {
+    fn default() -> Self {
+        Self {
+            editing: false,
+            terrain: TerrainType::Water,
+            size_index: 0,
+        }
+    }
+}
+
+impl TerrainBrush {
+    /// 以光标为中心的笔刷覆盖范围
+    fn cells(&self, center: GridPos) -> impl Iterator<Item = GridPos> {
+        let radius = BRUSH_SIZES[self.size_index] / 2;
+        (-radius..=radius)
+            .flat_map(move |dx| (-radius..=radius).map(move |dy| (dx, dy)))
+            .map(move |(dx, dy)| GridPos::new(center.x + dx, center.y + dy))
+    }
+}
+
+fn terrain_label(terrain: &TerrainType) -> &'static LocalizedText {
+    match terrain {
+        TerrainType::Water => &TERRAIN_WATER,
+        TerrainType::Mountain => &TERRAIN_MOUNTAIN,
+        TerrainType::Building => &TERRAIN_BUILDING,
+        TerrainType::Empty | TerrainType::Park => &TERRAIN_EMPTY,
+    }
+}
+
+#[derive(Component)]
+pub struct TerrainBrushPanel;
+
+// ============ 地形画笔插件 ============
+
+pub struct TerrainBrushPlugin;
+
+impl Plugin for TerrainBrushPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TerrainBrush>()
+            .add_systems(
+                OnExit(GameStateEnum::MainMenu),
+                begin_terrain_editing.after(load_current_level),
+            )
+            .add_systems(
+                OnEnter(GameStateEnum::Loading),
+                begin_terrain_editing.after(load_current_level),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_terrain_brush_buttons,
+                    paint_terrain,
+                    refresh_terrain_brush_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+    }
+}
+
+/// 只有沙盒（自定义关卡）进入编辑阶段
+fn begin_terrain_editing(
+    mut brush: ResMut<TerrainBrush>,
+    custom_level: Res<CustomLevel>,
+    mut game_state: ResMut<GameState>,
+) {
+    brush.editing = custom_level.0.is_some();
+    if brush.editing {
+        game_state.is_paused = true;
+        info!("🖌️ 沙盒编辑阶段：绘制地形后点击开始模拟");
+    }
+}
+
+// ============ 绘制地形 ============
+
+fn paint_terrain(
+    brush: Res<TerrainBrush>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_state: Res<InputState>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+    mut game_state: ResMut<GameState>,
+    mut level_manager: ResMut<LevelManager>,
+    mut chunks: Query<&mut TerrainChunk>,
+) {
+    if !brush.editing
+        || !mouse_button_input.pressed(MouseButton::Left)
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    let Some(center) = input_state.grid_cursor_pos else {
+        return;
+    };
+    let Some(level_data) = &mut game_state.current_level else {
+        return;
+    };
+
+    let (width, height) = level_data.grid_size;
+    let mut painted = Vec::new();
+    for pos in brush.cells(center) {
+        let in_bounds = pos.x >= 0 && pos.y >= 0 && pos.x < width as i32 && pos.y < height as i32;
+        // 站点所在的格子不能改变地形
+        let is_station = level_data
+            .stations
+            .iter()
+            .any(|station| station.position == pos);
+        if !in_bounds || is_station || level_data.terrain.get(&pos) == Some(&brush.terrain) {
+            continue;
+        }
+
+        level_data.terrain.insert(pos, brush.terrain.clone());
+        painted.push(pos);
+    }
+
+    if painted.is_empty() {
+        return;
+    }
+
+    // 部分系统从 LevelManager 读取关卡数据，保持一致
+    if let Some(managed_level) = &mut level_manager.current_level {
+        managed_level.terrain = level_data.terrain.clone();
+    }
+
+    // 只修改涉及的区块，变化的区块会重建网格
+    for mut chunk in chunks.iter_mut() {
+        let chunk_tiles: Vec<GridPos> = painted
+            .iter()
+            .copied()
+            .filter(|pos| chunk.contains(*pos))
+            .collect();
+        for pos in chunk_tiles {
+            chunk.set_terrain(pos, brush.terrain.clone());
+        }
+    }
+}
+
+/// 站点四周都无法放置路线段时视为被埋住
+fn find_buried_station(game_state: &GameState, language: Language) -> Option<String> {
+    let level_data = game_state.current_level.as_ref()?;
+
+    level_data
+        .stations
+        .iter()
+        .find(|station| {
+            let pos = station.position;
+            ![(0, 1), (1, 0), (0, -1), (-1, 0)].iter().any(|(dx, dy)| {
+                let neighbor = GridPos::new(pos.x + dx, pos.y + dy);
+                level_data
+                    .available_segments
+                    .iter()
+                    .any(|segment| is_valid_placement(game_state, neighbor, &segment.segment_type))
+            })
+        })
+        .map(|station| station.get_localized_name(language))
+}
+
+// ============ 画笔面板 ============
+
+fn handle_terrain_brush_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut brush: ResMut<TerrainBrush>,
+    mut game_state: ResMut<GameState>,
+    mut custom_level: ResMut<CustomLevel>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match &button_component.button_type {
+            ButtonType::SelectTerrainBrush(terrain) => brush.terrain = terrain.clone(),
+            ButtonType::CycleBrushSize => {
+                brush.size_index = (brush.size_index + 1) % BRUSH_SIZES.len();
+            }
+            ButtonType::StartSandboxSimulation => {
+                if let Some(station_name) =
+                    find_buried_station(&game_state, current_language.language)
+                {
+                    show_contextual_tip(
+                        &mut commands,
+                        &ui_assets,
+                        &get_text_with_args(
+                            &STATION_BURIED,
+                            current_language.language,
+                            &[&station_name],
+                        ),
+                        TipType::Warning,
+                        4.0,
+                    );
+                    warn!("站点被地形围住，不能开始模拟: {}", station_name);
+                    continue;
+                }
+
+                // 保存绘制结果，重新开始关卡时保留地形
+                if let (Some(custom), Some(level_data)) =
+                    (&mut custom_level.0, &game_state.current_level)
+                {
+                    custom.terrain = level_data.terrain.clone();
+                }
+
+                brush.editing = false;
+                game_state.is_paused = false;
+                info!("▶️ 沙盒开始模拟");
+            }
+            _ => {}
+        }
+    }
+}
+
+fn refresh_terrain_brush_panel(
+    mut commands: Commands,
+    brush: Res<TerrainBrush>,
+    panels: Query<Entity, With<TerrainBrushPanel>>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    // 从暂停菜单返回时面板已随 GameplayUI 清理，需要重新生成
+    let missing = brush.editing && panels.is_empty();
+    if !brush.is_changed() && !current_language.is_changed() && !missing {
+        return;
+    }
+
+    for entity in panels.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if brush.editing {
+        spawn_terrain_brush_panel(&mut commands, &ui_assets, &brush, current_language.language);
+    }
+}
+
+fn spawn_terrain_brush_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    brush: &TerrainBrush,
+    language: Language,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Px(220.0),
+                bottom: Px(120.0),
+                width: Px(220.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Px(10.0)),
+                row_gap: Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.95)),
+            Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.4, 0.8, 0.4)),
+            ZIndex(250),
+            GameplayUI,
+            TerrainBrushPanel,
+            Name::new("Terrain Brush Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&TERRAIN_BRUSH, language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+            ));
+
+            for terrain in BRUSH_TERRAINS {
+                let check = if brush.terrain == terrain {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                let label = format!("{} {}", check, get_text(terrain_label(&terrain), language));
+                spawn_brush_button(
+                    parent,
+                    ui_assets,
+                    ButtonType::SelectTerrainBrush(terrain),
+                    label,
+                );
+            }
+
+            spawn_brush_button(
+                parent,
+                ui_assets,
+                ButtonType::CycleBrushSize,
+                get_text_with_args(
+                    &BRUSH_SIZE,
+                    language,
+                    &[&BRUSH_SIZES[brush.size_index].to_string()],
+                ),
+            );
+            spawn_brush_button(
+                parent,
+                ui_assets,
+                ButtonType::StartSandboxSimulation,
+                get_text(&START_SIMULATION, language),
+            );
+        });
+}
+
+fn spawn_brush_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    button_type: ButtonType,
+    label: String,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                height: Px(30.0),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.35, 0.25)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
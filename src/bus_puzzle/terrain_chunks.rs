@@ -0,0 +1,206 @@
+// src/bus_puzzle/terrain_chunks.rs - 分块地形渲染（每个区块按地形类型合并为网格，大地图也只需少量实体）
+
+use crate::bus_puzzle::{GridPos, LevelEntity, TerrainType, ThemeRegistry, TERRAIN_Z};
+use bevy::{
+    asset::RenderAssetUsages,
+    platform::collections::HashMap,
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+};
+
+/// 区块边长（格子数），100x100 的地图约 49 个区块
+pub const TERRAIN_CHUNK_SIZE: i32 = 16;
+
+/// 同一地形类型的所有格子合并为一个网格，按此顺序排列区块的子实体
+const TERRAIN_LAYERS: [TerrainType; 5] = [
+    TerrainType::Empty,
+    TerrainType::Park,
+    TerrainType::Water,
+    TerrainType::Mountain,
+    TerrainType::Building,
+];
+
+// ============ 地形区块组件 ============
+
+/// 一个区块的地形数据，修改后自动重建网格
+#[derive(Component)]
+#[require(LevelEntity, Transform, Visibility)]
+pub struct TerrainChunk {
+    pub origin: GridPos,           // 区块中 x、y 最小的格子
+    pub size: (i32, i32),          // 地图边缘的区块可能不足 TERRAIN_CHUNK_SIZE
+    pub terrain: Vec<TerrainType>, // 按行存储，索引为 dy * size.0 + dx
+    pub grid_size: (u32, u32),
+    pub tile_size: f32,
+}
+
+impl TerrainChunk {
+    pub fn contains(&self, pos: GridPos) -> bool {
+        let (dx, dy) = (pos.x - self.origin.x, pos.y - self.origin.y);
+        dx >= 0 && dy >= 0 && dx < self.size.0 && dy < self.size.1
+    }
+
+    pub fn set_terrain(&mut self, pos: GridPos, terrain_type: TerrainType) {
+        if self.contains(pos) {
+            let index = self.index(pos);
+            self.terrain[index] = terrain_type;
+        }
+    }
+
+    fn index(&self, pos: GridPos) -> usize {
+        ((pos.y - self.origin.y) * self.size.0 + (pos.x - self.origin.x)) as usize
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (GridPos, &TerrainType)> {
+        (0..self.size.1)
+            .flat_map(move |dy| (0..self.size.0).map(move |dx| (dx, dy)))
+            .map(move |(dx, dy)| GridPos::new(self.origin.x + dx, self.origin.y + dy))
+            .zip(self.terrain.iter())
+    }
+}
+
+/// 区块中某种地形合并后的网格
+#[derive(Component)]
+pub struct TerrainChunkLayer(pub TerrainType);
+
+// ============ 分块地形插件 ============
+
+pub struct TerrainChunkPlugin;
+
+impl Plugin for TerrainChunkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (rebuild_terrain_chunks, apply_terrain_theme).chain(),
+        );
+    }
+}
+
+/// 按区块生成地形实体，网格在 rebuild_terrain_chunks 中创建
+pub fn spawn_terrain_chunks(
+    commands: &mut Commands,
+    level_terrain: &HashMap<GridPos, TerrainType>,
+    grid_size: (u32, u32),
+    tile_size: f32,
+) {
+    let (width, height) = (grid_size.0 as i32, grid_size.1 as i32);
+
+    for chunk_y in (0..height).step_by(TERRAIN_CHUNK_SIZE as usize) {
+        for chunk_x in (0..width).step_by(TERRAIN_CHUNK_SIZE as usize) {
+            let size = (
+                TERRAIN_CHUNK_SIZE.min(width - chunk_x),
+                TERRAIN_CHUNK_SIZE.min(height - chunk_y),
+            );
+            let terrain = (0..size.1)
+                .flat_map(|dy| (0..size.0).map(move |dx| (dx, dy)))
+                .map(|(dx, dy)| {
+                    level_terrain
+                        .get(&GridPos::new(chunk_x + dx, chunk_y + dy))
+                        .cloned()
+                        .unwrap_or(TerrainType::Empty)
+                })
+                .collect();
+
+            commands.spawn((
+                TerrainChunk {
+                    origin: GridPos::new(chunk_x, chunk_y),
+                    size,
+                    terrain,
+                    grid_size,
+                    tile_size,
+                },
+                Transform::from_xyz(0.0, 0.0, TERRAIN_Z),
+                Name::new(format!("Terrain Chunk ({}, {})", chunk_x, chunk_y)),
+            ));
+        }
+    }
+}
+
+// ============ 网格重建 ============
+
+fn rebuild_terrain_chunks(
+    mut commands: Commands,
+    chunks: Query<(Entity, &TerrainChunk), Changed<TerrainChunk>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
+    for (entity, chunk) in chunks.iter() {
+        commands.entity(entity).despawn_related::<Children>();
+
+        for terrain_type in TERRAIN_LAYERS {
+            let Some(mesh) = build_layer_mesh(chunk, &terrain_type) else {
+                continue;
+            };
+
+            let material = materials.add(ColorMaterial {
+                texture: Some(asset_server.load(theme.terrain(&terrain_type))),
+                ..default()
+            });
+            commands.entity(entity).with_child((
+                Mesh2d(meshes.add(mesh)),
+                MeshMaterial2d(material),
+                TerrainChunkLayer(terrain_type),
+            ));
+        }
+    }
+}
+
+/// 每个格子一个四边形，贴图完整覆盖一格；区块里没有该地形时返回 None
+fn build_layer_mesh(chunk: &TerrainChunk, terrain_type: &TerrainType) -> Option<Mesh> {
+    let half = chunk.tile_size * 0.5;
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+
+    for (pos, cell_terrain) in chunk.cells() {
+        if cell_terrain != terrain_type {
+            continue;
+        }
+
+        let center = pos.to_world_pos(chunk.tile_size, chunk.grid_size.0, chunk.grid_size.1);
+        let first = positions.len() as u32;
+        positions.extend([
+            [center.x - half, center.y - half, 0.0],
+            [center.x + half, center.y - half, 0.0],
+            [center.x + half, center.y + half, 0.0],
+            [center.x - half, center.y + half, 0.0],
+        ]);
+        uvs.extend([[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]);
+        indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
+
+    if positions.is_empty() {
+        return None;
+    }
+
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    Some(
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices)),
+    )
+}
+
+/// 切换主题后替换地形网格的贴图（精灵的贴图由 ThemePlugin 替换）
+fn apply_terrain_theme(
+    theme: Res<ThemeRegistry>,
+    asset_server: Res<AssetServer>,
+    layers: Query<(&TerrainChunkLayer, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+
+    for (layer, material) in layers.iter() {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.texture = Some(asset_server.load(theme.terrain(&layer.0)));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// src/bus_puzzle/text_to_speech.rs - 语音朗读（开启后把读屏播报交给语音合成后端，原生平台调用系统自带的朗读命令）
+
+use crate::bus_puzzle::{CurrentLanguage, GameOptions, Language, ScreenReaderAnnouncement};
+use bevy::prelude::*;
+
+// ============ 语音合成后端 ============
+
+/// 语音合成后端。网页端以后可以用 Web Speech API 实现这个接口，在添加插件前插入
+/// `TextToSpeech::with_backend(...)` 资源即可替换默认后端
+pub trait SpeechBackend: Send + Sync + 'static {
+    /// 朗读一句话，打断正在朗读的内容
+    fn speak(&mut self, text: &str, language: Language);
+
+    /// 停止朗读
+    fn stop(&mut self);
+}
+
+/// 不发声的后端，用于暂时没有语音合成实现的平台
+#[cfg(target_family = "wasm")]
+pub struct SilentSpeechBackend;
+
+#[cfg(target_family = "wasm")]
+impl SpeechBackend for SilentSpeechBackend {
+    fn speak(&mut self, _text: &str, _language: Language) {}
+
+    fn stop(&mut self) {}
+}
+
+/// 原生平台调用系统自带的朗读命令：macOS 用 say，Windows 用 System.Speech，
+/// Linux 用 speech-dispatcher 的 spd-say
+#[cfg(not(target_family = "wasm"))]
+#[derive(Default)]
+pub struct SystemSpeechBackend {
+    child: Option<std::process::Child>,
+    unavailable: bool, // 命令启动失败后不再尝试，避免每次播报都刷警告
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl SystemSpeechBackend {
+    fn command(text: &str, language: Language) -> std::process::Command {
+        use std::process::Command;
+
+        if cfg!(target_os = "macos") {
+            let mut command = Command::new("say");
+            command.arg(text);
+            command
+        } else if cfg!(target_os = "windows") {
+            // 文本通过环境变量传入，不拼接进脚本
+            let mut command = Command::new("powershell");
+            command
+                .args([
+                    "-NoProfile",
+                    "-Command",
+                    "Add-Type -AssemblyName System.Speech; \
+                     (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:LAST_STOP_TTS_TEXT)",
+                ])
+                .env("LAST_STOP_TTS_TEXT", text);
+            command
+        } else {
+            let mut command = Command::new("spd-say");
+            command.args(["--wait", "-l", language.code(), "--", text]);
+            command
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl SpeechBackend for SystemSpeechBackend {
+    fn speak(&mut self, text: &str, language: Language) {
+        if self.unavailable {
+            return;
+        }
+        self.stop();
+
+        match Self::command(text, language)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+        {
+            Ok(child) => self.child = Some(child),
+            Err(e) => {
+                warn!("语音朗读不可用: {}", e);
+                self.unavailable = true;
+            }
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            // 已经读完的进程 kill 会失败，wait 负责回收
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// 当前使用的语音合成后端
+#[derive(Resource)]
+pub struct TextToSpeech {
+    backend: Box<dyn SpeechBackend>,
+}
+
+impl Default for TextToSpeech {
+    fn default() -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        let backend = SystemSpeechBackend::default();
+        #[cfg(target_family = "wasm")]
+        let backend = SilentSpeechBackend;
+        Self::with_backend(backend)
+    }
+}
+
+impl TextToSpeech {
+    pub fn with_backend(backend: impl SpeechBackend) -> Self {
+        Self {
+            backend: Box::new(backend),
+        }
+    }
+}
+
+impl Drop for TextToSpeech {
+    fn drop(&mut self) {
+        self.backend.stop();
+    }
+}
+
+// ============ 语音朗读插件 ============
+
+pub struct TextToSpeechPlugin;
+
+impl Plugin for TextToSpeechPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextToSpeech>()
+            .add_systems(Update, speak_announcements);
+    }
+}
+
+/// 选项开启时朗读每帧最后一条读屏播报（焦点按钮、关卡完成、失败原因等），关闭时立即停止
+fn speak_announcements(
+    mut announcements: EventReader<ScreenReaderAnnouncement>,
+    game_options: Res<GameOptions>,
+    current_language: Res<CurrentLanguage>,
+    mut text_to_speech: ResMut<TextToSpeech>,
+) {
+    if !game_options.text_to_speech {
+        announcements.clear();
+        if game_options.is_changed() {
+            text_to_speech.backend.stop();
+        }
+        return;
+    }
+
+    if let Some(announcement) = announcements.read().last() {
+        text_to_speech
+            .backend
+            .speak(&announcement.0, current_language.language);
+    }
+}
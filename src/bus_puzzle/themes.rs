@@ -0,0 +1,293 @@
+// src/bus_puzzle/themes.rs - 纹理主题（在设置中切换地形、站点、路线、乘客和公交车贴图）
+
+use crate::bus_puzzle::{
+    get_text, Language, PassengerColor, RouteSegmentType, StationType, TerrainType, UIAssets,
+    THEME_DEFAULT,
+};
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+/// 默认主题的纹理目录
+const DEFAULT_THEME_ROOT: &str = "textures";
+
+/// 额外主题放在 assets/themes/<主题名>/ 下，目录结构与 assets/textures 相同，
+/// 缺少的图片沿用默认主题
+#[cfg(not(target_family = "wasm"))]
+const THEMES_DIR: &str = "themes";
+
+// ============ 纹理文件表 ============
+
+const TERRAIN_TYPES: [TerrainType; 5] = [
+    TerrainType::Empty,
+    TerrainType::Building,
+    TerrainType::Water,
+    TerrainType::Park,
+    TerrainType::Mountain,
+];
+
+const STATION_TYPES: [StationType; 3] = [
+    StationType::BusStop,
+    StationType::TransferHub,
+    StationType::Terminal,
+];
+
+const SEGMENT_TYPES: [RouteSegmentType; 6] = [
+    RouteSegmentType::Straight,
+    RouteSegmentType::Curve,
+    RouteSegmentType::TSplit,
+    RouteSegmentType::Cross,
+    RouteSegmentType::Bridge,
+    RouteSegmentType::Tunnel,
+];
+
+const PASSENGER_COLORS: [PassengerColor; 6] = [
+    PassengerColor::Red,
+    PassengerColor::Blue,
+    PassengerColor::Green,
+    PassengerColor::Yellow,
+    PassengerColor::Purple,
+    PassengerColor::Orange,
+];
+
+const BUS_FILE: &str = "bus.png";
+
+fn terrain_file(terrain_type: &TerrainType) -> &'static str {
+    match terrain_type {
+        TerrainType::Empty => "terrain/grass.png",
+        TerrainType::Building => "terrain/building.png",
+        TerrainType::Water => "terrain/water.png",
+        TerrainType::Park => "terrain/park.png",
+        TerrainType::Mountain => "terrain/mountain.png",
+    }
+}
+
+fn station_file(station_type: &StationType) -> &'static str {
+    match station_type {
+        StationType::BusStop => "stations/bus_stop.png",
+        StationType::TransferHub => "stations/transfer_hub.png",
+        StationType::Terminal => "stations/terminal.png",
+        StationType::Depot => "stations/terminal.png", // 车场沿用终点站贴图，生成时染色区分
+    }
+}
+
+fn route_file(segment_type: &RouteSegmentType) -> &'static str {
+    match segment_type {
+        RouteSegmentType::Straight => "routes/straight.png",
+        RouteSegmentType::Curve => "routes/curve.png",
+        RouteSegmentType::TSplit => "routes/t_split.png",
+        RouteSegmentType::Cross => "routes/cross.png",
+        RouteSegmentType::Bridge => "routes/bridge.png",
+        RouteSegmentType::Tunnel => "routes/tunnel.png",
+    }
+}
+
+fn passenger_file(color: PassengerColor) -> &'static str {
+    match color {
+        PassengerColor::Red => "passengers/red.png",
+        PassengerColor::Blue => "passengers/blue.png",
+        PassengerColor::Green => "passengers/green.png",
+        PassengerColor::Yellow => "passengers/yellow.png",
+        PassengerColor::Purple => "passengers/purple.png",
+        PassengerColor::Orange => "passengers/orange.png",
+    }
+}
+
+/// 主题可以替换的全部图片
+fn theme_files() -> impl Iterator<Item = &'static str> {
+    TERRAIN_TYPES
+        .iter()
+        .map(terrain_file)
+        .chain(STATION_TYPES.iter().map(station_file))
+        .chain(SEGMENT_TYPES.iter().map(route_file))
+        .chain(PASSENGER_COLORS.into_iter().map(passenger_file))
+        .chain(std::iter::once(BUS_FILE))
+}
+
+// ============ 主题注册表 ============
+
+struct Theme {
+    name: String,
+    root: String,
+    /// 该主题提供的图片，None 表示默认主题（提供全部图片）
+    files: Option<HashSet<&'static str>>,
+}
+
+/// 所有纹理路径都通过注册表解析，切换主题后新生成的实体直接使用新贴图
+#[derive(Resource)]
+pub struct ThemeRegistry {
+    themes: Vec<Theme>,
+    current: usize,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        let mut themes = vec![Theme {
+            name: String::new(),
+            root: DEFAULT_THEME_ROOT.to_string(),
+            files: None,
+        }];
+        #[cfg(not(target_family = "wasm"))]
+        themes.extend(discover_themes());
+
+        Self { themes, current: 0 }
+    }
+}
+
+impl ThemeRegistry {
+    pub fn current_name(&self, language: Language) -> String {
+        match self.current {
+            0 => get_text(&THEME_DEFAULT, language),
+            index => self.themes[index].name.clone(),
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        self.current = (self.current + 1) % self.themes.len();
+    }
+
+    fn resolve(&self, theme_index: usize, file: &str) -> String {
+        let theme = &self.themes[theme_index];
+        match &theme.files {
+            Some(files) if !files.contains(file) => format!("{}/{}", DEFAULT_THEME_ROOT, file),
+            _ => format!("{}/{}", theme.root, file),
+        }
+    }
+
+    fn texture(&self, file: &str) -> String {
+        self.resolve(self.current, file)
+    }
+
+    pub fn terrain(&self, terrain_type: &TerrainType) -> String {
+        self.texture(terrain_file(terrain_type))
+    }
+
+    pub fn station(&self, station_type: &StationType) -> String {
+        self.texture(station_file(station_type))
+    }
+
+    pub fn route(&self, segment_type: &RouteSegmentType) -> String {
+        self.texture(route_file(segment_type))
+    }
+
+    pub fn passenger(&self, color: PassengerColor) -> String {
+        self.texture(passenger_file(color))
+    }
+
+    pub fn bus(&self) -> String {
+        self.texture(BUS_FILE)
+    }
+
+    pub fn segment_icons(
+        &self,
+        asset_server: &AssetServer,
+    ) -> HashMap<RouteSegmentType, Handle<Image>> {
+        SEGMENT_TYPES
+            .iter()
+            .map(|segment_type| (*segment_type, asset_server.load(self.route(segment_type))))
+            .collect()
+    }
+
+    pub fn passenger_icons(
+        &self,
+        asset_server: &AssetServer,
+    ) -> HashMap<PassengerColor, Handle<Image>> {
+        PASSENGER_COLORS
+            .into_iter()
+            .map(|color| (color, asset_server.load(self.passenger(color))))
+            .collect()
+    }
+}
+
+/// 只登记主题目录中实际存在的图片，避免请求不存在的文件
+#[cfg(not(target_family = "wasm"))]
+fn discover_themes() -> Vec<Theme> {
+    let assets_dir = bevy::asset::io::file::FileAssetReader::get_base_path().join("assets");
+    let Ok(entries) = std::fs::read_dir(assets_dir.join(THEMES_DIR)) else {
+        return Vec::new();
+    };
+
+    let mut themes: Vec<Theme> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let files: HashSet<&'static str> = theme_files()
+                .filter(|file| entry.path().join(file).is_file())
+                .collect();
+            if files.is_empty() {
+                warn!("主题 {} 中没有可用的图片，已跳过", name);
+                return None;
+            }
+            info!("🎨 发现主题 {}（{} 张图片）", name, files.len());
+            Some(Theme {
+                root: format!("{}/{}", THEMES_DIR, name),
+                name,
+                files: Some(files),
+            })
+        })
+        .collect();
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    themes
+}
+
+// ============ 主题插件 ============
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ThemeRegistry>()
+            .add_systems(Update, apply_theme);
+    }
+}
+
+/// 主题切换后，把场景和界面中使用旧主题图片的贴图换成新主题的
+fn apply_theme(
+    registry: Res<ThemeRegistry>,
+    asset_server: Res<AssetServer>,
+    ui_assets: Option<ResMut<UIAssets>>,
+    mut sprites: Query<&mut Sprite>,
+    mut image_nodes: Query<&mut ImageNode>,
+    mut applied: Local<usize>,
+) {
+    if registry.current == *applied {
+        return;
+    }
+
+    let replacements: HashMap<String, Handle<Image>> = theme_files()
+        .map(|file| {
+            (
+                registry.resolve(*applied, file),
+                asset_server.load(registry.texture(file)),
+            )
+        })
+        .collect();
+    let replacement = |image: &Handle<Image>| {
+        let path = asset_server.get_path(image.id())?;
+        replacements.get(&path.path().to_string_lossy().to_string())
+    };
+
+    for mut sprite in sprites.iter_mut() {
+        if let Some(new_image) = replacement(&sprite.image) {
+            sprite.image = new_image.clone();
+        }
+    }
+    for mut image_node in image_nodes.iter_mut() {
+        if let Some(new_image) = replacement(&image_node.image) {
+            image_node.image = new_image.clone();
+        }
+    }
+
+    if let Some(mut ui_assets) = ui_assets {
+        ui_assets.segment_icons = registry.segment_icons(&asset_server);
+        ui_assets.passenger_icons = registry.passenger_icons(&asset_server);
+    }
+
+    *applied = registry.current;
+    info!(
+        "🎨 已切换主题: {}",
+        registry.current_name(Language::Chinese)
+    );
+}
@@ -0,0 +1,265 @@
+// src/bus_puzzle/timelapse.rs - 关卡解法延时动画（通关时导出 APNG）
+
+use crate::bus_puzzle::{
+    GameState, GameStateEnum, GridPos, LevelData, RouteSegmentType, SimClock, TerrainType,
+};
+use bevy::prelude::*;
+
+/// 棋盘快照的采样间隔（游戏时间，秒）
+const TIMELAPSE_INTERVAL: f32 = 1.0;
+
+/// 最多保留的帧数，超出后丢弃中间帧
+const TIMELAPSE_MAX_FRAMES: usize = 240;
+
+/// 导出图片中每个网格的像素大小
+#[cfg(not(target_family = "wasm"))]
+const TIMELAPSE_CELL_SIZE: u32 = 16;
+
+/// 每帧显示时长（秒）= 分子 / 分母
+#[cfg(not(target_family = "wasm"))]
+const TIMELAPSE_FRAME_DELAY: (u16, u16) = (1, 4);
+
+// ============ 延时录制资源 ============
+
+type BoardSnapshot = Vec<(GridPos, RouteSegmentType, u32)>;
+
+/// 关卡进行中按固定间隔记录的棋盘快照
+#[derive(Resource, Default)]
+pub struct TimelapseRecorder {
+    frames: Vec<BoardSnapshot>,
+    time_since_sample: f32,
+}
+
+impl TimelapseRecorder {
+    fn clear(&mut self) {
+        self.frames.clear();
+        self.time_since_sample = 0.0;
+    }
+
+    /// 只在棋盘发生变化时追加一帧
+    fn sample(&mut self, game_state: &GameState) {
+        let mut snapshot: BoardSnapshot = game_state
+            .placed_segments
+            .iter()
+            .map(|(pos, placed)| (*pos, placed.segment_type, placed.rotation))
+            .collect();
+        snapshot.sort_by_key(|(pos, ..)| (pos.x, pos.y));
+
+        if self.frames.last() == Some(&snapshot) {
+            return;
+        }
+
+        if self.frames.len() >= TIMELAPSE_MAX_FRAMES {
+            // 隔帧抽稀，保留首尾
+            let mut index = 0;
+            self.frames.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+        }
+        self.frames.push(snapshot);
+    }
+}
+
+// ============ 延时录制插件 ============
+
+pub struct TimelapsePlugin;
+
+impl Plugin for TimelapsePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimelapseRecorder>()
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_timelapse)
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_timelapse)
+            .add_systems(
+                Update,
+                record_timelapse_frames.run_if(in_state(GameStateEnum::Playing)),
+            )
+            .add_systems(OnEnter(GameStateEnum::LevelComplete), export_timelapse);
+    }
+}
+
+fn reset_timelapse(mut recorder: ResMut<TimelapseRecorder>) {
+    recorder.clear();
+}
+
+fn record_timelapse_frames(
+    sim_clock: Res<SimClock>,
+    game_state: Res<GameState>,
+    mut recorder: ResMut<TimelapseRecorder>,
+) {
+    recorder.time_since_sample += sim_clock.delta_secs();
+    if recorder.time_since_sample < TIMELAPSE_INTERVAL {
+        return;
+    }
+
+    recorder.time_since_sample = 0.0;
+    recorder.sample(&game_state);
+}
+
+fn export_timelapse(mut recorder: ResMut<TimelapseRecorder>, game_state: Res<GameState>) {
+    // 补上通关时的最终画面
+    recorder.sample(&game_state);
+
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let path = format!(
+            "timelapse_{}_{}.png",
+            level_data.id,
+            chrono::Utc::now().format("%Y%m%d_%H%M%S")
+        );
+        match write_apng(&path, level_data, &recorder.frames) {
+            Ok(()) => info!("🎞️ 延时动画已导出: {} ({} 帧)", path, recorder.frames.len()),
+            Err(e) => warn!("导出延时动画失败: {}", e),
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        info!(
+            "WASM环境：不导出延时动画 ({}，{} 帧)",
+            level_data.id,
+            recorder.frames.len()
+        );
+    }
+}
+
+// ============ 画面绘制 ============
+
+/// 关卡缩略图也使用同一套地形配色
+pub fn terrain_color(terrain_type: &TerrainType) -> [u8; 3] {
+    match terrain_type {
+        TerrainType::Empty => [92, 140, 72],
+        TerrainType::Building => [110, 110, 120],
+        TerrainType::Water => [60, 110, 190],
+        TerrainType::Park => [70, 170, 80],
+        TerrainType::Mountain => [130, 100, 70],
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn segment_color(segment_type: &RouteSegmentType) -> [u8; 3] {
+    match segment_type {
+        RouteSegmentType::Bridge => [200, 170, 90],
+        RouteSegmentType::Tunnel => [70, 70, 70],
+        _ => [240, 240, 240],
+    }
+}
+
+pub const STATION_COLOR: [u8; 3] = [230, 60, 60];
+
+/// 把一帧快照绘制成 RGB 像素（网格 y 轴向上，图片行向下）
+#[cfg(not(target_family = "wasm"))]
+fn render_frame(level_data: &LevelData, snapshot: &BoardSnapshot) -> Vec<u8> {
+    let (grid_width, grid_height) = level_data.grid_size;
+    let cell = TIMELAPSE_CELL_SIZE;
+    let image_width = grid_width * cell;
+    let image_height = grid_height * cell;
+    let mut pixels = vec![0u8; (image_width * image_height * 3) as usize];
+
+    let mut fill = |x0: u32, y0: u32, w: u32, h: u32, color: [u8; 3]| {
+        for y in y0..(y0 + h).min(image_height) {
+            for x in x0..(x0 + w).min(image_width) {
+                let index = ((y * image_width + x) * 3) as usize;
+                pixels[index..index + 3].copy_from_slice(&color);
+            }
+        }
+    };
+
+    // 网格左上角像素坐标
+    let cell_origin = |pos: GridPos| (pos.x as u32 * cell, (grid_height - 1 - pos.y as u32) * cell);
+
+    for x in 0..grid_width as i32 {
+        for y in 0..grid_height as i32 {
+            let pos = GridPos::new(x, y);
+            let terrain = level_data
+                .terrain
+                .get(&pos)
+                .cloned()
+                .unwrap_or(TerrainType::Empty);
+            let (px, py) = cell_origin(pos);
+            fill(px, py, cell, cell, terrain_color(&terrain));
+        }
+    }
+
+    // 路线段：从格子中心向每个连接方向画一条粗线
+    let half = cell / 2;
+    let thickness = (cell / 4).max(2);
+    for (pos, segment_type, rotation) in snapshot {
+        if pos.x < 0 || pos.y < 0 || pos.x as u32 >= grid_width || pos.y as u32 >= grid_height {
+            continue;
+        }
+        let (px, py) = cell_origin(*pos);
+        let color = segment_color(segment_type);
+        let center = (px + half - thickness / 2, py + half - thickness / 2);
+        fill(center.0, center.1, thickness, thickness, color);
+
+        for (dx, dy) in segment_type.get_connection_offsets(*rotation) {
+            match (dx, -dy) {
+                (1, 0) => fill(
+                    center.0,
+                    center.1,
+                    cell - half + thickness / 2,
+                    thickness,
+                    color,
+                ),
+                (-1, 0) => fill(px, center.1, half, thickness, color),
+                (0, 1) => fill(
+                    center.0,
+                    center.1,
+                    thickness,
+                    cell - half + thickness / 2,
+                    color,
+                ),
+                (0, -1) => fill(center.0, py, thickness, half, color),
+                _ => {}
+            }
+        }
+    }
+
+    for station in &level_data.stations {
+        let (px, py) = cell_origin(station.position);
+        let inset = cell / 4;
+        fill(
+            px + inset,
+            py + inset,
+            cell - inset * 2,
+            cell - inset * 2,
+            STATION_COLOR,
+        );
+    }
+
+    pixels
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write_apng(
+    path: &str,
+    level_data: &LevelData,
+    frames: &[BoardSnapshot],
+) -> Result<(), png::EncodingError> {
+    let file = std::fs::File::create(path)?;
+    let (grid_width, grid_height) = level_data.grid_size;
+
+    let mut encoder = png::Encoder::new(
+        std::io::BufWriter::new(file),
+        grid_width * TIMELAPSE_CELL_SIZE,
+        grid_height * TIMELAPSE_CELL_SIZE,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len().max(1) as u32, 0)?; // 0 = 无限循环
+    encoder.set_frame_delay(TIMELAPSE_FRAME_DELAY.0, TIMELAPSE_FRAME_DELAY.1)?;
+
+    let mut writer = encoder.write_header()?;
+    if frames.is_empty() {
+        writer.write_image_data(&render_frame(level_data, &Vec::new()))?;
+    }
+    for snapshot in frames {
+        writer.write_image_data(&render_frame(level_data, snapshot))?;
+    }
+    writer.finish()
+}
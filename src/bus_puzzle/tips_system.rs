@@ -1,13 +1,19 @@
 // src/bus_puzzle/tips_system.rs - 游戏提示系统
 
 use crate::bus_puzzle::{
-    get_text, get_text_with_args, CurrentLanguage, GameState, GameStateEnum, Language, LevelData,
+    format_time, get_text, get_text_with_args, ui_audio::GameplayUI, AudioCueIndicator,
+    ButtonComponent, ButtonType, CurrentLanguage, GameState, GameStateEnum, Language, LevelData,
     BUDGET_WARNING, BUS_ROUTES_READY_INFO, PASSENGERS_GAVE_UP_WARNING, PASSENGERS_WAITING_HINT,
+    TIP_HISTORY, TIP_HISTORY_EMPTY,
 };
-use bevy::prelude::{
-    Val::{Percent, Px},
-    *,
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
 };
+use std::collections::VecDeque;
 
 // ============ Tips 组件和资源 ============
 
@@ -30,19 +36,23 @@ pub enum TipType {
 
 // ============ 动态提示系统 ============
 
+fn tip_type_color(tip_type: &TipType) -> Color {
+    match tip_type {
+        TipType::LevelGoal => Color::srgb(0.2, 0.8, 0.2),
+        TipType::Strategy => Color::srgb(0.9, 0.7, 0.2),
+        TipType::Controls => Color::srgb(0.2, 0.6, 0.9),
+        TipType::Warning => Color::srgb(0.9, 0.3, 0.2),
+    }
+}
+
 pub fn show_contextual_tip(
     commands: &mut Commands,
     ui_assets: &crate::bus_puzzle::UIAssets,
     tip_content: &str,
     tip_type: TipType,
     duration: f32,
-) {
-    let tip_color = match tip_type {
-        TipType::LevelGoal => Color::srgb(0.2, 0.8, 0.2),
-        TipType::Strategy => Color::srgb(0.9, 0.7, 0.2),
-        TipType::Controls => Color::srgb(0.2, 0.6, 0.9),
-        TipType::Warning => Color::srgb(0.9, 0.3, 0.2),
-    };
+) -> Entity {
+    let tip_color = tip_type_color(&tip_type);
 
     commands
         .spawn((
@@ -59,6 +69,10 @@ pub fn show_contextual_tip(
             Outline::new(Px(2.0), Val::ZERO, tip_color),
             ZIndex(200),
             Name::new("Contextual Tip"),
+            ContextualTip {
+                content: tip_content.to_string(),
+                tip_type: tip_type.clone(),
+            },
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -74,15 +88,263 @@ pub fn show_contextual_tip(
         .insert(
             // 添加自动消失组件
             TipTimer(Timer::from_seconds(duration, TimerMode::Once)),
-        );
+        )
+        .id()
+}
+
+/// 屏幕顶部居中的脉动警告横幅，用于即将失败等需要立刻引起注意的提示
+pub fn show_warning_banner(
+    commands: &mut Commands,
+    ui_assets: &crate::bus_puzzle::UIAssets,
+    tip_content: &str,
+    duration: f32,
+) -> Entity {
+    let tip_color = tip_type_color(&TipType::Warning);
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Px(90.0),
+                left: Percent(50.0),
+                width: Px(420.0),
+                margin: UiRect::left(Px(-210.0)), // 水平居中
+                padding: UiRect::axes(Px(16.0), Px(10.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(tip_color.with_alpha(0.85)),
+            Outline::new(Px(3.0), Val::ZERO, tip_color),
+            ZIndex(210),
+            Name::new("Warning Banner"),
+            ContextualTip {
+                content: tip_content.to_string(),
+                tip_type: TipType::Warning,
+            },
+            PulsingTip,
+            TipTimer(Timer::from_seconds(duration, TimerMode::Once)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(tip_content),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        })
+        .id()
 }
 
 #[derive(Debug, Component, Deref, DerefMut)]
 struct TipTimer(Timer);
 
+/// 脉动闪烁的提示横幅
+#[derive(Component)]
+pub struct PulsingTip;
+
+/// 横幅脉动频率（弧度/秒）
+const PULSE_SPEED: f32 = 6.0;
+
+/// 弹出提示的内容，用于记录到提示历史
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
+pub struct ContextualTip {
+    pub content: String,
+    pub tip_type: TipType,
+}
+
+// ============ 提示历史 ============
+
+/// 提示历史最多保留的条数
+pub const TIP_HISTORY_LIMIT: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct TipHistoryEntry {
+    pub content: String,
+    pub tip_type: TipType,
+    pub game_time: f32,
+}
+
+#[derive(Component)]
+pub struct TipHistoryPanel;
+
+#[derive(Component)]
+pub struct TipHistoryList;
+
+/// 记录新弹出的提示
+fn record_shown_tips(
+    mut tips_manager: ResMut<LocalizedTipsManager>,
+    game_state: Res<GameState>,
+    new_tips: Query<&ContextualTip, (Added<ContextualTip>, Without<AudioCueIndicator>)>,
+) {
+    for tip in new_tips.iter() {
+        tips_manager.push_history(TipHistoryEntry {
+            content: tip.content.clone(),
+            tip_type: tip.tip_type.clone(),
+            game_time: game_state.game_time,
+        });
+    }
+}
+
+/// "!" 按钮切换提示历史面板
+fn handle_tip_history_toggle(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    tips_manager: Res<LocalizedTipsManager>,
+    ui_assets: Res<crate::bus_puzzle::UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    existing_panels: Query<Entity, With<TipHistoryPanel>>,
+) {
+    let toggled = button_query.iter().any(|(interaction, button)| {
+        matches!(*interaction, Interaction::Pressed)
+            && matches!(button.button_type, ButtonType::ToggleTipHistory)
+    });
+
+    if !toggled {
+        return;
+    }
+
+    if existing_panels.is_empty() {
+        spawn_tip_history_panel(
+            &mut commands,
+            &ui_assets,
+            &tips_manager,
+            current_language.language,
+        );
+        info!("打开提示历史，共 {} 条", tips_manager.tip_history.len());
+    } else {
+        for entity in existing_panels.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn spawn_tip_history_panel(
+    commands: &mut Commands,
+    ui_assets: &crate::bus_puzzle::UIAssets,
+    tips_manager: &LocalizedTipsManager,
+    language: Language,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(70.0),
+                top: Px(450.0),
+                width: Px(250.0),
+                height: Px(220.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Px(8.0)),
+                row_gap: Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.1, 0.95)),
+            Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.9, 0.7, 0.2)),
+            ZIndex(250),
+            GameplayUI,
+            TipHistoryPanel,
+            Name::new("Tip History Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(get_text(&TIP_HISTORY, language)),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+            ));
+
+            // 可滚动的历史列表（最新的在最上面）
+            parent
+                .spawn((
+                    Node {
+                        width: Percent(100.0),
+                        flex_grow: 1.0,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Px(4.0),
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                    ScrollPosition::default(),
+                    Interaction::None,
+                    TipHistoryList,
+                ))
+                .with_children(|parent| {
+                    if tips_manager.tip_history.is_empty() {
+                        parent.spawn((
+                            Text::new(get_text(&TIP_HISTORY_EMPTY, language)),
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                        ));
+                    }
+
+                    for entry in tips_manager.tip_history.iter().rev() {
+                        parent.spawn((
+                            Text::new(format!(
+                                "[{}] {}",
+                                format_time(entry.game_time),
+                                entry.content
+                            )),
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(tip_type_color(&entry.tip_type)),
+                            Node {
+                                width: Percent(100.0),
+                                flex_shrink: 0.0,
+                                ..default()
+                            },
+                        ));
+                    }
+                });
+        });
+}
+
+/// 鼠标悬停在历史列表上时用滚轮滚动
+fn scroll_tip_history(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut lists: Query<(&Interaction, &mut ScrollPosition), With<TipHistoryList>>,
+) {
+    for wheel_event in mouse_wheel_events.read() {
+        let dy = match wheel_event.unit {
+            MouseScrollUnit::Line => wheel_event.y * 20.0,
+            MouseScrollUnit::Pixel => wheel_event.y,
+        };
+
+        for (interaction, mut scroll_position) in lists.iter_mut() {
+            if !matches!(*interaction, Interaction::None) {
+                scroll_position.offset_y = (scroll_position.offset_y - dy).max(0.0);
+            }
+        }
+    }
+}
+
 // ============ 上下文感知提示 ============
 
 // 添加清理过期提示的系统（安全删除）
+fn pulse_warning_banners(
+    time: Res<Time>,
+    mut banners: Query<(&mut BackgroundColor, &mut Outline), With<PulsingTip>>,
+) {
+    let pulse = (time.elapsed_secs() * PULSE_SPEED).sin() * 0.5 + 0.5;
+    for (mut background, mut outline) in banners.iter_mut() {
+        background.0.set_alpha(0.55 + 0.35 * pulse);
+        outline.color = Color::srgb(1.0, 0.3 + 0.5 * pulse, 0.2);
+    }
+}
+
 fn cleanup_expired_tips(
     mut commands: Commands,
     mut timers: Query<(Entity, &mut TipTimer), With<TipTimer>>,
@@ -222,14 +484,14 @@ impl LocalizedGameTip {
     pub fn get_title(&self, language: Language) -> &str {
         match language {
             Language::Chinese => &self.title_zh,
-            Language::English => &self.title_en,
+            _ => &self.title_en,
         }
     }
 
     pub fn get_content(&self, language: Language) -> &str {
         match language {
             Language::Chinese => &self.content_zh,
-            Language::English => &self.content_en,
+            _ => &self.content_en,
         }
     }
 }
@@ -241,9 +503,18 @@ pub struct LocalizedTipsManager {
     pub current_tips: Vec<LocalizedGameTip>,
     pub is_expanded: bool,
     pub last_level_id: String,
+    pub tip_history: VecDeque<TipHistoryEntry>, // 最近弹出的提示，最多 TIP_HISTORY_LIMIT 条
 }
 
 impl LocalizedTipsManager {
+    /// 记录一条提示，超出上限时丢弃最旧的
+    pub fn push_history(&mut self, entry: TipHistoryEntry) {
+        if self.tip_history.len() >= TIP_HISTORY_LIMIT {
+            self.tip_history.pop_front();
+        }
+        self.tip_history.push_back(entry);
+    }
+
     pub fn generate_localized_tips_for_level(&mut self, level_data: &LevelData) {
         self.current_tips.clear();
 
@@ -464,7 +735,7 @@ pub fn create_localized_tips_panel(
         .with_children(|parent| {
             let title_text = match current_language.language {
                 Language::Chinese => "💡 关卡提示",
-                Language::English => "💡 Level Tips",
+                _ => "💡 Level Tips",
             };
 
             parent.spawn((
@@ -479,7 +750,7 @@ pub fn create_localized_tips_panel(
 
             let toggle_text = match current_language.language {
                 Language::Chinese => "F1 切换",
-                Language::English => "F1 Toggle",
+                _ => "F1 Toggle",
             };
 
             parent.spawn((
@@ -619,6 +890,7 @@ pub struct LocalizedTipText {
 }
 
 #[derive(Component)]
+#[allow(dead_code)]
 pub struct LocalizedTipsPanel;
 
 // 响应语言切换的更新系统（修复层级关系问题）
@@ -660,6 +932,10 @@ impl Plugin for LocalizedTipsSystemPlugin {
                 update_tips_display,        // 更新可见性
                 update_tips_panel_language, // 语言切换响应
                 cleanup_expired_tips,
+                pulse_warning_banners,
+                record_shown_tips,         // 记录提示历史
+                handle_tip_history_toggle, // "!" 按钮打开历史
+                scroll_tip_history,
                 debug_tips_panel_state, // F2调试信息
             )
                 .run_if(in_state(GameStateEnum::Playing)),
@@ -0,0 +1,238 @@
+// src/bus_puzzle/touch_ui.rs - 触屏布局（大按钮、底部库存栏、屏幕旋转/拆除按钮）
+
+use crate::bus_puzzle::{
+    localized_text, ui_audio::GameplayUI, ButtonComponent, ButtonType, GameStateEnum, GridPos,
+    InputState, InventoryPanel, InventorySlot, RemoveSegmentRequestEvent,
+    RotateSegmentRequestEvent, UIAssets, TOUCH_DELETE, TOUCH_ROTATE,
+};
+use bevy::prelude::{
+    Val::{Auto, Percent, Px},
+    *,
+};
+
+/// 触屏布局下按钮的最小高度（像素），保证手指可以轻松点中
+const TOUCH_MIN_BUTTON_SIZE: f32 = 56.0;
+
+/// 触屏布局下库存槽位大小（像素）
+const TOUCH_SLOT_SIZE: f32 = 84.0;
+
+/// 底部库存栏高度（像素）
+const TOUCH_BAR_HEIGHT: f32 = TOUCH_SLOT_SIZE + 24.0;
+
+/// 移动平台或启用 touch_ui 特性时默认使用触屏布局
+const TOUCH_UI_BY_DEFAULT: bool = cfg!(any(
+    feature = "touch_ui",
+    target_os = "android",
+    target_os = "ios"
+));
+
+// ============ 触屏布局资源 ============
+
+#[derive(Resource)]
+pub struct TouchUi {
+    pub enabled: bool,
+    pub target_cell: Option<GridPos>, // 最近一次点击的格子，屏幕按钮作用于它
+}
+
+impl Default for TouchUi {
+    fn default() -> Self {
+        Self {
+            enabled: TOUCH_UI_BY_DEFAULT,
+            target_cell: None,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct TouchControls;
+
+// ============ 触屏布局插件 ============
+
+pub struct TouchUiPlugin;
+
+impl Plugin for TouchUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchUi>()
+            .add_systems(OnEnter(GameStateEnum::Playing), spawn_touch_controls)
+            .add_systems(OnExit(GameStateEnum::Playing), clear_touch_target)
+            .add_systems(Update, (apply_touch_layout, enlarge_touch_buttons))
+            .add_systems(
+                Update,
+                (track_touch_target, handle_touch_control_buttons)
+                    .chain()
+                    .run_if(in_state(GameStateEnum::Playing)),
+            );
+
+        // 网页端无法在编译期区分设备，检测到触摸输入后切换为触屏布局
+        #[cfg(target_family = "wasm")]
+        app.add_systems(Update, detect_touch_input);
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn detect_touch_input(touches: Res<Touches>, mut touch_ui: ResMut<TouchUi>) {
+    if !touch_ui.enabled && touches.any_just_pressed() {
+        touch_ui.enabled = true;
+        info!("📱 检测到触摸输入，切换为触屏布局");
+    }
+}
+
+fn clear_touch_target(mut touch_ui: ResMut<TouchUi>) {
+    touch_ui.target_cell = None;
+}
+
+// ============ 布局调整 ============
+
+/// 把左侧库存面板改为底部横向库存栏，并放大槽位
+fn apply_touch_layout(
+    touch_ui: Res<TouchUi>,
+    mut panels: Query<&mut Node, Added<InventoryPanel>>,
+    mut slots: Query<&mut Node, (Added<InventorySlot>, Without<InventoryPanel>)>,
+) {
+    if !touch_ui.enabled {
+        return;
+    }
+
+    for mut node in panels.iter_mut() {
+        node.width = Percent(100.0);
+        node.height = Px(TOUCH_BAR_HEIGHT);
+        node.left = Px(0.0);
+        node.top = Auto;
+        node.bottom = Px(0.0);
+        node.flex_direction = FlexDirection::Row;
+        node.justify_content = JustifyContent::Center;
+        node.align_items = AlignItems::Center;
+        node.column_gap = Px(12.0);
+    }
+
+    for mut node in slots.iter_mut() {
+        node.width = Px(TOUCH_SLOT_SIZE);
+        node.height = Px(TOUCH_SLOT_SIZE);
+    }
+}
+
+/// 所有按钮至少保持手指可点的高度
+fn enlarge_touch_buttons(
+    touch_ui: Res<TouchUi>,
+    mut buttons: Query<&mut Node, (Added<ButtonComponent>, Without<InventorySlot>)>,
+) {
+    if !touch_ui.enabled {
+        return;
+    }
+
+    for mut node in buttons.iter_mut() {
+        node.min_height = Px(TOUCH_MIN_BUTTON_SIZE);
+    }
+}
+
+// ============ 屏幕操作按钮 ============
+
+fn spawn_touch_controls(mut commands: Commands, ui_assets: Res<UIAssets>, touch_ui: Res<TouchUi>) {
+    if !touch_ui.enabled {
+        return;
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(20.0),
+                bottom: Px(TOUCH_BAR_HEIGHT + 16.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Px(12.0),
+                ..default()
+            },
+            ZIndex(60),
+            GameplayUI,
+            TouchControls,
+            Name::new("Touch Controls"),
+        ))
+        .with_children(|parent| {
+            for (button_type, label) in [
+                (ButtonType::TouchRotate, &TOUCH_ROTATE),
+                (ButtonType::TouchDelete, &TOUCH_DELETE),
+            ] {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Px(120.0),
+                            height: Px(TOUCH_MIN_BUTTON_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.85)),
+                        ButtonComponent {
+                            button_type,
+                            is_hovered: false,
+                            is_pressed: false,
+                        },
+                    ))
+                    .with_children(|parent| {
+                        let (localized, text) = localized_text(label);
+                        parent.spawn((
+                            text,
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            localized,
+                        ));
+                    });
+            }
+        });
+}
+
+/// 在棋盘上点击（而不是点击按钮）时记住目标格子
+fn track_touch_target(
+    mut touch_ui: ResMut<TouchUi>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    input_state: Res<InputState>,
+    ui_buttons: Query<&Interaction, With<Button>>,
+) {
+    if !touch_ui.enabled {
+        return;
+    }
+
+    let tapped = mouse_button_input.just_released(MouseButton::Left) || touches.any_just_released();
+    if !tapped
+        || ui_buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    touch_ui.target_cell = input_state.grid_cursor_pos;
+}
+
+fn handle_touch_control_buttons(
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    touch_ui: Res<TouchUi>,
+    mut rotate_requests: EventWriter<RotateSegmentRequestEvent>,
+    mut remove_requests: EventWriter<RemoveSegmentRequestEvent>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::TouchRotate => {
+                rotate_requests.write(RotateSegmentRequestEvent {
+                    position: touch_ui.target_cell,
+                });
+            }
+            ButtonType::TouchDelete => {
+                if let Some(position) = touch_ui.target_cell {
+                    remove_requests.write(RemoveSegmentRequestEvent { position });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,400 @@
+// src/bus_puzzle/transit_import.rs - 导入简化公交数据（GTFS-lite）生成沙盒关卡
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, show_contextual_tip, ActiveChallenge, AvailableSegment,
+    ButtonComponent, ButtonType, CurrentLanguage, GameStateEnum, GridPos, Language, LevelData,
+    ObjectiveCondition, ObjectiveType, PassengerColor, PassengerDemand, RefundPolicy,
+    RouteSegmentType, ScoringConfig, Station, StationType, TerrainType, TipType, UIAssets,
+    IMPORTED_CITY, IMPORTED_CITY_DESCRIPTION, IMPORT_FAILED, OBJECTIVE_CONNECT_ALL,
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
+
+#[cfg(not(target_family = "wasm"))]
+pub const TRANSIT_IMPORT_FILE: &str = "transit_import.json";
+
+/// 沙盒地图尺寸
+const SANDBOX_GRID_SIZE: (u32, u32) = (16, 12);
+
+/// 地图边缘留空的格数
+const SANDBOX_MARGIN: i32 = 1;
+
+/// 最多导入的站点数，避免地图过于拥挤
+const MAX_IMPORTED_STOPS: usize = 40;
+
+const ROUTE_COLORS: [PassengerColor; 6] = [
+    PassengerColor::Red,
+    PassengerColor::Blue,
+    PassengerColor::Green,
+    PassengerColor::Yellow,
+    PassengerColor::Purple,
+    PassengerColor::Orange,
+];
+
+// ============ 导入数据格式 ============
+
+/// 简化的公交数据：站点坐标 + 按顺序经过站点的线路
+///
+/// ```json
+/// {
+///   "name": "My City",
+///   "stops": [{ "id": "s1", "name": "Main St", "lat": 31.23, "lon": 121.47 }],
+///   "routes": [{ "id": "r1", "name": "Line 1", "stops": ["s1", "s2"] }]
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct TransitFeed {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub stops: Vec<TransitStop>,
+    pub routes: Vec<TransitRoute>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransitStop {
+    pub id: String,
+    pub name: String,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransitRoute {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub stops: Vec<String>,
+}
+
+/// 代替内置关卡加载的自定义关卡（例如导入的城市地图）
+#[derive(Resource, Default)]
+pub struct CustomLevel(pub Option<LevelData>);
+
+// ============ 公交数据导入插件 ============
+
+pub struct TransitImportPlugin;
+
+impl Plugin for TransitImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CustomLevel>()
+            .add_systems(OnEnter(GameStateEnum::MainMenu), clear_custom_level)
+            .add_systems(
+                Update,
+                handle_transit_import_button.run_if(in_state(GameStateEnum::MainMenu)),
+            );
+    }
+}
+
+fn clear_custom_level(mut custom_level: ResMut<CustomLevel>) {
+    custom_level.0 = None;
+}
+
+// ============ 数据转换 ============
+
+/// 把公交数据转换为沙盒关卡：经纬度按比例映射到网格，每条线路生成一组首末站乘客需求
+pub fn transit_feed_to_level(feed: &TransitFeed, language: Language) -> Result<LevelData, String> {
+    // 只保留被线路引用的站点
+    let mut route_counts: HashMap<&str, u32> = HashMap::new();
+    let mut terminals: Vec<&str> = Vec::new();
+    for route in &feed.routes {
+        for stop_id in &route.stops {
+            *route_counts.entry(stop_id.as_str()).or_insert(0) += 1;
+        }
+        if let (Some(first), Some(last)) = (route.stops.first(), route.stops.last()) {
+            terminals.push(first);
+            terminals.push(last);
+        }
+    }
+
+    let stops: Vec<&TransitStop> = feed
+        .stops
+        .iter()
+        .filter(|stop| route_counts.contains_key(stop.id.as_str()))
+        .take(MAX_IMPORTED_STOPS)
+        .collect();
+
+    if stops.len() < 2 {
+        return Err("at least 2 stops referenced by routes are required".to_string());
+    }
+    if feed.stops.len() > MAX_IMPORTED_STOPS {
+        warn!(
+            "公交数据站点过多，只导入前 {} 个被线路使用的站点",
+            MAX_IMPORTED_STOPS
+        );
+    }
+
+    // 经纬度范围
+    let (min_lat, max_lat) = min_max(stops.iter().map(|stop| stop.lat));
+    let (min_lon, max_lon) = min_max(stops.iter().map(|stop| stop.lon));
+
+    let (width, height) = SANDBOX_GRID_SIZE;
+    let usable_width = width as i32 - 1 - SANDBOX_MARGIN * 2;
+    let usable_height = height as i32 - 1 - SANDBOX_MARGIN * 2;
+    let scale = |value: f64, min: f64, max: f64, usable: i32| -> i32 {
+        if (max - min).abs() < f64::EPSILON {
+            usable / 2
+        } else {
+            ((value - min) / (max - min) * usable as f64).round() as i32
+        }
+    };
+
+    let mut occupied: Vec<GridPos> = Vec::new();
+    let mut stop_names: HashMap<&str, String> = HashMap::new();
+    let mut stations = Vec::new();
+
+    for stop in &stops {
+        let desired = GridPos::new(
+            SANDBOX_MARGIN + scale(stop.lon, min_lon, max_lon, usable_width),
+            SANDBOX_MARGIN + scale(stop.lat, min_lat, max_lat, usable_height), // 北方朝上
+        );
+        let Some(position) = nearest_free_cell(desired, &occupied) else {
+            warn!("沙盒地图已满，跳过站点 {}", stop.name);
+            continue;
+        };
+        occupied.push(position);
+
+        // 站点名称在寻路中作为唯一键，重名时附加站点ID
+        let name = if stations
+            .iter()
+            .any(|station: &Station| station.name == stop.name)
+        {
+            format!("{} ({})", stop.name, stop.id)
+        } else {
+            stop.name.clone()
+        };
+        stop_names.insert(stop.id.as_str(), name.clone());
+
+        let station_type = if route_counts[stop.id.as_str()] >= 2 {
+            StationType::TransferHub
+        } else if terminals.contains(&stop.id.as_str()) {
+            StationType::Terminal
+        } else {
+            StationType::BusStop
+        };
+
+        stations.push(Station {
+            position,
+            capacity: if station_type == StationType::TransferHub {
+                30
+            } else {
+                20
+            },
+            station_type,
+            name,
+            name_key: None,
+            passenger_types: vec![],
+        });
+    }
+
+    // 每条线路的首末站之间往返生成乘客
+    let mut passenger_demands = Vec::new();
+    for (index, route) in feed.routes.iter().enumerate() {
+        let endpoints = route
+            .stops
+            .first()
+            .and_then(|first| stop_names.get(first.as_str()))
+            .zip(
+                route
+                    .stops
+                    .last()
+                    .and_then(|last| stop_names.get(last.as_str())),
+            );
+        let Some((origin, destination)) = endpoints else {
+            warn!("线路 {} 的首末站未导入，跳过", route.id);
+            continue;
+        };
+        if origin == destination {
+            continue;
+        }
+
+        let color = ROUTE_COLORS[index % ROUTE_COLORS.len()];
+        info!(
+            "导入线路 {}: {} -> {}",
+            route.name.as_deref().unwrap_or(&route.id),
+            origin,
+            destination
+        );
+        passenger_demands.push(sandbox_demand(color, origin, destination));
+        passenger_demands.push(sandbox_demand(color, destination, origin));
+    }
+
+    if passenger_demands.is_empty() {
+        return Err("no route connects two imported stops".to_string());
+    }
+
+    let mut terrain = HashMap::new();
+    for x in 0..width as i32 {
+        for y in 0..height as i32 {
+            terrain.insert(GridPos::new(x, y), TerrainType::Empty);
+        }
+    }
+
+    let city_name = feed
+        .name
+        .clone()
+        .unwrap_or_else(|| get_text(&IMPORTED_CITY, language));
+
+    Ok(LevelData {
+        id: "sandbox_import".to_string(),
+        name: city_name.clone(),
+        description: get_text_with_args(
+            &IMPORTED_CITY_DESCRIPTION,
+            language,
+            &[
+                &city_name,
+                &stations.len().to_string(),
+                &feed.routes.len().to_string(),
+            ],
+        ),
+        name_key: None,
+        description_key: None,
+        difficulty: 3,
+        grid_size: SANDBOX_GRID_SIZE,
+        terrain,
+        stations,
+        passenger_demands,
+        available_segments: [
+            (RouteSegmentType::Straight, 60),
+            (RouteSegmentType::Curve, 30),
+            (RouteSegmentType::TSplit, 12),
+            (RouteSegmentType::Cross, 6),
+        ]
+        .into_iter()
+        .map(|(segment_type, count)| AvailableSegment {
+            segment_type,
+            count,
+            cost: segment_type.get_cost(),
+        })
+        .collect(),
+        objectives: vec![ObjectiveCondition {
+            description: get_text(&OBJECTIVE_CONNECT_ALL, language),
+            description_key: Some(&OBJECTIVE_CONNECT_ALL),
+            condition_type: ObjectiveType::ConnectAllPassengers,
+        }],
+        preset_routes: vec![],
+        dynamic_events: vec![],
+        random_events: false,
+        manual_fleet: false,
+        time_cycle: None,
+        scenario: Vec::new(),
+        scoring: ScoringConfig {
+            base_points: 500,
+            efficiency_bonus: 200,
+            speed_bonus: 100,
+            cost_bonus: 100,
+            speed_time_limit: 60.0,
+            cost_threshold: 15,
+            multiplier: 1.0,
+            refund_policy: RefundPolicy::Full,
+        },
+    })
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::MAX, f64::MIN), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+/// 从目标格子开始向外逐圈查找未被占用的格子
+fn nearest_free_cell(desired: GridPos, occupied: &[GridPos]) -> Option<GridPos> {
+    let (width, height) = SANDBOX_GRID_SIZE;
+    let max_radius = width.max(height) as i32;
+
+    for radius in 0..=max_radius {
+        for dx in -radius..=radius {
+            for dy in -radius..=radius {
+                if dx.abs() != radius && dy.abs() != radius {
+                    continue; // 只检查当前这一圈
+                }
+                let candidate = GridPos::new(desired.x + dx, desired.y + dy);
+                let in_bounds = candidate.x >= 0
+                    && candidate.y >= 0
+                    && candidate.x < width as i32
+                    && candidate.y < height as i32;
+                if in_bounds && !occupied.contains(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn sandbox_demand(color: PassengerColor, origin: &str, destination: &str) -> PassengerDemand {
+    PassengerDemand {
+        color,
+        origin: origin.to_string(),
+        destination: destination.to_string(),
+        origin_key: None,
+        destination_key: None,
+        spawn_rate: 0.3,
+        patience: 180.0,
+        spawn_time_range: Some((3.0, 90.0)),
+        total_count: Some(6),
+        spawned_count: 0,
+        via: None,
+        group_size: None,
+        demand_curve: None,
+    }
+}
+
+fn load_transit_level(language: Language) -> Result<LevelData, String> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let content = std::fs::read_to_string(TRANSIT_IMPORT_FILE)
+            .map_err(|e| format!("{}: {}", TRANSIT_IMPORT_FILE, e))?;
+        let feed: TransitFeed = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        transit_feed_to_level(&feed, language)
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        let _ = language;
+        Err("not supported on web".to_string())
+    }
+}
+
+// ============ 主菜单导入按钮 ============
+
+fn handle_transit_import_button(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    mut custom_level: ResMut<CustomLevel>,
+    mut active_challenge: ResMut<ActiveChallenge>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed)
+            || button_component.button_type != ButtonType::ImportTransitMap
+        {
+            continue;
+        }
+
+        match load_transit_level(current_language.language) {
+            Ok(level_data) => {
+                info!(
+                    "🗺️ 导入城市地图: {} ({} 个站点)",
+                    level_data.name,
+                    level_data.stations.len()
+                );
+                custom_level.0 = Some(level_data);
+                active_challenge.0 = None;
+                next_state.set(GameStateEnum::Playing);
+            }
+            Err(e) => {
+                warn!("导入公交数据失败: {}", e);
+                show_contextual_tip(
+                    &mut commands,
+                    &ui_assets,
+                    &get_text_with_args(&IMPORT_FAILED, current_language.language, &[&e]),
+                    TipType::Warning,
+                    4.0,
+                );
+            }
+        }
+    }
+}
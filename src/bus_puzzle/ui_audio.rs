@@ -4,19 +4,26 @@
 use super::{
     create_localized_tips_panel, ease_out_back, format_time, get_text, get_text_with_args,
     localized_text, localized_text_with_args, AgentState, AudioAssets, CostText, CurrentLanguage,
-    GameState, GameStateEnum, InventoryCountText, InventorySlot, Language, LanguageChangedEvent,
-    LevelCompletedEvent, LevelData, LevelManager, LocalizedText, LocalizedTextComponent,
-    LocalizedTipsManager, ObjectiveCompletedEvent, ObjectiveCondition, ObjectiveType,
-    PassengerColor, PassengerCountText, PathfindingAgent, RouteSegmentType, ScoreText,
-    SegmentPlacedEvent, SegmentRemovedEvent, TimerText, TipsPanel, UIElement, ALL_LEVELS_COMPLETE,
-    ARRIVED, COMPLETION_TIME, CONGRATULATIONS, COST, DONT_GIVE_UP, FAILURE_REASON, FINAL_SCORE,
-    GAME_DURATION, GAME_PAUSED, GAME_STATISTICS, GAME_TITLE, GAME_VERSION, INVENTORY_SLOT_SIZE,
-    LEVEL_COMPLETE, MAIN_MENU, MISSION_FAILED, NEXT_LEVEL, OBJECTIVES, PASSENGERS,
+    GameScore, GameState, GameStateEnum, GridPos, InventoryCountText, InventoryPanel,
+    InventorySlot, Language, LanguageChangedEvent, LevelCompletedEvent, LevelData, LevelManager,
+    LocalizedText, LocalizedTextComponent, LocalizedTipsManager, ObjectiveCompletedEvent,
+    ObjectiveCondition, ObjectiveType, PassengerColor, PassengerCountText, PathfindingAgent,
+    PendingAutosave, RouteSegmentType, ScoreChangedEvent, ScoreText, SegmentPlacedEvent,
+    SegmentRemovedEvent, TimerText, TipsPanel, UIElement, ALL_LEVELS_COMPLETE, ARRIVED,
+    COMPLETION_TIME, CONGRATULATIONS, CONTINUE_GAME, COST, DEFAULT_FONT_PATH, DONT_GIVE_UP,
+    EXPORT_RESULTS, FAILURE_REASON, FINAL_SCORE, GAME_DURATION, GAME_PAUSED, GAME_STATISTICS,
+    GAME_TITLE, GAME_VERSION, IMPORT_CITY, INVENTORY_SLOT_SIZE, LEVEL_COMPLETE, LEVEL_SELECT,
+    LEVEL_STATISTICS, MAIN_MENU, MISSION_FAILED, NEW_BADGE, NEXT_LEVEL, OBJECTIVES, PASSENGERS,
     PASSENGERS_GAVE_UP, PASSENGER_STATUS, PAUSE, QUIT_GAME, RESTART_LEVEL, RESUME_GAME, RETRY,
-    ROUTE_SEGMENTS, SCORE, SCORE_BREAKDOWN, SCORE_EARNED, START_GAME, THANK_YOU, TIME, TOTAL_COST,
-    WAITING,
+    RETRY_KEEP_NETWORK, ROUTE_SEGMENTS, SCORE, SCORE_BREAKDOWN, SCORE_EARNED, SETTINGS, START_GAME,
+    THANK_YOU, TIME, TOTAL_COST, WAITING, WEEKLY_CHALLENGES,
+};
+use crate::bus_puzzle::{
+    check_and_show_contextual_tips, ActiveChallenge, AudioChannel, ConfirmAction, ConfirmDialogUI,
+    ConfirmRequestEvent, CustomLevel, DialogConfirmedEvent, GameOptions, InputAction, InputMap,
+    LevelSelectTab, MutatorKind, SettingsMenuUI, TerrainType, ThemeRegistry, WeeklyChallengeBadge,
+    EXPORT_LEVEL,
 };
-use crate::bus_puzzle::check_and_show_contextual_tips;
 use bevy::{
     audio::{PlaybackMode, Volume},
     platform::collections::HashMap,
@@ -26,28 +33,31 @@ use bevy::{
 
 // ============ UI 组件 ============
 
+// 各界面的根实体标记，通过 StateScoped 在离开对应状态时统一清理
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::MainMenu))]
 pub struct MainMenuUI;
 
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Playing))]
 pub struct GameplayUI;
 
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::Paused))]
 pub struct PauseMenuUI;
 
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::LevelComplete))]
 pub struct LevelCompleteUI;
 
 #[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::GameOver))]
 pub struct GameOverUI;
 
 // 音乐标记组件
 #[derive(Component)]
 pub struct BackgroundMusic;
 
-#[derive(Component)]
-pub struct StateMusic;
-
 #[derive(Component)]
 #[allow(dead_code)]
 pub struct ObjectiveUI {
@@ -119,7 +129,10 @@ pub struct ButtonComponent {
 #[derive(Resource)]
 #[allow(dead_code)]
 pub struct UIAssets {
-    pub font: Handle<Font>,
+    pub font: Handle<Font>,         // 当前界面字体，由 apply_ui_font 按设置切换
+    pub default_font: Handle<Font>, // 默认字体（含中文字形）
+    pub language_fonts: HashMap<&'static str, Handle<Font>>, // 各语言字体链中已请求加载的字体
+    pub dyslexic_font: Option<Handle<Font>>, // 易读字体，首次启用时才加载
     pub button_texture: Handle<Image>,
     pub panel_texture: Handle<Image>,
     pub progress_bar_bg: Handle<Image>,
@@ -132,6 +145,7 @@ pub struct UIAssets {
 pub struct LevelCompleteData {
     pub final_score: u32,
     pub completion_time: f32,
+    pub score_breakdown: GameScore, // 最近一次 ScoreChangedEvent 的分数明细
 }
 
 #[derive(Resource, Default)]
@@ -146,10 +160,82 @@ pub struct GameOverData {
 pub struct AudioSettings {
     pub master_volume: f32,
     pub music_volume: f32,
-    pub sfx_volume: f32,
+    pub ui_volume: f32,       // 界面音效（按钮点击、悬停等）
+    pub world_volume: f32,    // 游戏场景音效（放置路线、乘客到达、目标完成等）
+    pub hover_volume: f32,    // 按钮悬停音，相对音效音量
+    pub disabled_volume: f32, // 点击不可用按钮的提示音，相对音效音量
     pub is_muted: bool,
 }
 
+impl AudioSettings {
+    /// 通道的最终音量（已乘总音量，静音时为 0）
+    pub fn bus_volume(&self, bus: AudioBus) -> f32 {
+        if self.is_muted {
+            return 0.0;
+        }
+        let bus_volume = match bus {
+            AudioBus::Music => self.music_volume,
+            AudioBus::Ui => self.ui_volume,
+            AudioBus::World => self.world_volume,
+        };
+        bus_volume * self.master_volume
+    }
+
+    /// 一次性音效的播放设置：音量为通道音量乘以音效自身的倍率。
+    /// 倍率记录在 SoundGain 中，调节音量时据此重新计算正在播放的声音
+    pub fn sound(&self, bus: AudioBus, gain: f32) -> (PlaybackSettings, AudioBus, SoundGain) {
+        (
+            PlaybackSettings {
+                mode: PlaybackMode::Despawn,
+                volume: Volume::Linear(self.bus_volume(bus) * gain),
+                ..default()
+            },
+            bus,
+            SoundGain(gain),
+        )
+    }
+}
+
+/// 声音相对所属通道音量的倍率，没有该组件时按 1 计算
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SoundGain(pub f32);
+
+/// 音频通道，每个 AudioPlayer 都带上所属通道，玩家可以分别调节
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioBus {
+    Music,
+    Ui,
+    World,
+}
+
+/// 压低后的音乐音量倍率，以及压低保持、压下和恢复的时长（秒）
+const DUCK_LEVEL: f32 = 0.3;
+const DUCK_HOLD_SECS: f32 = 1.5;
+const DUCK_ATTACK_SECS: f32 = 0.1;
+const DUCK_RELEASE_SECS: f32 = 0.6;
+
+/// 目标完成、过关、失败等提示音播放时暂时压低背景音乐，避免两者混在一起
+#[derive(Resource)]
+pub struct MusicDucking {
+    pub factor: f32, // 当前作用在音乐音量上的倍率
+    hold: f32,       // 剩余的压低时间
+}
+
+impl Default for MusicDucking {
+    fn default() -> Self {
+        Self {
+            factor: 1.0,
+            hold: 0.0,
+        }
+    }
+}
+
+impl MusicDucking {
+    pub fn duck(&mut self) {
+        self.hold = self.hold.max(DUCK_HOLD_SECS);
+    }
+}
+
 // ============ 插件系统 ============
 
 pub struct GameUIPlugin;
@@ -159,126 +245,80 @@ impl Plugin for GameUIPlugin {
         app.insert_resource(AudioSettings {
             master_volume: 1.0,
             music_volume: 0.7,
-            sfx_volume: 0.8,
+            ui_volume: 0.8,
+            world_volume: 0.8,
+            hover_volume: 0.4,
+            disabled_volume: 0.8,
             is_muted: false,
         })
-            .insert_resource(LevelCompleteData::default())
-            .insert_resource(GameOverData::default())
-            .add_systems(Startup, (load_ui_assets, load_audio_assets))
-            .add_systems(OnEnter(GameStateEnum::MainMenu), setup_main_menu)
-            .add_systems(OnEnter(GameStateEnum::Playing), setup_gameplay_ui)
-            .add_systems(OnEnter(GameStateEnum::Paused), setup_pause_menu)
-            .add_systems(
-                OnEnter(GameStateEnum::LevelComplete),
-                setup_level_complete_ui,
-            )
-            .add_systems(OnEnter(GameStateEnum::GameOver), setup_game_over_ui)
-            .add_systems(OnExit(GameStateEnum::MainMenu), cleanup_main_menu)
-            .add_systems(OnExit(GameStateEnum::Playing), cleanup_gameplay_ui)
-            .add_systems(OnExit(GameStateEnum::Paused), cleanup_pause_menu)
-            .add_systems(
-                OnExit(GameStateEnum::LevelComplete),
-                cleanup_level_complete_ui,
-            )
-            .add_systems(OnExit(GameStateEnum::GameOver), cleanup_game_over_ui)
-            .add_systems(
-                Update,
-                (
-                    handle_button_interactions,
-                    update_ui_animations,
-                    update_gameplay_ui_values,
-                    update_progress_bars,
-                    update_passenger_stats_ui, // 新增：更新乘客统计UI
-                    handle_audio_events,
-                    capture_level_complete_data, // 新增：捕获关卡完成数据
-                    check_and_show_contextual_tips, // 新增：上下文感知提示
-                    update_inventory_selection_state, // 新增：更新库存选中状态
-                )
-                    .run_if(in_state(GameStateEnum::Playing)),
-            )
-            .add_systems(
-                Update,
-                (handle_menu_buttons, handle_button_interactions)
-                    .run_if(in_state(GameStateEnum::MainMenu)),
-            )
-            .add_systems(
-                Update,
-                (
-                    handle_pause_input,
-                    handle_button_interactions.before(handle_pause_buttons), // 修复：确保交互处理在按钮逻辑之前
-                    handle_pause_buttons,
-                    debug_pause_menu_state, // 调试系统
-                ).run_if(in_state(GameStateEnum::Paused)),
-            )
-            .add_systems(
-                Update,
-                (handle_level_complete_buttons, handle_button_interactions)
-                    .run_if(in_state(GameStateEnum::LevelComplete)),
+        .insert_resource(LevelCompleteData::default())
+        .insert_resource(GameOverData::default())
+        .init_resource::<MusicDucking>()
+        .add_systems(Startup, (load_ui_assets, load_audio_assets))
+        .add_systems(OnEnter(GameStateEnum::MainMenu), setup_main_menu)
+        .add_systems(OnEnter(GameStateEnum::Playing), setup_gameplay_ui)
+        .add_systems(OnEnter(GameStateEnum::Paused), setup_pause_menu)
+        .add_systems(
+            OnEnter(GameStateEnum::LevelComplete),
+            setup_level_complete_ui,
+        )
+        .add_systems(OnEnter(GameStateEnum::GameOver), setup_game_over_ui)
+        .add_systems(
+            Update,
+            (
+                handle_button_interactions,
+                update_ui_animations,
+                update_gameplay_ui_values,
+                flash_cost_text_on_spend,
+                update_progress_bars,
+                update_passenger_stats_ui, // 新增：更新乘客统计UI
+                handle_audio_events,
+                capture_level_complete_data,    // 新增：捕获关卡完成数据
+                check_and_show_contextual_tips, // 新增：上下文感知提示
+                update_inventory_selection_state, // 新增：更新库存选中状态
             )
-            .add_systems(
-                Update,
-                (handle_game_over_buttons, handle_button_interactions)
-                    .run_if(in_state(GameStateEnum::GameOver)),
+                .run_if(in_state(GameStateEnum::Playing)),
+        )
+        .add_systems(
+            Update,
+            (handle_menu_buttons, handle_button_interactions)
+                .run_if(in_state(GameStateEnum::MainMenu)),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_pause_input,
+                handle_button_interactions.before(handle_pause_buttons), // 修复：确保交互处理在按钮逻辑之前
+                handle_pause_buttons,
+                debug_pause_menu_state, // 调试系统
             )
-            .add_systems(Update, (update_background_music,)); // 全局音频系统
+                .run_if(in_state(GameStateEnum::Paused)),
+        )
+        .add_systems(
+            Update,
+            (handle_level_complete_buttons, handle_button_interactions)
+                .run_if(in_state(GameStateEnum::LevelComplete)),
+        )
+        .add_systems(
+            Update,
+            (handle_game_over_buttons, handle_button_interactions)
+                .run_if(in_state(GameStateEnum::GameOver)),
+        )
+        .add_systems(OnEnter(GameStateEnum::GameOver), duck_music_on_game_over)
+        .add_systems(Update, update_music_ducking); // 全局音频系统
     }
 }
 
 // ============ 资源加载 ============
 
-fn load_ui_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn load_ui_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<ThemeRegistry>,
+) {
     info!("加载 UI 资源");
-    let mut segment_icons = HashMap::new();
-    segment_icons.insert(
-        RouteSegmentType::Straight,
-        asset_server.load("textures/routes/straight.png"),
-    );
-    segment_icons.insert(
-        RouteSegmentType::Curve,
-        asset_server.load("textures/routes/curve.png"),
-    );
-    segment_icons.insert(
-        RouteSegmentType::TSplit,
-        asset_server.load("textures/routes/t_split.png"),
-    );
-    segment_icons.insert(
-        RouteSegmentType::Cross,
-        asset_server.load("textures/routes/cross.png"),
-    );
-    segment_icons.insert(
-        RouteSegmentType::Bridge,
-        asset_server.load("textures/routes/bridge.png"),
-    );
-    segment_icons.insert(
-        RouteSegmentType::Tunnel,
-        asset_server.load("textures/routes/tunnel.png"),
-    );
-
-    let mut passenger_icons = HashMap::new();
-    passenger_icons.insert(
-        PassengerColor::Red,
-        asset_server.load("textures/passengers/red.png"),
-    );
-    passenger_icons.insert(
-        PassengerColor::Blue,
-        asset_server.load("textures/passengers/blue.png"),
-    );
-    passenger_icons.insert(
-        PassengerColor::Green,
-        asset_server.load("textures/passengers/green.png"),
-    );
-    passenger_icons.insert(
-        PassengerColor::Yellow,
-        asset_server.load("textures/passengers/yellow.png"),
-    );
-    passenger_icons.insert(
-        PassengerColor::Purple,
-        asset_server.load("textures/passengers/purple.png"),
-    );
-    passenger_icons.insert(
-        PassengerColor::Orange,
-        asset_server.load("textures/passengers/orange.png"),
-    );
+    let segment_icons = theme.segment_icons(&asset_server);
+    let passenger_icons = theme.passenger_icons(&asset_server);
 
     // 尝试加载UI纹理，如果不存在会加载失败但不会崩溃
     let button_texture = asset_server.load("ui/button.png");
@@ -288,8 +328,12 @@ fn load_ui_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
 
     info!("UI纹理加载完成（如果文件不存在会显示错误但不影响游戏运行）");
 
+    let default_font = asset_server.load(DEFAULT_FONT_PATH);
     commands.insert_resource(UIAssets {
-        font: asset_server.load("fonts/quan.ttf"),
+        font: default_font.clone(),
+        language_fonts: HashMap::from([(DEFAULT_FONT_PATH, default_font.clone())]),
+        default_font,
+        dyslexic_font: None,
         button_texture,
         panel_texture,
         progress_bar_bg,
@@ -301,13 +345,13 @@ fn load_ui_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
 
 fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(AudioAssets {
-        background_music: asset_server.load("audio/background_music.ogg"),
         segment_place_sound: asset_server.load("audio/place_segment.ogg"),
         segment_remove_sound: asset_server.load("audio/remove_segment.ogg"),
         passenger_arrive_sound: asset_server.load("audio/passenger_arrive.ogg"),
         objective_complete_sound: asset_server.load("audio/objective_complete.ogg"),
         level_complete_sound: asset_server.load("audio/level_complete.ogg"),
         button_click_sound: asset_server.load("audio/button_click.ogg"),
+        button_hover_sound: asset_server.load("audio/sound_effects/button_hover.ogg"),
         error_sound: asset_server.load("audio/error.ogg"),
     });
 }
@@ -318,6 +362,7 @@ fn setup_main_menu(
     mut commands: Commands,
     ui_assets: Res<UIAssets>,
     current_language: Res<CurrentLanguage>,
+    pending_autosave: Res<PendingAutosave>,
 ) {
     commands
         .spawn((
@@ -350,6 +395,41 @@ fn setup_main_menu(
                 localized_title,
             ));
 
+            // 继续按钮：有中途退出的关卡进度时显示
+            if pending_autosave.0.is_some() {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Px(200.0),
+                            height: Px(60.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::all(Px(10.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.2, 0.6, 0.2)),
+                        ButtonComponent {
+                            button_type: ButtonType::ContinueGame,
+                            is_hovered: false,
+                            is_pressed: false,
+                        },
+                    ))
+                    .with_children(|parent| {
+                        let (localized_continue, continue_text) = localized_text(&CONTINUE_GAME);
+                        parent.spawn((
+                            continue_text,
+                            TextFont {
+                                font: ui_assets.font.clone(),
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            localized_continue,
+                        ));
+                    });
+            }
+
             // 开始游戏按钮
             parent
                 .spawn((
@@ -383,6 +463,165 @@ fn setup_main_menu(
                     ));
                 });
 
+            // 关卡选择按钮
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Px(200.0),
+                        height: Px(60.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    ButtonComponent {
+                        button_type: ButtonType::OpenLevelSelect,
+                        is_hovered: false,
+                        is_pressed: false,
+                    },
+                ))
+                .with_children(|parent| {
+                    let (localized_select, select_text) = localized_text(&LEVEL_SELECT);
+                    parent.spawn((
+                        select_text,
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        localized_select,
+                    ));
+                });
+
+            // 每周挑战按钮（本周有未挑战关卡时显示角标）
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Px(200.0),
+                        height: Px(60.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    ButtonComponent {
+                        button_type: ButtonType::OpenWeeklyChallenges,
+                        is_hovered: false,
+                        is_pressed: false,
+                    },
+                ))
+                .with_children(|parent| {
+                    let (localized_weekly, weekly_text) = localized_text(&WEEKLY_CHALLENGES);
+                    parent.spawn((
+                        weekly_text,
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        localized_weekly,
+                    ));
+
+                    parent
+                        .spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                top: Px(-8.0),
+                                right: Px(-8.0),
+                                padding: UiRect::axes(Px(6.0), Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.9, 0.2, 0.2)),
+                            Visibility::Hidden,
+                            WeeklyChallengeBadge,
+                        ))
+                        .with_children(|parent| {
+                            let (localized_badge, badge_text) = localized_text(&NEW_BADGE);
+                            parent.spawn((
+                                badge_text,
+                                TextFont {
+                                    font: ui_assets.font.clone(),
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                                localized_badge,
+                            ));
+                        });
+                });
+
+            // 导入城市公交数据（沙盒）
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Px(200.0),
+                        height: Px(60.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    ButtonComponent {
+                        button_type: ButtonType::ImportTransitMap,
+                        is_hovered: false,
+                        is_pressed: false,
+                    },
+                ))
+                .with_children(|parent| {
+                    let (localized_import, import_text) = localized_text(&IMPORT_CITY);
+                    parent.spawn((
+                        import_text,
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        localized_import,
+                    ));
+                });
+
+            // 设置按钮
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Px(200.0),
+                        height: Px(60.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::all(Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    ButtonComponent {
+                        button_type: ButtonType::OpenSettings,
+                        is_hovered: false,
+                        is_pressed: false,
+                    },
+                ))
+                .with_children(|parent| {
+                    let (localized_settings, settings_text) = localized_text(&SETTINGS);
+                    parent.spawn((
+                        settings_text,
+                        TextFont {
+                            font: ui_assets.font.clone(),
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        localized_settings,
+                    ));
+                });
+
             // 退出游戏按钮
             parent
                 .spawn((
@@ -436,14 +675,9 @@ fn setup_main_menu(
                     },
                 ))
                 .with_children(|parent| {
-                    // 显示当前语言的另一种语言（切换目标）
-                    let next_language = match current_language.language {
-                        Language::English => "中文",
-                        Language::Chinese => "English",
-                    };
-
+                    // 显示切换后的语言
                     parent.spawn((
-                        Text::new(next_language),
+                        Text::new(current_language.language.next().display_name()),
                         TextFont {
                             font: ui_assets.font.clone(),
                             font_size: 16.0,
@@ -630,6 +864,7 @@ fn setup_gameplay_ui(
             BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.7)),
             ZIndex(50),
             GameplayUI,
+            InventoryPanel,
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -946,6 +1181,42 @@ fn setup_gameplay_ui(
         .with_children(|parent| {
             create_localized_tips_panel(parent, &ui_assets, &tips_manager, &current_language);
         });
+
+    // 提示历史按钮（位于弹出提示的右侧）
+    commands
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Px(10.0),
+                top: Px(450.0),
+                width: Px(40.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ZIndex(200),
+            GameplayUI,
+            ButtonComponent {
+                button_type: ButtonType::ToggleTipHistory,
+                is_hovered: false,
+                is_pressed: false,
+            },
+            Name::new("Tip History Button"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("!"),
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.3)),
+            ));
+        });
 }
 
 fn setup_pause_menu(
@@ -974,7 +1245,7 @@ fn setup_pause_menu(
                 .spawn((
                     Node {
                         width: Px(300.0),
-                        height: Px(400.0),
+                        height: Px(530.0),
                         flex_direction: FlexDirection::Column,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
@@ -1008,6 +1279,20 @@ fn setup_pause_menu(
                         ButtonType::RestartLevel,
                         Color::srgb(0.6, 0.6, 0.2),
                     );
+                    spawn_pause_menu_button(
+                        parent,
+                        &ui_assets,
+                        &get_text(&SETTINGS, current_language.language),
+                        ButtonType::OpenSettings,
+                        Color::srgb(0.3, 0.3, 0.5),
+                    );
+                    spawn_pause_menu_button(
+                        parent,
+                        &ui_assets,
+                        &get_text(&EXPORT_LEVEL, current_language.language),
+                        ButtonType::ExportLevelFile,
+                        Color::srgb(0.2, 0.45, 0.6),
+                    );
                     spawn_pause_menu_button(
                         parent,
                         &ui_assets,
@@ -1020,11 +1305,24 @@ fn setup_pause_menu(
 }
 
 // 新增：更新乘客统计UI的系统
+// 只在乘客状态变化、乘客生成或移除、界面重建或切换语言时重新统计
 fn update_passenger_stats_ui(
     passengers: Query<&PathfindingAgent>,
+    changed_passengers: Query<(), Changed<PathfindingAgent>>,
+    mut removed_passengers: RemovedComponents<PathfindingAgent>,
+    added_texts: Query<(), Added<PassengerColorCountText>>,
     mut passenger_count_texts: Query<(&PassengerColorCountText, &mut Text)>,
     current_language: Res<CurrentLanguage>,
 ) {
+    let passengers_removed = removed_passengers.read().count() > 0;
+    if changed_passengers.is_empty()
+        && !passengers_removed
+        && added_texts.is_empty()
+        && !current_language.is_changed()
+    {
+        return;
+    }
+
     // 统计每种颜色的乘客状态
     let mut waiting_counts = HashMap::new();
     let mut arrived_counts = HashMap::new();
@@ -1051,17 +1349,17 @@ fn update_passenger_stats_ui(
 
         // 根据文本内容判断是等待还是到达的计数器
         if text.0.starts_with("等待:") || text.0.starts_with("Waiting:") {
-            *text = Text::new(get_text_with_args(
+            text.set_if_neq(Text::new(get_text_with_args(
                 &WAITING,
                 current_language.language,
                 &[waiting_count.to_string().as_str()],
-            ));
+            )));
         } else if text.0.starts_with("到达:") || text.0.starts_with("Arrived:") {
-            *text = Text::new(get_text_with_args(
+            text.set_if_neq(Text::new(get_text_with_args(
                 &ARRIVED,
                 current_language.language,
                 &[arrived_count.to_string().as_str()],
-            ));
+            )));
         }
     }
 }
@@ -1074,7 +1372,7 @@ fn get_objective_passenger_colors(objective: &ObjectiveCondition) -> Option<Vec<
             // 这里返回None，表示不显示特定颜色图标
             None
         }
-        // 可以扩展其他目标类型的颜色识别
+        ObjectiveType::DeliverColor(color, _) => Some(vec![*color]),
         _ => None,
     }
 }
@@ -1091,8 +1389,14 @@ fn get_level_passenger_colors(level_data: &LevelData) -> Vec<PassengerColor> {
 }
 fn capture_level_complete_data(
     mut level_completed_events: EventReader<LevelCompletedEvent>,
+    mut score_changed_events: EventReader<ScoreChangedEvent>,
     mut level_complete_data: ResMut<LevelCompleteData>,
 ) {
+    // 结算界面展示最后一次分数变化的明细
+    if let Some(event) = score_changed_events.read().last() {
+        level_complete_data.score_breakdown = event.breakdown.clone();
+    }
+
     for event in level_completed_events.read() {
         level_complete_data.final_score = event.final_score;
         level_complete_data.completion_time = event.completion_time;
@@ -1223,35 +1527,6 @@ fn spawn_pause_menu_button(
         });
 }
 
-// ============ 清理系统 ============
-
-fn cleanup_main_menu(mut commands: Commands, ui_query: Query<Entity, With<MainMenuUI>>) {
-    for entity in ui_query.iter() {
-        commands.entity(entity).despawn();
-    }
-}
-
-fn cleanup_gameplay_ui(mut commands: Commands, ui_query: Query<Entity, With<GameplayUI>>) {
-    for entity in ui_query.iter() {
-        commands.entity(entity).despawn();
-    }
-}
-
-fn cleanup_pause_menu(mut commands: Commands, ui_query: Query<Entity, With<PauseMenuUI>>) {
-    for entity in ui_query.iter() {
-        commands.entity(entity).despawn();
-    }
-}
-
-fn cleanup_level_complete_ui(
-    mut commands: Commands,
-    ui_query: Query<Entity, With<LevelCompleteUI>>,
-) {
-    for entity in ui_query.iter() {
-        commands.entity(entity).despawn();
-    }
-}
-
 // ============ 交互处理系统 ============
 
 fn handle_button_interactions(
@@ -1266,6 +1541,7 @@ fn handle_button_interactions(
     >,
     audio_assets: Res<AudioAssets>,
     audio_settings: Res<AudioSettings>,
+    game_state: Res<GameState>,
     mut commands: Commands,
 ) {
     for (interaction, mut button_component, mut bg_color, mut image_node) in button_query.iter_mut()
@@ -1274,6 +1550,14 @@ fn handle_button_interactions(
             Interaction::Pressed => {
                 button_component.is_pressed = true;
 
+                // 库存为空的槽位不可选，播放区别于点击的提示音
+                let is_disabled = match &button_component.button_type {
+                    ButtonType::InventorySlot(segment_type) => {
+                        game_state.player_inventory.get(segment_type).copied() == Some(0)
+                    }
+                    _ => false,
+                };
+
                 // 根据按钮类型（背景色或纹理）应用交互效果
                 if let Some(ref mut color) = bg_color {
                     **color = Color::srgb(0.1, 0.1, 0.1).into();
@@ -1282,19 +1566,29 @@ fn handle_button_interactions(
                 }
 
                 if !audio_settings.is_muted {
+                    let (sound, gain) = if is_disabled {
+                        (
+                            audio_assets.error_sound.clone(),
+                            audio_settings.disabled_volume * 0.5,
+                        )
+                    } else {
+                        (audio_assets.button_click_sound.clone(), 1.0)
+                    };
                     commands.spawn((
-                        AudioPlayer::new(audio_assets.button_click_sound.clone()),
-                        PlaybackSettings {
-                            mode: PlaybackMode::Despawn,
-                            volume: Volume::Linear(
-                                audio_settings.sfx_volume * audio_settings.master_volume,
-                            ),
-                            ..default()
-                        },
+                        AudioPlayer::new(sound),
+                        audio_settings.sound(AudioBus::Ui, gain),
                     ));
                 }
             }
             Interaction::Hovered => {
+                // 只在指针刚移入时播放，按下后松开不重复播放
+                if !button_component.is_hovered && !audio_settings.is_muted {
+                    commands.spawn((
+                        AudioPlayer::new(audio_assets.button_hover_sound.clone()),
+                        audio_settings.sound(AudioBus::Ui, audio_settings.hover_volume),
+                    ));
+                }
+
                 button_component.is_hovered = true;
                 button_component.is_pressed = false;
 
@@ -1316,8 +1610,11 @@ fn handle_button_interactions(
                         ButtonType::ResumeGame => Color::srgb(0.2, 0.6, 0.2),
                         ButtonType::RestartLevel => Color::srgb(0.6, 0.6, 0.2),
                         ButtonType::MainMenu => Color::srgb(0.6, 0.2, 0.2),
-                        ButtonType::StartGame => Color::srgb(0.2, 0.6, 0.2),
+                        ButtonType::StartGame | ButtonType::ContinueGame => {
+                            Color::srgb(0.2, 0.6, 0.2)
+                        }
                         ButtonType::QuitGame => Color::srgb(0.6, 0.2, 0.2),
+                        ButtonType::ConfirmDialog => Color::srgb(0.6, 0.2, 0.2),
                         ButtonType::NextLevel => Color::srgb(0.2, 0.6, 0.2),
                         ButtonType::PauseGame => Color::srgb(0.3, 0.3, 0.3),
                         _ => Color::srgb(0.3, 0.3, 0.5),
@@ -1334,29 +1631,29 @@ fn handle_button_interactions(
 fn handle_menu_buttons(
     button_query: Query<&ButtonComponent, (Changed<ButtonComponent>, With<Button>)>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
-    mut app_exit_events: EventWriter<AppExit>,
 ) {
     for button in button_query.iter() {
-        if button.is_pressed {
-            match button.button_type {
-                ButtonType::StartGame => {
-                    next_state.set(GameStateEnum::Playing);
-                }
-                ButtonType::QuitGame => {
-                    app_exit_events.write(AppExit::Success);
-                }
-                _ => {}
-            }
+        // 退出游戏由 ExitFlowPlugin 弹出确认框处理
+        if button.is_pressed && button.button_type == ButtonType::StartGame {
+            next_state.set(GameStateEnum::Playing);
         }
     }
 }
 
 fn handle_pause_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
     current_state: Res<State<GameStateEnum>>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
+    settings_menus: Query<(), With<SettingsMenuUI>>,
+    confirm_dialogs: Query<(), With<ConfirmDialogUI>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
+    // 设置菜单或确认框打开时 ESC 用于关闭它们
+    if !settings_menus.is_empty() || !confirm_dialogs.is_empty() {
+        return;
+    }
+
+    if input_map.just_pressed(&keyboard_input, InputAction::Pause) {
         match current_state.get() {
             GameStateEnum::Playing => {
                 next_state.set(GameStateEnum::Paused);
@@ -1371,6 +1668,8 @@ fn handle_pause_input(
 
 fn handle_pause_buttons(
     mut button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    mut confirm_requests: EventWriter<ConfirmRequestEvent>,
+    mut confirmed_events: EventReader<DialogConfirmedEvent>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
     mut level_manager: ResMut<LevelManager>,
 ) {
@@ -1384,17 +1683,16 @@ fn handle_pause_buttons(
                     info!("继续游戏");
                     next_state.set(GameStateEnum::Playing);
                 }
+                // 重新开始和返回主菜单会放弃当前进度，先弹出确认框
                 ButtonType::RestartLevel => {
-                    info!("重新开始关卡");
-                    next_state.set(GameStateEnum::Loading);
+                    confirm_requests.write(ConfirmRequestEvent(ConfirmAction::RestartLevel));
                 }
                 ButtonType::MainMenu => {
-                    info!("返回主菜单");
-                    next_state.set(GameStateEnum::MainMenu);
+                    confirm_requests.write(ConfirmRequestEvent(ConfirmAction::ReturnToMainMenu));
                 }
                 ButtonType::NextLevel => {
-                    level_manager.current_level_index += 1;
-                    if level_manager.current_level_index < level_manager.available_levels.len() {
+                    if level_manager.has_next_level() {
+                        level_manager.current_level_index += 1;
                         next_state.set(GameStateEnum::Loading);
                     } else {
                         next_state.set(GameStateEnum::MainMenu);
@@ -1404,23 +1702,47 @@ fn handle_pause_buttons(
             }
         }
     }
+
+    for DialogConfirmedEvent(action) in confirmed_events.read() {
+        match action {
+            ConfirmAction::RestartLevel => {
+                info!("重新开始关卡");
+                next_state.set(GameStateEnum::Loading);
+            }
+            ConfirmAction::ReturnToMainMenu => {
+                info!("返回主菜单");
+                next_state.set(GameStateEnum::MainMenu);
+            }
+            _ => {}
+        }
+    }
 }
 
 fn handle_level_complete_buttons(
     button_query: Query<&ButtonComponent, (Changed<ButtonComponent>, With<Button>)>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
     mut level_manager: ResMut<LevelManager>,
+    active_challenge: Res<ActiveChallenge>,
+    custom_level: Res<CustomLevel>,
 ) {
     for button in button_query.iter() {
         if button.is_pressed {
             info!("关卡完成界面按钮被点击: {:?}", button.button_type);
             match button.button_type {
+                ButtonType::NextLevel
+                    if active_challenge.0.is_some() || custom_level.0.is_some() =>
+                {
+                    // 每周挑战和自定义关卡没有下一关，回到主菜单
+                    next_state.set(GameStateEnum::MainMenu);
+                }
                 ButtonType::NextLevel => {
                     // 解锁下一关
                     let next_level_index = level_manager.current_level_index + 1;
-                    if next_level_index < level_manager.available_levels.len() {
-                        // 确保下一关被解锁
-                        if next_level_index < level_manager.unlocked_levels.len() {
+                    if level_manager.has_next_level() {
+                        // 确保下一关被解锁（奖励关卡由星星总数决定）
+                        if level_manager.required_stars(next_level_index).is_none()
+                            && next_level_index < level_manager.unlocked_levels.len()
+                        {
                             level_manager.unlocked_levels[next_level_index] = true;
                             info!(
                                 "解锁关卡: {} ({})",
@@ -1464,11 +1786,17 @@ fn update_ui_animations(
     mut commands: Commands,
     mut animated_ui_query: Query<(Entity, &mut AnimatedUI, &mut Transform), With<AnimatedUI>>,
     time: Res<Time>,
+    game_options: Res<GameOptions>,
 ) {
     let dt = time.delta_secs();
 
     for (entity, mut animation, mut transform) in animated_ui_query.iter_mut() {
-        animation.elapsed += dt;
+        // 减少动态效果：跳过过程，直接进入最终状态
+        if game_options.allows_motion() {
+            animation.elapsed += dt;
+        } else {
+            animation.elapsed = animation.duration;
+        }
         let progress = (animation.elapsed / animation.duration).clamp(0.0, 1.0);
 
         match animation.animation_type {
@@ -1535,16 +1863,8 @@ fn update_progress_bars(
             }
             ProgressBarType::BudgetUsed => {
                 if let Some(level_data) = &game_state.current_level {
-                    if let Some(budget_limit) =
-                        level_data
-                            .objectives
-                            .iter()
-                            .find_map(|obj| match &obj.condition_type {
-                                ObjectiveType::MaxCost(limit) => Some(*limit as f32),
-                                _ => None,
-                            })
-                    {
-                        (game_state.total_cost as f32 / budget_limit).clamp(0.0, 1.0)
+                    if let Some(budget_limit) = level_data.max_cost() {
+                        (game_state.total_cost as f32 / budget_limit as f32).clamp(0.0, 1.0)
                     } else {
                         0.0
                     }
@@ -1561,125 +1881,194 @@ fn update_progress_bars(
 
 // ============ 音频系统 ============
 
+/// 到达音效的合并窗口：窗口内到达的乘客只播放一次音效
+const ARRIVAL_SOUND_WINDOW_SECS: f32 = 0.25;
+
+/// 合并后的音效最多放大到的音量倍数
+const SOUND_BATCH_MAX_BOOST: f32 = 1.5;
+
+/// 同时播放的场景音效上限，超出时丢弃新的放置、拆除和到达音效，避免繁忙时爆音
+const MAX_CONCURRENT_WORLD_SOUNDS: usize = 8;
+
+/// 画面边缘处的音量倍率，以及画面外衰减到的最低倍率
+const SPATIAL_EDGE_VOLUME: f32 = 0.7;
+const SPATIAL_MIN_VOLUME: f32 = 0.2;
+
+/// 一帧（或一个合并窗口）内同一音效的多次触发，合并成一次播放
+#[derive(Default)]
+struct SoundBatch {
+    count: u32,
+    position_sum: Vec3,
+}
+
+impl SoundBatch {
+    fn add(&mut self, position: Vec3) {
+        self.count += 1;
+        self.position_sum += position;
+    }
+
+    /// 次数越多音量略大，但不超过上限
+    fn boost(&self) -> f32 {
+        (1.0 + 0.1 * self.count.saturating_sub(1) as f32).min(SOUND_BATCH_MAX_BOOST)
+    }
+
+    /// 各次触发位置的平均值，作为合并后音效的发声位置
+    fn center(&self) -> Vec3 {
+        self.position_sum / self.count.max(1) as f32
+    }
+}
+
+/// 尚未播放的到达音效，大量乘客同时到达时合并成一次播放
+#[derive(Default)]
+struct ArrivalSoundBatch {
+    batch: SoundBatch,
+    cooldown: f32,
+}
+
+impl ArrivalSoundBatch {
+    /// 合并窗口结束时返回这一批到达
+    fn flush(&mut self, dt: f32) -> Option<SoundBatch> {
+        self.cooldown = (self.cooldown - dt).max(0.0);
+        if self.batch.count == 0 || self.cooldown > 0.0 {
+            return None;
+        }
+        self.cooldown = ARRIVAL_SOUND_WINDOW_SECS;
+        Some(std::mem::take(&mut self.batch))
+    }
+}
+
+/// 按发声位置与镜头的距离衰减音量：画面中心最响，画面边缘为 SPATIAL_EDGE_VOLUME，
+/// 画面外继续减弱，离开一个画面宽度后降到 SPATIAL_MIN_VOLUME
+fn spatial_attenuation(camera: Option<(&Camera, &GlobalTransform)>, position: Vec3) -> f32 {
+    let Some(ndc) = camera.and_then(|(camera, transform)| camera.world_to_ndc(transform, position))
+    else {
+        return 1.0;
+    };
+    let distance = ndc.truncate().length(); // 1.0 为画面边缘
+    if distance <= 1.0 {
+        1.0 - (1.0 - SPATIAL_EDGE_VOLUME) * distance
+    } else {
+        let t = ((distance - 1.0) / 2.0).min(1.0);
+        SPATIAL_EDGE_VOLUME + (SPATIAL_MIN_VOLUME - SPATIAL_EDGE_VOLUME) * t
+    }
+}
+
 fn handle_audio_events(
     mut commands: Commands,
+    time: Res<Time>,
+    mut arrival_batch: Local<ArrivalSoundBatch>,
     audio_assets: Res<AudioAssets>,
     audio_settings: Res<AudioSettings>,
+    game_state: Res<GameState>,
+    level_manager: Res<LevelManager>,
     mut segment_placed_events: EventReader<SegmentPlacedEvent>,
     mut segment_removed_events: EventReader<SegmentRemovedEvent>,
     mut objective_completed_events: EventReader<ObjectiveCompletedEvent>,
     mut level_completed_events: EventReader<LevelCompletedEvent>,
-    passengers: Query<&PathfindingAgent, Changed<PathfindingAgent>>,
+    passengers: Query<(&PathfindingAgent, &GlobalTransform), Changed<PathfindingAgent>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    playing_sounds: Query<&AudioBus, With<AudioSink>>,
 ) {
     if audio_settings.is_muted {
         return;
     }
 
-    let base_volume = audio_settings.sfx_volume * audio_settings.master_volume;
-
-    // 路线段放置音效
-    for _event in segment_placed_events.read() {
-        commands.spawn((
-            AudioPlayer::new(audio_assets.segment_place_sound.clone()),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                volume: Volume::Linear(base_volume),
-                ..default()
-            },
-        ));
+    let tile_world_pos = |pos: GridPos| {
+        let (width, height) = game_state
+            .current_level
+            .as_ref()
+            .map_or((0, 0), |level_data| level_data.grid_size);
+        pos.to_world_pos(level_manager.tile_size, width, height)
+    };
+    let camera = cameras.iter().next();
+    let mut world_sounds = playing_sounds
+        .iter()
+        .filter(|bus| **bus == AudioBus::World)
+        .count();
+
+    // 放置、拆除和到达音效按位置衰减，同一帧内的多次触发合并为一次
+    let mut placed = SoundBatch::default();
+    for event in segment_placed_events.read() {
+        placed.add(tile_world_pos(event.position));
     }
-
-    // 路线段移除音效
+    let mut removed = SoundBatch::default();
     for event in segment_removed_events.read() {
-        info!("segment removed at: {:?}", event.position);
+        removed.add(tile_world_pos(event.position));
+    }
+    for (agent, transform) in passengers.iter() {
+        if matches!(agent.state, AgentState::Arrived) {
+            arrival_batch.batch.add(transform.translation());
+        }
+    }
+    let arrived = arrival_batch.flush(time.delta_secs());
+
+    for (batch, sound, gain) in [
+        (Some(placed), &audio_assets.segment_place_sound, 1.0),
+        (Some(removed), &audio_assets.segment_remove_sound, 1.0),
+        (arrived, &audio_assets.passenger_arrive_sound, 0.8),
+    ] {
+        let Some(batch) = batch.filter(|batch| batch.count > 0) else {
+            continue;
+        };
+        if world_sounds >= MAX_CONCURRENT_WORLD_SOUNDS {
+            continue;
+        }
+        world_sounds += 1;
+        let attenuation = spatial_attenuation(camera, batch.center());
         commands.spawn((
-            AudioPlayer::new(audio_assets.segment_remove_sound.clone()),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                volume: Volume::Linear(base_volume),
-                ..default()
-            },
+            AudioPlayer::new(sound.clone()),
+            audio_settings.sound(AudioBus::World, gain * batch.boost() * attenuation),
         ));
     }
 
-    // 目标完成音效
-    for _event in objective_completed_events.read() {
+    // 目标完成和关卡完成是全局提示，不衰减也不受数量上限限制
+    if objective_completed_events.read().count() > 0 {
         commands.spawn((
             AudioPlayer::new(audio_assets.objective_complete_sound.clone()),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                volume: Volume::Linear(base_volume * 1.2),
-                ..default()
-            },
+            audio_settings.sound(AudioBus::World, 1.2),
         ));
     }
-
-    // 关卡完成音效
-    for _event in level_completed_events.read() {
+    if level_completed_events.read().count() > 0 {
         commands.spawn((
             AudioPlayer::new(audio_assets.level_complete_sound.clone()),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                volume: Volume::Linear(base_volume * 1.5),
-                ..default()
-            },
+            audio_settings.sound(AudioBus::World, 1.5),
         ));
     }
+}
 
-    // 乘客到达音效
-    for agent in passengers.iter() {
-        if matches!(agent.state, AgentState::Arrived) {
-            commands.spawn((
-                AudioPlayer::new(audio_assets.passenger_arrive_sound.clone()),
-                PlaybackSettings {
-                    mode: PlaybackMode::Despawn,
-                    volume: Volume::Linear(base_volume * 0.8),
-                    ..default()
-                },
-            ));
-        }
-    }
+fn duck_music_on_game_over(mut ducking: ResMut<MusicDucking>) {
+    ducking.duck();
 }
 
-fn update_background_music(
-    mut commands: Commands,
-    audio_assets: Res<AudioAssets>,
-    audio_settings: Res<AudioSettings>,
-    current_state: Res<State<GameStateEnum>>,
-    background_music_query: Query<Entity, (With<AudioSink>, With<BackgroundMusic>)>,
+/// 提示音响起时快速压低音乐，保持一段时间后再缓慢恢复
+fn update_music_ducking(
+    time: Res<Time>,
+    mut ducking: ResMut<MusicDucking>,
+    mut objective_completed_events: EventReader<ObjectiveCompletedEvent>,
+    mut level_completed_events: EventReader<LevelCompletedEvent>,
 ) {
-    if audio_settings.is_muted {
-        return;
+    if objective_completed_events.read().count() > 0 || level_completed_events.read().count() > 0 {
+        ducking.duck();
     }
 
-    // 只在游戏进行状态播放背景音乐
-    match current_state.get() {
-        GameStateEnum::Playing => {
-            // 如果没有背景音乐在播放，则开始播放
-            if background_music_query.is_empty() {
-                commands.spawn((
-                    AudioPlayer::new(audio_assets.background_music.clone()),
-                    PlaybackSettings {
-                        mode: PlaybackMode::Loop,
-                        volume: Volume::Linear(
-                            audio_settings.music_volume * audio_settings.master_volume,
-                        ),
-                        ..default()
-                    },
-                    BackgroundMusic, // 标记为背景音乐
-                ));
-            }
-        }
-        GameStateEnum::LevelComplete | GameStateEnum::GameOver => {
-            // 在胜利/失败状态下停止背景音乐
-            for entity in background_music_query.iter() {
-                commands.entity(entity).despawn();
-            }
-        }
-        _ => {
-            // 其他状态下保持背景音乐播放
-        }
+    let dt = time.delta_secs();
+    let target = if ducking.hold > 0.0 { DUCK_LEVEL } else { 1.0 };
+    if ducking.factor == target && ducking.hold <= 0.0 {
+        return;
     }
+
+    ducking.hold = (ducking.hold - dt).max(0.0);
+    let rate = if target < ducking.factor {
+        (1.0 - DUCK_LEVEL) / DUCK_ATTACK_SECS
+    } else {
+        (1.0 - DUCK_LEVEL) / DUCK_RELEASE_SECS
+    };
+    let step = rate * dt;
+    ducking.factor = if (target - ducking.factor).abs() <= step {
+        target
+    } else {
+        ducking.factor + step * (target - ducking.factor).signum()
+    };
 }
 
 fn setup_game_over_ui(
@@ -1708,7 +2097,7 @@ fn setup_game_over_ui(
                 .spawn((
                     Node {
                         width: Px(450.0),
-                        height: Px(550.0),
+                        min_height: Px(550.0),
                         flex_direction: FlexDirection::Column,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
@@ -1833,6 +2222,14 @@ fn setup_game_over_ui(
                         &get_text(&RETRY, current_language.language),
                         ButtonType::RestartLevel,
                     );
+                    if game_state.segment_count() > 0 {
+                        spawn_menu_button(
+                            parent,
+                            &ui_assets,
+                            &get_text(&RETRY_KEEP_NETWORK, current_language.language),
+                            ButtonType::RetryKeepingNetwork,
+                        );
+                    }
                     spawn_menu_button(
                         parent,
                         &ui_assets,
@@ -1859,12 +2256,6 @@ fn setup_game_over_ui(
     });
 }
 
-fn cleanup_game_over_ui(mut commands: Commands, ui_query: Query<Entity, With<GameOverUI>>) {
-    for entity in ui_query.iter() {
-        commands.entity(entity).despawn();
-    }
-}
-
 fn handle_game_over_buttons(
     button_query: Query<&ButtonComponent, (Changed<ButtonComponent>, With<Button>)>,
     mut next_state: ResMut<NextState<GameStateEnum>>,
@@ -1896,6 +2287,8 @@ fn get_failure_tip(reason: &str, language: Language) -> String {
         "TIP_TIME_LIMIT"
     } else if reason.contains("预算超支") || reason.contains("budget") {
         "TIP_BUDGET_EXCEEDED"
+    } else if reason.contains("破产") || reason.contains("bankrupt") {
+        "TIP_BANKRUPT"
     } else {
         "TIP_GENERAL"
     };
@@ -1904,22 +2297,27 @@ fn get_failure_tip(reason: &str, language: Language) -> String {
     match (tip_key, language) {
         ("TIP_PASSENGERS_GAVE_UP", Language::Chinese) =>
             "💡 提示：尝试建设更短的路径，或者增加换乘站点来减少等待时间".to_string(),
-        ("TIP_PASSENGERS_GAVE_UP", Language::English) =>
+        ("TIP_PASSENGERS_GAVE_UP", _) =>
             "💡 Tip: Try building shorter paths or adding transfer stations to reduce waiting time".to_string(),
 
         ("TIP_TIME_LIMIT", Language::Chinese) =>
             "💡 提示：优先连接最重要的站点，不要追求完美的网络设计".to_string(),
-        ("TIP_TIME_LIMIT", Language::English) =>
+        ("TIP_TIME_LIMIT", _) =>
             "💡 Tip: Focus on connecting the most important stations, don't aim for perfect network design".to_string(),
 
         ("TIP_BUDGET_EXCEEDED", Language::Chinese) =>
             "💡 提示：多使用便宜的直线段，减少昂贵的复杂路段".to_string(),
-        ("TIP_BUDGET_EXCEEDED", Language::English) =>
+        ("TIP_BUDGET_EXCEEDED", _) =>
             "💡 Tip: Use more cheap straight segments and reduce expensive complex segments".to_string(),
 
+        ("TIP_BANKRUPT", Language::Chinese) =>
+            "💡 提示：先用少量路线段连通客流最大的站点，用车费收入再扩建".to_string(),
+        ("TIP_BANKRUPT", _) =>
+            "💡 Tip: Connect the busiest stations with a few segments first, then expand using fare income".to_string(),
+
         (_, Language::Chinese) =>
             "💡 提示：分析失败原因，调整策略后重新挑战".to_string(),
-        (_, Language::English) =>
+        _ =>
             "💡 Tip: Analyze the failure reason and adjust your strategy before retrying".to_string(),
     }
 }
@@ -1978,7 +2376,7 @@ fn setup_level_complete_ui(
                 .spawn((
                     Node {
                         width: Px(400.0),
-                        height: Px(500.0),
+                        height: Px(640.0),
                         flex_direction: FlexDirection::Column,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
@@ -1991,8 +2389,7 @@ fn setup_level_complete_ui(
                 ))
                 .with_children(|parent| {
                     // 检查是否是最后一关
-                    let is_final_level = level_manager.current_level_index + 1
-                        >= level_manager.available_levels.len();
+                    let is_final_level = !level_manager.has_next_level();
 
                     // 标题
                     let title_key = if is_final_level {
@@ -2023,12 +2420,15 @@ fn setup_level_complete_ui(
                     );
 
                     // 分数详细分解
-                    let score = &game_state.score;
+                    let score = &level_complete_data.score_breakdown;
                     let breakdown_args = vec![
                         score.base_points.to_string(),
                         score.efficiency_bonus.to_string(),
                         score.speed_bonus.to_string(),
                         score.cost_bonus.to_string(),
+                        score.combo_bonus.to_string(),
+                        score.contract_bonus.to_string(),
+                        format!("{:.2}", score.multiplier),
                     ];
                     spawn_localized_score_text_with_args(
                         parent,
@@ -2082,6 +2482,18 @@ fn setup_level_complete_ui(
                         &RETRY,
                         ButtonType::RestartLevel,
                     );
+                    spawn_localized_menu_button(
+                        parent,
+                        &ui_assets,
+                        &LEVEL_STATISTICS,
+                        ButtonType::OpenLevelStats,
+                    );
+                    spawn_localized_menu_button(
+                        parent,
+                        &ui_assets,
+                        &EXPORT_RESULTS,
+                        ButtonType::ExportResultsCsv,
+                    );
                     spawn_localized_menu_button(
                         parent,
                         &ui_assets,
@@ -2113,9 +2525,26 @@ pub struct LanguageToggleText;
 
 // ============ 动态文本更新系统 ============
 
+/// 只在显示的参数变化（时间跨过整秒、成本或人数变化）或切换语言时改写 HUD 文本，
+/// 避免每帧重写 `Text` 触发重新排版
+pub fn set_hud_text_args(
+    localized: &mut LocalizedTextComponent,
+    text: &mut Mut<Text>,
+    args: Vec<String>,
+    language: Language,
+    force: bool,
+) {
+    if !force && localized.format_args.as_ref() == Some(&args) {
+        return;
+    }
+    localized.format_args = Some(args);
+    text.set_if_neq(Text::new(localized.get_text(language)));
+}
+
 fn update_gameplay_ui_values(
     game_state: Res<GameState>,
     current_language: Res<CurrentLanguage>,
+    mut score_changed_events: EventReader<ScoreChangedEvent>,
     mut score_text: Query<
         (&mut LocalizedTextComponent, &mut Text),
         (
@@ -2153,40 +2582,84 @@ fn update_gameplay_ui_values(
         ),
     >,
 ) {
-    // 更新分数文本
-    if let Ok((mut localized, mut text)) = score_text.single_mut() {
-        let score = &game_state.score;
-        localized.format_args = Some(vec![
-            score.total_score.to_string(),
-            score.base_points.to_string(),
-            score.efficiency_bonus.to_string(),
-            score.speed_bonus.to_string(),
-            score.cost_bonus.to_string(),
-        ]);
-        *text = Text::new(localized.get_text(current_language.language));
+    let language = current_language.language;
+    let language_changed = current_language.is_changed();
+
+    // 分数只在变化时更新
+    if let Some(event) = score_changed_events.read().last()
+        && let Ok((mut localized, mut text)) = score_text.single_mut()
+    {
+        let score = &event.breakdown;
+        set_hud_text_args(
+            &mut localized,
+            &mut text,
+            vec![score.total_score.to_string()],
+            language,
+            language_changed,
+        );
     }
 
-    // 更新时间文本
+    // 更新时间文本（按整秒显示）
     if let Ok((mut localized, mut text)) = timer_text.single_mut() {
-        localized.format_args = Some(vec![format_time(game_state.game_time)]);
-        *text = Text::new(localized.get_text(current_language.language));
+        set_hud_text_args(
+            &mut localized,
+            &mut text,
+            vec![format_time(game_state.game_time)],
+            language,
+            language_changed,
+        );
     }
 
     // 更新成本文本
     if let Ok((mut localized, mut text)) = cost_text.single_mut() {
-        localized.format_args = Some(vec![game_state.total_cost.to_string()]);
-        *text = Text::new(localized.get_text(current_language.language));
+        set_hud_text_args(
+            &mut localized,
+            &mut text,
+            vec![game_state.total_cost.to_string()],
+            language,
+            language_changed,
+        );
     }
 
     // 更新乘客文本
     if let Ok((mut localized, mut text)) = passenger_text.single_mut() {
         let arrived_passengers = game_state.passenger_stats.total_arrived;
         let total_passengers = game_state.passenger_stats.total_spawned;
-        localized.format_args = Some(vec![
-            arrived_passengers.to_string(),
-            total_passengers.to_string(),
-        ]);
-        *text = Text::new(localized.get_text(current_language.language));
+        set_hud_text_args(
+            &mut localized,
+            &mut text,
+            vec![arrived_passengers.to_string(), total_passengers.to_string()],
+            language,
+            language_changed,
+        );
+    }
+}
+
+/// 每次扣费时成本文字闪烁橙红色，渐变回白色
+fn flash_cost_text_on_spend(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mut last_cost: Local<u32>,
+    mut flash_remaining: Local<f32>,
+    mut cost_text: Query<&mut TextColor, With<CostText>>,
+) {
+    const FLASH_SECS: f32 = 0.6;
+    const FLASH_COLOR: Color = Color::srgb(1.0, 0.45, 0.3);
+
+    // 拆除退款和重新开始会让成本下降，不闪烁
+    if game_state.total_cost > *last_cost {
+        *flash_remaining = FLASH_SECS;
+    }
+    *last_cost = game_state.total_cost;
+
+    if *flash_remaining <= 0.0 {
+        return;
+    }
+    *flash_remaining = (*flash_remaining - time.delta_secs()).max(0.0);
+
+    let t = *flash_remaining / FLASH_SECS;
+    for mut color in cost_text.iter_mut() {
+        color.0 = Color::WHITE.mix(&FLASH_COLOR, t);
     }
 }
 
@@ -2298,14 +2771,96 @@ fn spawn_localized_menu_button(
 #[derive(Clone, Debug, PartialEq)]
 pub enum ButtonType {
     StartGame,
+    ContinueGame, // 主菜单：恢复上次中途退出的关卡
     PauseGame,
     ResumeGame,
     RestartLevel,
+    RetryKeepingNetwork, // 失败界面：重试并重建失败时的路网
     NextLevel,
     MainMenu,
     QuitGame,
-    ToggleLanguage, // 新增：语言切换按钮
+    ToggleLanguage,   // 新增：语言切换按钮
+    ToggleTipHistory, // 打开/关闭提示历史
+    OpenSettings,
+    CloseSettings,
+    VolumeDown(AudioChannel),
+    VolumeUp(AudioChannel),
+    ToggleMute,
+    TogglePauseOnFocusLoss,
+    CycleFrameLimit,
+    ToggleBatterySaver,
+    ToggleDyslexiaFont,
+    ToggleVisualAudioCues,
+    ToggleReducedMotion,
+    ToggleTextToSpeech,
+    CycleTheme,
+    CycleWindowMode,
+    ToggleVsync,
+    UiScaleDown,
+    UiScaleUp,
+    OpenKeyBindings,
+    CloseKeyBindings,
+    RebindKey(InputAction),
+    ResetKeyBindings,
+    ConfirmDialog,
+    CancelDialog,
+    OpenLevelSelect,
+    CloseLevelSelect,
+    SelectLevel(usize),
+    LevelSelectTab(LevelSelectTab),
+    PlayCustomLevelFile(usize),
+    DeleteCustomLevelFile(usize),
+    ExportLevelFile,
+    OpenWeeklyChallenges,
+    CloseWeeklyChallenges,
+    PlayChallenge(&'static str),
+    ToggleMutator(MutatorKind),
+    ToggleMirrorMode,
+    ToggleHotSeat,
+    ToggleScoreAttack,
+    ToggleEconomyMode,
+    ToggleEndlessMode,
+    ToggleContracts,
+    UpgradeStationCapacity,
+    UpgradeStationShelter,
+    UpgradeStationAccessibility,
+    CloseStationPanel,
+    ToggleRoutePanel,
+    CloseRoutePanel,
+    RouteBusesDown(usize),
+    RouteBusesUp(usize),
+    CycleRouteFrequency(usize),
+    CycleRouteLine,
+    BuyBus,
+    SetSimSpeed(usize),
+    StepSimulation,
+    ToggleNetworkLayer,
+    ExportResultsCsv,
+    OpenLevelStats,
+    CloseLevelStats,
+    ImportTransitMap,
+    SelectTerrainBrush(TerrainType),
+    CycleBrushSize,
+    StartSandboxSimulation,
+    StartLevel,
+    TouchRotate,
+    TouchDelete,
     InventorySlot(RouteSegmentType),
+    ToggleFullscreen,
+    CopySeed,
+    OpenSeedInput,
+    ConfirmSeedInput,
+    CancelSeedInput,
+    WatchReplay,
+    ReplaySlower,
+    ReplayFaster,
+    ReplayTogglePause,
+    ExitReplay,
+    StampBlueprint(usize),
+    DeleteBlueprint(usize),
+    CloseBlueprintLibrary,
+    SaveBlueprint,
+    CancelBlueprintName,
 }
 
 // ============ 库存选中状态更新系统 ============
@@ -2329,3 +2884,48 @@ fn update_inventory_selection_state(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct TimerTextRewrites(u32);
+
+    fn count_timer_text_rewrites(
+        rewritten: Query<(), (Changed<Text>, With<TimerText>)>,
+        mut rewrites: ResMut<TimerTextRewrites>,
+    ) {
+        rewrites.0 += rewritten.iter().count() as u32;
+    }
+
+    /// 每次改写 `Text` 都会触发一次文本排版，这是 HUD 每帧的主要开销。
+    /// 帧时间受机器负载影响无法稳定断言，这里以排版次数代替：
+    /// 计时器按 10 帧/秒推进 25 帧，只应在显示的整秒变化时改写 3 次
+    #[test]
+    fn hud_timer_relayouts_only_on_whole_second_changes() {
+        let mut app = App::new();
+        app.add_event::<ScoreChangedEvent>()
+            .init_resource::<GameState>()
+            .init_resource::<CurrentLanguage>()
+            .init_resource::<TimerTextRewrites>()
+            .add_systems(
+                Update,
+                (update_gameplay_ui_values, count_timer_text_rewrites).chain(),
+            );
+        app.world_mut().spawn((
+            LocalizedTextComponent::with_args(&TIME, Vec::new()),
+            Text::default(),
+            TimerText,
+        ));
+
+        const FRAMES: u32 = 25;
+        for _ in 0..FRAMES {
+            app.world_mut().resource_mut::<GameState>().game_time += 0.1;
+            app.update();
+        }
+
+        let rewrites = app.world().resource::<TimerTextRewrites>().0;
+        assert_eq!(rewrites, 3, "{} 帧中改写了 {} 次", FRAMES, rewrites);
+    }
+}
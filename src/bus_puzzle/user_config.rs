@@ -0,0 +1,296 @@
+// src/bus_puzzle/user_config.rs - 用户设置（音频、画面、游戏选项、语言和按键绑定）保存到 config.toml，网页版写入 localStorage
+
+use crate::bus_puzzle::{
+    key_config_name, parse_key_config_name,
+    window_settings::{mode_name, parse_mode},
+    AudioSettings, CurrentLanguage, FlushSavesEvent, FrameLimit, GameOptions, InputAction,
+    InputMap, Language,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+
+#[cfg(not(target_family = "wasm"))]
+const USER_CONFIG_FILE: &str = "config.toml";
+
+#[cfg(target_family = "wasm")]
+const USER_CONFIG_KEY: &str = "config";
+
+/// 界面缩放的允许范围
+pub const MIN_UI_SCALE: f32 = 0.8;
+pub const MAX_UI_SCALE: f32 = 1.5;
+
+// ============ 用户设置插件 ============
+
+pub struct UserConfigPlugin;
+
+impl Plugin for UserConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_user_config)
+            .add_systems(Update, save_user_config)
+            .add_systems(PostUpdate, flush_user_config); // 退出前保存设置
+    }
+}
+
+// ============ 读写 ============
+
+fn read_user_config() -> Option<String> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        std::fs::read_to_string(USER_CONFIG_FILE).ok()
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        crate::bus_puzzle::web_storage::read(USER_CONFIG_KEY)
+    }
+}
+
+fn write_user_config(content: &str) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(USER_CONFIG_FILE, content) {
+            warn!("保存用户设置失败: {}", e);
+        } else {
+            info!("⚙️ 用户设置已保存到 {}", USER_CONFIG_FILE);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        crate::bus_puzzle::web_storage::write(USER_CONFIG_KEY, content);
+        info!("⚙️ 用户设置已保存到 localStorage");
+    }
+}
+
+/// 启动和回到主菜单时读取保存的语言，没有设置文件或没有语言项时返回 None
+pub fn saved_language() -> Option<Language> {
+    let content = read_user_config()?;
+    parse_entries(&content)
+        .find(|(section, key, _)| *section == "gameplay" && *key == "language")
+        .and_then(|(_, _, value)| parse_language(value))
+}
+
+// ============ 加载与保存 ============
+
+fn load_user_config(
+    mut audio_settings: ResMut<AudioSettings>,
+    mut game_options: ResMut<GameOptions>,
+    mut input_map: ResMut<InputMap>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    // 没有设置文件时沿用窗口设置中记住的显示模式
+    if let Ok(window) = windows.single() {
+        game_options.window_mode = window.mode;
+    }
+
+    let Some(content) = read_user_config() else {
+        info!("未找到用户设置，使用默认设置");
+        return;
+    };
+
+    deserialize_user_config(
+        &content,
+        &mut audio_settings,
+        &mut game_options,
+        &mut input_map,
+    );
+    info!("⚙️ 已恢复用户设置");
+}
+
+fn save_user_config(
+    audio_settings: Res<AudioSettings>,
+    game_options: Res<GameOptions>,
+    current_language: Res<CurrentLanguage>,
+    input_map: Res<InputMap>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut last_saved: Local<String>,
+    mut dirty: Local<bool>,
+) {
+    if audio_settings.is_changed()
+        || game_options.is_changed()
+        || current_language.is_changed()
+        || input_map.is_changed()
+    {
+        *dirty = true;
+    }
+    // 拖动音量滑块时每帧都在变化，松开鼠标后再写盘
+    if !*dirty || mouse_buttons.pressed(MouseButton::Left) {
+        return;
+    }
+    *dirty = false;
+
+    let content = serialize_user_config(
+        &audio_settings,
+        &game_options,
+        current_language.language,
+        &input_map,
+    );
+    if last_saved.is_empty() {
+        // 启动后第一次只记录当前设置，避免没有改动也写盘
+        *last_saved = content;
+        return;
+    }
+    if content != *last_saved {
+        write_user_config(&content);
+        *last_saved = content;
+    }
+}
+
+fn flush_user_config(
+    mut flush_events: EventReader<FlushSavesEvent>,
+    audio_settings: Res<AudioSettings>,
+    game_options: Res<GameOptions>,
+    current_language: Res<CurrentLanguage>,
+    input_map: Res<InputMap>,
+) {
+    if flush_events.read().last().is_some() {
+        write_user_config(&serialize_user_config(
+            &audio_settings,
+            &game_options,
+            current_language.language,
+            &input_map,
+        ));
+    }
+}
+
+// ============ 序列化 ============
+
+/// 生成分节的 TOML 文本，玩家也可以手动编辑
+fn serialize_user_config(
+    audio: &AudioSettings,
+    options: &GameOptions,
+    language: Language,
+    input_map: &InputMap,
+) -> String {
+    let controls = InputAction::ALL.into_iter().map(|action| {
+        format!(
+            "{} = \"{}\"",
+            action.config_key(),
+            key_config_name(input_map.key(action))
+        )
+    });
+
+    [
+        "[audio]".to_string(),
+        format!("master = {}", audio.master_volume),
+        format!("music = {}", audio.music_volume),
+        format!("ui = {}", audio.ui_volume),
+        format!("world = {}", audio.world_volume),
+        format!("hover = {}", audio.hover_volume),
+        format!("disabled = {}", audio.disabled_volume),
+        format!("muted = {}", audio.is_muted),
+        String::new(),
+        "[video]".to_string(),
+        format!("window_mode = \"{}\"", mode_name(options.window_mode)),
+        format!("vsync = {}", options.vsync),
+        format!("ui_scale = {}", options.ui_scale),
+        format!("frame_limit = {}", options.frame_limit.fps().unwrap_or(0)),
+        format!("battery_saver = {}", options.battery_saver),
+        String::new(),
+        "[gameplay]".to_string(),
+        format!("language = \"{}\"", language.code()),
+        format!("pause_on_focus_loss = {}", options.pause_on_focus_loss),
+        format!("dyslexia_font = {}", options.dyslexia_font),
+        format!("visual_audio_cues = {}", options.visual_audio_cues),
+        format!("reduced_motion = {}", options.reduced_motion),
+        format!("text_to_speech = {}", options.text_to_speech),
+        String::new(),
+        "[controls]".to_string(),
+    ]
+    .into_iter()
+    .chain(controls)
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// 逐行解析，无法识别的键或值保留当前设置
+fn deserialize_user_config(
+    content: &str,
+    audio: &mut AudioSettings,
+    options: &mut GameOptions,
+    input_map: &mut InputMap,
+) {
+    for (section, key, value) in parse_entries(content) {
+        match (section, key) {
+            ("audio", "muted") => set_bool(&mut audio.is_muted, value),
+            ("audio", key) => {
+                let Ok(volume) = value.parse::<f32>() else {
+                    continue;
+                };
+                let volume = volume.clamp(0.0, 1.0);
+                match key {
+                    "master" => audio.master_volume = volume,
+                    "music" => audio.music_volume = volume,
+                    "ui" => audio.ui_volume = volume,
+                    "world" => audio.world_volume = volume,
+                    "hover" => audio.hover_volume = volume,
+                    "disabled" => audio.disabled_volume = volume,
+                    _ => {}
+                }
+            }
+            ("video", "window_mode") => {
+                if let Some(mode) = parse_mode(value) {
+                    options.window_mode = mode;
+                }
+            }
+            ("video", "vsync") => set_bool(&mut options.vsync, value),
+            ("video", "ui_scale") => {
+                if let Ok(scale) = value.parse::<f32>() {
+                    options.ui_scale = scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+                }
+            }
+            ("video", "frame_limit") => match value {
+                "30" => options.frame_limit = FrameLimit::Fps30,
+                "60" => options.frame_limit = FrameLimit::Fps60,
+                "0" => options.frame_limit = FrameLimit::Uncapped,
+                _ => {}
+            },
+            ("video", "battery_saver") => set_bool(&mut options.battery_saver, value),
+            ("gameplay", "language") => {} // 由 saved_language 在加载语言设置时读取
+            ("gameplay", "pause_on_focus_loss") => {
+                set_bool(&mut options.pause_on_focus_loss, value)
+            }
+            ("gameplay", "dyslexia_font") => set_bool(&mut options.dyslexia_font, value),
+            ("gameplay", "visual_audio_cues") => set_bool(&mut options.visual_audio_cues, value),
+            ("gameplay", "reduced_motion") => set_bool(&mut options.reduced_motion, value),
+            ("gameplay", "text_to_speech") => set_bool(&mut options.text_to_speech, value),
+            ("controls", key) => {
+                if let (Some(action), Some(key_code)) = (
+                    InputAction::from_config_key(key),
+                    parse_key_config_name(value),
+                ) {
+                    input_map.rebind(action, key_code);
+                }
+            }
+            _ => warn!("用户设置中有无法识别的键: [{}] {}", section, key),
+        }
+    }
+}
+
+/// 按行拆出 (节名, 键, 值)，去掉字符串值的引号，跳过空行和注释
+fn parse_entries(content: &str) -> impl Iterator<Item = (&str, &str, &str)> {
+    let mut section = "";
+    content.lines().filter_map(move |line| {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim();
+            return None;
+        }
+        let (key, value) = line.split_once('=')?;
+        Some((section, key.trim(), value.trim().trim_matches('"')))
+    })
+}
+
+fn set_bool(target: &mut bool, value: &str) {
+    if let Ok(value) = value.parse() {
+        *target = value;
+    }
+}
+
+fn parse_language(code: &str) -> Option<Language> {
+    Language::ALL
+        .into_iter()
+        .find(|language| language.code() == code)
+}
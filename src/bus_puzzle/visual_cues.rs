@@ -0,0 +1,118 @@
+// src/bus_puzzle/visual_cues.rs - 音效视觉提示（听障玩家可以看到提示音触发）
+
+use crate::bus_puzzle::{
+    get_text, show_contextual_tip, AgentState, CurrentLanguage, GameOptions, GameStateEnum,
+    Language, LocalizedText, ObjectiveCompletedEvent, PathfindingAgent, TipType, UIAssets,
+    CUE_ERROR, CUE_OBJECTIVE_COMPLETE, CUE_PASSENGER_ARRIVED,
+};
+use bevy::prelude::*;
+
+/// 视觉提示显示时长（秒）
+const CUE_DURATION: f32 = 1.5;
+
+/// 音效对应的弹出提示，同一时间只保留一条，避免乘客连续到达时刷屏
+#[derive(Component)]
+pub struct AudioCueIndicator;
+
+// ============ 音效视觉提示插件 ============
+
+pub struct VisualCuesPlugin;
+
+impl Plugin for VisualCuesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            show_gameplay_cues.run_if(in_state(GameStateEnum::Playing)),
+        )
+        // 失败时播放错误音
+        .add_systems(OnEnter(GameStateEnum::GameOver), show_error_cue)
+        // 提示计时只在关卡中推进，回到主菜单时清掉残留的提示
+        .add_systems(OnEnter(GameStateEnum::MainMenu), clear_cues);
+    }
+}
+
+/// 与 handle_audio_events 监听相同的事件；静音时同样显示，方便关闭声音游玩的玩家
+fn show_gameplay_cues(
+    mut commands: Commands,
+    game_options: Res<GameOptions>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    mut objective_events: EventReader<ObjectiveCompletedEvent>,
+    passengers: Query<&PathfindingAgent, Changed<PathfindingAgent>>,
+    indicators: Query<Entity, With<AudioCueIndicator>>,
+) {
+    if !game_options.visual_audio_cues {
+        objective_events.clear();
+        return;
+    }
+
+    // 同一帧有多个音效时只显示最重要的一个
+    let cue = if objective_events.read().count() > 0 {
+        Some((&CUE_OBJECTIVE_COMPLETE, TipType::LevelGoal))
+    } else if passengers
+        .iter()
+        .any(|agent| matches!(agent.state, AgentState::Arrived))
+    {
+        Some((&CUE_PASSENGER_ARRIVED, TipType::Controls))
+    } else {
+        None
+    };
+
+    if let Some((text_key, tip_type)) = cue {
+        show_cue(
+            &mut commands,
+            &ui_assets,
+            &indicators,
+            text_key,
+            tip_type,
+            current_language.language,
+        );
+    }
+}
+
+fn show_error_cue(
+    mut commands: Commands,
+    game_options: Res<GameOptions>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    indicators: Query<Entity, With<AudioCueIndicator>>,
+) {
+    if game_options.visual_audio_cues {
+        show_cue(
+            &mut commands,
+            &ui_assets,
+            &indicators,
+            &CUE_ERROR,
+            TipType::Warning,
+            current_language.language,
+        );
+    }
+}
+
+fn show_cue(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    indicators: &Query<Entity, With<AudioCueIndicator>>,
+    text_key: &LocalizedText,
+    tip_type: TipType,
+    language: Language,
+) {
+    for entity in indicators.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let entity = show_contextual_tip(
+        commands,
+        ui_assets,
+        &get_text(text_key, language),
+        tip_type,
+        CUE_DURATION,
+    );
+    commands.entity(entity).insert(AudioCueIndicator);
+}
+
+fn clear_cues(mut commands: Commands, indicators: Query<Entity, With<AudioCueIndicator>>) {
+    for entity in indicators.iter() {
+        commands.entity(entity).despawn();
+    }
+}
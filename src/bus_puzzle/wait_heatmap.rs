@@ -0,0 +1,181 @@
+// src/bus_puzzle/wait_heatmap.rs - 等待热力图（按乘客平均等待时间和拥挤程度给格子着色）
+
+use crate::bus_puzzle::{
+    get_text_with_args, show_contextual_tip, world_to_grid, AgentState, CurrentLanguage, GameState,
+    GameStateEnum, GridPos, InputAction, InputMap, LevelEntity, LevelManager, PathfindingAgent,
+    TipType, UIAssets, TERRAIN_Z, WAIT_HEATMAP_HINT,
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// 每秒根据乘客状态重新统计一次
+const WAIT_HEATMAP_REFRESH_SECS: f32 = 1.0;
+
+/// 平均等待达到该时长，或同一格子里等待的人数达到该值时显示为最热
+const HOT_WAIT_SECS: f32 = 30.0;
+const CONGESTED_PASSENGERS: f32 = 6.0;
+
+const COOL_COLOR: Color = Color::srgb(0.2, 0.85, 0.3);
+const WARM_COLOR: Color = Color::srgb(0.95, 0.8, 0.2);
+const HOT_COLOR: Color = Color::srgb(0.95, 0.2, 0.2);
+const MIN_ALPHA: f32 = 0.25;
+const MAX_ALPHA: f32 = 0.55;
+
+/// 热力图开关与刷新计时
+#[derive(Resource)]
+pub struct WaitHeatmap {
+    pub visible: bool,
+    refresh_timer: Timer,
+}
+
+impl Default for WaitHeatmap {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            refresh_timer: Timer::from_seconds(WAIT_HEATMAP_REFRESH_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// 热力图格子
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct WaitHeatmapCell;
+
+/// 一个格子上正在等待的乘客统计
+#[derive(Default)]
+struct TileWait {
+    passengers: u32,
+    total_wait: f32,
+}
+
+impl TileWait {
+    /// 0 为畅通，1 为严重拥堵：取平均等待和拥挤人数中更糟的一项
+    fn heat(&self) -> f32 {
+        let average_wait = self.total_wait / self.passengers.max(1) as f32;
+        (average_wait / HOT_WAIT_SECS)
+            .max(self.passengers as f32 / CONGESTED_PASSENGERS)
+            .clamp(0.0, 1.0)
+    }
+}
+
+// ============ 等待热力图插件 ============
+
+pub struct WaitHeatmapPlugin;
+
+impl Plugin for WaitHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaitHeatmap>().add_systems(
+            Update,
+            (toggle_wait_heatmap, update_wait_heatmap)
+                .chain()
+                .run_if(in_state(GameStateEnum::Playing)),
+        );
+    }
+}
+
+fn toggle_wait_heatmap(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut heatmap: ResMut<WaitHeatmap>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    if !input_map.just_pressed(&keyboard_input, InputAction::ToggleWaitHeatmap) {
+        return;
+    }
+
+    heatmap.visible = !heatmap.visible;
+    if heatmap.visible {
+        // 打开时立即统计一次，不用等下一个刷新周期
+        let duration = heatmap.refresh_timer.duration();
+        heatmap.refresh_timer.set_elapsed(duration);
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &WAIT_HEATMAP_HINT,
+                current_language.language,
+                &[&input_map.key_name(InputAction::ToggleWaitHeatmap)],
+            ),
+            TipType::Strategy,
+            4.0,
+        );
+    }
+    info!("🔥 等待热力图: {}", heatmap.visible);
+}
+
+/// 按格子汇总在站台等待（含换乘等待）的乘客
+fn collect_tile_waits(
+    agents: &Query<(&PathfindingAgent, &Transform)>,
+    tile_size: f32,
+    grid_size: (u32, u32),
+) -> HashMap<GridPos, TileWait> {
+    let mut tiles: HashMap<GridPos, TileWait> = HashMap::default();
+    for (agent, transform) in agents.iter() {
+        if !matches!(
+            agent.state,
+            AgentState::WaitingAtStation | AgentState::Transferring
+        ) {
+            continue;
+        }
+
+        let grid_pos = world_to_grid(transform.translation, tile_size, grid_size.0, grid_size.1);
+        let tile = tiles.entry(grid_pos).or_default();
+        tile.passengers += 1;
+        tile.total_wait += agent.waiting_time;
+    }
+    tiles
+}
+
+fn heat_color(heat: f32) -> Color {
+    let color = if heat < 0.5 {
+        COOL_COLOR.mix(&WARM_COLOR, heat * 2.0)
+    } else {
+        WARM_COLOR.mix(&HOT_COLOR, (heat - 0.5) * 2.0)
+    };
+    color.with_alpha(MIN_ALPHA + (MAX_ALPHA - MIN_ALPHA) * heat)
+}
+
+fn update_wait_heatmap(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut heatmap: ResMut<WaitHeatmap>,
+    game_state: Res<GameState>,
+    level_manager: Res<LevelManager>,
+    agents: Query<(&PathfindingAgent, &Transform)>,
+    cells: Query<Entity, With<WaitHeatmapCell>>,
+) {
+    if !heatmap.visible {
+        for entity in cells.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    if !heatmap.refresh_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    for entity in cells.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let (width, height) = level_data.grid_size;
+    let tile_size = level_manager.tile_size;
+    for (grid_pos, tile) in collect_tile_waits(&agents, tile_size, level_data.grid_size) {
+        let mut world_pos = grid_pos.to_world_pos(tile_size, width, height);
+        // 画在覆盖热力图之上，两者同时打开时仍能看清拥堵
+        world_pos.z = TERRAIN_Z + 0.6;
+        commands.spawn((
+            Sprite::from_color(heat_color(tile.heat()), Vec2::splat(tile_size)),
+            Transform::from_translation(world_pos),
+            WaitHeatmapCell,
+            Name::new("Wait Heatmap Cell"),
+        ));
+    }
+}
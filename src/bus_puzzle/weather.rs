@@ -0,0 +1,216 @@
+// src/bus_puzzle/weather.rs - 天气事件（雨、雪、雾笼罩一片格子，期间公交车减速、候车乘客更急躁）
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, show_contextual_tip, simulation_running, CurrentLanguage,
+    DynamicEvent, EventType, GameState, GameStateEnum, GridPos, Language, LevelEntity,
+    LevelManager, TipType, UIAssets, EFFECT_Z, WEATHER_CLEARED, WEATHER_FOG, WEATHER_RAIN,
+    WEATHER_SNOW, WEATHER_STARTED,
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// 天气提示显示时长
+const WEATHER_TIP_DURATION: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WeatherKind {
+    Rain,
+    Snow,
+    Fog,
+}
+
+impl WeatherKind {
+    pub const ALL: [WeatherKind; 3] = [WeatherKind::Rain, WeatherKind::Snow, WeatherKind::Fog];
+
+    /// 公交车驶过受影响格子时的速度倍率
+    pub fn speed_factor(self) -> f32 {
+        match self {
+            WeatherKind::Rain => 0.75,
+            WeatherKind::Snow => 0.5,
+            WeatherKind::Fog => 0.65,
+        }
+    }
+
+    /// 受影响站点的乘客耐心消耗倍率
+    pub fn patience_drain_multiplier(self) -> f32 {
+        match self {
+            WeatherKind::Rain => 1.3,
+            WeatherKind::Snow => 1.5,
+            WeatherKind::Fog => 1.2,
+        }
+    }
+
+    /// 寻路时驶入受影响格子的成本倍率：车速越慢，通过成本越高
+    pub fn cost_multiplier(self) -> f32 {
+        1.0 / self.speed_factor()
+    }
+
+    pub fn localized_name(self, language: Language) -> String {
+        let text = match self {
+            WeatherKind::Rain => &WEATHER_RAIN,
+            WeatherKind::Snow => &WEATHER_SNOW,
+            WeatherKind::Fog => &WEATHER_FOG,
+        };
+        get_text(text, language)
+    }
+
+    fn tile_color(self) -> Color {
+        match self {
+            WeatherKind::Rain => Color::srgba(0.3, 0.5, 0.95, 0.3),
+            WeatherKind::Snow => Color::srgba(0.95, 0.97, 1.0, 0.45),
+            WeatherKind::Fog => Color::srgba(0.7, 0.72, 0.75, 0.5),
+        }
+    }
+}
+
+/// 当前受天气影响的格子（只影响地面层，高架层不受影响）
+#[derive(Resource, Default)]
+pub struct ActiveWeather {
+    pub tiles: HashMap<GridPos, WeatherKind>,
+}
+
+impl ActiveWeather {
+    pub fn at(&self, pos: GridPos) -> Option<WeatherKind> {
+        self.tiles.get(&pos).copied()
+    }
+
+    pub fn speed_factor(&self, pos: GridPos) -> f32 {
+        self.at(pos).map_or(1.0, WeatherKind::speed_factor)
+    }
+
+    pub fn patience_drain_multiplier(&self, pos: GridPos) -> f32 {
+        self.at(pos)
+            .map_or(1.0, WeatherKind::patience_drain_multiplier)
+    }
+}
+
+/// 受天气影响的格子上的半透明遮罩
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct WeatherTile {
+    pub grid_pos: GridPos,
+}
+
+// ============ 天气插件 ============
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveWeather>()
+            .add_systems(OnEnter(GameStateEnum::Loading), reset_weather)
+            .add_systems(OnExit(GameStateEnum::MainMenu), reset_weather)
+            .add_systems(Update, sync_weather.run_if(simulation_running));
+    }
+}
+
+fn reset_weather(mut weather: ResMut<ActiveWeather>) {
+    *weather = ActiveWeather::default();
+}
+
+/// 按游戏时间筛出正在生效的天气事件（恢复存档、重新开始后也能得到正确状态）
+fn active_weather_events(
+    game_state: &GameState,
+) -> impl Iterator<Item = (&DynamicEvent, WeatherKind, &Vec<GridPos>)> {
+    let game_time = game_state.game_time;
+
+    game_state
+        .current_level
+        .iter()
+        .flat_map(|level_data| level_data.dynamic_events.iter())
+        .filter(move |event| {
+            game_time >= event.trigger_time
+                && event
+                    .duration
+                    .is_none_or(|duration| game_time < event.trigger_time + duration)
+        })
+        .filter_map(|event| match &event.event_type {
+            EventType::Weather(kind, tiles) => Some((event, *kind, tiles)),
+            _ => None,
+        })
+}
+
+// ============ 天气同步 ============
+
+/// 天气变化时更新格子遮罩并通过提示面板通知玩家；寻路图每帧重建，会自动采用新的通行成本
+fn sync_weather(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    mut weather: ResMut<ActiveWeather>,
+    level_manager: Res<LevelManager>,
+    weather_tiles: Query<(Entity, &WeatherTile)>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+) {
+    let current: HashMap<GridPos, WeatherKind> = active_weather_events(&game_state)
+        .flat_map(|(_, kind, tiles)| tiles.iter().map(move |pos| (*pos, kind)))
+        .collect();
+    if current == weather.tiles {
+        return;
+    }
+    let Some(level_data) = &game_state.current_level else {
+        return;
+    };
+
+    for (entity, tile) in weather_tiles.iter() {
+        if current.get(&tile.grid_pos) != weather.tiles.get(&tile.grid_pos) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let (width, height) = level_data.grid_size;
+    for (pos, kind) in &current {
+        if weather.at(*pos) != Some(*kind) {
+            let mut world_pos = pos.to_world_pos(level_manager.tile_size, width, height);
+            world_pos.z = EFFECT_Z;
+            commands.spawn((
+                Sprite::from_color(kind.tile_color(), Vec2::splat(level_manager.tile_size)),
+                Transform::from_translation(world_pos),
+                WeatherTile { grid_pos: *pos },
+                Name::new("Weather Tile"),
+            ));
+        }
+    }
+
+    // 新开始的天气事件各通知一次
+    let language = current_language.language;
+    for (event, kind, tiles) in active_weather_events(&game_state) {
+        if tiles.iter().all(|pos| weather.at(*pos) == Some(kind)) {
+            continue;
+        }
+        let first = tiles.first().copied().unwrap_or(GridPos::new(0, 0));
+        let remaining = event.duration.map_or(0.0, |duration| {
+            event.trigger_time + duration - game_state.game_time
+        });
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text_with_args(
+                &WEATHER_STARTED,
+                language,
+                &[
+                    &kind.localized_name(language),
+                    &first.x.to_string(),
+                    &first.y.to_string(),
+                    &format!("{:.0}", remaining),
+                ],
+            ),
+            TipType::Warning,
+            WEATHER_TIP_DURATION,
+        );
+        info!("🌦 天气变化: {:?} 影响 {} 个格子", kind, tiles.len());
+    }
+
+    if current.is_empty() {
+        show_contextual_tip(
+            &mut commands,
+            &ui_assets,
+            &get_text(&WEATHER_CLEARED, language),
+            TipType::Strategy,
+            3.0,
+        );
+        info!("🌤 天气转晴");
+    }
+
+    weather.tiles = current;
+}
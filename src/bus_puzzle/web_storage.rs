@@ -0,0 +1,35 @@
+// src/bus_puzzle/web_storage.rs - 网页版 localStorage 读写，用户设置和关卡进度分别由 UserConfigPlugin 和 SaveGamePlugin 写入
+
+use bevy::prelude::*;
+
+/// 键名前缀，避免与同域名下的其他游戏冲突
+const KEY_PREFIX: &str = "last-stop.";
+
+// ============ localStorage 读写 ============
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+pub fn read(key: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("{}{}", KEY_PREFIX, key))
+        .ok()
+        .flatten()
+}
+
+pub fn write(key: &str, value: &str) {
+    let Some(storage) = local_storage() else {
+        warn!(
+            "localStorage 不可用（可能处于隐私模式），{} 不会被保存",
+            key
+        );
+        return;
+    };
+    if storage
+        .set_item(&format!("{}{}", KEY_PREFIX, key), value)
+        .is_err()
+    {
+        warn!("写入 localStorage 失败: {}", key);
+    }
+}
@@ -0,0 +1,917 @@
+// src/bus_puzzle/weekly_challenge.rs - 每周轮换挑战关卡
+
+use crate::bus_puzzle::{
+    get_text, get_text_with_args, passenger_color_name, AvailableSegment, ButtonComponent,
+    ButtonType, CurrentLanguage, FlushSavesEvent, GameStateEnum, GridPos, Language,
+    LevelCompletedEvent, LevelData, LocalizedText, ObjectiveCondition, ObjectiveType,
+    PassengerColor, PassengerDemand, RefundPolicy, RouteSegmentType, ScoringConfig, Station,
+    StationType, TerrainType, UIAssets, BACK, CENTRAL_HUB, CHALLENGE_BEST_SCORE,
+    CHALLENGE_DOWNTOWN_MAZE, CHALLENGE_DOWNTOWN_MAZE_DESCRIPTION, CHALLENGE_MOUNTAIN_PASS,
+    CHALLENGE_MOUNTAIN_PASS_DESCRIPTION, CHALLENGE_PLAY, CHALLENGE_RIVER_CROSSING,
+    CHALLENGE_RIVER_CROSSING_DESCRIPTION, CHALLENGE_RUSH_HOUR, CHALLENGE_RUSH_HOUR_DESCRIPTION,
+    CHALLENGE_SHOESTRING, CHALLENGE_SHOESTRING_DESCRIPTION, CHALLENGE_TWIN_HUBS,
+    CHALLENGE_TWIN_HUBS_DESCRIPTION, CHALLENGE_UNPLAYED, CHALLENGE_WEEK, NORTHEAST_STATION,
+    NORTH_STATION, OBJECTIVE_CONNECT_ALL, OBJECTIVE_DELIVER_COLOR, OBJECTIVE_MAX_AVERAGE_WAIT,
+    OBJECTIVE_MAX_COST, OBJECTIVE_MAX_SEGMENTS, OBJECTIVE_MAX_TRANSFERS, OBJECTIVE_TIME_LIMIT,
+    SOUTHEAST_STATION, SOUTH_STATION, STATION_A, STATION_B, STATION_C, TRANSFER_HUB,
+    WEEKLY_CHALLENGES,
+};
+use bevy::{
+    platform::collections::HashMap,
+    prelude::{
+        Val::{Percent, Px},
+        *,
+    },
+};
+use chrono::Datelike;
+
+/// 挑战关卡池，每周从中轮换出 FEATURED_CHALLENGE_COUNT 个
+pub const CHALLENGE_POOL: [&str; 6] = [
+    "challenge_river_crossing",
+    "challenge_downtown_maze",
+    "challenge_rush_hour",
+    "challenge_shoestring",
+    "challenge_twin_hubs",
+    "challenge_mountain_pass",
+];
+
+pub const FEATURED_CHALLENGE_COUNT: usize = 3;
+
+#[cfg(not(target_family = "wasm"))]
+const CHALLENGE_SCORES_FILE: &str = "challenge_scores.txt";
+
+// ============ 每周挑战组件和资源 ============
+
+/// 当前正在进行的挑战关卡（None 表示普通关卡）
+#[derive(Resource, Default)]
+pub struct ActiveChallenge(pub Option<&'static str>);
+
+/// ISO 周编号，例如 (2025, 27)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeWeek {
+    pub year: i32,
+    pub week: u32,
+}
+
+impl ChallengeWeek {
+    pub fn current() -> Self {
+        let iso_week = chrono::Local::now().date_naive().iso_week();
+        Self {
+            year: iso_week.year(),
+            week: iso_week.week(),
+        }
+    }
+
+    fn key(&self) -> String {
+        format!("{}-W{:02}", self.year, self.week)
+    }
+}
+
+/// 挑战成绩，与主线关卡的分数分开记录
+#[derive(Resource)]
+pub struct ChallengeProgress {
+    pub week: ChallengeWeek,
+    pub featured: [&'static str; FEATURED_CHALLENGE_COUNT],
+    pub best_scores: HashMap<String, u32>, // 挑战ID -> 历史最佳分数
+    pub weekly_best_scores: HashMap<String, u32>, // 挑战ID -> 本周最佳分数
+    week_records: Vec<(String, String, u32)>, // (周, 挑战ID, 分数)，用于持久化
+}
+
+impl Default for ChallengeProgress {
+    fn default() -> Self {
+        let week = ChallengeWeek::current();
+        Self {
+            week,
+            featured: featured_challenges(week),
+            best_scores: HashMap::new(),
+            weekly_best_scores: HashMap::new(),
+            week_records: Vec::new(),
+        }
+    }
+}
+
+impl ChallengeProgress {
+    /// 本周尚未挑战的精选关卡数量
+    pub fn unplayed_count(&self) -> usize {
+        self.featured
+            .iter()
+            .filter(|id| !self.weekly_best_scores.contains_key(**id))
+            .count()
+    }
+
+    fn insert_record(&mut self, week_key: String, challenge_id: String, score: u32) {
+        let best = self.best_scores.entry(challenge_id.clone()).or_insert(0);
+        *best = (*best).max(score);
+
+        if week_key == self.week.key() {
+            let weekly_best = self
+                .weekly_best_scores
+                .entry(challenge_id.clone())
+                .or_insert(0);
+            *weekly_best = (*weekly_best).max(score);
+        }
+
+        if let Some(record) = self
+            .week_records
+            .iter_mut()
+            .find(|(week, id, _)| *week == week_key && *id == challenge_id)
+        {
+            record.2 = record.2.max(score);
+        } else {
+            self.week_records.push((week_key, challenge_id, score));
+        }
+    }
+
+    pub fn record_score(&mut self, challenge_id: &str, score: u32) {
+        let week_key = self.week.key();
+        self.insert_record(week_key, challenge_id.to_string(), score);
+    }
+
+    fn serialize(&self) -> String {
+        self.week_records
+            .iter()
+            .map(|(week, id, score)| format!("{} {} {}", week, id, score))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn deserialize(&mut self, content: &str) {
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(week), Some(id), Some(score)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            if let Ok(score) = score.parse::<u32>() {
+                self.insert_record(week.to_string(), id.to_string(), score);
+            }
+        }
+    }
+}
+
+/// 根据 ISO 周挑选本周的精选挑战（同一周在任何机器上结果一致）
+pub fn featured_challenges(week: ChallengeWeek) -> [&'static str; FEATURED_CHALLENGE_COUNT] {
+    let start = (week.year as usize * 53 + week.week as usize) % CHALLENGE_POOL.len();
+    std::array::from_fn(|i| CHALLENGE_POOL[(start + i) % CHALLENGE_POOL.len()])
+}
+
+#[derive(Component)]
+#[require(StateScoped::<GameStateEnum>(GameStateEnum::MainMenu))]
+pub struct WeeklyChallengeUI;
+
+#[derive(Component)]
+pub struct WeeklyChallengeBadge;
+
+// ============ 每周挑战插件 ============
+
+pub struct WeeklyChallengePlugin;
+
+impl Plugin for WeeklyChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveChallenge>()
+            .init_resource::<ChallengeProgress>()
+            .add_systems(Startup, load_challenge_progress)
+            .add_systems(OnEnter(GameStateEnum::MainMenu), clear_active_challenge)
+            .add_systems(
+                Update,
+                (handle_weekly_challenge_buttons, update_weekly_badge)
+                    .run_if(in_state(GameStateEnum::MainMenu)),
+            )
+            .add_systems(Update, record_challenge_scores)
+            .add_systems(PostUpdate, flush_challenge_progress); // 退出前保存挑战成绩
+    }
+}
+
+// ============ 挑战关卡定义 ============
+
+pub fn create_challenge_level(challenge_id: &str, language: Language) -> Option<LevelData> {
+    let level = match challenge_id {
+        "challenge_river_crossing" => {
+            let mut terrain = empty_terrain(12, 8);
+            for y in 0..8 {
+                terrain.insert(GridPos::new(5, y), TerrainType::Water);
+                terrain.insert(GridPos::new(6, y), TerrainType::Water);
+            }
+            challenge_level(
+                challenge_id,
+                &CHALLENGE_RIVER_CROSSING,
+                &CHALLENGE_RIVER_CROSSING_DESCRIPTION,
+                language,
+                (12, 8),
+                terrain,
+                vec![
+                    station((1, 2), StationType::Terminal, &STATION_A, language),
+                    station((1, 5), StationType::Terminal, &STATION_B, language),
+                    station((10, 4), StationType::Terminal, &STATION_C, language),
+                ],
+                vec![
+                    demand(PassengerColor::Red, &STATION_A, &STATION_C, language, 4),
+                    demand(PassengerColor::Blue, &STATION_B, &STATION_C, language, 4),
+                ],
+                &[
+                    (RouteSegmentType::Straight, 14),
+                    (RouteSegmentType::Curve, 6),
+                    (RouteSegmentType::TSplit, 2),
+                    (RouteSegmentType::Bridge, 2),
+                ],
+                vec![objective_max_cost(30, language)],
+            )
+        }
+        "challenge_downtown_maze" => {
+            let mut terrain = empty_terrain(12, 10);
+            for (x, y) in [
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (5, 5),
+                (5, 6),
+                (5, 7),
+                (5, 8),
+                (7, 1),
+                (7, 2),
+                (7, 3),
+                (7, 4),
+                (9, 6),
+                (9, 7),
+            ] {
+                terrain.insert(GridPos::new(x, y), TerrainType::Building);
+            }
+            challenge_level(
+                challenge_id,
+                &CHALLENGE_DOWNTOWN_MAZE,
+                &CHALLENGE_DOWNTOWN_MAZE_DESCRIPTION,
+                language,
+                (12, 10),
+                terrain,
+                vec![
+                    station((1, 1), StationType::Terminal, &NORTH_STATION, language),
+                    station((1, 8), StationType::Terminal, &SOUTH_STATION, language),
+                    station((10, 4), StationType::Terminal, &NORTHEAST_STATION, language),
+                ],
+                vec![
+                    demand(
+                        PassengerColor::Green,
+                        &NORTH_STATION,
+                        &NORTHEAST_STATION,
+                        language,
+                        3,
+                    ),
+                    demand(
+                        PassengerColor::Yellow,
+                        &SOUTH_STATION,
+                        &NORTHEAST_STATION,
+                        language,
+                        3,
+                    ),
+                ],
+                &[
+                    (RouteSegmentType::Straight, 14),
+                    (RouteSegmentType::Curve, 8),
+                    (RouteSegmentType::TSplit, 3),
+                ],
+                vec![objective_max_segments(20, language)],
+            )
+        }
+        "challenge_rush_hour" => {
+            let mut terrain = empty_terrain(10, 8);
+            terrain.insert(GridPos::new(4, 3), TerrainType::Park);
+            terrain.insert(GridPos::new(5, 4), TerrainType::Park);
+            let mut level = challenge_level(
+                challenge_id,
+                &CHALLENGE_RUSH_HOUR,
+                &CHALLENGE_RUSH_HOUR_DESCRIPTION,
+                language,
+                (10, 8),
+                terrain,
+                vec![
+                    station((1, 1), StationType::Terminal, &STATION_A, language),
+                    station((1, 6), StationType::Terminal, &STATION_B, language),
+                    station((8, 3), StationType::Terminal, &STATION_C, language),
+                ],
+                vec![
+                    demand(PassengerColor::Red, &STATION_A, &STATION_C, language, 6),
+                    demand(PassengerColor::Purple, &STATION_B, &STATION_C, language, 6),
+                ],
+                &[
+                    (RouteSegmentType::Straight, 12),
+                    (RouteSegmentType::Curve, 6),
+                    (RouteSegmentType::TSplit, 2),
+                ],
+                vec![objective_time_limit(120.0, language)],
+            );
+            // 早高峰：乘客来得更快、耐心更短
+            for passenger_demand in &mut level.passenger_demands {
+                passenger_demand.spawn_rate = 0.8;
+                passenger_demand.patience = 60.0;
+            }
+            level
+        }
+        "challenge_shoestring" => {
+            let mut terrain = empty_terrain(10, 6);
+            terrain.insert(GridPos::new(4, 2), TerrainType::Building);
+            terrain.insert(GridPos::new(4, 3), TerrainType::Building);
+            challenge_level(
+                challenge_id,
+                &CHALLENGE_SHOESTRING,
+                &CHALLENGE_SHOESTRING_DESCRIPTION,
+                language,
+                (10, 6),
+                terrain,
+                vec![
+                    station((1, 2), StationType::Terminal, &STATION_A, language),
+                    station((8, 3), StationType::Terminal, &STATION_B, language),
+                ],
+                vec![demand(
+                    PassengerColor::Blue,
+                    &STATION_A,
+                    &STATION_B,
+                    language,
+                    5,
+                )],
+                &[
+                    (RouteSegmentType::Straight, 7),
+                    (RouteSegmentType::Curve, 4),
+                ],
+                vec![objective_max_cost(12, language)],
+            )
+        }
+        "challenge_twin_hubs" => {
+            let terrain = empty_terrain(14, 10);
+            challenge_level(
+                challenge_id,
+                &CHALLENGE_TWIN_HUBS,
+                &CHALLENGE_TWIN_HUBS_DESCRIPTION,
+                language,
+                (14, 10),
+                terrain,
+                vec![
+                    station((1, 1), StationType::Terminal, &NORTH_STATION, language),
+                    station((1, 8), StationType::Terminal, &SOUTH_STATION, language),
+                    station((12, 1), StationType::Terminal, &NORTHEAST_STATION, language),
+                    station((12, 8), StationType::Terminal, &SOUTHEAST_STATION, language),
+                    station((5, 4), StationType::TransferHub, &TRANSFER_HUB, language),
+                    station((8, 5), StationType::TransferHub, &CENTRAL_HUB, language),
+                ],
+                vec![
+                    demand(
+                        PassengerColor::Red,
+                        &NORTH_STATION,
+                        &SOUTHEAST_STATION,
+                        language,
+                        3,
+                    ),
+                    demand(
+                        PassengerColor::Blue,
+                        &SOUTH_STATION,
+                        &NORTHEAST_STATION,
+                        language,
+                        3,
+                    ),
+                    demand(
+                        PassengerColor::Green,
+                        &NORTH_STATION,
+                        &NORTHEAST_STATION,
+                        language,
+                        2,
+                    ),
+                    demand(
+                        PassengerColor::Yellow,
+                        &SOUTH_STATION,
+                        &SOUTHEAST_STATION,
+                        language,
+                        2,
+                    ),
+                ],
+                &[
+                    (RouteSegmentType::Straight, 24),
+                    (RouteSegmentType::Curve, 10),
+                    (RouteSegmentType::TSplit, 6),
+                    (RouteSegmentType::Cross, 2),
+                ],
+                vec![
+                    objective_max_transfers(2, language),
+                    objective_max_average_wait(30.0, language),
+                ],
+            )
+        }
+        "challenge_mountain_pass" => {
+            let mut terrain = empty_terrain(12, 8);
+            for y in 0..8 {
+                terrain.insert(GridPos::new(6, y), TerrainType::Mountain);
+            }
+            terrain.insert(GridPos::new(5, 3), TerrainType::Mountain);
+            terrain.insert(GridPos::new(7, 4), TerrainType::Mountain);
+            challenge_level(
+                challenge_id,
+                &CHALLENGE_MOUNTAIN_PASS,
+                &CHALLENGE_MOUNTAIN_PASS_DESCRIPTION,
+                language,
+                (12, 8),
+                terrain,
+                vec![
+                    station((1, 3), StationType::Terminal, &STATION_A, language),
+                    station((10, 2), StationType::Terminal, &STATION_B, language),
+                    station((10, 6), StationType::Terminal, &STATION_C, language),
+                ],
+                vec![
+                    demand(PassengerColor::Yellow, &STATION_A, &STATION_B, language, 3),
+                    demand(PassengerColor::Green, &STATION_A, &STATION_C, language, 3),
+                ],
+                &[
+                    (RouteSegmentType::Straight, 14),
+                    (RouteSegmentType::Curve, 6),
+                    (RouteSegmentType::TSplit, 2),
+                    (RouteSegmentType::Tunnel, 2),
+                ],
+                vec![
+                    objective_max_cost(36, language),
+                    objective_deliver_color(PassengerColor::Yellow, 3, language),
+                ],
+            )
+        }
+        _ => return None,
+    };
+
+    Some(level)
+}
+
+fn challenge_level(
+    id: &str,
+    name_key: &'static LocalizedText,
+    description_key: &'static LocalizedText,
+    language: Language,
+    grid_size: (u32, u32),
+    terrain: HashMap<GridPos, TerrainType>,
+    stations: Vec<Station>,
+    passenger_demands: Vec<PassengerDemand>,
+    segments: &[(RouteSegmentType, u32)],
+    extra_objectives: Vec<ObjectiveCondition>,
+) -> LevelData {
+    let mut objectives = vec![ObjectiveCondition {
+        description: get_text(&OBJECTIVE_CONNECT_ALL, language),
+        description_key: Some(&OBJECTIVE_CONNECT_ALL),
+        condition_type: ObjectiveType::ConnectAllPassengers,
+    }];
+    objectives.extend(extra_objectives);
+
+    LevelData {
+        id: id.to_string(),
+        name: get_text(name_key, language),
+        description: get_text(description_key, language),
+        name_key: Some(name_key),
+        description_key: Some(description_key),
+        difficulty: 4,
+        grid_size,
+        terrain,
+        stations,
+        passenger_demands,
+        available_segments: segments
+            .iter()
+            .map(|(segment_type, count)| AvailableSegment {
+                segment_type: *segment_type,
+                count: *count,
+                cost: segment_type.get_cost(),
+            })
+            .collect(),
+        objectives,
+        preset_routes: vec![],
+        dynamic_events: vec![],
+        random_events: false,
+        manual_fleet: false,
+        time_cycle: None,
+        scenario: Vec::new(),
+        scoring: ScoringConfig {
+            base_points: 400,
+            efficiency_bonus: 200,
+            speed_bonus: 100,
+            cost_bonus: 100,
+            speed_time_limit: 60.0,
+            cost_threshold: 15,
+            multiplier: 1.0,
+            refund_policy: RefundPolicy::Percentage(50),
+        },
+    }
+}
+
+fn empty_terrain(width: i32, height: i32) -> HashMap<GridPos, TerrainType> {
+    let mut terrain = HashMap::new();
+    for x in 0..width {
+        for y in 0..height {
+            terrain.insert(GridPos::new(x, y), TerrainType::Empty);
+        }
+    }
+    terrain
+}
+
+fn station(
+    position: (i32, i32),
+    station_type: StationType,
+    name_key: &'static LocalizedText,
+    language: Language,
+) -> Station {
+    Station {
+        position: GridPos::new(position.0, position.1),
+        capacity: if station_type == StationType::TransferHub {
+            30
+        } else {
+            20
+        },
+        station_type,
+        name: get_text(name_key, language),
+        name_key: Some(name_key),
+        passenger_types: vec![],
+    }
+}
+
+fn demand(
+    color: PassengerColor,
+    origin_key: &'static LocalizedText,
+    destination_key: &'static LocalizedText,
+    language: Language,
+    total_count: u32,
+) -> PassengerDemand {
+    PassengerDemand {
+        color,
+        origin: get_text(origin_key, language),
+        destination: get_text(destination_key, language),
+        origin_key: Some(origin_key),
+        destination_key: Some(destination_key),
+        spawn_rate: 0.4,
+        patience: 150.0,
+        spawn_time_range: Some((3.0, 60.0)),
+        total_count: Some(total_count),
+        spawned_count: 0,
+        via: None,
+        group_size: None,
+        demand_curve: None,
+    }
+}
+
+fn objective_max_cost(limit: u32, language: Language) -> ObjectiveCondition {
+    ObjectiveCondition {
+        description: get_text_with_args(&OBJECTIVE_MAX_COST, language, &[&limit.to_string()]),
+        description_key: Some(&OBJECTIVE_MAX_COST),
+        condition_type: ObjectiveType::MaxCost(limit),
+    }
+}
+
+fn objective_max_segments(limit: u32, language: Language) -> ObjectiveCondition {
+    ObjectiveCondition {
+        description: get_text_with_args(&OBJECTIVE_MAX_SEGMENTS, language, &[&limit.to_string()]),
+        description_key: Some(&OBJECTIVE_MAX_SEGMENTS),
+        condition_type: ObjectiveType::MaxSegments(limit),
+    }
+}
+
+fn objective_max_transfers(limit: u32, language: Language) -> ObjectiveCondition {
+    ObjectiveCondition {
+        description: get_text_with_args(&OBJECTIVE_MAX_TRANSFERS, language, &[&limit.to_string()]),
+        description_key: Some(&OBJECTIVE_MAX_TRANSFERS),
+        condition_type: ObjectiveType::MaxTransfers(limit),
+    }
+}
+
+fn objective_deliver_color(
+    color: PassengerColor,
+    count: u32,
+    language: Language,
+) -> ObjectiveCondition {
+    ObjectiveCondition {
+        description: get_text_with_args(
+            &OBJECTIVE_DELIVER_COLOR,
+            language,
+            &[
+                &count.to_string(),
+                &get_text(passenger_color_name(color), language),
+            ],
+        ),
+        description_key: Some(&OBJECTIVE_DELIVER_COLOR),
+        condition_type: ObjectiveType::DeliverColor(color, count),
+    }
+}
+
+fn objective_max_average_wait(seconds: f32, language: Language) -> ObjectiveCondition {
+    ObjectiveCondition {
+        description: get_text_with_args(
+            &OBJECTIVE_MAX_AVERAGE_WAIT,
+            language,
+            &[&format!("{:.0}", seconds)],
+        ),
+        description_key: Some(&OBJECTIVE_MAX_AVERAGE_WAIT),
+        condition_type: ObjectiveType::MaxAverageWait(seconds),
+    }
+}
+
+fn objective_time_limit(seconds: f32, language: Language) -> ObjectiveCondition {
+    ObjectiveCondition {
+        description: get_text_with_args(
+            &OBJECTIVE_TIME_LIMIT,
+            language,
+            &[&format!("{:.0}", seconds)],
+        ),
+        description_key: Some(&OBJECTIVE_TIME_LIMIT),
+        condition_type: ObjectiveType::TimeLimit(seconds),
+    }
+}
+
+// ============ 成绩记录与持久化 ============
+
+fn load_challenge_progress(mut progress: ResMut<ChallengeProgress>) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Ok(content) = std::fs::read_to_string(CHALLENGE_SCORES_FILE) {
+            progress.deserialize(&content);
+        } else {
+            info!("未找到挑战成绩文件");
+        }
+    }
+
+    info!(
+        "本周挑战 {}: {:?}，未挑战 {} 个",
+        progress.week.key(),
+        progress.featured,
+        progress.unplayed_count()
+    );
+}
+
+fn save_challenge_progress(progress: &ChallengeProgress) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(CHALLENGE_SCORES_FILE, progress.serialize()) {
+            warn!("保存挑战成绩失败: {}", e);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        let _ = progress.serialize();
+        info!("WASM环境：挑战成绩已更新到内存");
+    }
+}
+
+fn record_challenge_scores(
+    mut level_completed_events: EventReader<LevelCompletedEvent>,
+    active_challenge: Res<ActiveChallenge>,
+    mut progress: ResMut<ChallengeProgress>,
+) {
+    for event in level_completed_events.read() {
+        if let Some(challenge_id) = active_challenge.0 {
+            progress.record_score(challenge_id, event.final_score);
+            save_challenge_progress(&progress);
+            info!("🏆 挑战 {} 完成，得分 {}", challenge_id, event.final_score);
+        }
+    }
+}
+
+fn flush_challenge_progress(
+    mut flush_events: EventReader<FlushSavesEvent>,
+    progress: Res<ChallengeProgress>,
+) {
+    if flush_events.read().last().is_some() {
+        save_challenge_progress(&progress);
+    }
+}
+
+fn clear_active_challenge(mut active_challenge: ResMut<ActiveChallenge>) {
+    active_challenge.0 = None;
+}
+
+// ============ 主菜单挑战面板 ============
+
+fn update_weekly_badge(
+    progress: Res<ChallengeProgress>,
+    mut badges: Query<&mut Visibility, With<WeeklyChallengeBadge>>,
+    new_badges: Query<(), Added<WeeklyChallengeBadge>>,
+) {
+    if !progress.is_changed() && new_badges.is_empty() {
+        return;
+    }
+
+    let visibility = if progress.unplayed_count() > 0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for mut badge_visibility in badges.iter_mut() {
+        *badge_visibility = visibility;
+    }
+}
+
+fn handle_weekly_challenge_buttons(
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &ButtonComponent), (Changed<Interaction>, With<Button>)>,
+    panels: Query<Entity, With<WeeklyChallengeUI>>,
+    progress: Res<ChallengeProgress>,
+    ui_assets: Res<UIAssets>,
+    current_language: Res<CurrentLanguage>,
+    mut active_challenge: ResMut<ActiveChallenge>,
+    mut next_state: ResMut<NextState<GameStateEnum>>,
+) {
+    for (interaction, button_component) in button_query.iter() {
+        if !matches!(*interaction, Interaction::Pressed) {
+            continue;
+        }
+
+        match button_component.button_type {
+            ButtonType::OpenWeeklyChallenges if panels.is_empty() => {
+                spawn_weekly_challenge_panel(
+                    &mut commands,
+                    &ui_assets,
+                    &progress,
+                    current_language.language,
+                );
+            }
+            ButtonType::CloseWeeklyChallenges => {
+                for entity in panels.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ButtonType::PlayChallenge(challenge_id) => {
+                info!("开始每周挑战: {}", challenge_id);
+                active_challenge.0 = Some(challenge_id);
+                next_state.set(GameStateEnum::Playing);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn spawn_weekly_challenge_panel(
+    commands: &mut Commands,
+    ui_assets: &UIAssets,
+    progress: &ChallengeProgress,
+    language: Language,
+) {
+    commands
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                top: Px(0.0),
+                left: Px(0.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(4000),
+            WeeklyChallengeUI,
+            Name::new("Weekly Challenge Panel"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Px(520.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Px(12.0),
+                        padding: UiRect::all(Px(24.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.25)),
+                    Outline::new(Px(2.0), Val::ZERO, Color::srgb(0.9, 0.7, 0.2)),
+                ))
+                .with_children(|parent| {
+                    spawn_panel_text(
+                        parent,
+                        ui_assets,
+                        get_text(&WEEKLY_CHALLENGES, language),
+                        28.0,
+                        Color::srgb(0.9, 0.9, 0.3),
+                    );
+                    spawn_panel_text(
+                        parent,
+                        ui_assets,
+                        get_text_with_args(
+                            &CHALLENGE_WEEK,
+                            language,
+                            &[
+                                &progress.week.year.to_string(),
+                                &progress.week.week.to_string(),
+                            ],
+                        ),
+                        14.0,
+                        Color::srgb(0.7, 0.7, 0.7),
+                    );
+
+                    for challenge_id in progress.featured {
+                        spawn_challenge_row(parent, ui_assets, progress, challenge_id, language);
+                    }
+
+                    spawn_panel_button(
+                        parent,
+                        ui_assets,
+                        get_text(&BACK, language),
+                        ButtonType::CloseWeeklyChallenges,
+                    );
+                });
+        });
+}
+
+fn spawn_challenge_row(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    progress: &ChallengeProgress,
+    challenge_id: &'static str,
+    language: Language,
+) {
+    let Some(level) = create_challenge_level(challenge_id, language) else {
+        return;
+    };
+
+    // 精选挑战每周轮换，显示本周成绩而不是历史最佳
+    let status = match progress.weekly_best_scores.get(challenge_id) {
+        Some(weekly_best) => {
+            get_text_with_args(&CHALLENGE_BEST_SCORE, language, &[&weekly_best.to_string()])
+        }
+        None => get_text(&CHALLENGE_UNPLAYED, language),
+    };
+
+    parent
+        .spawn((
+            Node {
+                width: Percent(100.0),
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(4.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_panel_text(parent, ui_assets, level.name.clone(), 18.0, Color::WHITE);
+                    spawn_panel_text(
+                        parent,
+                        ui_assets,
+                        level.description.clone(),
+                        12.0,
+                        Color::srgb(0.8, 0.8, 0.8),
+                    );
+                    spawn_panel_text(parent, ui_assets, status, 12.0, Color::srgb(0.9, 0.7, 0.2));
+                });
+
+            spawn_panel_button(
+                parent,
+                ui_assets,
+                get_text(&CHALLENGE_PLAY, language),
+                ButtonType::PlayChallenge(challenge_id),
+            );
+        });
+}
+
+fn spawn_panel_text(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    font_size: f32,
+    color: Color,
+) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn spawn_panel_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    ui_assets: &UIAssets,
+    text: String,
+    button_type: ButtonType,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Px(110.0),
+                height: Px(40.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+            ButtonComponent {
+                button_type,
+                is_hovered: false,
+                is_pressed: false,
+            },
+        ))
+        .with_children(|parent| {
+            spawn_panel_text(parent, ui_assets, text, 16.0, Color::WHITE);
+        });
+}
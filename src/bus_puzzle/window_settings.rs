@@ -0,0 +1,225 @@
+// src/bus_puzzle/window_settings.rs - 记住窗口大小、位置和显示模式
+
+use crate::bus_puzzle::FlushSavesEvent;
+use bevy::{
+    prelude::*,
+    window::{MonitorSelection, PrimaryWindow, VideoModeSelection, WindowMode},
+};
+
+#[cfg(not(target_family = "wasm"))]
+const WINDOW_SETTINGS_FILE: &str = "window_settings.txt";
+
+/// 窗口停止变化多久（秒）后写入设置，避免拖动窗口时频繁写盘
+const WINDOW_SAVE_DELAY: f32 = 1.0;
+
+/// 小于该尺寸的窗口（例如最小化）不保存
+const MIN_WINDOW_SIZE: f32 = 320.0;
+
+// ============ 窗口设置数据 ============
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSettings {
+    pub width: f32,
+    pub height: f32,
+    pub position: Option<IVec2>, // 物理像素坐标，决定窗口所在的显示器
+    pub mode: WindowMode,
+}
+
+impl WindowSettings {
+    /// 启动时读取上次保存的窗口设置，在创建主窗口前调用
+    pub fn load() -> Option<Self> {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let content = std::fs::read_to_string(WINDOW_SETTINGS_FILE).ok()?;
+            let settings = Self::deserialize(&content);
+            if settings.is_none() {
+                warn!("窗口设置格式错误，使用默认窗口");
+            }
+            settings
+        }
+
+        // 网页版窗口大小由画布决定
+        #[cfg(target_family = "wasm")]
+        {
+            None
+        }
+    }
+
+    pub fn apply(&self, window: &mut Window) {
+        window.resolution.set(self.width, self.height);
+        if let Some(position) = self.position {
+            window.position = WindowPosition::At(position);
+        }
+        window.mode = self.mode;
+    }
+
+    fn capture(window: &Window) -> Option<Self> {
+        if window.width() < MIN_WINDOW_SIZE || window.height() < MIN_WINDOW_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            width: window.width(),
+            height: window.height(),
+            position: match window.position {
+                WindowPosition::At(position) => Some(position),
+                _ => None,
+            },
+            mode: window.mode,
+        })
+    }
+
+    fn serialize(&self) -> String {
+        let mut lines = vec![
+            format!("width={}", self.width),
+            format!("height={}", self.height),
+        ];
+        if let Some(position) = self.position {
+            lines.push(format!("position={},{}", position.x, position.y));
+        }
+        lines.push(format!("mode={}", mode_name(self.mode)));
+        lines.join("\n")
+    }
+
+    fn deserialize(content: &str) -> Option<Self> {
+        let mut width = None;
+        let mut height = None;
+        let mut position = None;
+        let mut mode = WindowMode::Windowed;
+
+        for line in content.lines() {
+            let Some((key, value)) = line.trim().split_once('=') else {
+                continue;
+            };
+
+            match key {
+                "width" => width = value.parse().ok(),
+                "height" => height = value.parse().ok(),
+                "position" => {
+                    let (x, y) = value.split_once(',')?;
+                    position = Some(IVec2::new(x.parse().ok()?, y.parse().ok()?));
+                }
+                "mode" => mode = parse_mode(value)?,
+                _ => warn!("窗口设置中有无法识别的行: {}", line),
+            }
+        }
+
+        let (width, height): (f32, f32) = (width?, height?);
+        (width >= MIN_WINDOW_SIZE && height >= MIN_WINDOW_SIZE).then_some(Self {
+            width,
+            height,
+            position,
+            mode,
+        })
+    }
+}
+
+pub(crate) fn mode_name(mode: WindowMode) -> &'static str {
+    match mode {
+        WindowMode::Windowed => "windowed",
+        WindowMode::BorderlessFullscreen(_) => "borderless",
+        WindowMode::Fullscreen(..) => "fullscreen",
+    }
+}
+
+/// 全屏模式使用窗口当前所在的显示器（由保存的位置决定）
+pub(crate) fn parse_mode(name: &str) -> Option<WindowMode> {
+    match name {
+        "windowed" => Some(WindowMode::Windowed),
+        "borderless" => Some(WindowMode::BorderlessFullscreen(MonitorSelection::Current)),
+        "fullscreen" => Some(WindowMode::Fullscreen(
+            MonitorSelection::Current,
+            VideoModeSelection::Current,
+        )),
+        _ => None,
+    }
+}
+
+/// 记录已保存的设置和待保存的变化
+#[derive(Resource, Default)]
+struct WindowSettingsTracker {
+    saved: Option<WindowSettings>,
+    pending: Option<WindowSettings>,
+    time_since_change: f32,
+}
+
+// ============ 窗口设置插件 ============
+
+pub struct WindowSettingsPlugin;
+
+impl Plugin for WindowSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindowSettingsTracker>()
+            .add_systems(Update, track_window_changes)
+            .add_systems(PostUpdate, flush_window_settings); // 退出前写入窗口设置
+    }
+}
+
+fn track_window_changes(
+    time: Res<Time>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut tracker: ResMut<WindowSettingsTracker>,
+) {
+    let Some(current) = windows.single().ok().and_then(WindowSettings::capture) else {
+        return;
+    };
+
+    // 首帧记录启动时的窗口，不立即写盘
+    if tracker.saved.is_none() {
+        tracker.saved = Some(current);
+        return;
+    }
+
+    if tracker.saved.as_ref() == Some(&current) {
+        tracker.pending = None;
+        return;
+    }
+
+    if tracker.pending.as_ref() != Some(&current) {
+        tracker.pending = Some(current);
+        tracker.time_since_change = 0.0;
+        return;
+    }
+
+    tracker.time_since_change += time.delta_secs();
+    if tracker.time_since_change >= WINDOW_SAVE_DELAY
+        && let Some(settings) = tracker.pending.take()
+    {
+        write_window_settings(&settings);
+        tracker.saved = Some(settings);
+    }
+}
+
+fn flush_window_settings(
+    mut flush_events: EventReader<FlushSavesEvent>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    if flush_events.read().last().is_none() {
+        return;
+    }
+
+    if let Some(settings) = windows.single().ok().and_then(WindowSettings::capture) {
+        write_window_settings(&settings);
+    }
+}
+
+fn write_window_settings(settings: &WindowSettings) {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        if let Err(e) = std::fs::write(WINDOW_SETTINGS_FILE, settings.serialize()) {
+            warn!("保存窗口设置失败: {}", e);
+        } else {
+            info!(
+                "🪟 窗口设置已保存: {}x{} {}",
+                settings.width,
+                settings.height,
+                mode_name(settings.mode)
+            );
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        let _ = settings.serialize();
+    }
+}
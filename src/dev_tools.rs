@@ -1,9 +1,226 @@
 //! Development tools for the bus_puzzle. This plugin is only enabled in dev builds.
 
-use crate::bus_puzzle::GameStateEnum;
-use bevy::{dev_tools::states::log_transitions, prelude::*};
+use crate::bus_puzzle::{
+    ui_audio::BackgroundMusic, BusVehicle, ConfirmDialogUI, ContextualTip, GameStateEnum,
+    HoverTooltip, LevelEntity, PathfindingAgent, RotationHintUI, RouteSegment, SegmentPreview,
+};
+use bevy::{
+    dev_tools::states::log_transitions,
+    ecs::{archetype::Archetypes, component::Components, world::EntityRef},
+    platform::collections::HashSet,
+    prelude::*,
+    state::state::StateTransitionEvent,
+};
+
+/// Key that toggles the entity diagnostics overlay.
+const DIAGNOSTICS_TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+
+/// How often the overlay numbers are refreshed, in seconds.
+const DIAGNOSTICS_REFRESH_SECS: f32 = 0.5;
 
 pub(super) fn plugin(app: &mut App) {
     // Log `GameStateEnum` state transitions.
     app.add_systems(Update, log_transitions::<GameStateEnum>);
+
+    // Entity-count / memory overlay to catch leaks such as accumulating previews.
+    app.init_resource::<DiagnosticsOverlay>().add_systems(
+        Update,
+        (toggle_diagnostics_overlay, update_diagnostics_overlay).chain(),
+    );
+
+    // Report entities that survive a state exit without any owner marker.
+    app.add_systems(Update, audit_state_exit_leaks);
+}
+
+#[derive(Resource)]
+struct DiagnosticsOverlay {
+    visible: bool,
+    refresh: Timer,
+}
+
+impl Default for DiagnosticsOverlay {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            refresh: Timer::from_seconds(DIAGNOSTICS_REFRESH_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+#[derive(Component)]
+struct DiagnosticsOverlayText;
+
+fn toggle_diagnostics_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DiagnosticsOverlay>,
+    overlay_texts: Query<Entity, With<DiagnosticsOverlayText>>,
+) {
+    if !keyboard_input.just_pressed(DIAGNOSTICS_TOGGLE_KEY) {
+        return;
+    }
+
+    overlay.visible = !overlay.visible;
+    if overlay.visible {
+        commands.spawn((
+            Text::new("..."),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            GlobalZIndex(i32::MAX),
+            DiagnosticsOverlayText,
+            Name::new("Diagnostics Overlay"),
+        ));
+        // Refresh on the next frame instead of waiting a full interval.
+        let duration = overlay.refresh.duration();
+        overlay.refresh.set_elapsed(duration);
+    } else {
+        for entity in overlay_texts.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_diagnostics_overlay(
+    time: Res<Time>,
+    mut overlay: ResMut<DiagnosticsOverlay>,
+    mut overlay_texts: Query<(&mut Text, &mut TextColor), With<DiagnosticsOverlayText>>,
+    archetypes: &Archetypes,
+    components: &Components,
+    entities: Query<Entity>,
+    passengers: Query<(), With<PathfindingAgent>>,
+    buses: Query<(), With<BusVehicle>>,
+    segments: Query<(), With<RouteSegment>>,
+    previews: Query<(), With<SegmentPreview>>,
+    tooltips: Query<(), With<HoverTooltip>>,
+    rotation_hints: Query<(), With<RotationHintUI>>,
+    tips: Query<(), With<ContextualTip>>,
+    ui_nodes: Query<(), With<Node>>,
+) {
+    if !overlay.visible || !overlay.refresh.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    // At most one preview / tooltip / rotation hint should exist at any time.
+    let preview_count = previews.iter().count();
+    let tooltip_count = tooltips.iter().count();
+    let rotation_hint_count = rotation_hints.iter().count();
+    let suspicious = preview_count > 1 || tooltip_count > 1 || rotation_hint_count > 1;
+
+    let lines = [
+        format!("Entities: {}", entities.iter().count()),
+        format!("  Passengers: {}", passengers.iter().count()),
+        format!("  Buses: {}", buses.iter().count()),
+        format!("  Segments: {}", segments.iter().count()),
+        format!("  Previews: {}", preview_count),
+        format!("  Tooltips: {}", tooltip_count),
+        format!("  Rotation hints: {}", rotation_hint_count),
+        format!("  Tips: {}", tips.iter().count()),
+        format!("  UI nodes: {}", ui_nodes.iter().count()),
+        format!("Archetypes: {}", archetypes.len()),
+        format!(
+            "Component memory: ~{:.1} KiB",
+            approximate_component_bytes(archetypes, components) as f32 / 1024.0
+        ),
+    ];
+
+    for (mut text, mut color) in overlay_texts.iter_mut() {
+        *text = Text::new(lines.join("\n"));
+        color.0 = if suspicious {
+            Color::srgb(1.0, 0.6, 0.2)
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+/// Sum of `entity count * component sizes` over all archetypes. This ignores heap data owned by
+/// components (strings, vectors) and allocator overhead, so it is a lower bound that is mostly
+/// useful for spotting growth over time.
+fn approximate_component_bytes(archetypes: &Archetypes, components: &Components) -> usize {
+    archetypes
+        .iter()
+        .map(|archetype| {
+            let bytes_per_entity: usize = archetype
+                .components()
+                .filter_map(|component_id| components.get_info(component_id))
+                .map(|info| info.layout().size())
+                .sum();
+            archetype.len() * bytes_per_entity
+        })
+        .sum()
+}
+
+/// Returns true if the entity is owned by level cleanup or state scoping.
+fn has_owner_marker(entity: &EntityRef) -> bool {
+    entity.contains::<LevelEntity>()
+        || entity.contains::<StateScoped<GameStateEnum>>()
+        // Intentionally long-lived or self-despawning.
+        || entity.contains::<BackgroundMusic>()
+        || entity.contains::<AudioPlayer>()
+        || entity.contains::<Observer>()
+        || entity.contains::<DiagnosticsOverlayText>()
+        || entity.contains::<ConfirmDialogUI>()
+}
+
+/// After every `GameStateEnum` exit, warns about root entities that are neither owned by a known
+/// cleanup marker nor part of the startup baseline (camera, window, screen reader region, ...).
+fn audit_state_exit_leaks(
+    world: &World,
+    mut transitions: EventReader<StateTransitionEvent<GameStateEnum>>,
+    mut baseline: Local<Option<HashSet<Entity>>>,
+    mut reported: Local<HashSet<Entity>>,
+) {
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+
+    // The first transition (entering Splash) captures everything spawned at startup.
+    let Some(baseline) = baseline.as_ref() else {
+        *baseline = Some(world.iter_entities().map(|entity| entity.id()).collect());
+        return;
+    };
+    let Some(exited) = &transition.exited else {
+        return;
+    };
+
+    for entity in world.iter_entities() {
+        if entity.contains::<ChildOf>()
+            || baseline.contains(&entity.id())
+            || reported.contains(&entity.id())
+            || has_owner_marker(&entity)
+        {
+            continue;
+        }
+
+        reported.insert(entity.id());
+        let description = match entity.get::<Name>() {
+            Some(name) => name.to_string(),
+            None => world
+                .inspect_entity(entity.id())
+                .map(|infos| {
+                    infos
+                        .filter_map(|info| info.name().rsplit("::").next())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default(),
+        };
+        warn!(
+            "Possible entity leak after exiting {:?}: {} [{}]",
+            exited,
+            entity.id(),
+            description
+        );
+    }
 }
@@ -18,6 +18,19 @@ pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
+        let mut primary_window = Window {
+            // 启动时的默认标题，语言确定后由 LocalizationPlugin 更新
+            title: "Last Stop".to_string(),
+            // X11 WM_CLASS / Wayland app_id，任务栏据此归组
+            name: Some("last-stop".to_string()),
+            fit_canvas_to_parent: true,
+            ..default()
+        };
+        // 恢复上次关闭时的窗口大小、位置和显示模式
+        if let Some(window_settings) = bus_puzzle::WindowSettings::load() {
+            window_settings.apply(&mut primary_window);
+        }
+
         // 添加Bevy插件。
         app.add_plugins(
             DefaultPlugins
@@ -29,12 +42,9 @@ impl Plugin for AppPlugin {
                     ..default()
                 })
                 .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "Last Stop".to_string(),
-                        fit_canvas_to_parent: true,
-                        ..default()
-                    }
-                    .into(),
+                    primary_window: Some(primary_window),
+                    // 关闭窗口时先弹出退出确认框并保存，由 ExitFlowPlugin 负责退出
+                    close_when_requested: false,
                     ..default()
                 })
                 .set(ImagePlugin::default_nearest()),
@@ -55,7 +65,8 @@ impl Plugin for AppPlugin {
         app.add_systems(Startup, spawn_camera);
 
         #[cfg(not(target_family = "wasm"))]
-        app.add_systems(Update, screenshot_system);
+        app.add_systems(Startup, set_window_icon)
+            .add_systems(Update, screenshot_system);
     }
 }
 
@@ -63,10 +74,51 @@ fn spawn_camera(mut commands: Commands) {
     commands.spawn((Name::new("Camera"), Camera2d));
 }
 
+/// 窗口/任务栏图标，编译进程序避免依赖运行目录
+#[cfg(not(target_family = "wasm"))]
+const WINDOW_ICON: &[u8] = include_bytes!("../assets/icons/icon.png");
+
+/// 用自带图标替换默认的 Bevy 图标（网页版使用 index.html 中的 favicon）
+#[cfg(not(target_family = "wasm"))]
+fn set_window_icon(
+    winit_windows: NonSend<bevy::winit::WinitWindows>,
+    primary_window: Query<Entity, With<bevy::window::PrimaryWindow>>,
+) {
+    let Ok(entity) = primary_window.single() else {
+        return;
+    };
+    let Some(window) = winit_windows.get_window(entity) else {
+        return;
+    };
+
+    let image = match image::load_from_memory_with_format(WINDOW_ICON, image::ImageFormat::Png) {
+        Ok(image) => image.into_rgba8(),
+        Err(e) => {
+            warn!("窗口图标解码失败: {}", e);
+            return;
+        }
+    };
+    let (width, height) = image.dimensions();
+
+    match winit::window::Icon::from_rgba(image.into_raw(), width, height) {
+        Ok(icon) => window.set_window_icon(Some(icon)),
+        Err(e) => warn!("设置窗口图标失败: {}", e),
+    }
+}
+
+/// F12 保存截图到文件，Shift+F12 复制截图到系统剪贴板
 #[cfg(not(target_family = "wasm"))]
 fn screenshot_system(keyboard_input: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
     use bevy::render::view::screenshot::{save_to_disk, Screenshot};
     if keyboard_input.just_pressed(KeyCode::F12) {
+        if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            info!("截图复制到剪贴板");
+            commands
+                .spawn(Screenshot::primary_window())
+                .observe(copy_to_clipboard);
+            return;
+        }
+
         let path = format!(
             "screenshot_{}.png",
             chrono::Utc::now().format("%Y%m%d_%H%M%S")
@@ -77,3 +129,76 @@ fn screenshot_system(keyboard_input: Res<ButtonInput<KeyCode>>, mut commands: Co
             .observe(save_to_disk(path));
     }
 }
+
+#[cfg(not(target_family = "wasm"))]
+fn copy_to_clipboard(trigger: Trigger<bevy::render::view::screenshot::ScreenshotCaptured>) {
+    let image = match trigger.event().0.clone().try_into_dynamic() {
+        Ok(image) => image.to_rgb8(), // 丢弃 HDR 下存放亮度的 alpha 通道，与 save_to_disk 一致
+        Err(e) => {
+            warn!("截图格式无法转换: {}", e);
+            return;
+        }
+    };
+
+    // 剪贴板工具读取临时 PNG 文件
+    let path = std::env::temp_dir().join("last_stop_screenshot.png");
+    if let Err(e) = image.save_with_format(&path, image::ImageFormat::Png) {
+        warn!("写入临时截图失败: {}", e);
+        return;
+    }
+
+    match clipboard_command(&path).and_then(|mut command| command.status()) {
+        Ok(status) if status.success() => info!("📋 截图已复制到剪贴板"),
+        Ok(status) => warn!("剪贴板命令执行失败: {}", status),
+        Err(e) => warn!("无法调用系统剪贴板: {}", e),
+    }
+}
+
+/// 各平台将 PNG 图片放入剪贴板的命令
+#[cfg(not(target_family = "wasm"))]
+fn clipboard_command(path: &std::path::Path) -> std::io::Result<std::process::Command> {
+    use std::process::Command;
+
+    let path_str = path.display().to_string();
+
+    if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "Add-Type -AssemblyName System.Windows.Forms, System.Drawing; \
+                 [System.Windows.Forms.Clipboard]::SetImage([System.Drawing.Image]::FromFile('{}'))",
+                path_str
+            ),
+        ]);
+        Ok(command)
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.args([
+            "-e",
+            &format!(
+                "set the clipboard to (read (POSIX file \"{}\") as «class PNGf»)",
+                path_str
+            ),
+        ]);
+        Ok(command)
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let mut command = Command::new("wl-copy");
+        command
+            .args(["--type", "image/png"])
+            .stdin(std::fs::File::open(path)?);
+        Ok(command)
+    } else {
+        let mut command = Command::new("xclip");
+        command.args([
+            "-selection",
+            "clipboard",
+            "-t",
+            "image/png",
+            "-i",
+            &path_str,
+        ]);
+        Ok(command)
+    }
+}